@@ -1,4 +1,10 @@
 use crypto_market_type::MarketType;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::{BTreeMap, HashMap};
+
+use super::utils::http_get;
+use crate::trading_rules::TradingRules;
 
 pub(crate) fn get_contract_value(market_type: MarketType, pair: &str) -> Option<f64> {
     match market_type {
@@ -10,3 +16,92 @@ pub(crate) fn get_contract_value(market_type: MarketType, pair: &str) -> Option<
         _ => None,
     }
 }
+
+// get tick sizes, lot sizes and minimum notionals from filters, see:
+// https://binance-docs.github.io/apidocs/spot/en/#exchange-information
+// https://binance-docs.github.io/apidocs/futures/en/#exchange-information
+// https://binance-docs.github.io/apidocs/delivery/en/#exchange-information
+pub(crate) fn fetch_trading_rules(market_type: MarketType) -> BTreeMap<String, TradingRules> {
+    #[derive(Serialize, Deserialize)]
+    #[allow(non_snake_case)]
+    struct RawMarket {
+        symbol: String,
+        contractType: Option<String>,
+        filters: Vec<HashMap<String, Value>>,
+        #[serde(flatten)]
+        extra: HashMap<String, Value>,
+    }
+
+    #[derive(Serialize, Deserialize)]
+    struct ExchangeInfo {
+        symbols: Vec<RawMarket>,
+    }
+
+    let url = match market_type {
+        MarketType::Spot => "https://api.binance.com/api/v3/exchangeInfo",
+        MarketType::LinearSwap | MarketType::LinearFuture => {
+            "https://fapi.binance.com/fapi/v1/exchangeInfo"
+        }
+        MarketType::InverseSwap | MarketType::InverseFuture => {
+            "https://dapi.binance.com/dapi/v1/exchangeInfo"
+        }
+        _ => return BTreeMap::new(),
+    };
+    let perpetual =
+        matches!(market_type, MarketType::LinearSwap | MarketType::InverseSwap | MarketType::Spot);
+
+    let mut mapping: BTreeMap<String, TradingRules> = BTreeMap::new();
+
+    if let Ok(txt) = http_get(url) {
+        if let Ok(exchange_info) = serde_json::from_str::<ExchangeInfo>(&txt) {
+            for market in exchange_info
+                .symbols
+                .iter()
+                .filter(|x| x.contractType.as_ref().is_none_or(|t| (t == "PERPETUAL") == perpetual))
+            {
+                let filter = |filter_type: &str, field: &str| {
+                    market
+                        .filters
+                        .iter()
+                        .find(|x| x["filterType"].as_str() == Some(filter_type))
+                        .and_then(|x| x.get(field))
+                        .and_then(|x| x.as_str())
+                        .and_then(|x| x.parse::<f64>().ok())
+                };
+                if let (Some(tick_size), Some(lot_size)) =
+                    (filter("PRICE_FILTER", "tickSize"), filter("LOT_SIZE", "stepSize"))
+                {
+                    let min_notional = filter("NOTIONAL", "minNotional")
+                        .or_else(|| filter("MIN_NOTIONAL", "minNotional"))
+                        .or_else(|| filter("MIN_NOTIONAL", "notional"));
+                    mapping.insert(
+                        market.symbol.clone(),
+                        TradingRules { tick_size, lot_size, min_notional },
+                    );
+                }
+            }
+        }
+    }
+
+    mapping
+}
+
+#[cfg(test)]
+mod tests {
+    use crypto_market_type::MarketType;
+
+    use super::fetch_trading_rules;
+
+    #[ignore]
+    #[test]
+    fn trading_rules() {
+        for market_type in [MarketType::Spot, MarketType::LinearSwap, MarketType::InverseSwap] {
+            for (symbol, rules) in fetch_trading_rules(market_type).iter().take(5) {
+                println!(
+                    "(\"binance\", MarketType::{market_type:?}, \"{symbol}\", {}, {}, {:?}),",
+                    rules.tick_size, rules.lot_size, rules.min_notional
+                );
+            }
+        }
+    }
+}
@@ -3,7 +3,8 @@ use once_cell::sync::Lazy;
 use serde::{Deserialize, Serialize};
 use std::collections::{BTreeMap, HashMap};
 
-use super::utils::http_get;
+use super::utils::{decimals_to_step, http_get};
+use crate::trading_rules::TradingRules;
 
 static LINEAR_SWAP_CONTRACT_VALUES: Lazy<HashMap<String, f64>> = Lazy::new(|| {
     // offline data, in case the network is down
@@ -71,10 +72,92 @@ fn fetch_contract_val() -> BTreeMap<String, f64> {
     mapping
 }
 
+// get the priceScale, quantityScale and minTradeUSDT fields of spot markets, and
+// the pricePlace, priceEndStep and sizeMultiplier fields of futures markets
+pub(crate) fn fetch_trading_rules(market_type: MarketType) -> BTreeMap<String, TradingRules> {
+    // see https://bitgetlimited.github.io/apidoc/en/spot/#get-all-instruments
+    #[derive(Serialize, Deserialize)]
+    #[allow(non_snake_case)]
+    struct SpotMarket {
+        symbol: String, // e.g., BTCUSDT_SPBL
+        priceScale: String,
+        quantityScale: String,
+        minTradeUSDT: Option<String>,
+    }
+
+    // see https://bitgetlimited.github.io/apidoc/en/mix/#get-all-symbols
+    #[derive(Serialize, Deserialize)]
+    #[allow(non_snake_case)]
+    struct FuturesMarket {
+        symbol: String,       // e.g., BTCUSDT_UMCBL, BTCUSD_DMCBL_221230
+        pricePlace: String,   // decimals of prices
+        priceEndStep: String, // tick size = priceEndStep * 10^-pricePlace
+        sizeMultiplier: String,
+    }
+
+    #[derive(Serialize, Deserialize)]
+    struct Response<T> {
+        code: String,
+        data: Vec<T>,
+    }
+
+    let mut mapping: BTreeMap<String, TradingRules> = BTreeMap::new();
+
+    let url = match market_type {
+        MarketType::Spot => "https://api.bitget.com/api/spot/v1/public/products",
+        MarketType::LinearSwap | MarketType::LinearFuture => {
+            "https://api.bitget.com/api/mix/v1/market/contracts?productType=umcbl"
+        }
+        MarketType::InverseSwap | MarketType::InverseFuture => {
+            "https://api.bitget.com/api/mix/v1/market/contracts?productType=dmcbl"
+        }
+        _ => return mapping,
+    };
+    if let Ok(txt) = http_get(url) {
+        if market_type == MarketType::Spot {
+            if let Ok(resp) = serde_json::from_str::<Response<SpotMarket>>(&txt) {
+                for market in resp.data {
+                    if let (Ok(price_scale), Ok(quantity_scale)) =
+                        (market.priceScale.parse::<i32>(), market.quantityScale.parse::<i32>())
+                    {
+                        let rules = TradingRules {
+                            tick_size: decimals_to_step(price_scale),
+                            lot_size: decimals_to_step(quantity_scale),
+                            min_notional: market.minTradeUSDT.and_then(|x| x.parse::<f64>().ok()),
+                        };
+                        mapping.insert(market.symbol, rules);
+                    }
+                }
+            }
+        } else if let Ok(resp) = serde_json::from_str::<Response<FuturesMarket>>(&txt) {
+            for market in resp
+                .data
+                .into_iter()
+                .filter(|x| crypto_pair::get_market_type(&x.symbol, "bitget", None) == market_type)
+            {
+                if let (Ok(price_place), Ok(price_end_step), Ok(lot_size)) = (
+                    market.pricePlace.parse::<i32>(),
+                    market.priceEndStep.parse::<f64>(),
+                    market.sizeMultiplier.parse::<f64>(),
+                ) {
+                    let rules = TradingRules {
+                        tick_size: price_end_step * decimals_to_step(price_place),
+                        lot_size,
+                        min_notional: None,
+                    };
+                    mapping.insert(market.symbol, rules);
+                }
+            }
+        }
+    }
+
+    mapping
+}
+
 pub(crate) fn get_contract_value(market_type: MarketType, pair: &str) -> Option<f64> {
     match market_type {
         MarketType::InverseSwap => Some(1.0),
-        MarketType::LinearSwap => Some(LINEAR_SWAP_CONTRACT_VALUES[pair]),
+        MarketType::LinearSwap => LINEAR_SWAP_CONTRACT_VALUES.get(pair).copied(),
         _ => None,
     }
 }
@@ -1,6 +1,7 @@
 use std::collections::{BTreeMap, HashMap};
 
 use super::utils::http_get;
+use crate::trading_rules::TradingRules;
 use crypto_market_type::MarketType;
 use once_cell::sync::Lazy;
 use serde::{Deserialize, Serialize};
@@ -133,9 +134,40 @@ fn fetch_contract_values() -> BTreeMap<String, f64> {
     mapping
 }
 
+// get the tickSize and lotSize fields, lot sizes are in contracts
+pub(crate) fn fetch_trading_rules(market_type: MarketType) -> BTreeMap<String, TradingRules> {
+    #[derive(Serialize, Deserialize)]
+    #[allow(non_snake_case)]
+    struct RawMarket {
+        symbol: String,
+        tickSize: f64,
+        lotSize: f64,
+    }
+
+    let mut mapping: BTreeMap<String, TradingRules> = BTreeMap::new();
+
+    if let Ok(text) = http_get("https://www.bitmex.com/api/v1/instrument/active") {
+        if let Ok(markets) = serde_json::from_str::<Vec<RawMarket>>(&text) {
+            for market in markets
+                .into_iter()
+                .filter(|x| crypto_pair::get_market_type(&x.symbol, "bitmex", None) == market_type)
+            {
+                let rules = TradingRules {
+                    tick_size: market.tickSize,
+                    lot_size: market.lotSize,
+                    min_notional: None,
+                };
+                mapping.insert(market.symbol, rules);
+            }
+        }
+    }
+
+    mapping
+}
+
 pub(crate) fn get_contract_value(market_type: MarketType, pair: &str) -> Option<f64> {
     if market_type == MarketType::Unknown {
-        panic!("Must be a specific market type");
+        return None; // must be a specific market type
     }
     let key = market_type.to_string() + "." + pair;
     if CONTRACT_VALUES.contains_key(key.as_str()) { Some(CONTRACT_VALUES[&key]) } else { Some(1.0) }
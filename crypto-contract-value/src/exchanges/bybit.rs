@@ -1,4 +1,10 @@
 use crypto_market_type::MarketType;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::{BTreeMap, HashMap};
+
+use super::utils::http_get;
+use crate::trading_rules::TradingRules;
 
 pub(crate) fn get_contract_value(market_type: MarketType, _pair: &str) -> Option<f64> {
     match market_type {
@@ -12,3 +18,58 @@ pub(crate) fn get_contract_value(market_type: MarketType, _pair: &str) -> Option
         _ => None,
     }
 }
+
+// get the priceFilter and lotSizeFilter fields, see:
+// https://bybit-exchange.github.io/docs/v5/market/instrument
+pub(crate) fn fetch_trading_rules(market_type: MarketType) -> BTreeMap<String, TradingRules> {
+    #[derive(Serialize, Deserialize)]
+    #[allow(non_snake_case)]
+    struct RawMarket {
+        symbol: String,
+        contractType: Option<String>, // LinearPerpetual, InverseFutures, etc.
+        priceFilter: HashMap<String, Value>,
+        lotSizeFilter: HashMap<String, Value>,
+        #[serde(flatten)]
+        extra: HashMap<String, Value>,
+    }
+
+    let (category, contract_type) = match market_type {
+        MarketType::Spot => ("spot", None),
+        MarketType::LinearSwap => ("linear", Some("LinearPerpetual")),
+        MarketType::InverseSwap => ("inverse", Some("InversePerpetual")),
+        MarketType::InverseFuture => ("inverse", Some("InverseFutures")),
+        _ => return BTreeMap::new(),
+    };
+
+    let mut mapping: BTreeMap<String, TradingRules> = BTreeMap::new();
+
+    let url = format!("https://api.bybit.com/v5/market/instruments-info?category={category}");
+    if let Ok(txt) = http_get(url.as_str()) {
+        if let Some(markets) = serde_json::from_str::<Value>(&txt)
+            .ok()
+            .and_then(|json_obj| json_obj.pointer("/result/list").cloned())
+            .and_then(|list| serde_json::from_value::<Vec<RawMarket>>(list).ok())
+        {
+            for market in markets.iter().filter(|x| x.contractType.as_deref() == contract_type) {
+                let field = |obj: &HashMap<String, Value>, key: &str| {
+                    obj.get(key).and_then(|x| x.as_str()).and_then(|x| x.parse::<f64>().ok())
+                };
+                // spot markets have basePrecision instead of qtyStep
+                let lot_size = field(&market.lotSizeFilter, "qtyStep")
+                    .or_else(|| field(&market.lotSizeFilter, "basePrecision"));
+                let min_notional = field(&market.lotSizeFilter, "minNotionalValue")
+                    .or_else(|| field(&market.lotSizeFilter, "minOrderAmt"));
+                if let (Some(tick_size), Some(lot_size)) =
+                    (field(&market.priceFilter, "tickSize"), lot_size)
+                {
+                    mapping.insert(
+                        market.symbol.clone(),
+                        TradingRules { tick_size, lot_size, min_notional },
+                    );
+                }
+            }
+        }
+    }
+
+    mapping
+}
@@ -1,4 +1,9 @@
 pub use crypto_market_type::MarketType;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+use super::utils::http_get;
+use crate::trading_rules::TradingRules;
 
 pub(crate) fn get_contract_value(market_type: MarketType, pair: &str) -> Option<f64> {
     match market_type {
@@ -14,3 +19,53 @@ pub(crate) fn get_contract_value(market_type: MarketType, pair: &str) -> Option<
         _ => None,
     }
 }
+
+// get the tick_size, min_trade_amount and contract_size fields, amounts of
+// futures are in USD, so lot sizes are converted to contracts
+pub(crate) fn fetch_trading_rules(market_type: MarketType) -> BTreeMap<String, TradingRules> {
+    // see https://docs.deribit.com/#public-get_instruments
+    #[derive(Serialize, Deserialize)]
+    struct RawMarket {
+        instrument_name: String,
+        settlement_period: String, // perpetual, day, week, month
+        tick_size: f64,
+        min_trade_amount: f64,
+        contract_size: f64,
+    }
+
+    #[derive(Serialize, Deserialize)]
+    struct Response {
+        result: Vec<RawMarket>,
+    }
+
+    let mut mapping: BTreeMap<String, TradingRules> = BTreeMap::new();
+
+    let kind = match market_type {
+        MarketType::InverseSwap | MarketType::InverseFuture => "future",
+        MarketType::EuropeanOption => "option",
+        _ => return mapping,
+    };
+    for currency in ["BTC", "ETH"] {
+        let url = format!(
+            "https://www.deribit.com/api/v2/public/get_instruments?currency={currency}&kind={kind}"
+        );
+        if let Ok(txt) = http_get(url.as_str()) {
+            if let Ok(resp) = serde_json::from_str::<Response>(&txt) {
+                for market in resp.result.into_iter().filter(|x| {
+                    market_type == MarketType::EuropeanOption
+                        || (x.settlement_period == "perpetual")
+                            == (market_type == MarketType::InverseSwap)
+                }) {
+                    let rules = TradingRules {
+                        tick_size: market.tick_size,
+                        lot_size: market.min_trade_amount / market.contract_size,
+                        min_notional: None,
+                    };
+                    mapping.insert(market.instrument_name, rules);
+                }
+            }
+        }
+    }
+
+    mapping
+}
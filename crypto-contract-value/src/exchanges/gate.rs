@@ -3,7 +3,8 @@ use once_cell::sync::Lazy;
 use serde::{Deserialize, Serialize};
 use std::collections::{BTreeMap, HashMap};
 
-use super::utils::http_get;
+use super::utils::{decimals_to_step, http_get};
+use crate::trading_rules::TradingRules;
 
 static CONTRACT_VALUES: Lazy<HashMap<MarketType, HashMap<String, f64>>> = Lazy::new(|| {
     let inverse_swap: HashMap<String, f64> = {
@@ -523,11 +524,65 @@ fn fetch_quanto_multipliers(url: &str) -> BTreeMap<String, f64> {
     mapping
 }
 
+// get the precision, amount_precision and min_quote_amount fields of spot markets,
+// and the order_price_round field of futures markets, whose lot size is always
+// one contract
+pub(crate) fn fetch_trading_rules(market_type: MarketType) -> BTreeMap<String, TradingRules> {
+    // see https://www.gate.io/docs/developers/apiv4/en/#list-all-currency-pairs-supported
+    #[derive(Serialize, Deserialize)]
+    struct SpotMarket {
+        id: String,
+        precision: i32,
+        amount_precision: i32,
+        min_quote_amount: Option<String>,
+    }
+
+    // see https://www.gate.io/docs/developers/apiv4/en/#list-all-futures-contracts
+    #[derive(Serialize, Deserialize)]
+    struct FuturesMarket {
+        name: String,
+        order_price_round: String,
+    }
+
+    let mut mapping: BTreeMap<String, TradingRules> = BTreeMap::new();
+
+    let url = match market_type {
+        MarketType::Spot => "https://api.gateio.ws/api/v4/spot/currency_pairs",
+        MarketType::InverseSwap => INVERSE_SWAP_URL,
+        MarketType::LinearSwap => LINEAR_SWAP_URL,
+        MarketType::LinearFuture => LINEAR_FUTURE_URL,
+        _ => return mapping,
+    };
+    if let Ok(txt) = http_get(url) {
+        if market_type == MarketType::Spot {
+            for market in serde_json::from_str::<Vec<SpotMarket>>(&txt).unwrap_or_default() {
+                let rules = TradingRules {
+                    tick_size: decimals_to_step(market.precision),
+                    lot_size: decimals_to_step(market.amount_precision),
+                    min_notional: market.min_quote_amount.and_then(|x| x.parse::<f64>().ok()),
+                };
+                mapping.insert(market.id, rules);
+            }
+        } else {
+            for market in serde_json::from_str::<Vec<FuturesMarket>>(&txt).unwrap_or_default() {
+                if let Ok(tick_size) = market.order_price_round.parse::<f64>() {
+                    mapping.insert(
+                        market.name,
+                        TradingRules { tick_size, lot_size: 1.0, min_notional: None },
+                    );
+                }
+            }
+        }
+    }
+
+    mapping
+}
+
 pub(crate) fn get_contract_value(market_type: MarketType, pair: &str) -> Option<f64> {
     match market_type {
         MarketType::InverseSwap | MarketType::InverseFuture => Some(1.0),
         MarketType::LinearSwap | MarketType::LinearFuture => {
-            Some(CONTRACT_VALUES[&market_type][pair])
+            CONTRACT_VALUES.get(&market_type).and_then(|m| m.get(pair)).copied()
         }
         _ => None,
     }
@@ -537,7 +592,7 @@ pub(crate) fn get_contract_value(market_type: MarketType, pair: &str) -> Option<
 mod tests {
     use crypto_market_type::MarketType;
 
-    use super::{fetch_quanto_multipliers, INVERSE_SWAP_URL, LINEAR_FUTURE_URL, LINEAR_SWAP_URL};
+    use super::{INVERSE_SWAP_URL, LINEAR_FUTURE_URL, LINEAR_SWAP_URL, fetch_quanto_multipliers};
 
     #[ignore]
     #[test]
@@ -3,7 +3,8 @@ use once_cell::sync::Lazy;
 use serde::{Deserialize, Serialize};
 use std::collections::{BTreeMap, HashMap};
 
-use super::utils::http_get;
+use super::utils::{decimals_to_step, http_get};
+use crate::trading_rules::TradingRules;
 
 static CONTRACT_VALUES: Lazy<HashMap<MarketType, HashMap<String, f64>>> = Lazy::new(|| {
     let linear_swap: HashMap<String, f64> = {
@@ -284,13 +285,93 @@ fn fetch_contract_size(url: &str) -> BTreeMap<String, f64> {
     mapping
 }
 
+// get the price-precision, amount-precision and min-order-value fields of spot
+// markets, and the price_tick field of futures markets, whose lot size is always
+// one contract
+pub(crate) fn fetch_trading_rules(market_type: MarketType) -> BTreeMap<String, TradingRules> {
+    // see https://huobiapi.github.io/docs/spot/v1/en/#get-all-supported-trading-symbol
+    #[derive(Serialize, Deserialize)]
+    struct SpotMarket {
+        symbol: String,
+        #[serde(rename = "price-precision")]
+        price_precision: i32,
+        #[serde(rename = "amount-precision")]
+        amount_precision: i32,
+        #[serde(rename = "min-order-value")]
+        min_order_value: Option<f64>,
+    }
+
+    // see https://huobiapi.github.io/docs/dm/v1/en/#get-contract-info
+    #[derive(Serialize, Deserialize)]
+    struct FuturesMarket {
+        symbol: String,
+        contract_code: String,
+        contract_type: Option<String>, // this_week, next_week, quarter, next_quarter, swap
+        price_tick: f64,
+    }
+
+    #[derive(Serialize, Deserialize)]
+    struct Response<T> {
+        status: String,
+        data: Vec<T>,
+    }
+
+    let mut mapping: BTreeMap<String, TradingRules> = BTreeMap::new();
+
+    let url = match market_type {
+        MarketType::Spot => "https://api.huobi.pro/v1/common/symbols",
+        MarketType::InverseFuture => "https://api.hbdm.com/api/v1/contract_contract_info",
+        MarketType::InverseSwap => "https://api.hbdm.com/swap-api/v1/swap_contract_info",
+        MarketType::LinearSwap => LINEAR_SWAP_URL,
+        _ => return mapping,
+    };
+    if let Ok(txt) = http_get(url) {
+        if market_type == MarketType::Spot {
+            if let Ok(resp) = serde_json::from_str::<Response<SpotMarket>>(&txt) {
+                for market in resp.data {
+                    let rules = TradingRules {
+                        tick_size: decimals_to_step(market.price_precision),
+                        lot_size: decimals_to_step(market.amount_precision),
+                        min_notional: market.min_order_value,
+                    };
+                    mapping.insert(market.symbol, rules);
+                }
+            }
+        } else if let Ok(resp) = serde_json::from_str::<Response<FuturesMarket>>(&txt) {
+            for market in resp.data {
+                // inverse futures are subscribed by contract type, e.g., BTC_CQ
+                let symbol = match market.contract_type.as_deref() {
+                    Some("this_week") => format!("{}_CW", market.symbol),
+                    Some("next_week") => format!("{}_NW", market.symbol),
+                    Some("quarter") => format!("{}_CQ", market.symbol),
+                    Some("next_quarter") => format!("{}_NQ", market.symbol),
+                    Some("swap") | None => market.contract_code,
+                    Some(_) => continue,
+                };
+                if crypto_pair::get_market_type(&symbol, "huobi", None) == market_type {
+                    mapping.insert(
+                        symbol,
+                        TradingRules {
+                            tick_size: market.price_tick,
+                            lot_size: 1.0,
+                            min_notional: None,
+                        },
+                    );
+                }
+            }
+        }
+    }
+
+    mapping
+}
+
 pub(crate) fn get_contract_value(market_type: MarketType, pair: &str) -> Option<f64> {
     match market_type {
         MarketType::InverseSwap | MarketType::InverseFuture => {
             Some(if pair.starts_with("BTC") { 100.0 } else { 10.0 })
         }
         MarketType::LinearSwap | MarketType::LinearFuture | MarketType::EuropeanOption => {
-            Some(CONTRACT_VALUES[&market_type][pair])
+            CONTRACT_VALUES.get(&market_type).and_then(|m| m.get(pair)).copied()
         }
         _ => None,
     }
@@ -300,7 +381,7 @@ pub(crate) fn get_contract_value(market_type: MarketType, pair: &str) -> Option<
 mod tests {
     use crypto_market_type::MarketType;
 
-    use super::{fetch_contract_size, LINEAR_OPTION_URL, LINEAR_SWAP_URL};
+    use super::{LINEAR_OPTION_URL, LINEAR_SWAP_URL, fetch_contract_size};
 
     #[ignore]
     #[test]
@@ -5,6 +5,7 @@ use serde_json::Value;
 use std::collections::{BTreeMap, HashMap};
 
 use super::utils::http_get;
+use crate::trading_rules::TradingRules;
 
 static LINEAR_CONTRACT_VALUES: Lazy<HashMap<String, f64>> = Lazy::new(|| {
     // offline data, in case the network is down
@@ -311,10 +312,80 @@ fn fetch_linear_multipliers() -> BTreeMap<String, f64> {
     mapping
 }
 
+// get the priceIncrement, baseIncrement and minFunds fields of spot markets,
+// and the tickSize and lotSize fields of futures markets
+pub(crate) fn fetch_trading_rules(market_type: MarketType) -> BTreeMap<String, TradingRules> {
+    // see https://docs.kucoin.com/#get-symbols-list
+    #[derive(Serialize, Deserialize)]
+    #[allow(non_snake_case)]
+    struct SpotMarket {
+        symbol: String,
+        priceIncrement: String,
+        baseIncrement: String,
+        minFunds: Option<String>,
+    }
+
+    // see https://docs.kucoin.com/futures/#get-open-contract-list
+    #[derive(Serialize, Deserialize)]
+    #[allow(non_snake_case)]
+    struct FuturesMarket {
+        symbol: String,
+        #[serde(rename = "type")]
+        type_: String, // FFWCSX, perpetual; FFICSX, futures
+        isInverse: bool,
+        tickSize: f64,
+        lotSize: f64,
+    }
+
+    #[derive(Serialize, Deserialize)]
+    struct Response<T> {
+        code: String,
+        data: Vec<T>,
+    }
+
+    let mut mapping: BTreeMap<String, TradingRules> = BTreeMap::new();
+
+    if market_type == MarketType::Spot {
+        if let Ok(txt) = http_get("https://api.kucoin.com/api/v2/symbols") {
+            if let Ok(resp) = serde_json::from_str::<Response<SpotMarket>>(&txt) {
+                for market in resp.data {
+                    if let (Ok(tick_size), Ok(lot_size)) =
+                        (market.priceIncrement.parse::<f64>(), market.baseIncrement.parse::<f64>())
+                    {
+                        let min_notional = market.minFunds.and_then(|x| x.parse::<f64>().ok());
+                        mapping.insert(
+                            market.symbol,
+                            TradingRules { tick_size, lot_size, min_notional },
+                        );
+                    }
+                }
+            }
+        }
+    } else if let Ok(txt) = http_get("https://api-futures.kucoin.com/api/v1/contracts/active") {
+        if let Ok(resp) = serde_json::from_str::<Response<FuturesMarket>>(&txt) {
+            for market in resp.data.iter().filter(|x| {
+                let inverse =
+                    matches!(market_type, MarketType::InverseSwap | MarketType::InverseFuture);
+                let swap = matches!(market_type, MarketType::InverseSwap | MarketType::LinearSwap);
+                x.isInverse == inverse && (x.type_ == "FFWCSX") == swap
+            }) {
+                let rules = TradingRules {
+                    tick_size: market.tickSize,
+                    lot_size: market.lotSize,
+                    min_notional: None,
+                };
+                mapping.insert(market.symbol.clone(), rules);
+            }
+        }
+    }
+
+    mapping
+}
+
 pub(crate) fn get_contract_value(market_type: MarketType, pair: &str) -> Option<f64> {
     match market_type {
         MarketType::InverseSwap | MarketType::InverseFuture => Some(1.0),
-        MarketType::LinearSwap => Some(LINEAR_CONTRACT_VALUES[pair]),
+        MarketType::LinearSwap => LINEAR_CONTRACT_VALUES.get(pair).copied(),
         _ => None,
     }
 }
@@ -3,7 +3,8 @@ use once_cell::sync::Lazy;
 use serde::{Deserialize, Serialize};
 use std::collections::{BTreeMap, HashMap};
 
-use super::utils::http_get;
+use super::utils::{decimals_to_step, http_get};
+use crate::trading_rules::TradingRules;
 
 static LINEAR_CONTRACT_VALUES: Lazy<HashMap<String, f64>> = Lazy::new(|| {
     // offline data, in case the network is down
@@ -508,10 +509,79 @@ fn fetch_linear_contract_sizes() -> BTreeMap<String, f64> {
     mapping
 }
 
+// get the price_scale, quantity_scale and min_amount fields of spot markets, and
+// the priceUnit and volUnit fields of swap markets
+pub(crate) fn fetch_trading_rules(market_type: MarketType) -> BTreeMap<String, TradingRules> {
+    // see https://mxcdevelop.github.io/APIDoc/open.api.v2.en.html#all-symbols
+    #[derive(Serialize, Deserialize)]
+    struct SpotMarket {
+        symbol: String,
+        price_scale: i32,
+        quantity_scale: i32,
+        min_amount: Option<String>,
+    }
+
+    // see https://mxcdevelop.github.io/APIDoc/contract.api.cn.html#4483df6e28
+    #[derive(Serialize, Deserialize)]
+    #[allow(non_snake_case)]
+    struct SwapMarket {
+        symbol: String,
+        quoteCoin: String,
+        settleCoin: String,
+        priceUnit: f64,
+        volUnit: f64,
+    }
+
+    #[derive(Serialize, Deserialize)]
+    struct Response<T> {
+        code: i64,
+        data: Vec<T>,
+    }
+
+    let mut mapping: BTreeMap<String, TradingRules> = BTreeMap::new();
+
+    match market_type {
+        MarketType::Spot => {
+            if let Ok(txt) = http_get("https://www.mexc.com/open/api/v2/market/symbols") {
+                if let Ok(resp) = serde_json::from_str::<Response<SpotMarket>>(&txt) {
+                    for market in resp.data {
+                        let rules = TradingRules {
+                            tick_size: decimals_to_step(market.price_scale),
+                            lot_size: decimals_to_step(market.quantity_scale),
+                            min_notional: market.min_amount.and_then(|x| x.parse::<f64>().ok()),
+                        };
+                        mapping.insert(market.symbol, rules);
+                    }
+                }
+            }
+        }
+        MarketType::LinearSwap | MarketType::InverseSwap => {
+            if let Ok(txt) = http_get("https://contract.mexc.com/api/v1/contract/detail") {
+                if let Ok(resp) = serde_json::from_str::<Response<SwapMarket>>(&txt) {
+                    let linear = market_type == MarketType::LinearSwap;
+                    for market in
+                        resp.data.into_iter().filter(|x| (x.settleCoin == x.quoteCoin) == linear)
+                    {
+                        let rules = TradingRules {
+                            tick_size: market.priceUnit,
+                            lot_size: market.volUnit,
+                            min_notional: None,
+                        };
+                        mapping.insert(market.symbol, rules);
+                    }
+                }
+            }
+        }
+        _ => {}
+    }
+
+    mapping
+}
+
 pub(crate) fn get_contract_value(market_type: MarketType, pair: &str) -> Option<f64> {
     match market_type {
         MarketType::InverseSwap => Some(if pair.starts_with("BTC") { 100.0 } else { 10.0 }),
-        MarketType::LinearSwap => Some(*LINEAR_CONTRACT_VALUES.get(pair).expect(pair)),
+        MarketType::LinearSwap => LINEAR_CONTRACT_VALUES.get(pair).copied(),
         _ => None,
     }
 }
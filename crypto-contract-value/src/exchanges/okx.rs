@@ -4,6 +4,7 @@ use serde::{Deserialize, Serialize};
 use std::collections::{BTreeMap, HashMap};
 
 use super::utils::http_get;
+use crate::trading_rules::TradingRules;
 use serde_json::Value;
 
 static CONTRACT_VALUES: Lazy<HashMap<MarketType, HashMap<String, f64>>> = Lazy::new(|| {
@@ -283,13 +284,61 @@ fn fetch_contract_val(inst_type: &str) -> BTreeMap<String, f64> {
     mapping
 }
 
+// get the tickSz and lotSz fields
+pub(crate) fn fetch_trading_rules(market_type: MarketType) -> BTreeMap<String, TradingRules> {
+    // see <https://www.okx.com/docs-v5/en/#rest-api-public-data-get-instruments>
+    #[derive(Serialize, Deserialize)]
+    #[allow(non_snake_case)]
+    struct RawMarket {
+        instId: String, // Instrument ID, e.g. BTC-USD-SWAP
+        tickSz: String, // Tick size, e.g. 0.0001
+        lotSz: String,  // Lot size, in base currency for SPOT and in contracts for FUTURES/SWAP
+        ctType: String, // Contract type, linear, inverse. Only applicable to FUTURES/SWAP
+        #[serde(flatten)]
+        extra: HashMap<String, Value>,
+    }
+
+    let (inst_type, ct_type) = match market_type {
+        MarketType::Spot => ("SPOT", ""),
+        MarketType::LinearSwap => ("SWAP", "linear"),
+        MarketType::InverseSwap => ("SWAP", "inverse"),
+        MarketType::LinearFuture => ("FUTURES", "linear"),
+        MarketType::InverseFuture => ("FUTURES", "inverse"),
+        _ => return BTreeMap::new(),
+    };
+
+    let mut mapping: BTreeMap<String, TradingRules> = BTreeMap::new();
+
+    let url = format!("https://www.okx.com/api/v5/public/instruments?instType={inst_type}");
+    if let Ok(txt) = http_get(url.as_str()) {
+        if let Some(markets) = serde_json::from_str::<HashMap<String, Value>>(&txt)
+            .ok()
+            .and_then(|json_obj| json_obj.get("data").cloned())
+            .and_then(|data| serde_json::from_value::<Vec<RawMarket>>(data).ok())
+        {
+            for market in markets.iter().filter(|x| x.ctType == ct_type) {
+                if let (Ok(tick_size), Ok(lot_size)) =
+                    (market.tickSz.parse::<f64>(), market.lotSz.parse::<f64>())
+                {
+                    mapping.insert(
+                        market.instId.clone(),
+                        TradingRules { tick_size, lot_size, min_notional: None },
+                    );
+                }
+            }
+        }
+    }
+
+    mapping
+}
+
 pub(crate) fn get_contract_value(market_type: MarketType, pair: &str) -> Option<f64> {
     match market_type {
         MarketType::InverseSwap | MarketType::InverseFuture => {
             Some(if pair.starts_with("BTC") { 100.0 } else { 10.0 })
         }
         MarketType::LinearSwap | MarketType::LinearFuture => {
-            Some(CONTRACT_VALUES[&market_type][pair])
+            CONTRACT_VALUES.get(&market_type).and_then(|m| m.get(pair)).copied()
         }
         MarketType::EuropeanOption => Some(1.0),
         _ => None,
@@ -1,5 +1,14 @@
+#[cfg(all(feature = "online", not(feature = "offline")))]
 use reqwest::{header, Result};
 
+/// The step of a number with `decimals` digits after the decimal point, e.g.,
+/// 0.01 for 2 decimals.
+pub(super) fn decimals_to_step(decimals: i32) -> f64 {
+    // 10^decimals is exact, so the division rounds only once
+    1.0 / 10f64.powi(decimals)
+}
+
+#[cfg(all(feature = "online", not(feature = "offline")))]
 pub(super) fn http_get(url: &str) -> Result<String> {
     let mut headers = header::HeaderMap::new();
     headers.insert(header::CONTENT_TYPE, header::HeaderValue::from_static("application/json"));
@@ -16,3 +25,9 @@ pub(super) fn http_get(url: &str) -> Result<String> {
         Err(error) => Err(error),
     }
 }
+
+/// Network access is disabled, callers fall back to the embedded tables.
+#[cfg(not(all(feature = "online", not(feature = "offline"))))]
+pub(super) fn http_get(_url: &str) -> std::result::Result<String, &'static str> {
+    Err("Network access is disabled")
+}
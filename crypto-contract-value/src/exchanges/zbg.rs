@@ -4,6 +4,7 @@ use serde::{Deserialize, Serialize};
 use std::collections::{BTreeMap, HashMap};
 
 use super::utils::http_get;
+use crate::trading_rules::TradingRules;
 
 static SWAP_CONTRACT_VALUES: Lazy<HashMap<String, f64>> = Lazy::new(|| {
     // offline data, in case the network is down
@@ -84,9 +85,53 @@ fn fetch_contract_val() -> BTreeMap<String, f64> {
     mapping
 }
 
+// get the tickSize and lotSize fields of swap markets
+pub(crate) fn fetch_trading_rules(market_type: MarketType) -> BTreeMap<String, TradingRules> {
+    #[derive(Serialize, Deserialize)]
+    #[allow(non_snake_case)]
+    struct RawMarket {
+        symbol: String,
+        tickSize: Option<String>,
+        lotSize: Option<String>,
+    }
+
+    #[derive(Serialize, Deserialize)]
+    struct Response {
+        datas: Vec<RawMarket>,
+    }
+
+    let mut mapping: BTreeMap<String, TradingRules> = BTreeMap::new();
+
+    if !matches!(market_type, MarketType::InverseSwap | MarketType::LinearSwap) {
+        return mapping;
+    }
+    // See https://zbgapi.github.io/docs/future/v1/en/#public-get-contracts
+    if let Ok(txt) = http_get("https://www.zbg.com/exchange/api/v1/future/common/contracts") {
+        if let Ok(resp) = serde_json::from_str::<Response>(&txt) {
+            for market in resp
+                .datas
+                .into_iter()
+                .filter(|x| crypto_pair::get_market_type(&x.symbol, "zbg", None) == market_type)
+            {
+                let field = |x: Option<&String>| x.and_then(|x| x.parse::<f64>().ok());
+                if let (Some(tick_size), Some(lot_size)) =
+                    (field(market.tickSize.as_ref()), field(market.lotSize.as_ref()))
+                {
+                    mapping.insert(
+                        market.symbol,
+                        TradingRules { tick_size, lot_size, min_notional: None },
+                    );
+                }
+            }
+        }
+    }
+
+    mapping
+}
+
 pub(crate) fn get_contract_value(market_type: MarketType, pair: &str) -> Option<f64> {
     match market_type {
-        MarketType::InverseSwap | MarketType::LinearSwap => Some(SWAP_CONTRACT_VALUES[pair]),
+        MarketType::InverseSwap | MarketType::LinearSwap => SWAP_CONTRACT_VALUES.get(pair).copied(),
         _ => None,
     }
 }
@@ -0,0 +1,107 @@
+use crypto_market_type::MarketType;
+
+/// Contract values that are no longer effective.
+///
+/// Each entry is `(exchange, market_type, pair, effective_until, contract_value)`,
+/// `contract_value` applied to messages with timestamps before `effective_until`
+/// (Unix timestamp in milliseconds). The per-exchange tables always hold the
+/// current values, so only append an entry here when an exchange re-denominates
+/// a contract, with the old value and the date the new one took effect.
+///
+/// Put a link to the exchange announcement above every entry, an unsourced
+/// cutoff silently corrupts the volumes of every message around it. Until an
+/// entry is verified, ship it through the `contract_value_history` table of a
+/// `crypto_pair::TableRegistry` instead.
+static CONTRACT_VALUE_HISTORY: &[(&str, MarketType, &str, i64, f64)] = &[];
+
+/// The historical contract value effective at `timestamp`, `None` if the current
+/// value applies.
+pub(crate) fn get_historical_contract_value(
+    exchange: &str,
+    market_type: MarketType,
+    pair: &str,
+    timestamp: i64,
+) -> Option<f64> {
+    lookup(CONTRACT_VALUE_HISTORY, exchange, market_type, pair, timestamp)
+}
+
+fn lookup(
+    history: &[(&str, MarketType, &str, i64, f64)],
+    exchange: &str,
+    market_type: MarketType,
+    pair: &str,
+    timestamp: i64,
+) -> Option<f64> {
+    history
+        .iter()
+        .filter(|x| x.0 == exchange && x.1 == market_type && x.2 == pair && timestamp < x.3)
+        .min_by_key(|x| x.3)
+        .map(|x| x.4)
+}
+
+#[cfg(test)]
+mod tests {
+    use crypto_market_type::MarketType;
+
+    #[test]
+    fn lookup() {
+        let history = [
+            ("okx", MarketType::LinearSwap, "FOO/USDT", 1640995200000, 100.0),
+            ("okx", MarketType::LinearSwap, "FOO/USDT", 1609459200000, 1000.0),
+        ];
+
+        let lookup = |timestamp| {
+            super::lookup(&history, "okx", MarketType::LinearSwap, "FOO/USDT", timestamp)
+        };
+        assert_eq!(Some(1000.0), lookup(1600000000000));
+        assert_eq!(Some(100.0), lookup(1609459200000));
+        assert_eq!(Some(100.0), lookup(1640995199999));
+        assert_eq!(None, lookup(1640995200000));
+        assert_eq!(
+            None,
+            super::lookup(&history, "okx", MarketType::InverseSwap, "FOO/USDT", 1600000000000)
+        );
+    }
+
+    // Every entry applies right before its date and no longer applies from it
+    #[test]
+    fn history_table() {
+        for &(exchange, market_type, pair, effective_until, contract_value) in
+            super::CONTRACT_VALUE_HISTORY
+        {
+            assert_eq!(
+                Some(contract_value),
+                super::get_historical_contract_value(
+                    exchange,
+                    market_type,
+                    pair,
+                    effective_until - 1
+                )
+            );
+            assert_ne!(
+                Some(contract_value),
+                crate::get_contract_value_at(exchange, market_type, pair, effective_until),
+                "{exchange} {market_type} {pair} wasn't re-denominated at {effective_until}"
+            );
+        }
+    }
+
+    #[test]
+    fn registry_history() {
+        let registry = crypto_pair::TableRegistry::from_json(
+            r#"{
+                "contract_values": {"okx": {"linear_swap": {"BAR/USDT": 10.0}}},
+                "contract_value_history": {"okx": {"linear_swap": {"BAR/USDT": [[1640995200000, 100.0]]}}}
+            }"#,
+        )
+        .unwrap();
+        let _guard = crypto_pair::scoped_instrument_registry(std::sync::Arc::new(registry));
+
+        let get = |timestamp| {
+            crate::get_contract_value_at("okx", MarketType::LinearSwap, "BAR/USDT", timestamp)
+        };
+        assert_eq!(Some(100.0), get(1609459200000));
+        assert_eq!(Some(10.0), get(1640995200000));
+        assert_eq!(Some(1.0), crate::get_contract_value_at("okx", MarketType::Spot, "BAR/USDT", 0));
+    }
+}
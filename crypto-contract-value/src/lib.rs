@@ -1,11 +1,21 @@
 use crypto_market_type::MarketType;
 
 mod exchanges;
+mod history;
+mod trading_rules;
 
+/// Get the contract value of a pair.
+///
+/// Returns `None` if the exchange, market type or pair is unknown.
 pub fn get_contract_value(exchange: &str, market_type: MarketType, pair: &str) -> Option<f64> {
     if market_type == MarketType::Spot {
         return Some(1.0);
     }
+    if let Some(contract_value) =
+        crypto_pair::instrument_registry().contract_value(exchange, market_type, pair)
+    {
+        return Some(contract_value);
+    }
 
     match exchange {
         "binance" => exchanges::binance::get_contract_value(market_type, pair),
@@ -24,6 +34,73 @@ pub fn get_contract_value(exchange: &str, market_type: MarketType, pair: &str) -
         "okex" | "okx" => exchanges::okx::get_contract_value(market_type, pair),
         "zb" => Some(1.0),
         "zbg" => exchanges::zbg::get_contract_value(market_type, pair),
-        _ => panic!("Unknown exchange {exchange}"),
+        _ => None,
+    }
+}
+
+/// Get the contract value effective at `timestamp`.
+///
+/// Exchanges re-denominate contracts from time to time, use this function
+/// instead of `get_contract_value()` when parsing historical messages.
+///
+/// # Arguments
+///
+/// * `timestamp` - Unix timestamp in milliseconds
+pub fn get_contract_value_at(
+    exchange: &str,
+    market_type: MarketType,
+    pair: &str,
+    timestamp: i64,
+) -> Option<f64> {
+    if market_type == MarketType::Spot {
+        return Some(1.0);
+    }
+    if let Some(contract_value) =
+        crypto_pair::instrument_registry().contract_value_at(exchange, market_type, pair, timestamp)
+    {
+        return Some(contract_value);
+    }
+    if let Some(contract_value) =
+        history::get_historical_contract_value(exchange, market_type, pair, timestamp)
+    {
+        return Some(contract_value);
     }
+
+    get_contract_value(exchange, market_type, pair)
+}
+
+/// Get the tick size, i.e., the minimum price increment, of a symbol.
+///
+/// Tick sizes supplied by the `crypto_pair::InstrumentRegistry` take precedence.
+///
+/// # Arguments
+///
+/// * `symbol` - The exchange-specific symbol, e.g., `BTCUSDT` or `BTC-USD-220325`,
+///   futures of the same pair may have different tick sizes
+pub fn get_tick_size(exchange: &str, market_type: MarketType, symbol: &str) -> Option<f64> {
+    crypto_pair::instrument_registry().tick_size(exchange, market_type, symbol).or_else(|| {
+        trading_rules::get_trading_rules(exchange, market_type, symbol).map(|x| x.tick_size)
+    })
+}
+
+/// Get the lot size, i.e., the minimum quantity increment, of a symbol.
+///
+/// The lot size is in base coins for spot markets and in contracts for
+/// derivatives, multiply it by `get_contract_value()` to get coins or USDs.
+/// Lot sizes supplied by the `crypto_pair::InstrumentRegistry` take precedence.
+pub fn get_lot_size(exchange: &str, market_type: MarketType, symbol: &str) -> Option<f64> {
+    crypto_pair::instrument_registry().lot_size(exchange, market_type, symbol).or_else(|| {
+        trading_rules::get_trading_rules(exchange, market_type, symbol).map(|x| x.lot_size)
+    })
+}
+
+/// Get the minimum order value of a symbol, in quote currency.
+///
+/// Returns `None` if the symbol is unknown or the exchange has no such limit.
+/// Minimum order values supplied by the `crypto_pair::InstrumentRegistry` take
+/// precedence.
+pub fn get_min_notional(exchange: &str, market_type: MarketType, symbol: &str) -> Option<f64> {
+    crypto_pair::instrument_registry().min_notional(exchange, market_type, symbol).or_else(|| {
+        trading_rules::get_trading_rules(exchange, market_type, symbol).and_then(|x| x.min_notional)
+    })
 }
@@ -0,0 +1,238 @@
+use std::{
+    collections::{BTreeMap, HashMap},
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use crypto_market_type::MarketType;
+use once_cell::sync::Lazy;
+
+use crate::exchanges;
+
+/// Order size and price restrictions of an instrument.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub(crate) struct TradingRules {
+    /// The minimum price increment
+    pub tick_size: f64,
+    /// The minimum quantity increment, in base coins for spot markets and in
+    /// contracts for derivatives
+    pub lot_size: f64,
+    /// The minimum order value in quote currency
+    pub min_notional: Option<f64>,
+}
+
+// exchange, market_type, symbol, tick_size, lot_size, min_notional
+type Row = (&'static str, MarketType, &'static str, f64, f64, Option<f64>);
+
+/// Offline data, in case the network is down.
+///
+/// Keyed by symbol, since futures of the same pair may have different rules.
+/// Exchanges with online data override these entries on first use.
+static TRADING_RULES: &[Row] = &[
+    ("binance", MarketType::Spot, "BTCUSDT", 0.01, 0.00001, Some(5.0)),
+    ("binance", MarketType::Spot, "ETHUSDT", 0.01, 0.0001, Some(5.0)),
+    ("binance", MarketType::LinearSwap, "BTCUSDT", 0.1, 0.001, Some(100.0)),
+    ("binance", MarketType::LinearSwap, "ETHUSDT", 0.01, 0.001, Some(20.0)),
+    ("binance", MarketType::InverseSwap, "BTCUSD_PERP", 0.1, 1.0, None),
+    ("binance", MarketType::InverseSwap, "ETHUSD_PERP", 0.01, 1.0, None),
+    ("bitget", MarketType::LinearSwap, "BTCUSDT_UMCBL", 0.1, 0.001, Some(5.0)),
+    ("bitget", MarketType::LinearSwap, "ETHUSDT_UMCBL", 0.01, 0.01, Some(5.0)),
+    ("bitmex", MarketType::InverseSwap, "XBTUSD", 0.5, 100.0, None),
+    ("bitmex", MarketType::QuantoSwap, "ETHUSD", 0.05, 1.0, None),
+    ("bybit", MarketType::Spot, "BTCUSDT", 0.01, 0.000001, Some(1.0)),
+    ("bybit", MarketType::InverseSwap, "BTCUSD", 0.5, 1.0, None),
+    ("bybit", MarketType::InverseSwap, "ETHUSD", 0.05, 1.0, None),
+    ("bybit", MarketType::LinearSwap, "BTCUSDT", 0.1, 0.001, Some(5.0)),
+    ("bybit", MarketType::LinearSwap, "ETHUSDT", 0.01, 0.01, Some(5.0)),
+    ("deribit", MarketType::InverseSwap, "BTC-PERPETUAL", 0.5, 1.0, None),
+    ("deribit", MarketType::InverseSwap, "ETH-PERPETUAL", 0.05, 1.0, None),
+    ("dydx", MarketType::LinearSwap, "BTC-USD", 1.0, 0.0001, None),
+    ("dydx", MarketType::LinearSwap, "ETH-USD", 0.1, 0.001, None),
+    ("ftx", MarketType::Spot, "BTC/USD", 1.0, 0.0001, None),
+    ("ftx", MarketType::LinearSwap, "BTC-PERP", 1.0, 0.0001, None),
+    ("gate", MarketType::Spot, "BTC_USDT", 0.1, 0.000001, Some(3.0)),
+    ("gate", MarketType::Spot, "ETH_USDT", 0.01, 0.0001, Some(3.0)),
+    ("gate", MarketType::InverseSwap, "BTC_USD", 0.1, 1.0, None),
+    ("gate", MarketType::LinearSwap, "BTC_USDT", 0.1, 1.0, None),
+    ("gate", MarketType::LinearSwap, "ETH_USDT", 0.01, 1.0, None),
+    ("huobi", MarketType::Spot, "btcusdt", 0.01, 0.000001, Some(5.0)),
+    ("huobi", MarketType::InverseSwap, "BTC-USD", 0.1, 1.0, None),
+    ("huobi", MarketType::LinearSwap, "BTC-USDT", 0.1, 1.0, None),
+    ("kraken", MarketType::Spot, "XBT/USD", 0.1, 0.00000001, None),
+    ("kraken", MarketType::InverseSwap, "PI_XBTUSD", 0.5, 1.0, None),
+    ("kucoin", MarketType::Spot, "BTC-USDT", 0.1, 0.00000001, Some(0.1)),
+    ("kucoin", MarketType::Spot, "ETH-USDT", 0.01, 0.0000001, Some(0.1)),
+    ("kucoin", MarketType::InverseSwap, "XBTUSDM", 1.0, 1.0, None),
+    ("kucoin", MarketType::LinearSwap, "XBTUSDTM", 0.1, 1.0, None),
+    ("mexc", MarketType::Spot, "BTC_USDT", 0.01, 0.000001, Some(5.0)),
+    ("mexc", MarketType::LinearSwap, "BTC_USDT", 0.1, 1.0, None),
+    ("okx", MarketType::Spot, "BTC-USDT", 0.1, 0.00000001, None),
+    ("okx", MarketType::Spot, "ETH-USDT", 0.01, 0.000001, None),
+    ("okx", MarketType::InverseSwap, "BTC-USD-SWAP", 0.1, 1.0, None),
+    ("okx", MarketType::LinearSwap, "BTC-USDT-SWAP", 0.1, 0.01, None),
+    ("okx", MarketType::LinearSwap, "ETH-USDT-SWAP", 0.01, 0.01, None),
+    ("zb", MarketType::Spot, "btc_usdt", 0.01, 0.0001, None),
+];
+
+// Failed fetches are retried after this delay, not on every call
+const RETRY_DELAY: Duration = Duration::from_secs(60);
+
+enum OnlineRules {
+    Fetched(Arc<BTreeMap<String, TradingRules>>),
+    // the time of the last failed fetch
+    Failed(Instant),
+}
+
+// exchange, market_type -> symbol -> trading rules
+type OnlineTable = HashMap<(String, MarketType), OnlineRules>;
+
+static ONLINE_TRADING_RULES: Lazy<Mutex<OnlineTable>> = Lazy::new(|| Mutex::new(HashMap::new()));
+
+fn fetch_trading_rules(exchange: &str, market_type: MarketType) -> BTreeMap<String, TradingRules> {
+    match exchange {
+        "binance" => exchanges::binance::fetch_trading_rules(market_type),
+        "bitget" => exchanges::bitget::fetch_trading_rules(market_type),
+        "bitmex" => exchanges::bitmex::fetch_trading_rules(market_type),
+        "bybit" => exchanges::bybit::fetch_trading_rules(market_type),
+        "deribit" => exchanges::deribit::fetch_trading_rules(market_type),
+        "gate" => exchanges::gate::fetch_trading_rules(market_type),
+        "huobi" => exchanges::huobi::fetch_trading_rules(market_type),
+        "kucoin" => exchanges::kucoin::fetch_trading_rules(market_type),
+        "mexc" => exchanges::mexc::fetch_trading_rules(market_type),
+        "okx" => exchanges::okx::fetch_trading_rules(market_type),
+        "zbg" => exchanges::zbg::fetch_trading_rules(market_type),
+        _ => BTreeMap::new(),
+    }
+}
+
+/// The trading rules of a symbol, online data first, then the embedded table.
+///
+/// Online data is fetched once per exchange and market type, an empty result
+/// is treated as a failure and fetched again after `RETRY_DELAY`.
+pub(crate) fn get_trading_rules(
+    exchange: &str,
+    market_type: MarketType,
+    symbol: &str,
+) -> Option<TradingRules> {
+    let exchange = if exchange == "okex" { "okx" } else { exchange };
+    let key = (exchange.to_string(), market_type);
+    // `None` if the table has to be fetched
+    let cached = match ONLINE_TRADING_RULES.lock().unwrap_or_else(|e| e.into_inner()).get(&key) {
+        Some(OnlineRules::Fetched(rules)) => Some(Some(rules.clone())),
+        Some(OnlineRules::Failed(time)) if time.elapsed() < RETRY_DELAY => Some(None),
+        _ => None,
+    };
+    let online = cached.unwrap_or_else(|| {
+        // Fetch without holding the lock, so that other exchanges aren't blocked,
+        // concurrent fetches of the same table are harmless
+        let fetched = fetch_trading_rules(exchange, market_type);
+        let mut cache = ONLINE_TRADING_RULES.lock().unwrap_or_else(|e| e.into_inner());
+        if fetched.is_empty() {
+            cache.insert(key, OnlineRules::Failed(Instant::now()));
+            None
+        } else {
+            let fetched = Arc::new(fetched);
+            cache.insert(key, OnlineRules::Fetched(fetched.clone()));
+            Some(fetched)
+        }
+    });
+    online
+        .and_then(|rules| rules.get(symbol).copied())
+        .or_else(|| lookup(TRADING_RULES, exchange, market_type, symbol))
+}
+
+fn lookup(
+    table: &[Row],
+    exchange: &str,
+    market_type: MarketType,
+    symbol: &str,
+) -> Option<TradingRules> {
+    table
+        .iter()
+        .find(|x| x.0 == exchange && x.1 == market_type && x.2 == symbol)
+        .map(|x| TradingRules { tick_size: x.3, lot_size: x.4, min_notional: x.5 })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use crypto_market_type::MarketType;
+
+    use super::{TRADING_RULES, TradingRules};
+
+    #[test]
+    fn lookup() {
+        let lookup = |exchange, market_type, symbol| {
+            super::lookup(TRADING_RULES, exchange, market_type, symbol)
+        };
+        assert_eq!(
+            Some(TradingRules { tick_size: 0.01, lot_size: 0.00001, min_notional: Some(5.0) }),
+            lookup("binance", MarketType::Spot, "BTCUSDT")
+        );
+        assert_eq!(
+            Some(TradingRules { tick_size: 0.1, lot_size: 1.0, min_notional: None }),
+            lookup("okx", MarketType::InverseSwap, "BTC-USD-SWAP")
+        );
+        assert_eq!(None, lookup("binance", MarketType::InverseFuture, "BTCUSDT"));
+    }
+
+    #[ignore]
+    #[test]
+    fn public_api() {
+        // online data may differ from the embedded table
+        assert!(crate::get_tick_size("binance", MarketType::Spot, "BTCUSDT").is_some());
+        assert!(crate::get_lot_size("okex", MarketType::LinearSwap, "BTC-USDT-SWAP").is_some());
+        assert!(crate::get_min_notional("binance", MarketType::Spot, "BTCUSDT").is_some());
+        assert_eq!(None, crate::get_min_notional("okx", MarketType::InverseSwap, "BTC-USD-SWAP"));
+        assert_eq!(None, crate::get_tick_size("dydx", MarketType::LinearSwap, "FOO-USD"));
+    }
+
+    #[test]
+    fn registry_rules() {
+        let registry = crypto_pair::TableRegistry::from_json(
+            r#"{
+                "tick_sizes": {"dydx": {"linear_swap": {"FOO-USD": 0.25}}},
+                "lot_sizes": {"dydx": {"linear_swap": {"FOO-USD": 0.5}}},
+                "min_notionals": {"dydx": {"linear_swap": {"FOO-USD": 10.0}}}
+            }"#,
+        )
+        .unwrap();
+        let _guard = crypto_pair::scoped_instrument_registry(Arc::new(registry));
+
+        assert_eq!(Some(0.25), crate::get_tick_size("dydx", MarketType::LinearSwap, "FOO-USD"));
+        assert_eq!(Some(0.5), crate::get_lot_size("dydx", MarketType::LinearSwap, "FOO-USD"));
+        assert_eq!(Some(10.0), crate::get_min_notional("dydx", MarketType::LinearSwap, "FOO-USD"));
+        assert_eq!(Some(1.0), crate::get_tick_size("dydx", MarketType::LinearSwap, "BTC-USD"));
+        assert_eq!(Some(0.0001), crate::get_lot_size("dydx", MarketType::LinearSwap, "BTC-USD"));
+    }
+
+    #[test]
+    fn failed_fetch() {
+        // dydx has no online data, which counts as a failed fetch
+        assert!(super::get_trading_rules("dydx", MarketType::LinearSwap, "BTC-USD").is_some());
+        let cache = super::ONLINE_TRADING_RULES.lock().unwrap();
+        let key = ("dydx".to_string(), MarketType::LinearSwap);
+        assert!(matches!(cache.get(&key), Some(super::OnlineRules::Failed(_))));
+    }
+
+    #[test]
+    fn embedded_table() {
+        for (exchange, market_type, symbol, tick_size, lot_size, min_notional) in TRADING_RULES {
+            assert!(*tick_size > 0.0 && *lot_size > 0.0, "{exchange} {market_type} {symbol}");
+            assert!(min_notional.is_none_or(|x| x > 0.0), "{exchange} {market_type} {symbol}");
+            assert!(
+                crypto_pair::normalize_pair(symbol, exchange).is_some(),
+                "{exchange} {market_type} {symbol}"
+            );
+            assert_eq!(
+                1,
+                TRADING_RULES
+                    .iter()
+                    .filter(|x| x.0 == *exchange && x.1 == *market_type && x.2 == *symbol)
+                    .count(),
+                "duplicated {exchange} {market_type} {symbol}"
+            );
+        }
+    }
+}
@@ -0,0 +1,231 @@
+use std::{fmt, str::FromStr};
+
+use crypto_pair::days_from_civil;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+/// Unit of a candlestick period.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum PeriodUnit {
+    Second,
+    Minute,
+    Hour,
+    Day,
+    Week,
+    /// Calendar month, which has no fixed length
+    Month,
+}
+
+impl PeriodUnit {
+    /// Length in milliseconds, `None` for months.
+    const fn millis(self) -> Option<i64> {
+        match self {
+            PeriodUnit::Second => Some(1000),
+            PeriodUnit::Minute => Some(60 * 1000),
+            PeriodUnit::Hour => Some(3600 * 1000),
+            PeriodUnit::Day => Some(86400 * 1000),
+            PeriodUnit::Week => Some(7 * 86400 * 1000),
+            PeriodUnit::Month => None,
+        }
+    }
+
+    const fn suffix(self) -> char {
+        match self {
+            PeriodUnit::Second => 's',
+            PeriodUnit::Minute => 'm',
+            PeriodUnit::Hour => 'h',
+            PeriodUnit::Day => 'd',
+            PeriodUnit::Week => 'w',
+            PeriodUnit::Month => 'M',
+        }
+    }
+}
+
+/// The period of a candlestick, e.g., 1 minute or 4 hours.
+///
+/// Periods are normalized to the largest unit that divides them, so that `60m`
+/// and `1h` compare equal. The string form is the count followed by `s`, `m`,
+/// `h`, `d`, `w` or `M` (month), e.g., `15m`, `4h` and `1M`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct CandlePeriod {
+    count: u32,
+    unit: PeriodUnit,
+}
+
+const FIXED_UNITS: [PeriodUnit; 5] =
+    [PeriodUnit::Week, PeriodUnit::Day, PeriodUnit::Hour, PeriodUnit::Minute, PeriodUnit::Second];
+
+impl CandlePeriod {
+    /// Returns `None` if `count` is zero.
+    pub fn new(count: u32, unit: PeriodUnit) -> Option<Self> {
+        if count == 0 {
+            None
+        } else if let Some(millis) = unit.millis() {
+            Self::from_millis(count as i64 * millis)
+        } else {
+            Some(CandlePeriod { count, unit })
+        }
+    }
+
+    /// Returns `None` unless `millis` is a positive multiple of one second.
+    pub fn from_millis(millis: i64) -> Option<Self> {
+        if millis <= 0 {
+            return None;
+        }
+        FIXED_UNITS.iter().find_map(|unit| {
+            let unit_millis = unit.millis().unwrap();
+            if millis % unit_millis == 0 {
+                u32::try_from(millis / unit_millis)
+                    .ok()
+                    .map(|count| CandlePeriod { count, unit: *unit })
+            } else {
+                None
+            }
+        })
+    }
+
+    pub fn count(&self) -> u32 {
+        self.count
+    }
+
+    pub fn unit(&self) -> PeriodUnit {
+        self.unit
+    }
+
+    /// Length in milliseconds, `None` for monthly periods.
+    pub fn as_millis(&self) -> Option<i64> {
+        self.unit.millis().map(|millis| self.count as i64 * millis)
+    }
+
+    /// The end of the candle that begins at `begin_time`, exclusive, both in
+    /// milliseconds.
+    pub fn end_time(&self, begin_time: i64) -> i64 {
+        match self.as_millis() {
+            Some(millis) => begin_time + millis,
+            None => add_months(begin_time, self.count as i64),
+        }
+    }
+}
+
+const MILLIS_PER_DAY: i64 = 86400 * 1000;
+
+// The inverse of `crypto_pair::days_from_civil()`, returns (year, month, day).
+fn civil_from_days(days: i64) -> (i64, i64, i64) {
+    let z = days + 719468;
+    let era = if z >= 0 { z } else { z - 146096 } / 146097;
+    let doe = z - era * 146097;
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    (yoe + era * 400 + i64::from(month <= 2), month, day)
+}
+
+fn add_months(timestamp: i64, months: i64) -> i64 {
+    let days = timestamp.div_euclid(MILLIS_PER_DAY);
+    let (year, month, day) = civil_from_days(days);
+    let month0 = month - 1 + months;
+    let days = days_from_civil(year + month0.div_euclid(12), month0.rem_euclid(12) + 1, day);
+    days * MILLIS_PER_DAY + timestamp.rem_euclid(MILLIS_PER_DAY)
+}
+
+impl fmt::Display for CandlePeriod {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}{}", self.count, self.unit.suffix())
+    }
+}
+
+/// Error returned when a string is not a `CandlePeriod`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParsePeriodError(String);
+
+impl fmt::Display for ParsePeriodError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid candle period {}", self.0)
+    }
+}
+
+impl std::error::Error for ParsePeriodError {}
+
+impl FromStr for CandlePeriod {
+    type Err = ParsePeriodError;
+
+    /// Parses the string form, e.g., `1m` and `1M`, see `parse_candle_period()`
+    /// of `crypto-msg-parser` for exchange-specific notations.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let err = || ParsePeriodError(s.to_string());
+        let (count, suffix) = s.split_at(s.find(|c: char| !c.is_ascii_digit()).ok_or_else(err)?);
+        let unit = match suffix {
+            "s" => PeriodUnit::Second,
+            "m" => PeriodUnit::Minute,
+            "h" => PeriodUnit::Hour,
+            "d" => PeriodUnit::Day,
+            "w" => PeriodUnit::Week,
+            "M" => PeriodUnit::Month,
+            _ => return Err(err()),
+        };
+        CandlePeriod::new(count.parse().map_err(|_| err())?, unit).ok_or_else(err)
+    }
+}
+
+impl Serialize for CandlePeriod {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for CandlePeriod {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        s.parse().map_err(serde::de::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{CandlePeriod, PeriodUnit};
+
+    #[test]
+    fn normalize() {
+        let one_hour = CandlePeriod::new(1, PeriodUnit::Hour).unwrap();
+        assert_eq!(Some(one_hour), CandlePeriod::new(60, PeriodUnit::Minute));
+        assert_eq!(Some(one_hour), CandlePeriod::from_millis(3600000));
+        assert_eq!("1w", CandlePeriod::new(7, PeriodUnit::Day).unwrap().to_string());
+        assert_eq!("30d", CandlePeriod::new(30, PeriodUnit::Day).unwrap().to_string());
+        assert_eq!("90s", CandlePeriod::from_millis(90000).unwrap().to_string());
+        assert_eq!(None, CandlePeriod::new(0, PeriodUnit::Minute));
+        assert_eq!(None, CandlePeriod::from_millis(1500));
+    }
+
+    #[test]
+    fn from_str() {
+        assert_eq!(CandlePeriod::new(15, PeriodUnit::Minute), "15m".parse().ok());
+        assert_eq!(CandlePeriod::new(1, PeriodUnit::Month), "1M".parse().ok());
+        assert_eq!(CandlePeriod::new(4, PeriodUnit::Hour), "240m".parse().ok());
+        assert!("1min".parse::<CandlePeriod>().is_err());
+        assert!("m".parse::<CandlePeriod>().is_err());
+        assert!("0h".parse::<CandlePeriod>().is_err());
+    }
+
+    #[test]
+    fn end_time() {
+        let four_hours = CandlePeriod::new(4, PeriodUnit::Hour).unwrap();
+        assert_eq!(Some(14400000), four_hours.as_millis());
+        assert_eq!(1654243200000 + 14400000, four_hours.end_time(1654243200000));
+
+        let one_month = CandlePeriod::new(1, PeriodUnit::Month).unwrap();
+        assert_eq!(None, one_month.as_millis());
+        // 2022-05-01 -> 2022-06-01
+        assert_eq!(1654041600000, one_month.end_time(1651363200000));
+        // 2023-12-01 -> 2024-03-01, across a leap February
+        let three_months = CandlePeriod::new(3, PeriodUnit::Month).unwrap();
+        assert_eq!(1709251200000, three_months.end_time(1701388800000));
+    }
+
+    #[test]
+    fn serde() {
+        let period = CandlePeriod::new(1, PeriodUnit::Day).unwrap();
+        assert_eq!(r#""1d""#, serde_json::to_string(&period).unwrap());
+        assert_eq!(period, serde_json::from_str::<CandlePeriod>(r#""24h""#).unwrap());
+    }
+}
@@ -1,5 +1,5 @@
 pub use crate::compact::{Order, QuantityChoice};
-use crate::TradeSide;
+use crate::{CandlePeriod, TradeSide};
 use ahash::RandomState;
 use crypto_market_type::MarketType;
 use crypto_msg_type::MessageType;
@@ -149,14 +149,16 @@ pub struct FundingRateMsg {
     /// Unix timestamp, in milliseconds
     pub timestamp: i64,
 
-    // Funding rate, which is calculated on data between [funding_time-16h, funding_time-8h]
+    /// Funding rate of one funding period, settled at `funding_time`
     pub funding_rate: f64,
-    // Funding time, the moment when funding rate is used
+    /// Funding time, the moment when funding rate is used
     pub funding_time: i64,
-    // Estimated funding rate between [funding_time-h, funding_time], it will be static after
-    // funding_time
+    /// Estimated funding rate of the next period, it will be static after
+    /// funding_time
     #[serde(skip_serializing_if = "Option::is_none")]
     pub estimated_rate: Option<f64>,
+    /// Length of a funding period in milliseconds
+    pub funding_interval: i64,
 }
 
 add_common_fields!(
@@ -204,8 +206,7 @@ add_common_fields!(
         low: f64,
         close: f64,
         volume: f64,
-        /// m, minute; H, hour; D, day; W, week; M, month; Y, year
-        period: String,
+        period: CandlePeriod,
     }
 );
 
@@ -334,7 +335,7 @@ impl CandlestickMsg {
             low: msg.low,
             close: msg.close,
             volume: msg.volume,
-            period: msg.period.clone(),
+            period: msg.period,
         }
     }
 }
@@ -352,6 +353,7 @@ impl FundingRateMsg {
             funding_rate: msg.funding_rate,
             funding_time: msg.funding_time,
             estimated_rate: msg.estimated_rate,
+            funding_interval: msg.funding_interval,
         }
     }
 }
@@ -362,7 +364,7 @@ macro_rules! impl_partial_ord {
     ($struct_name:ident) => {
         impl PartialOrd for $struct_name {
             fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
-                Some(self.timestamp.cmp(&other.timestamp))
+                Some(self.cmp(other))
             }
         }
     };
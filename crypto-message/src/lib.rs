@@ -1,10 +1,17 @@
+mod candle;
 pub mod compact;
 mod order;
+mod orderbook;
 pub mod proto;
+mod sequence;
 
+pub use crate::candle::{CandlePeriod, ParsePeriodError, PeriodUnit};
 pub use crate::order::Order;
+pub use crate::orderbook::{OrderBook, OrderBookError, Price};
+pub use crate::sequence::{SequenceEvent, SequenceValidator};
 use crypto_market_type::MarketType;
 use crypto_msg_type::MessageType;
+pub use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
 use std::str::FromStr;
 use strum_macros::{Display, EnumString};
@@ -82,6 +89,13 @@ pub struct TradeMsg {
     /// Number of contracts, always None for Spot
     #[serde(skip_serializing_if = "Option::is_none")]
     pub quantity_contract: Option<f64>,
+    /// Exact price as sent by the exchange, None if the exchange sends numbers
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub price_exact: Option<Decimal>,
+    /// Exact quantity as sent by the exchange, in contracts if
+    /// `quantity_contract` is Some, otherwise in base coins
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub quantity_exact: Option<Decimal>,
     // Trade ID
     pub trade_id: String,
     /// the original JSON message
@@ -139,14 +153,145 @@ pub struct FundingRateMsg {
     /// Unix timestamp, in milliseconds
     pub timestamp: i64,
 
-    // Funding rate, which is calculated on data between [funding_time-16h, funding_time-8h]
+    /// Funding rate of one funding period, settled at `funding_time`
     pub funding_rate: f64,
-    // Funding time, the moment when funding rate is used
+    /// Funding time, the moment when funding rate is used
     pub funding_time: i64,
-    // Estimated funding rate between [funding_time-h, funding_time], it will be static after
-    // funding_time
+    /// Estimated funding rate of the next period, it will be static after
+    /// funding_time
     #[serde(skip_serializing_if = "Option::is_none")]
     pub estimated_rate: Option<f64>,
+    /// Length of a funding period in milliseconds, e.g., 28800000 for 8 hours
+    pub funding_interval: i64,
+    /// The maximum funding rate per period, if the exchange publishes it
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub funding_rate_cap: Option<f64>,
+    /// The minimum funding rate per period, if the exchange publishes it
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub funding_rate_floor: Option<f64>,
+    /// `funding_rate` scaled to 365 days, comparable across funding intervals
+    pub annualized_rate: f64,
+    /// the original JSON message
+    pub json: String,
+}
+
+/// Liquidation message, a forced order that closes a position.
+///
+/// There is no liquidation type in `MessageType`, so this message has no
+/// `msg_type` field.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct LiquidationMsg {
+    /// The exchange name, unique for each exchage
+    pub exchange: String,
+    /// Market type
+    pub market_type: MarketType,
+    /// Exchange-specific trading symbol or id, recognized by RESTful API
+    pub symbol: String,
+    /// Unified pair, base/quote, e.g., BTC/USDT
+    pub pair: String,
+    /// Unix timestamp, in milliseconds
+    pub timestamp: i64,
+
+    /// Side of the liquidation order, Sell means a long position is liquidated
+    pub side: TradeSide,
+    /// Bankruptcy price or fill price, depending on the exchange
+    pub price: f64,
+    /// Number of base coins
+    pub quantity_base: f64,
+    /// Number of quote coins(mostly USDT)
+    pub quantity_quote: f64,
+    /// Number of contracts, always None for Spot
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub quantity_contract: Option<f64>,
+    /// the original JSON message
+    pub json: String,
+}
+
+/// Mark price message.
+///
+/// There is no mark price type in `MessageType`, so this message has no
+/// `msg_type` field.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct MarkPriceMsg {
+    /// The exchange name, unique for each exchage
+    pub exchange: String,
+    /// Market type
+    pub market_type: MarketType,
+    /// Exchange-specific trading symbol or id, recognized by RESTful API
+    pub symbol: String,
+    /// Unified pair, base/quote, e.g., BTC/USDT
+    pub pair: String,
+    /// Unix timestamp, in milliseconds
+    pub timestamp: i64,
+
+    /// Mark price, in the same unit as the price of trades, e.g., options on
+    /// deribit are quoted in base coins
+    pub price: f64,
+    /// the original JSON message
+    pub json: String,
+}
+
+/// Index price message.
+///
+/// Standalone index channels are not tied to a contract, their `symbol` is the
+/// index name, e.g., `btc_usd`, and their `market_type` is the one passed to
+/// the parser.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct IndexPriceMsg {
+    /// The exchange name, unique for each exchage
+    pub exchange: String,
+    /// Market type
+    pub market_type: MarketType,
+    /// Exchange-specific trading symbol or index name
+    pub symbol: String,
+    /// Unified pair, base/quote, e.g., BTC/USDT
+    pub pair: String,
+    /// Unix timestamp, in milliseconds
+    pub timestamp: i64,
+
+    /// Index price, in quote coins
+    pub price: f64,
+    /// the original JSON message
+    pub json: String,
+}
+
+/// Option ticker with implied volatilities and greeks.
+///
+/// Implied volatilities are annualized fractions, e.g., 0.8 means 80%, and
+/// greeks are Black-Scholes greeks in quote coins. It carries different
+/// fields from `TickerMsg`, so it has no `msg_type` field and is not part of
+/// `Message`.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct OptionTickerMsg {
+    /// The exchange name, unique for each exchage
+    pub exchange: String,
+    /// Market type, always EuropeanOption
+    pub market_type: MarketType,
+    /// Exchange-specific trading symbol or id, recognized by RESTful API
+    pub symbol: String,
+    /// Unified pair, base/quote, e.g., BTC/USDT
+    pub pair: String,
+    /// Unix timestamp, in milliseconds
+    pub timestamp: i64,
+
+    /// Implied volatility of the mark price
+    pub mark_iv: f64,
+    /// Implied volatility of the best bid, None if there is no bid
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bid_iv: Option<f64>,
+    /// Implied volatility of the best ask, None if there is no ask
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ask_iv: Option<f64>,
+    pub delta: f64,
+    pub gamma: f64,
+    pub vega: f64,
+    pub theta: f64,
+    /// Price of the underlying, None if not in the message
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub underlying_price: Option<f64>,
+    /// Open interest in contracts, None if not in the message
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub open_interest: Option<f64>,
     /// the original JSON message
     pub json: String,
 }
@@ -189,6 +334,15 @@ add_common_fields!(
         ask_quantity_base: f64,
         ask_quantity_quote: f64,
         ask_quantity_contract: Option<f64>,
+        /// Exact decimals as sent by the exchange, see `TradeMsg`
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        bid_price_exact: Option<Decimal>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        bid_quantity_exact: Option<Decimal>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        ask_price_exact: Option<Decimal>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        ask_quantity_exact: Option<Decimal>,
         #[serde(skip_serializing_if = "Option::is_none")]
         id: Option<u64>,
     }
@@ -197,21 +351,130 @@ add_common_fields!(
 add_common_fields!(
     #[derive(Serialize, Deserialize, Debug)]
     struct CandlestickMsg {
-        /// Begin time of the candle cycle, in seconds
+        /// Begin time of the candle cycle, in milliseconds
         begin_time: i64,
+        /// End time of the candle cycle, exclusive, in milliseconds
+        end_time: i64,
         open: f64,
         high: f64,
         low: f64,
         close: f64,
         /// base volume
         volume: f64,
-        /// m, minute; H, hour; D, day; W, week; M, month; Y, year
-        period: String,
+        period: CandlePeriod,
         /// quote volume
         quote_volume: Option<f64>,
+        /// Whether the candle is final, None if the exchange doesn't tell
+        is_closed: Option<bool>,
+    }
+);
+
+add_common_fields!(
+    /// Open interest message.
+    #[derive(Serialize, Deserialize, Debug)]
+    struct OpenInterestMsg {
+        /// Number of contracts
+        quantity_contract: f64,
+        /// None if there is no price in the message to convert contracts
+        quantity_base: Option<f64>,
+        /// None if there is no price in the message to convert contracts
+        quantity_quote: Option<f64>,
+    }
+);
+
+/// What happened to an order in a level3 orderbook.
+#[derive(Copy, Clone, Eq, PartialEq, Serialize, Deserialize, Display, Debug, EnumString)]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum L3Action {
+    /// A new order is placed on the book, or replaces the order with the same
+    /// ID if the exchange doesn't tell them apart
+    Open,
+    /// The size of an order changed
+    Change,
+    /// An order is removed from the book, filled or canceled
+    Done,
+    /// A maker order is filled partially or fully
+    Match,
+}
+
+add_common_fields!(
+    /// Level3 (order-by-order) message.
+    #[derive(Serialize, Deserialize, Debug)]
+    struct L3Msg {
+        /// Exchange-specific order ID, the maker order ID for matches
+        order_id: String,
+        action: L3Action,
+        /// Buy means bid and Sell means ask, None if the exchange doesn't send it
+        side: Option<TradeSide>,
+        /// None if the exchange doesn't send it, e.g., KuCoin done messages
+        price: Option<f64>,
+        /// Remaining quantity of the order, or the filled quantity for matches,
+        /// 0 if unknown
+        quantity_base: f64,
+        /// 0 if unknown
+        quantity_quote: f64,
+        /// Number of contracts, always None for Spot
+        quantity_contract: Option<f64>,
+        /// The sequence ID (not all exchanges provide this information)
+        seq_id: Option<u64>,
     }
 );
 
+/// Message represents multiple types of messages.
+#[derive(Serialize, Deserialize, Debug)]
+pub enum Message {
+    Trade(TradeMsg),
+    Bbo(BboMsg),
+    Level2(OrderBookMsg), // Level2, L2TopK, L2Snapshot
+    FundingRate(FundingRateMsg),
+    Candlestick(CandlestickMsg),
+    Ticker(TickerMsg),
+    Level3(L3Msg), // L3Event, L3Snapshot
+    OpenInterest(OpenInterestMsg),
+    Liquidation(LiquidationMsg),
+    MarkPrice(MarkPriceMsg),
+    IndexPrice(IndexPriceMsg),
+    OptionTicker(OptionTickerMsg),
+}
+
+impl Message {
+    pub fn get_timestamp(&self) -> i64 {
+        match self {
+            Message::Trade(trade) => trade.timestamp,
+            Message::Bbo(bbo) => bbo.timestamp,
+            Message::Level2(level2) => level2.timestamp,
+            Message::FundingRate(funding_rate) => funding_rate.timestamp,
+            Message::Candlestick(candlestick) => candlestick.timestamp,
+            Message::Ticker(ticker) => ticker.timestamp,
+            Message::Level3(level3) => level3.timestamp,
+            Message::OpenInterest(open_interest) => open_interest.timestamp,
+            Message::Liquidation(liquidation) => liquidation.timestamp,
+            Message::MarkPrice(mark_price) => mark_price.timestamp,
+            Message::IndexPrice(index_price) => index_price.timestamp,
+            Message::OptionTicker(option_ticker) => option_ticker.timestamp,
+        }
+    }
+
+    pub fn get_msg_type(&self) -> MessageType {
+        match self {
+            Message::Trade(trade) => trade.msg_type,
+            Message::Bbo(bbo) => bbo.msg_type,
+            Message::Level2(level2) => level2.msg_type,
+            Message::FundingRate(funding_rate) => funding_rate.msg_type,
+            Message::Candlestick(candlestick) => candlestick.msg_type,
+            Message::Ticker(ticker) => ticker.msg_type,
+            Message::Level3(level3) => level3.msg_type,
+            Message::OpenInterest(open_interest) => open_interest.msg_type,
+            // MessageType has no variants for these messages
+            Message::Liquidation(_) | Message::MarkPrice(_) | Message::IndexPrice(_) => {
+                MessageType::Other
+            }
+            Message::OptionTicker(_) => MessageType::Ticker,
+        }
+    }
+}
+
 // CSV utilities.
 
 const PRECISION: f64 = 1000000000.0; // 9 decimals
@@ -220,14 +483,30 @@ fn round(f: f64) -> f64 {
     (f * PRECISION).round() / PRECISION
 }
 
+// Exact decimals are strings in protobuf messages.
+fn parse_exact(s: &Option<String>) -> Option<Decimal> {
+    s.as_deref().map(|x| Decimal::from_str(x).unwrap())
+}
+
 impl TradeMsg {
     /// Convert to a CSV string.
     ///
     /// The `exchange`, `market_type`, `msg_type`, `pair` and `symbol` fields
-    /// are not included to save some disk space.
+    /// are not included to save some disk space. `price_exact` and
+    /// `quantity_exact` are written after `quantity_contract` only if one of
+    /// them is Some, so the line has 8 or 10 columns.
     pub fn to_csv_string(&self) -> String {
+        let exact = if self.price_exact.is_some() || self.quantity_exact.is_some() {
+            format!(
+                "{}\t{}\t",
+                self.price_exact.map(|x| x.to_string()).unwrap_or_default(),
+                self.quantity_exact.map(|x| x.to_string()).unwrap_or_default()
+            )
+        } else {
+            "".to_string()
+        };
         format!(
-            "{}\t{}\t{}\t{}\t{}\t{}\t{}\t{}",
+            "{}\t{}\t{}\t{}\t{}\t{}\t{}{}\t{}",
             self.timestamp,
             self.side,
             self.price,
@@ -238,6 +517,7 @@ impl TradeMsg {
             } else {
                 "".to_string()
             },
+            exact,
             self.trade_id,
             self.json
         )
@@ -252,8 +532,17 @@ impl TradeMsg {
         symbol: &str,
         s: &str,
     ) -> Self {
-        let v: Vec<&str> = s.split('\t').collect();
-        assert_eq!(8, v.len());
+        let mut v: Vec<&str> = s.split('\t').collect();
+        assert!(v.len() == 8 || v.len() == 10);
+        let (price_exact, quantity_exact) = if v.len() == 10 {
+            let exact: Vec<Option<Decimal>> = v
+                .drain(6..8)
+                .map(|x| if x.is_empty() { None } else { Some(Decimal::from_str(x).unwrap()) })
+                .collect();
+            (exact[0], exact[1])
+        } else {
+            (None, None)
+        };
         let market_type = MarketType::from_str(market_type).unwrap();
         let msg_type = MessageType::from_str(msg_type).unwrap();
         let side = TradeSide::from_str(v[1]).unwrap();
@@ -274,6 +563,8 @@ impl TradeMsg {
             quantity_base,
             quantity_quote,
             quantity_contract,
+            price_exact,
+            quantity_exact,
             side,
             trade_id: v[6].to_string(),
             json: v[7].to_string(),
@@ -293,6 +584,8 @@ impl TradeMsg {
         proto_msg.quantity_base = self.quantity_base as f32;
         proto_msg.quantity_quote = self.quantity_quote as f32;
         proto_msg.quantity_contract = self.quantity_contract.map(|x| x as f32);
+        proto_msg.price_exact = self.price_exact.map(|x| x.to_string());
+        proto_msg.quantity_exact = self.quantity_exact.map(|x| x.to_string());
 
         proto_msg
     }
@@ -323,6 +616,8 @@ impl TradeMsg {
             quantity_base: proto_msg.quantity_base as f64,
             quantity_quote: proto_msg.quantity_quote as f64,
             quantity_contract: proto_msg.quantity_contract.map(|x| x as f64),
+            price_exact: parse_exact(&proto_msg.price_exact),
+            quantity_exact: parse_exact(&proto_msg.quantity_exact),
             side,
             trade_id: "".to_string(),
             json: "".to_string(),
@@ -399,6 +694,8 @@ impl OrderBookMsg {
                 o.quantity_base = order.quantity_base as f32;
                 o.quantity_quote = order.quantity_quote as f32;
                 o.quantity_contract = order.quantity_contract.map(|x| x as f32);
+                o.price_exact = order.price_exact.map(|x| x.to_string());
+                o.quantity_exact = order.quantity_exact.map(|x| x.to_string());
                 o
             })
             .collect();
@@ -411,6 +708,8 @@ impl OrderBookMsg {
                 o.quantity_base = order.quantity_base as f32;
                 o.quantity_quote = order.quantity_quote as f32;
                 o.quantity_contract = order.quantity_contract.map(|x| x as f32);
+                o.price_exact = order.price_exact.map(|x| x.to_string());
+                o.quantity_exact = order.quantity_exact.map(|x| x.to_string());
                 o
             })
             .collect();
@@ -439,6 +738,8 @@ impl OrderBookMsg {
                 quantity_base: order.quantity_base as f64,
                 quantity_quote: order.quantity_quote as f64,
                 quantity_contract: order.quantity_contract.map(|x| x as f64),
+                price_exact: parse_exact(&order.price_exact),
+                quantity_exact: parse_exact(&order.quantity_exact),
             })
             .collect();
         let bids = proto_msg
@@ -449,6 +750,8 @@ impl OrderBookMsg {
                 quantity_base: order.quantity_base as f64,
                 quantity_quote: order.quantity_quote as f64,
                 quantity_contract: order.quantity_contract.map(|x| x as f64),
+                price_exact: parse_exact(&order.price_exact),
+                quantity_exact: parse_exact(&order.quantity_exact),
             })
             .collect();
 
@@ -474,6 +777,8 @@ mod tests {
     use super::{Order, OrderBookMsg, TradeMsg, TradeSide};
     use crypto_market_type::MarketType;
     use crypto_msg_type::MessageType;
+    use rust_decimal::Decimal;
+    use std::str::FromStr;
 
     #[test]
     fn test_trade() {
@@ -489,6 +794,8 @@ mod tests {
             quantity_base: 0.001,
             quantity_quote: 43.1508,
             quantity_contract: Some(0.001),
+            price_exact: None,
+            quantity_exact: None,
             trade_id: "1108933367".to_string(),
             json: r#"{"stream":"btcusdt@aggTrade","data":{"e":"aggTrade","E":1646092800098,"a":1108933367,"s":"BTCUSDT","p":"43150.80","q":"0.001","f":1987119093,"l":1987119093,"T":1646092800027,"m":true}}"#.to_string(),
         };
@@ -526,12 +833,16 @@ mod tests {
                     quantity_base: 0.0,
                     quantity_quote: 0.0,
                     quantity_contract: Some(0.0),
+                    price_exact: None,
+                    quantity_exact: None,
                 },
                 Order {
                     price: 44427.2,
                     quantity_base: 0.0,
                     quantity_quote: 0.0,
                     quantity_contract: Some(0.0),
+                    price_exact: None,
+                    quantity_exact: None,
                 },
             ],
             bids: vec![
@@ -540,12 +851,16 @@ mod tests {
                     quantity_base: 4.515,
                     quantity_quote: 197004.801,
                     quantity_contract: Some(4.515),
+                    price_exact: None,
+                    quantity_exact: None,
                 },
                 Order {
                     price: 43855.6,
                     quantity_base: 6.058,
                     quantity_quote: 265677.2248,
                     quantity_contract: Some(6.058),
+                    price_exact: None,
+                    quantity_exact: None,
                 },
             ],
             seq_id: Some(1343268964711_u64),
@@ -569,4 +884,88 @@ mod tests {
             serde_json::to_string(&orderbook_msg_restored).unwrap()
         );
     }
+
+    #[test]
+    fn test_exact_decimals() {
+        let trade_msg = TradeMsg {
+            exchange: "gate".to_string(),
+            market_type: MarketType::Spot,
+            symbol: "SHIB_USDT".to_string(),
+            pair: "SHIB/USDT".to_string(),
+            msg_type: MessageType::Trade,
+            timestamp: 1646092800027,
+            side: TradeSide::Buy,
+            price: 0.0000000001,
+            quantity_base: 123456789012.5,
+            quantity_quote: 12.34567890125,
+            quantity_contract: None,
+            price_exact: Some(Decimal::from_str("0.0000000001").unwrap()),
+            quantity_exact: Some(Decimal::from_str("123456789012.50").unwrap()),
+            trade_id: "1".to_string(),
+            json: "{}".to_string(),
+        };
+        let csv_string = trade_msg.to_csv_string();
+        assert_eq!(
+            "1646092800027\tbuy\t0.0000000001\t123456789012.5\t12.345678901\t\t0.0000000001\t123456789012.50\t1\t{}",
+            csv_string
+        );
+        let trade_msg_restored =
+            TradeMsg::from_csv_string("gate", "spot", "trade", "SHIB/USDT", "SHIB_USDT", &csv_string);
+        assert_eq!(trade_msg.price_exact, trade_msg_restored.price_exact);
+        assert_eq!(trade_msg.quantity_exact, trade_msg_restored.quantity_exact);
+        assert_eq!("1", trade_msg_restored.trade_id);
+
+        let trade_msg_restored = TradeMsg::from_proto(
+            "gate",
+            "spot",
+            "trade",
+            "SHIB/USDT",
+            "SHIB_USDT",
+            &trade_msg.to_proto(),
+        );
+        assert_eq!("0.0000000001", trade_msg_restored.price_exact.unwrap().to_string());
+        assert_eq!("123456789012.50", trade_msg_restored.quantity_exact.unwrap().to_string());
+
+        let order = Order {
+            price: 0.0000000001,
+            quantity_base: 10.0,
+            quantity_quote: 0.000000001,
+            quantity_contract: None,
+            price_exact: Some(Decimal::from_str("0.0000000001").unwrap()),
+            quantity_exact: Some(Decimal::from_str("10").unwrap()),
+        };
+        let orderbook_msg = OrderBookMsg {
+            exchange: "gate".to_string(),
+            market_type: MarketType::Spot,
+            symbol: "SHIB_USDT".to_string(),
+            pair: "SHIB/USDT".to_string(),
+            msg_type: MessageType::L2Event,
+            timestamp: 1648785270714,
+            snapshot: false,
+            asks: vec![order],
+            bids: Vec::new(),
+            seq_id: None,
+            prev_seq_id: None,
+            json: "".to_string(),
+        };
+        let orderbook_msg_restored = OrderBookMsg::from_csv_string(
+            "gate",
+            "spot",
+            "l2_event",
+            "SHIB/USDT",
+            "SHIB_USDT",
+            &orderbook_msg.to_csv_string(),
+        );
+        assert_eq!(order, orderbook_msg_restored.asks[0]);
+        let orderbook_msg_restored = OrderBookMsg::from_proto(
+            "gate",
+            "spot",
+            "l2_event",
+            "SHIB/USDT",
+            "SHIB_USDT",
+            &orderbook_msg.to_proto(),
+        );
+        assert_eq!(order.price_exact, orderbook_msg_restored.asks[0].price_exact);
+        assert_eq!(order.quantity_exact, orderbook_msg_restored.asks[0].quantity_exact);
+    }
 }
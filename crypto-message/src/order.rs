@@ -1,5 +1,6 @@
+use rust_decimal::Decimal;
 use serde::{
-    de::{Deserializer, SeqAccess, Visitor},
+    de::{self, Deserializer, SeqAccess, Visitor},
     ser::{SerializeSeq, Serializer},
     Deserialize, Serialize,
 };
@@ -15,6 +16,11 @@ pub struct Order {
     pub quantity_quote: f64,
     /// Number of contracts, always None for Spot
     pub quantity_contract: Option<f64>,
+    /// Exact price as sent by the exchange, None if the exchange sends numbers
+    pub price_exact: Option<Decimal>,
+    /// Exact quantity as sent by the exchange, in contracts if
+    /// `quantity_contract` is Some, otherwise in base coins
+    pub quantity_exact: Option<Decimal>,
 }
 
 impl PartialEq for Order {
@@ -23,17 +29,30 @@ impl PartialEq for Order {
             && self.quantity_base == other.quantity_base
             && self.quantity_quote == other.quantity_quote
             && self.quantity_contract == other.quantity_contract
+            && self.price_exact == other.price_exact
+            && self.quantity_exact == other.quantity_exact
     }
 }
 
 impl Eq for Order {}
 
+/// Serialized as `[price, quantity_base, quantity_quote]`, followed by
+/// `quantity_contract` if Some. If either exact decimal is Some, the array has
+/// six elements, `[price, quantity_base, quantity_quote, quantity_contract,
+/// "price_exact", "quantity_exact"]`, with nulls for the missing ones.
 impl Serialize for Order {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
         S: Serializer,
     {
-        let len: usize = if self.quantity_contract.is_some() { 4 } else { 3 };
+        let has_exact = self.price_exact.is_some() || self.quantity_exact.is_some();
+        let len: usize = if has_exact {
+            6
+        } else if self.quantity_contract.is_some() {
+            4
+        } else {
+            3
+        };
         let mut seq = serializer.serialize_seq(Some(len))?;
         seq.serialize_element(&self.price)?;
         // limit the number of decimals to 9
@@ -41,7 +60,11 @@ impl Serialize for Order {
         let quantity_quote = format!("{:.9}", self.quantity_quote).as_str().parse::<f64>().unwrap();
         seq.serialize_element(&quantity_base)?;
         seq.serialize_element(&quantity_quote)?;
-        if let Some(qc) = self.quantity_contract {
+        if has_exact {
+            seq.serialize_element(&self.quantity_contract)?;
+            seq.serialize_element(&self.price_exact)?;
+            seq.serialize_element(&self.quantity_exact)?;
+        } else if let Some(qc) = self.quantity_contract {
             seq.serialize_element(&qc)?;
         }
 
@@ -49,30 +72,73 @@ impl Serialize for Order {
     }
 }
 
+/// An element of the serialized array, exact decimals are strings.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum Element {
+    Number(f64),
+    Decimal(Decimal),
+}
+
+impl Element {
+    fn number<E: de::Error>(self) -> Result<f64, E> {
+        match self {
+            Element::Number(x) => Ok(x),
+            Element::Decimal(x) => Err(E::custom(format!("expected a number, found \"{x}\""))),
+        }
+    }
+
+    fn decimal<E: de::Error>(self) -> Result<Decimal, E> {
+        match self {
+            Element::Decimal(x) => Ok(x),
+            Element::Number(x) => Err(E::custom(format!("expected a decimal string, found {x}"))),
+        }
+    }
+}
+
 struct OrderVisitor;
 
 impl<'de> Visitor<'de> for OrderVisitor {
     type Value = Order;
 
     fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
-        formatter.write_str("a nonempty sequence of numbers")
+        formatter.write_str("a sequence of 3, 4 or 6 elements")
     }
 
     fn visit_seq<V>(self, mut visitor: V) -> Result<Order, V::Error>
     where
         V: SeqAccess<'de>,
     {
-        let mut vec = Vec::<f64>::new();
+        let mut vec = Vec::<Option<Element>>::new();
 
         while let Some(elem) = visitor.next_element()? {
             vec.push(elem);
         }
+        if !matches!(vec.len(), 3 | 4 | 6) {
+            return Err(de::Error::invalid_length(vec.len(), &self));
+        }
+
+        let mut iter = vec.into_iter();
+        let mut number = || -> Result<f64, V::Error> {
+            iter.next()
+                .flatten()
+                .ok_or_else(|| de::Error::custom("price and quantities can not be null"))?
+                .number()
+        };
+        let price = number()?;
+        let quantity_base = number()?;
+        let quantity_quote = number()?;
+        let quantity_contract = iter.next().flatten().map(Element::number).transpose()?;
+        let price_exact = iter.next().flatten().map(Element::decimal).transpose()?;
+        let quantity_exact = iter.next().flatten().map(Element::decimal).transpose()?;
 
         let order = Order {
-            price: vec[0],
-            quantity_base: vec[1],
-            quantity_quote: vec[2],
-            quantity_contract: if vec.len() == 4 { Some(vec[3]) } else { None },
+            price,
+            quantity_base,
+            quantity_quote,
+            quantity_contract,
+            price_exact,
+            quantity_exact,
         };
 
         Ok(order)
@@ -91,6 +157,8 @@ impl<'de> Deserialize<'de> for Order {
 #[cfg(test)]
 mod tests {
     use crate::order::Order;
+    use rust_decimal::Decimal;
+    use std::str::FromStr;
 
     #[test]
     fn order_serialize() {
@@ -99,6 +167,8 @@ mod tests {
             quantity_base: 1.7000000001,
             quantity_quote: 59999.8 * 1.7,
             quantity_contract: Some(1.7),
+            price_exact: None,
+            quantity_exact: None,
         };
         let text = serde_json::to_string(&order).unwrap();
         assert_eq!(text.as_str(), "[59999.8,1.7,101999.66,1.7]");
@@ -108,6 +178,8 @@ mod tests {
             quantity_base: 1.7000000006,
             quantity_quote: 59999.8 * 1.7,
             quantity_contract: Some(1.7),
+            price_exact: None,
+            quantity_exact: None,
         };
         let text = serde_json::to_string(&order).unwrap();
         assert_eq!(text.as_str(), "[59999.8,1.700000001,101999.66,1.7]");
@@ -120,6 +192,8 @@ mod tests {
             quantity_base: 1.7,
             quantity_quote: 59999.8 * 1.7,
             quantity_contract: Some(1.7),
+            price_exact: None,
+            quantity_exact: None,
         };
         let actual = serde_json::from_str::<Order>("[59999.8,1.7,101999.66,1.7]").unwrap();
         assert_eq!(expected.price, actual.price);
@@ -127,4 +201,30 @@ mod tests {
         assert_eq!(expected.quantity_quote, actual.quantity_quote);
         assert_eq!(expected.quantity_contract, actual.quantity_contract);
     }
+
+    #[test]
+    fn order_exact() {
+        let order = Order {
+            price: 0.0000000001,
+            quantity_base: 12345678901.0,
+            quantity_quote: 1.2345678901,
+            quantity_contract: None,
+            price_exact: Some(Decimal::from_str("0.00000000010").unwrap()),
+            quantity_exact: Some(Decimal::from_str("12345678901").unwrap()),
+        };
+        let text = serde_json::to_string(&order).unwrap();
+        assert_eq!(
+            text.as_str(),
+            r#"[1e-10,12345678901.0,1.23456789,null,"0.00000000010","12345678901"]"#
+        );
+
+        let restored = serde_json::from_str::<Order>(&text).unwrap();
+        assert_eq!(order.price_exact, restored.price_exact);
+        assert_eq!("0.00000000010", restored.price_exact.unwrap().to_string());
+        assert_eq!(order.quantity_exact, restored.quantity_exact);
+        assert_eq!(None, restored.quantity_contract);
+
+        assert!(serde_json::from_str::<Order>("[1.0,2.0]").is_err());
+        assert!(serde_json::from_str::<Order>(r#"[1.0,2.0,2.0,null,1.0,"2"]"#).is_err());
+    }
 }
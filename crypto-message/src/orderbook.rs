@@ -0,0 +1,396 @@
+use std::{cmp::Ordering, collections::BTreeMap, fmt};
+
+use crypto_market_type::MarketType;
+
+use crate::{
+    Order, OrderBookMsg,
+    sequence::{SequenceEvent, check_sequence},
+};
+
+/// f64 with total ordering, used as the key of price levels.
+#[derive(Copy, Clone, Debug)]
+pub struct Price(pub f64);
+
+impl PartialEq for Price {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Price {}
+
+impl PartialOrd for Price {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Price {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.0.total_cmp(&other.0)
+    }
+}
+
+/// Errors returned by `OrderBook::apply()`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum OrderBookError {
+    /// An update arrived before the first snapshot, or after a gap.
+    MissingSnapshot,
+    /// One or more updates between `last_seq_id` and this update were lost,
+    /// the book has to be rebuilt from a new snapshot.
+    SequenceGap {
+        /// The sequence ID of the last applied message
+        last_seq_id: u64,
+        /// The sequence ID of the rejected update
+        seq_id: Option<u64>,
+        /// The previous sequence ID of the rejected update
+        prev_seq_id: Option<u64>,
+    },
+}
+
+impl fmt::Display for OrderBookError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            OrderBookError::MissingSnapshot => write!(f, "no snapshot applied"),
+            OrderBookError::SequenceGap { last_seq_id, seq_id, prev_seq_id } => write!(
+                f,
+                "sequence gap, last_seq_id: {last_seq_id}, seq_id: {seq_id:?}, prev_seq_id: {prev_seq_id:?}"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for OrderBookError {}
+
+/// Level2 orderbook rebuilt from snapshots and updates.
+///
+/// Feed it `OrderBookMsg`s of one symbol in the order they were received,
+/// snapshots replace all levels and updates are merged into them, levels with
+/// zero quantity are removed.
+///
+/// Gaps are detected with the same rules as `SequenceValidator`, on exchanges
+/// without sequence IDs stale updates can't be told apart and are applied.
+#[derive(Clone, Debug)]
+pub struct OrderBook {
+    exchange: String,
+    market_type: MarketType,
+    symbol: String,
+    timestamp: i64,
+    seq_id: Option<u64>,
+    synced: bool,
+    asks: BTreeMap<Price, Order>,
+    bids: BTreeMap<Price, Order>,
+}
+
+impl OrderBook {
+    pub fn new(exchange: &str, market_type: MarketType, symbol: &str) -> Self {
+        OrderBook {
+            exchange: exchange.to_string(),
+            market_type,
+            symbol: symbol.to_string(),
+            timestamp: 0,
+            seq_id: None,
+            synced: false,
+            asks: BTreeMap::new(),
+            bids: BTreeMap::new(),
+        }
+    }
+
+    /// Apply a snapshot or an update.
+    ///
+    /// Returns `Ok(false)` if the update is older than the book and was
+    /// skipped. After a `SequenceGap` all updates are rejected with
+    /// `MissingSnapshot` until the next snapshot.
+    pub fn apply(&mut self, msg: &OrderBookMsg) -> Result<bool, OrderBookError> {
+        debug_assert_eq!(self.exchange, msg.exchange);
+        debug_assert_eq!(self.market_type, msg.market_type);
+        debug_assert_eq!(self.symbol, msg.symbol);
+
+        if msg.snapshot {
+            self.asks.clear();
+            self.bids.clear();
+        } else {
+            if !self.synced {
+                return Err(OrderBookError::MissingSnapshot);
+            }
+            match check_sequence(self.seq_id, msg) {
+                SequenceEvent::Duplicate { .. } => return Ok(false),
+                SequenceEvent::Gap { missing } => {
+                    self.synced = false;
+                    return Err(OrderBookError::SequenceGap {
+                        last_seq_id: *missing.start() - 1,
+                        seq_id: msg.seq_id,
+                        prev_seq_id: msg.prev_seq_id,
+                    });
+                }
+                _ => {}
+            }
+        }
+
+        Self::merge(&mut self.asks, &msg.asks);
+        Self::merge(&mut self.bids, &msg.bids);
+        self.timestamp = msg.timestamp;
+        // a snapshot replaces the sequence number, even if it has none
+        if msg.snapshot || msg.seq_id.is_some() {
+            self.seq_id = msg.seq_id;
+        }
+        self.synced = true;
+        Ok(true)
+    }
+
+    /// Drop the levels beyond the best `depth` on each side.
+    ///
+    /// Exchanges which only publish the top levels, e.g., Kraken `book-25`,
+    /// don't delete levels that fall out of the subscribed depth.
+    pub fn truncate(&mut self, depth: usize) {
+        while self.asks.len() > depth {
+            self.asks.pop_last();
+        }
+        while self.bids.len() > depth {
+            self.bids.pop_first();
+        }
+    }
+
+    fn merge(levels: &mut BTreeMap<Price, Order>, orders: &[Order]) {
+        for order in orders {
+            if order.quantity_base == 0.0 {
+                levels.remove(&Price(order.price));
+            } else {
+                levels.insert(Price(order.price), *order);
+            }
+        }
+    }
+
+    pub fn exchange(&self) -> &str {
+        &self.exchange
+    }
+
+    pub fn market_type(&self) -> MarketType {
+        self.market_type
+    }
+
+    pub fn symbol(&self) -> &str {
+        &self.symbol
+    }
+
+    /// Whether a snapshot has been applied and no gap was detected since.
+    pub fn is_synced(&self) -> bool {
+        self.synced
+    }
+
+    /// The timestamp of the last applied message, in milliseconds.
+    pub fn timestamp(&self) -> i64 {
+        self.timestamp
+    }
+
+    /// The sequence ID of the last applied message.
+    pub fn seq_id(&self) -> Option<u64> {
+        self.seq_id
+    }
+
+    pub fn best_ask(&self) -> Option<&Order> {
+        self.asks.values().next()
+    }
+
+    pub fn best_bid(&self) -> Option<&Order> {
+        self.bids.values().next_back()
+    }
+
+    /// Asks in ascending order by price.
+    pub fn asks(&self) -> impl Iterator<Item = &Order> {
+        self.asks.values()
+    }
+
+    /// Bids in descending order by price.
+    pub fn bids(&self) -> impl Iterator<Item = &Order> {
+        self.bids.values().rev()
+    }
+
+    /// The best `k` asks, in ascending order by price.
+    pub fn top_asks(&self, k: usize) -> Vec<Order> {
+        self.asks().take(k).copied().collect()
+    }
+
+    /// The best `k` bids, in descending order by price.
+    pub fn top_bids(&self, k: usize) -> Vec<Order> {
+        self.bids().take(k).copied().collect()
+    }
+
+    /// Total base quantity of asks priced at or below `price`.
+    pub fn ask_depth(&self, price: f64) -> f64 {
+        self.asks.range(..=Price(price)).map(|(_, order)| order.quantity_base).sum()
+    }
+
+    /// Total base quantity of bids priced at or above `price`.
+    pub fn bid_depth(&self, price: f64) -> f64 {
+        self.bids.range(Price(price)..).map(|(_, order)| order.quantity_base).sum()
+    }
+
+    /// Number of ask and bid levels.
+    pub fn len(&self) -> (usize, usize) {
+        (self.asks.len(), self.bids.len())
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.asks.is_empty() && self.bids.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{OrderBook, OrderBookError};
+    use crate::{Order, OrderBookMsg};
+    use crypto_market_type::MarketType;
+    use crypto_msg_type::MessageType;
+
+    fn order(price: f64, quantity: f64) -> Order {
+        Order {
+            price,
+            quantity_base: quantity,
+            quantity_quote: price * quantity,
+            quantity_contract: None,
+            price_exact: None,
+            quantity_exact: None,
+        }
+    }
+
+    fn orderbook_msg(
+        exchange: &str,
+        snapshot: bool,
+        asks: Vec<Order>,
+        bids: Vec<Order>,
+        seq_id: Option<u64>,
+        prev_seq_id: Option<u64>,
+    ) -> OrderBookMsg {
+        OrderBookMsg {
+            exchange: exchange.to_string(),
+            market_type: MarketType::InverseFuture,
+            symbol: "BTC-PERPETUAL".to_string(),
+            pair: "BTC/USD".to_string(),
+            msg_type: MessageType::L2Event,
+            timestamp: 1648785270714,
+            snapshot,
+            asks,
+            bids,
+            seq_id,
+            prev_seq_id,
+            json: "".to_string(),
+        }
+    }
+
+    #[test]
+    fn snapshot_and_updates() {
+        let mut book = OrderBook::new("deribit", MarketType::InverseFuture, "BTC-PERPETUAL");
+        assert_eq!(
+            Err(OrderBookError::MissingSnapshot),
+            book.apply(&orderbook_msg("deribit", false, vec![], vec![], Some(1), None))
+        );
+
+        let snapshot = orderbook_msg(
+            "deribit",
+            true,
+            vec![order(101.0, 1.0), order(102.0, 2.0), order(103.0, 3.0)],
+            vec![order(100.0, 1.0), order(99.0, 2.0)],
+            Some(10),
+            None,
+        );
+        assert_eq!(Ok(true), book.apply(&snapshot));
+        assert_eq!(book.best_ask(), Some(&order(101.0, 1.0)));
+        assert_eq!(book.best_bid(), Some(&order(100.0, 1.0)));
+
+        let update = orderbook_msg(
+            "deribit",
+            false,
+            vec![order(101.0, 0.0), order(102.5, 5.0)],
+            vec![order(100.5, 4.0)],
+            Some(12),
+            Some(10),
+        );
+        assert_eq!(Ok(true), book.apply(&update));
+        assert_eq!(book.len(), (3, 3));
+        assert_eq!(book.top_asks(2), vec![order(102.0, 2.0), order(102.5, 5.0)]);
+        assert_eq!(book.top_bids(2), vec![order(100.5, 4.0), order(100.0, 1.0)]);
+        assert_eq!(book.ask_depth(102.5), 7.0);
+        assert_eq!(book.bid_depth(100.0), 5.0);
+        assert_eq!(book.seq_id(), Some(12));
+
+        // stale
+        assert_eq!(Ok(false), book.apply(&update));
+
+        let gap = orderbook_msg("deribit", false, vec![], vec![], Some(15), Some(13));
+        assert_eq!(
+            Err(OrderBookError::SequenceGap {
+                last_seq_id: 12,
+                seq_id: Some(15),
+                prev_seq_id: Some(13)
+            }),
+            book.apply(&gap)
+        );
+        assert!(!book.is_synced());
+        assert_eq!(
+            Err(OrderBookError::MissingSnapshot),
+            book.apply(&orderbook_msg("deribit", false, vec![], vec![], Some(16), Some(15)))
+        );
+        assert_eq!(Ok(true), book.apply(&snapshot));
+        assert!(book.is_synced());
+
+        book.truncate(1);
+        assert_eq!(book.len(), (1, 1));
+        assert_eq!(book.best_ask(), Some(&order(101.0, 1.0)));
+        assert_eq!(book.best_bid(), Some(&order(100.0, 1.0)));
+    }
+
+    #[test]
+    fn consecutive_seq_id() {
+        let mut book = OrderBook::new("kraken", MarketType::InverseFuture, "BTC-PERPETUAL");
+        book.apply(&orderbook_msg("kraken", true, vec![order(101.0, 1.0)], vec![], Some(5), None))
+            .unwrap();
+        assert_eq!(
+            Ok(true),
+            book.apply(&orderbook_msg(
+                "kraken",
+                false,
+                vec![order(102.0, 1.0)],
+                vec![],
+                Some(6),
+                None
+            ))
+        );
+        assert!(matches!(
+            book.apply(&orderbook_msg("kraken", false, vec![], vec![], Some(8), None)),
+            Err(OrderBookError::SequenceGap { last_seq_id: 6, .. })
+        ));
+    }
+
+    #[test]
+    fn snapshot_without_seq_id() {
+        let mut book = OrderBook::new("deribit", MarketType::InverseFuture, "BTC-PERPETUAL");
+        book.apply(&orderbook_msg(
+            "deribit",
+            true,
+            vec![order(101.0, 1.0)],
+            vec![],
+            Some(10),
+            None,
+        ))
+        .unwrap();
+        assert_eq!(book.seq_id(), Some(10));
+
+        book.apply(&orderbook_msg("deribit", true, vec![order(102.0, 1.0)], vec![], None, None))
+            .unwrap();
+        assert_eq!(book.seq_id(), None);
+        // not a duplicate of the first snapshot
+        assert_eq!(
+            Ok(true),
+            book.apply(&orderbook_msg(
+                "deribit",
+                false,
+                vec![order(103.0, 1.0)],
+                vec![],
+                Some(5),
+                None
+            ))
+        );
+        assert_eq!(book.seq_id(), Some(5));
+    }
+}
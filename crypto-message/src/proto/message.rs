@@ -1,5 +1,5 @@
-// This file is generated by rust-protobuf 3.2.0. Do not edit
-// .proto file is parsed by protoc --rust-out=...
+// This file is generated by rust-protobuf 3.7.2. Do not edit
+// .proto file is parsed by pure
 // @generated
 
 // https://github.com/rust-lang/rust-clippy/issues/702
@@ -9,7 +9,6 @@
 #![allow(unused_attributes)]
 #![cfg_attr(rustfmt, rustfmt::skip)]
 
-#![allow(box_pointers)]
 #![allow(dead_code)]
 #![allow(missing_docs)]
 #![allow(non_camel_case_types)]
@@ -23,29 +22,28 @@
 
 /// Generated files are compatible only with the same version
 /// of protobuf runtime.
-const _PROTOBUF_VERSION_CHECK: () = ::protobuf::VERSION_3_5_0;
+const _PROTOBUF_VERSION_CHECK: () = ::protobuf::VERSION_3_7_2;
 
-///  Tick-by-tick trade message.
-#[derive(PartialEq,Clone,Default,Debug)]
 // @@protoc_insertion_point(message:crypto_crawler.Trade)
+#[derive(PartialEq,Clone,Default,Debug)]
 pub struct Trade {
     // message fields
     // @@protoc_insertion_point(field:crypto_crawler.Trade.timestamp)
     pub timestamp: ::protobuf::MessageField<::protobuf::well_known_types::timestamp::Timestamp>,
-    ///  Which side is taker? True, seller is taker; False, buyer is taker
     // @@protoc_insertion_point(field:crypto_crawler.Trade.side)
     pub side: bool,
     // @@protoc_insertion_point(field:crypto_crawler.Trade.price)
     pub price: f32,
-    ///  Number of base coins, 0 means delete
     // @@protoc_insertion_point(field:crypto_crawler.Trade.quantity_base)
     pub quantity_base: f32,
-    ///  Number of quote coins, 0 means delete
     // @@protoc_insertion_point(field:crypto_crawler.Trade.quantity_quote)
     pub quantity_quote: f32,
-    ///  Number of contracts, empty for spot markets
     // @@protoc_insertion_point(field:crypto_crawler.Trade.quantity_contract)
     pub quantity_contract: ::std::option::Option<f32>,
+    // @@protoc_insertion_point(field:crypto_crawler.Trade.price_exact)
+    pub price_exact: ::std::option::Option<::std::string::String>,
+    // @@protoc_insertion_point(field:crypto_crawler.Trade.quantity_exact)
+    pub quantity_exact: ::std::option::Option<::std::string::String>,
     // special fields
     // @@protoc_insertion_point(special_field:crypto_crawler.Trade.special_fields)
     pub special_fields: ::protobuf::SpecialFields,
@@ -63,7 +61,7 @@ impl Trade {
     }
 
     fn generated_message_descriptor_data() -> ::protobuf::reflect::GeneratedMessageDescriptorData {
-        let mut fields = ::std::vec::Vec::with_capacity(6);
+        let mut fields = ::std::vec::Vec::with_capacity(8);
         let mut oneofs = ::std::vec::Vec::with_capacity(0);
         fields.push(::protobuf::reflect::rt::v2::make_message_field_accessor::<_, ::protobuf::well_known_types::timestamp::Timestamp>(
             "timestamp",
@@ -95,6 +93,16 @@ impl Trade {
             |m: &Trade| { &m.quantity_contract },
             |m: &mut Trade| { &mut m.quantity_contract },
         ));
+        fields.push(::protobuf::reflect::rt::v2::make_option_accessor::<_, _>(
+            "price_exact",
+            |m: &Trade| { &m.price_exact },
+            |m: &mut Trade| { &mut m.price_exact },
+        ));
+        fields.push(::protobuf::reflect::rt::v2::make_option_accessor::<_, _>(
+            "quantity_exact",
+            |m: &Trade| { &m.quantity_exact },
+            |m: &mut Trade| { &mut m.quantity_exact },
+        ));
         ::protobuf::reflect::GeneratedMessageDescriptorData::new_2::<Trade>(
             "Trade",
             fields,
@@ -131,6 +139,12 @@ impl ::protobuf::Message for Trade {
                 53 => {
                     self.quantity_contract = ::std::option::Option::Some(is.read_float()?);
                 },
+                58 => {
+                    self.price_exact = ::std::option::Option::Some(is.read_string()?);
+                },
+                66 => {
+                    self.quantity_exact = ::std::option::Option::Some(is.read_string()?);
+                },
                 tag => {
                     ::protobuf::rt::read_unknown_or_skip_group(tag, is, self.special_fields.mut_unknown_fields())?;
                 },
@@ -162,6 +176,12 @@ impl ::protobuf::Message for Trade {
         if let Some(v) = self.quantity_contract {
             my_size += 1 + 4;
         }
+        if let Some(v) = self.price_exact.as_ref() {
+            my_size += ::protobuf::rt::string_size(7, &v);
+        }
+        if let Some(v) = self.quantity_exact.as_ref() {
+            my_size += ::protobuf::rt::string_size(8, &v);
+        }
         my_size += ::protobuf::rt::unknown_fields_size(self.special_fields.unknown_fields());
         self.special_fields.cached_size().set(my_size as u32);
         my_size
@@ -186,6 +206,12 @@ impl ::protobuf::Message for Trade {
         if let Some(v) = self.quantity_contract {
             os.write_float(6, v)?;
         }
+        if let Some(v) = self.price_exact.as_ref() {
+            os.write_string(7, v)?;
+        }
+        if let Some(v) = self.quantity_exact.as_ref() {
+            os.write_string(8, v)?;
+        }
         os.write_unknown_fields(self.special_fields.unknown_fields())?;
         ::std::result::Result::Ok(())
     }
@@ -209,6 +235,8 @@ impl ::protobuf::Message for Trade {
         self.quantity_base = 0.;
         self.quantity_quote = 0.;
         self.quantity_contract = ::std::option::Option::None;
+        self.price_exact = ::std::option::Option::None;
+        self.quantity_exact = ::std::option::Option::None;
         self.special_fields.clear();
     }
 
@@ -220,6 +248,8 @@ impl ::protobuf::Message for Trade {
             quantity_base: 0.,
             quantity_quote: 0.,
             quantity_contract: ::std::option::Option::None,
+            price_exact: ::std::option::Option::None,
+            quantity_exact: ::std::option::Option::None,
             special_fields: ::protobuf::SpecialFields::new(),
         };
         &instance
@@ -243,20 +273,16 @@ impl ::protobuf::reflect::ProtobufValue for Trade {
     type RuntimeType = ::protobuf::reflect::rt::RuntimeTypeMessage<Self>;
 }
 
-///  Level2 orderbook.
-#[derive(PartialEq,Clone,Default,Debug)]
 // @@protoc_insertion_point(message:crypto_crawler.Orderbook)
+#[derive(PartialEq,Clone,Default,Debug)]
 pub struct Orderbook {
     // message fields
     // @@protoc_insertion_point(field:crypto_crawler.Orderbook.timestamp)
     pub timestamp: ::protobuf::MessageField<::protobuf::well_known_types::timestamp::Timestamp>,
-    ///  snapshot or updates
     // @@protoc_insertion_point(field:crypto_crawler.Orderbook.snapshot)
     pub snapshot: bool,
-    ///  sorted in ascending order by price if snapshot=true, otherwise not sorted
     // @@protoc_insertion_point(field:crypto_crawler.Orderbook.asks)
     pub asks: ::std::vec::Vec<orderbook::Order>,
-    ///  sorted in descending order by price if snapshot=true, otherwise not sorted
     // @@protoc_insertion_point(field:crypto_crawler.Orderbook.bids)
     pub bids: ::std::vec::Vec<orderbook::Order>,
     // special fields
@@ -428,21 +454,22 @@ impl ::protobuf::reflect::ProtobufValue for Orderbook {
 
 /// Nested message and enums of message `Orderbook`
 pub mod orderbook {
-    #[derive(PartialEq,Clone,Default,Debug)]
     // @@protoc_insertion_point(message:crypto_crawler.Orderbook.Order)
+    #[derive(PartialEq,Clone,Default,Debug)]
     pub struct Order {
         // message fields
         // @@protoc_insertion_point(field:crypto_crawler.Orderbook.Order.price)
         pub price: f32,
-        ///  Number of base coins, 0 means delete
         // @@protoc_insertion_point(field:crypto_crawler.Orderbook.Order.quantity_base)
         pub quantity_base: f32,
-        ///  Number of quote coins, 0 means delete
         // @@protoc_insertion_point(field:crypto_crawler.Orderbook.Order.quantity_quote)
         pub quantity_quote: f32,
-        ///  Number of contracts, empty for spot markets
         // @@protoc_insertion_point(field:crypto_crawler.Orderbook.Order.quantity_contract)
         pub quantity_contract: ::std::option::Option<f32>,
+        // @@protoc_insertion_point(field:crypto_crawler.Orderbook.Order.price_exact)
+        pub price_exact: ::std::option::Option<::std::string::String>,
+        // @@protoc_insertion_point(field:crypto_crawler.Orderbook.Order.quantity_exact)
+        pub quantity_exact: ::std::option::Option<::std::string::String>,
         // special fields
         // @@protoc_insertion_point(special_field:crypto_crawler.Orderbook.Order.special_fields)
         pub special_fields: ::protobuf::SpecialFields,
@@ -460,7 +487,7 @@ pub mod orderbook {
         }
 
         pub(in super) fn generated_message_descriptor_data() -> ::protobuf::reflect::GeneratedMessageDescriptorData {
-            let mut fields = ::std::vec::Vec::with_capacity(4);
+            let mut fields = ::std::vec::Vec::with_capacity(6);
             let mut oneofs = ::std::vec::Vec::with_capacity(0);
             fields.push(::protobuf::reflect::rt::v2::make_simpler_field_accessor::<_, _>(
                 "price",
@@ -482,6 +509,16 @@ pub mod orderbook {
                 |m: &Order| { &m.quantity_contract },
                 |m: &mut Order| { &mut m.quantity_contract },
             ));
+            fields.push(::protobuf::reflect::rt::v2::make_option_accessor::<_, _>(
+                "price_exact",
+                |m: &Order| { &m.price_exact },
+                |m: &mut Order| { &mut m.price_exact },
+            ));
+            fields.push(::protobuf::reflect::rt::v2::make_option_accessor::<_, _>(
+                "quantity_exact",
+                |m: &Order| { &m.quantity_exact },
+                |m: &mut Order| { &mut m.quantity_exact },
+            ));
             ::protobuf::reflect::GeneratedMessageDescriptorData::new_2::<Order>(
                 "Orderbook.Order",
                 fields,
@@ -512,6 +549,12 @@ pub mod orderbook {
                     37 => {
                         self.quantity_contract = ::std::option::Option::Some(is.read_float()?);
                     },
+                    42 => {
+                        self.price_exact = ::std::option::Option::Some(is.read_string()?);
+                    },
+                    50 => {
+                        self.quantity_exact = ::std::option::Option::Some(is.read_string()?);
+                    },
                     tag => {
                         ::protobuf::rt::read_unknown_or_skip_group(tag, is, self.special_fields.mut_unknown_fields())?;
                     },
@@ -536,6 +579,12 @@ pub mod orderbook {
             if let Some(v) = self.quantity_contract {
                 my_size += 1 + 4;
             }
+            if let Some(v) = self.price_exact.as_ref() {
+                my_size += ::protobuf::rt::string_size(5, &v);
+            }
+            if let Some(v) = self.quantity_exact.as_ref() {
+                my_size += ::protobuf::rt::string_size(6, &v);
+            }
             my_size += ::protobuf::rt::unknown_fields_size(self.special_fields.unknown_fields());
             self.special_fields.cached_size().set(my_size as u32);
             my_size
@@ -554,6 +603,12 @@ pub mod orderbook {
             if let Some(v) = self.quantity_contract {
                 os.write_float(4, v)?;
             }
+            if let Some(v) = self.price_exact.as_ref() {
+                os.write_string(5, v)?;
+            }
+            if let Some(v) = self.quantity_exact.as_ref() {
+                os.write_string(6, v)?;
+            }
             os.write_unknown_fields(self.special_fields.unknown_fields())?;
             ::std::result::Result::Ok(())
         }
@@ -575,6 +630,8 @@ pub mod orderbook {
             self.quantity_base = 0.;
             self.quantity_quote = 0.;
             self.quantity_contract = ::std::option::Option::None;
+            self.price_exact = ::std::option::Option::None;
+            self.quantity_exact = ::std::option::Option::None;
             self.special_fields.clear();
         }
 
@@ -584,6 +641,8 @@ pub mod orderbook {
                 quantity_base: 0.,
                 quantity_quote: 0.,
                 quantity_contract: ::std::option::Option::None,
+                price_exact: ::std::option::Option::None,
+                quantity_exact: ::std::option::Option::None,
                 special_fields: ::protobuf::SpecialFields::new(),
             };
             &instance
@@ -608,9 +667,8 @@ pub mod orderbook {
     }
 }
 
-///  Best bid and offer.
-#[derive(PartialEq,Clone,Default,Debug)]
 // @@protoc_insertion_point(message:crypto_crawler.Bbo)
+#[derive(PartialEq,Clone,Default,Debug)]
 pub struct Bbo {
     // message fields
     // @@protoc_insertion_point(field:crypto_crawler.Bbo.timestamp)
@@ -631,6 +689,14 @@ pub struct Bbo {
     pub ask_quantity_quote: f32,
     // @@protoc_insertion_point(field:crypto_crawler.Bbo.ask_quantity_contract)
     pub ask_quantity_contract: ::std::option::Option<f32>,
+    // @@protoc_insertion_point(field:crypto_crawler.Bbo.bid_price_exact)
+    pub bid_price_exact: ::std::option::Option<::std::string::String>,
+    // @@protoc_insertion_point(field:crypto_crawler.Bbo.bid_quantity_exact)
+    pub bid_quantity_exact: ::std::option::Option<::std::string::String>,
+    // @@protoc_insertion_point(field:crypto_crawler.Bbo.ask_price_exact)
+    pub ask_price_exact: ::std::option::Option<::std::string::String>,
+    // @@protoc_insertion_point(field:crypto_crawler.Bbo.ask_quantity_exact)
+    pub ask_quantity_exact: ::std::option::Option<::std::string::String>,
     // special fields
     // @@protoc_insertion_point(special_field:crypto_crawler.Bbo.special_fields)
     pub special_fields: ::protobuf::SpecialFields,
@@ -648,7 +714,7 @@ impl Bbo {
     }
 
     fn generated_message_descriptor_data() -> ::protobuf::reflect::GeneratedMessageDescriptorData {
-        let mut fields = ::std::vec::Vec::with_capacity(9);
+        let mut fields = ::std::vec::Vec::with_capacity(13);
         let mut oneofs = ::std::vec::Vec::with_capacity(0);
         fields.push(::protobuf::reflect::rt::v2::make_message_field_accessor::<_, ::protobuf::well_known_types::timestamp::Timestamp>(
             "timestamp",
@@ -695,6 +761,26 @@ impl Bbo {
             |m: &Bbo| { &m.ask_quantity_contract },
             |m: &mut Bbo| { &mut m.ask_quantity_contract },
         ));
+        fields.push(::protobuf::reflect::rt::v2::make_option_accessor::<_, _>(
+            "bid_price_exact",
+            |m: &Bbo| { &m.bid_price_exact },
+            |m: &mut Bbo| { &mut m.bid_price_exact },
+        ));
+        fields.push(::protobuf::reflect::rt::v2::make_option_accessor::<_, _>(
+            "bid_quantity_exact",
+            |m: &Bbo| { &m.bid_quantity_exact },
+            |m: &mut Bbo| { &mut m.bid_quantity_exact },
+        ));
+        fields.push(::protobuf::reflect::rt::v2::make_option_accessor::<_, _>(
+            "ask_price_exact",
+            |m: &Bbo| { &m.ask_price_exact },
+            |m: &mut Bbo| { &mut m.ask_price_exact },
+        ));
+        fields.push(::protobuf::reflect::rt::v2::make_option_accessor::<_, _>(
+            "ask_quantity_exact",
+            |m: &Bbo| { &m.ask_quantity_exact },
+            |m: &mut Bbo| { &mut m.ask_quantity_exact },
+        ));
         ::protobuf::reflect::GeneratedMessageDescriptorData::new_2::<Bbo>(
             "Bbo",
             fields,
@@ -740,6 +826,18 @@ impl ::protobuf::Message for Bbo {
                 77 => {
                     self.ask_quantity_contract = ::std::option::Option::Some(is.read_float()?);
                 },
+                82 => {
+                    self.bid_price_exact = ::std::option::Option::Some(is.read_string()?);
+                },
+                90 => {
+                    self.bid_quantity_exact = ::std::option::Option::Some(is.read_string()?);
+                },
+                98 => {
+                    self.ask_price_exact = ::std::option::Option::Some(is.read_string()?);
+                },
+                106 => {
+                    self.ask_quantity_exact = ::std::option::Option::Some(is.read_string()?);
+                },
                 tag => {
                     ::protobuf::rt::read_unknown_or_skip_group(tag, is, self.special_fields.mut_unknown_fields())?;
                 },
@@ -780,6 +878,18 @@ impl ::protobuf::Message for Bbo {
         if let Some(v) = self.ask_quantity_contract {
             my_size += 1 + 4;
         }
+        if let Some(v) = self.bid_price_exact.as_ref() {
+            my_size += ::protobuf::rt::string_size(10, &v);
+        }
+        if let Some(v) = self.bid_quantity_exact.as_ref() {
+            my_size += ::protobuf::rt::string_size(11, &v);
+        }
+        if let Some(v) = self.ask_price_exact.as_ref() {
+            my_size += ::protobuf::rt::string_size(12, &v);
+        }
+        if let Some(v) = self.ask_quantity_exact.as_ref() {
+            my_size += ::protobuf::rt::string_size(13, &v);
+        }
         my_size += ::protobuf::rt::unknown_fields_size(self.special_fields.unknown_fields());
         self.special_fields.cached_size().set(my_size as u32);
         my_size
@@ -813,6 +923,18 @@ impl ::protobuf::Message for Bbo {
         if let Some(v) = self.ask_quantity_contract {
             os.write_float(9, v)?;
         }
+        if let Some(v) = self.bid_price_exact.as_ref() {
+            os.write_string(10, v)?;
+        }
+        if let Some(v) = self.bid_quantity_exact.as_ref() {
+            os.write_string(11, v)?;
+        }
+        if let Some(v) = self.ask_price_exact.as_ref() {
+            os.write_string(12, v)?;
+        }
+        if let Some(v) = self.ask_quantity_exact.as_ref() {
+            os.write_string(13, v)?;
+        }
         os.write_unknown_fields(self.special_fields.unknown_fields())?;
         ::std::result::Result::Ok(())
     }
@@ -839,6 +961,10 @@ impl ::protobuf::Message for Bbo {
         self.ask_quantity_base = 0.;
         self.ask_quantity_quote = 0.;
         self.ask_quantity_contract = ::std::option::Option::None;
+        self.bid_price_exact = ::std::option::Option::None;
+        self.bid_quantity_exact = ::std::option::Option::None;
+        self.ask_price_exact = ::std::option::Option::None;
+        self.ask_quantity_exact = ::std::option::Option::None;
         self.special_fields.clear();
     }
 
@@ -853,6 +979,10 @@ impl ::protobuf::Message for Bbo {
             ask_quantity_base: 0.,
             ask_quantity_quote: 0.,
             ask_quantity_contract: ::std::option::Option::None,
+            bid_price_exact: ::std::option::Option::None,
+            bid_quantity_exact: ::std::option::Option::None,
+            ask_price_exact: ::std::option::Option::None,
+            ask_quantity_exact: ::std::option::Option::None,
             special_fields: ::protobuf::SpecialFields::new(),
         };
         &instance
@@ -876,9 +1006,8 @@ impl ::protobuf::reflect::ProtobufValue for Bbo {
     type RuntimeType = ::protobuf::reflect::rt::RuntimeTypeMessage<Self>;
 }
 
-///  24hr rolling window ticker.
-#[derive(PartialEq,Clone,Default,Debug)]
 // @@protoc_insertion_point(message:crypto_crawler.Ticker)
+#[derive(PartialEq,Clone,Default,Debug)]
 pub struct Ticker {
     // message fields
     // @@protoc_insertion_point(field:crypto_crawler.Ticker.timestamp)
@@ -905,10 +1034,8 @@ pub struct Ticker {
     pub best_ask_price: ::std::option::Option<f32>,
     // @@protoc_insertion_point(field:crypto_crawler.Ticker.best_ask_quantity)
     pub best_ask_quantity: ::std::option::Option<f32>,
-    ///  availale in Futures and Swap markets
     // @@protoc_insertion_point(field:crypto_crawler.Ticker.open_interest)
     pub open_interest: ::std::option::Option<f32>,
-    ///  availale in Futures and Swap markets
     // @@protoc_insertion_point(field:crypto_crawler.Ticker.open_interest_quote)
     pub open_interest_quote: ::std::option::Option<f32>,
     // special fields
@@ -1238,179 +1365,60 @@ impl ::protobuf::reflect::ProtobufValue for Ticker {
 
 static file_descriptor_proto_data: &'static [u8] = b"\
     \n\rmessage.proto\x12\x0ecrypto_crawler\x1a\x1fgoogle/protobuf/timestamp\
-    .proto\"\xff\x01\n\x05Trade\x128\n\ttimestamp\x18\x01\x20\x01(\x0b2\x1a.\
+    .proto\"\xf4\x02\n\x05Trade\x128\n\ttimestamp\x18\x01\x20\x01(\x0b2\x1a.\
     google.protobuf.TimestampR\ttimestamp\x12\x12\n\x04side\x18\x02\x20\x01(\
     \x08R\x04side\x12\x14\n\x05price\x18\x03\x20\x01(\x02R\x05price\x12#\n\r\
     quantity_base\x18\x04\x20\x01(\x02R\x0cquantityBase\x12%\n\x0equantity_q\
     uote\x18\x05\x20\x01(\x02R\rquantityQuote\x120\n\x11quantity_contract\
-    \x18\x06\x20\x01(\x02H\0R\x10quantityContract\x88\x01\x01B\x14\n\x12_qua\
-    ntity_contract\"\xff\x02\n\tOrderbook\x128\n\ttimestamp\x18\x01\x20\x01(\
-    \x0b2\x1a.google.protobuf.TimestampR\ttimestamp\x12\x1a\n\x08snapshot\
-    \x18\x02\x20\x01(\x08R\x08snapshot\x123\n\x04asks\x18\x03\x20\x03(\x0b2\
-    \x1f.crypto_crawler.Orderbook.OrderR\x04asks\x123\n\x04bids\x18\x04\x20\
-    \x03(\x0b2\x1f.crypto_crawler.Orderbook.OrderR\x04bids\x1a\xb1\x01\n\x05\
-    Order\x12\x14\n\x05price\x18\x01\x20\x01(\x02R\x05price\x12#\n\rquantity\
-    _base\x18\x02\x20\x01(\x02R\x0cquantityBase\x12%\n\x0equantity_quote\x18\
-    \x03\x20\x01(\x02R\rquantityQuote\x120\n\x11quantity_contract\x18\x04\
-    \x20\x01(\x02H\0R\x10quantityContract\x88\x01\x01B\x14\n\x12_quantity_co\
-    ntract\"\xd3\x03\n\x03Bbo\x128\n\ttimestamp\x18\x01\x20\x01(\x0b2\x1a.go\
-    ogle.protobuf.TimestampR\ttimestamp\x12\x1b\n\tbid_price\x18\x02\x20\x01\
-    (\x02R\x08bidPrice\x12*\n\x11bid_quantity_base\x18\x03\x20\x01(\x02R\x0f\
-    bidQuantityBase\x12,\n\x12bid_quantity_quote\x18\x04\x20\x01(\x02R\x10bi\
-    dQuantityQuote\x127\n\x15bid_quantity_contract\x18\x05\x20\x01(\x02H\0R\
-    \x13bidQuantityContract\x88\x01\x01\x12\x1b\n\task_price\x18\x06\x20\x01\
-    (\x02R\x08askPrice\x12*\n\x11ask_quantity_base\x18\x07\x20\x01(\x02R\x0f\
-    askQuantityBase\x12,\n\x12ask_quantity_quote\x18\x08\x20\x01(\x02R\x10as\
-    kQuantityQuote\x127\n\x15ask_quantity_contract\x18\t\x20\x01(\x02H\x01R\
-    \x13askQuantityContract\x88\x01\x01B\x18\n\x16_bid_quantity_contractB\
-    \x18\n\x16_ask_quantity_contract\"\x9c\x05\n\x06Ticker\x128\n\ttimestamp\
-    \x18\x01\x20\x01(\x0b2\x1a.google.protobuf.TimestampR\ttimestamp\x12\x12\
-    \n\x04open\x18\x02\x20\x01(\x02R\x04open\x12\x12\n\x04high\x18\x03\x20\
-    \x01(\x02R\x04high\x12\x10\n\x03low\x18\x04\x20\x01(\x02R\x03low\x12\x14\
-    \n\x05close\x18\x05\x20\x01(\x02R\x05close\x12\x16\n\x06volume\x18\x06\
-    \x20\x01(\x02R\x06volume\x12!\n\x0cquote_volume\x18\x07\x20\x01(\x02R\
-    \x0bquoteVolume\x12(\n\rlast_quantity\x18\x08\x20\x01(\x02H\0R\x0clastQu\
-    antity\x88\x01\x01\x12)\n\x0ebest_bid_price\x18\t\x20\x01(\x02H\x01R\x0c\
-    bestBidPrice\x88\x01\x01\x12/\n\x11best_bid_quantity\x18\n\x20\x01(\x02H\
-    \x02R\x0fbestBidQuantity\x88\x01\x01\x12)\n\x0ebest_ask_price\x18\x0b\
-    \x20\x01(\x02H\x03R\x0cbestAskPrice\x88\x01\x01\x12/\n\x11best_ask_quant\
-    ity\x18\x0c\x20\x01(\x02H\x04R\x0fbestAskQuantity\x88\x01\x01\x12(\n\rop\
-    en_interest\x18\r\x20\x01(\x02H\x05R\x0copenInterest\x88\x01\x01\x123\n\
-    \x13open_interest_quote\x18\x0e\x20\x01(\x02H\x06R\x11openInterestQuote\
-    \x88\x01\x01B\x10\n\x0e_last_quantityB\x11\n\x0f_best_bid_priceB\x14\n\
-    \x12_best_bid_quantityB\x11\n\x0f_best_ask_priceB\x14\n\x12_best_ask_qua\
-    ntityB\x10\n\x0e_open_interestB\x16\n\x14_open_interest_quoteJ\x9f\x18\n\
-    \x06\x12\x04\0\0G\x01\n\x08\n\x01\x0c\x12\x03\0\0\x12\n\x08\n\x01\x02\
-    \x12\x03\x01\0\x17\n\t\n\x02\x03\0\x12\x03\x03\0)\n)\n\x02\x04\0\x12\x04\
-    \x07\0\x12\x01\x1a\x1d\x20Tick-by-tick\x20trade\x20message.\n\n\n\n\x03\
-    \x04\0\x01\x12\x03\x07\x08\r\n\x0b\n\x04\x04\0\x02\0\x12\x03\x08\x02*\n\
-    \x0c\n\x05\x04\0\x02\0\x06\x12\x03\x08\x02\x1b\n\x0c\n\x05\x04\0\x02\0\
-    \x01\x12\x03\x08\x1c%\n\x0c\n\x05\x04\0\x02\0\x03\x12\x03\x08()\nP\n\x04\
-    \x04\0\x02\x01\x12\x03\n\x02\x10\x1aC\x20Which\x20side\x20is\x20taker?\
-    \x20True,\x20seller\x20is\x20taker;\x20False,\x20buyer\x20is\x20taker\n\
-    \n\x0c\n\x05\x04\0\x02\x01\x05\x12\x03\n\x02\x06\n\x0c\n\x05\x04\0\x02\
-    \x01\x01\x12\x03\n\x07\x0b\n\x0c\n\x05\x04\0\x02\x01\x03\x12\x03\n\x0e\
-    \x0f\n\x0b\n\x04\x04\0\x02\x02\x12\x03\x0b\x02\x12\n\x0c\n\x05\x04\0\x02\
-    \x02\x05\x12\x03\x0b\x02\x07\n\x0c\n\x05\x04\0\x02\x02\x01\x12\x03\x0b\
-    \x08\r\n\x0c\n\x05\x04\0\x02\x02\x03\x12\x03\x0b\x10\x11\n3\n\x04\x04\0\
-    \x02\x03\x12\x03\r\x02\x1a\x1a&\x20Number\x20of\x20base\x20coins,\x200\
-    \x20means\x20delete\n\n\x0c\n\x05\x04\0\x02\x03\x05\x12\x03\r\x02\x07\n\
-    \x0c\n\x05\x04\0\x02\x03\x01\x12\x03\r\x08\x15\n\x0c\n\x05\x04\0\x02\x03\
-    \x03\x12\x03\r\x18\x19\n4\n\x04\x04\0\x02\x04\x12\x03\x0f\x02\x1b\x1a'\
-    \x20Number\x20of\x20quote\x20coins,\x200\x20means\x20delete\n\n\x0c\n\
-    \x05\x04\0\x02\x04\x05\x12\x03\x0f\x02\x07\n\x0c\n\x05\x04\0\x02\x04\x01\
-    \x12\x03\x0f\x08\x16\n\x0c\n\x05\x04\0\x02\x04\x03\x12\x03\x0f\x19\x1a\n\
-    :\n\x04\x04\0\x02\x05\x12\x03\x11\x02'\x1a-\x20Number\x20of\x20contracts\
-    ,\x20empty\x20for\x20spot\x20markets\n\n\x0c\n\x05\x04\0\x02\x05\x04\x12\
-    \x03\x11\x02\n\n\x0c\n\x05\x04\0\x02\x05\x05\x12\x03\x11\x0b\x10\n\x0c\n\
-    \x05\x04\0\x02\x05\x01\x12\x03\x11\x11\"\n\x0c\n\x05\x04\0\x02\x05\x03\
-    \x12\x03\x11%&\n\x1f\n\x02\x04\x01\x12\x04\x15\0&\x01\x1a\x13\x20Level2\
-    \x20orderbook.\n\n\n\n\x03\x04\x01\x01\x12\x03\x15\x08\x11\n\x0c\n\x04\
-    \x04\x01\x03\0\x12\x04\x16\x02\x1e\x03\n\x0c\n\x05\x04\x01\x03\0\x01\x12\
-    \x03\x16\n\x0f\n\r\n\x06\x04\x01\x03\0\x02\0\x12\x03\x17\x04\x14\n\x0e\n\
-    \x07\x04\x01\x03\0\x02\0\x05\x12\x03\x17\x04\t\n\x0e\n\x07\x04\x01\x03\0\
-    \x02\0\x01\x12\x03\x17\n\x0f\n\x0e\n\x07\x04\x01\x03\0\x02\0\x03\x12\x03\
-    \x17\x12\x13\n5\n\x06\x04\x01\x03\0\x02\x01\x12\x03\x19\x04\x1c\x1a&\x20\
-    Number\x20of\x20base\x20coins,\x200\x20means\x20delete\n\n\x0e\n\x07\x04\
-    \x01\x03\0\x02\x01\x05\x12\x03\x19\x04\t\n\x0e\n\x07\x04\x01\x03\0\x02\
-    \x01\x01\x12\x03\x19\n\x17\n\x0e\n\x07\x04\x01\x03\0\x02\x01\x03\x12\x03\
-    \x19\x1a\x1b\n6\n\x06\x04\x01\x03\0\x02\x02\x12\x03\x1b\x04\x1d\x1a'\x20\
-    Number\x20of\x20quote\x20coins,\x200\x20means\x20delete\n\n\x0e\n\x07\
-    \x04\x01\x03\0\x02\x02\x05\x12\x03\x1b\x04\t\n\x0e\n\x07\x04\x01\x03\0\
-    \x02\x02\x01\x12\x03\x1b\n\x18\n\x0e\n\x07\x04\x01\x03\0\x02\x02\x03\x12\
-    \x03\x1b\x1b\x1c\n<\n\x06\x04\x01\x03\0\x02\x03\x12\x03\x1d\x04)\x1a-\
-    \x20Number\x20of\x20contracts,\x20empty\x20for\x20spot\x20markets\n\n\
-    \x0e\n\x07\x04\x01\x03\0\x02\x03\x04\x12\x03\x1d\x04\x0c\n\x0e\n\x07\x04\
-    \x01\x03\0\x02\x03\x05\x12\x03\x1d\r\x12\n\x0e\n\x07\x04\x01\x03\0\x02\
-    \x03\x01\x12\x03\x1d\x13$\n\x0e\n\x07\x04\x01\x03\0\x02\x03\x03\x12\x03\
-    \x1d'(\n\x0b\n\x04\x04\x01\x02\0\x12\x03\x1f\x02*\n\x0c\n\x05\x04\x01\
-    \x02\0\x06\x12\x03\x1f\x02\x1b\n\x0c\n\x05\x04\x01\x02\0\x01\x12\x03\x1f\
-    \x1c%\n\x0c\n\x05\x04\x01\x02\0\x03\x12\x03\x1f()\n\"\n\x04\x04\x01\x02\
-    \x01\x12\x03!\x02\x14\x1a\x15\x20snapshot\x20or\x20updates\n\n\x0c\n\x05\
-    \x04\x01\x02\x01\x05\x12\x03!\x02\x06\n\x0c\n\x05\x04\x01\x02\x01\x01\
-    \x12\x03!\x07\x0f\n\x0c\n\x05\x04\x01\x02\x01\x03\x12\x03!\x12\x13\nX\n\
-    \x04\x04\x01\x02\x02\x12\x03#\x02\x1a\x1aK\x20sorted\x20in\x20ascending\
-    \x20order\x20by\x20price\x20if\x20snapshot=true,\x20otherwise\x20not\x20\
-    sorted\n\n\x0c\n\x05\x04\x01\x02\x02\x04\x12\x03#\x02\n\n\x0c\n\x05\x04\
-    \x01\x02\x02\x06\x12\x03#\x0b\x10\n\x0c\n\x05\x04\x01\x02\x02\x01\x12\
-    \x03#\x11\x15\n\x0c\n\x05\x04\x01\x02\x02\x03\x12\x03#\x18\x19\nY\n\x04\
-    \x04\x01\x02\x03\x12\x03%\x02\x1a\x1aL\x20sorted\x20in\x20descending\x20\
-    order\x20by\x20price\x20if\x20snapshot=true,\x20otherwise\x20not\x20sort\
-    ed\n\n\x0c\n\x05\x04\x01\x02\x03\x04\x12\x03%\x02\n\n\x0c\n\x05\x04\x01\
-    \x02\x03\x06\x12\x03%\x0b\x10\n\x0c\n\x05\x04\x01\x02\x03\x01\x12\x03%\
-    \x11\x15\n\x0c\n\x05\x04\x01\x02\x03\x03\x12\x03%\x18\x19\n!\n\x02\x04\
-    \x02\x12\x04)\03\x01\x1a\x15\x20Best\x20bid\x20and\x20offer.\n\n\n\n\x03\
-    \x04\x02\x01\x12\x03)\x08\x0b\n\x0b\n\x04\x04\x02\x02\0\x12\x03*\x02*\n\
-    \x0c\n\x05\x04\x02\x02\0\x06\x12\x03*\x02\x1b\n\x0c\n\x05\x04\x02\x02\0\
-    \x01\x12\x03*\x1c%\n\x0c\n\x05\x04\x02\x02\0\x03\x12\x03*()\n\x0b\n\x04\
-    \x04\x02\x02\x01\x12\x03+\x02\x16\n\x0c\n\x05\x04\x02\x02\x01\x05\x12\
-    \x03+\x02\x07\n\x0c\n\x05\x04\x02\x02\x01\x01\x12\x03+\x08\x11\n\x0c\n\
-    \x05\x04\x02\x02\x01\x03\x12\x03+\x14\x15\n\x0b\n\x04\x04\x02\x02\x02\
-    \x12\x03,\x02\x1e\n\x0c\n\x05\x04\x02\x02\x02\x05\x12\x03,\x02\x07\n\x0c\
-    \n\x05\x04\x02\x02\x02\x01\x12\x03,\x08\x19\n\x0c\n\x05\x04\x02\x02\x02\
-    \x03\x12\x03,\x1c\x1d\n\x0b\n\x04\x04\x02\x02\x03\x12\x03-\x02\x1f\n\x0c\
-    \n\x05\x04\x02\x02\x03\x05\x12\x03-\x02\x07\n\x0c\n\x05\x04\x02\x02\x03\
-    \x01\x12\x03-\x08\x1a\n\x0c\n\x05\x04\x02\x02\x03\x03\x12\x03-\x1d\x1e\n\
-    \x0b\n\x04\x04\x02\x02\x04\x12\x03.\x02+\n\x0c\n\x05\x04\x02\x02\x04\x04\
-    \x12\x03.\x02\n\n\x0c\n\x05\x04\x02\x02\x04\x05\x12\x03.\x0b\x10\n\x0c\n\
-    \x05\x04\x02\x02\x04\x01\x12\x03.\x11&\n\x0c\n\x05\x04\x02\x02\x04\x03\
-    \x12\x03.)*\n\x0b\n\x04\x04\x02\x02\x05\x12\x03/\x02\x16\n\x0c\n\x05\x04\
-    \x02\x02\x05\x05\x12\x03/\x02\x07\n\x0c\n\x05\x04\x02\x02\x05\x01\x12\
-    \x03/\x08\x11\n\x0c\n\x05\x04\x02\x02\x05\x03\x12\x03/\x14\x15\n\x0b\n\
-    \x04\x04\x02\x02\x06\x12\x030\x02\x1e\n\x0c\n\x05\x04\x02\x02\x06\x05\
-    \x12\x030\x02\x07\n\x0c\n\x05\x04\x02\x02\x06\x01\x12\x030\x08\x19\n\x0c\
-    \n\x05\x04\x02\x02\x06\x03\x12\x030\x1c\x1d\n\x0b\n\x04\x04\x02\x02\x07\
-    \x12\x031\x02\x1f\n\x0c\n\x05\x04\x02\x02\x07\x05\x12\x031\x02\x07\n\x0c\
-    \n\x05\x04\x02\x02\x07\x01\x12\x031\x08\x1a\n\x0c\n\x05\x04\x02\x02\x07\
-    \x03\x12\x031\x1d\x1e\n\x0b\n\x04\x04\x02\x02\x08\x12\x032\x02+\n\x0c\n\
-    \x05\x04\x02\x02\x08\x04\x12\x032\x02\n\n\x0c\n\x05\x04\x02\x02\x08\x05\
-    \x12\x032\x0b\x10\n\x0c\n\x05\x04\x02\x02\x08\x01\x12\x032\x11&\n\x0c\n\
-    \x05\x04\x02\x02\x08\x03\x12\x032)*\n)\n\x02\x04\x03\x12\x046\0G\x01\x1a\
-    \x1d\x2024hr\x20rolling\x20window\x20ticker.\n\n\n\n\x03\x04\x03\x01\x12\
-    \x036\x08\x0e\n\x0b\n\x04\x04\x03\x02\0\x12\x037\x02*\n\x0c\n\x05\x04\
-    \x03\x02\0\x06\x12\x037\x02\x1b\n\x0c\n\x05\x04\x03\x02\0\x01\x12\x037\
-    \x1c%\n\x0c\n\x05\x04\x03\x02\0\x03\x12\x037()\n\x0b\n\x04\x04\x03\x02\
-    \x01\x12\x038\x02\x11\n\x0c\n\x05\x04\x03\x02\x01\x05\x12\x038\x02\x07\n\
-    \x0c\n\x05\x04\x03\x02\x01\x01\x12\x038\x08\x0c\n\x0c\n\x05\x04\x03\x02\
-    \x01\x03\x12\x038\x0f\x10\n\x0b\n\x04\x04\x03\x02\x02\x12\x039\x02\x11\n\
-    \x0c\n\x05\x04\x03\x02\x02\x05\x12\x039\x02\x07\n\x0c\n\x05\x04\x03\x02\
-    \x02\x01\x12\x039\x08\x0c\n\x0c\n\x05\x04\x03\x02\x02\x03\x12\x039\x0f\
-    \x10\n\x0b\n\x04\x04\x03\x02\x03\x12\x03:\x02\x10\n\x0c\n\x05\x04\x03\
-    \x02\x03\x05\x12\x03:\x02\x07\n\x0c\n\x05\x04\x03\x02\x03\x01\x12\x03:\
-    \x08\x0b\n\x0c\n\x05\x04\x03\x02\x03\x03\x12\x03:\x0e\x0f\n\x0b\n\x04\
-    \x04\x03\x02\x04\x12\x03;\x02\x12\n\x0c\n\x05\x04\x03\x02\x04\x05\x12\
-    \x03;\x02\x07\n\x0c\n\x05\x04\x03\x02\x04\x01\x12\x03;\x08\r\n\x0c\n\x05\
-    \x04\x03\x02\x04\x03\x12\x03;\x10\x11\n\x0b\n\x04\x04\x03\x02\x05\x12\
-    \x03<\x02\x13\n\x0c\n\x05\x04\x03\x02\x05\x05\x12\x03<\x02\x07\n\x0c\n\
-    \x05\x04\x03\x02\x05\x01\x12\x03<\x08\x0e\n\x0c\n\x05\x04\x03\x02\x05\
-    \x03\x12\x03<\x11\x12\n\x0b\n\x04\x04\x03\x02\x06\x12\x03=\x02\x19\n\x0c\
-    \n\x05\x04\x03\x02\x06\x05\x12\x03=\x02\x07\n\x0c\n\x05\x04\x03\x02\x06\
-    \x01\x12\x03=\x08\x14\n\x0c\n\x05\x04\x03\x02\x06\x03\x12\x03=\x17\x18\n\
-    \x0b\n\x04\x04\x03\x02\x07\x12\x03>\x02#\n\x0c\n\x05\x04\x03\x02\x07\x04\
-    \x12\x03>\x02\n\n\x0c\n\x05\x04\x03\x02\x07\x05\x12\x03>\x0b\x10\n\x0c\n\
-    \x05\x04\x03\x02\x07\x01\x12\x03>\x11\x1e\n\x0c\n\x05\x04\x03\x02\x07\
-    \x03\x12\x03>!\"\n\x0b\n\x04\x04\x03\x02\x08\x12\x03?\x02$\n\x0c\n\x05\
-    \x04\x03\x02\x08\x04\x12\x03?\x02\n\n\x0c\n\x05\x04\x03\x02\x08\x05\x12\
-    \x03?\x0b\x10\n\x0c\n\x05\x04\x03\x02\x08\x01\x12\x03?\x11\x1f\n\x0c\n\
-    \x05\x04\x03\x02\x08\x03\x12\x03?\"#\n\x0b\n\x04\x04\x03\x02\t\x12\x03@\
-    \x02(\n\x0c\n\x05\x04\x03\x02\t\x04\x12\x03@\x02\n\n\x0c\n\x05\x04\x03\
-    \x02\t\x05\x12\x03@\x0b\x10\n\x0c\n\x05\x04\x03\x02\t\x01\x12\x03@\x11\"\
-    \n\x0c\n\x05\x04\x03\x02\t\x03\x12\x03@%'\n\x0b\n\x04\x04\x03\x02\n\x12\
-    \x03A\x02%\n\x0c\n\x05\x04\x03\x02\n\x04\x12\x03A\x02\n\n\x0c\n\x05\x04\
-    \x03\x02\n\x05\x12\x03A\x0b\x10\n\x0c\n\x05\x04\x03\x02\n\x01\x12\x03A\
-    \x11\x1f\n\x0c\n\x05\x04\x03\x02\n\x03\x12\x03A\"$\n\x0b\n\x04\x04\x03\
-    \x02\x0b\x12\x03B\x02(\n\x0c\n\x05\x04\x03\x02\x0b\x04\x12\x03B\x02\n\n\
-    \x0c\n\x05\x04\x03\x02\x0b\x05\x12\x03B\x0b\x10\n\x0c\n\x05\x04\x03\x02\
-    \x0b\x01\x12\x03B\x11\"\n\x0c\n\x05\x04\x03\x02\x0b\x03\x12\x03B%'\n3\n\
-    \x04\x04\x03\x02\x0c\x12\x03D\x02$\x1a&\x20availale\x20in\x20Futures\x20\
-    and\x20Swap\x20markets\n\n\x0c\n\x05\x04\x03\x02\x0c\x04\x12\x03D\x02\n\
-    \n\x0c\n\x05\x04\x03\x02\x0c\x05\x12\x03D\x0b\x10\n\x0c\n\x05\x04\x03\
-    \x02\x0c\x01\x12\x03D\x11\x1e\n\x0c\n\x05\x04\x03\x02\x0c\x03\x12\x03D!#\
-    \n3\n\x04\x04\x03\x02\r\x12\x03F\x02*\x1a&\x20availale\x20in\x20Futures\
-    \x20and\x20Swap\x20markets\n\n\x0c\n\x05\x04\x03\x02\r\x04\x12\x03F\x02\
-    \n\n\x0c\n\x05\x04\x03\x02\r\x05\x12\x03F\x0b\x10\n\x0c\n\x05\x04\x03\
-    \x02\r\x01\x12\x03F\x11$\n\x0c\n\x05\x04\x03\x02\r\x03\x12\x03F')b\x06pr\
-    oto3\
+    \x18\x06\x20\x01(\x02H\0R\x10quantityContract\x88\x01\x01\x12$\n\x0bpric\
+    e_exact\x18\x07\x20\x01(\tH\x01R\npriceExact\x88\x01\x01\x12*\n\x0equant\
+    ity_exact\x18\x08\x20\x01(\tH\x02R\rquantityExact\x88\x01\x01B\x14\n\x12\
+    _quantity_contractB\x0e\n\x0c_price_exactB\x11\n\x0f_quantity_exact\"\
+    \xf4\x03\n\tOrderbook\x128\n\ttimestamp\x18\x01\x20\x01(\x0b2\x1a.google\
+    .protobuf.TimestampR\ttimestamp\x12\x1a\n\x08snapshot\x18\x02\x20\x01(\
+    \x08R\x08snapshot\x123\n\x04asks\x18\x03\x20\x03(\x0b2\x1f.crypto_crawle\
+    r.Orderbook.OrderR\x04asks\x123\n\x04bids\x18\x04\x20\x03(\x0b2\x1f.cryp\
+    to_crawler.Orderbook.OrderR\x04bids\x1a\xa6\x02\n\x05Order\x12\x14\n\x05\
+    price\x18\x01\x20\x01(\x02R\x05price\x12#\n\rquantity_base\x18\x02\x20\
+    \x01(\x02R\x0cquantityBase\x12%\n\x0equantity_quote\x18\x03\x20\x01(\x02\
+    R\rquantityQuote\x120\n\x11quantity_contract\x18\x04\x20\x01(\x02H\0R\
+    \x10quantityContract\x88\x01\x01\x12$\n\x0bprice_exact\x18\x05\x20\x01(\
+    \tH\x01R\npriceExact\x88\x01\x01\x12*\n\x0equantity_exact\x18\x06\x20\
+    \x01(\tH\x02R\rquantityExact\x88\x01\x01B\x14\n\x12_quantity_contractB\
+    \x0e\n\x0c_price_exactB\x11\n\x0f_quantity_exact\"\xe9\x05\n\x03Bbo\x128\
+    \n\ttimestamp\x18\x01\x20\x01(\x0b2\x1a.google.protobuf.TimestampR\ttime\
+    stamp\x12\x1b\n\tbid_price\x18\x02\x20\x01(\x02R\x08bidPrice\x12*\n\x11b\
+    id_quantity_base\x18\x03\x20\x01(\x02R\x0fbidQuantityBase\x12,\n\x12bid_\
+    quantity_quote\x18\x04\x20\x01(\x02R\x10bidQuantityQuote\x127\n\x15bid_q\
+    uantity_contract\x18\x05\x20\x01(\x02H\0R\x13bidQuantityContract\x88\x01\
+    \x01\x12\x1b\n\task_price\x18\x06\x20\x01(\x02R\x08askPrice\x12*\n\x11as\
+    k_quantity_base\x18\x07\x20\x01(\x02R\x0faskQuantityBase\x12,\n\x12ask_q\
+    uantity_quote\x18\x08\x20\x01(\x02R\x10askQuantityQuote\x127\n\x15ask_qu\
+    antity_contract\x18\t\x20\x01(\x02H\x01R\x13askQuantityContract\x88\x01\
+    \x01\x12+\n\x0fbid_price_exact\x18\n\x20\x01(\tH\x02R\rbidPriceExact\x88\
+    \x01\x01\x121\n\x12bid_quantity_exact\x18\x0b\x20\x01(\tH\x03R\x10bidQua\
+    ntityExact\x88\x01\x01\x12+\n\x0fask_price_exact\x18\x0c\x20\x01(\tH\x04\
+    R\raskPriceExact\x88\x01\x01\x121\n\x12ask_quantity_exact\x18\r\x20\x01(\
+    \tH\x05R\x10askQuantityExact\x88\x01\x01B\x18\n\x16_bid_quantity_contrac\
+    tB\x18\n\x16_ask_quantity_contractB\x12\n\x10_bid_price_exactB\x15\n\x13\
+    _bid_quantity_exactB\x12\n\x10_ask_price_exactB\x15\n\x13_ask_quantity_e\
+    xact\"\x9c\x05\n\x06Ticker\x128\n\ttimestamp\x18\x01\x20\x01(\x0b2\x1a.g\
+    oogle.protobuf.TimestampR\ttimestamp\x12\x12\n\x04open\x18\x02\x20\x01(\
+    \x02R\x04open\x12\x12\n\x04high\x18\x03\x20\x01(\x02R\x04high\x12\x10\n\
+    \x03low\x18\x04\x20\x01(\x02R\x03low\x12\x14\n\x05close\x18\x05\x20\x01(\
+    \x02R\x05close\x12\x16\n\x06volume\x18\x06\x20\x01(\x02R\x06volume\x12!\
+    \n\x0cquote_volume\x18\x07\x20\x01(\x02R\x0bquoteVolume\x12(\n\rlast_qua\
+    ntity\x18\x08\x20\x01(\x02H\0R\x0clastQuantity\x88\x01\x01\x12)\n\x0ebes\
+    t_bid_price\x18\t\x20\x01(\x02H\x01R\x0cbestBidPrice\x88\x01\x01\x12/\n\
+    \x11best_bid_quantity\x18\n\x20\x01(\x02H\x02R\x0fbestBidQuantity\x88\
+    \x01\x01\x12)\n\x0ebest_ask_price\x18\x0b\x20\x01(\x02H\x03R\x0cbestAskP\
+    rice\x88\x01\x01\x12/\n\x11best_ask_quantity\x18\x0c\x20\x01(\x02H\x04R\
+    \x0fbestAskQuantity\x88\x01\x01\x12(\n\ropen_interest\x18\r\x20\x01(\x02\
+    H\x05R\x0copenInterest\x88\x01\x01\x123\n\x13open_interest_quote\x18\x0e\
+    \x20\x01(\x02H\x06R\x11openInterestQuote\x88\x01\x01B\x10\n\x0e_last_qua\
+    ntityB\x11\n\x0f_best_bid_priceB\x14\n\x12_best_bid_quantityB\x11\n\x0f_\
+    best_ask_priceB\x14\n\x12_best_ask_quantityB\x10\n\x0e_open_interestB\
+    \x16\n\x14_open_interest_quoteb\x06proto3\
 ";
 
 /// `FileDescriptorProto` object which was a source for this generated file
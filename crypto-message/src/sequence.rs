@@ -0,0 +1,206 @@
+use std::{collections::HashMap, ops::RangeInclusive};
+
+use crypto_market_type::MarketType;
+
+use crate::OrderBookMsg;
+
+/// The result of checking the sequence ID of an orderbook message.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum SequenceEvent {
+    /// The message follows the previous one, or it is the first message.
+    InOrder,
+    /// The message has been seen already, or it is older than the last one.
+    Duplicate { seq_id: u64, last_seq_id: u64 },
+    /// Messages in the `missing` range were lost.
+    Gap { missing: RangeInclusive<u64> },
+    /// A snapshot, which starts a new sequence.
+    Reset {
+        /// The sequence ID before the snapshot
+        last_seq_id: Option<u64>,
+    },
+    /// The message has no sequence ID and can't be checked.
+    Unsequenced,
+}
+
+/// The sequence ID the previous message must have, `None` if the exchange
+/// gives no way to tell.
+///
+/// Most exchanges set `prev_seq_id` in parsers, e.g., `U - 1` for Gate and
+/// `sequenceStart - 1` for KuCoin spot, the rest increment `seq_id` by one per
+/// message.
+fn expected_prev_seq_id(msg: &OrderBookMsg) -> Option<u64> {
+    if msg.prev_seq_id.is_some() {
+        return msg.prev_seq_id;
+    }
+    let seq_id = msg.seq_id?;
+    match msg.exchange.as_str() {
+        // Kraken Futures seq, KuCoin futures sequence and MEXC futures version
+        "kraken" | "kucoin" | "mexc" if msg.market_type != MarketType::Spot => {
+            seq_id.checked_sub(1)
+        }
+        _ => None,
+    }
+}
+
+/// Check `msg` against the sequence ID of the last message of the same symbol.
+pub(crate) fn check_sequence(last_seq_id: Option<u64>, msg: &OrderBookMsg) -> SequenceEvent {
+    if msg.snapshot {
+        return SequenceEvent::Reset { last_seq_id };
+    }
+    let seq_id = match msg.seq_id {
+        Some(seq_id) => seq_id,
+        None => return SequenceEvent::Unsequenced,
+    };
+    let last_seq_id = match last_seq_id {
+        Some(last_seq_id) => last_seq_id,
+        None => return SequenceEvent::InOrder,
+    };
+
+    if seq_id <= last_seq_id {
+        SequenceEvent::Duplicate { seq_id, last_seq_id }
+    } else {
+        match expected_prev_seq_id(msg) {
+            // prev_seq_id < last_seq_id happens on the first update after a
+            // snapshot, which overlaps with it
+            Some(prev_seq_id) if prev_seq_id > last_seq_id => {
+                SequenceEvent::Gap { missing: last_seq_id + 1..=prev_seq_id }
+            }
+            _ => SequenceEvent::InOrder,
+        }
+    }
+}
+
+/// Checks sequence IDs of orderbook messages per exchange, market type and
+/// symbol.
+///
+/// Feed it parsed `OrderBookMsg`s in the order they were received, and alert on
+/// `SequenceEvent::Gap`.
+#[derive(Clone, Debug, Default)]
+pub struct SequenceValidator {
+    last_seq_ids: HashMap<(String, MarketType, String), u64>,
+}
+
+impl SequenceValidator {
+    pub fn new() -> Self {
+        SequenceValidator::default()
+    }
+
+    /// Check a message and remember its sequence ID.
+    pub fn check(&mut self, msg: &OrderBookMsg) -> SequenceEvent {
+        let key = (msg.exchange.clone(), msg.market_type, msg.symbol.clone());
+        let event = check_sequence(self.last_seq_ids.get(&key).copied(), msg);
+        match event {
+            SequenceEvent::Reset { .. } => {
+                if let Some(seq_id) = msg.seq_id {
+                    self.last_seq_ids.insert(key, seq_id);
+                } else {
+                    self.last_seq_ids.remove(&key);
+                }
+            }
+            SequenceEvent::InOrder | SequenceEvent::Gap { .. } => {
+                if let Some(seq_id) = msg.seq_id {
+                    self.last_seq_ids.insert(key, seq_id);
+                }
+            }
+            SequenceEvent::Duplicate { .. } | SequenceEvent::Unsequenced => {}
+        }
+        event
+    }
+
+    /// The sequence ID of the last message of a symbol.
+    pub fn last_seq_id(
+        &self,
+        exchange: &str,
+        market_type: MarketType,
+        symbol: &str,
+    ) -> Option<u64> {
+        self.last_seq_ids.get(&(exchange.to_string(), market_type, symbol.to_string())).copied()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{SequenceEvent, SequenceValidator};
+    use crate::OrderBookMsg;
+    use crypto_market_type::MarketType;
+    use crypto_msg_type::MessageType;
+
+    fn orderbook_msg(
+        exchange: &str,
+        market_type: MarketType,
+        snapshot: bool,
+        seq_id: Option<u64>,
+        prev_seq_id: Option<u64>,
+    ) -> OrderBookMsg {
+        OrderBookMsg {
+            exchange: exchange.to_string(),
+            market_type,
+            symbol: "BTC_USDT".to_string(),
+            pair: "BTC/USDT".to_string(),
+            msg_type: MessageType::L2Event,
+            timestamp: 1648785270714,
+            snapshot,
+            asks: Vec::new(),
+            bids: Vec::new(),
+            seq_id,
+            prev_seq_id,
+            json: "".to_string(),
+        }
+    }
+
+    #[test]
+    fn prev_seq_id() {
+        let mut validator = SequenceValidator::new();
+        let msg = |snapshot, seq_id, prev_seq_id| {
+            orderbook_msg("gate", MarketType::Spot, snapshot, Some(seq_id), prev_seq_id)
+        };
+
+        assert_eq!(
+            SequenceEvent::Reset { last_seq_id: None },
+            validator.check(&msg(true, 100, None))
+        );
+        // overlaps with the snapshot
+        assert_eq!(SequenceEvent::InOrder, validator.check(&msg(false, 103, Some(98))));
+        assert_eq!(SequenceEvent::InOrder, validator.check(&msg(false, 105, Some(103))));
+        assert_eq!(
+            SequenceEvent::Duplicate { seq_id: 105, last_seq_id: 105 },
+            validator.check(&msg(false, 105, Some(103)))
+        );
+        assert_eq!(
+            SequenceEvent::Gap { missing: 106..=109 },
+            validator.check(&msg(false, 112, Some(109)))
+        );
+        assert_eq!(Some(112), validator.last_seq_id("gate", MarketType::Spot, "BTC_USDT"));
+        assert_eq!(
+            SequenceEvent::Reset { last_seq_id: Some(112) },
+            validator.check(&msg(true, 200, None))
+        );
+        assert_eq!(
+            SequenceEvent::Unsequenced,
+            validator.check(&orderbook_msg("gate", MarketType::Spot, false, None, None))
+        );
+    }
+
+    #[test]
+    fn consecutive_seq_id() {
+        let mut validator = SequenceValidator::new();
+        let msg =
+            |seq_id| orderbook_msg("kraken", MarketType::InverseFuture, false, Some(seq_id), None);
+
+        assert_eq!(SequenceEvent::InOrder, validator.check(&msg(7)));
+        assert_eq!(SequenceEvent::InOrder, validator.check(&msg(8)));
+        assert_eq!(SequenceEvent::Gap { missing: 9..=9 }, validator.check(&msg(10)));
+        // other symbols are tracked separately
+        assert_eq!(
+            SequenceEvent::InOrder,
+            validator.check(&orderbook_msg(
+                "kraken",
+                MarketType::InverseSwap,
+                false,
+                Some(3),
+                None
+            ))
+        );
+        assert_eq!(SequenceEvent::InOrder, validator.check(&msg(11)));
+    }
+}
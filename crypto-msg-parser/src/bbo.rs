@@ -0,0 +1,123 @@
+use std::collections::HashMap;
+
+use crypto_market_type::MarketType;
+use crypto_message::{BboMsg, Order, OrderBook, OrderBookMsg};
+use crypto_msg_type::MessageType;
+
+fn to_bbo(
+    exchange: &str,
+    market_type: MarketType,
+    symbol: &str,
+    pair: String,
+    timestamp: i64,
+    best_ask: &Order,
+    best_bid: &Order,
+) -> BboMsg {
+    BboMsg {
+        exchange: exchange.to_string(),
+        market_type,
+        symbol: symbol.to_string(),
+        pair,
+        msg_type: MessageType::BBO,
+        timestamp,
+        ask_price: best_ask.price,
+        ask_quantity_base: best_ask.quantity_base,
+        ask_quantity_quote: best_ask.quantity_quote,
+        ask_quantity_contract: best_ask.quantity_contract,
+        bid_price: best_bid.price,
+        bid_quantity_base: best_bid.quantity_base,
+        bid_quantity_quote: best_bid.quantity_quote,
+        bid_quantity_contract: best_bid.quantity_contract,
+        bid_price_exact: best_bid.price_exact,
+        bid_quantity_exact: best_bid.quantity_exact,
+        ask_price_exact: best_ask.price_exact,
+        ask_quantity_exact: best_ask.quantity_exact,
+        id: None,
+        json: "".to_string(),
+    }
+}
+
+/// Derive a BBO message from an orderbook snapshot, e.g., `L2TopK` or
+/// `L2Snapshot`.
+///
+/// Quantities are taken from the orders, which parsers already converted with
+/// contract values. Returns `None` for incremental updates and books with an
+/// empty side.
+pub fn derive_bbo(orderbook: &OrderBookMsg) -> Option<BboMsg> {
+    if !orderbook.snapshot {
+        return None;
+    }
+    let best_ask = orderbook.asks.iter().min_by(|a, b| a.price.total_cmp(&b.price))?;
+    let best_bid = orderbook.bids.iter().max_by(|a, b| a.price.total_cmp(&b.price))?;
+
+    let mut bbo = to_bbo(
+        &orderbook.exchange,
+        orderbook.market_type,
+        &orderbook.symbol,
+        orderbook.pair.clone(),
+        orderbook.timestamp,
+        best_ask,
+        best_bid,
+    );
+    bbo.id = orderbook.seq_id;
+    bbo.json = orderbook.json.clone();
+    Some(bbo)
+}
+
+/// Derive a BBO message from a reconstructed orderbook.
+///
+/// Returns `None` if the book is out of sync, has an empty side, or its symbol
+/// can't be normalized.
+pub fn derive_bbo_from_book(book: &OrderBook) -> Option<BboMsg> {
+    if !book.is_synced() {
+        return None;
+    }
+    let pair = crypto_pair::normalize_pair(book.symbol(), book.exchange())?;
+    let mut bbo = to_bbo(
+        book.exchange(),
+        book.market_type(),
+        book.symbol(),
+        pair,
+        book.timestamp(),
+        book.best_ask()?,
+        book.best_bid()?,
+    );
+    bbo.id = book.seq_id();
+    Some(bbo)
+}
+
+/// Derives BBO messages for exchanges without a BBO channel, and emits them
+/// only when the top of book changes.
+#[derive(Clone, Debug, Default)]
+pub struct BboDeriver {
+    // exchange, market_type, symbol -> [bid price, bid quantity, ask price, ask quantity]
+    last: HashMap<(String, MarketType, String), [f64; 4]>,
+}
+
+impl BboDeriver {
+    pub fn new() -> Self {
+        BboDeriver::default()
+    }
+
+    /// Feed an orderbook snapshot, see `derive_bbo()`.
+    pub fn on_orderbook(&mut self, orderbook: &OrderBookMsg) -> Option<BboMsg> {
+        derive_bbo(orderbook).and_then(|bbo| self.dedup(bbo))
+    }
+
+    /// Feed a reconstructed orderbook after applying an update, see
+    /// `derive_bbo_from_book()`.
+    pub fn on_book(&mut self, book: &OrderBook) -> Option<BboMsg> {
+        derive_bbo_from_book(book).and_then(|bbo| self.dedup(bbo))
+    }
+
+    fn dedup(&mut self, bbo: BboMsg) -> Option<BboMsg> {
+        let top = [bbo.bid_price, bbo.bid_quantity_base, bbo.ask_price, bbo.ask_quantity_base];
+        let key = (bbo.exchange.clone(), bbo.market_type, bbo.symbol.clone());
+        if self.last.get(&key) == Some(&top) {
+            None
+        } else {
+            self.last.insert(key, top);
+            Some(bbo)
+        }
+    }
+}
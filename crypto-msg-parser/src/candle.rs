@@ -0,0 +1,73 @@
+use crypto_market_type::MarketType;
+use crypto_message::{CandlePeriod, PeriodUnit};
+
+// A period without a unit, e.g., kraken `5` and zbg `60000`.
+fn parse_bare_number(exchange: &str, market_type: MarketType, count: u32) -> Option<CandlePeriod> {
+    match exchange {
+        "kraken" if market_type == MarketType::Spot => CandlePeriod::new(count, PeriodUnit::Minute),
+        "bybit" | "deribit" => CandlePeriod::new(count, PeriodUnit::Minute),
+        "bitstamp" | "coinbase_pro" | "ftx" => CandlePeriod::new(count, PeriodUnit::Second),
+        "zbg" if market_type != MarketType::Spot => CandlePeriod::from_millis(count as i64),
+        _ => None,
+    }
+}
+
+fn parse_unit(exchange: &str, unit: &str) -> Option<(u32, PeriodUnit)> {
+    // Only zb and zbg write minutes as `M`
+    if unit == "M" {
+        return if exchange == "zb" || exchange == "zbg" {
+            Some((1, PeriodUnit::Minute))
+        } else {
+            Some((1, PeriodUnit::Month))
+        };
+    }
+    if unit == "m" {
+        return Some((1, PeriodUnit::Minute));
+    }
+    let parsed = match unit.to_lowercase().as_str() {
+        "s" | "sec" | "second" | "seconds" => (1, PeriodUnit::Second),
+        "min" | "mins" | "minute" | "minutes" => (1, PeriodUnit::Minute),
+        "h" | "hour" | "hours" => (1, PeriodUnit::Hour),
+        "d" | "day" | "days" => (1, PeriodUnit::Day),
+        "w" | "week" | "weeks" => (1, PeriodUnit::Week),
+        "mon" | "month" | "months" => (1, PeriodUnit::Month),
+        "y" | "year" | "years" => (12, PeriodUnit::Month),
+        _ => return None,
+    };
+    Some(parsed)
+}
+
+/// Parse the candlestick period of an exchange.
+///
+/// Accepts the intervals of all supported exchanges, e.g., binance `1m`, okx
+/// `candle1H`, huobi `1mon`, mexc `Min15`, bithumb `h1`, bitmex `tradeBin1m`
+/// and kraken `5`, which is in minutes. Returns `None` for unknown notations.
+pub fn parse_candle_period(
+    exchange: &str,
+    market_type: MarketType,
+    period: &str,
+) -> Option<CandlePeriod> {
+    let period =
+        period.strip_prefix("candle").or_else(|| period.strip_prefix("tradeBin")).unwrap_or(period);
+    let period = period.strip_suffix("utc").unwrap_or(period);
+    if period.is_empty() || !period.is_ascii() {
+        return None;
+    }
+
+    // The number comes either first, e.g., `15m`, or last, e.g., `Min15`
+    let (digits, unit) = if period.starts_with(|c: char| c.is_ascii_digit()) {
+        let pos = period.find(|c: char| !c.is_ascii_digit()).unwrap_or(period.len());
+        (&period[..pos], &period[pos..])
+    } else {
+        let pos = period.rfind(|c: char| !c.is_ascii_digit()).unwrap() + 1;
+        (&period[pos..], &period[..pos])
+    };
+    let count = if digits.is_empty() { 1 } else { digits.parse::<u32>().ok()? };
+
+    if unit.is_empty() {
+        parse_bare_number(exchange, market_type, count)
+    } else {
+        let (multiplier, unit) = parse_unit(exchange, unit)?;
+        CandlePeriod::new(count.checked_mul(multiplier)?, unit)
+    }
+}
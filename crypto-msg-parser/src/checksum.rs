@@ -0,0 +1,143 @@
+use std::collections::BTreeMap;
+
+use crypto_market_type::MarketType;
+use crypto_message::{OrderBook, Price};
+
+use crate::{ParseError, error::catch_panic, exchanges};
+
+// The number of levels on each side passed to checksum functions.
+const CHECKSUM_LEVELS: usize = 25;
+
+/// Price levels of an orderbook message, with the original strings.
+pub(crate) struct L2Levels {
+    pub snapshot: bool,
+    /// [price, size]
+    pub asks: Vec<[String; 2]>,
+    /// [price, size]
+    pub bids: Vec<[String; 2]>,
+    /// The checksum sent by the exchange
+    pub checksum: Option<u32>,
+    /// The subscribed depth, levels beyond it must be dropped
+    pub depth: Option<usize>,
+}
+
+/// An orderbook which keeps the original price and size strings, to verify
+/// the checksums sent by exchanges.
+///
+/// Supported exchanges:
+///
+/// * OKX v5, CRC32 over the top 25 levels
+/// * Kraken spot, CRC32 over the top 10 levels
+///
+/// A mismatch means the local book diverged from the exchange, e.g., a message
+/// was lost or reordered, the caller should resubscribe to get a new snapshot.
+///
+/// The same messages are parsed into an `OrderBook`, so a verified book can be
+/// queried without maintaining a second one.
+#[derive(Clone, Debug)]
+pub struct ChecksumBook {
+    exchange: String,
+    market_type: MarketType,
+    asks: BTreeMap<Price, [String; 2]>,
+    bids: BTreeMap<Price, [String; 2]>,
+    book: Option<OrderBook>,
+}
+
+impl ChecksumBook {
+    pub fn new(exchange: &str, market_type: MarketType) -> Self {
+        ChecksumBook {
+            exchange: exchange.to_string(),
+            market_type,
+            asks: BTreeMap::new(),
+            bids: BTreeMap::new(),
+            book: None,
+        }
+    }
+
+    /// The orderbook rebuilt from the applied messages, `None` before the first
+    /// one.
+    pub fn book(&self) -> Option<&OrderBook> {
+        self.book.as_ref()
+    }
+
+    /// Apply a raw orderbook message and verify its checksum.
+    ///
+    /// Returns `Some(true)` if the checksum matches the book, `Some(false)` if
+    /// not or if `book()` detected a sequence gap, and `None` if the message
+    /// carries no checksum.
+    pub fn update(&mut self, msg: &str) -> Result<Option<bool>, ParseError> {
+        let ret = catch_panic(|| self.update_inner(msg));
+        ret.map_err(|err| err.with_context(&self.exchange, self.market_type, msg))
+    }
+
+    fn update_inner(&mut self, msg: &str) -> Result<Option<bool>, ParseError> {
+        let levels = match (self.exchange.as_str(), self.market_type) {
+            ("okex" | "okx", _) => exchanges::okx::parse_l2_levels(msg),
+            ("kraken", MarketType::Spot) => exchanges::kraken::parse_l2_levels(msg),
+            _ => Err(ParseError::unsupported(format!(
+                "{} {} doesn't provide orderbook checksums",
+                self.exchange, self.market_type
+            ))),
+        }?;
+
+        if levels.snapshot {
+            self.asks.clear();
+            self.bids.clear();
+        }
+        Self::merge(&mut self.asks, levels.asks)?;
+        Self::merge(&mut self.bids, levels.bids)?;
+        if let Some(depth) = levels.depth {
+            while self.asks.len() > depth {
+                self.asks.pop_last();
+            }
+            while self.bids.len() > depth {
+                self.bids.pop_first();
+            }
+        }
+
+        let mut synced = true;
+        for msg in crate::parse_l2(&self.exchange, self.market_type, msg, None)? {
+            let book = self
+                .book
+                .get_or_insert_with(|| OrderBook::new(&msg.exchange, msg.market_type, &msg.symbol));
+            synced &= book.apply(&msg).is_ok();
+            if let Some(depth) = levels.depth {
+                book.truncate(depth);
+            }
+        }
+
+        if !synced {
+            Ok(Some(false))
+        } else if let Some(expected) = levels.checksum {
+            Ok(Some(self.checksum() == expected))
+        } else {
+            Ok(None)
+        }
+    }
+
+    fn merge(
+        book: &mut BTreeMap<Price, [String; 2]>,
+        levels: Vec<[String; 2]>,
+    ) -> Result<(), ParseError> {
+        for level in levels {
+            let price = Price(level[0].parse::<f64>()?);
+            if level[1].parse::<f64>()? == 0.0 {
+                book.remove(&price);
+            } else {
+                book.insert(price, level);
+            }
+        }
+        Ok(())
+    }
+
+    /// The checksum of the book, computed the same way as the exchange.
+    pub fn checksum(&self) -> u32 {
+        let asks: Vec<&[String; 2]> = self.asks.values().take(CHECKSUM_LEVELS).collect();
+        let bids: Vec<&[String; 2]> = self.bids.values().rev().take(CHECKSUM_LEVELS).collect();
+        match self.exchange.as_str() {
+            "okex" | "okx" => exchanges::okx::calc_checksum(&asks, &bids),
+            "kraken" => exchanges::kraken::calc_checksum(&asks, &bids),
+            _ => 0,
+        }
+    }
+}
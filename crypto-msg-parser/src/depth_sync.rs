@@ -0,0 +1,111 @@
+use std::collections::VecDeque;
+
+use crypto_market_type::MarketType;
+use crypto_message::{OrderBook, OrderBookMsg};
+
+/// The state of a `BinanceDepthSync`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum DepthSyncStatus {
+    /// Diffs are buffered until a snapshot is applied.
+    Buffering,
+    /// The book is consistent with the exchange.
+    Synced,
+    /// A diff was lost or the snapshot is older than the buffered diffs, fetch
+    /// a new snapshot and pass it to `on_snapshot()`.
+    ResyncRequired,
+}
+
+/// Keeps a local Binance orderbook in sync with the `@depth` stream and the
+/// RESTful depth snapshot.
+///
+/// This follows the procedure documented by Binance:
+///
+/// 1. Subscribe to the `@depth` stream and buffer the diffs
+/// 2. Fetch a depth snapshot, parsed by `parse_l2_snapshot()`
+/// 3. Drop buffered diffs with `u <= lastUpdateId`
+/// 4. The first remaining diff must straddle `lastUpdateId`, which is
+///    `U <= lastUpdateId + 1` for spot and `U <= lastUpdateId` for USD-M and
+///    COIN-M futures
+/// 5. Every following diff must continue the previous one, `U` equals the
+///    previous `u + 1` for spot, `pu` equals the previous `u` for futures
+///
+/// Parsed spot diffs carry `U - 1` and futures diffs carry `pu` in
+/// `prev_seq_id`, so both markets are checked the same way.
+///
+/// At most `MAX_BUFFERED_DIFFS` diffs are buffered, when a snapshot doesn't
+/// arrive in time the oldest diffs are dropped and the status becomes
+/// `ResyncRequired`.
+#[derive(Debug)]
+pub struct BinanceDepthSync {
+    book: OrderBook,
+    buffer: VecDeque<OrderBookMsg>,
+    status: DepthSyncStatus,
+}
+
+impl BinanceDepthSync {
+    /// The maximum number of buffered diffs, 100 seconds of a `@depth@100ms`
+    /// stream.
+    pub const MAX_BUFFERED_DIFFS: usize = 1000;
+
+    pub fn new(market_type: MarketType, symbol: &str) -> Self {
+        BinanceDepthSync {
+            book: OrderBook::new("binance", market_type, symbol),
+            buffer: VecDeque::new(),
+            status: DepthSyncStatus::Buffering,
+        }
+    }
+
+    /// Feed a diff parsed by `parse_l2()`.
+    pub fn on_diff(&mut self, diff: OrderBookMsg) -> DepthSyncStatus {
+        debug_assert!(!diff.snapshot);
+        if self.status == DepthSyncStatus::Synced {
+            if self.book.apply(&diff).is_err() {
+                self.buffer.push_back(diff);
+                self.status = DepthSyncStatus::ResyncRequired;
+            }
+        } else {
+            if self.buffer.len() >= Self::MAX_BUFFERED_DIFFS {
+                // the next snapshot is newer than the oldest diffs anyway
+                self.buffer.pop_front();
+                self.status = DepthSyncStatus::ResyncRequired;
+            }
+            self.buffer.push_back(diff);
+        }
+        self.status
+    }
+
+    /// Apply a depth snapshot and the buffered diffs after it.
+    pub fn on_snapshot(&mut self, snapshot: &OrderBookMsg) -> DepthSyncStatus {
+        debug_assert!(snapshot.snapshot);
+        let last_update_id = snapshot.seq_id.unwrap_or_default();
+        self.buffer.retain(|diff| diff.seq_id.is_some_and(|u| u > last_update_id));
+        if let Some(first) = self.buffer.front() {
+            if first.prev_seq_id.is_some_and(|prev_seq_id| prev_seq_id > last_update_id) {
+                // the snapshot is too old
+                self.status = DepthSyncStatus::ResyncRequired;
+                return self.status;
+            }
+        }
+
+        self.status = DepthSyncStatus::Synced;
+        // always Ok for snapshots
+        let _ = self.book.apply(snapshot);
+        while let Some(diff) = self.buffer.pop_front() {
+            if self.book.apply(&diff).is_err() {
+                self.buffer.push_front(diff);
+                self.status = DepthSyncStatus::ResyncRequired;
+                break;
+            }
+        }
+        self.status
+    }
+
+    pub fn status(&self) -> DepthSyncStatus {
+        self.status
+    }
+
+    /// The local orderbook, `None` unless it is in sync.
+    pub fn book(&self) -> Option<&OrderBook> {
+        if self.status == DepthSyncStatus::Synced { Some(&self.book) } else { None }
+    }
+}
@@ -0,0 +1,237 @@
+use std::{
+    fmt,
+    num::{ParseFloatError, ParseIntError},
+    panic::{self, AssertUnwindSafe},
+};
+
+use crypto_market_type::MarketType;
+
+/// Maximum number of characters of the original message kept in an error.
+const EXCERPT_LEN: usize = 256;
+
+/// Where a parse error happened.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ErrorContext {
+    pub exchange: String,
+    pub market_type: MarketType,
+    /// The first few hundred characters of the original message.
+    pub excerpt: String,
+}
+
+impl Default for ErrorContext {
+    fn default() -> Self {
+        ErrorContext {
+            exchange: String::new(),
+            market_type: MarketType::Unknown,
+            excerpt: String::new(),
+        }
+    }
+}
+
+/// The error type returned by all public functions of this crate.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ParseError {
+    /// The exchange is not supported.
+    UnknownExchange { exchange: String },
+    /// The message is not valid JSON, or doesn't have the expected shape.
+    MalformedJson { context: ErrorContext, reason: String },
+    /// The message type is not supported for this exchange and market.
+    UnsupportedMessage { context: ErrorContext, reason: String },
+    /// The exchange-specific symbol could not be normalized to a pair.
+    SymbolNormalization { context: ErrorContext, symbol: String },
+    /// Neither the message nor the caller provided a timestamp.
+    MissingTimestamp { context: ErrorContext },
+}
+
+impl ParseError {
+    pub(crate) fn unknown_exchange(exchange: &str) -> Self {
+        ParseError::UnknownExchange { exchange: exchange.to_string() }
+    }
+
+    pub(crate) fn malformed(reason: impl Into<String>) -> Self {
+        ParseError::MalformedJson { context: ErrorContext::default(), reason: reason.into() }
+    }
+
+    pub(crate) fn unsupported(reason: impl Into<String>) -> Self {
+        ParseError::UnsupportedMessage { context: ErrorContext::default(), reason: reason.into() }
+    }
+
+    pub(crate) fn symbol_normalization(symbol: &str) -> Self {
+        ParseError::SymbolNormalization {
+            context: ErrorContext::default(),
+            symbol: symbol.to_string(),
+        }
+    }
+
+    pub(crate) fn missing_timestamp() -> Self {
+        ParseError::MissingTimestamp { context: ErrorContext::default() }
+    }
+
+    /// Fills in the exchange, market type and message excerpt.
+    ///
+    /// Exchange modules create errors without context, the public functions
+    /// in `lib.rs` attach it before returning.
+    pub(crate) fn with_context(
+        mut self,
+        exchange: &str,
+        market_type: MarketType,
+        msg: &str,
+    ) -> Self {
+        if let Some(context) = self.context_mut() {
+            if context.exchange.is_empty() {
+                context.exchange = exchange.to_string();
+            }
+            if context.market_type == MarketType::Unknown {
+                context.market_type = market_type;
+            }
+            if context.excerpt.is_empty() {
+                context.excerpt = excerpt(msg);
+            }
+        }
+        self
+    }
+
+    /// The context of this error, `None` for `UnknownExchange`.
+    pub fn context(&self) -> Option<&ErrorContext> {
+        match self {
+            ParseError::UnknownExchange { .. } => None,
+            ParseError::MalformedJson { context, .. }
+            | ParseError::UnsupportedMessage { context, .. }
+            | ParseError::SymbolNormalization { context, .. }
+            | ParseError::MissingTimestamp { context } => Some(context),
+        }
+    }
+
+    fn context_mut(&mut self) -> Option<&mut ErrorContext> {
+        match self {
+            ParseError::UnknownExchange { .. } => None,
+            ParseError::MalformedJson { context, .. }
+            | ParseError::UnsupportedMessage { context, .. }
+            | ParseError::SymbolNormalization { context, .. }
+            | ParseError::MissingTimestamp { context } => Some(context),
+        }
+    }
+
+    pub fn exchange(&self) -> &str {
+        match self {
+            ParseError::UnknownExchange { exchange } => exchange,
+            _ => self.context().map(|c| c.exchange.as_str()).unwrap_or_default(),
+        }
+    }
+
+    pub fn market_type(&self) -> MarketType {
+        self.context().map(|c| c.market_type).unwrap_or(MarketType::Unknown)
+    }
+
+    pub fn excerpt(&self) -> &str {
+        self.context().map(|c| c.excerpt.as_str()).unwrap_or_default()
+    }
+}
+
+/// Truncates `msg` to at most `EXCERPT_LEN` characters.
+fn excerpt(msg: &str) -> String {
+    match msg.char_indices().nth(EXCERPT_LEN) {
+        Some((idx, _)) => format!("{}...", &msg[..idx]),
+        None => msg.to_string(),
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseError::UnknownExchange { exchange } => write!(f, "Unknown exchange {exchange}"),
+            ParseError::MalformedJson { context, reason } => write!(
+                f,
+                "Malformed {} {} message, {reason}: {}",
+                context.exchange, context.market_type, context.excerpt
+            ),
+            ParseError::UnsupportedMessage { context, reason } => write!(
+                f,
+                "Unsupported {} {} message, {reason}: {}",
+                context.exchange, context.market_type, context.excerpt
+            ),
+            ParseError::SymbolNormalization { context, symbol } => write!(
+                f,
+                "Failed to normalize {} {} symbol {symbol} from {}",
+                context.exchange, context.market_type, context.excerpt
+            ),
+            ParseError::MissingTimestamp { context } => write!(
+                f,
+                "No timestamp in {} {} message {}",
+                context.exchange, context.market_type, context.excerpt
+            ),
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// Runs `f` and converts a panic into `ParseError::MalformedJson`.
+///
+/// This is a best-effort backstop, not error handling. Exchange modules must
+/// return errors on unexpected input, and `tests/fuzz.rs` fails on every
+/// panic, caught or not. The panic hook still runs, so a caught panic is
+/// printed to stderr, and with `panic = "abort"` the process aborts before
+/// this function can do anything.
+pub(crate) fn catch_panic<T>(f: impl FnOnce() -> Result<T, ParseError>) -> Result<T, ParseError> {
+    panic::catch_unwind(AssertUnwindSafe(f)).unwrap_or_else(|payload| {
+        let reason = if let Some(s) = payload.downcast_ref::<&str>() {
+            s.to_string()
+        } else if let Some(s) = payload.downcast_ref::<String>() {
+            s.clone()
+        } else {
+            "unknown reason".to_string()
+        };
+        Err(ParseError::malformed(format!("panicked while parsing, {reason}")))
+    })
+}
+
+impl From<serde_json::Error> for ParseError {
+    fn from(err: serde_json::Error) -> Self {
+        ParseError::malformed(err.to_string())
+    }
+}
+
+impl From<ParseFloatError> for ParseError {
+    fn from(err: ParseFloatError) -> Self {
+        ParseError::malformed(format!("Failed to parse a number, {err}"))
+    }
+}
+
+impl From<ParseIntError> for ParseError {
+    fn from(err: ParseIntError) -> Self {
+        ParseError::malformed(format!("Failed to parse an integer, {err}"))
+    }
+}
+
+impl From<chrono::ParseError> for ParseError {
+    fn from(err: chrono::ParseError) -> Self {
+        ParseError::malformed(format!("Failed to parse a datetime, {err}"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn excerpt_is_truncated() {
+        let msg = "x".repeat(1000);
+        let err =
+            ParseError::malformed("invalid JSON").with_context("binance", MarketType::Spot, &msg);
+        assert_eq!(err.exchange(), "binance");
+        assert_eq!(err.market_type(), MarketType::Spot);
+        assert_eq!(err.excerpt().len(), EXCERPT_LEN + 3);
+    }
+
+    #[test]
+    fn panic_is_caught() {
+        let ret: Result<(), ParseError> = catch_panic(|| panic!("index out of bounds"));
+        match ret {
+            Err(ParseError::MalformedJson { reason, .. }) => {
+                assert!(reason.contains("index out of bounds"))
+            }
+            _ => panic!("expected MalformedJson"),
+        }
+    }
+}
@@ -2,13 +2,20 @@ use crypto_market_type::MarketType;
 use crypto_msg_type::MessageType;
 
 use crypto_message::{
-    BboMsg, CandlestickMsg, FundingRateMsg, Order, OrderBookMsg, TradeMsg, TradeSide,
+    BboMsg, CandlestickMsg, FundingRateMsg, IndexPriceMsg, LiquidationMsg, MarkPriceMsg,
+    OpenInterestMsg, Order, OrderBookMsg, TickerMsg, TradeMsg, TradeSide,
 };
 
-use super::{super::utils::calc_quantity_and_volume, EXCHANGE_NAME};
+use super::{
+    super::utils::{
+        annualize_funding_rate, calc_open_interest, calc_quantity_and_volume, parse_candle_period,
+        parse_exact,
+    },
+    EXCHANGE_NAME,
+};
+use crate::ParseError;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
-use simple_error::SimpleError;
 use std::collections::HashMap;
 
 // see https://binance-docs.github.io/apidocs/spot/en/#aggregate-trade-streams
@@ -105,32 +112,30 @@ struct RawL2SnapshotLinearMsg {
     extra: HashMap<String, Value>,
 }
 
-pub(super) fn parse_trade(
-    market_type: MarketType,
-    msg: &str,
-) -> Result<Vec<TradeMsg>, SimpleError> {
+pub(super) fn parse_trade(market_type: MarketType, msg: &str) -> Result<Vec<TradeMsg>, ParseError> {
     let obj = serde_json::from_str::<HashMap<String, Value>>(msg)
-        .map_err(|_e| SimpleError::new(format!("{msg} is not a JSON object")))?;
-    let data = obj
-        .get("data")
-        .ok_or_else(|| SimpleError::new(format!("There is no data field in {msg}")))?;
-    let event_type = data["e"].as_str().ok_or_else(|| {
-        SimpleError::new(format!("There is no e field in the data field of {msg}"))
-    })?;
+        .map_err(|_e| ParseError::malformed("not a JSON object"))?;
+    let data = obj.get("data").ok_or_else(|| ParseError::unsupported("There is no data field"))?;
+    let event_type = data["e"]
+        .as_str()
+        .ok_or_else(|| ParseError::unsupported("There is no e field in the data field"))?;
 
     match event_type {
         "aggTrade" => {
-            let agg_trade: AggTradeMsg = serde_json::from_value(data.clone()).map_err(|_e| {
-                SimpleError::new(format!("Failed to deserialize {msg} to AggTradeMsg"))
-            })?;
-            let pair =
-                crypto_pair::normalize_pair(&agg_trade.s, EXCHANGE_NAME).ok_or_else(|| {
-                    SimpleError::new(format!("Failed to normalize {} from {}", agg_trade.s, msg))
-                })?;
-            let price = agg_trade.p.parse::<f64>().unwrap();
-            let quantity = agg_trade.q.parse::<f64>().unwrap();
-            let (quantity_base, quantity_quote, quantity_contract) =
-                calc_quantity_and_volume(EXCHANGE_NAME, market_type, &pair, price, quantity);
+            let agg_trade: AggTradeMsg = serde_json::from_value(data.clone())
+                .map_err(|_e| ParseError::malformed("expected AggTradeMsg"))?;
+            let pair = crypto_pair::normalize_pair(&agg_trade.s, EXCHANGE_NAME)
+                .ok_or_else(|| ParseError::symbol_normalization(&agg_trade.s))?;
+            let price = agg_trade.p.parse::<f64>()?;
+            let quantity = agg_trade.q.parse::<f64>()?;
+            let (quantity_base, quantity_quote, quantity_contract) = calc_quantity_and_volume(
+                EXCHANGE_NAME,
+                market_type,
+                &pair,
+                price,
+                quantity,
+                agg_trade.E,
+            )?;
             let trade = TradeMsg {
                 exchange: EXCHANGE_NAME.to_string(),
                 market_type,
@@ -142,6 +147,8 @@ pub(super) fn parse_trade(
                 quantity_base,
                 quantity_quote,
                 quantity_contract,
+                price_exact: parse_exact(&agg_trade.p),
+                quantity_exact: parse_exact(&agg_trade.q),
                 side: if agg_trade.m { TradeSide::Sell } else { TradeSide::Buy },
                 trade_id: agg_trade.a.to_string(),
                 json: msg.to_string(),
@@ -151,16 +158,20 @@ pub(super) fn parse_trade(
         }
         "trade" => {
             let raw_trade: RawTradeMsg = serde_json::from_value(data.clone()).map_err(|_e| {
-                SimpleError::new(format!("Failed to deserialize {data} to RawTradeMsg"))
+                ParseError::malformed(format!("Failed to deserialize {data} to RawTradeMsg"))
             })?;
-            let pair =
-                crypto_pair::normalize_pair(&raw_trade.s, EXCHANGE_NAME).ok_or_else(|| {
-                    SimpleError::new(format!("Failed to normalize {} from {}", raw_trade.s, msg))
-                })?;
-            let price = raw_trade.p.parse::<f64>().unwrap();
-            let quantity = raw_trade.q.parse::<f64>().unwrap();
-            let (quantity_base, quantity_quote, quantity_contract) =
-                calc_quantity_and_volume(EXCHANGE_NAME, market_type, &pair, price, quantity);
+            let pair = crypto_pair::normalize_pair(&raw_trade.s, EXCHANGE_NAME)
+                .ok_or_else(|| ParseError::symbol_normalization(&raw_trade.s))?;
+            let price = raw_trade.p.parse::<f64>()?;
+            let quantity = raw_trade.q.parse::<f64>()?;
+            let (quantity_base, quantity_quote, quantity_contract) = calc_quantity_and_volume(
+                EXCHANGE_NAME,
+                market_type,
+                &pair,
+                price,
+                quantity,
+                raw_trade.E,
+            )?;
             let trade = TradeMsg {
                 exchange: EXCHANGE_NAME.to_string(),
                 market_type,
@@ -172,6 +183,8 @@ pub(super) fn parse_trade(
                 quantity_base,
                 quantity_quote,
                 quantity_contract,
+                price_exact: parse_exact(&raw_trade.p),
+                quantity_exact: parse_exact(&raw_trade.q),
                 side: if raw_trade.m { TradeSide::Sell } else { TradeSide::Buy },
                 trade_id: raw_trade.t.to_string(),
                 json: msg.to_string(),
@@ -179,31 +192,43 @@ pub(super) fn parse_trade(
 
             Ok(vec![trade])
         }
-        _ => Err(SimpleError::new(format!("Unsupported event type {event_type}"))),
+        _ => Err(ParseError::unsupported(format!("Unsupported event type {event_type}"))),
     }
 }
 
-fn parse_order(market_type: MarketType, pair: &str, raw_order: &RawOrder) -> Order {
-    let price = raw_order[0].parse::<f64>().unwrap();
+fn parse_order(
+    market_type: MarketType,
+    pair: &str,
+    raw_order: &RawOrder,
+    timestamp: i64,
+) -> Result<Order, ParseError> {
+    let price = raw_order[0].parse::<f64>()?;
     let (quantity_base, quantity_quote, quantity_contract) = calc_quantity_and_volume(
         EXCHANGE_NAME,
         market_type,
         pair,
         price,
-        raw_order[1].parse::<f64>().unwrap(),
-    );
-    Order { price, quantity_base, quantity_quote, quantity_contract }
+        raw_order[1].parse::<f64>()?,
+        timestamp,
+    )?;
+    Ok(Order {
+        price,
+        quantity_base,
+        quantity_quote,
+        quantity_contract,
+        price_exact: parse_exact(&raw_order[0]),
+        quantity_exact: parse_exact(&raw_order[1]),
+    })
 }
 
 pub(super) fn parse_l2(
     market_type: MarketType,
     msg: &str,
-) -> Result<Vec<OrderBookMsg>, SimpleError> {
+) -> Result<Vec<OrderBookMsg>, ParseError> {
     let ws_msg =
-        serde_json::from_str::<WebsocketMsg<RawOrderbookMsg>>(msg).map_err(SimpleError::from)?;
-    let pair = crypto_pair::normalize_pair(&ws_msg.data.s, EXCHANGE_NAME).ok_or_else(|| {
-        SimpleError::new(format!("Failed to normalize {} from {}", ws_msg.data.s, msg))
-    })?;
+        serde_json::from_str::<WebsocketMsg<RawOrderbookMsg>>(msg).map_err(ParseError::from)?;
+    let pair = crypto_pair::normalize_pair(&ws_msg.data.s, EXCHANGE_NAME)
+        .ok_or_else(|| ParseError::symbol_normalization(&ws_msg.data.s))?;
 
     let orderbook = OrderBookMsg {
         exchange: EXCHANGE_NAME.to_string(),
@@ -216,20 +241,21 @@ pub(super) fn parse_l2(
         prev_seq_id: if let Some(id) = ws_msg.data.pu {
             if id == -1 { None } else { Some(id as u64) }
         } else {
-            None
+            // spot has no pu, the next diff starts from u + 1
+            ws_msg.data.U.checked_sub(1)
         },
         asks: ws_msg
             .data
             .a
             .iter()
-            .map(|raw_order| -> Order { parse_order(market_type, &pair, raw_order) })
-            .collect::<Vec<Order>>(),
+            .map(|raw_order| parse_order(market_type, &pair, raw_order, ws_msg.data.E))
+            .collect::<Result<Vec<Order>, ParseError>>()?,
         bids: ws_msg
             .data
             .b
             .iter()
-            .map(|raw_order| -> Order { parse_order(market_type, &pair, raw_order) })
-            .collect::<Vec<Order>>(),
+            .map(|raw_order| parse_order(market_type, &pair, raw_order, ws_msg.data.E))
+            .collect::<Result<Vec<Order>, ParseError>>()?,
         snapshot: false,
         json: msg.to_string(),
     };
@@ -239,7 +265,7 @@ pub(super) fn parse_l2(
 pub(super) fn parse_l2_topk(
     market_type: MarketType,
     msg: &str,
-) -> Result<Vec<OrderBookMsg>, SimpleError> {
+) -> Result<Vec<OrderBookMsg>, ParseError> {
     match parse_l2(market_type, msg) {
         Ok(mut orderbooks) => {
             for ob in orderbooks.iter_mut() {
@@ -252,12 +278,11 @@ pub(super) fn parse_l2_topk(
     }
 }
 
-///
 pub(super) fn parse_l2_snapshot(
     market_type: MarketType,
     msg: &str,
     symbol: Option<&str>,
-) -> Result<Vec<OrderBookMsg>, SimpleError> {
+) -> Result<Vec<OrderBookMsg>, ParseError> {
     match market_type {
         MarketType::InverseFuture | MarketType::InverseSwap => {
             parse_l2_snapshot_inverse(market_type, msg)
@@ -266,18 +291,17 @@ pub(super) fn parse_l2_snapshot(
         MarketType::LinearFuture | MarketType::LinearSwap => {
             parse_l2_snapshot_linear(market_type, msg, symbol)
         }
-        _ => Err(SimpleError::new("Not implemented")),
+        _ => Err(ParseError::unsupported("Not implemented")),
     }
 }
 
 pub(super) fn parse_l2_snapshot_inverse(
     market_type: MarketType,
     msg: &str,
-) -> Result<Vec<OrderBookMsg>, SimpleError> {
-    let ws_msg = serde_json::from_str::<RawL2SnapshotInverseMsg>(msg).map_err(SimpleError::from)?;
-    let pair = crypto_pair::normalize_pair(&ws_msg.symbol, EXCHANGE_NAME).ok_or_else(|| {
-        SimpleError::new(format!("Failed to normalize {} from {}", &ws_msg.symbol, msg))
-    })?;
+) -> Result<Vec<OrderBookMsg>, ParseError> {
+    let ws_msg = serde_json::from_str::<RawL2SnapshotInverseMsg>(msg).map_err(ParseError::from)?;
+    let pair = crypto_pair::normalize_pair(&ws_msg.symbol, EXCHANGE_NAME)
+        .ok_or_else(|| ParseError::symbol_normalization(&ws_msg.symbol))?;
 
     let orderbook = OrderBookMsg {
         exchange: EXCHANGE_NAME.to_string(),
@@ -291,13 +315,13 @@ pub(super) fn parse_l2_snapshot_inverse(
         asks: ws_msg
             .asks
             .iter()
-            .map(|raw_order| -> Order { parse_order(market_type, &pair, raw_order) })
-            .collect::<Vec<Order>>(),
+            .map(|raw_order| parse_order(market_type, &pair, raw_order, ws_msg.E))
+            .collect::<Result<Vec<Order>, ParseError>>()?,
         bids: ws_msg
             .bids
             .iter()
-            .map(|raw_order| -> Order { parse_order(market_type, &pair, raw_order) })
-            .collect::<Vec<Order>>(),
+            .map(|raw_order| parse_order(market_type, &pair, raw_order, ws_msg.E))
+            .collect::<Result<Vec<Order>, ParseError>>()?,
         snapshot: true,
         json: msg.to_string(),
     };
@@ -308,16 +332,16 @@ pub(super) fn parse_l2_snapshot_linear(
     market_type: MarketType,
     msg: &str,
     symbol: Option<&str>,
-) -> Result<Vec<OrderBookMsg>, SimpleError> {
-    let ws_msg = serde_json::from_str::<RawL2SnapshotLinearMsg>(msg).map_err(SimpleError::from)?;
-    let pair = crypto_pair::normalize_pair(symbol.unwrap(), EXCHANGE_NAME).ok_or_else(|| {
-        SimpleError::new(format!("Failed to normalize {} from {}", symbol.unwrap(), msg))
-    })?;
+) -> Result<Vec<OrderBookMsg>, ParseError> {
+    let ws_msg = serde_json::from_str::<RawL2SnapshotLinearMsg>(msg).map_err(ParseError::from)?;
+    let symbol = symbol.ok_or_else(|| ParseError::unsupported("symbol is required"))?;
+    let pair = crypto_pair::normalize_pair(symbol, EXCHANGE_NAME)
+        .ok_or_else(|| ParseError::symbol_normalization(symbol))?;
 
     let orderbook = OrderBookMsg {
         exchange: EXCHANGE_NAME.to_string(),
         market_type,
-        symbol: symbol.unwrap().to_string(),
+        symbol: symbol.to_string(),
         pair: pair.clone(),
         msg_type: MessageType::L2Snapshot,
         timestamp: ws_msg.E,
@@ -326,13 +350,13 @@ pub(super) fn parse_l2_snapshot_linear(
         asks: ws_msg
             .asks
             .iter()
-            .map(|raw_order| -> Order { parse_order(market_type, &pair, raw_order) })
-            .collect::<Vec<Order>>(),
+            .map(|raw_order| parse_order(market_type, &pair, raw_order, ws_msg.E))
+            .collect::<Result<Vec<Order>, ParseError>>()?,
         bids: ws_msg
             .bids
             .iter()
-            .map(|raw_order| -> Order { parse_order(market_type, &pair, raw_order) })
-            .collect::<Vec<Order>>(),
+            .map(|raw_order| parse_order(market_type, &pair, raw_order, ws_msg.E))
+            .collect::<Result<Vec<Order>, ParseError>>()?,
         snapshot: true,
         json: msg.to_string(),
     };
@@ -360,32 +384,39 @@ pub(super) fn parse_bbo(
     market_type: MarketType,
     msg: &str,
     received_at: Option<i64>,
-) -> Result<Vec<BboMsg>, SimpleError> {
-    let ws_msg = serde_json::from_str::<WebsocketMsg<RawBboMsg>>(msg).map_err(|_e| {
-        SimpleError::new(format!("Failed to deserialize {msg} to WebsocketMsg<RawBboMsg>"))
-    })?;
-    debug_assert!(ws_msg.stream.ends_with("bookTicker"));
-    let timestamp =
-        if market_type == MarketType::Spot { received_at.unwrap() } else { ws_msg.data.E.unwrap() };
+) -> Result<Vec<BboMsg>, ParseError> {
+    let ws_msg = serde_json::from_str::<WebsocketMsg<RawBboMsg>>(msg)
+        .map_err(|_e| ParseError::malformed("expected WebsocketMsg<RawBboMsg>"))?;
+    if !ws_msg.stream.ends_with("bookTicker") {
+        return Err(ParseError::unsupported(format!("Unsupported stream {}", ws_msg.stream)));
+    }
+    let timestamp = if market_type == MarketType::Spot {
+        received_at.ok_or_else(ParseError::missing_timestamp)?
+    } else {
+        ws_msg.data.E.ok_or_else(ParseError::missing_timestamp)?
+    };
 
     let symbol = ws_msg.data.s.as_str();
-    let pair = crypto_pair::normalize_pair(symbol, EXCHANGE_NAME).unwrap();
+    let pair = crypto_pair::normalize_pair(symbol, EXCHANGE_NAME)
+        .ok_or_else(|| ParseError::symbol_normalization(symbol))?;
 
     let (ask_quantity_base, ask_quantity_quote, ask_quantity_contract) = calc_quantity_and_volume(
         EXCHANGE_NAME,
         market_type,
         &pair,
-        ws_msg.data.a.parse::<f64>().unwrap(),
-        ws_msg.data.A.parse::<f64>().unwrap(),
-    );
+        ws_msg.data.a.parse::<f64>()?,
+        ws_msg.data.A.parse::<f64>()?,
+        timestamp,
+    )?;
 
     let (bid_quantity_base, bid_quantity_quote, bid_quantity_contract) = calc_quantity_and_volume(
         EXCHANGE_NAME,
         market_type,
         &pair,
-        ws_msg.data.b.parse::<f64>().unwrap(),
-        ws_msg.data.B.parse::<f64>().unwrap(),
-    );
+        ws_msg.data.b.parse::<f64>()?,
+        ws_msg.data.B.parse::<f64>()?,
+        timestamp,
+    )?;
 
     let bbo_msg = BboMsg {
         exchange: EXCHANGE_NAME.to_string(),
@@ -394,14 +425,18 @@ pub(super) fn parse_bbo(
         pair,
         msg_type: MessageType::BBO,
         timestamp,
-        ask_price: ws_msg.data.a.parse::<f64>().unwrap(),
+        ask_price: ws_msg.data.a.parse::<f64>()?,
         ask_quantity_base,
         ask_quantity_quote,
         ask_quantity_contract,
-        bid_price: ws_msg.data.b.parse::<f64>().unwrap(),
+        bid_price: ws_msg.data.b.parse::<f64>()?,
         bid_quantity_base,
         bid_quantity_quote,
         bid_quantity_contract,
+        bid_price_exact: parse_exact(&ws_msg.data.b),
+        bid_quantity_exact: parse_exact(&ws_msg.data.B),
+        ask_price_exact: parse_exact(&ws_msg.data.a),
+        ask_quantity_exact: parse_exact(&ws_msg.data.A),
         id: Some(ws_msg.data.u),
         json: msg.to_string(),
     };
@@ -424,49 +459,218 @@ struct RawFundingRateMsg {
     extra: HashMap<String, Value>,
 }
 
+// Both !markPrice@arr and <symbol>@markPrice
+fn parse_mark_price_stream(msg: &str) -> Result<Vec<RawFundingRateMsg>, ParseError> {
+    let obj = serde_json::from_str::<HashMap<String, Value>>(msg)
+        .map_err(|_e| ParseError::malformed("expected HashMap<String, Value>"))?;
+    let stream = obj
+        .get("stream")
+        .and_then(|x| x.as_str())
+        .ok_or_else(|| ParseError::unsupported("There is no stream field"))?;
+    if stream == "!markPrice@arr" {
+        let data = obj.get("data").cloned().unwrap_or_default();
+        Ok(serde_json::from_value::<Vec<RawFundingRateMsg>>(data)?)
+    } else if stream.ends_with("@markPrice") {
+        let data = obj.get("data").cloned().unwrap_or_default();
+        Ok(vec![serde_json::from_value::<RawFundingRateMsg>(data)?])
+    } else {
+        Err(ParseError::unsupported("Unknown mark price message"))
+    }
+}
+
+// The markPrice stream doesn't carry the funding interval, most perpetual
+// swaps settle funding every 8 hours
+const FUNDING_INTERVAL: i64 = 8 * 3600 * 1000;
+
 pub(super) fn parse_funding_rate(
     market_type: MarketType,
     msg: &str,
-) -> Result<Vec<FundingRateMsg>, SimpleError> {
-    let obj = serde_json::from_str::<HashMap<String, Value>>(msg).map_err(|_e| {
-        SimpleError::new(format!("Failed to deserialize {msg} to HashMap<String, Value>"))
-    })?;
-    let stream = obj.get("stream").unwrap().as_str().unwrap();
-    let data = if stream == "!markPrice@arr" {
-        obj.get("data")
-            .unwrap()
-            .as_array()
-            .unwrap()
-            .iter()
-            .map(|x| serde_json::from_value::<RawFundingRateMsg>(x.clone()).unwrap())
-            .collect()
-    } else if stream.ends_with("@markPrice") {
-        vec![serde_json::from_value::<RawFundingRateMsg>(obj.get("data").unwrap().clone()).unwrap()]
-    } else {
-        return Err(SimpleError::new(format!("Unknown funding rate messaeg {msg}")));
-    };
+) -> Result<Vec<FundingRateMsg>, ParseError> {
+    let data = parse_mark_price_stream(msg)?;
     let mut funding_rates: Vec<FundingRateMsg> = data
         .into_iter()
         .filter(|x| !x.r.is_empty())
-        .map(|raw_msg| FundingRateMsg {
-            exchange: EXCHANGE_NAME.to_string(),
-            market_type,
-            symbol: raw_msg.s.clone(),
-            pair: crypto_pair::normalize_pair(&raw_msg.s, EXCHANGE_NAME).unwrap(),
-            msg_type: MessageType::FundingRate,
-            timestamp: raw_msg.E,
-            funding_rate: raw_msg.r.parse::<f64>().unwrap(),
-            funding_time: raw_msg.T,
-            estimated_rate: None,
-            json: serde_json::to_string(&raw_msg).unwrap(),
+        .map(|raw_msg| -> Result<FundingRateMsg, ParseError> {
+            let funding_rate = raw_msg.r.parse::<f64>()?;
+            Ok(FundingRateMsg {
+                exchange: EXCHANGE_NAME.to_string(),
+                market_type,
+                symbol: raw_msg.s.clone(),
+                pair: crypto_pair::normalize_pair(&raw_msg.s, EXCHANGE_NAME)
+                    .ok_or_else(|| ParseError::symbol_normalization(&raw_msg.s))?,
+                msg_type: MessageType::FundingRate,
+                timestamp: raw_msg.E,
+                funding_rate,
+                funding_time: raw_msg.T,
+                estimated_rate: None,
+                funding_interval: FUNDING_INTERVAL,
+                funding_rate_cap: None,
+                funding_rate_floor: None,
+                annualized_rate: annualize_funding_rate(funding_rate, FUNDING_INTERVAL),
+                json: serde_json::to_string(&raw_msg).unwrap(),
+            })
         })
-        .collect();
+        .collect::<Result<Vec<FundingRateMsg>, ParseError>>()?;
     if funding_rates.len() == 1 {
         funding_rates[0].json = msg.to_string();
     }
     Ok(funding_rates)
 }
 
+pub(super) fn parse_mark_price(
+    market_type: MarketType,
+    msg: &str,
+) -> Result<Vec<MarkPriceMsg>, ParseError> {
+    let mut mark_prices = parse_mark_price_stream(msg)?
+        .into_iter()
+        .map(|raw_msg| -> Result<MarkPriceMsg, ParseError> {
+            Ok(MarkPriceMsg {
+                exchange: EXCHANGE_NAME.to_string(),
+                market_type,
+                symbol: raw_msg.s.clone(),
+                pair: crypto_pair::normalize_pair(&raw_msg.s, EXCHANGE_NAME)
+                    .ok_or_else(|| ParseError::symbol_normalization(&raw_msg.s))?,
+                timestamp: raw_msg.E,
+                price: raw_msg.p.parse::<f64>()?,
+                json: serde_json::to_string(&raw_msg).unwrap(),
+            })
+        })
+        .collect::<Result<Vec<MarkPriceMsg>, ParseError>>()?;
+    if mark_prices.len() == 1 {
+        mark_prices[0].json = msg.to_string();
+    }
+    Ok(mark_prices)
+}
+
+pub(super) fn parse_index_price(
+    market_type: MarketType,
+    msg: &str,
+) -> Result<Vec<IndexPriceMsg>, ParseError> {
+    let mut index_prices = parse_mark_price_stream(msg)?
+        .into_iter()
+        .filter(|x| x.i.is_some())
+        .map(|raw_msg| -> Result<IndexPriceMsg, ParseError> {
+            Ok(IndexPriceMsg {
+                exchange: EXCHANGE_NAME.to_string(),
+                market_type,
+                symbol: raw_msg.s.clone(),
+                pair: crypto_pair::normalize_pair(&raw_msg.s, EXCHANGE_NAME)
+                    .ok_or_else(|| ParseError::symbol_normalization(&raw_msg.s))?,
+                timestamp: raw_msg.E,
+                price: raw_msg.i.as_ref().unwrap().parse::<f64>()?,
+                json: serde_json::to_string(&raw_msg).unwrap(),
+            })
+        })
+        .collect::<Result<Vec<IndexPriceMsg>, ParseError>>()?;
+    if index_prices.len() == 1 {
+        index_prices[0].json = msg.to_string();
+    }
+    Ok(index_prices)
+}
+
+// See:
+// * https://binance-docs.github.io/apidocs/futures/en/#liquidation-order-streams
+// * https://binance-docs.github.io/apidocs/delivery/en/#liquidation-order-streams
+#[derive(Serialize, Deserialize)]
+#[allow(non_snake_case)]
+struct RawForceOrder {
+    s: String,  // Symbol
+    S: String,  // Side, SELL means a long position is liquidated
+    q: String,  // Original quantity
+    p: String,  // Price
+    ap: String, // Average price
+    z: String,  // Order filled accumulated quantity
+    T: i64,     // Order trade time
+    #[serde(flatten)]
+    extra: HashMap<String, Value>,
+}
+
+#[derive(Serialize, Deserialize)]
+#[allow(non_snake_case)]
+struct RawForceOrderMsg {
+    e: String, // Event type
+    E: i64,    // Event time
+    o: RawForceOrder,
+}
+
+pub(super) fn parse_liquidation(
+    market_type: MarketType,
+    msg: &str,
+) -> Result<Vec<LiquidationMsg>, ParseError> {
+    let ws_msg = serde_json::from_str::<WebsocketMsg<RawForceOrderMsg>>(msg)
+        .map_err(|_e| ParseError::malformed("expected WebsocketMsg<RawForceOrderMsg>"))?;
+    if !ws_msg.stream.ends_with("forceOrder") && ws_msg.stream != "!forceOrder@arr" {
+        return Err(ParseError::unsupported(format!("Unsupported stream {}", ws_msg.stream)));
+    }
+    let raw_order = ws_msg.data.o;
+    let pair = crypto_pair::normalize_pair(&raw_order.s, EXCHANGE_NAME)
+        .ok_or_else(|| ParseError::symbol_normalization(&raw_order.s))?;
+    // average price and filled quantity are zero until the order is filled
+    let price = match raw_order.ap.parse::<f64>()? {
+        x if x > 0.0 => x,
+        _ => raw_order.p.parse::<f64>()?,
+    };
+    let quantity = match raw_order.z.parse::<f64>()? {
+        x if x > 0.0 => x,
+        _ => raw_order.q.parse::<f64>()?,
+    };
+    let (quantity_base, quantity_quote, quantity_contract) =
+        calc_quantity_and_volume(EXCHANGE_NAME, market_type, &pair, price, quantity, raw_order.T)?;
+
+    Ok(vec![LiquidationMsg {
+        exchange: EXCHANGE_NAME.to_string(),
+        market_type,
+        symbol: raw_order.s.clone(),
+        pair,
+        timestamp: raw_order.T,
+        side: if raw_order.S == "SELL" { TradeSide::Sell } else { TradeSide::Buy },
+        price,
+        quantity_base,
+        quantity_quote,
+        quantity_contract,
+        json: msg.to_string(),
+    }])
+}
+
+// See:
+// * https://binance-docs.github.io/apidocs/futures/en/#open-interest
+// * https://binance-docs.github.io/apidocs/delivery/en/#open-interest
+#[derive(Serialize, Deserialize)]
+#[allow(non_snake_case)]
+struct RawOpenInterestMsg {
+    symbol: String,
+    openInterest: String, // contracts for inverse markets, base coins for linear markets
+    time: i64,
+    #[serde(flatten)]
+    extra: HashMap<String, Value>,
+}
+
+pub(super) fn parse_open_interest(
+    market_type: MarketType,
+    msg: &str,
+) -> Result<Vec<OpenInterestMsg>, ParseError> {
+    let raw_msg = serde_json::from_str::<RawOpenInterestMsg>(msg)
+        .map_err(|_e| ParseError::malformed("expected RawOpenInterestMsg"))?;
+    let pair = crypto_pair::normalize_pair(&raw_msg.symbol, EXCHANGE_NAME)
+        .ok_or_else(|| ParseError::symbol_normalization(&raw_msg.symbol))?;
+    let quantity_contract = raw_msg.openInterest.parse::<f64>()?;
+    let (quantity_base, quantity_quote) =
+        calc_open_interest(EXCHANGE_NAME, market_type, &pair, None, quantity_contract, raw_msg.time)?;
+
+    Ok(vec![OpenInterestMsg {
+        exchange: EXCHANGE_NAME.to_string(),
+        market_type,
+        symbol: raw_msg.symbol,
+        pair,
+        msg_type: MessageType::OpenInterest,
+        timestamp: raw_msg.time,
+        quantity_contract,
+        quantity_base,
+        quantity_quote,
+        json: msg.to_string(),
+    }])
+}
+
 #[derive(Serialize, Deserialize)]
 #[allow(non_snake_case)]
 struct RawKlineMsgWithTime {
@@ -505,25 +709,31 @@ struct RawKlineMsg {
 pub(super) fn parse_candlestick(
     market_type: MarketType,
     msg: &str,
-) -> Result<Vec<CandlestickMsg>, SimpleError> {
-    let obj = serde_json::from_str::<WebsocketMsg<RawKlineMsgWithTime>>(msg)
-        .map_err(SimpleError::from)?;
+) -> Result<Vec<CandlestickMsg>, ParseError> {
+    let obj =
+        serde_json::from_str::<WebsocketMsg<RawKlineMsgWithTime>>(msg).map_err(ParseError::from)?;
 
     let symbol = obj.data.k.s.as_str();
-    let pair = crypto_pair::normalize_pair(symbol, EXCHANGE_NAME).unwrap();
-
-    let v = obj.data.k.v.parse::<f64>().unwrap();
-    let q = obj.data.k.q.parse::<f64>().unwrap();
-    let (volume, quote_volume) = if market_type == MarketType::InverseFuture
-        || market_type == MarketType::InverseSwap
-    {
-        let contract_value =
-            crypto_contract_value::get_contract_value(EXCHANGE_NAME, market_type, &pair).unwrap();
-        let quote_volume = v * contract_value;
-        (q, quote_volume)
-    } else {
-        (v, q)
-    };
+    let pair = crypto_pair::normalize_pair(symbol, EXCHANGE_NAME)
+        .ok_or_else(|| ParseError::symbol_normalization(symbol))?;
+
+    let period = parse_candle_period(EXCHANGE_NAME, market_type, &obj.data.k.i)?;
+    let v = obj.data.k.v.parse::<f64>()?;
+    let q = obj.data.k.q.parse::<f64>()?;
+    let (volume, quote_volume) =
+        if market_type == MarketType::InverseFuture || market_type == MarketType::InverseSwap {
+            let contract_value = crypto_contract_value::get_contract_value_at(
+                EXCHANGE_NAME,
+                market_type,
+                &pair,
+                obj.data.E,
+            )
+            .ok_or_else(|| ParseError::unsupported(format!("No contract value for {pair}")))?;
+            let quote_volume = v * contract_value;
+            (q, quote_volume)
+        } else {
+            (v, q)
+        };
 
     let kline_msg = CandlestickMsg {
         exchange: EXCHANGE_NAME.to_string(),
@@ -532,16 +742,125 @@ pub(super) fn parse_candlestick(
         symbol: symbol.to_string(),
         pair,
         timestamp: obj.data.E,
-        period: obj.data.k.i,
-        begin_time: obj.data.k.t / 1000,
-        open: obj.data.k.o.parse().unwrap(),
-        high: obj.data.k.h.parse().unwrap(),
-        low: obj.data.k.l.parse().unwrap(),
-        close: obj.data.k.c.parse().unwrap(),
+        period,
+        begin_time: obj.data.k.t,
+        end_time: period.end_time(obj.data.k.t),
+        open: obj.data.k.o.parse()?,
+        high: obj.data.k.h.parse()?,
+        low: obj.data.k.l.parse()?,
+        close: obj.data.k.c.parse()?,
         volume,
         quote_volume: Some(quote_volume),
+        is_closed: Some(obj.data.k.x),
         json: msg.to_string(),
     };
 
     Ok(vec![kline_msg])
 }
+
+// See:
+// * https://binance-docs.github.io/apidocs/spot/en/#individual-symbol-ticker-streams
+// * https://binance-docs.github.io/apidocs/futures/en/#individual-symbol-ticker-streams
+// * https://binance-docs.github.io/apidocs/delivery/en/#individual-symbol-ticker-streams
+#[derive(Serialize, Deserialize)]
+#[allow(non_snake_case)]
+struct Raw24hrTickerMsg {
+    e: String,         // Event type
+    E: i64,            // Event time
+    s: String,         // Symbol
+    c: String,         // Last price
+    Q: String,         // Last quantity, in contracts in inverse markets
+    b: Option<String>, // Best bid price, spot only
+    B: Option<String>, // Best bid quantity, spot only
+    a: Option<String>, // Best ask price, spot only
+    A: Option<String>, // Best ask quantity, spot only
+    o: String,         // Open price
+    h: String,         // High price
+    l: String,         // Low price
+    v: String,         // Total traded base asset volume, or contracts in inverse markets
+    q: String,         // Total traded quote asset volume, or base asset volume in inverse markets
+    #[serde(flatten)]
+    extra: HashMap<String, Value>,
+}
+
+pub(super) fn parse_ticker(
+    market_type: MarketType,
+    msg: &str,
+) -> Result<Vec<TickerMsg>, ParseError> {
+    let ws_msg = serde_json::from_str::<WebsocketMsg<Value>>(msg)
+        .map_err(|_e| ParseError::malformed("expected WebsocketMsg<Value>"))?;
+    let raw_tickers = if ws_msg.stream == "!ticker@arr" {
+        serde_json::from_value::<Vec<Raw24hrTickerMsg>>(ws_msg.data)
+            .map_err(|_e| ParseError::malformed("expected Vec<Raw24hrTickerMsg>"))?
+    } else if ws_msg.stream.ends_with("@ticker") {
+        vec![
+            serde_json::from_value::<Raw24hrTickerMsg>(ws_msg.data)
+                .map_err(|_e| ParseError::malformed("expected Raw24hrTickerMsg"))?,
+        ]
+    } else {
+        return Err(ParseError::unsupported("Unknown ticker message"));
+    };
+
+    let parse_f64 = |s: &str| -> Result<f64, ParseError> {
+        s.parse::<f64>().map_err(|_e| ParseError::malformed(format!("Failed to parse {s} as f64")))
+    };
+    let parse_opt_f64 = |s: &Option<String>| -> Result<Option<f64>, ParseError> {
+        s.as_ref().map(|x| parse_f64(x)).transpose()
+    };
+
+    let mut tickers = Vec::with_capacity(raw_tickers.len());
+    for raw_ticker in raw_tickers.into_iter() {
+        let pair = crypto_pair::normalize_pair(&raw_ticker.s, EXCHANGE_NAME)
+            .ok_or_else(|| ParseError::symbol_normalization(&raw_ticker.s))?;
+        let close = parse_f64(&raw_ticker.c)?;
+        let (last_quantity, _, _) = calc_quantity_and_volume(
+            EXCHANGE_NAME,
+            market_type,
+            &pair,
+            close,
+            parse_f64(&raw_ticker.Q)?,
+            raw_ticker.E,
+        )?;
+        let (volume, quote_volume) =
+            if market_type == MarketType::InverseFuture || market_type == MarketType::InverseSwap {
+                let (_, quote_volume, _) = calc_quantity_and_volume(
+                    EXCHANGE_NAME,
+                    market_type,
+                    &pair,
+                    close,
+                    parse_f64(&raw_ticker.v)?,
+                    raw_ticker.E,
+                )?;
+                (parse_f64(&raw_ticker.q)?, quote_volume)
+            } else {
+                (parse_f64(&raw_ticker.v)?, parse_f64(&raw_ticker.q)?)
+            };
+
+        tickers.push(TickerMsg {
+            exchange: EXCHANGE_NAME.to_string(),
+            market_type,
+            symbol: raw_ticker.s.clone(),
+            pair,
+            msg_type: MessageType::Ticker,
+            timestamp: raw_ticker.E,
+            open: parse_f64(&raw_ticker.o)?,
+            high: parse_f64(&raw_ticker.h)?,
+            low: parse_f64(&raw_ticker.l)?,
+            close,
+            volume,
+            quote_volume,
+            last_quantity: Some(last_quantity),
+            best_bid_price: parse_opt_f64(&raw_ticker.b)?,
+            best_bid_quantity: parse_opt_f64(&raw_ticker.B)?,
+            best_ask_price: parse_opt_f64(&raw_ticker.a)?,
+            best_ask_quantity: parse_opt_f64(&raw_ticker.A)?,
+            open_interest: None,
+            open_interest_quote: None,
+            json: serde_json::to_string(&raw_ticker).unwrap(),
+        });
+    }
+    if tickers.len() == 1 {
+        tickers[0].json = msg.to_string();
+    }
+    Ok(tickers)
+}
@@ -1,11 +1,11 @@
 use crypto_market_type::MarketType;
 use crypto_msg_type::MessageType;
 
-use crypto_message::{TradeMsg, TradeSide};
+use crypto_message::{OptionTickerMsg, TradeMsg, TradeSide};
 
+use crate::{ParseError, exchanges::utils::parse_exact};
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
-use simple_error::SimpleError;
 use std::collections::HashMap;
 
 const EXCHANGE_NAME: &str = "binance";
@@ -34,35 +34,54 @@ struct OptionTradeAllMsg {
     t: Vec<OptionTradeMsg>,
 }
 
+// See https://binance-docs.github.io/apidocs/voptions/en/#24-hour-ticker
+#[derive(Serialize, Deserialize)]
+#[allow(non_snake_case)]
+struct RawOptionTickerMsg {
+    e: String,  // Event type, ticker or 24hrTicker
+    E: i64,     // Event time
+    s: String,  // Symbol
+    b: String,  // Implied volatility of the best bid
+    a: String,  // Implied volatility of the best ask
+    d: String,  // Delta
+    t: String,  // Theta
+    g: String,  // Gamma
+    v: String,  // Vega
+    vo: String, // Implied volatility of the mark price
+    #[serde(flatten)]
+    extra: HashMap<String, Value>,
+}
+
 #[derive(Serialize, Deserialize)]
 struct WebsocketMsg<T: Sized> {
     stream: String,
     data: T,
 }
 
-pub(crate) fn parse_trade(msg: &str) -> Result<Vec<TradeMsg>, SimpleError> {
+pub(crate) fn parse_trade(msg: &str) -> Result<Vec<TradeMsg>, ParseError> {
     let obj = serde_json::from_str::<HashMap<String, Value>>(msg)
-        .map_err(|_e| SimpleError::new(format!("{msg} is not a JSON object")))?;
-    let data = obj
-        .get("data")
-        .ok_or_else(|| SimpleError::new(format!("There is no data field in {msg}")))?;
-    let event_type = data["e"].as_str().ok_or_else(|| {
-        SimpleError::new(format!("There is no e field in the data field of {msg}"))
-    })?;
+        .map_err(|_e| ParseError::malformed("not a JSON object"))?;
+    let data = obj.get("data").ok_or_else(|| ParseError::unsupported("There is no data field"))?;
+    let event_type = data["e"]
+        .as_str()
+        .ok_or_else(|| ParseError::unsupported("There is no e field in the data field"))?;
 
-    assert_eq!(event_type, "trade_all");
+    if event_type != "trade_all" {
+        return Err(ParseError::unsupported(format!("Unsupported event type {event_type}")));
+    }
 
     let all_trades: OptionTradeAllMsg = serde_json::from_value(data.clone()).map_err(|_e| {
-        SimpleError::new(format!("Failed to deserialize {data} to OptionTradeAllMsg"))
+        ParseError::malformed(format!("Failed to deserialize {data} to OptionTradeAllMsg"))
     })?;
     let trades: Vec<TradeMsg> = all_trades
         .t
         .into_iter()
-        .map(|trade| {
-            let pair = crypto_pair::normalize_pair(&trade.S, EXCHANGE_NAME).unwrap();
-            let price = trade.p.parse::<f64>().unwrap();
-            let quantity = trade.q.parse::<f64>().unwrap();
-            TradeMsg {
+        .map(|trade| -> Result<TradeMsg, ParseError> {
+            let pair = crypto_pair::normalize_pair(&trade.S, EXCHANGE_NAME)
+                .ok_or_else(|| ParseError::symbol_normalization(&trade.S))?;
+            let price = trade.p.parse::<f64>()?;
+            let quantity = trade.q.parse::<f64>()?;
+            Ok(TradeMsg {
                 exchange: EXCHANGE_NAME.to_string(),
                 market_type: MarketType::EuropeanOption,
                 symbol: trade.S.clone(),
@@ -73,6 +92,8 @@ pub(crate) fn parse_trade(msg: &str) -> Result<Vec<TradeMsg>, SimpleError> {
                 quantity_base: quantity,
                 quantity_quote: price * quantity,
                 quantity_contract: Some(quantity),
+                price_exact: parse_exact(&trade.p),
+                quantity_exact: parse_exact(&trade.q),
                 side: if trade.s == "1" {
                     // TODO: find out the meaning of the field s
                     TradeSide::Sell
@@ -81,8 +102,42 @@ pub(crate) fn parse_trade(msg: &str) -> Result<Vec<TradeMsg>, SimpleError> {
                 },
                 trade_id: trade.a.to_string(),
                 json: serde_json::to_string(&trade).unwrap(),
-            }
+            })
         })
-        .collect();
+        .collect::<Result<Vec<TradeMsg>, ParseError>>()?;
     Ok(trades)
 }
+
+pub(crate) fn parse_option_ticker(msg: &str) -> Result<Vec<OptionTickerMsg>, ParseError> {
+    let ws_msg = serde_json::from_str::<WebsocketMsg<RawOptionTickerMsg>>(msg)
+        .map_err(|_e| ParseError::malformed("expected WebsocketMsg<RawOptionTickerMsg>"))?;
+    let raw_msg = ws_msg.data;
+    if raw_msg.e != "ticker" && raw_msg.e != "24hrTicker" {
+        return Err(ParseError::unsupported(format!("Unsupported event type {}", raw_msg.e)));
+    }
+    let pair = crypto_pair::normalize_pair(&raw_msg.s, EXCHANGE_NAME)
+        .ok_or_else(|| ParseError::symbol_normalization(&raw_msg.s))?;
+    // zero if there is no order on that side
+    let parse_iv = |iv: &str| -> Result<Option<f64>, ParseError> {
+        let iv = iv.parse::<f64>()?;
+        Ok(if iv > 0.0 { Some(iv) } else { None })
+    };
+
+    Ok(vec![OptionTickerMsg {
+        exchange: EXCHANGE_NAME.to_string(),
+        market_type: MarketType::EuropeanOption,
+        symbol: raw_msg.s.clone(),
+        pair,
+        timestamp: raw_msg.E,
+        mark_iv: raw_msg.vo.parse::<f64>()?,
+        bid_iv: parse_iv(&raw_msg.b)?,
+        ask_iv: parse_iv(&raw_msg.a)?,
+        delta: raw_msg.d.parse::<f64>()?,
+        gamma: raw_msg.g.parse::<f64>()?,
+        vega: raw_msg.v.parse::<f64>()?,
+        theta: raw_msg.t.parse::<f64>()?,
+        underlying_price: None,
+        open_interest: None,
+        json: msg.to_string(),
+    }])
+}
@@ -1,12 +1,11 @@
 use crypto_market_type::MarketType;
 use crypto_msg_type::MessageType;
 
-use crate::{Order, OrderBookMsg};
+use crate::{Order, OrderBookMsg, ParseError};
 
-use super::EXCHANGE_NAME;
+use super::{super::utils::parse_exact, EXCHANGE_NAME};
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
-use simple_error::SimpleError;
 use std::collections::HashMap;
 
 /// price, quantity
@@ -44,25 +43,28 @@ struct RawL2SnapshotMsg {
 pub(super) fn parse_l2_topk(
     msg: &str,
     received_at: Option<i64>,
-) -> Result<Vec<OrderBookMsg>, SimpleError> {
-    let ws_msg = serde_json::from_str::<WebsocketMsg<RawL2TopKMsg>>(msg).map_err(|_e| {
-        SimpleError::new(format!("Failed to deserialize {msg} to WebsocketMsg<RawL2TopKMsg>"))
-    })?;
-    debug_assert!(!ws_msg.stream.starts_with('!'));
-    let symbol = ws_msg.stream.as_str().split('@').next().unwrap().to_uppercase();
+) -> Result<Vec<OrderBookMsg>, ParseError> {
+    let ws_msg = serde_json::from_str::<WebsocketMsg<RawL2TopKMsg>>(msg)
+        .map_err(|_e| ParseError::malformed("expected WebsocketMsg<RawL2TopKMsg>"))?;
+    if ws_msg.stream.starts_with('!') {
+        return Err(ParseError::unsupported(format!("Unsupported stream {}", ws_msg.stream)));
+    }
+    let symbol = ws_msg.stream.split('@').next().unwrap_or_default().to_uppercase();
     let pair = crypto_pair::normalize_pair(&symbol, EXCHANGE_NAME)
-        .ok_or_else(|| SimpleError::new(format!("Failed to normalize {} from {}", &symbol, msg)))?;
-    let timestamp = received_at.expect("Binance spot L2TopK doesn't have timestamp");
+        .ok_or_else(|| ParseError::symbol_normalization(&symbol))?;
+    let timestamp = received_at.ok_or_else(ParseError::missing_timestamp)?;
 
-    let parse_order = |raw_order: &RawOrder| -> Order {
-        let price = raw_order[0].parse::<f64>().unwrap();
-        let quantity_base = raw_order[1].parse::<f64>().unwrap();
-        Order {
+    let parse_order = |raw_order: &RawOrder| -> Result<Order, ParseError> {
+        let price = raw_order[0].parse::<f64>()?;
+        let quantity_base = raw_order[1].parse::<f64>()?;
+        Ok(Order {
             price,
             quantity_base,
             quantity_quote: price * quantity_base,
             quantity_contract: None,
-        }
+            price_exact: parse_exact(&raw_order[0]),
+            quantity_exact: parse_exact(&raw_order[1]),
+        })
     };
 
     let orderbook = OrderBookMsg {
@@ -74,8 +76,18 @@ pub(super) fn parse_l2_topk(
         timestamp,
         seq_id: Some(ws_msg.data.lastUpdateId),
         prev_seq_id: None,
-        asks: ws_msg.data.asks.iter().map(parse_order).collect::<Vec<Order>>(),
-        bids: ws_msg.data.bids.iter().map(parse_order).collect::<Vec<Order>>(),
+        asks: ws_msg
+            .data
+            .asks
+            .iter()
+            .map(parse_order)
+            .collect::<Result<Vec<Order>, ParseError>>()?,
+        bids: ws_msg
+            .data
+            .bids
+            .iter()
+            .map(parse_order)
+            .collect::<Result<Vec<Order>, ParseError>>()?,
         snapshot: true,
         json: msg.to_string(),
     };
@@ -87,38 +99,39 @@ pub(super) fn parse_l2_snapshot(
     msg: &str,
     symbol: Option<&str>,
     received_at: Option<i64>,
-) -> Result<Vec<OrderBookMsg>, SimpleError> {
-    let ws_msg = serde_json::from_str::<RawL2SnapshotMsg>(msg).map_err(|_e| {
-        SimpleError::new(format!("Failed to deserialize {msg} to RawL2SnapshotMsg"))
-    })?;
+) -> Result<Vec<OrderBookMsg>, ParseError> {
+    let ws_msg = serde_json::from_str::<RawL2SnapshotMsg>(msg)
+        .map_err(|_e| ParseError::malformed("expected RawL2SnapshotMsg"))?;
 
-    let pair = crypto_pair::normalize_pair(symbol.unwrap(), EXCHANGE_NAME).ok_or_else(|| {
-        SimpleError::new(format!("Failed to normalize {} from {}", symbol.unwrap(), msg))
-    })?;
-    let timestamp = received_at.expect("Binance spot L2 Snapshot doesn't have timestamp");
+    let symbol = symbol.ok_or_else(|| ParseError::unsupported("symbol is required"))?;
+    let pair = crypto_pair::normalize_pair(symbol, EXCHANGE_NAME)
+        .ok_or_else(|| ParseError::symbol_normalization(symbol))?;
+    let timestamp = received_at.ok_or_else(ParseError::missing_timestamp)?;
 
-    let parse_order = |raw_order: &RawOrder| -> Order {
-        let price = raw_order[0].parse::<f64>().unwrap();
-        let quantity_base = raw_order[1].parse::<f64>().unwrap();
-        Order {
+    let parse_order = |raw_order: &RawOrder| -> Result<Order, ParseError> {
+        let price = raw_order[0].parse::<f64>()?;
+        let quantity_base = raw_order[1].parse::<f64>()?;
+        Ok(Order {
             price,
             quantity_base,
             quantity_quote: price * quantity_base,
             quantity_contract: None,
-        }
+            price_exact: parse_exact(&raw_order[0]),
+            quantity_exact: parse_exact(&raw_order[1]),
+        })
     };
 
     let orderbook = OrderBookMsg {
         exchange: EXCHANGE_NAME.to_string(),
         market_type: MarketType::Spot,
-        symbol: symbol.unwrap().to_string(),
+        symbol: symbol.to_string(),
         pair,
         msg_type: MessageType::L2Snapshot,
         timestamp,
         seq_id: Some(ws_msg.lastUpdateId),
         prev_seq_id: None,
-        asks: ws_msg.asks.iter().map(parse_order).collect::<Vec<Order>>(),
-        bids: ws_msg.bids.iter().map(parse_order).collect::<Vec<Order>>(),
+        asks: ws_msg.asks.iter().map(parse_order).collect::<Result<Vec<Order>, ParseError>>()?,
+        bids: ws_msg.bids.iter().map(parse_order).collect::<Result<Vec<Order>, ParseError>>()?,
         snapshot: true,
         json: msg.to_string(),
     };
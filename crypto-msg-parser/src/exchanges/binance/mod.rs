@@ -5,24 +5,24 @@ mod binance_spot;
 use std::collections::HashMap;
 
 use crypto_market_type::MarketType;
-use crypto_message::CandlestickMsg;
+use crypto_message::{
+    CandlestickMsg, IndexPriceMsg, LiquidationMsg, MarkPriceMsg, OpenInterestMsg, OptionTickerMsg,
+};
 use crypto_msg_type::MessageType;
 
-use crate::{BboMsg, FundingRateMsg, OrderBookMsg, TradeMsg};
+use crate::{
+    exchanges::utils::{json_array, json_i64},
+    BboMsg, FundingRateMsg, OrderBookMsg, ParseError, TickerMsg, TradeMsg,
+};
 
 use serde_json::Value;
-use simple_error::SimpleError;
 
 const EXCHANGE_NAME: &str = "binance";
 
-pub(crate) fn extract_symbol(msg: &str) -> Result<String, SimpleError> {
+pub(crate) fn extract_symbol(msg: &str) -> Result<String, ParseError> {
     let obj = serde_json::from_str::<HashMap<String, Value>>(msg)
-        .map_err(|_e| SimpleError::new(format!("Failed to parse the JSON string {msg}")))?;
-    let stream = if obj.contains_key("stream") && obj["stream"].is_string() {
-        obj["stream"].as_str().unwrap().to_string()
-    } else {
-        "".to_string()
-    };
+        .map_err(|_e| ParseError::malformed("invalid JSON"))?;
+    let stream = obj.get("stream").and_then(|x| x.as_str()).unwrap_or_default().to_string();
     if stream.starts_with('!') && stream.ends_with("@arr") {
         // for example: !ticker@arr, !markPrice@arr
         return Ok("ALL".to_string());
@@ -32,16 +32,16 @@ pub(crate) fn extract_symbol(msg: &str) -> Result<String, SimpleError> {
     }
 
     let data = if obj.contains_key("stream") {
-        serde_json::from_value::<HashMap<String, Value>>(obj["data"].clone()).unwrap()
+        serde_json::from_value::<HashMap<String, Value>>(
+            obj.get("data").cloned().unwrap_or_default(),
+        )?
     } else {
         obj
     };
 
-    if data.contains_key("s") && data["s"].is_string() {
-        let symbol = data["s"].as_str().unwrap();
+    if let Some(symbol) = data.get("s").and_then(|x| x.as_str()) {
         Ok(symbol.to_string())
-    } else if data.contains_key("symbol") && data["symbol"].is_string() {
-        let symbol = data["symbol"].as_str().unwrap();
+    } else if let Some(symbol) = data.get("symbol").and_then(|x| x.as_str()) {
         Ok(symbol.to_string())
     } else if stream.contains('@') && !(stream.starts_with('!') && stream.ends_with("@arr")) {
         let symbol = stream.split('@').next().unwrap();
@@ -52,35 +52,47 @@ pub(crate) fn extract_symbol(msg: &str) -> Result<String, SimpleError> {
     {
         Ok("NONE".to_string())
     } else {
-        Err(SimpleError::new(format!("Failed to extract symbol from {msg}")))
+        Err(ParseError::unsupported("Failed to extract symbol"))
     }
 }
 
-pub(crate) fn extract_timestamp(msg: &str) -> Result<Option<i64>, SimpleError> {
-    let obj = serde_json::from_str::<Value>(msg)
-        .map_err(|_e| SimpleError::new(format!("Failed to parse the JSON string {msg}")))?;
+pub(crate) fn extract_timestamp(msg: &str) -> Result<Option<i64>, ParseError> {
+    let obj =
+        serde_json::from_str::<Value>(msg).map_err(|_e| ParseError::malformed("invalid JSON"))?;
     let data = if let Some(data) = obj.get("data") { data } else { &obj };
     if data.is_object() {
         if let Some(e) = data.get("E") {
-            Ok(Some(e.as_i64().unwrap()))
+            Ok(Some(json_i64(e, "E")?))
         } else if let Some(time) = data.get("time") {
-            Ok(Some(time.as_i64().unwrap()))
+            Ok(Some(json_i64(time, "time")?))
         } else {
             Ok(None) // !bookTicker has no E field
         }
     } else if data.is_array() {
-        let timestamp = data.as_array().unwrap().iter().map(|x| x["E"].as_i64().unwrap()).max();
+        let timestamp = json_array(data, "data")?
+            .iter()
+            .map(|x| json_i64(&x["E"], "E"))
+            .collect::<Result<Vec<i64>, ParseError>>()?
+            .into_iter()
+            .max();
         Ok(timestamp)
     } else {
-        Err(SimpleError::new(format!("Failed to extract timestamp from {msg}")))
+        Err(ParseError::missing_timestamp())
     }
 }
 
 pub(crate) fn get_msg_type(msg: &str) -> MessageType {
     if let Ok(obj) = serde_json::from_str::<HashMap<String, Value>>(msg) {
-        if obj.get("stream").is_none() {
-            MessageType::L2Snapshot
-        } else if let Some(stream) = obj.get("stream").unwrap().as_str() {
+        if !obj.contains_key("stream") {
+            if obj.contains_key("lastUpdateId") {
+                MessageType::L2Snapshot
+            } else if obj.contains_key("openInterest") {
+                MessageType::OpenInterest
+            } else {
+                // subscription responses, e.g., {"result":null,"id":1}
+                MessageType::Other
+            }
+        } else if let Some(stream) = obj["stream"].as_str() {
             if stream.ends_with("@aggTrade") {
                 MessageType::Trade
             } else if stream.ends_with("@depth") || stream.ends_with("@depth@100ms") {
@@ -92,7 +104,7 @@ pub(crate) fn get_msg_type(msg: &str) -> MessageType {
                 MessageType::L2TopK
             } else if stream.ends_with("@bookTicker") {
                 MessageType::BBO
-            } else if stream.ends_with("@ticker") {
+            } else if stream.ends_with("@ticker") || stream == "!ticker@arr" {
                 MessageType::Ticker
             } else if stream.contains("@kline_") {
                 MessageType::Candlestick
@@ -109,10 +121,7 @@ pub(crate) fn get_msg_type(msg: &str) -> MessageType {
     }
 }
 
-pub(crate) fn parse_trade(
-    market_type: MarketType,
-    msg: &str,
-) -> Result<Vec<TradeMsg>, SimpleError> {
+pub(crate) fn parse_trade(market_type: MarketType, msg: &str) -> Result<Vec<TradeMsg>, ParseError> {
     if market_type == MarketType::EuropeanOption {
         binance_option::parse_trade(msg)
     } else {
@@ -123,14 +132,58 @@ pub(crate) fn parse_trade(
 pub(crate) fn parse_funding_rate(
     market_type: MarketType,
     msg: &str,
-) -> Result<Vec<FundingRateMsg>, SimpleError> {
+) -> Result<Vec<FundingRateMsg>, ParseError> {
     binance_all::parse_funding_rate(market_type, msg)
 }
 
+pub(crate) fn parse_mark_price(
+    market_type: MarketType,
+    msg: &str,
+) -> Result<Vec<MarkPriceMsg>, ParseError> {
+    if market_type == MarketType::Spot || market_type == MarketType::EuropeanOption {
+        Err(ParseError::unsupported("Only futures and swap markets have mark prices"))
+    } else {
+        binance_all::parse_mark_price(market_type, msg)
+    }
+}
+
+pub(crate) fn parse_index_price(
+    market_type: MarketType,
+    msg: &str,
+) -> Result<Vec<IndexPriceMsg>, ParseError> {
+    if market_type == MarketType::Spot || market_type == MarketType::EuropeanOption {
+        Err(ParseError::unsupported("Only futures and swap markets have index prices"))
+    } else {
+        binance_all::parse_index_price(market_type, msg)
+    }
+}
+
+pub(crate) fn parse_liquidation(
+    market_type: MarketType,
+    msg: &str,
+) -> Result<Vec<LiquidationMsg>, ParseError> {
+    if market_type == MarketType::Spot || market_type == MarketType::EuropeanOption {
+        Err(ParseError::unsupported("Only futures and swap markets have liquidations"))
+    } else {
+        binance_all::parse_liquidation(market_type, msg)
+    }
+}
+
+pub(crate) fn parse_open_interest(
+    market_type: MarketType,
+    msg: &str,
+) -> Result<Vec<OpenInterestMsg>, ParseError> {
+    if market_type == MarketType::Spot || market_type == MarketType::EuropeanOption {
+        Err(ParseError::unsupported("Only futures and swap markets have open interest"))
+    } else {
+        binance_all::parse_open_interest(market_type, msg)
+    }
+}
+
 pub(crate) fn parse_l2(
     market_type: MarketType,
     msg: &str,
-) -> Result<Vec<OrderBookMsg>, SimpleError> {
+) -> Result<Vec<OrderBookMsg>, ParseError> {
     if market_type == MarketType::EuropeanOption {
         Ok(Vec::new())
     } else {
@@ -142,9 +195,9 @@ pub(crate) fn parse_l2_topk(
     market_type: MarketType,
     msg: &str,
     received_at: Option<i64>,
-) -> Result<Vec<OrderBookMsg>, SimpleError> {
+) -> Result<Vec<OrderBookMsg>, ParseError> {
     if market_type == MarketType::EuropeanOption {
-        Err(SimpleError::new("Not implemented"))
+        Err(ParseError::unsupported("Not implemented"))
     } else if market_type == MarketType::Spot {
         binance_spot::parse_l2_topk(msg, received_at)
     } else {
@@ -156,18 +209,33 @@ pub(crate) fn parse_bbo(
     market_type: MarketType,
     msg: &str,
     received_at: Option<i64>,
-) -> Result<Vec<BboMsg>, SimpleError> {
+) -> Result<Vec<BboMsg>, ParseError> {
     if market_type == MarketType::EuropeanOption {
-        Err(SimpleError::new("Not implemented"))
+        Err(ParseError::unsupported("Not implemented"))
     } else {
         binance_all::parse_bbo(market_type, msg, received_at)
     }
 }
 
+pub(crate) fn parse_ticker(
+    market_type: MarketType,
+    msg: &str,
+) -> Result<Vec<TickerMsg>, ParseError> {
+    if market_type == MarketType::EuropeanOption {
+        Err(ParseError::unsupported("Not implemented"))
+    } else {
+        binance_all::parse_ticker(market_type, msg)
+    }
+}
+
+pub(crate) fn parse_option_ticker(msg: &str) -> Result<Vec<OptionTickerMsg>, ParseError> {
+    binance_option::parse_option_ticker(msg)
+}
+
 pub(crate) fn parse_candlestick(
     market_type: MarketType,
     msg: &str,
-) -> Result<Vec<CandlestickMsg>, SimpleError> {
+) -> Result<Vec<CandlestickMsg>, ParseError> {
     binance_all::parse_candlestick(market_type, msg)
 }
 
@@ -176,9 +244,9 @@ pub(crate) fn parse_l2_snapshot(
     msg: &str,
     symbol: Option<&str>,
     received_at: Option<i64>,
-) -> Result<Vec<OrderBookMsg>, SimpleError> {
+) -> Result<Vec<OrderBookMsg>, ParseError> {
     if market_type == MarketType::EuropeanOption {
-        Err(SimpleError::new("Not implemented"))
+        Err(ParseError::unsupported("Not implemented"))
     } else if market_type == MarketType::Spot {
         binance_spot::parse_l2_snapshot(msg, symbol, received_at)
     } else {
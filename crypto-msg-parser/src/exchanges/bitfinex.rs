@@ -1,92 +1,162 @@
 use crate::{
-    exchanges::utils::calc_quantity_and_volume, CandlestickMsg, Order, OrderBookMsg, TradeMsg,
-    TradeSide,
+    CandlestickMsg, FundingRateMsg, L3Action, L3Msg, Order, OrderBookMsg, ParseError, TickerMsg,
+    TradeMsg, TradeSide,
+    exchanges::utils::{
+        annualize_funding_rate, calc_quantity_and_volume, json_array, json_object, json_str,
+        parse_candle_period,
+    },
 };
 use crypto_market_type::MarketType;
+use crypto_message::CandlePeriod;
 use crypto_msg_type::MessageType;
 
 use serde_json::Value;
-use simple_error::SimpleError;
 
 const EXCHANGE_NAME: &str = "bitfinex";
 
-pub(crate) fn extract_symbol(msg: &str) -> Result<String, SimpleError> {
+pub(crate) fn extract_symbol(msg: &str) -> Result<String, ParseError> {
     let arr = serde_json::from_str::<Vec<Value>>(msg)
-        .map_err(|_e| SimpleError::new(format!("Failed to deserialize {msg} to Vec<Value>")))?;
+        .map_err(|_e| ParseError::malformed("expected Vec<Value>"))?;
     if arr.is_empty() {
         return Ok("NONE".to_string());
     }
-    if !arr[0].is_object() {
-        return Ok("NONE".to_string());
-    }
-    let obj = arr[0].as_object().unwrap();
-    let channel = obj["channel"].as_str().unwrap();
+    let obj = match arr[0].as_object() {
+        Some(obj) => obj,
+        None => return Ok("NONE".to_string()),
+    };
+    let channel = obj.get("channel").and_then(|x| x.as_str()).unwrap_or_default();
     if let Some(symbol) = obj.get("symbol") {
-        Ok(symbol.as_str().unwrap().to_string())
+        Ok(json_str(symbol, "symbol")?.to_string())
     } else if channel == "candles" {
-        let key = &(obj["key"].as_str().unwrap())["trade:".len()..];
-        let pos = key.find(':').unwrap();
-        Ok(key[pos + 1..].to_string())
+        let (_, symbol) = split_candle_key(json_str(&arr[0]["key"], "key")?)?;
+        Ok(symbol.to_string())
+    } else if channel == "status" {
+        let key = json_str(&arr[0]["key"], "key")?;
+        Ok(key.strip_prefix("deriv:").unwrap_or(key).to_string())
     } else {
-        Err(SimpleError::new(format!("Failed to extract symbol from {msg}")))
+        Err(ParseError::unsupported("Failed to extract symbol"))
     }
 }
 
-pub(crate) fn extract_timestamp(msg: &str) -> Result<Option<i64>, SimpleError> {
+// Splits `trade:1m:tBTCUSD` into the period and the symbol.
+fn split_candle_key(key: &str) -> Result<(&str, &str), ParseError> {
+    key.strip_prefix("trade:")
+        .and_then(|key| key.split_once(':'))
+        .ok_or_else(|| ParseError::malformed(format!("Invalid candle key {key}")))
+}
+
+pub(crate) fn extract_timestamp(msg: &str) -> Result<Option<i64>, ParseError> {
     let arr = serde_json::from_str::<Vec<Value>>(msg)
-        .map_err(|_e| SimpleError::new(format!("Failed to deserialize {msg} to Vec<Value>")))?;
+        .map_err(|_e| ParseError::malformed("expected Vec<Value>"))?;
     if arr.is_empty() {
         return Ok(None);
     }
-    if !arr[0].is_object() {
-        return Ok(None);
-    }
-    let obj = arr[0].as_object().unwrap();
-    let channel = obj["channel"].as_str().unwrap();
+    let obj = match arr[0].as_object() {
+        Some(obj) => obj,
+        None => return Ok(None),
+    };
+    let channel = obj.get("channel").and_then(|x| x.as_str()).unwrap_or_default();
+    let data = arr.get(1).cloned().unwrap_or_default();
     match channel {
         "trades" => {
             // see https://docs.bitfinex.com/reference#ws-public-trades
-            if arr[1].is_string() {
-                if let Some(timestamp) = arr[2].as_array().unwrap()[1].as_i64() {
+            if data.is_string() {
+                if let Some(timestamp) = arr.get(2).and_then(|x| x.get(1)).and_then(|x| x.as_i64())
+                {
                     Ok(Some(timestamp))
                 } else {
-                    Err(SimpleError::new(format!("Failed to extract timestamp from {msg}")))
+                    Err(ParseError::missing_timestamp())
                 }
-            } else if arr[1].is_array() {
+            } else if data.is_array() {
                 // snapshot
-                let raw_trades: Vec<Vec<f64>> = serde_json::from_value(arr[1].clone()).unwrap();
-                let timestamp = raw_trades.iter().map(|raw_trade| raw_trade[1] as i64).max();
+                let raw_trades: Vec<Vec<f64>> = serde_json::from_value(data)?;
+                let timestamp = raw_trades
+                    .iter()
+                    .map(|raw_trade| raw_trade.get(1).map(|x| *x as i64))
+                    .collect::<Option<Vec<i64>>>()
+                    .ok_or_else(ParseError::missing_timestamp)?
+                    .into_iter()
+                    .max();
                 Ok(timestamp) // Sometimes data can be empty, for example: [{"channel":"trades","symbol":"tBTC:CNHT"}, []]
             } else {
-                Err(SimpleError::new(format!("Failed to extract timestamp from {msg}")))
+                Err(ParseError::missing_timestamp())
             }
         }
         "candles" => {
-            if let Ok(arr_2d) = serde_json::from_value::<Vec<Vec<f64>>>(arr[1].clone()) {
-                let timestamp = arr_2d.iter().map(|v| v[0] as i64).max();
+            if let Ok(arr_2d) = serde_json::from_value::<Vec<Vec<f64>>>(data.clone()) {
+                let timestamp = arr_2d.iter().filter_map(|v| v.first()).map(|x| *x as i64).max();
                 Ok(timestamp)
             } else {
-                let arr: Vec<f64> = serde_json::from_value(arr[1].clone()).unwrap();
-                Ok(Some(arr[0] as i64))
+                let arr: Vec<f64> = serde_json::from_value(data)?;
+                Ok(arr.first().map(|x| *x as i64))
             }
         }
         "book" | "ticker" => Ok(None),
-        _ => Err(SimpleError::new(format!("Failed to extract timestamp from {msg}"))),
+        "status" => Ok(data.get(0).and_then(|x| x.as_i64())),
+        _ => Err(ParseError::missing_timestamp()),
     }
 }
 
-fn parse_one_trade(market_type: MarketType, symbol: &str, nums: &[f64]) -> TradeMsg {
-    assert_eq!(4, nums.len());
-    let pair = crypto_pair::normalize_pair(symbol, EXCHANGE_NAME).unwrap();
+pub(crate) fn get_msg_type(msg: &str) -> MessageType {
+    if let Ok(arr) = serde_json::from_str::<Vec<Value>>(msg) {
+        if let Some(obj) = arr.first().and_then(|x| x.as_object()) {
+            match obj.get("channel").and_then(|x| x.as_str()).unwrap_or_default() {
+                "trades" => MessageType::Trade,
+                // raw books carry individual orders
+                "book" if obj.get("prec").and_then(|x| x.as_str()) == Some("R0") => {
+                    MessageType::L3Event
+                }
+                "book" => MessageType::L2Event,
+                "ticker" => MessageType::Ticker,
+                "candles" => MessageType::Candlestick,
+                "status"
+                    if obj
+                        .get("key")
+                        .and_then(|x| x.as_str())
+                        .is_some_and(|x| x.starts_with("deriv:")) =>
+                {
+                    MessageType::FundingRate
+                }
+                _ => MessageType::Other,
+            }
+        } else if !arr.is_empty() && serde_json::from_str::<Vec<[f64; 3]>>(msg).is_ok() {
+            // RESTful book snapshot, e.g., [[68361,2,0.17328582],[68360,1,0.65244918]]
+            MessageType::L2Snapshot
+        } else if arr
+            .first()
+            .and_then(|row| row.get(0))
+            .and_then(|x| x.as_str())
+            .is_some_and(|x| x.contains("F0:"))
+        {
+            // RESTful derivatives status, e.g., [["tBTCF0:USTF0",1654162706000,...]]
+            MessageType::FundingRate
+        } else {
+            MessageType::Other
+        }
+    } else {
+        MessageType::Other
+    }
+}
+
+fn parse_one_trade(
+    market_type: MarketType,
+    symbol: &str,
+    nums: &[f64],
+) -> Result<TradeMsg, ParseError> {
+    if nums.len() != 4 {
+        return Err(ParseError::malformed("A trade should have 4 numbers"));
+    }
+    let pair = crypto_pair::normalize_pair(symbol, EXCHANGE_NAME)
+        .ok_or_else(|| ParseError::symbol_normalization(symbol))?;
     let trade_id = nums[0] as i64;
     let timestamp = nums[1] as i64;
     let quantity = f64::abs(nums[2]);
     let price = nums[3];
 
     let (quantity_base, quantity_quote, quantity_contract) =
-        calc_quantity_and_volume(EXCHANGE_NAME, market_type, &pair, price, quantity);
+        calc_quantity_and_volume(EXCHANGE_NAME, market_type, &pair, price, quantity, timestamp)?;
 
-    TradeMsg {
+    Ok(TradeMsg {
         exchange: EXCHANGE_NAME.to_string(),
         market_type,
         symbol: symbol.to_string(),
@@ -97,40 +167,45 @@ fn parse_one_trade(market_type: MarketType, symbol: &str, nums: &[f64]) -> Trade
         quantity_base,
         quantity_quote,
         quantity_contract,
+        price_exact: None,
+        quantity_exact: None,
         side: if quantity < 0.0 { TradeSide::Sell } else { TradeSide::Buy },
         trade_id: trade_id.to_string(),
         json: serde_json::to_string(&nums).unwrap(),
-    }
+    })
 }
 
 // See <https://docs.bitfinex.com/reference/ws-public-trades>
-pub(crate) fn parse_trade(
-    market_type: MarketType,
-    msg: &str,
-) -> Result<Vec<TradeMsg>, SimpleError> {
+pub(crate) fn parse_trade(market_type: MarketType, msg: &str) -> Result<Vec<TradeMsg>, ParseError> {
     let arr = serde_json::from_str::<Vec<Value>>(msg)
-        .map_err(|_e| SimpleError::new(format!("Failed to deserialize {msg} to Vec<Value>")))?;
-    let obj = arr[0].as_object().unwrap();
+        .map_err(|_e| ParseError::malformed("expected Vec<Value>"))?;
+    if arr.len() < 2 {
+        return Err(ParseError::malformed("expected [channel, data]"));
+    }
+    let obj = json_object(&arr[0], "channel")?;
     let symbol = if let Some(symbol) = obj.get("symbol") {
-        symbol.as_str().unwrap()
+        json_str(symbol, "symbol")?
     } else {
-        return Err(SimpleError::new(format!("Failed to extract symbol from {msg}")));
+        return Err(ParseError::unsupported("Failed to extract symbol"));
     };
 
     // see https://docs.bitfinex.com/reference#ws-public-trades
     match arr[1].as_str() {
+        Some("hb") => Ok(Vec::new()),
         Some(_) => {
             // te, tu
-            let nums: Vec<f64> = serde_json::from_value(arr[2].clone()).unwrap();
-            let mut trade = parse_one_trade(market_type, symbol, &nums);
+            let nums: Vec<f64> = serde_json::from_value(arr.get(2).cloned().unwrap_or_default())?;
+            let mut trade = parse_one_trade(market_type, symbol, &nums)?;
             trade.json = msg.to_string();
             Ok(vec![trade])
         }
         None => {
             // snapshot
-            let nums_arr: Vec<Vec<f64>> = serde_json::from_value(arr[1].clone()).unwrap();
-            let mut trades: Vec<TradeMsg> =
-                nums_arr.iter().map(|nums| parse_one_trade(market_type, symbol, nums)).collect();
+            let nums_arr: Vec<Vec<f64>> = serde_json::from_value(arr[1].clone())?;
+            let mut trades = nums_arr
+                .iter()
+                .map(|nums| parse_one_trade(market_type, symbol, nums))
+                .collect::<Result<Vec<TradeMsg>, ParseError>>()?;
             if trades.len() == 1 {
                 trades[0].json = msg.to_string();
             }
@@ -144,33 +219,49 @@ pub(crate) fn parse_l2(
     market_type: MarketType,
     msg: &str,
     timestamp: i64,
-) -> Result<Vec<OrderBookMsg>, SimpleError> {
+) -> Result<Vec<OrderBookMsg>, ParseError> {
     let ws_msg = serde_json::from_str::<Vec<Value>>(msg)
-        .map_err(|_e| SimpleError::new(format!("Failed to deserialize {msg} to Vec<Value>")))?;
+        .map_err(|_e| ParseError::malformed("expected Vec<Value>"))?;
 
-    let symbol = ws_msg[0].as_object().unwrap()["symbol"].as_str().unwrap();
+    if ws_msg.len() < 2 {
+        return Err(ParseError::malformed("expected [channel, data]"));
+    }
+    let symbol = json_str(&ws_msg[0]["symbol"], "symbol")?;
     let pair = crypto_pair::normalize_pair(symbol, EXCHANGE_NAME)
-        .ok_or_else(|| SimpleError::new(format!("Failed to normalize {symbol} from {msg}")))?;
+        .ok_or_else(|| ParseError::symbol_normalization(symbol))?;
 
     let data = ws_msg[1].clone();
-    if data.as_array().unwrap().is_empty() {
+    if data.is_string() {
+        // heartbeat
         return Ok(vec![]);
     }
-
-    let snapshot = {
-        let arr = data.as_array().unwrap();
-        arr[0].is_array()
+    let snapshot = match json_array(&data, "data")?.first() {
+        Some(first) => first.is_array(),
+        None => return Ok(vec![]),
     };
 
-    let parse_order = |x: &[f64; 3]| -> Order {
+    let parse_order = |x: &[f64; 3]| -> Result<Order, ParseError> {
         let price = x[0];
         // delete price level if count = 0
         let quantity = if (x[1] as i32) == 0 { 0.0 } else { f64::abs(x[2]) };
 
-        let (quantity_base, quantity_quote, quantity_contract) =
-            calc_quantity_and_volume(EXCHANGE_NAME, market_type, &pair, price, quantity);
-
-        Order { price, quantity_base, quantity_quote, quantity_contract }
+        let (quantity_base, quantity_quote, quantity_contract) = calc_quantity_and_volume(
+            EXCHANGE_NAME,
+            market_type,
+            &pair,
+            price,
+            quantity,
+            timestamp,
+        )?;
+
+        Ok(Order {
+            price,
+            quantity_base,
+            quantity_quote,
+            quantity_contract,
+            price_exact: None,
+            quantity_exact: None,
+        })
     };
 
     let mut orderbook = OrderBookMsg {
@@ -190,14 +281,14 @@ pub(crate) fn parse_l2(
 
     let raw_orders = if snapshot {
         // snapshot
-        serde_json::from_value::<Vec<[f64; 3]>>(data).unwrap()
+        serde_json::from_value::<Vec<[f64; 3]>>(data)?
     } else {
         // update
-        let raw_order = serde_json::from_value::<[f64; 3]>(data).unwrap();
+        let raw_order = serde_json::from_value::<[f64; 3]>(data)?;
         vec![raw_order]
     };
     for raw_order in raw_orders.iter() {
-        let order = parse_order(raw_order);
+        let order = parse_order(raw_order)?;
         if raw_order[2] > 0.0 {
             orderbook.bids.push(order);
         } else {
@@ -208,6 +299,81 @@ pub(crate) fn parse_l2(
     Ok(vec![orderbook])
 }
 
+// See <https://docs.bitfinex.com/reference/ws-public-raw-books>
+pub(crate) fn parse_l3(
+    market_type: MarketType,
+    msg: &str,
+    timestamp: i64,
+) -> Result<Vec<L3Msg>, ParseError> {
+    let ws_msg = serde_json::from_str::<Vec<Value>>(msg)
+        .map_err(|_e| ParseError::malformed("expected Vec<Value>"))?;
+
+    if ws_msg.len() < 2 {
+        return Err(ParseError::malformed("expected [channel, data]"));
+    }
+    let data = ws_msg[1].clone();
+    // heartbeat, the channel can be a chanId instead of the subscription
+    if data.as_str() == Some("hb") {
+        return Ok(vec![]);
+    }
+    let symbol = json_str(&ws_msg[0]["symbol"], "symbol")?;
+    let pair = crypto_pair::normalize_pair(symbol, EXCHANGE_NAME)
+        .ok_or_else(|| ParseError::symbol_normalization(symbol))?;
+
+    let arr = json_array(&data, "data")?;
+    if arr.is_empty() {
+        return Ok(vec![]);
+    }
+    let snapshot = arr[0].is_array();
+
+    // [ORDER_ID, PRICE, AMOUNT], order IDs may exceed the precision of f64
+    let raw_orders = if snapshot {
+        serde_json::from_value::<Vec<(u64, f64, f64)>>(data)?
+    } else {
+        vec![serde_json::from_value::<(u64, f64, f64)>(data)?]
+    };
+    let mut l3_msgs = raw_orders
+        .iter()
+        .map(|raw_order| {
+            let (order_id, price, amount) = *raw_order;
+            // delete the order if price = 0
+            let (action, quantity) =
+                if price == 0.0 { (L3Action::Done, 0.0) } else { (L3Action::Open, amount.abs()) };
+            let (quantity_base, quantity_quote, quantity_contract) = calc_quantity_and_volume(
+                EXCHANGE_NAME,
+                market_type,
+                &pair,
+                price,
+                quantity,
+                timestamp,
+            )?;
+
+            Ok(L3Msg {
+                exchange: EXCHANGE_NAME.to_string(),
+                market_type,
+                symbol: symbol.to_string(),
+                pair: pair.clone(),
+                msg_type: if snapshot { MessageType::L3Snapshot } else { MessageType::L3Event },
+                timestamp,
+                order_id: order_id.to_string(),
+                action,
+                side: Some(if amount > 0.0 { TradeSide::Buy } else { TradeSide::Sell }),
+                price: if price == 0.0 { None } else { Some(price) },
+                quantity_base,
+                quantity_quote,
+                quantity_contract,
+                seq_id: None,
+                json: serde_json::to_string(raw_order)?,
+            })
+        })
+        .collect::<Result<Vec<L3Msg>, ParseError>>()?;
+    if l3_msgs.len() == 1 {
+        l3_msgs[0].json = msg.to_string();
+    }
+
+    Ok(l3_msgs)
+}
+
 // See https://docs.bitfinex.com/reference/rest-public-book
 // See https://api-pub.bitfinex.com/v2/book/{symbol}/{precision}
 // request example https://api-pub.bitfinex.com/v2/book/tBTCUSD/P0?len=100
@@ -218,32 +384,48 @@ pub(crate) fn parse_l2_snapshot(
     msg: &str,
     symbol: Option<&str>,
     received_at: Option<i64>,
-) -> Result<Vec<OrderBookMsg>, SimpleError> {
-    let raw_orders = serde_json::from_str::<Vec<[Value; 3]>>(msg).map_err(|_e| {
-        SimpleError::new(format!("Failed to deserialize {msg} to Vec<[Value;3]]>"))
-    })?;
+) -> Result<Vec<OrderBookMsg>, ParseError> {
+    let raw_orders = serde_json::from_str::<Vec<[Value; 3]>>(msg)
+        .map_err(|_e| ParseError::malformed("expected Vec<[Value;3]]>"))?;
 
-    let symbol = symbol.unwrap().to_string();
+    let symbol = symbol
+        .ok_or_else(|| ParseError::unsupported("A symbol is required for snapshot messages"))?
+        .to_string();
     let pair = crypto_pair::normalize_pair(&symbol, EXCHANGE_NAME)
-        .ok_or_else(|| SimpleError::new(format!("Failed to normalize {symbol} from {msg}")))?;
+        .ok_or_else(|| ParseError::symbol_normalization(&symbol))?;
+    let timestamp = received_at.ok_or_else(ParseError::missing_timestamp)?;
 
-    let parse_order = |x: &[Value; 3]| -> Order {
-        let price = x[0].as_f64().unwrap();
+    let parse_order = |x: &[Value; 3]| -> Result<Order, ParseError> {
+        let price = x[0].as_f64().ok_or_else(|| ParseError::malformed("price is not a number"))?;
         // delete price level if count = 0
-        let quantity = x[2].as_f64().unwrap().abs();
-
-        let (quantity_base, quantity_quote, quantity_contract) =
-            calc_quantity_and_volume(EXCHANGE_NAME, market_type, &pair, price, quantity);
-
-        Order { price, quantity_base, quantity_quote, quantity_contract }
+        let quantity =
+            x[2].as_f64().ok_or_else(|| ParseError::malformed("amount is not a number"))?.abs();
+
+        let (quantity_base, quantity_quote, quantity_contract) = calc_quantity_and_volume(
+            EXCHANGE_NAME,
+            market_type,
+            &pair,
+            price,
+            quantity,
+            timestamp,
+        )?;
+
+        Ok(Order {
+            price,
+            quantity_base,
+            quantity_quote,
+            quantity_contract,
+            price_exact: None,
+            quantity_exact: None,
+        })
     };
 
     let mut asks = Vec::new();
     let mut bids = Vec::new();
 
     for raw_order in raw_orders.iter() {
-        let order = parse_order(raw_order);
-        if raw_order[2].as_f64().unwrap() > 0.0 {
+        let order = parse_order(raw_order)?;
+        if raw_order[2].as_f64().unwrap_or_default() > 0.0 {
             bids.push(order);
         } else {
             asks.push(order);
@@ -256,7 +438,7 @@ pub(crate) fn parse_l2_snapshot(
         symbol,
         pair: pair.clone(),
         msg_type: MessageType::L2Snapshot,
-        timestamp: received_at.unwrap(),
+        timestamp,
         seq_id: None,
         prev_seq_id: None,
         asks: asks.clone(),
@@ -268,11 +450,157 @@ pub(crate) fn parse_l2_snapshot(
     Ok(vec![orderbook])
 }
 
+/// See <https://docs.bitfinex.com/reference/ws-public-ticker>
+///
+/// Ticker messages have no timestamp, so `received_at` is required.
+pub(crate) fn parse_ticker(
+    market_type: MarketType,
+    msg: &str,
+    received_at: Option<i64>,
+) -> Result<Vec<TickerMsg>, ParseError> {
+    let ws_msg = serde_json::from_str::<Vec<Value>>(msg).map_err(ParseError::from)?;
+    if ws_msg.len() != 2 {
+        return Err(ParseError::unsupported("Invalid ticker message"));
+    }
+    if ws_msg[1].is_string() {
+        // heartbeat
+        return Ok(Vec::new());
+    }
+    let symbol = ws_msg[0]
+        .get("symbol")
+        .and_then(|x| x.as_str())
+        .ok_or_else(|| ParseError::unsupported("Failed to extract symbol"))?;
+    let pair = crypto_pair::normalize_pair(symbol, EXCHANGE_NAME)
+        .ok_or_else(|| ParseError::symbol_normalization(symbol))?;
+    let timestamp = received_at.ok_or_else(ParseError::missing_timestamp)?;
+
+    // [BID, BID_SIZE, ASK, ASK_SIZE, DAILY_CHANGE, DAILY_CHANGE_RELATIVE, LAST_PRICE, VOLUME,
+    // HIGH, LOW]
+    let nums = serde_json::from_value::<[f64; 10]>(ws_msg[1].clone())
+        .map_err(|_e| ParseError::malformed("expected [f64; 10]"))?;
+    let close = nums[6];
+    let (volume, quote_volume, _) =
+        calc_quantity_and_volume(EXCHANGE_NAME, market_type, &pair, close, nums[7], timestamp)?;
+    let (best_bid_quantity, _, _) =
+        calc_quantity_and_volume(EXCHANGE_NAME, market_type, &pair, nums[0], nums[1], timestamp)?;
+    let (best_ask_quantity, _, _) =
+        calc_quantity_and_volume(EXCHANGE_NAME, market_type, &pair, nums[2], nums[3], timestamp)?;
+
+    let ticker_msg = TickerMsg {
+        exchange: EXCHANGE_NAME.to_string(),
+        market_type,
+        symbol: symbol.to_string(),
+        pair,
+        msg_type: MessageType::Ticker,
+        timestamp,
+        open: close - nums[4],
+        high: nums[8],
+        low: nums[9],
+        close,
+        volume,
+        // Bitfinex doesn't provide quote volume, so it is approximated by the last price
+        quote_volume,
+        last_quantity: None,
+        best_bid_price: Some(nums[0]),
+        best_bid_quantity: Some(best_bid_quantity),
+        best_ask_price: Some(nums[2]),
+        best_ask_quantity: Some(best_ask_quantity),
+        open_interest: None,
+        open_interest_quote: None,
+        json: msg.to_string(),
+    };
+
+    Ok(vec![ticker_msg])
+}
+
+// Derivatives settle funding every 8 hours
+const FUNDING_INTERVAL: i64 = 8 * 3600 * 1000;
+
+fn parse_one_status(
+    market_type: MarketType,
+    symbol: &str,
+    fields: &[Value],
+) -> Result<FundingRateMsg, ParseError> {
+    // [MTS, _, DERIV_PRICE, SPOT_PRICE, _, INSURANCE_FUND_BALANCE, _, NEXT_FUNDING_EVT_MTS,
+    // NEXT_FUNDING_ACCRUED, NEXT_FUNDING_STEP, _, CURRENT_FUNDING, ...]
+    let get_field = |index: usize| {
+        fields
+            .get(index)
+            .filter(|x| !x.is_null())
+            .ok_or_else(|| ParseError::malformed(format!("status field {index} is missing")))
+    };
+    let to_f64 = |v: &Value| v.as_f64().ok_or_else(|| ParseError::malformed("expected f64"));
+    let to_i64 = |v: &Value| v.as_i64().ok_or_else(|| ParseError::malformed("expected i64"));
+    let pair = crypto_pair::normalize_pair(symbol, EXCHANGE_NAME)
+        .ok_or_else(|| ParseError::symbol_normalization(symbol))?;
+    let funding_rate = to_f64(get_field(11)?)?;
+
+    Ok(FundingRateMsg {
+        exchange: EXCHANGE_NAME.to_string(),
+        market_type,
+        symbol: symbol.to_string(),
+        pair,
+        msg_type: MessageType::FundingRate,
+        timestamp: to_i64(get_field(0)?)?,
+        funding_rate,
+        funding_time: to_i64(get_field(7)?)?,
+        estimated_rate: Some(to_f64(get_field(8)?)?),
+        funding_interval: FUNDING_INTERVAL,
+        funding_rate_cap: None,
+        funding_rate_floor: None,
+        annualized_rate: annualize_funding_rate(funding_rate, FUNDING_INTERVAL),
+        json: serde_json::to_string(fields).unwrap(),
+    })
+}
+
+/// See <https://docs.bitfinex.com/reference/ws-public-status> and
+/// <https://docs.bitfinex.com/reference/rest-public-derivatives-status>
+///
+/// `funding_rate` is `CURRENT_FUNDING`, and `estimated_rate` is
+/// `NEXT_FUNDING_ACCRUED`, the funding accrued so far for the next period.
+pub(crate) fn parse_funding_rate(
+    market_type: MarketType,
+    msg: &str,
+) -> Result<Vec<FundingRateMsg>, ParseError> {
+    let arr = serde_json::from_str::<Vec<Value>>(msg)
+        .map_err(|_e| ParseError::malformed("expected Vec<Value>"))?;
+    let mut rates = if let Some(obj) = arr.first().and_then(|x| x.as_object()) {
+        // websocket, e.g., [{"channel":"status","key":"deriv:tBTCF0:USTF0"},[...]]
+        let symbol = obj
+            .get("key")
+            .and_then(|x| x.as_str())
+            .and_then(|x| x.strip_prefix("deriv:"))
+            .ok_or_else(|| ParseError::unsupported("Failed to extract symbol"))?;
+        match arr.get(1) {
+            // heartbeat
+            Some(Value::String(_)) => Vec::new(),
+            Some(Value::Array(fields)) => vec![parse_one_status(market_type, symbol, fields)?],
+            _ => return Err(ParseError::malformed("Invalid status message")),
+        }
+    } else {
+        // RESTful, each row starts with the key, e.g., [["tBTCF0:USTF0",...]]
+        arr.iter()
+            .map(|row| {
+                let row = row.as_array().ok_or_else(|| ParseError::malformed("expected array"))?;
+                let symbol = row
+                    .first()
+                    .and_then(|x| x.as_str())
+                    .ok_or_else(|| ParseError::unsupported("Failed to extract symbol"))?;
+                parse_one_status(market_type, symbol, &row[1..])
+            })
+            .collect::<Result<Vec<FundingRateMsg>, ParseError>>()?
+    };
+    if rates.len() == 1 {
+        rates[0].json = msg.to_string();
+    }
+    Ok(rates)
+}
+
 fn parse_one_candle(
     market_type: MarketType,
     symbol: &str,
     pair: &str,
-    period: &str,
+    period: CandlePeriod,
     nums: &[f64; 6],
 ) -> CandlestickMsg {
     let begin_time = nums[0] as i64;
@@ -290,13 +618,15 @@ fn parse_one_candle(
         msg_type: MessageType::Candlestick,
         timestamp: begin_time,
         begin_time,
+        end_time: period.end_time(begin_time),
         open,
         high,
         low,
         close,
         volume,
-        period: period.to_string(),
+        period,
         quote_volume: None,
+        is_closed: None,
         json: "".to_string(),
     }
 }
@@ -313,40 +643,36 @@ fn parse_one_candle(
 pub(crate) fn parse_candlestick(
     market_type: MarketType,
     msg: &str,
-) -> Result<Vec<CandlestickMsg>, SimpleError> {
-    let ws_msg = serde_json::from_str::<Vec<Value>>(msg).map_err(SimpleError::from)?;
-
-    let (symbol, period) = {
-        let key =
-            ws_msg[0].as_object().unwrap()["key"].as_str().unwrap().strip_prefix("trade:").unwrap();
-        let pos = key.find(':').unwrap();
-        let period = &key[..pos];
-        let symbol = &key[pos + 1..];
-        (symbol, period)
-    };
+) -> Result<Vec<CandlestickMsg>, ParseError> {
+    let ws_msg = serde_json::from_str::<Vec<Value>>(msg).map_err(ParseError::from)?;
+
+    if ws_msg.len() < 2 {
+        return Err(ParseError::malformed("expected [channel, data]"));
+    }
+    let (period, symbol) = split_candle_key(json_str(&ws_msg[0]["key"], "key")?)?;
+    let period = parse_candle_period(EXCHANGE_NAME, market_type, period)?;
 
-    let pair = crypto_pair::normalize_pair(symbol, EXCHANGE_NAME).unwrap();
+    let pair = crypto_pair::normalize_pair(symbol, EXCHANGE_NAME)
+        .ok_or_else(|| ParseError::symbol_normalization(symbol))?;
 
     let data = ws_msg[1].clone();
-    if data.as_array().unwrap().is_empty() {
+    if data.is_string() {
+        // heartbeat
         return Ok(vec![]);
     }
-
-    let snapshot = {
-        let arr = data.as_array().unwrap();
-        arr[0].is_array()
+    let snapshot = match json_array(&data, "data")?.first() {
+        Some(first) => first.is_array(),
+        None => return Ok(vec![]),
     };
 
     if snapshot {
-        let arr = data.as_array().unwrap();
-        let candles: Vec<CandlestickMsg> = arr
+        let candles = serde_json::from_value::<Vec<[f64; 6]>>(data)?
             .iter()
-            .map(|v| serde_json::from_value::<[f64; 6]>(v.clone()).unwrap())
-            .map(|nums| parse_one_candle(market_type, symbol, &pair, period, &nums))
+            .map(|nums| parse_one_candle(market_type, symbol, &pair, period, nums))
             .collect();
         Ok(candles)
     } else {
-        let nums = serde_json::from_value::<[f64; 6]>(data).unwrap();
+        let nums = serde_json::from_value::<[f64; 6]>(data)?;
         let candlestick_msg = parse_one_candle(market_type, symbol, &pair, period, &nums);
         Ok(vec![candlestick_msg])
     }
@@ -1,14 +1,17 @@
 use crypto_market_type::MarketType;
 use crypto_msg_type::MessageType;
 
-use super::super::super::utils::calc_quantity_and_volume;
+use super::super::super::utils::{
+    annualize_funding_rate, calc_quantity_and_volume, json_array, json_str, parse_candle_period,
+    parse_exact,
+};
 use crypto_message::{CandlestickMsg, FundingRateMsg, Order, OrderBookMsg, TradeMsg, TradeSide};
 
 use super::super::EXCHANGE_NAME;
+use crate::ParseError;
 use chrono::prelude::*;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
-use simple_error::SimpleError;
 use std::collections::HashMap;
 
 // see https://bitgetlimited.github.io/apidoc/en/swap/#public-trading-channel
@@ -47,60 +50,51 @@ struct WebsocketMsg<T: Sized> {
     action: Option<String>,
 }
 
-pub(super) fn extract_symbol(msg: &str) -> Result<String, SimpleError> {
-    let ws_msg = serde_json::from_str::<WebsocketMsg<Value>>(msg).map_err(|_e| {
-        SimpleError::new(format!("Failed to deserialize {msg} to WebsocketMsg<Value>"))
-    })?;
+pub(super) fn extract_symbol(msg: &str) -> Result<String, ParseError> {
+    let ws_msg = serde_json::from_str::<WebsocketMsg<Value>>(msg)
+        .map_err(|_e| ParseError::malformed("expected WebsocketMsg<Value>"))?;
     if ws_msg.data.is_array() {
-        let instrument_ids = ws_msg
-            .data
-            .as_array()
-            .unwrap()
+        let instrument_ids = json_array(&ws_msg.data, "data")?
             .iter()
-            .map(|v| v["instrument_id"].as_str().unwrap())
-            .collect::<Vec<&str>>();
+            .map(|v| json_str(&v["instrument_id"], "instrument_id"))
+            .collect::<Result<Vec<&str>, ParseError>>()?;
         if instrument_ids.is_empty() {
-            Err(SimpleError::new(format!("data is empty {msg}")))
+            Err(ParseError::unsupported("data is empty"))
         } else {
             Ok(instrument_ids[0].to_string())
         }
     } else if ws_msg.data.is_object() && ws_msg.data.get("instrument_id").is_some() {
-        Ok(ws_msg.data["instrument_id"].as_str().unwrap().to_string())
+        Ok(json_str(&ws_msg.data["instrument_id"], "instrument_id")?.to_string())
     } else {
-        Err(SimpleError::new(format!("Failed to extract symbol from {msg}")))
+        Err(ParseError::unsupported("Failed to extract symbol"))
     }
 }
 
-pub(super) fn extract_timestamp(msg: &str) -> Result<Option<i64>, SimpleError> {
-    let ws_msg = serde_json::from_str::<WebsocketMsg<Value>>(msg).map_err(|_e| {
-        SimpleError::new(format!("Failed to deserialize {msg} to WebsocketMsg<Value>"))
-    })?;
+pub(super) fn extract_timestamp(msg: &str) -> Result<Option<i64>, ParseError> {
+    let ws_msg = serde_json::from_str::<WebsocketMsg<Value>>(msg)
+        .map_err(|_e| ParseError::malformed("expected WebsocketMsg<Value>"))?;
     let table = ws_msg.table.as_str();
     let timestamp = if table.starts_with("swap/candle") {
-        Some(ws_msg.data["candle"].as_array().unwrap()[0].as_str().unwrap().parse::<i64>().unwrap())
+        let candle = json_array(&ws_msg.data["candle"], "candle")?;
+        let time = candle.first().ok_or_else(|| ParseError::unsupported("candle is empty"))?;
+        Some(json_str(time, "candle[0]")?.parse::<i64>()?)
     } else {
-        ws_msg
-            .data
-            .as_array()
-            .unwrap()
+        json_array(&ws_msg.data, "data")?
             .iter()
-            .map(|v| v["timestamp"].as_str().unwrap().parse::<i64>().unwrap())
+            .map(|v| -> Result<i64, ParseError> {
+                Ok(json_str(&v["timestamp"], "timestamp")?.parse::<i64>()?)
+            })
+            .collect::<Result<Vec<i64>, ParseError>>()?
+            .into_iter()
             .max()
     };
-    if timestamp.is_none() {
-        Err(SimpleError::new(format!("data is empty in {msg}")))
-    } else {
-        Ok(timestamp)
-    }
+    if timestamp.is_none() { Err(ParseError::unsupported("data is empty")) } else { Ok(timestamp) }
 }
 
 pub(super) fn get_msg_type(msg: &str) -> MessageType {
     if let Ok(ws_msg) = serde_json::from_str::<WebsocketMsg<Value>>(msg) {
         let table = ws_msg.table;
-        let channel = {
-            let arr = table.split('/').collect::<Vec<&str>>();
-            arr[1]
-        };
+        let channel = table.split('/').nth(1).unwrap_or_default();
         if channel == "trade" {
             MessageType::Trade
         } else if channel == "depth" {
@@ -121,42 +115,47 @@ pub(super) fn get_msg_type(msg: &str) -> MessageType {
     }
 }
 
-pub(super) fn parse_trade(
-    market_type: MarketType,
-    msg: &str,
-) -> Result<Vec<TradeMsg>, SimpleError> {
-    let ws_msg = serde_json::from_str::<WebsocketMsg<Vec<SwapTradeMsg>>>(msg).map_err(|_e| {
-        SimpleError::new(format!("Failed to deserialize {msg} to WebsocketMsg<SwapTradeMsg>"))
-    })?;
+pub(super) fn parse_trade(market_type: MarketType, msg: &str) -> Result<Vec<TradeMsg>, ParseError> {
+    let ws_msg = serde_json::from_str::<WebsocketMsg<Vec<SwapTradeMsg>>>(msg)
+        .map_err(|_e| ParseError::malformed("expected WebsocketMsg<SwapTradeMsg>"))?;
     let mut trades: Vec<TradeMsg> = ws_msg
         .data
         .into_iter()
-        .map(|raw_trade| {
-            let pair =
-                crypto_pair::normalize_pair(&raw_trade.instrument_id, EXCHANGE_NAME).unwrap();
-            let price = raw_trade.price.parse::<f64>().unwrap();
-            let size = raw_trade.size.parse::<f64>().unwrap();
-            let (quantity_base, quantity_quote, quantity_contract) =
-                calc_quantity_and_volume(EXCHANGE_NAME, market_type, &pair, price, size);
+        .map(|raw_trade| -> Result<TradeMsg, ParseError> {
+            let pair = crypto_pair::normalize_pair(&raw_trade.instrument_id, EXCHANGE_NAME)
+                .ok_or_else(|| ParseError::symbol_normalization(&raw_trade.instrument_id))?;
+            let price = raw_trade.price.parse::<f64>()?;
+            let size = raw_trade.size.parse::<f64>()?;
+            let timestamp = raw_trade.timestamp.parse::<i64>()?;
+            let (quantity_base, quantity_quote, quantity_contract) = calc_quantity_and_volume(
+                EXCHANGE_NAME,
+                market_type,
+                &pair,
+                price,
+                size,
+                timestamp,
+            )?;
 
-            TradeMsg {
+            Ok(TradeMsg {
                 exchange: EXCHANGE_NAME.to_string(),
                 market_type,
                 symbol: raw_trade.instrument_id.clone(),
                 pair,
                 msg_type: MessageType::Trade,
-                timestamp: raw_trade.timestamp.parse::<i64>().unwrap(),
+                timestamp,
                 price,
                 quantity_base,
                 quantity_quote,
                 quantity_contract,
+                price_exact: parse_exact(&raw_trade.price),
+                quantity_exact: parse_exact(&raw_trade.size),
                 side: if raw_trade.side == "sell" { TradeSide::Sell } else { TradeSide::Buy },
                 // Use timestamp as ID because bitget doesn't provide trade_id
                 trade_id: raw_trade.timestamp.to_string(),
                 json: serde_json::to_string(&raw_trade).unwrap(),
-            }
+            })
         })
-        .collect();
+        .collect::<Result<Vec<TradeMsg>, ParseError>>()?;
     if trades.len() == 1 {
         trades[0].json = msg.to_string();
     }
@@ -173,33 +172,40 @@ struct RawFundingRateMsg {
     extra: HashMap<String, Value>,
 }
 
+// Perpetual swaps settle funding every 8 hours
+const FUNDING_INTERVAL: i64 = 8 * 3600 * 1000;
+
 pub(super) fn parse_funding_rate(
     market_type: MarketType,
     msg: &str,
-) -> Result<Vec<FundingRateMsg>, SimpleError> {
-    let ws_msg =
-        serde_json::from_str::<WebsocketMsg<Vec<RawFundingRateMsg>>>(msg).map_err(|_e| {
-            SimpleError::new(format!(
-                "Failed to deserialize {msg} to WebsocketMsg<RawFundingRateMsg>"
-            ))
-        })?;
+) -> Result<Vec<FundingRateMsg>, ParseError> {
+    let ws_msg = serde_json::from_str::<WebsocketMsg<Vec<RawFundingRateMsg>>>(msg)
+        .map_err(|_e| ParseError::malformed("expected WebsocketMsg<RawFundingRateMsg>"))?;
 
     let mut rates: Vec<FundingRateMsg> = ws_msg
         .data
         .into_iter()
-        .map(|raw_msg| FundingRateMsg {
-            exchange: EXCHANGE_NAME.to_string(),
-            market_type,
-            symbol: raw_msg.instrument_id.clone(),
-            pair: crypto_pair::normalize_pair(&raw_msg.instrument_id, EXCHANGE_NAME).unwrap(),
-            msg_type: MessageType::FundingRate,
-            timestamp: Utc::now().timestamp_millis(),
-            funding_rate: raw_msg.funding_rate.parse::<f64>().unwrap(),
-            funding_time: raw_msg.funding_time.parse::<i64>().unwrap(),
-            estimated_rate: None,
-            json: serde_json::to_string(&raw_msg).unwrap(),
+        .map(|raw_msg| -> Result<FundingRateMsg, ParseError> {
+            let funding_rate = raw_msg.funding_rate.parse::<f64>()?;
+            Ok(FundingRateMsg {
+                exchange: EXCHANGE_NAME.to_string(),
+                market_type,
+                symbol: raw_msg.instrument_id.clone(),
+                pair: crypto_pair::normalize_pair(&raw_msg.instrument_id, EXCHANGE_NAME)
+                    .ok_or_else(|| ParseError::symbol_normalization(&raw_msg.instrument_id))?,
+                msg_type: MessageType::FundingRate,
+                timestamp: Utc::now().timestamp_millis(),
+                funding_rate,
+                funding_time: raw_msg.funding_time.parse::<i64>()?,
+                estimated_rate: None,
+                funding_interval: FUNDING_INTERVAL,
+                funding_rate_cap: None,
+                funding_rate_floor: None,
+                annualized_rate: annualize_funding_rate(funding_rate, FUNDING_INTERVAL),
+                json: serde_json::to_string(&raw_msg).unwrap(),
+            })
         })
-        .collect();
+        .collect::<Result<Vec<FundingRateMsg>, ParseError>>()?;
     if rates.len() == 1 {
         rates[0].json = msg.to_string();
     }
@@ -209,18 +215,17 @@ pub(super) fn parse_funding_rate(
 pub(super) fn parse_l2(
     market_type: MarketType,
     msg: &str,
-) -> Result<Vec<OrderBookMsg>, SimpleError> {
+) -> Result<Vec<OrderBookMsg>, ParseError> {
     let ws_msg = serde_json::from_str::<WebsocketMsg<Vec<SwapOrderbookMsg>>>(msg)
-        .map_err(SimpleError::from)?;
+        .map_err(ParseError::from)?;
     let table = ws_msg.table.as_str();
 
     let snapshot = if let Some(action) = ws_msg.action {
         action.as_str() == "partial"
     } else {
-        table.starts_with("swap/depth") && table.chars().last().unwrap().is_numeric()
+        table.starts_with("swap/depth") && table.ends_with(|c: char| c.is_numeric())
     };
-    let msg_type = if table.starts_with("swap/depth") && table.chars().last().unwrap().is_numeric()
-    {
+    let msg_type = if table.starts_with("swap/depth") && table.ends_with(|c: char| c.is_numeric()) {
         MessageType::L2TopK
     } else {
         MessageType::L2Event
@@ -231,15 +236,28 @@ pub(super) fn parse_l2(
     for raw_orderbook in ws_msg.data.iter() {
         let symbol = raw_orderbook.instrument_id.as_str();
         let pair = crypto_pair::normalize_pair(symbol, EXCHANGE_NAME)
-            .ok_or_else(|| SimpleError::new(format!("Failed to normalize {symbol} from {msg}")))?;
-        let timestamp = raw_orderbook.timestamp.parse::<i64>().unwrap();
+            .ok_or_else(|| ParseError::symbol_normalization(symbol))?;
+        let timestamp = raw_orderbook.timestamp.parse::<i64>()?;
 
-        let parse_order = |raw_order: &[String; 2]| -> Order {
-            let price = raw_order[0].parse::<f64>().unwrap();
-            let quantity = raw_order[1].parse::<f64>().unwrap();
-            let (quantity_base, quantity_quote, quantity_contract) =
-                calc_quantity_and_volume(EXCHANGE_NAME, market_type, &pair, price, quantity);
-            Order { price, quantity_base, quantity_quote, quantity_contract }
+        let parse_order = |raw_order: &[String; 2]| -> Result<Order, ParseError> {
+            let price = raw_order[0].parse::<f64>()?;
+            let quantity = raw_order[1].parse::<f64>()?;
+            let (quantity_base, quantity_quote, quantity_contract) = calc_quantity_and_volume(
+                EXCHANGE_NAME,
+                market_type,
+                &pair,
+                price,
+                quantity,
+                timestamp,
+            )?;
+            Ok(Order {
+                price,
+                quantity_base,
+                quantity_quote,
+                quantity_contract,
+                price_exact: parse_exact(&raw_order[0]),
+                quantity_exact: parse_exact(&raw_order[1]),
+            })
         };
 
         let orderbook = OrderBookMsg {
@@ -251,8 +269,16 @@ pub(super) fn parse_l2(
             timestamp,
             seq_id: None,
             prev_seq_id: None,
-            asks: raw_orderbook.asks.iter().map(parse_order).collect(),
-            bids: raw_orderbook.bids.iter().map(parse_order).collect(),
+            asks: raw_orderbook
+                .asks
+                .iter()
+                .map(parse_order)
+                .collect::<Result<Vec<Order>, ParseError>>()?,
+            bids: raw_orderbook
+                .bids
+                .iter()
+                .map(parse_order)
+                .collect::<Result<Vec<Order>, ParseError>>()?,
             snapshot,
             json: msg.to_string(),
         };
@@ -266,43 +292,50 @@ pub(super) fn parse_l2(
 pub(super) fn parse_candlestick(
     market_type: MarketType,
     msg: &str,
-) -> Result<Vec<CandlestickMsg>, SimpleError> {
+) -> Result<Vec<CandlestickMsg>, ParseError> {
     let ws_msg =
-        serde_json::from_str::<WebsocketMsg<RawCandlestickMsg>>(msg).map_err(SimpleError::from)?;
-    debug_assert!(ws_msg.table.starts_with("swap/candle"));
-    let period = ws_msg.table.as_str().strip_prefix("swap/candle").unwrap().to_string();
-    let duration = match period.to_string().pop().unwrap() {
-        's' => period.strip_suffix('s').unwrap().parse::<i64>().unwrap() * 1000,
-        'm' => period.strip_suffix('m').unwrap().parse::<i64>().unwrap() * 1000 * 60,
-        'd' => period.strip_suffix('d').unwrap().parse::<i64>().unwrap() * 1000 * 24 * 60 * 60,
-        _ => 0,
-    };
+        serde_json::from_str::<WebsocketMsg<RawCandlestickMsg>>(msg).map_err(ParseError::from)?;
+    if !ws_msg.table.starts_with("swap/candle") {
+        return Err(ParseError::unsupported(format!("Unsupported table {}", ws_msg.table)));
+    }
+    let period = parse_candle_period(
+        EXCHANGE_NAME,
+        market_type,
+        ws_msg
+            .table
+            .strip_prefix("swap/")
+            .ok_or_else(|| ParseError::unsupported(format!("Unknown table {}", ws_msg.table)))?,
+    )?;
 
-    let pair = crypto_pair::normalize_pair(&ws_msg.data.instrument_id, EXCHANGE_NAME).unwrap();
+    let pair = crypto_pair::normalize_pair(&ws_msg.data.instrument_id, EXCHANGE_NAME)
+        .ok_or_else(|| ParseError::symbol_normalization(&ws_msg.data.instrument_id))?;
     let raw_candlestickmsg = ws_msg.data.candle;
-    let timestamp = raw_candlestickmsg[0].parse::<i64>().unwrap();
-    let open = raw_candlestickmsg[1].parse::<f64>().unwrap();
-    let high = raw_candlestickmsg[2].parse::<f64>().unwrap();
-    let low = raw_candlestickmsg[3].parse::<f64>().unwrap();
-    let close = raw_candlestickmsg[4].parse::<f64>().unwrap();
+    let timestamp = raw_candlestickmsg[0].parse::<i64>()?;
+    let open = raw_candlestickmsg[1].parse::<f64>()?;
+    let high = raw_candlestickmsg[2].parse::<f64>()?;
+    let low = raw_candlestickmsg[3].parse::<f64>()?;
+    let close = raw_candlestickmsg[4].parse::<f64>()?;
     let (volume, quote_volume) = match market_type {
         MarketType::InverseSwap => {
-            let volume = raw_candlestickmsg[6].parse::<f64>().unwrap();
-            let quote_volume = raw_candlestickmsg[5].parse::<f64>().unwrap();
+            let volume = raw_candlestickmsg[6].parse::<f64>()?;
+            let quote_volume = raw_candlestickmsg[5].parse::<f64>()?;
             (volume, quote_volume)
         }
         MarketType::LinearSwap => {
-            let contract_value = crypto_contract_value::get_contract_value(
+            let contract_value = crypto_contract_value::get_contract_value_at(
                 EXCHANGE_NAME,
                 market_type,
                 pair.as_str(),
+                timestamp,
             )
-            .unwrap();
-            let volume = raw_candlestickmsg[5].parse::<f64>().unwrap();
-            let quote_volume = raw_candlestickmsg[6].parse::<f64>().unwrap();
+            .ok_or_else(|| ParseError::unsupported(format!("No contract value for {pair}")))?;
+            let volume = raw_candlestickmsg[5].parse::<f64>()?;
+            let quote_volume = raw_candlestickmsg[6].parse::<f64>()?;
             (volume * contract_value, quote_volume)
         }
-        _ => panic!("Unknown market_type: {}", market_type),
+        _ => {
+            return Err(ParseError::unsupported(format!("Unknown market_type: {market_type}")));
+        }
     };
 
     let candlestick_msg = CandlestickMsg {
@@ -310,16 +343,20 @@ pub(super) fn parse_candlestick(
         market_type,
         msg_type: MessageType::Candlestick,
         symbol: ws_msg.data.instrument_id.clone(),
-        pair: crypto_pair::normalize_pair(&ws_msg.data.instrument_id, EXCHANGE_NAME).unwrap(),
+        pair: crypto_pair::normalize_pair(&ws_msg.data.instrument_id, EXCHANGE_NAME)
+            .ok_or_else(|| ParseError::symbol_normalization(&ws_msg.data.instrument_id))?,
         timestamp,
         period,
-        begin_time: timestamp - duration,
+        // the start time of the candle
+        begin_time: timestamp,
+        end_time: period.end_time(timestamp),
         open,
         high,
         low,
         close,
         volume,
         quote_volume: Some(quote_volume),
+        is_closed: None,
         json: msg.to_string(),
     };
 
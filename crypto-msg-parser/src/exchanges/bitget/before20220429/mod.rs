@@ -3,18 +3,16 @@ mod bitget_swap;
 use crypto_market_type::MarketType;
 use crypto_msg_type::MessageType;
 
-use crate::{CandlestickMsg, FundingRateMsg, OrderBookMsg, TradeMsg};
+use crate::{CandlestickMsg, FundingRateMsg, OrderBookMsg, ParseError, TradeMsg};
 
-use simple_error::SimpleError;
-
-pub(super) fn extract_symbol(_market_type: MarketType, msg: &str) -> Result<String, SimpleError> {
+pub(super) fn extract_symbol(_market_type: MarketType, msg: &str) -> Result<String, ParseError> {
     bitget_swap::extract_symbol(msg)
 }
 
 pub(super) fn extract_timestamp(
     _market_type: MarketType,
     msg: &str,
-) -> Result<Option<i64>, SimpleError> {
+) -> Result<Option<i64>, ParseError> {
     bitget_swap::extract_timestamp(msg)
 }
 
@@ -22,12 +20,9 @@ pub(super) fn get_msg_type(msg: &str) -> MessageType {
     bitget_swap::get_msg_type(msg)
 }
 
-pub(super) fn parse_trade(
-    market_type: MarketType,
-    msg: &str,
-) -> Result<Vec<TradeMsg>, SimpleError> {
+pub(super) fn parse_trade(market_type: MarketType, msg: &str) -> Result<Vec<TradeMsg>, ParseError> {
     if market_type == MarketType::Spot {
-        Err(SimpleError::new("Not implemented"))
+        Err(ParseError::unsupported("Not implemented"))
     } else {
         bitget_swap::parse_trade(market_type, msg)
     }
@@ -36,9 +31,9 @@ pub(super) fn parse_trade(
 pub(super) fn parse_l2(
     market_type: MarketType,
     msg: &str,
-) -> Result<Vec<OrderBookMsg>, SimpleError> {
+) -> Result<Vec<OrderBookMsg>, ParseError> {
     if market_type == MarketType::Spot {
-        Err(SimpleError::new("Not implemented"))
+        Err(ParseError::unsupported("Not implemented"))
     } else {
         bitget_swap::parse_l2(market_type, msg)
     }
@@ -47,13 +42,13 @@ pub(super) fn parse_l2(
 pub(super) fn parse_funding_rate(
     market_type: MarketType,
     msg: &str,
-) -> Result<Vec<FundingRateMsg>, SimpleError> {
+) -> Result<Vec<FundingRateMsg>, ParseError> {
     bitget_swap::parse_funding_rate(market_type, msg)
 }
 
 pub(super) fn parse_candlestick(
     market_type: MarketType,
     msg: &str,
-) -> Result<Vec<CandlestickMsg>, SimpleError> {
+) -> Result<Vec<CandlestickMsg>, ParseError> {
     bitget_swap::parse_candlestick(market_type, msg)
 }
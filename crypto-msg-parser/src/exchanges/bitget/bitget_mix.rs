@@ -1,14 +1,16 @@
 use crypto_market_type::MarketType;
 use crypto_msg_type::MessageType;
 
-use crypto_message::{CandlestickMsg, Order, OrderBookMsg, TradeMsg, TradeSide};
+use crypto_message::{CandlestickMsg, Order, OrderBookMsg, TickerMsg, TradeMsg, TradeSide};
 
-use crate::exchanges::utils::calc_quantity_and_volume;
+use crate::{
+    ParseError,
+    exchanges::utils::{calc_quantity_and_volume, json_object, parse_candle_period, parse_exact},
+};
 
 use super::EXCHANGE_NAME;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
-use simple_error::SimpleError;
 use std::collections::HashMap;
 
 #[derive(Serialize, Deserialize)]
@@ -56,27 +58,50 @@ struct RawL2SnapshotData {
     timestamp: String,
 }
 
-fn extract_market_type_and_symbol(arg: &Arg) -> (MarketType, String) {
+// See:
+// * https://bitgetlimited.github.io/apidoc/en/spot/#tickers-channel
+// * https://bitgetlimited.github.io/apidoc/en/mix/#tickers-channel
+#[derive(Serialize, Deserialize)]
+#[allow(non_snake_case)]
+struct RawTickerMsg {
+    instId: String,
+    last: String,
+    open24h: Option<String>, // spot only
+    high24h: String,
+    low24h: String,
+    bestBid: String,
+    bestAsk: String,
+    baseVolume: String,
+    quoteVolume: String,
+    priceChangePercent: Option<String>, // mix only
+    holding: Option<String>,            // open interest in base currency, mix only
+    ts: Option<i64>,                    // spot only
+    systemTime: Option<i64>,            // mix only
+    #[serde(flatten)]
+    extra: HashMap<String, Value>,
+}
+
+fn extract_market_type_and_symbol(arg: &Arg) -> Result<(MarketType, String), ParseError> {
     match arg.instType.as_str() {
-        "sp" => (MarketType::Spot, format!("{}_SPBL", arg.instId)),
+        "sp" => Ok((MarketType::Spot, format!("{}_SPBL", arg.instId))),
         "mc" => {
             let (market_type, suffix) = if arg.instId.ends_with("USDT") {
                 (MarketType::LinearSwap, "UMCBL")
             } else if arg.instId.ends_with("USD") {
                 (MarketType::InverseSwap, "DMCBL")
             } else {
-                panic!("Unknown instId {}", arg.instId);
+                return Err(ParseError::unsupported(format!("Unknown instId {}", arg.instId)));
             };
             let symbol = format!("{}_{}", arg.instId, suffix);
-            (market_type, symbol)
+            Ok((market_type, symbol))
         }
-        _ => panic!("Unknown instType {}", arg.instType),
+        _ => Err(ParseError::unsupported(format!("Unknown instType {}", arg.instType))),
     }
 }
 
-pub(super) fn extract_symbol(msg: &str) -> Result<String, SimpleError> {
+pub(super) fn extract_symbol(msg: &str) -> Result<String, ParseError> {
     let obj = serde_json::from_str::<WebsocketMsg<Value>>(msg)
-        .map_err(|_e| SimpleError::new(format!("Failed to parse JSON string {msg}")))?;
+        .map_err(|_e| ParseError::malformed("invalid JSON"))?;
     let inst_type = obj.arg.instType.as_str();
     let symbol = obj.arg.instId.as_str();
     match inst_type {
@@ -88,37 +113,37 @@ pub(super) fn extract_symbol(msg: &str) -> Result<String, SimpleError> {
                 Ok(format!("{symbol}_DMCBL"))
             }
         }
-        _ => Err(SimpleError::new(format!("Unsupported instType {inst_type} in {msg}"))),
+        _ => Err(ParseError::unsupported(format!("Unsupported instType {inst_type}"))),
     }
 }
 
-pub(super) fn extract_timestamp(msg: &str) -> Result<Option<i64>, SimpleError> {
+pub(super) fn extract_timestamp(msg: &str) -> Result<Option<i64>, ParseError> {
     let obj = serde_json::from_str::<WebsocketMsg<Value>>(msg)
-        .map_err(|_e| SimpleError::new(format!("Failed to parse JSON string {msg}")))?;
+        .map_err(|_e| ParseError::malformed("invalid JSON"))?;
     let timestamp = obj
         .data
         .iter()
-        .map(|x| {
+        .map(|x| -> Result<i64, ParseError> {
             let v = if x.is_array() {
-                x[0].clone()
+                &x[0]
             } else {
-                let obj = x.as_object().unwrap();
-                if obj.contains_key("ts") {
-                    obj["ts"].clone()
-                } else if obj.contains_key("systemTime") {
-                    obj["systemTime"].clone()
+                let obj = json_object(x, "data")?;
+                if let Some(ts) = obj.get("ts") {
+                    ts
+                } else if let Some(system_time) = obj.get("systemTime") {
+                    system_time
                 } else {
-                    panic!("Can not find timestamp related fields in {msg}");
+                    return Err(ParseError::missing_timestamp());
                 }
             };
-            if v.is_string() {
-                v.as_str().unwrap().parse::<i64>().unwrap()
-            } else if v.is_i64() {
-                v.as_i64().unwrap()
+            if let Some(s) = v.as_str() {
+                Ok(s.parse::<i64>()?)
             } else {
-                panic!("Unsupported data format {msg}");
+                v.as_i64().ok_or_else(|| ParseError::malformed("timestamp is not an integer"))
             }
         })
+        .collect::<Result<Vec<i64>, ParseError>>()?
+        .into_iter()
         .max();
     Ok(timestamp)
 }
@@ -126,22 +151,25 @@ pub(super) fn extract_timestamp(msg: &str) -> Result<Option<i64>, SimpleError> {
 /// docs:
 /// * https://bitgetlimited.github.io/apidoc/en/spot/#trades-channel
 /// * https://bitgetlimited.github.io/apidoc/en/mix/#trades-channel
-pub(super) fn parse_trade(msg: &str) -> Result<Vec<TradeMsg>, SimpleError> {
+pub(super) fn parse_trade(msg: &str) -> Result<Vec<TradeMsg>, ParseError> {
     let ws_msg = serde_json::from_str::<WebsocketMsg<[String; 4]>>(msg)
-        .map_err(|_e| SimpleError::new(format!("Failed to parse JSON string {msg}")))?;
-    debug_assert_eq!("trade", ws_msg.arg.channel.as_str());
-    let (market_type, symbol) = extract_market_type_and_symbol(&ws_msg.arg);
-    let pair = crypto_pair::normalize_pair(&symbol, EXCHANGE_NAME).unwrap();
+        .map_err(|_e| ParseError::malformed("invalid JSON"))?;
+    if ws_msg.arg.channel != "trade" {
+        return Err(ParseError::unsupported(format!("Unsupported channel {}", ws_msg.arg.channel)));
+    }
+    let (market_type, symbol) = extract_market_type_and_symbol(&ws_msg.arg)?;
+    let pair = crypto_pair::normalize_pair(&symbol, EXCHANGE_NAME)
+        .ok_or_else(|| ParseError::symbol_normalization(&symbol))?;
     let mut trades: Vec<TradeMsg> = ws_msg
         .data
         .into_iter()
-        .map(|raw_trade| {
-            let timestamp = raw_trade[0].parse::<i64>().unwrap();
-            let price = raw_trade[1].parse::<f64>().unwrap();
-            let quantity = raw_trade[2].parse::<f64>().unwrap();
+        .map(|raw_trade| -> Result<TradeMsg, ParseError> {
+            let timestamp = raw_trade[0].parse::<i64>()?;
+            let price = raw_trade[1].parse::<f64>()?;
+            let quantity = raw_trade[2].parse::<f64>()?;
             let side =
                 if raw_trade[3].as_str() == "sell" { TradeSide::Sell } else { TradeSide::Buy };
-            TradeMsg {
+            Ok(TradeMsg {
                 exchange: EXCHANGE_NAME.to_string(),
                 market_type,
                 symbol: symbol.clone(),
@@ -156,13 +184,15 @@ pub(super) fn parse_trade(msg: &str) -> Result<Vec<TradeMsg>, SimpleError> {
                 } else {
                     Some(quantity)
                 },
+                price_exact: parse_exact(&raw_trade[1]),
+                quantity_exact: parse_exact(&raw_trade[2]),
                 side,
                 // Use timestamp as ID because bitget doesn't have trade_id
                 trade_id: timestamp.to_string(),
                 json: serde_json::to_string(&raw_trade).unwrap(),
-            }
+            })
         })
-        .collect();
+        .collect::<Result<Vec<TradeMsg>, ParseError>>()?;
     if trades.len() == 1 {
         trades[0].json = msg.to_string();
     }
@@ -172,42 +202,55 @@ pub(super) fn parse_trade(msg: &str) -> Result<Vec<TradeMsg>, SimpleError> {
 /// docs:
 /// * https://bitgetlimited.github.io/apidoc/en/spot/#depth-channel
 /// * https://bitgetlimited.github.io/apidoc/en/mix/#order-book-channel
-pub(super) fn parse_l2(msg: &str) -> Result<Vec<OrderBookMsg>, SimpleError> {
+pub(super) fn parse_l2(msg: &str) -> Result<Vec<OrderBookMsg>, ParseError> {
     let ws_msg = serde_json::from_str::<WebsocketMsg<RawOrderBook>>(msg)
-        .map_err(|_e| SimpleError::new(format!("Failed to parse JSON string {msg}")))?;
+        .map_err(|_e| ParseError::malformed("invalid JSON"))?;
     let snapshot = ws_msg.action == "snapshot";
-    let (market_type, symbol) = extract_market_type_and_symbol(&ws_msg.arg);
-    let pair = crypto_pair::normalize_pair(&symbol, EXCHANGE_NAME).unwrap();
+    let (market_type, symbol) = extract_market_type_and_symbol(&ws_msg.arg)?;
+    let pair = crypto_pair::normalize_pair(&symbol, EXCHANGE_NAME)
+        .ok_or_else(|| ParseError::symbol_normalization(&symbol))?;
 
-    let parse_order = |raw_order: &[String; 2]| -> Order {
-        let price = raw_order[0].parse::<f64>().unwrap();
-        let quantity = raw_order[1].parse::<f64>().unwrap();
-        Order {
+    let parse_order = |raw_order: &[String; 2]| -> Result<Order, ParseError> {
+        let price = raw_order[0].parse::<f64>()?;
+        let quantity = raw_order[1].parse::<f64>()?;
+        Ok(Order {
             price,
             quantity_base: quantity,
             quantity_quote: quantity * price,
             quantity_contract: if market_type == MarketType::Spot { None } else { Some(quantity) },
-        }
+            price_exact: parse_exact(&raw_order[0]),
+            quantity_exact: parse_exact(&raw_order[1]),
+        })
     };
 
     let orderbooks = ws_msg
         .data
         .iter()
-        .map(|raw_orderbook| OrderBookMsg {
-            exchange: EXCHANGE_NAME.to_string(),
-            market_type,
-            symbol: symbol.to_string(),
-            pair: pair.clone(),
-            msg_type: MessageType::L2Event,
-            timestamp: raw_orderbook.ts.parse::<i64>().unwrap(),
-            seq_id: None,
-            prev_seq_id: None,
-            asks: raw_orderbook.asks.iter().map(parse_order).collect(),
-            bids: raw_orderbook.bids.iter().map(parse_order).collect(),
-            snapshot,
-            json: serde_json::to_string(raw_orderbook).unwrap(),
+        .map(|raw_orderbook| -> Result<OrderBookMsg, ParseError> {
+            Ok(OrderBookMsg {
+                exchange: EXCHANGE_NAME.to_string(),
+                market_type,
+                symbol: symbol.to_string(),
+                pair: pair.clone(),
+                msg_type: MessageType::L2Event,
+                timestamp: raw_orderbook.ts.parse::<i64>()?,
+                seq_id: None,
+                prev_seq_id: None,
+                asks: raw_orderbook
+                    .asks
+                    .iter()
+                    .map(parse_order)
+                    .collect::<Result<Vec<Order>, ParseError>>()?,
+                bids: raw_orderbook
+                    .bids
+                    .iter()
+                    .map(parse_order)
+                    .collect::<Result<Vec<Order>, ParseError>>()?,
+                snapshot,
+                json: serde_json::to_string(raw_orderbook).unwrap(),
+            })
         })
-        .collect();
+        .collect::<Result<Vec<OrderBookMsg>, ParseError>>()?;
 
     Ok(orderbooks)
 }
@@ -221,19 +264,27 @@ pub(super) fn parse_l2_snapshot(
     market_type: MarketType,
     msg: &str,
     symbol: Option<&str>,
-) -> Result<Vec<OrderBookMsg>, SimpleError> {
+) -> Result<Vec<OrderBookMsg>, ParseError> {
     let rs_msg = serde_json::from_str::<RestMsg<RawL2SnapshotData>>(msg)
-        .map_err(|_e| SimpleError::new(format!("Failed to parse JSON string {msg}")))?;
+        .map_err(|_e| ParseError::malformed("invalid JSON"))?;
 
     let symbol = symbol.unwrap_or_default();
-    let pair = crypto_pair::normalize_pair(symbol, EXCHANGE_NAME).unwrap();
+    let pair = crypto_pair::normalize_pair(symbol, EXCHANGE_NAME)
+        .ok_or_else(|| ParseError::symbol_normalization(symbol))?;
+    let timestamp = rs_msg.data.timestamp.parse::<i64>()?;
 
-    let parse_order = |raw_order: &[String; 2]| -> Order {
-        let price = raw_order[0].parse::<f64>().unwrap();
-        let quantity = raw_order[1].parse::<f64>().unwrap();
-        let (quantity_base, quantity_quote, quantity_contract) =
-            calc_quantity_and_volume(EXCHANGE_NAME, market_type, &pair, price, quantity);
-        Order { price, quantity_base, quantity_quote, quantity_contract }
+    let parse_order = |raw_order: &[String; 2]| -> Result<Order, ParseError> {
+        let price = raw_order[0].parse::<f64>()?;
+        let quantity = raw_order[1].parse::<f64>()?;
+        let (quantity_base, quantity_quote, quantity_contract) = calc_quantity_and_volume(
+            EXCHANGE_NAME,
+            market_type,
+            &pair,
+            price,
+            quantity,
+            timestamp,
+        )?;
+        Ok(Order { price, quantity_base, quantity_quote, quantity_contract, price_exact: parse_exact(&raw_order[0]), quantity_exact: parse_exact(&raw_order[1]) })
     };
 
     let orderbook_msg = OrderBookMsg {
@@ -242,11 +293,21 @@ pub(super) fn parse_l2_snapshot(
         symbol: symbol.to_string(),
         pair: pair.clone(),
         msg_type: MessageType::L2Snapshot,
-        timestamp: rs_msg.data.timestamp.parse::<i64>().unwrap(),
+        timestamp,
         seq_id: None,
         prev_seq_id: None,
-        asks: rs_msg.data.asks.iter().map(parse_order).collect(),
-        bids: rs_msg.data.bids.iter().map(parse_order).collect(),
+        asks: rs_msg
+            .data
+            .asks
+            .iter()
+            .map(parse_order)
+            .collect::<Result<Vec<Order>, ParseError>>()?,
+        bids: rs_msg
+            .data
+            .bids
+            .iter()
+            .map(parse_order)
+            .collect::<Result<Vec<Order>, ParseError>>()?,
         snapshot: true,
         json: serde_json::to_string(&rs_msg).unwrap(),
     };
@@ -257,49 +318,109 @@ pub(super) fn parse_l2_snapshot(
 /// docs：
 /// * https://bitgetlimited.github.io/apidoc/en/spot/#candlesticks-channel
 /// * https://bitgetlimited.github.io/apidoc/en/mix/#candlesticks-channel
-pub(super) fn parse_candlestick(msg: &str) -> Result<Vec<CandlestickMsg>, SimpleError> {
+pub(super) fn parse_candlestick(msg: &str) -> Result<Vec<CandlestickMsg>, ParseError> {
     let ws_msg =
-        serde_json::from_str::<WebsocketMsg<[String; 6]>>(msg).map_err(SimpleError::from)?;
-    debug_assert!(ws_msg.arg.channel.starts_with("candle"));
-    let period = ws_msg.arg.channel.as_str().strip_prefix("candle").unwrap();
-    let m_seconds = match period.to_string().pop().unwrap() {
-        's' => period.strip_suffix('s').unwrap().parse::<i64>().unwrap() * 1000,
-        'm' => period.strip_suffix('m').unwrap().parse::<i64>().unwrap() * 1000 * 60,
-        'd' => period.strip_suffix('d').unwrap().parse::<i64>().unwrap() * 1000 * 24 * 60 * 60,
-        _ => 0,
-    };
-
-    let (market_type, symbol) = extract_market_type_and_symbol(&ws_msg.arg);
-    let pair = crypto_pair::normalize_pair(&symbol, EXCHANGE_NAME).unwrap();
+        serde_json::from_str::<WebsocketMsg<[String; 6]>>(msg).map_err(ParseError::from)?;
+    if !ws_msg.arg.channel.starts_with("candle") {
+        return Err(ParseError::unsupported(format!("Unsupported channel {}", ws_msg.arg.channel)));
+    }
+    let (market_type, symbol) = extract_market_type_and_symbol(&ws_msg.arg)?;
+    let period = parse_candle_period(EXCHANGE_NAME, market_type, &ws_msg.arg.channel)?;
+    let pair = crypto_pair::normalize_pair(&symbol, EXCHANGE_NAME)
+        .ok_or_else(|| ParseError::symbol_normalization(&symbol))?;
     let candlestick_msgs: Vec<CandlestickMsg> = ws_msg
         .data
         .into_iter()
-        .map(|raw_candlestickmsg| {
-            let timestamp = raw_candlestickmsg[0].parse::<i64>().unwrap();
-            let open = raw_candlestickmsg[1].parse::<f64>().unwrap();
-            let high = raw_candlestickmsg[2].parse::<f64>().unwrap();
-            let low = raw_candlestickmsg[3].parse::<f64>().unwrap();
-            let close = raw_candlestickmsg[4].parse::<f64>().unwrap();
-            let volume = raw_candlestickmsg[5].parse::<f64>().unwrap();
+        .map(|raw_candlestickmsg| -> Result<CandlestickMsg, ParseError> {
+            let timestamp = raw_candlestickmsg[0].parse::<i64>()?;
+            let open = raw_candlestickmsg[1].parse::<f64>()?;
+            let high = raw_candlestickmsg[2].parse::<f64>()?;
+            let low = raw_candlestickmsg[3].parse::<f64>()?;
+            let close = raw_candlestickmsg[4].parse::<f64>()?;
+            let volume = raw_candlestickmsg[5].parse::<f64>()?;
 
-            CandlestickMsg {
+            Ok(CandlestickMsg {
                 exchange: EXCHANGE_NAME.to_string(),
                 market_type,
                 msg_type: MessageType::Candlestick,
                 symbol: symbol.clone(),
                 pair: pair.clone(),
                 timestamp,
-                period: period.to_string(),
-                begin_time: timestamp - m_seconds,
+                period,
+                // the start time of the candle
+                begin_time: timestamp,
+                end_time: period.end_time(timestamp),
                 open,
                 high,
                 low,
                 close,
                 volume,
                 quote_volume: None,
+                is_closed: None,
                 json: serde_json::to_string(&raw_candlestickmsg).unwrap(),
-            }
+            })
         })
-        .collect();
+        .collect::<Result<Vec<CandlestickMsg>, ParseError>>()?;
     Ok(candlestick_msgs)
 }
+
+/// docs:
+/// * https://bitgetlimited.github.io/apidoc/en/spot/#tickers-channel
+/// * https://bitgetlimited.github.io/apidoc/en/mix/#tickers-channel
+pub(super) fn parse_ticker(msg: &str) -> Result<Vec<TickerMsg>, ParseError> {
+    let ws_msg = serde_json::from_str::<WebsocketMsg<RawTickerMsg>>(msg)
+        .map_err(|_e| ParseError::malformed("expected WebsocketMsg<RawTickerMsg>"))?;
+    if ws_msg.arg.channel != "ticker" {
+        return Err(ParseError::unsupported(format!("Unsupported channel {}", ws_msg.arg.channel)));
+    }
+    let (market_type, symbol) = extract_market_type_and_symbol(&ws_msg.arg)?;
+    let pair = crypto_pair::normalize_pair(&symbol, EXCHANGE_NAME)
+        .ok_or_else(|| ParseError::symbol_normalization(&symbol))?;
+    let parse_f64 = |s: &str| -> Result<f64, ParseError> {
+        s.parse::<f64>().map_err(|_e| ParseError::malformed(format!("Failed to parse {s} as f64")))
+    };
+
+    let mut tickers = Vec::with_capacity(ws_msg.data.len());
+    for raw_ticker in ws_msg.data.into_iter() {
+        let close = parse_f64(&raw_ticker.last)?;
+        let open = if let Some(open24h) = raw_ticker.open24h.as_ref() {
+            parse_f64(open24h)?
+        } else if let Some(percent) = raw_ticker.priceChangePercent.as_ref() {
+            close / (1.0 + parse_f64(percent)?)
+        } else {
+            return Err(ParseError::unsupported("No open price"));
+        };
+        let timestamp = raw_ticker
+            .ts
+            .or(raw_ticker.systemTime)
+            .ok_or_else(ParseError::missing_timestamp)?;
+        let open_interest = raw_ticker.holding.as_ref().map(|x| parse_f64(x)).transpose()?;
+
+        tickers.push(TickerMsg {
+            exchange: EXCHANGE_NAME.to_string(),
+            market_type,
+            symbol: symbol.clone(),
+            pair: pair.clone(),
+            msg_type: MessageType::Ticker,
+            timestamp,
+            open,
+            high: parse_f64(&raw_ticker.high24h)?,
+            low: parse_f64(&raw_ticker.low24h)?,
+            close,
+            volume: parse_f64(&raw_ticker.baseVolume)?,
+            quote_volume: parse_f64(&raw_ticker.quoteVolume)?,
+            last_quantity: None,
+            best_bid_price: Some(parse_f64(&raw_ticker.bestBid)?),
+            best_bid_quantity: None,
+            best_ask_price: Some(parse_f64(&raw_ticker.bestAsk)?),
+            best_ask_quantity: None,
+            open_interest,
+            open_interest_quote: open_interest.map(|x| x * close),
+            json: serde_json::to_string(&raw_ticker).unwrap(),
+        });
+    }
+    if tickers.len() == 1 {
+        tickers[0].json = msg.to_string();
+    }
+    Ok(tickers)
+}
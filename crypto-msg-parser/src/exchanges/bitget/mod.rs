@@ -6,75 +6,77 @@ use std::collections::HashMap;
 use crypto_market_type::MarketType;
 use crypto_message::CandlestickMsg;
 use crypto_msg_type::MessageType;
-use serde::{Deserialize, Serialize};
 use serde_json::Value;
 
-use crate::{FundingRateMsg, OrderBookMsg, TradeMsg};
-
-use simple_error::SimpleError;
+use crate::{
+    exchanges::utils::json_str, FundingRateMsg, OrderBookMsg, ParseError, TickerMsg, TradeMsg,
+};
 
 const EXCHANGE_NAME: &str = "bitget";
 
-pub(crate) fn extract_symbol(market_type: MarketType, msg: &str) -> Result<String, SimpleError> {
+pub(crate) fn extract_symbol(market_type: MarketType, msg: &str) -> Result<String, ParseError> {
     let obj = serde_json::from_str::<HashMap<String, Value>>(msg)
-        .map_err(|_e| SimpleError::new(format!("Failed to parse JSON string {msg}")))?;
+        .map_err(|_e| ParseError::malformed("invalid JSON"))?;
     if !obj.contains_key("data") {
-        return Err(SimpleError::new(format!("No data field in {msg}")));
+        return Err(ParseError::unsupported("No data field"));
     }
     if obj.contains_key("table") {
         before20220429::extract_symbol(market_type, msg)
     } else if obj.contains_key("arg") {
         bitget_mix::extract_symbol(msg)
     } else if obj.contains_key("code") && obj.contains_key("msg") {
-        let code = obj["code"].as_str().unwrap();
+        let code = json_str(&obj["code"], "code")?;
         // from RESTful API
         if code == "00000" {
-            let symbol = obj["data"].get("symbol").map(|x| x.as_str().unwrap());
+            let symbol = obj["data"].get("symbol").map(|x| json_str(x, "symbol")).transpose()?;
             if let Some(symbol) = symbol { Ok(symbol.to_string()) } else { Ok("NONE".to_string()) }
         } else {
-            Err(SimpleError::new(format!("This is a failed HTTP response {msg}")))
+            Err(ParseError::unsupported("This is a failed HTTP response"))
         }
     } else {
-        Err(SimpleError::new(format!("Failed to extract symbol from {msg}")))
+        Err(ParseError::unsupported("Failed to extract symbol"))
     }
 }
 
 pub(crate) fn extract_timestamp(
     market_type: MarketType,
     msg: &str,
-) -> Result<Option<i64>, SimpleError> {
+) -> Result<Option<i64>, ParseError> {
     let obj = serde_json::from_str::<HashMap<String, Value>>(msg)
-        .map_err(|_e| SimpleError::new(format!("Failed to parse JSON string {msg}")))?;
+        .map_err(|_e| ParseError::malformed("invalid JSON"))?;
     if !obj.contains_key("data") {
-        return Err(SimpleError::new(format!("No data field in {msg}")));
+        return Err(ParseError::unsupported("No data field"));
     }
     if obj.contains_key("table") {
         before20220429::extract_timestamp(market_type, msg)
     } else if obj.contains_key("arg") {
         bitget_mix::extract_timestamp(msg)
     } else if obj.contains_key("code") {
-        let code = obj["code"].as_str().unwrap();
+        let code = json_str(&obj["code"], "code")?;
         // from RESTful API
         if code == "00000" {
-            let timestamp =
-                obj["data"].get("timestamp").map(|x| x.as_str().unwrap().parse::<i64>().unwrap());
+            let timestamp = obj["data"]
+                .get("timestamp")
+                .map(|x| json_str(x, "timestamp")?.parse::<i64>().map_err(ParseError::from))
+                .transpose()?;
             Ok(timestamp)
         } else {
-            Err(SimpleError::new(format!("This is a failed HTTP response {msg}")))
+            Err(ParseError::unsupported("This is a failed HTTP response"))
         }
     } else {
-        Err(SimpleError::new(format!("Failed to extract timestamp from {msg}")))
+        Err(ParseError::missing_timestamp())
     }
 }
 
 pub(crate) fn get_msg_type(msg: &str) -> MessageType {
-    let obj = serde_json::from_str::<HashMap<String, Value>>(msg)
-        .map_err(|_e| SimpleError::new(format!("Failed to parse JSON string {msg}")))
-        .unwrap();
+    let obj = match serde_json::from_str::<HashMap<String, Value>>(msg) {
+        Ok(obj) => obj,
+        Err(_) => return MessageType::Other,
+    };
     if obj.contains_key("data") && obj.contains_key("table") {
         before20220429::get_msg_type(msg)
     } else if obj.contains_key("data") && obj.contains_key("arg") {
-        let channel = obj["arg"]["channel"].as_str().unwrap();
+        let channel = obj["arg"]["channel"].as_str().unwrap_or_default();
         match channel {
             "trade" => MessageType::Trade,
             "books" => MessageType::L2Event,
@@ -93,57 +95,37 @@ pub(crate) fn get_msg_type(msg: &str) -> MessageType {
     }
 }
 
-#[derive(Serialize, Deserialize)]
-#[allow(non_snake_case)]
-struct Arg {
-    instType: String,
-    channel: String,
-    instId: String,
-    #[serde(flatten)]
-    extra: HashMap<String, Value>,
-}
-
-#[derive(Serialize, Deserialize)]
-struct WebsocketMsg<T: Sized> {
-    action: String,
-    arg: Arg,
-    data: Vec<T>,
-}
-
-pub(crate) fn parse_trade(
-    market_type: MarketType,
-    msg: &str,
-) -> Result<Vec<TradeMsg>, SimpleError> {
+pub(crate) fn parse_trade(market_type: MarketType, msg: &str) -> Result<Vec<TradeMsg>, ParseError> {
     let obj = serde_json::from_str::<HashMap<String, Value>>(msg)
-        .map_err(|_e| SimpleError::new(format!("Failed to parse JSON string {msg}")))?;
+        .map_err(|_e| ParseError::malformed("invalid JSON"))?;
     if obj.contains_key("data") && obj.contains_key("table") {
         before20220429::parse_trade(market_type, msg)
     } else if obj.contains_key("data") && obj.contains_key("arg") {
         bitget_mix::parse_trade(msg)
     } else {
-        Err(SimpleError::new(format!("Unsupported Trade message {msg}")))
+        Err(ParseError::unsupported("Unsupported Trade message"))
     }
 }
 
 pub(crate) fn parse_l2(
     market_type: MarketType,
     msg: &str,
-) -> Result<Vec<OrderBookMsg>, SimpleError> {
+) -> Result<Vec<OrderBookMsg>, ParseError> {
     let obj = serde_json::from_str::<HashMap<String, Value>>(msg)
-        .map_err(|_e| SimpleError::new(format!("Failed to parse JSON string {msg}")))?;
+        .map_err(|_e| ParseError::malformed("invalid JSON"))?;
     if obj.contains_key("data") && obj.contains_key("table") {
         before20220429::parse_l2(market_type, msg)
     } else if obj.contains_key("data") && obj.contains_key("arg") {
         bitget_mix::parse_l2(msg)
     } else {
-        Err(SimpleError::new(format!("Unsupported L2Event message {msg}")))
+        Err(ParseError::unsupported("Unsupported L2Event message"))
     }
 }
 
 pub(crate) fn parse_l2_topk(
     market_type: MarketType,
     msg: &str,
-) -> Result<Vec<OrderBookMsg>, SimpleError> {
+) -> Result<Vec<OrderBookMsg>, ParseError> {
     match parse_l2(market_type, msg) {
         Ok(mut orderbooks) => {
             for ob in orderbooks.iter_mut() {
@@ -159,30 +141,43 @@ pub(crate) fn parse_l2_topk(
 pub(crate) fn parse_funding_rate(
     market_type: MarketType,
     msg: &str,
-) -> Result<Vec<FundingRateMsg>, SimpleError> {
+) -> Result<Vec<FundingRateMsg>, ParseError> {
     let obj = serde_json::from_str::<HashMap<String, Value>>(msg)
-        .map_err(|_e| SimpleError::new(format!("Failed to parse JSON string {msg}")))?;
+        .map_err(|_e| ParseError::malformed("invalid JSON"))?;
     if obj.contains_key("data") && obj.contains_key("table") {
         before20220429::parse_funding_rate(market_type, msg)
     } else if obj.contains_key("data") && obj.contains_key("arg") {
-        Err(SimpleError::new("Not implemented"))
+        Err(ParseError::unsupported("Not implemented"))
+    } else {
+        Err(ParseError::unsupported("Unsupported FundingRate message"))
+    }
+}
+
+pub(crate) fn parse_ticker(
+    _market_type: MarketType,
+    msg: &str,
+) -> Result<Vec<TickerMsg>, ParseError> {
+    let obj = serde_json::from_str::<HashMap<String, Value>>(msg)
+        .map_err(|_e| ParseError::malformed("invalid JSON"))?;
+    if obj.contains_key("data") && obj.contains_key("arg") {
+        bitget_mix::parse_ticker(msg)
     } else {
-        Err(SimpleError::new(format!("Unsupported FundingRate message {msg}")))
+        Err(ParseError::unsupported("Unsupported ticker message"))
     }
 }
 
 pub(crate) fn parse_candlestick(
     market_type: MarketType,
     msg: &str,
-) -> Result<Vec<CandlestickMsg>, SimpleError> {
+) -> Result<Vec<CandlestickMsg>, ParseError> {
     let obj = serde_json::from_str::<HashMap<String, Value>>(msg)
-        .map_err(|_e| SimpleError::new(format!("failed to parse JSON string{msg}")))?;
+        .map_err(|_e| ParseError::malformed("failed to parse JSON string"))?;
     if obj.contains_key("data") && obj.contains_key("table") {
         before20220429::parse_candlestick(market_type, msg)
     } else if obj.contains_key("data") && obj.contains_key("arg") {
         bitget_mix::parse_candlestick(msg)
     } else {
-        Err(SimpleError::new(format!("Unsupported Candlestick message {msg}")))
+        Err(ParseError::unsupported("Unsupported Candlestick message"))
     }
 }
 
@@ -190,6 +185,6 @@ pub(crate) fn parse_l2_snapshot(
     market_type: MarketType,
     msg: &str,
     symbol: Option<&str>,
-) -> Result<Vec<OrderBookMsg>, SimpleError> {
+) -> Result<Vec<OrderBookMsg>, ParseError> {
     bitget_mix::parse_l2_snapshot(market_type, msg, symbol)
 }
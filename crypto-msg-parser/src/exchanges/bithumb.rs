@@ -1,11 +1,14 @@
 use crypto_market_type::MarketType;
 use crypto_msg_type::MessageType;
 
-use crypto_message::{Order, OrderBookMsg, TradeMsg, TradeSide};
+use crypto_message::{CandlestickMsg, Order, OrderBookMsg, TickerMsg, TradeMsg, TradeSide};
 
+use crate::{
+    ParseError,
+    exchanges::utils::{infer_candle_period, json_array, json_i64, json_str, parse_exact},
+};
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
-use simple_error::SimpleError;
 use std::collections::HashMap;
 
 const EXCHANGE_NAME: &str = "bithumb";
@@ -34,6 +37,40 @@ struct SpotOrderbookMsg {
     extra: HashMap<String, Value>,
 }
 
+// see https://github.com/bithumb-pro/bithumb.pro-official-api-docs/blob/master/ws-api.md#ticker-the-last-24h-ticker-data
+#[derive(Serialize, Deserialize)]
+struct SpotTickerMsg {
+    c: String, // last price
+    h: String,
+    l: String,
+    p: String, // 24h change ratio
+    symbol: String,
+    t: String, // 24h volume in quote currency
+    v: String, // 24h volume in base currency
+    #[serde(flatten)]
+    extra: HashMap<String, Value>,
+}
+
+// see https://github.com/bithumb-pro/bithumb.pro-official-api-docs/blob/master/rest-api.md#6-kline
+#[derive(Serialize, Deserialize)]
+struct SpotKlineMsg {
+    c: String,
+    h: String,
+    l: String,
+    o: String,
+    t: String, // Unix timestamp in seconds
+    v: String,
+    #[serde(flatten)]
+    extra: HashMap<String, Value>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct RestfulMsg<T: Sized> {
+    code: String,
+    data: T,
+    timestamp: i64,
+}
+
 #[derive(Serialize, Deserialize)]
 struct WebsocketMsg<T: Sized> {
     code: String,
@@ -42,7 +79,7 @@ struct WebsocketMsg<T: Sized> {
     topic: String,
 }
 
-pub(crate) fn extract_symbol(_market_type: MarketType, msg: &str) -> Result<String, SimpleError> {
+pub(crate) fn extract_symbol(_market_type: MarketType, msg: &str) -> Result<String, ParseError> {
     #[derive(Serialize, Deserialize)]
     struct RawMsg {
         code: String,
@@ -50,75 +87,101 @@ pub(crate) fn extract_symbol(_market_type: MarketType, msg: &str) -> Result<Stri
         timestamp: i64,
     }
     let raw_msg = serde_json::from_str::<RawMsg>(msg)
-        .map_err(|_e| SimpleError::new(format!("Failed to deserialize {msg} to RawMsg")))?;
+        .map_err(|_e| ParseError::malformed("expected RawMsg"))?;
     if raw_msg.data.is_object() {
-        Ok(raw_msg.data["symbol"].as_str().unwrap().to_string())
+        Ok(json_str(&raw_msg.data["symbol"], "symbol")?.to_string())
     } else if raw_msg.data.is_array() {
-        let arr = raw_msg.data.as_array().unwrap();
-        let symbol = arr.iter().map(|v| v["symbol"].as_str().unwrap()).next().unwrap();
+        let arr = json_array(&raw_msg.data, "data")?;
+        if arr.iter().all(|v| v.get("symbol").is_none()) {
+            // RESTful klines have no symbol
+            return Ok("NONE".to_string());
+        }
+        let symbol = json_str(&arr[0]["symbol"], "symbol")?;
         Ok(symbol.to_string())
     } else {
-        Err(SimpleError::new(format!("Unknown message format: {msg}")))
+        Err(ParseError::unsupported("Unknown message format"))
     }
 }
 
 pub(crate) fn extract_timestamp(
     _market_type: MarketType,
     msg: &str,
-) -> Result<Option<i64>, SimpleError> {
+) -> Result<Option<i64>, ParseError> {
     let obj = serde_json::from_str::<HashMap<String, Value>>(msg)
-        .map_err(|_e| SimpleError::new(format!("Failed to parse the JSON string {msg}")))?;
-    Ok(obj.get("timestamp").map(|x| x.as_i64().unwrap()))
+        .map_err(|_e| ParseError::malformed("invalid JSON"))?;
+    obj.get("timestamp").map(|x| json_i64(x, "timestamp")).transpose()
 }
 
-pub(crate) fn parse_trade(
-    market_type: MarketType,
-    msg: &str,
-) -> Result<Vec<TradeMsg>, SimpleError> {
-    let ws_msg = serde_json::from_str::<WebsocketMsg<Value>>(msg).map_err(|_e| {
-        SimpleError::new(format!("Failed to deserialize {msg} to WebsocketMsg<Value>"))
-    })?;
+pub(crate) fn get_msg_type(msg: &str) -> MessageType {
+    if let Ok(obj) = serde_json::from_str::<HashMap<String, Value>>(msg) {
+        if let Some(topic) = obj.get("topic").and_then(|x| x.as_str()) {
+            match topic {
+                "TRADE" => MessageType::Trade,
+                "ORDERBOOK" => MessageType::L2Event,
+                "TICKER" => MessageType::Ticker,
+                _ => MessageType::Other,
+            }
+        } else if obj
+            .get("data")
+            .is_some_and(|data| data.get("b").is_some() && data.get("s").is_some())
+        {
+            // RESTful orderbook snapshot
+            MessageType::L2Snapshot
+        } else if obj.get("data").and_then(|data| data.as_array()).is_some_and(|arr| {
+            !arr.is_empty() && arr.iter().all(|x| x.get("o").is_some() && x.get("t").is_some())
+        }) {
+            // RESTful klines
+            MessageType::Candlestick
+        } else {
+            MessageType::Other
+        }
+    } else {
+        MessageType::Other
+    }
+}
+
+pub(crate) fn parse_trade(market_type: MarketType, msg: &str) -> Result<Vec<TradeMsg>, ParseError> {
+    let ws_msg = serde_json::from_str::<WebsocketMsg<Value>>(msg)
+        .map_err(|_e| ParseError::malformed("expected WebsocketMsg<Value>"))?;
     let raw_trades = if ws_msg.code == "00006" {
         // snapshot
-        let ws_msg =
-            serde_json::from_str::<WebsocketMsg<Vec<SpotTradeMsg>>>(msg).map_err(|_e| {
-                SimpleError::new(format!(
-                    "Failed to deserialize {msg} to WebsocketMsg<Vec<SpotTradeMsg>>"
-                ))
-            })?;
+        let ws_msg = serde_json::from_str::<WebsocketMsg<Vec<SpotTradeMsg>>>(msg)
+            .map_err(|_e| ParseError::malformed("expected WebsocketMsg<Vec<SpotTradeMsg>>"))?;
         ws_msg.data
     } else if ws_msg.code == "00007" {
         // updates
-        let ws_msg = serde_json::from_str::<WebsocketMsg<SpotTradeMsg>>(msg).map_err(|_e| {
-            SimpleError::new(format!("Failed to deserialize {msg} to WebsocketMsg<SpotTradeMsg>"))
-        })?;
+        let ws_msg = serde_json::from_str::<WebsocketMsg<SpotTradeMsg>>(msg)
+            .map_err(|_e| ParseError::malformed("expected WebsocketMsg<SpotTradeMsg>"))?;
         vec![ws_msg.data]
     } else {
-        return Err(SimpleError::new(format!("Invalid trade msg {msg}")));
+        return Err(ParseError::unsupported("Invalid trade msg"));
     };
     let mut trades: Vec<TradeMsg> = raw_trades
         .into_iter()
-        .map(|raw_trade| {
-            let price = raw_trade.p.parse::<f64>().unwrap();
-            let quantity = raw_trade.v.parse::<f64>().unwrap();
-            let timestamp = raw_trade.t.parse::<i64>().unwrap() * 1000;
-            TradeMsg {
+        .map(|raw_trade| -> Result<TradeMsg, ParseError> {
+            let price = raw_trade.p.parse::<f64>()?;
+            let quantity = raw_trade.v.parse::<f64>()?;
+            let timestamp = raw_trade.t.parse::<i64>()? * 1000;
+            Ok(TradeMsg {
                 exchange: EXCHANGE_NAME.to_string(),
                 market_type,
                 symbol: raw_trade.symbol.to_string(),
-                pair: crypto_pair::normalize_pair(&raw_trade.symbol, EXCHANGE_NAME).unwrap(),
+                pair: crypto_pair::normalize_pair(&raw_trade.symbol, EXCHANGE_NAME)
+                    .ok_or_else(|| ParseError::symbol_normalization(&raw_trade.symbol))?,
                 msg_type: MessageType::Trade,
                 timestamp,
                 price,
                 quantity_base: quantity,
                 quantity_quote: price * quantity,
                 quantity_contract: None,
+                price_exact: parse_exact(&raw_trade.p),
+                quantity_exact: parse_exact(&raw_trade.v),
                 side: if raw_trade.s == "sell" { TradeSide::Sell } else { TradeSide::Buy },
                 trade_id: raw_trade.ver.clone(),
                 json: serde_json::to_string(&raw_trade).unwrap(),
-            }
+            })
         })
-        .collect();
+        .collect::<Result<Vec<TradeMsg>, ParseError>>()?;
     if trades.len() == 1 {
         trades[0].json = msg.to_string();
     }
@@ -128,33 +191,36 @@ pub(crate) fn parse_trade(
 pub(crate) fn parse_l2(
     market_type: MarketType,
     msg: &str,
-) -> Result<Vec<OrderBookMsg>, SimpleError> {
-    let ws_msg = serde_json::from_str::<WebsocketMsg<SpotOrderbookMsg>>(msg).map_err(|_e| {
-        SimpleError::new(format!("Failed to deserialize {msg} to WebsocketMsg<SpotOrderbookMsg>"))
-    })?;
-    debug_assert_eq!(ws_msg.topic, "ORDERBOOK");
+) -> Result<Vec<OrderBookMsg>, ParseError> {
+    let ws_msg = serde_json::from_str::<WebsocketMsg<SpotOrderbookMsg>>(msg)
+        .map_err(|_e| ParseError::malformed("expected WebsocketMsg<SpotOrderbookMsg>"))?;
+    if ws_msg.topic != "ORDERBOOK" {
+        return Err(ParseError::unsupported(format!("Unsupported topic {}", ws_msg.topic)));
+    }
     let snapshot = if ws_msg.code == "00006" {
         true
     } else if ws_msg.code == "00007" {
         false
     } else {
-        return Err(SimpleError::new(format!("Unknown code {}", ws_msg.code)));
+        return Err(ParseError::unsupported(format!("Unknown code {}", ws_msg.code)));
     };
     let symbol = ws_msg.data.symbol;
     let pair = crypto_pair::normalize_pair(&symbol, EXCHANGE_NAME)
-        .ok_or_else(|| SimpleError::new(format!("Failed to normalize {symbol} from {msg}")))?;
+        .ok_or_else(|| ParseError::symbol_normalization(&symbol))?;
     let timestamp = ws_msg.timestamp;
 
-    let parse_order = |raw_order: &[String; 2]| -> Order {
-        let price = raw_order[0].parse::<f64>().unwrap();
-        let quantity_base = raw_order[1].parse::<f64>().unwrap();
+    let parse_order = |raw_order: &[String; 2]| -> Result<Order, ParseError> {
+        let price = raw_order[0].parse::<f64>()?;
+        let quantity_base = raw_order[1].parse::<f64>()?;
 
-        Order {
+        Ok(Order {
             price,
             quantity_base,
             quantity_quote: price * quantity_base,
             quantity_contract: None,
-        }
+            price_exact: parse_exact(&raw_order[0]),
+            quantity_exact: parse_exact(&raw_order[1]),
+        })
     };
 
     let orderbook = OrderBookMsg {
@@ -166,11 +232,102 @@ pub(crate) fn parse_l2(
         timestamp,
         seq_id: ws_msg.data.ver.parse().ok(),
         prev_seq_id: None,
-        asks: ws_msg.data.s.iter().map(parse_order).collect(),
-        bids: ws_msg.data.b.iter().map(parse_order).collect(),
+        asks: ws_msg.data.s.iter().map(parse_order).collect::<Result<Vec<Order>, ParseError>>()?,
+        bids: ws_msg.data.b.iter().map(parse_order).collect::<Result<Vec<Order>, ParseError>>()?,
         snapshot,
         json: msg.to_string(),
     };
 
     Ok(vec![orderbook])
 }
+
+pub(crate) fn parse_ticker(
+    market_type: MarketType,
+    msg: &str,
+) -> Result<Vec<TickerMsg>, ParseError> {
+    let ws_msg = serde_json::from_str::<WebsocketMsg<SpotTickerMsg>>(msg)
+        .map_err(|_e| ParseError::malformed("expected WebsocketMsg<SpotTickerMsg>"))?;
+    let raw_ticker = ws_msg.data;
+    let close = raw_ticker.c.parse::<f64>()?;
+
+    let ticker_msg = TickerMsg {
+        exchange: EXCHANGE_NAME.to_string(),
+        market_type,
+        symbol: raw_ticker.symbol.clone(),
+        pair: crypto_pair::normalize_pair(&raw_ticker.symbol, EXCHANGE_NAME)
+            .ok_or_else(|| ParseError::symbol_normalization(&raw_ticker.symbol))?,
+        msg_type: MessageType::Ticker,
+        timestamp: ws_msg.timestamp,
+        // Bithumb has only the change ratio, the open price is derived from it
+        open: close / (1.0 + raw_ticker.p.parse::<f64>()?),
+        high: raw_ticker.h.parse::<f64>()?,
+        low: raw_ticker.l.parse::<f64>()?,
+        close,
+        volume: raw_ticker.v.parse::<f64>()?,
+        quote_volume: raw_ticker.t.parse::<f64>()?,
+        last_quantity: None,
+        best_bid_price: None,
+        best_bid_quantity: None,
+        best_ask_price: None,
+        best_ask_quantity: None,
+        open_interest: None,
+        open_interest_quote: None,
+        json: msg.to_string(),
+    };
+
+    Ok(vec![ticker_msg])
+}
+
+pub(crate) fn parse_candlestick(
+    market_type: MarketType,
+    msg: &str,
+    symbol: Option<&str>,
+) -> Result<Vec<CandlestickMsg>, ParseError> {
+    let symbol = symbol.ok_or_else(|| ParseError::unsupported("symbol is required"))?;
+    let pair = crypto_pair::normalize_pair(symbol, EXCHANGE_NAME)
+        .ok_or_else(|| ParseError::symbol_normalization(symbol))?;
+    let rest_msg = serde_json::from_str::<RestfulMsg<Vec<SpotKlineMsg>>>(msg)
+        .map_err(|_e| ParseError::malformed("expected RestfulMsg<Vec<SpotKlineMsg>>"))?;
+    if rest_msg.code != "0" {
+        return Err(ParseError::unsupported(format!(
+            "Error HTTP response, code {}",
+            rest_msg.code
+        )));
+    }
+    let begin_times = rest_msg
+        .data
+        .iter()
+        .map(|raw_kline| raw_kline.t.parse::<i64>().map(|t| t * 1000))
+        .collect::<Result<Vec<i64>, _>>()?;
+    // the kline type is a request parameter only
+    let period = infer_candle_period(&begin_times)?;
+
+    let candlesticks = rest_msg
+        .data
+        .into_iter()
+        .zip(begin_times)
+        .map(|(raw_kline, begin_time)| -> Result<CandlestickMsg, ParseError> {
+            Ok(CandlestickMsg {
+                exchange: EXCHANGE_NAME.to_string(),
+                market_type,
+                msg_type: MessageType::Candlestick,
+                symbol: symbol.to_string(),
+                pair: pair.clone(),
+                timestamp: rest_msg.timestamp,
+                period,
+                begin_time,
+                end_time: period.end_time(begin_time),
+                open: raw_kline.o.parse::<f64>()?,
+                high: raw_kline.h.parse::<f64>()?,
+                low: raw_kline.l.parse::<f64>()?,
+                close: raw_kline.c.parse::<f64>()?,
+                volume: raw_kline.v.parse::<f64>()?,
+                quote_volume: None,
+                is_closed: None,
+                json: serde_json::to_string(&raw_kline).unwrap(),
+            })
+        })
+        .collect::<Result<Vec<CandlestickMsg>, ParseError>>()?;
+
+    Ok(candlesticks)
+}
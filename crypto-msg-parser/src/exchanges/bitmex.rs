@@ -1,17 +1,23 @@
 use crypto_market_type::MarketType;
 use crypto_message::{
-    BboMsg, CandlestickMsg, FundingRateMsg, Order, OrderBookMsg, TradeMsg, TradeSide,
+    BboMsg, CandlestickMsg, FundingRateMsg, IndexPriceMsg, LiquidationMsg, MarkPriceMsg,
+    OpenInterestMsg, Order, OrderBookMsg, TradeMsg, TradeSide,
 };
 use crypto_msg_type::MessageType;
 use crypto_pair::get_market_type;
 
-use crate::exchanges::utils::{calc_quantity_and_volume, http_get, round};
+use crate::{
+    ParseError,
+    exchanges::utils::{
+        annualize_funding_rate, calc_open_interest, calc_quantity_and_volume, http_get, json_str,
+        parse_candle_period, round,
+    },
+};
 
 use chrono::DateTime;
 use once_cell::sync::Lazy;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
-use simple_error::SimpleError;
 use std::collections::{BTreeMap, HashMap};
 
 const EXCHANGE_NAME: &str = "bitmex";
@@ -586,6 +592,32 @@ struct RawFundingRateMsg {
     extra: HashMap<String, Value>,
 }
 
+// see https://www.bitmex.com/app/wsAPI#Liquidation
+#[derive(Serialize, Deserialize)]
+#[allow(non_snake_case)]
+struct RawLiquidationMsg {
+    orderID: String,
+    symbol: Option<String>, // None if action = update or delete
+    side: Option<String>,   // Sell, Buy
+    price: Option<f64>,
+    leavesQty: Option<f64>,
+    #[serde(flatten)]
+    extra: HashMap<String, Value>,
+}
+
+// see https://www.bitmex.com/app/wsAPI#Instrument
+#[derive(Serialize, Deserialize)]
+#[allow(non_snake_case)]
+struct RawInstrumentMsg {
+    symbol: String,
+    openInterest: Option<f64>, // None if unchanged in an update
+    markPrice: Option<f64>,
+    indicativeSettlePrice: Option<f64>, // Index price, None if unchanged in an update
+    timestamp: Option<String>,
+    #[serde(flatten)]
+    extra: HashMap<String, Value>,
+}
+
 #[derive(Serialize, Deserialize)]
 struct WebsocketMsg<T: Sized> {
     table: String,
@@ -593,37 +625,39 @@ struct WebsocketMsg<T: Sized> {
     data: Vec<T>,
 }
 
-pub(crate) fn extract_symbol(_market_type: MarketType, msg: &str) -> Result<String, SimpleError> {
+pub(crate) fn extract_symbol(_market_type: MarketType, msg: &str) -> Result<String, ParseError> {
     if msg.starts_with(r#"[{"symbol":"#) {
         // l2_snapshot
-        let arr = serde_json::from_str::<Vec<HashMap<String, Value>>>(msg).unwrap();
-        let symbol = arr[0]["symbol"].as_str().unwrap();
-        return Ok(symbol.to_string());
+        let arr = serde_json::from_str::<Vec<HashMap<String, Value>>>(msg)?;
+        let symbol = arr
+            .first()
+            .and_then(|x| x.get("symbol"))
+            .ok_or_else(|| ParseError::unsupported("data is empty array"))?;
+        return Ok(json_str(symbol, "symbol")?.to_string());
     }
-    let ws_msg = serde_json::from_str::<WebsocketMsg<Value>>(msg).map_err(|_e| {
-        SimpleError::new(format!("Failed to deserialize {msg} to WebsocketMsg<Value>"))
-    })?;
+    let ws_msg = serde_json::from_str::<WebsocketMsg<Value>>(msg)
+        .map_err(|_e| ParseError::malformed("expected WebsocketMsg<Value>"))?;
     if ws_msg.table == "funding" && ws_msg.data.len() > 1 {
         return Ok("ALL".to_string());
     }
-    let symbol = ws_msg.data.iter().map(|v| v["symbol"].as_str().unwrap()).next();
+    let symbol = ws_msg.data.first().map(|v| json_str(&v["symbol"], "symbol")).transpose()?;
     if let Some(symbol) = symbol {
         Ok(symbol.to_string())
     } else {
-        Err(SimpleError::new("data is empty array"))
+        Err(ParseError::unsupported("data is empty array"))
     }
 }
 
 pub(crate) fn extract_timestamp(
     _market_type: MarketType,
     msg: &str,
-) -> Result<Option<i64>, SimpleError> {
+) -> Result<Option<i64>, ParseError> {
     if msg.starts_with(r#"[{"symbol":"#) {
         // l2_snapshot doesn't have timestamp
         return Ok(None);
     }
     let ws_msg = serde_json::from_str::<WebsocketMsg<HashMap<String, Value>>>(msg)
-        .map_err(|_e| SimpleError::new(format!("Failed to parse the JSON string {msg}")))?;
+        .map_err(|_e| ParseError::malformed("invalid JSON"))?;
     if ws_msg.table == "funding" {
         return Ok(None);
     }
@@ -631,11 +665,12 @@ pub(crate) fn extract_timestamp(
         .data
         .iter()
         .filter(|x| x.contains_key("timestamp"))
-        .map(|x| {
-            DateTime::parse_from_rfc3339(x["timestamp"].as_str().unwrap())
-                .unwrap()
-                .timestamp_millis()
+        .map(|x| -> Result<i64, ParseError> {
+            Ok(DateTime::parse_from_rfc3339(json_str(&x["timestamp"], "timestamp")?)?
+                .timestamp_millis())
         })
+        .collect::<Result<Vec<i64>, ParseError>>()?
+        .into_iter()
         .max();
     Ok(timestamp)
 }
@@ -657,119 +692,369 @@ pub(crate) fn get_msg_type(msg: &str) -> MessageType {
     }
 }
 
-pub(crate) fn parse_trade(
-    market_type: MarketType,
-    msg: &str,
-) -> Result<Vec<TradeMsg>, SimpleError> {
+pub(crate) fn parse_trade(market_type: MarketType, msg: &str) -> Result<Vec<TradeMsg>, ParseError> {
     let ws_msg =
-        serde_json::from_str::<WebsocketMsg<RawTradeMsg>>(msg).map_err(SimpleError::from)?;
-    debug_assert_eq!("trade", ws_msg.table);
+        serde_json::from_str::<WebsocketMsg<RawTradeMsg>>(msg).map_err(ParseError::from)?;
+    if ws_msg.table != "trade" {
+        return Err(ParseError::unsupported(format!("Unsupported table {}", ws_msg.table)));
+    }
     let raw_trades = ws_msg.data;
     let mut trades: Vec<TradeMsg> = raw_trades
         .into_iter()
-        .map(|raw_trade| {
+        .map(|raw_trade| -> Result<TradeMsg, ParseError> {
             // assert_eq!(raw_trade.foreignNotional, raw_trade.homeNotional *
             // raw_trade.price); // tiny diff actually exists
-            let timestamp = DateTime::parse_from_rfc3339(&raw_trade.timestamp).unwrap();
+            let timestamp = DateTime::parse_from_rfc3339(&raw_trade.timestamp)?;
+            let pair = crypto_pair::normalize_pair(&raw_trade.symbol, EXCHANGE_NAME)
+                .ok_or_else(|| ParseError::symbol_normalization(&raw_trade.symbol))?;
             let market_type = if market_type == MarketType::Unknown {
                 get_market_type(&raw_trade.symbol, EXCHANGE_NAME, None)
             } else {
                 market_type
             };
-            TradeMsg {
+            Ok(TradeMsg {
                 exchange: EXCHANGE_NAME.to_string(),
                 market_type,
                 symbol: raw_trade.symbol.to_string(),
-                pair: crypto_pair::normalize_pair(&raw_trade.symbol, EXCHANGE_NAME).unwrap(),
+                pair,
                 msg_type: MessageType::Trade,
                 timestamp: timestamp.timestamp_millis(),
                 price: raw_trade.price,
                 quantity_base: raw_trade.homeNotional,
                 quantity_quote: raw_trade.foreignNotional,
                 quantity_contract: Some(raw_trade.size),
+                price_exact: None,
+                quantity_exact: None,
                 side: if raw_trade.side == "Sell" { TradeSide::Sell } else { TradeSide::Buy },
                 trade_id: raw_trade.trdMatchID.clone(),
                 json: serde_json::to_string(&raw_trade).unwrap(),
-            }
+            })
         })
-        .collect();
+        .collect::<Result<Vec<TradeMsg>, ParseError>>()?;
     if trades.len() == 1 {
         trades[0].json = msg.to_string();
     }
     Ok(trades)
 }
 
+// Perpetual swaps settle funding every 8 hours unless fundingInterval says otherwise
+const FUNDING_INTERVAL: i64 = 8 * 3600 * 1000;
+
 pub(crate) fn parse_funding_rate(
     market_type: MarketType,
     msg: &str,
     received_at: i64,
-) -> Result<Vec<FundingRateMsg>, SimpleError> {
+) -> Result<Vec<FundingRateMsg>, ParseError> {
     let ws_msg =
-        serde_json::from_str::<WebsocketMsg<RawFundingRateMsg>>(msg).map_err(SimpleError::from)?;
-    debug_assert_eq!("funding", ws_msg.table);
+        serde_json::from_str::<WebsocketMsg<RawFundingRateMsg>>(msg).map_err(ParseError::from)?;
+    if ws_msg.table != "funding" {
+        return Err(ParseError::unsupported(format!("Unsupported table {}", ws_msg.table)));
+    }
     let mut rates: Vec<FundingRateMsg> = ws_msg
         .data
         .into_iter()
-        .map(|raw_msg| {
-            let settlement_time = DateTime::parse_from_rfc3339(&raw_msg.timestamp).unwrap();
+        .map(|raw_msg| -> Result<FundingRateMsg, ParseError> {
+            let settlement_time = DateTime::parse_from_rfc3339(&raw_msg.timestamp)?;
+            // fundingInterval is a time since 2000-01-01T00:00:00Z, e.g., 2000-01-01T08:00:00.000Z
+            let funding_interval = Some(
+                DateTime::parse_from_rfc3339(&raw_msg.fundingInterval)?.timestamp_millis()
+                    - 946684800000,
+            )
+            .filter(|x| *x > 0)
+            .unwrap_or(FUNDING_INTERVAL);
             let market_type = if market_type == MarketType::Unknown {
                 get_market_type(&raw_msg.symbol, EXCHANGE_NAME, None)
             } else {
                 market_type
             };
-            FundingRateMsg {
+            let pair = crypto_pair::normalize_pair(&raw_msg.symbol, EXCHANGE_NAME)
+                .ok_or_else(|| ParseError::symbol_normalization(&raw_msg.symbol))?;
+            Ok(FundingRateMsg {
                 exchange: EXCHANGE_NAME.to_string(),
                 market_type,
                 symbol: raw_msg.symbol.clone(),
-                pair: crypto_pair::normalize_pair(&raw_msg.symbol, EXCHANGE_NAME).unwrap(),
+                pair,
                 msg_type: MessageType::FundingRate,
                 timestamp: received_at,
                 funding_rate: raw_msg.fundingRate,
                 funding_time: settlement_time.timestamp_millis(),
                 estimated_rate: None,
+                funding_interval,
+                funding_rate_cap: None,
+                funding_rate_floor: None,
+                annualized_rate: annualize_funding_rate(raw_msg.fundingRate, funding_interval),
                 json: serde_json::to_string(&raw_msg).unwrap(),
-            }
+            })
         })
-        .collect();
+        .collect::<Result<Vec<FundingRateMsg>, ParseError>>()?;
     if rates.len() == 1 {
         rates[0].json = msg.to_string();
     }
     Ok(rates)
 }
 
+/// Only `insert` actions are new liquidations, `update` and `delete` actions
+/// produce an empty vector.
+pub(crate) fn parse_liquidation(
+    market_type: MarketType,
+    msg: &str,
+    received_at: i64,
+) -> Result<Vec<LiquidationMsg>, ParseError> {
+    let ws_msg =
+        serde_json::from_str::<WebsocketMsg<RawLiquidationMsg>>(msg).map_err(ParseError::from)?;
+    if ws_msg.table != "liquidation" {
+        return Err(ParseError::unsupported(format!("Unsupported table {}", ws_msg.table)));
+    }
+    if ws_msg.action != "insert" {
+        return Ok(Vec::new());
+    }
+    let mut liquidations = ws_msg
+        .data
+        .into_iter()
+        .map(|raw_msg| -> Result<LiquidationMsg, ParseError> {
+            let symbol =
+                raw_msg.symbol.clone().ok_or_else(|| ParseError::malformed("no symbol"))?;
+            let pair = crypto_pair::normalize_pair(&symbol, EXCHANGE_NAME)
+                .ok_or_else(|| ParseError::symbol_normalization(&symbol))?;
+            let market_type = if market_type == MarketType::Unknown {
+                get_market_type(&symbol, EXCHANGE_NAME, None)
+            } else {
+                market_type
+            };
+            let price = raw_msg.price.ok_or_else(|| ParseError::malformed("no price"))?;
+            let (quantity_base, quantity_quote, quantity_contract) = calc_quantity_and_volume(
+                EXCHANGE_NAME,
+                market_type,
+                &pair,
+                price,
+                raw_msg.leavesQty.unwrap_or(0.0),
+                received_at,
+            )?;
+            Ok(LiquidationMsg {
+                exchange: EXCHANGE_NAME.to_string(),
+                market_type,
+                symbol,
+                pair,
+                timestamp: received_at,
+                side: if raw_msg.side.as_deref() == Some("Sell") {
+                    TradeSide::Sell
+                } else {
+                    TradeSide::Buy
+                },
+                price,
+                quantity_base,
+                quantity_quote,
+                quantity_contract,
+                json: serde_json::to_string(&raw_msg).unwrap(),
+            })
+        })
+        .collect::<Result<Vec<LiquidationMsg>, ParseError>>()?;
+    if liquidations.len() == 1 {
+        liquidations[0].json = msg.to_string();
+    }
+    Ok(liquidations)
+}
+
+/// Instruments without `openInterest` in the message are skipped.
+pub(crate) fn parse_open_interest(
+    market_type: MarketType,
+    msg: &str,
+    received_at: Option<i64>,
+) -> Result<Vec<OpenInterestMsg>, ParseError> {
+    let ws_msg =
+        serde_json::from_str::<WebsocketMsg<RawInstrumentMsg>>(msg).map_err(ParseError::from)?;
+    if ws_msg.table != "instrument" {
+        return Err(ParseError::unsupported(format!("Unsupported table {}", ws_msg.table)));
+    }
+    let mut open_interests = ws_msg
+        .data
+        .into_iter()
+        .filter(|raw_msg| raw_msg.openInterest.is_some())
+        .map(|raw_msg| -> Result<OpenInterestMsg, ParseError> {
+            let pair = crypto_pair::normalize_pair(&raw_msg.symbol, EXCHANGE_NAME)
+                .ok_or_else(|| ParseError::symbol_normalization(&raw_msg.symbol))?;
+            let market_type = if market_type == MarketType::Unknown {
+                get_market_type(&raw_msg.symbol, EXCHANGE_NAME, None)
+            } else {
+                market_type
+            };
+            let timestamp = match raw_msg.timestamp.as_ref() {
+                Some(x) => DateTime::parse_from_rfc3339(x)?.timestamp_millis(),
+                None => received_at.ok_or_else(ParseError::missing_timestamp)?,
+            };
+            let quantity_contract = raw_msg
+                .openInterest
+                .ok_or_else(|| ParseError::unsupported("Failed to extract openInterest"))?;
+            // quanto contracts are valued in XBT, neither in base nor in quote
+            let (quantity_base, quantity_quote) = if market_type == MarketType::QuantoSwap
+                || market_type == MarketType::QuantoFuture
+            {
+                (None, None)
+            } else {
+                calc_open_interest(
+                    EXCHANGE_NAME,
+                    market_type,
+                    &pair,
+                    raw_msg.markPrice,
+                    quantity_contract,
+                    timestamp,
+                )?
+            };
+            Ok(OpenInterestMsg {
+                exchange: EXCHANGE_NAME.to_string(),
+                market_type,
+                symbol: raw_msg.symbol.clone(),
+                pair,
+                msg_type: MessageType::OpenInterest,
+                timestamp,
+                quantity_contract,
+                quantity_base,
+                quantity_quote,
+                json: serde_json::to_string(&raw_msg).unwrap(),
+            })
+        })
+        .collect::<Result<Vec<OpenInterestMsg>, ParseError>>()?;
+    if open_interests.len() == 1 {
+        open_interests[0].json = msg.to_string();
+    }
+    Ok(open_interests)
+}
+
+// symbol, pair, market_type, timestamp, price, json
+type InstrumentPrice = (String, String, MarketType, i64, f64, String);
+
+// Instruments that carry the price, index instruments such as .BXBT are skipped
+fn parse_instrument_prices(
+    market_type: MarketType,
+    msg: &str,
+    received_at: Option<i64>,
+    get_price: fn(&RawInstrumentMsg) -> Option<f64>,
+) -> Result<Vec<InstrumentPrice>, ParseError> {
+    let ws_msg =
+        serde_json::from_str::<WebsocketMsg<RawInstrumentMsg>>(msg).map_err(ParseError::from)?;
+    if ws_msg.table != "instrument" {
+        return Err(ParseError::unsupported(format!("Unsupported table {}", ws_msg.table)));
+    }
+    ws_msg
+        .data
+        .into_iter()
+        .filter(|raw_msg| !raw_msg.symbol.starts_with('.'))
+        .filter_map(|raw_msg| get_price(&raw_msg).map(|price| (raw_msg, price)))
+        .map(|(raw_msg, price)| {
+            let pair = crypto_pair::normalize_pair(&raw_msg.symbol, EXCHANGE_NAME)
+                .ok_or_else(|| ParseError::symbol_normalization(&raw_msg.symbol))?;
+            let market_type = if market_type == MarketType::Unknown {
+                get_market_type(&raw_msg.symbol, EXCHANGE_NAME, None)
+            } else {
+                market_type
+            };
+            let timestamp = match raw_msg.timestamp.as_ref() {
+                Some(x) => DateTime::parse_from_rfc3339(x)?.timestamp_millis(),
+                None => received_at.ok_or_else(ParseError::missing_timestamp)?,
+            };
+            let json = serde_json::to_string(&raw_msg).unwrap();
+            Ok((raw_msg.symbol, pair, market_type, timestamp, price, json))
+        })
+        .collect()
+}
+
+pub(crate) fn parse_mark_price(
+    market_type: MarketType,
+    msg: &str,
+    received_at: Option<i64>,
+) -> Result<Vec<MarkPriceMsg>, ParseError> {
+    let mut mark_prices = parse_instrument_prices(market_type, msg, received_at, |x| x.markPrice)?
+        .into_iter()
+        .map(|(symbol, pair, market_type, timestamp, price, json)| MarkPriceMsg {
+            exchange: EXCHANGE_NAME.to_string(),
+            market_type,
+            symbol,
+            pair,
+            timestamp,
+            price,
+            json,
+        })
+        .collect::<Vec<MarkPriceMsg>>();
+    if mark_prices.len() == 1 {
+        mark_prices[0].json = msg.to_string();
+    }
+    Ok(mark_prices)
+}
+
+/// The index price of a contract is its `indicativeSettlePrice`.
+pub(crate) fn parse_index_price(
+    market_type: MarketType,
+    msg: &str,
+    received_at: Option<i64>,
+) -> Result<Vec<IndexPriceMsg>, ParseError> {
+    let mut index_prices =
+        parse_instrument_prices(market_type, msg, received_at, |x| x.indicativeSettlePrice)?
+            .into_iter()
+            .map(|(symbol, pair, market_type, timestamp, price, json)| IndexPriceMsg {
+                exchange: EXCHANGE_NAME.to_string(),
+                market_type,
+                symbol,
+                pair,
+                timestamp,
+                price,
+                json,
+            })
+            .collect::<Vec<IndexPriceMsg>>();
+    if index_prices.len() == 1 {
+        index_prices[0].json = msg.to_string();
+    }
+    Ok(index_prices)
+}
+
+// The instrument registry takes precedence over the embedded table
+fn get_index_and_tick_size(symbol: &str) -> Result<(usize, f64), ParseError> {
+    let registry = crypto_pair::instrument_registry();
+    let market_type = crypto_pair::get_market_type(symbol, EXCHANGE_NAME, None);
+    let embedded = SYMBOL_INDEX_AND_TICK_SIZE_MAP.get(symbol);
+    let index = registry
+        .instrument_index(EXCHANGE_NAME, symbol)
+        .or_else(|| embedded.map(|x| x.0))
+        .ok_or_else(|| ParseError::unsupported(format!("No instrument index for {symbol}")))?;
+    let tick_size = registry
+        .tick_size(EXCHANGE_NAME, market_type, symbol)
+        .or_else(|| embedded.map(|x| x.1))
+        .ok_or_else(|| ParseError::unsupported(format!("No tick size for {symbol}")))?;
+    Ok((index, tick_size))
+}
+
 /// convert ID to price
 /// https://www.bitmex.com/app/wsAPI#OrderBookL2
 /// price = (100000000 * symbolIdx - ID) * tickSize
-pub fn id_to_price(symbol: &str, id: usize) -> f64 {
-    let (index, tick_size) = SYMBOL_INDEX_AND_TICK_SIZE_MAP.get(symbol).expect(symbol);
-    let (index, tick_size) = (*index, *tick_size);
-    round((100000000.0 * index as f64 - id as f64) * tick_size)
+pub fn id_to_price(symbol: &str, id: usize) -> Result<f64, ParseError> {
+    let (index, tick_size) = get_index_and_tick_size(symbol)?;
+    Ok(round((100000000.0 * index as f64 - id as f64) * tick_size))
 }
 
 /// convert price to ID
 /// https://www.bitmex.com/app/wsAPI#OrderBookL2
 /// ID = (100000000 * symbolIdx) - (price / tickSize)
-pub fn price_to_id(symbol: &str, price: f64) -> usize {
-    let (index, tick_size) = SYMBOL_INDEX_AND_TICK_SIZE_MAP.get(symbol).expect(symbol);
-    let (index, tick_size) = (*index, *tick_size);
+pub fn price_to_id(symbol: &str, price: f64) -> Result<usize, ParseError> {
+    let (index, tick_size) = get_index_and_tick_size(symbol)?;
 
-    (100000000.0 * index as f64 - price / tick_size) as usize
+    Ok((100000000.0 * index as f64 - price / tick_size) as usize)
 }
 
 pub(crate) fn parse_l2(
     market_type: MarketType,
     msg: &str,
     received_at: i64,
-) -> Result<Vec<OrderBookMsg>, SimpleError> {
-    let ws_msg = serde_json::from_str::<WebsocketMsg<RawOrder>>(msg).map_err(SimpleError::from)?;
-    debug_assert!(ws_msg.table.starts_with("orderBookL2")); // orderBookL2, orderBookL2_25
+) -> Result<Vec<OrderBookMsg>, ParseError> {
+    let ws_msg = serde_json::from_str::<WebsocketMsg<RawOrder>>(msg).map_err(ParseError::from)?;
+    if !ws_msg.table.starts_with("orderBookL2") {
+        return Err(ParseError::unsupported(format!("Unsupported table {}", ws_msg.table)));
+    }
     let snapshot = ws_msg.action == "partial";
     if ws_msg.data.is_empty() {
         return Ok(Vec::new());
     }
     let symbol = ws_msg.data[0].symbol.clone();
     let pair = crypto_pair::normalize_pair(&symbol, EXCHANGE_NAME)
-        .ok_or_else(|| SimpleError::new(format!("Failed to normalize {symbol} from {msg}")))?;
+        .ok_or_else(|| ParseError::symbol_normalization(&symbol))?;
     let market_type = if market_type == MarketType::Unknown {
         get_market_type(&symbol, EXCHANGE_NAME, None)
     } else {
@@ -779,25 +1064,39 @@ pub(crate) fn parse_l2(
     let timestamp = ws_msg
         .data
         .iter()
-        .filter(|x| x.timestamp.is_some())
-        .map(|x| {
-            DateTime::parse_from_rfc3339(x.timestamp.clone().unwrap().as_str())
-                .unwrap()
-                .timestamp_millis()
+        .filter_map(|x| x.timestamp.as_deref())
+        .map(|x| -> Result<i64, ParseError> {
+            Ok(DateTime::parse_from_rfc3339(x)?.timestamp_millis())
         })
-        .max();
+        .collect::<Result<Vec<i64>, ParseError>>()?
+        .into_iter()
+        .max()
+        .unwrap_or(received_at);
 
-    let parse_order = |raw_order: &RawOrder| -> Order {
+    let parse_order = |raw_order: &RawOrder| -> Result<Order, ParseError> {
         let price = if let Some(p) = raw_order.price {
             p
         } else {
-            id_to_price(&raw_order.symbol, raw_order.id)
+            id_to_price(&raw_order.symbol, raw_order.id)?
         };
 
         let quantity = raw_order.size.unwrap_or(0.0); // 0.0 means delete
-        let (quantity_base, quantity_quote, quantity_contract) =
-            calc_quantity_and_volume(EXCHANGE_NAME, market_type, &pair, price, quantity);
-        Order { price, quantity_base, quantity_quote, quantity_contract }
+        let (quantity_base, quantity_quote, quantity_contract) = calc_quantity_and_volume(
+            EXCHANGE_NAME,
+            market_type,
+            &pair,
+            price,
+            quantity,
+            timestamp,
+        )?;
+        Ok(Order {
+            price,
+            quantity_base,
+            quantity_quote,
+            quantity_contract,
+            price_exact: None,
+            quantity_exact: None,
+        })
     };
 
     let orderbook = OrderBookMsg {
@@ -806,11 +1105,21 @@ pub(crate) fn parse_l2(
         symbol,
         pair: pair.clone(),
         msg_type: MessageType::L2Event,
-        timestamp: timestamp.unwrap_or(received_at),
+        timestamp,
         seq_id: None,
         prev_seq_id: None,
-        asks: ws_msg.data.iter().filter(|x| x.side == "Sell").map(parse_order).collect(),
-        bids: ws_msg.data.iter().filter(|x| x.side == "Buy").map(parse_order).collect(),
+        asks: ws_msg
+            .data
+            .iter()
+            .filter(|x| x.side == "Sell")
+            .map(parse_order)
+            .collect::<Result<Vec<Order>, ParseError>>()?,
+        bids: ws_msg
+            .data
+            .iter()
+            .filter(|x| x.side == "Buy")
+            .map(parse_order)
+            .collect::<Result<Vec<Order>, ParseError>>()?,
         snapshot,
         json: msg.to_string(),
     };
@@ -845,32 +1154,45 @@ struct RawBboMsg {
 pub(crate) fn parse_l2_topk(
     market_type: MarketType,
     msg: &str,
-) -> Result<Vec<OrderBookMsg>, SimpleError> {
+) -> Result<Vec<OrderBookMsg>, ParseError> {
     let ws_msg =
-        serde_json::from_str::<WebsocketMsg<OrderBook10Msg>>(msg).map_err(SimpleError::from)?;
-    debug_assert_eq!("orderBook10", ws_msg.table);
+        serde_json::from_str::<WebsocketMsg<OrderBook10Msg>>(msg).map_err(ParseError::from)?;
+    if ws_msg.table != "orderBook10" {
+        return Err(ParseError::unsupported(format!("Unsupported table {}", ws_msg.table)));
+    }
     if ws_msg.data.is_empty() {
         return Ok(Vec::new());
     }
 
-    let parse_order = |raw_order: &[f64; 2], pair: &str| -> Order {
+    let parse_order = |raw_order: &[f64; 2],
+                       pair: &str,
+                       timestamp: i64|
+     -> Result<Order, ParseError> {
         let price = raw_order[0];
         let quantity = raw_order[1];
         let (quantity_base, quantity_quote, quantity_contract) =
-            calc_quantity_and_volume(EXCHANGE_NAME, market_type, pair, price, quantity);
-        Order { price, quantity_base, quantity_quote, quantity_contract }
+            calc_quantity_and_volume(EXCHANGE_NAME, market_type, pair, price, quantity, timestamp)?;
+        Ok(Order {
+            price,
+            quantity_base,
+            quantity_quote,
+            quantity_contract,
+            price_exact: None,
+            quantity_exact: None,
+        })
     };
 
     let orderbooks: Vec<OrderBookMsg> = ws_msg
         .data
         .into_iter()
-        .map(|orderbook10_msg| {
+        .map(|orderbook10_msg| -> Result<OrderBookMsg, ParseError> {
             let symbol = orderbook10_msg.symbol.as_str();
-            let pair = crypto_pair::normalize_pair(symbol, EXCHANGE_NAME).unwrap();
+            let pair = crypto_pair::normalize_pair(symbol, EXCHANGE_NAME)
+                .ok_or_else(|| ParseError::symbol_normalization(symbol))?;
             let timestamp = DateTime::parse_from_rfc3339(orderbook10_msg.timestamp.as_str())
-                .unwrap()
+                .map_err(|err| ParseError::malformed(err.to_string()))?
                 .timestamp_millis();
-            OrderBookMsg {
+            Ok(OrderBookMsg {
                 exchange: EXCHANGE_NAME.to_string(),
                 market_type,
                 symbol: symbol.to_string(),
@@ -879,24 +1201,29 @@ pub(crate) fn parse_l2_topk(
                 timestamp,
                 seq_id: None,
                 prev_seq_id: None,
-                asks: orderbook10_msg
-                    .asks
-                    .map_or(Vec::new(), |v| v.iter().map(|x| parse_order(x, &pair)).collect()),
-                bids: orderbook10_msg
-                    .bids
-                    .map_or(Vec::new(), |v| v.iter().map(|x| parse_order(x, &pair)).collect()),
+                asks: orderbook10_msg.asks.map_or(Ok(Vec::new()), |v| {
+                    v.iter()
+                        .map(|x| parse_order(x, &pair, timestamp))
+                        .collect::<Result<Vec<Order>, ParseError>>()
+                })?,
+                bids: orderbook10_msg.bids.map_or(Ok(Vec::new()), |v| {
+                    v.iter()
+                        .map(|x| parse_order(x, &pair, timestamp))
+                        .collect::<Result<Vec<Order>, ParseError>>()
+                })?,
                 snapshot: true,
                 json: msg.to_string(),
-            }
+            })
         })
-        .collect();
-    debug_assert_eq!(1, orderbooks.len());
+        .collect::<Result<Vec<OrderBookMsg>, ParseError>>()?;
     Ok(orderbooks)
 }
 
-pub(crate) fn parse_bbo(market_type: MarketType, msg: &str) -> Result<Vec<BboMsg>, SimpleError> {
-    let ws_msg = serde_json::from_str::<WebsocketMsg<RawBboMsg>>(msg).map_err(SimpleError::from)?;
-    debug_assert_eq!("quote", ws_msg.table);
+pub(crate) fn parse_bbo(market_type: MarketType, msg: &str) -> Result<Vec<BboMsg>, ParseError> {
+    let ws_msg = serde_json::from_str::<WebsocketMsg<RawBboMsg>>(msg).map_err(ParseError::from)?;
+    if ws_msg.table != "quote" {
+        return Err(ParseError::unsupported(format!("Unsupported table {}", ws_msg.table)));
+    }
     if ws_msg.data.is_empty() {
         return Ok(Vec::new());
     }
@@ -904,12 +1231,12 @@ pub(crate) fn parse_bbo(market_type: MarketType, msg: &str) -> Result<Vec<BboMsg
     let bbo_messages: Vec<BboMsg> = ws_msg
         .data
         .iter()
-        .map(|raw_bbo| {
+        .map(|raw_bbo| -> Result<BboMsg, ParseError> {
             let symbol = raw_bbo.symbol.as_str();
-            let pair = crypto_pair::normalize_pair(symbol, EXCHANGE_NAME).unwrap();
-            let timestamp = DateTime::parse_from_rfc3339(raw_bbo.timestamp.as_str())
-                .unwrap()
-                .timestamp_millis();
+            let pair = crypto_pair::normalize_pair(symbol, EXCHANGE_NAME)
+                .ok_or_else(|| ParseError::symbol_normalization(symbol))?;
+            let timestamp =
+                DateTime::parse_from_rfc3339(raw_bbo.timestamp.as_str())?.timestamp_millis();
             let (ask_quantity_base, ask_quantity_quote, ask_quantity_contract) =
                 calc_quantity_and_volume(
                     EXCHANGE_NAME,
@@ -917,7 +1244,8 @@ pub(crate) fn parse_bbo(market_type: MarketType, msg: &str) -> Result<Vec<BboMsg
                     &pair,
                     raw_bbo.askPrice,
                     raw_bbo.askSize,
-                );
+                    timestamp,
+                )?;
             let (bid_quantity_base, bid_quantity_quote, bid_quantity_contract) =
                 calc_quantity_and_volume(
                     EXCHANGE_NAME,
@@ -925,8 +1253,9 @@ pub(crate) fn parse_bbo(market_type: MarketType, msg: &str) -> Result<Vec<BboMsg
                     &pair,
                     raw_bbo.bidPrice,
                     raw_bbo.bidSize,
-                );
-            BboMsg {
+                    timestamp,
+                )?;
+            Ok(BboMsg {
                 exchange: EXCHANGE_NAME.to_string(),
                 market_type,
                 symbol: symbol.to_string(),
@@ -942,11 +1271,15 @@ pub(crate) fn parse_bbo(market_type: MarketType, msg: &str) -> Result<Vec<BboMsg
                 ask_quantity_base,
                 ask_quantity_quote,
                 ask_quantity_contract,
+                bid_price_exact: None,
+                bid_quantity_exact: None,
+                ask_price_exact: None,
+                ask_quantity_exact: None,
                 id: None,
                 json: msg.to_string(),
-            }
+            })
         })
-        .collect();
+        .collect::<Result<Vec<BboMsg>, ParseError>>()?;
 
     Ok(bbo_messages)
 }
@@ -972,31 +1305,27 @@ struct RawCandlestickMsg {
 pub(crate) fn parse_candlestick(
     market_type: MarketType,
     msg: &str,
-) -> Result<Vec<CandlestickMsg>, SimpleError> {
+) -> Result<Vec<CandlestickMsg>, ParseError> {
     let ws_msg: WebsocketMsg<RawCandlestickMsg> =
-        serde_json::from_str::<WebsocketMsg<RawCandlestickMsg>>(msg).map_err(SimpleError::from)?;
+        serde_json::from_str::<WebsocketMsg<RawCandlestickMsg>>(msg).map_err(ParseError::from)?;
     //debug_assert_eq!("tradeBin",ws_msg.table.as_str().substr(0,8));
     if ws_msg.data.is_empty() {
         return Ok(Vec::new());
     }
-    let period = ws_msg
-        .table
-        .as_str()
-        .strip_prefix("tradeBin")
-        .unwrap()
-        .strip_suffix('m')
-        .unwrap()
-        .parse::<i64>()
-        .unwrap();
+    let period = parse_candle_period(EXCHANGE_NAME, market_type, &ws_msg.table)?;
+    // bins are stamped with their close time
+    let period_millis = period
+        .as_millis()
+        .ok_or_else(|| ParseError::unsupported(format!("Unsupported bin {}", ws_msg.table)))?;
 
     let candlestick_msgs: Vec<CandlestickMsg> = ws_msg
         .data
         .iter()
-        .map(|raw_candlestick_msg| {
+        .map(|raw_candlestick_msg| -> Result<CandlestickMsg, ParseError> {
             let symbol = raw_candlestick_msg.symbol.as_str();
-            let pair = crypto_pair::normalize_pair(symbol, EXCHANGE_NAME).unwrap();
-            let timestamp = DateTime::parse_from_rfc3339(raw_candlestick_msg.timestamp.as_str())
-                .unwrap()
+            let pair = crypto_pair::normalize_pair(symbol, EXCHANGE_NAME)
+                .ok_or_else(|| ParseError::symbol_normalization(symbol))?;
+            let timestamp = DateTime::parse_from_rfc3339(raw_candlestick_msg.timestamp.as_str())?
                 .timestamp_millis();
             let quote_volume = if raw_candlestick_msg.foreignNotional > 0.0 {
                 Some(raw_candlestick_msg.foreignNotional)
@@ -1004,25 +1333,27 @@ pub(crate) fn parse_candlestick(
                 None
             };
 
-            CandlestickMsg {
+            Ok(CandlestickMsg {
                 exchange: EXCHANGE_NAME.to_string(),
                 market_type,
                 msg_type: MessageType::Candlestick,
                 symbol: symbol.to_string(),
                 pair,
                 timestamp,
-                period: format!("{}m", period),
-                begin_time: timestamp - period * 60000,
+                period,
+                begin_time: timestamp - period_millis,
+                end_time: timestamp,
                 open: raw_candlestick_msg.open,
                 high: raw_candlestick_msg.high,
                 low: raw_candlestick_msg.low,
                 close: raw_candlestick_msg.close,
                 volume: raw_candlestick_msg.homeNotional,
                 quote_volume,
+                is_closed: Some(true),
                 json: msg.to_string(),
-            }
+            })
         })
-        .collect();
+        .collect::<Result<Vec<CandlestickMsg>, ParseError>>()?;
     Ok(candlestick_msgs)
 }
 
@@ -1047,25 +1378,25 @@ mod tests {
     #[test]
     fn test_id_to_price() {
         // data are from https://www.bitmex.com/api/v1/orderBook/L2?symbol=XBTUSD&depth=25
-        assert_eq!(51366.5, super::id_to_price("XBTUSD", 8794863350));
-        assert_eq!(51306.0, super::id_to_price("XBTUSD", 8794869400));
+        assert_eq!(51366.5, super::id_to_price("XBTUSD", 8794863350).unwrap());
+        assert_eq!(51306.0, super::id_to_price("XBTUSD", 8794869400).unwrap());
 
-        assert_eq!(3460.0, super::id_to_price("ETHUSD", 29699930800));
-        assert_eq!(3451.0, super::id_to_price("ETHUSD", 29699930980));
+        assert_eq!(3460.0, super::id_to_price("ETHUSD", 29699930800).unwrap());
+        assert_eq!(3451.0, super::id_to_price("ETHUSD", 29699930980).unwrap());
 
-        assert_eq!(0.07369, super::id_to_price("ETHZ21", 63399992631));
-        assert_eq!(0.07216, super::id_to_price("ETHZ21", 63399992784));
+        assert_eq!(0.07369, super::id_to_price("ETHZ21", 63399992631).unwrap());
+        assert_eq!(0.07216, super::id_to_price("ETHZ21", 63399992784).unwrap());
     }
 
     #[test]
     fn test_price_to_id() {
-        assert_eq!(8794863350, super::price_to_id("XBTUSD", 51366.5));
-        assert_eq!(8794869400, super::price_to_id("XBTUSD", 51306.0));
+        assert_eq!(8794863350, super::price_to_id("XBTUSD", 51366.5).unwrap());
+        assert_eq!(8794869400, super::price_to_id("XBTUSD", 51306.0).unwrap());
 
-        assert_eq!(29699930800, super::price_to_id("ETHUSD", 3460.0));
-        assert_eq!(29699930980, super::price_to_id("ETHUSD", 3451.0));
+        assert_eq!(29699930800, super::price_to_id("ETHUSD", 3460.0).unwrap());
+        assert_eq!(29699930980, super::price_to_id("ETHUSD", 3451.0).unwrap());
 
-        assert_eq!(63399992631, super::price_to_id("ETHZ21", 0.07369));
-        assert_eq!(63399992784, super::price_to_id("ETHZ21", 0.07216));
+        assert_eq!(63399992631, super::price_to_id("ETHZ21", 0.07369).unwrap());
+        assert_eq!(63399992784, super::price_to_id("ETHZ21", 0.07216).unwrap());
     }
 }
@@ -1,11 +1,14 @@
 use crypto_market_type::MarketType;
 use crypto_msg_type::MessageType;
 
-use crypto_message::{Order, OrderBookMsg, TradeMsg, TradeSide};
+use crypto_message::{CandlestickMsg, L3Action, L3Msg, Order, OrderBookMsg, TradeMsg, TradeSide};
 
+use crate::{
+    ParseError,
+    exchanges::utils::{infer_candle_period, json_str, parse_exact},
+};
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
-use simple_error::SimpleError;
 use std::collections::HashMap;
 
 const EXCHANGE_NAME: &str = "bitstamp";
@@ -39,6 +42,44 @@ struct SpotOrderbookMsg {
     extra: HashMap<String, Value>,
 }
 
+// see "Live orders" at https://www.bitstamp.net/websocket/v2/
+#[derive(Serialize, Deserialize)]
+struct SpotOrderMsg {
+    id_str: String,         // Order ID
+    order_type: i64,        // Order type (0 - buy; 1 - sell)
+    microtimestamp: String, // Order microtimestamp
+    amount_str: String,     // Order amount represented in string format
+    price_str: String,      // Order price represented in string format
+    #[serde(flatten)]
+    extra: HashMap<String, Value>,
+}
+
+// see "OHLC data" at https://www.bitstamp.net/api/
+#[derive(Serialize, Deserialize)]
+struct RawCandlestickMsg {
+    timestamp: String, // Unix timestamp in seconds
+    open: String,
+    high: String,
+    low: String,
+    close: String,
+    volume: String,
+    #[serde(flatten)]
+    extra: HashMap<String, Value>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct RawOhlcMsg {
+    pair: String, // e.g., BTC/USD
+    ohlc: Vec<RawCandlestickMsg>,
+    #[serde(flatten)]
+    extra: HashMap<String, Value>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct RestfulMsg<T: Sized> {
+    data: T,
+}
+
 #[derive(Serialize, Deserialize)]
 struct WebsocketMsg<T: Sized> {
     channel: String,
@@ -46,45 +87,87 @@ struct WebsocketMsg<T: Sized> {
     data: T,
 }
 
-pub(crate) fn extract_symbol(_market_type: MarketType, msg: &str) -> Result<String, SimpleError> {
+pub(crate) fn extract_symbol(_market_type: MarketType, msg: &str) -> Result<String, ParseError> {
     let json_obj = serde_json::from_str::<HashMap<String, Value>>(msg)
-        .map_err(|_e| SimpleError::new(format!("Failed to parse the JSON string {msg}")))?;
+        .map_err(|_e| ParseError::malformed("invalid JSON"))?;
     if let Some(channel) = json_obj.get("channel") {
-        let symbol = channel.as_str().unwrap().split('_').last().unwrap();
+        let channel = json_str(channel, "channel")?;
+        let symbol = channel
+            .split('_')
+            .next_back()
+            .ok_or_else(|| ParseError::unsupported(format!("Invalid channel {channel}")))?;
         Ok(symbol.to_string())
     } else if json_obj.contains_key("asks") && json_obj.contains_key("bids") {
         // l2_snapshot has no symbol
         Ok("NONE".to_string())
+    } else if let Ok(rest_msg) = serde_json::from_str::<RestfulMsg<RawOhlcMsg>>(msg) {
+        Ok(rest_msg.data.pair.replace('/', "").to_lowercase())
     } else {
-        Err(SimpleError::new(format!("Failed to extract symbol from {msg}")))
+        Err(ParseError::unsupported("Failed to extract symbol"))
     }
 }
 
 pub(crate) fn extract_timestamp(
     _market_type: MarketType,
     msg: &str,
-) -> Result<Option<i64>, SimpleError> {
+) -> Result<Option<i64>, ParseError> {
     let json_obj = serde_json::from_str::<HashMap<String, Value>>(msg)
-        .map_err(|_e| SimpleError::new(format!("Failed to parse the JSON string {msg}")))?;
-    if let Some(data) = json_obj.get("data") {
-        Ok(Some(data["microtimestamp"].as_str().unwrap().parse::<i64>().unwrap() / 1000))
+        .map_err(|_e| ParseError::malformed("invalid JSON"))?;
+    if let Ok(rest_msg) = serde_json::from_str::<RestfulMsg<RawOhlcMsg>>(msg) {
+        let begin_times = rest_msg
+            .data
+            .ohlc
+            .iter()
+            .map(|x| x.timestamp.parse::<i64>())
+            .collect::<Result<Vec<i64>, _>>()?;
+        Ok(begin_times.into_iter().max().map(|x| x * 1000))
+    } else if let Some(data) = json_obj.get("data") {
+        Ok(Some(json_str(&data["microtimestamp"], "microtimestamp")?.parse::<i64>()? / 1000))
     } else if let Some(microtimestamp) = json_obj.get("microtimestamp") {
-        Ok(Some(microtimestamp.as_str().unwrap().parse::<i64>().unwrap() / 1000))
+        Ok(Some(json_str(microtimestamp, "microtimestamp")?.parse::<i64>()? / 1000))
     } else {
-        Err(SimpleError::new(format!("No microtimestamp field in  {msg}")))
+        Err(ParseError::missing_timestamp())
     }
 }
 
-pub(crate) fn parse_trade(
-    market_type: MarketType,
-    msg: &str,
-) -> Result<Vec<TradeMsg>, SimpleError> {
-    let ws_msg = serde_json::from_str::<WebsocketMsg<SpotTradeMsg>>(msg).map_err(|_e| {
-        SimpleError::new(format!("Failed to deserialize {msg} to WebsocketMsg<SpotTradeMsg>"))
-    })?;
-    let symbol = ws_msg.channel.split('_').last().unwrap();
+pub(crate) fn get_msg_type(msg: &str) -> MessageType {
+    if let Ok(ws_msg) = serde_json::from_str::<WebsocketMsg<Value>>(msg) {
+        if ws_msg.event.starts_with("bts:") {
+            // subscription responses and reconnect requests
+            MessageType::Other
+        } else if ws_msg.channel.starts_with("live_trades_") {
+            MessageType::Trade
+        } else if ws_msg.channel.starts_with("diff_order_book_") {
+            MessageType::L2Event
+        } else if ws_msg.channel.starts_with("order_book_") {
+            MessageType::L2TopK
+        } else if ws_msg.channel.starts_with("live_orders_") {
+            MessageType::L3Event
+        } else {
+            MessageType::Other
+        }
+    } else if let Ok(obj) = serde_json::from_str::<HashMap<String, Value>>(msg) {
+        if obj.contains_key("asks") && obj.contains_key("bids") {
+            MessageType::L2Snapshot
+        } else if obj.get("data").is_some_and(|data| data.get("ohlc").is_some()) {
+            MessageType::Candlestick
+        } else {
+            MessageType::Other
+        }
+    } else {
+        MessageType::Other
+    }
+}
+
+pub(crate) fn parse_trade(market_type: MarketType, msg: &str) -> Result<Vec<TradeMsg>, ParseError> {
+    let ws_msg = serde_json::from_str::<WebsocketMsg<SpotTradeMsg>>(msg)
+        .map_err(|_e| ParseError::malformed("expected WebsocketMsg<SpotTradeMsg>"))?;
+    let symbol =
+        ws_msg.channel.split('_').next_back().ok_or_else(|| {
+            ParseError::unsupported(format!("Invalid channel {}", ws_msg.channel))
+        })?;
     let pair = crypto_pair::normalize_pair(symbol, EXCHANGE_NAME)
-        .ok_or_else(|| SimpleError::new(format!("Failed to normalize {symbol} from {msg}")))?;
+        .ok_or_else(|| ParseError::symbol_normalization(symbol))?;
     let raw_trade = ws_msg.data;
 
     let trade = TradeMsg {
@@ -93,11 +176,13 @@ pub(crate) fn parse_trade(
         symbol: symbol.to_string(),
         pair,
         msg_type: MessageType::Trade,
-        timestamp: raw_trade.microtimestamp.parse::<i64>().unwrap() / 1000,
+        timestamp: raw_trade.microtimestamp.parse::<i64>()? / 1000,
         price: raw_trade.price,
         quantity_base: raw_trade.amount,
         quantity_quote: raw_trade.price * raw_trade.amount,
         quantity_contract: None,
+        price_exact: None,
+        quantity_exact: None,
         side: if raw_trade.type_ == 1 { TradeSide::Sell } else { TradeSide::Buy },
         trade_id: raw_trade.id.to_string(),
         json: msg.to_string(),
@@ -109,13 +194,15 @@ pub(crate) fn parse_trade(
 pub(crate) fn parse_l2(
     market_type: MarketType,
     msg: &str,
-) -> Result<Vec<OrderBookMsg>, SimpleError> {
-    let ws_msg = serde_json::from_str::<WebsocketMsg<SpotOrderbookMsg>>(msg).map_err(|_e| {
-        SimpleError::new(format!("Failed to deserialize {msg} to WebsocketMsg<SpotOrderbookMsg>"))
-    })?;
-    let symbol = ws_msg.channel.split('_').last().unwrap();
+) -> Result<Vec<OrderBookMsg>, ParseError> {
+    let ws_msg = serde_json::from_str::<WebsocketMsg<SpotOrderbookMsg>>(msg)
+        .map_err(|_e| ParseError::malformed("expected WebsocketMsg<SpotOrderbookMsg>"))?;
+    let symbol =
+        ws_msg.channel.split('_').next_back().ok_or_else(|| {
+            ParseError::unsupported(format!("Invalid channel {}", ws_msg.channel))
+        })?;
     let pair = crypto_pair::normalize_pair(symbol, EXCHANGE_NAME)
-        .ok_or_else(|| SimpleError::new(format!("Failed to normalize {symbol} from {msg}")))?;
+        .ok_or_else(|| ParseError::symbol_normalization(symbol))?;
     let msg_type = if ws_msg.channel.starts_with("diff_order_book_") {
         MessageType::L2Event
     } else {
@@ -123,16 +210,18 @@ pub(crate) fn parse_l2(
     };
     let raw_orderbook = ws_msg.data;
 
-    let parse_order = |raw_order: &[String; 2]| -> Order {
-        let price = raw_order[0].parse::<f64>().unwrap();
-        let quantity_base = raw_order[1].parse::<f64>().unwrap();
+    let parse_order = |raw_order: &[String; 2]| -> Result<Order, ParseError> {
+        let price = raw_order[0].parse::<f64>()?;
+        let quantity_base = raw_order[1].parse::<f64>()?;
 
-        Order {
+        Ok(Order {
             price,
             quantity_base,
             quantity_quote: price * quantity_base,
             quantity_contract: None,
-        }
+            price_exact: parse_exact(&raw_order[0]),
+            quantity_exact: parse_exact(&raw_order[1]),
+        })
     };
 
     let orderbook = OrderBookMsg {
@@ -141,11 +230,19 @@ pub(crate) fn parse_l2(
         symbol: symbol.to_string(),
         pair,
         msg_type,
-        timestamp: raw_orderbook.microtimestamp.parse::<i64>().unwrap() / 1000,
+        timestamp: raw_orderbook.microtimestamp.parse::<i64>()? / 1000,
         seq_id: None,
         prev_seq_id: None,
-        asks: raw_orderbook.asks.iter().map(parse_order).collect(),
-        bids: raw_orderbook.bids.iter().map(parse_order).collect(),
+        asks: raw_orderbook
+            .asks
+            .iter()
+            .map(parse_order)
+            .collect::<Result<Vec<Order>, ParseError>>()?,
+        bids: raw_orderbook
+            .bids
+            .iter()
+            .map(parse_order)
+            .collect::<Result<Vec<Order>, ParseError>>()?,
         snapshot: ws_msg.channel.starts_with("order_book_"),
         json: msg.to_string(),
     };
@@ -153,9 +250,101 @@ pub(crate) fn parse_l2(
     Ok(vec![orderbook])
 }
 
+pub(crate) fn parse_l3(market_type: MarketType, msg: &str) -> Result<Vec<L3Msg>, ParseError> {
+    let ws_msg = serde_json::from_str::<WebsocketMsg<SpotOrderMsg>>(msg)
+        .map_err(|_e| ParseError::malformed("expected WebsocketMsg<SpotOrderMsg>"))?;
+    if !ws_msg.channel.starts_with("live_orders_") {
+        return Err(ParseError::unsupported(format!("Unsupported channel {}", ws_msg.channel)));
+    }
+    let symbol =
+        ws_msg.channel.split('_').next_back().ok_or_else(|| {
+            ParseError::unsupported(format!("Invalid channel {}", ws_msg.channel))
+        })?;
+    let pair = crypto_pair::normalize_pair(symbol, EXCHANGE_NAME)
+        .ok_or_else(|| ParseError::symbol_normalization(symbol))?;
+    let action = match ws_msg.event.as_str() {
+        "order_created" => L3Action::Open,
+        "order_changed" => L3Action::Change,
+        "order_deleted" => L3Action::Done,
+        _ => return Err(ParseError::unsupported(format!("Unknown event {}", ws_msg.event))),
+    };
+    let raw_order = ws_msg.data;
+    let price = raw_order.price_str.parse::<f64>()?;
+    let quantity = raw_order.amount_str.parse::<f64>()?;
+
+    let l3_msg = L3Msg {
+        exchange: EXCHANGE_NAME.to_string(),
+        market_type,
+        symbol: symbol.to_string(),
+        pair,
+        msg_type: MessageType::L3Event,
+        timestamp: raw_order.microtimestamp.parse::<i64>()? / 1000,
+        order_id: raw_order.id_str,
+        action,
+        side: Some(if raw_order.order_type == 1 { TradeSide::Sell } else { TradeSide::Buy }),
+        price: Some(price),
+        quantity_base: quantity,
+        quantity_quote: price * quantity,
+        quantity_contract: None,
+        seq_id: None,
+        json: msg.to_string(),
+    };
+
+    Ok(vec![l3_msg])
+}
+
 pub(crate) fn parse_l2_topk(
     market_type: MarketType,
     msg: &str,
-) -> Result<Vec<OrderBookMsg>, SimpleError> {
+) -> Result<Vec<OrderBookMsg>, ParseError> {
     parse_l2(market_type, msg)
 }
+
+pub(crate) fn parse_candlestick(
+    market_type: MarketType,
+    msg: &str,
+) -> Result<Vec<CandlestickMsg>, ParseError> {
+    let rest_msg = serde_json::from_str::<RestfulMsg<RawOhlcMsg>>(msg)
+        .map_err(|_e| ParseError::malformed("expected RestfulMsg<RawOhlcMsg>"))?;
+    let symbol = rest_msg.data.pair.replace('/', "").to_lowercase();
+    let pair = crypto_pair::normalize_pair(&symbol, EXCHANGE_NAME)
+        .ok_or_else(|| ParseError::symbol_normalization(&symbol))?;
+    let begin_times = rest_msg
+        .data
+        .ohlc
+        .iter()
+        .map(|raw_candlestick| raw_candlestick.timestamp.parse::<i64>().map(|t| t * 1000))
+        .collect::<Result<Vec<i64>, _>>()?;
+    // the step in seconds is a request parameter only
+    let period = infer_candle_period(&begin_times)?;
+
+    let candlesticks = rest_msg
+        .data
+        .ohlc
+        .into_iter()
+        .zip(begin_times)
+        .map(|(raw_candlestick, begin_time)| -> Result<CandlestickMsg, ParseError> {
+            Ok(CandlestickMsg {
+                exchange: EXCHANGE_NAME.to_string(),
+                market_type,
+                msg_type: MessageType::Candlestick,
+                symbol: symbol.clone(),
+                pair: pair.clone(),
+                timestamp: begin_time,
+                period,
+                begin_time,
+                end_time: period.end_time(begin_time),
+                open: raw_candlestick.open.parse::<f64>()?,
+                high: raw_candlestick.high.parse::<f64>()?,
+                low: raw_candlestick.low.parse::<f64>()?,
+                close: raw_candlestick.close.parse::<f64>()?,
+                volume: raw_candlestick.volume.parse::<f64>()?,
+                quote_volume: None,
+                is_closed: None,
+                json: serde_json::to_string(&raw_candlestick).unwrap(),
+            })
+        })
+        .collect::<Result<Vec<CandlestickMsg>, ParseError>>()?;
+
+    Ok(candlesticks)
+}
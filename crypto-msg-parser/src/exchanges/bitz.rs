@@ -1,11 +1,14 @@
 use crypto_market_type::MarketType;
 use crypto_msg_type::MessageType;
 
-use crypto_message::{Order, OrderBookMsg, TradeMsg, TradeSide};
+use crypto_message::{CandlestickMsg, Order, OrderBookMsg, TickerMsg, TradeMsg, TradeSide};
 
+use crate::{
+    ParseError,
+    exchanges::utils::{json_exact, json_str, parse_candle_period, parse_exact},
+};
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
-use simple_error::SimpleError;
 use std::collections::HashMap;
 
 const EXCHANGE_NAME: &str = "bitz";
@@ -34,6 +37,29 @@ struct SpotOrderbookMsg {
     extra: HashMap<String, Value>,
 }
 
+// see https://apidocv2.bitz.plus/#kline
+// time, open, high, low, close, volume, amount, type, symbol
+type SpotKlineMsg = HashMap<String, [String; 9]>;
+
+// see https://apidocv2.bitz.plus/#market
+#[derive(Serialize, Deserialize)]
+#[allow(non_snake_case)]
+struct SpotMarketMsg {
+    symbol: String,
+    open: String,
+    high: String,
+    low: String,
+    now: String,         // last price
+    volume: String,      // 24h volume in base currency
+    quoteVolume: String, // 24h volume in quote currency
+    bidPrice: Option<String>,
+    bidQty: Option<String>,
+    askPrice: Option<String>,
+    askQty: Option<String>,
+    #[serde(flatten)]
+    extra: HashMap<String, Value>,
+}
+
 #[derive(Serialize, Deserialize)]
 #[allow(non_snake_case)]
 struct Params {
@@ -50,10 +76,9 @@ struct WebsocketMsg<T: Sized> {
     time: i64,
 }
 
-pub(crate) fn extract_symbol(_market_type: MarketType, msg: &str) -> Result<String, SimpleError> {
-    let ws_msg = serde_json::from_str::<WebsocketMsg<Value>>(msg).map_err(|_e| {
-        SimpleError::new(format!("Failed to deserialize {msg} to WebsocketMsg<Value>"))
-    })?;
+pub(crate) fn extract_symbol(_market_type: MarketType, msg: &str) -> Result<String, ParseError> {
+    let ws_msg = serde_json::from_str::<WebsocketMsg<Value>>(msg)
+        .map_err(|_e| ParseError::malformed("expected WebsocketMsg<Value>"))?;
     let symbol = ws_msg.params.symbol.as_str();
     Ok(symbol.to_string())
 }
@@ -61,36 +86,45 @@ pub(crate) fn extract_symbol(_market_type: MarketType, msg: &str) -> Result<Stri
 pub(crate) fn extract_timestamp(
     _market_type: MarketType,
     msg: &str,
-) -> Result<Option<i64>, SimpleError> {
-    let ws_msg = serde_json::from_str::<WebsocketMsg<Value>>(msg).map_err(|_e| {
-        SimpleError::new(format!("Failed to deserialize {msg} to WebsocketMsg<Value>"))
-    })?;
+) -> Result<Option<i64>, ParseError> {
+    let ws_msg = serde_json::from_str::<WebsocketMsg<Value>>(msg)
+        .map_err(|_e| ParseError::malformed("expected WebsocketMsg<Value>"))?;
     Ok(Some(ws_msg.time))
 }
 
-pub(crate) fn parse_trade(
-    market_type: MarketType,
-    msg: &str,
-) -> Result<Vec<TradeMsg>, SimpleError> {
-    let ws_msg = serde_json::from_str::<WebsocketMsg<Vec<SpotTradeMsg>>>(msg).map_err(|_e| {
-        SimpleError::new(format!("Failed to deserialize {msg} to WebsocketMsg<SpotTradeMsg>"))
-    })?;
+pub(crate) fn get_msg_type(msg: &str) -> MessageType {
+    if let Ok(ws_msg) = serde_json::from_str::<WebsocketMsg<Value>>(msg) {
+        match ws_msg.action.as_str() {
+            "Pushdata.order" => MessageType::Trade,
+            "Pushdata.depth" => MessageType::L2Event,
+            "Pushdata.market" => MessageType::Ticker,
+            "Pushdata.kline" => MessageType::Candlestick,
+            _ => MessageType::Other,
+        }
+    } else {
+        MessageType::Other
+    }
+}
+
+pub(crate) fn parse_trade(market_type: MarketType, msg: &str) -> Result<Vec<TradeMsg>, ParseError> {
+    let ws_msg = serde_json::from_str::<WebsocketMsg<Vec<SpotTradeMsg>>>(msg)
+        .map_err(|_e| ParseError::malformed("expected WebsocketMsg<SpotTradeMsg>"))?;
     let symbol = ws_msg.params.symbol.as_str();
     let pair = crypto_pair::normalize_pair(symbol, EXCHANGE_NAME)
-        .ok_or_else(|| SimpleError::new(format!("Failed to normalize {symbol} from {msg}")))?;
+        .ok_or_else(|| ParseError::symbol_normalization(symbol))?;
 
     let mut trades: Vec<TradeMsg> = ws_msg
         .data
         .into_iter()
-        .map(|raw_trade| {
-            let price = raw_trade.p.parse::<f64>().unwrap();
-            let quantity = raw_trade.n.parse::<f64>().unwrap();
+        .map(|raw_trade| -> Result<TradeMsg, ParseError> {
+            let price = raw_trade.p.parse::<f64>()?;
+            let quantity = raw_trade.n.parse::<f64>()?;
             let timestamp = if raw_trade.id.is_empty() {
                 raw_trade.T * 1000
             } else {
-                raw_trade.id.parse::<i64>().unwrap()
+                raw_trade.id.parse::<i64>()?
             };
-            TradeMsg {
+            Ok(TradeMsg {
                 exchange: EXCHANGE_NAME.to_string(),
                 market_type,
                 symbol: symbol.to_string(),
@@ -101,12 +135,14 @@ pub(crate) fn parse_trade(
                 quantity_base: quantity,
                 quantity_quote: price * quantity,
                 quantity_contract: None,
+                price_exact: parse_exact(&raw_trade.p),
+                quantity_exact: parse_exact(&raw_trade.n),
                 side: if raw_trade.s == "sell" { TradeSide::Sell } else { TradeSide::Buy },
                 trade_id: timestamp.to_string(),
                 json: serde_json::to_string(&raw_trade).unwrap(),
-            }
+            })
         })
-        .collect();
+        .collect::<Result<Vec<TradeMsg>, ParseError>>()?;
     if trades.len() == 1 {
         trades[0].json = msg.to_string();
     }
@@ -116,26 +152,34 @@ pub(crate) fn parse_trade(
 pub(crate) fn parse_l2(
     market_type: MarketType,
     msg: &str,
-) -> Result<Vec<OrderBookMsg>, SimpleError> {
-    let ws_msg = serde_json::from_str::<WebsocketMsg<SpotOrderbookMsg>>(msg).map_err(|_e| {
-        SimpleError::new(format!("Failed to deserialize {msg} to WebsocketMsg<SpotOrderbookMsg>"))
-    })?;
-    debug_assert_eq!(ws_msg.action, "Pushdata.depth");
+) -> Result<Vec<OrderBookMsg>, ParseError> {
+    let ws_msg = serde_json::from_str::<WebsocketMsg<SpotOrderbookMsg>>(msg)
+        .map_err(|_e| ParseError::malformed("expected WebsocketMsg<SpotOrderbookMsg>"))?;
+    if ws_msg.action != "Pushdata.depth" {
+        return Err(ParseError::unsupported(format!("Unsupported action {}", ws_msg.action)));
+    }
     let symbol = ws_msg.params.symbol.as_str();
     let pair = crypto_pair::normalize_pair(symbol, EXCHANGE_NAME)
-        .ok_or_else(|| SimpleError::new(format!("Failed to normalize {symbol} from {msg}")))?;
+        .ok_or_else(|| ParseError::symbol_normalization(symbol))?;
 
-    let parse_order = |raw_order: &[Value; 3]| -> Order {
-        let price = raw_order[0].as_str().unwrap().parse::<f64>().unwrap();
+    let parse_order = |raw_order: &[Value; 3]| -> Result<Order, ParseError> {
+        let price = json_str(&raw_order[0], "price")?.parse::<f64>()?;
         let (quantity_base, quantity_quote) = if raw_order[1].is_i64() {
             (0.0, 0.0)
         } else {
-            let base = raw_order[1].as_str().unwrap().parse::<f64>().unwrap();
-            let quote = raw_order[2].as_str().unwrap().parse::<f64>().unwrap();
+            let base = json_str(&raw_order[1], "quantity")?.parse::<f64>()?;
+            let quote = json_str(&raw_order[2], "volume")?.parse::<f64>()?;
             (base, quote)
         };
 
-        Order { price, quantity_base, quantity_quote, quantity_contract: None }
+        Ok(Order {
+            price,
+            quantity_base,
+            quantity_quote,
+            quantity_contract: None,
+            price_exact: json_exact(&raw_order[0]),
+            quantity_exact: json_exact(&raw_order[1]),
+        })
     };
 
     let orderbook = OrderBookMsg {
@@ -148,12 +192,12 @@ pub(crate) fn parse_l2(
         seq_id: None,
         prev_seq_id: None,
         asks: if let Some(asks) = ws_msg.data.asks {
-            asks.iter().map(parse_order).collect()
+            asks.iter().map(parse_order).collect::<Result<Vec<Order>, ParseError>>()?
         } else {
             Vec::new()
         },
         bids: if let Some(bids) = ws_msg.data.bids {
-            bids.iter().map(parse_order).collect()
+            bids.iter().map(parse_order).collect::<Result<Vec<Order>, ParseError>>()?
         } else {
             Vec::new()
         },
@@ -163,3 +207,91 @@ pub(crate) fn parse_l2(
 
     Ok(vec![orderbook])
 }
+
+pub(crate) fn parse_ticker(
+    market_type: MarketType,
+    msg: &str,
+) -> Result<Vec<TickerMsg>, ParseError> {
+    let ws_msg = serde_json::from_str::<WebsocketMsg<HashMap<String, SpotMarketMsg>>>(msg)
+        .map_err(|_e| ParseError::malformed("expected WebsocketMsg<SpotMarketMsg>"))?;
+    let parse_optional = |x: &Option<String>| x.as_deref().map(|x| x.parse::<f64>()).transpose();
+
+    let mut tickers = ws_msg
+        .data
+        .values()
+        .map(|raw_ticker| -> Result<TickerMsg, ParseError> {
+            let symbol = raw_ticker.symbol.as_str();
+            Ok(TickerMsg {
+                exchange: EXCHANGE_NAME.to_string(),
+                market_type,
+                symbol: symbol.to_string(),
+                pair: crypto_pair::normalize_pair(symbol, EXCHANGE_NAME)
+                    .ok_or_else(|| ParseError::symbol_normalization(symbol))?,
+                msg_type: MessageType::Ticker,
+                timestamp: ws_msg.time,
+                open: raw_ticker.open.parse::<f64>()?,
+                high: raw_ticker.high.parse::<f64>()?,
+                low: raw_ticker.low.parse::<f64>()?,
+                close: raw_ticker.now.parse::<f64>()?,
+                volume: raw_ticker.volume.parse::<f64>()?,
+                quote_volume: raw_ticker.quoteVolume.parse::<f64>()?,
+                last_quantity: None,
+                best_bid_price: parse_optional(&raw_ticker.bidPrice)?,
+                best_bid_quantity: parse_optional(&raw_ticker.bidQty)?,
+                best_ask_price: parse_optional(&raw_ticker.askPrice)?,
+                best_ask_quantity: parse_optional(&raw_ticker.askQty)?,
+                open_interest: None,
+                open_interest_quote: None,
+                json: serde_json::to_string(raw_ticker)?,
+            })
+        })
+        .collect::<Result<Vec<TickerMsg>, ParseError>>()?;
+    if tickers.len() == 1 {
+        tickers[0].json = msg.to_string();
+    }
+    Ok(tickers)
+}
+
+pub(crate) fn parse_candlestick(
+    market_type: MarketType,
+    msg: &str,
+) -> Result<Vec<CandlestickMsg>, ParseError> {
+    let ws_msg = serde_json::from_str::<WebsocketMsg<SpotKlineMsg>>(msg)
+        .map_err(|_e| ParseError::malformed("expected WebsocketMsg<SpotKlineMsg>"))?;
+    if ws_msg.action != "Pushdata.kline" {
+        return Err(ParseError::unsupported(format!("Unsupported action {}", ws_msg.action)));
+    }
+    let symbol = ws_msg.params.symbol.as_str();
+    let pair = crypto_pair::normalize_pair(symbol, EXCHANGE_NAME)
+        .ok_or_else(|| ParseError::symbol_normalization(symbol))?;
+
+    let candlesticks = ws_msg
+        .data
+        .iter()
+        .map(|(period, raw_kline)| -> Result<CandlestickMsg, ParseError> {
+            let period = parse_candle_period(EXCHANGE_NAME, market_type, period)?;
+            let begin_time = raw_kline[0].parse::<i64>()? * 1000;
+            Ok(CandlestickMsg {
+                exchange: EXCHANGE_NAME.to_string(),
+                market_type,
+                msg_type: MessageType::Candlestick,
+                symbol: symbol.to_string(),
+                pair: pair.clone(),
+                timestamp: ws_msg.time,
+                period,
+                begin_time,
+                end_time: period.end_time(begin_time),
+                open: raw_kline[1].parse::<f64>()?,
+                high: raw_kline[2].parse::<f64>()?,
+                low: raw_kline[3].parse::<f64>()?,
+                close: raw_kline[4].parse::<f64>()?,
+                volume: raw_kline[5].parse::<f64>()?,
+                quote_volume: Some(raw_kline[6].parse::<f64>()?),
+                is_closed: None,
+                json: msg.to_string(),
+            })
+        })
+        .collect::<Result<Vec<CandlestickMsg>, ParseError>>()?;
+
+    Ok(candlesticks)
+}
@@ -1,12 +1,22 @@
 use crypto_market_type::MarketType;
 use crypto_msg_type::MessageType;
 
-use crate::exchanges::utils::calc_quantity_and_volume;
-use crypto_message::{CandlestickMsg, Order, OrderBookMsg, TradeMsg, TradeSide};
+use crate::{
+    ParseError,
+    exchanges::utils::{
+        annualize_funding_rate, calc_open_interest, calc_quantity_and_volume, convert_timestamp,
+        json_array, json_i64, json_str, parse_candle_period, parse_exact,
+    },
+};
+use crypto_message::{
+    CandlestickMsg, FundingRateMsg, LiquidationMsg, OpenInterestMsg, Order, OrderBookMsg,
+    TickerMsg, TradeMsg, TradeSide,
+};
+
+use chrono::DateTime;
 
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
-use simple_error::SimpleError;
 use std::collections::HashMap;
 
 const EXCHANGE_NAME: &str = "bybit";
@@ -121,63 +131,119 @@ pub(crate) struct RawCandlestickMsgV2 {
     extra: HashMap<String, Value>,
 }
 
-pub(crate) fn extract_symbol(_market_type: MarketType, msg: &str) -> Result<String, SimpleError> {
+// See:
+// * https://bybit-exchange.github.io/docs/inverse/#t-websocketliquidation
+// * https://bybit-exchange.github.io/docs/linear/#t-websocketliquidation
+#[derive(Serialize, Deserialize)]
+struct RawLiquidationMsg {
+    symbol: String,
+    side: String, // Buy means a long position is liquidated
+    price: String,
+    qty: String,
+    time: i64,
+    #[serde(flatten)]
+    extra: HashMap<String, Value>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct LiquidationWebsocketMsg {
+    topic: String,
+    data: RawLiquidationMsg,
+}
+
+// See:
+// * https://bybit-exchange.github.io/docs/inverse/#t-marketopeninterest
+// * https://bybit-exchange.github.io/docs/linear/#t-marketopeninterest
+#[derive(Serialize, Deserialize)]
+struct RawOpenInterestMsg {
+    symbol: String,
+    open_interest: f64, // contracts
+    timestamp: i64,     // seconds
+    #[serde(flatten)]
+    extra: HashMap<String, Value>,
+}
+
+// See:
+// * https://bybit-exchange.github.io/docs/inverse/#t-fundingrate
+// * https://bybit-exchange.github.io/docs/linear/#t-fundingrate
+#[derive(Serialize, Deserialize)]
+struct RawFundingRateMsg {
+    symbol: String,
+    funding_rate: Value, // string in inverse markets, number in linear markets
+    funding_rate_timestamp: Value, // seconds in inverse markets, RFC 3339 in linear markets
+    #[serde(flatten)]
+    extra: HashMap<String, Value>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct RestfulMsg<T: Sized> {
+    ret_code: i64,
+    ret_msg: String,
+    result: T,
+    #[serde(flatten)]
+    extra: HashMap<String, Value>,
+}
+
+pub(crate) fn extract_symbol(_market_type: MarketType, msg: &str) -> Result<String, ParseError> {
     let json_obj = serde_json::from_str::<HashMap<String, Value>>(msg)
-        .map_err(|_e| SimpleError::new(format!("Failed to parse the JSON string {msg}")))?;
+        .map_err(|_e| ParseError::malformed("invalid JSON"))?;
     if json_obj.contains_key("topic") && json_obj["topic"].is_string() {
-        let symbol = json_obj["topic"].as_str().unwrap().split('.').last().unwrap();
+        let topic = json_str(&json_obj["topic"], "topic")?;
+        let symbol = topic
+            .split('.')
+            .next_back()
+            .ok_or_else(|| ParseError::unsupported(format!("Invalid topic {topic}")))?;
         Ok(symbol.to_string())
     } else if json_obj.contains_key("ret_code")
         && json_obj.contains_key("ret_msg")
         && json_obj.contains_key("result")
     {
         // Data from RESTful APIs
-        if json_obj["ret_code"].as_i64().unwrap() != 0 {
-            return Err(SimpleError::new(format!("Error HTTP response {msg}")));
+        if json_i64(&json_obj["ret_code"], "ret_code")? != 0 {
+            return Err(ParseError::unsupported("Error HTTP response"));
         }
-        let arr = json_obj["result"].as_array().unwrap();
-        Ok(arr[0]["symbol"].as_str().unwrap().to_string())
+        let result = &json_obj["result"];
+        let symbol = if let Some(arr) = result.as_array() {
+            arr.first().and_then(|x| x["symbol"].as_str())
+        } else {
+            result["symbol"].as_str()
+        };
+        symbol
+            .map(|x| x.to_string())
+            .ok_or_else(|| ParseError::unsupported("Failed to extract symbol"))
     } else {
-        Err(SimpleError::new(format!("Failed to extract symbol from {msg}")))
+        Err(ParseError::unsupported("Failed to extract symbol"))
     }
 }
 
 pub(crate) fn extract_timestamp(
     _market_type: MarketType,
     msg: &str,
-) -> Result<Option<i64>, SimpleError> {
+) -> Result<Option<i64>, ParseError> {
     let json_obj = serde_json::from_str::<HashMap<String, Value>>(msg)
-        .map_err(|_e| SimpleError::new(format!("Failed to parse the JSON string {msg}")))?;
+        .map_err(|_e| ParseError::malformed("invalid JSON"))?;
     if json_obj.contains_key("topic") && json_obj["topic"].is_string() {
-        let msg_type = json_obj["topic"].as_str().unwrap().split('.').next().unwrap();
+        let msg_type = json_str(&json_obj["topic"], "topic")?.split('.').next().unwrap();
         match msg_type {
             "trade" => {
-                let raw_trades = json_obj["data"].as_array().unwrap();
+                let raw_trades = json_array(json_obj.get("data").unwrap_or(&Value::Null), "data")?;
                 let timestamp = raw_trades
                     .iter()
-                    .map(|raw_trade| {
-                        if raw_trade["trade_time_ms"].is_i64() {
-                            raw_trade["trade_time_ms"].as_i64().unwrap()
-                        } else {
-                            raw_trade["trade_time_ms"].as_str().unwrap().parse::<i64>().unwrap()
-                        }
-                    })
+                    .map(|raw_trade| json_i64(&raw_trade["trade_time_ms"], "trade_time_ms"))
+                    .collect::<Result<Vec<i64>, ParseError>>()?
+                    .into_iter()
                     .max();
 
                 if timestamp.is_none() {
-                    Err(SimpleError::new(format!("data is empty in {msg}")))
+                    Err(ParseError::unsupported("data is empty"))
                 } else {
                     Ok(timestamp)
                 }
             }
             _ => {
-                let timestamp_e6 = &json_obj["timestamp_e6"];
-                let timestamp = if timestamp_e6.is_i64() {
-                    timestamp_e6.as_i64().unwrap()
-                } else {
-                    timestamp_e6.as_str().unwrap().parse::<i64>().unwrap()
-                } / 1000;
-                Ok(Some(timestamp))
+                let timestamp_e6 =
+                    json_obj.get("timestamp_e6").ok_or_else(ParseError::missing_timestamp)?;
+                Ok(Some(json_i64(timestamp_e6, "timestamp_e6")? / 1000))
             }
         }
     } else if json_obj.contains_key("ret_code")
@@ -185,20 +251,26 @@ pub(crate) fn extract_timestamp(
         && json_obj.contains_key("result")
     {
         // Data from RESTful APIs
-        if json_obj["ret_code"].as_i64().unwrap() != 0 {
-            return Err(SimpleError::new(format!("Error HTTP response {msg}")));
+        if json_i64(&json_obj["ret_code"], "ret_code")? != 0 {
+            return Err(ParseError::unsupported("Error HTTP response"));
         }
-        Ok(json_obj
+        json_obj
             .get("time_now")
-            .map(|x| (x.as_str().unwrap().parse::<f64>().unwrap() * 1000.0) as i64))
+            .and_then(|x| x.as_str())
+            .map(|x| -> Result<i64, ParseError> { Ok((x.parse::<f64>()? * 1000.0) as i64) })
+            .transpose()
     } else {
-        Err(SimpleError::new(format!("Failed to extract timestamp from {msg}")))
+        Err(ParseError::missing_timestamp())
     }
 }
 
 pub(crate) fn get_msg_type(msg: &str) -> MessageType {
-    if let Ok(ws_msg) = serde_json::from_str::<HashMap<String, Value>>(msg) {
-        let table = ws_msg.get("topic").unwrap().as_str().unwrap();
+    if let Ok(rest_msg) = serde_json::from_str::<RestfulMsg<Vec<RawOpenInterestMsg>>>(msg) {
+        if rest_msg.ret_code == 0 { MessageType::OpenInterest } else { MessageType::Other }
+    } else if let Ok(rest_msg) = serde_json::from_str::<RestfulMsg<RawFundingRateMsg>>(msg) {
+        if rest_msg.ret_code == 0 { MessageType::FundingRate } else { MessageType::Other }
+    } else if let Ok(ws_msg) = serde_json::from_str::<HashMap<String, Value>>(msg) {
+        let table = ws_msg.get("topic").and_then(|x| x.as_str()).unwrap_or_default();
         let channel = {
             let arr = table.split('.').collect::<Vec<&str>>();
             arr[0]
@@ -207,9 +279,9 @@ pub(crate) fn get_msg_type(msg: &str) -> MessageType {
             MessageType::Trade
         } else if channel == "orderBookL2_25" {
             MessageType::L2Event
-        } else if table == "instrument_info" {
+        } else if channel == "instrument_info" {
             MessageType::Ticker
-        } else if table == "klineV2" || table == "candle" {
+        } else if channel == "klineV2" || channel == "candle" {
             MessageType::Candlestick
         } else {
             MessageType::Other
@@ -219,73 +291,69 @@ pub(crate) fn get_msg_type(msg: &str) -> MessageType {
     }
 }
 
-pub(crate) fn parse_trade(
-    market_type: MarketType,
-    msg: &str,
-) -> Result<Vec<TradeMsg>, SimpleError> {
+pub(crate) fn parse_trade(market_type: MarketType, msg: &str) -> Result<Vec<TradeMsg>, ParseError> {
     match market_type {
         MarketType::InverseSwap | MarketType::InverseFuture => {
-            let ws_msg =
-                serde_json::from_str::<WebsocketMsg<InverseTradeMsg>>(msg).map_err(|_e| {
-                    SimpleError::new(format!(
-                        "Failed to deserialize {msg} to WebsocketMsg<InverseTradeMsg>"
-                    ))
-                })?;
+            let ws_msg = serde_json::from_str::<WebsocketMsg<InverseTradeMsg>>(msg)
+                .map_err(|_e| ParseError::malformed("expected WebsocketMsg<InverseTradeMsg>"))?;
 
             let mut trades: Vec<TradeMsg> = ws_msg
                 .data
                 .into_iter()
-                .map(|raw_trade| TradeMsg {
-                    exchange: EXCHANGE_NAME.to_string(),
-                    market_type,
-                    symbol: raw_trade.symbol.clone(),
-                    pair: crypto_pair::normalize_pair(&raw_trade.symbol, EXCHANGE_NAME).unwrap(),
-                    msg_type: MessageType::Trade,
-                    timestamp: raw_trade.trade_time_ms,
-                    price: raw_trade.price,
-                    quantity_base: raw_trade.size / raw_trade.price,
-                    // Each inverse contract value is 1 USD, see:
-                    // https://www.bybit.com/data/basic/inverse/contract-detail?symbol=BTCUSD
-                    // https://www.bybit.com/data/basic/future-inverse/contract-detail?symbol=BTCUSD0625
-                    quantity_quote: raw_trade.size,
-                    quantity_contract: Some(raw_trade.size),
-                    side: if raw_trade.side == "Sell" { TradeSide::Sell } else { TradeSide::Buy },
-                    trade_id: raw_trade.trade_id.clone(),
-                    json: serde_json::to_string(&raw_trade).unwrap(),
+                .map(|raw_trade| -> Result<TradeMsg, ParseError> {
+                    Ok(TradeMsg {
+                        exchange: EXCHANGE_NAME.to_string(),
+                        market_type,
+                        symbol: raw_trade.symbol.clone(),
+                        pair: crypto_pair::normalize_pair(&raw_trade.symbol, EXCHANGE_NAME)
+                            .ok_or_else(|| ParseError::symbol_normalization(&raw_trade.symbol))?,
+                        msg_type: MessageType::Trade,
+                        timestamp: raw_trade.trade_time_ms,
+                        price: raw_trade.price,
+                        quantity_base: raw_trade.size / raw_trade.price,
+                        // Each inverse contract value is 1 USD, see:
+                        // https://www.bybit.com/data/basic/inverse/contract-detail?symbol=BTCUSD
+                        // https://www.bybit.com/data/basic/future-inverse/contract-detail?symbol=BTCUSD0625
+                        quantity_quote: raw_trade.size,
+                        quantity_contract: Some(raw_trade.size),
+                        price_exact: None,
+                        quantity_exact: None,
+                        side: if raw_trade.side == "Sell" { TradeSide::Sell } else { TradeSide::Buy },
+                        trade_id: raw_trade.trade_id.clone(),
+                        json: serde_json::to_string(&raw_trade).unwrap(),
+                    })
                 })
-                .collect();
+                .collect::<Result<Vec<TradeMsg>, ParseError>>()?;
             if trades.len() == 1 {
                 trades[0].json = msg.to_string();
             }
             Ok(trades)
         }
         MarketType::LinearSwap => {
-            let ws_msg =
-                serde_json::from_str::<WebsocketMsg<LinearTradeMsg>>(msg).map_err(|_e| {
-                    SimpleError::new(format!(
-                        "Failed to deserialize {msg} to WebsocketMsg<LinearTradeMsg>"
-                    ))
-                })?;
+            let ws_msg = serde_json::from_str::<WebsocketMsg<LinearTradeMsg>>(msg)
+                .map_err(|_e| ParseError::malformed("expected WebsocketMsg<LinearTradeMsg>"))?;
 
             let mut trades: Vec<TradeMsg> = ws_msg
                 .data
                 .into_iter()
-                .map(|raw_trade| {
-                    let price = raw_trade.price.parse::<f64>().unwrap();
-                    TradeMsg {
+                .map(|raw_trade| -> Result<TradeMsg, ParseError> {
+                    let price = raw_trade.price.parse::<f64>()?;
+                    Ok(TradeMsg {
                         exchange: EXCHANGE_NAME.to_string(),
                         market_type,
                         symbol: raw_trade.symbol.clone(),
                         pair: crypto_pair::normalize_pair(&raw_trade.symbol, EXCHANGE_NAME)
-                            .unwrap(),
+                            .ok_or_else(|| ParseError::symbol_normalization(&raw_trade.symbol))?,
                         msg_type: MessageType::Trade,
-                        timestamp: raw_trade.trade_time_ms.parse::<i64>().unwrap(),
+                        timestamp: raw_trade.trade_time_ms.parse::<i64>()?,
                         price,
                         // Each linear contract value is 1 coin, see:
                         // https://www.bybit.com/data/basic/linear/contract-detail?symbol=BTCUSDT
                         quantity_base: raw_trade.size,
                         quantity_quote: price * raw_trade.size,
                         quantity_contract: Some(raw_trade.size),
+                        price_exact: parse_exact(&raw_trade.price),
+                        quantity_exact: None,
                         side: if raw_trade.side == "Sell" {
                             TradeSide::Sell
                         } else {
@@ -293,42 +361,248 @@ pub(crate) fn parse_trade(
                         },
                         trade_id: raw_trade.trade_id.clone(),
                         json: serde_json::to_string(&raw_trade).unwrap(),
-                    }
+                    })
                 })
-                .collect();
+                .collect::<Result<Vec<TradeMsg>, ParseError>>()?;
             if trades.len() == 1 {
                 trades[0].json = msg.to_string();
             }
             Ok(trades)
         }
-        _ => Err(SimpleError::new(format!("Unknown market_type {market_type}"))),
+        _ => Err(ParseError::unsupported(format!("Unknown market_type {market_type}"))),
     }
 }
 
-pub(crate) fn parse_l2(
+pub(crate) fn parse_liquidation(
+    market_type: MarketType,
+    msg: &str,
+) -> Result<Vec<LiquidationMsg>, ParseError> {
+    let ws_msg = serde_json::from_str::<LiquidationWebsocketMsg>(msg)
+        .map_err(|_e| ParseError::malformed("expected LiquidationWebsocketMsg"))?;
+    if !ws_msg.topic.starts_with("liquidation.") {
+        return Err(ParseError::unsupported(format!("Unsupported topic {}", ws_msg.topic)));
+    }
+    let raw_msg = ws_msg.data;
+    let pair = crypto_pair::normalize_pair(&raw_msg.symbol, EXCHANGE_NAME)
+        .ok_or_else(|| ParseError::symbol_normalization(&raw_msg.symbol))?;
+    let price = raw_msg.price.parse::<f64>()?;
+    let quantity = raw_msg.qty.parse::<f64>()?;
+    let (quantity_base, quantity_quote) = match market_type {
+        // Each inverse contract value is 1 USD
+        MarketType::InverseSwap | MarketType::InverseFuture => (quantity / price, quantity),
+        // Each linear contract value is 1 coin
+        MarketType::LinearSwap => (quantity, price * quantity),
+        _ => return Err(ParseError::unsupported(format!("Unknown market_type {market_type}"))),
+    };
+
+    Ok(vec![LiquidationMsg {
+        exchange: EXCHANGE_NAME.to_string(),
+        market_type,
+        symbol: raw_msg.symbol.clone(),
+        pair,
+        timestamp: raw_msg.time,
+        // the side of the liquidated position, the opposite of the order side
+        side: if raw_msg.side == "Buy" { TradeSide::Sell } else { TradeSide::Buy },
+        price,
+        quantity_base,
+        quantity_quote,
+        quantity_contract: Some(quantity),
+        json: msg.to_string(),
+    }])
+}
+
+pub(crate) fn parse_open_interest(
+    market_type: MarketType,
+    msg: &str,
+) -> Result<Vec<OpenInterestMsg>, ParseError> {
+    let rest_msg = serde_json::from_str::<RestfulMsg<Vec<RawOpenInterestMsg>>>(msg)
+        .map_err(|_e| ParseError::malformed("expected RestfulMsg<Vec<RawOpenInterestMsg>>"))?;
+    if rest_msg.ret_code != 0 {
+        return Err(ParseError::unsupported("Error HTTP response"));
+    }
+    let mut open_interests = rest_msg
+        .result
+        .into_iter()
+        .map(|raw_msg| -> Result<OpenInterestMsg, ParseError> {
+            let pair = crypto_pair::normalize_pair(&raw_msg.symbol, EXCHANGE_NAME)
+                .ok_or_else(|| ParseError::symbol_normalization(&raw_msg.symbol))?;
+            let timestamp = raw_msg.timestamp * 1000;
+            let (quantity_base, quantity_quote) = calc_open_interest(
+                EXCHANGE_NAME,
+                market_type,
+                &pair,
+                None,
+                raw_msg.open_interest,
+                timestamp,
+            )?;
+            Ok(OpenInterestMsg {
+                exchange: EXCHANGE_NAME.to_string(),
+                market_type,
+                symbol: raw_msg.symbol.clone(),
+                pair,
+                msg_type: MessageType::OpenInterest,
+                timestamp,
+                quantity_contract: raw_msg.open_interest,
+                quantity_base,
+                quantity_quote,
+                json: serde_json::to_string(&raw_msg).unwrap(),
+            })
+        })
+        .collect::<Result<Vec<OpenInterestMsg>, ParseError>>()?;
+    if open_interests.len() == 1 {
+        open_interests[0].json = msg.to_string();
+    }
+    Ok(open_interests)
+}
+
+// The default funding interval, snapshots of instrument_info carry the actual one
+const FUNDING_INTERVAL: i64 = 8 * 3600 * 1000;
+
+/// Parses the `prev-funding-rate` RESTful API and snapshots of `instrument_info`,
+/// deltas are skipped because they contain changed fields only.
+pub(crate) fn parse_funding_rate(
     market_type: MarketType,
     msg: &str,
-) -> Result<Vec<OrderBookMsg>, SimpleError> {
-    let ws_msg = serde_json::from_str::<RawOrderbookMsg>(msg).map_err(|_e| {
-        SimpleError::new(format!("Failed to deserialize {msg} to RawOrderbookMsg"))
-    })?;
-    let symbol = ws_msg.topic.strip_prefix("orderBookL2_25.").unwrap();
+) -> Result<Vec<FundingRateMsg>, ParseError> {
+    if let Ok(rest_msg) = serde_json::from_str::<RestfulMsg<RawFundingRateMsg>>(msg) {
+        if rest_msg.ret_code != 0 {
+            return Err(ParseError::unsupported("Error HTTP response"));
+        }
+        let raw_msg = rest_msg.result;
+        let pair = crypto_pair::normalize_pair(&raw_msg.symbol, EXCHANGE_NAME)
+            .ok_or_else(|| ParseError::symbol_normalization(&raw_msg.symbol))?;
+        let funding_rate = if let Some(s) = raw_msg.funding_rate.as_str() {
+            s.parse::<f64>()?
+        } else {
+            raw_msg
+                .funding_rate
+                .as_f64()
+                .ok_or_else(|| ParseError::unsupported("Failed to extract funding_rate"))?
+        };
+        let funding_time = if let Some(s) = raw_msg.funding_rate_timestamp.as_str() {
+            DateTime::parse_from_rfc3339(s)
+                .map_err(|_e| ParseError::malformed("invalid funding_rate_timestamp"))?
+                .timestamp_millis()
+        } else {
+            convert_timestamp(&raw_msg.funding_rate_timestamp)
+                .ok_or_else(|| ParseError::malformed("invalid funding_rate_timestamp"))?
+        };
+        let timestamp = rest_msg
+            .extra
+            .get("time_now")
+            .and_then(|x| x.as_str())
+            .map(|x| -> Result<i64, ParseError> { Ok((x.parse::<f64>()? * 1000.0) as i64) })
+            .transpose()?
+            .ok_or_else(ParseError::missing_timestamp)?;
+        return Ok(vec![FundingRateMsg {
+            exchange: EXCHANGE_NAME.to_string(),
+            market_type,
+            symbol: raw_msg.symbol.clone(),
+            pair,
+            msg_type: MessageType::FundingRate,
+            timestamp,
+            funding_rate,
+            funding_time,
+            estimated_rate: None,
+            funding_interval: FUNDING_INTERVAL,
+            funding_rate_cap: None,
+            funding_rate_floor: None,
+            annualized_rate: annualize_funding_rate(funding_rate, FUNDING_INTERVAL),
+            json: msg.to_string(),
+        }]);
+    }
+
+    let ws_msg = serde_json::from_str::<RawTickerMsg>(msg)
+        .map_err(|_e| ParseError::malformed("expected RawTickerMsg"))?;
+    if ws_msg.type_ != "snapshot" {
+        return Ok(Vec::new());
+    }
+    let symbol = ws_msg
+        .topic
+        .split('.')
+        .next_back()
+        .ok_or_else(|| ParseError::unsupported("Failed to extract symbol"))?;
     let pair = crypto_pair::normalize_pair(symbol, EXCHANGE_NAME)
-        .ok_or_else(|| SimpleError::new(format!("Failed to normalize {symbol} from {msg}")))?;
-    let snapshot = ws_msg.type_ == "snapshot";
-    let timestamp = if ws_msg.timestamp_e6.is_i64() {
-        ws_msg.timestamp_e6.as_i64().unwrap()
+        .ok_or_else(|| ParseError::symbol_normalization(symbol))?;
+    // Bybit uses numbers in inverse markets and strings in linear markets
+    let get_f64 = |field: &str| -> Option<f64> {
+        let v = ws_msg.data.get(field)?;
+        if let Some(s) = v.as_str() { s.parse::<f64>().ok() } else { v.as_f64() }
+    };
+    let timestamp = if let Some(s) = ws_msg.timestamp_e6.as_str() {
+        s.parse::<i64>()?
     } else {
-        ws_msg.timestamp_e6.as_str().unwrap().parse::<i64>().unwrap()
+        ws_msg.timestamp_e6.as_i64().ok_or_else(ParseError::missing_timestamp)?
     } / 1000;
+    let funding_rate = get_f64("funding_rate_e6")
+        .ok_or_else(|| ParseError::unsupported("Failed to extract funding_rate_e6"))?
+        / 1e6;
+    let funding_time = ws_msg
+        .data
+        .get("next_funding_time")
+        .and_then(|x| x.as_str())
+        .and_then(|x| DateTime::parse_from_rfc3339(x).ok())
+        .ok_or_else(|| ParseError::unsupported("Failed to extract next_funding_time"))?
+        .timestamp_millis();
+    // in hours
+    let funding_interval = get_f64("funding_rate_interval")
+        .map(|x| x as i64 * 3600 * 1000)
+        .filter(|x| *x > 0)
+        .unwrap_or(FUNDING_INTERVAL);
 
-    let parse_order = |raw_order: &RawOrder| -> Order {
-        let price = raw_order.price.parse::<f64>().unwrap();
+    Ok(vec![FundingRateMsg {
+        exchange: EXCHANGE_NAME.to_string(),
+        market_type,
+        symbol: symbol.to_string(),
+        pair,
+        msg_type: MessageType::FundingRate,
+        timestamp,
+        funding_rate,
+        funding_time,
+        estimated_rate: get_f64("predicted_funding_rate_e6").map(|x| x / 1e6),
+        funding_interval,
+        funding_rate_cap: None,
+        funding_rate_floor: None,
+        annualized_rate: annualize_funding_rate(funding_rate, funding_interval),
+        json: msg.to_string(),
+    }])
+}
+
+pub(crate) fn parse_l2(
+    market_type: MarketType,
+    msg: &str,
+) -> Result<Vec<OrderBookMsg>, ParseError> {
+    let ws_msg = serde_json::from_str::<RawOrderbookMsg>(msg)
+        .map_err(|_e| ParseError::malformed("expected RawOrderbookMsg"))?;
+    let symbol = ws_msg
+        .topic
+        .strip_prefix("orderBookL2_25.")
+        .ok_or_else(|| ParseError::unsupported(format!("Unknown topic {}", ws_msg.topic)))?;
+    let pair = crypto_pair::normalize_pair(symbol, EXCHANGE_NAME)
+        .ok_or_else(|| ParseError::symbol_normalization(symbol))?;
+    let snapshot = ws_msg.type_ == "snapshot";
+    let timestamp = json_i64(&ws_msg.timestamp_e6, "timestamp_e6")? / 1000;
+
+    let parse_order = |raw_order: &RawOrder| -> Result<Order, ParseError> {
+        let price = raw_order.price.parse::<f64>()?;
         let quantity = raw_order.size.unwrap_or(0.0);
-        let (quantity_base, quantity_quote, quantity_contract) =
-            calc_quantity_and_volume(EXCHANGE_NAME, market_type, &pair, price, quantity);
+        let (quantity_base, quantity_quote, quantity_contract) = calc_quantity_and_volume(
+            EXCHANGE_NAME,
+            market_type,
+            &pair,
+            price,
+            quantity,
+            timestamp,
+        )?;
 
-        Order { price, quantity_base, quantity_quote, quantity_contract }
+        Ok(Order {
+            price,
+            quantity_base,
+            quantity_quote,
+            quantity_contract,
+            price_exact: parse_exact(&raw_order.price),
+            quantity_exact: None,
+        })
     };
 
     let mut orderbook = OrderBookMsg {
@@ -350,7 +624,7 @@ pub(crate) fn parse_l2(
         MarketType::InverseSwap | MarketType::InverseFuture => {
             if snapshot {
                 serde_json::from_value::<Vec<RawOrder>>(ws_msg.data.clone()).map_err(|_e| {
-                    SimpleError::new(format!(
+                    ParseError::malformed(format!(
                         "Failed to deserialize {} to Vec<RawOrder>",
                         ws_msg.data
                     ))
@@ -358,7 +632,7 @@ pub(crate) fn parse_l2(
             } else {
                 let tmp = serde_json::from_value::<OrderbookDelta>(ws_msg.data.clone()).map_err(
                     |_e| {
-                        SimpleError::new(format!(
+                        ParseError::malformed(format!(
                             "Failed to deserialize {} to OrderbookDelta",
                             ws_msg.data
                         ))
@@ -375,7 +649,7 @@ pub(crate) fn parse_l2(
             if snapshot {
                 let tmp = serde_json::from_value::<LinearOrderbookSnapshot>(ws_msg.data.clone())
                     .map_err(|_e| {
-                        SimpleError::new(format!(
+                        ParseError::malformed(format!(
                             "Failed to deserialize {} to LinearOrderbookSnapshot",
                             ws_msg.data
                         ))
@@ -384,7 +658,7 @@ pub(crate) fn parse_l2(
             } else {
                 let tmp = serde_json::from_value::<OrderbookDelta>(ws_msg.data.clone()).map_err(
                     |_e| {
-                        SimpleError::new(format!(
+                        ParseError::malformed(format!(
                             "Failed to deserialize {} to OrderbookDelta",
                             ws_msg.data
                         ))
@@ -397,11 +671,11 @@ pub(crate) fn parse_l2(
                 v
             }
         }
-        _ => return Err(SimpleError::new(format!("Unknown market_type {market_type}"))),
+        _ => return Err(ParseError::unsupported(format!("Unknown market_type {market_type}"))),
     };
 
     for raw_order in raw_orders.iter() {
-        let order = parse_order(raw_order);
+        let order = parse_order(raw_order)?;
         if raw_order.side == "Buy" {
             orderbook.bids.push(order);
         } else {
@@ -411,56 +685,162 @@ pub(crate) fn parse_l2(
     Ok(vec![orderbook])
 }
 
+// https://bybit-exchange.github.io/docs/inverse/#t-websocketinstrumentinfo
+// https://bybit-exchange.github.io/docs/linear/#t-websocketinstrumentinfo
+#[derive(Serialize, Deserialize)]
+struct RawTickerMsg {
+    topic: String,
+    #[serde(rename = "type")]
+    type_: String, // snapshot, delta
+    data: HashMap<String, Value>,
+    timestamp_e6: Value, // i64 in inverse markets, string in linear markets
+    #[serde(flatten)]
+    extra: HashMap<String, Value>,
+}
+
+/// Only snapshots are parsed, because deltas contain changed fields only.
+pub(crate) fn parse_ticker(
+    market_type: MarketType,
+    msg: &str,
+) -> Result<Vec<TickerMsg>, ParseError> {
+    let ws_msg = serde_json::from_str::<RawTickerMsg>(msg)
+        .map_err(|_e| ParseError::malformed("expected RawTickerMsg"))?;
+    if ws_msg.type_ != "snapshot" {
+        return Ok(Vec::new());
+    }
+    let symbol = ws_msg
+        .topic
+        .split('.')
+        .next_back()
+        .ok_or_else(|| ParseError::unsupported("Failed to extract symbol"))?;
+    let pair = crypto_pair::normalize_pair(symbol, EXCHANGE_NAME)
+        .ok_or_else(|| ParseError::symbol_normalization(symbol))?;
+
+    // Bybit uses numbers in inverse markets and strings in linear markets
+    let to_f64 = |v: &Value| -> Option<f64> {
+        if let Some(s) = v.as_str() { s.parse::<f64>().ok() } else { v.as_f64() }
+    };
+    let get_f64 = |field: &str| -> Result<f64, ParseError> {
+        ws_msg
+            .data
+            .get(field)
+            .and_then(to_f64)
+            .ok_or_else(|| ParseError::unsupported(format!("Failed to extract {field}")))
+    };
+    let timestamp = to_f64(&ws_msg.timestamp_e6)
+        .map(|x| (x / 1000.0) as i64)
+        .ok_or_else(ParseError::missing_timestamp)?;
+
+    let close = get_f64("last_price")?;
+    let (volume, quote_volume, open_interest) =
+        if market_type == MarketType::LinearSwap || market_type == MarketType::LinearFuture {
+            (
+                get_f64("volume_24h_e8")? / 1e8,
+                get_f64("turnover_24h_e8")? / 1e8,
+                get_f64("open_interest_e8")? / 1e8,
+            )
+        } else {
+            let (_, quote_volume, _) = calc_quantity_and_volume(
+                EXCHANGE_NAME,
+                market_type,
+                &pair,
+                close,
+                get_f64("volume_24h")?,
+                timestamp,
+            )?;
+            let (open_interest, _, _) = calc_quantity_and_volume(
+                EXCHANGE_NAME,
+                market_type,
+                &pair,
+                close,
+                get_f64("open_interest")?,
+                timestamp,
+            )?;
+            (get_f64("turnover_24h_e8")? / 1e8, quote_volume, open_interest)
+        };
+
+    let ticker_msg = TickerMsg {
+        exchange: EXCHANGE_NAME.to_string(),
+        market_type,
+        symbol: symbol.to_string(),
+        pair,
+        msg_type: MessageType::Ticker,
+        timestamp,
+        open: get_f64("prev_price_24h")?,
+        high: get_f64("high_price_24h")?,
+        low: get_f64("low_price_24h")?,
+        close,
+        volume,
+        quote_volume,
+        last_quantity: None,
+        best_bid_price: get_f64("bid1_price").ok(),
+        best_bid_quantity: None,
+        best_ask_price: get_f64("ask1_price").ok(),
+        best_ask_quantity: None,
+        open_interest: Some(open_interest),
+        open_interest_quote: Some(open_interest * close),
+        json: msg.to_string(),
+    };
+    Ok(vec![ticker_msg])
+}
+
 pub(crate) fn parse_candlestick(
     market_type: MarketType,
     msg: &str,
-) -> Result<Vec<CandlestickMsg>, SimpleError> {
+) -> Result<Vec<CandlestickMsg>, ParseError> {
     match market_type {
         MarketType::LinearSwap => {
             let ws_msg = serde_json::from_str::<WebsocketMsg<RawCandlestickMsg>>(msg)
-                .map_err(SimpleError::from)?;
-            let symbol = ws_msg.topic.split('.').last().unwrap();
-            let pair = crypto_pair::normalize_pair(symbol, EXCHANGE_NAME).ok_or_else(|| {
-                SimpleError::new(format!("Failed to normalize {symbol} from {msg}"))
+                .map_err(ParseError::from)?;
+            let symbol = ws_msg.topic.split('.').next_back().ok_or_else(|| {
+                ParseError::unsupported(format!("Invalid topic {}", ws_msg.topic))
             })?;
+            let pair = crypto_pair::normalize_pair(symbol, EXCHANGE_NAME)
+                .ok_or_else(|| ParseError::symbol_normalization(symbol))?;
 
             let candlestick_messages = ws_msg
                 .data
                 .iter()
-                .map(|raw_candlestick| CandlestickMsg {
-                    exchange: EXCHANGE_NAME.to_string(),
-                    market_type,
-                    symbol: symbol.to_string(),
-                    pair: pair.clone(),
-                    msg_type: MessageType::Candlestick,
-                    timestamp: raw_candlestick.timestamp / 1000,
-                    begin_time: raw_candlestick.start,
-                    open: raw_candlestick.open,
-                    high: raw_candlestick.high,
-                    low: raw_candlestick.low,
-                    close: raw_candlestick.close,
-                    volume: raw_candlestick.volume.parse::<f64>().unwrap(),
-                    period: raw_candlestick.period.clone(),
-                    quote_volume: raw_candlestick.turnover.parse::<f64>().ok(),
-                    json: serde_json::to_string(&raw_candlestick).unwrap(),
+                .map(|raw_candlestick| -> Result<CandlestickMsg, ParseError> {
+                    let period =
+                        parse_candle_period(EXCHANGE_NAME, market_type, &raw_candlestick.period)?;
+                    let begin_time = raw_candlestick.start * 1000;
+                    Ok(CandlestickMsg {
+                        exchange: EXCHANGE_NAME.to_string(),
+                        market_type,
+                        symbol: symbol.to_string(),
+                        pair: pair.clone(),
+                        msg_type: MessageType::Candlestick,
+                        timestamp: raw_candlestick.timestamp / 1000,
+                        begin_time,
+                        end_time: period.end_time(begin_time),
+                        open: raw_candlestick.open,
+                        high: raw_candlestick.high,
+                        low: raw_candlestick.low,
+                        close: raw_candlestick.close,
+                        volume: raw_candlestick.volume.parse::<f64>()?,
+                        period,
+                        quote_volume: raw_candlestick.turnover.parse::<f64>().ok(),
+                        is_closed: Some(raw_candlestick.confirm),
+                        json: serde_json::to_string(&raw_candlestick).unwrap(),
+                    })
                 })
-                .collect();
+                .collect::<Result<Vec<CandlestickMsg>, ParseError>>()?;
             Ok(candlestick_messages)
         }
         MarketType::InverseFuture | MarketType::InverseSwap => {
             let ws_msg = serde_json::from_str::<WebsocketMsg<RawCandlestickMsgV2>>(msg)
-                .map_err(SimpleError::from)?;
+                .map_err(ParseError::from)?;
 
             let (period, symbol) = {
                 let arr: Vec<&str> = ws_msg.topic.split('.').collect();
                 if arr.len() != 3 {
-                    return Err(SimpleError::new(format!("Invalid topic format: {msg}")));
+                    return Err(ParseError::unsupported("Invalid topic format"));
                 }
-                (arr[1], arr[2])
+                (parse_candle_period(EXCHANGE_NAME, market_type, arr[1])?, arr[2])
             };
-            let pair = crypto_pair::normalize_pair(symbol, EXCHANGE_NAME).ok_or_else(|| {
-                SimpleError::new(format!("Failed to normalize {symbol} from {msg}"))
-            })?;
+            let pair = crypto_pair::normalize_pair(symbol, EXCHANGE_NAME)
+                .ok_or_else(|| ParseError::symbol_normalization(symbol))?;
 
             let candlestick_messages = ws_msg
                 .data
@@ -472,19 +852,21 @@ pub(crate) fn parse_candlestick(
                     pair: pair.clone(),
                     msg_type: MessageType::Candlestick,
                     timestamp: raw_candlestick.timestamp / 1000,
-                    begin_time: raw_candlestick.start,
+                    begin_time: raw_candlestick.start * 1000,
+                    end_time: period.end_time(raw_candlestick.start * 1000),
                     open: raw_candlestick.open,
                     high: raw_candlestick.high,
                     low: raw_candlestick.low,
                     close: raw_candlestick.close,
                     volume: raw_candlestick.turnover,
-                    period: period.to_string(),
+                    period,
                     quote_volume: Some(raw_candlestick.volume),
+                    is_closed: Some(raw_candlestick.confirm),
                     json: serde_json::to_string(&raw_candlestick).unwrap(),
                 })
                 .collect();
             Ok(candlestick_messages)
         }
-        _ => Err(SimpleError::new(format!("Unknown market type {market_type}"))),
+        _ => Err(ParseError::unsupported(format!("Unknown market type {market_type}"))),
     }
 }
@@ -1,12 +1,17 @@
 use crypto_market_type::MarketType;
 use crypto_msg_type::MessageType;
 
-use crypto_message::{Order, OrderBookMsg, TradeMsg, TradeSide};
+use crypto_message::{
+    CandlestickMsg, L3Action, L3Msg, Order, OrderBookMsg, TickerMsg, TradeMsg, TradeSide,
+};
 
+use crate::{
+    ParseError,
+    exchanges::utils::{infer_candle_period, json_str, parse_exact},
+};
 use chrono::DateTime;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
-use simple_error::SimpleError;
 use std::collections::HashMap;
 
 const EXCHANGE_NAME: &str = "coinbase_pro";
@@ -29,6 +34,25 @@ struct SpotTradeMsg {
     extra: HashMap<String, Value>,
 }
 
+// see https://docs.pro.coinbase.com/#the-full-channel
+#[derive(Serialize, Deserialize)]
+struct SpotOrderMsg {
+    #[serde(rename = "type")]
+    type_: String,
+    sequence: u64,
+    product_id: String,
+    time: String,
+    order_id: Option<String>,
+    maker_order_id: Option<String>,
+    side: String, // buy, sell
+    price: Option<String>,
+    size: Option<String>,           // match
+    remaining_size: Option<String>, // open, done
+    new_size: Option<String>,       // change
+    #[serde(flatten)]
+    extra: HashMap<String, Value>,
+}
+
 // see https://docs.pro.coinbase.com/#the-level2-channel
 #[derive(Serialize, Deserialize)]
 struct OrderbookSnapshotMsg {
@@ -53,74 +77,119 @@ struct OrderbookUpdateMsg {
     extra: HashMap<String, Value>,
 }
 
-pub(crate) fn extract_symbol(_market_type: MarketType, msg: &str) -> Result<String, SimpleError> {
+// see https://docs.cloud.coinbase.com/exchange/docs/websocket-channels#ticker-channel
+#[derive(Serialize, Deserialize)]
+struct SpotTickerMsg {
+    #[serde(rename = "type")]
+    type_: String,
+    product_id: String,
+    price: String,
+    open_24h: String,
+    volume_24h: String,
+    low_24h: String,
+    high_24h: String,
+    best_bid: String,
+    best_ask: String,
+    time: String,
+    last_size: Option<String>,
+    #[serde(flatten)]
+    extra: HashMap<String, Value>,
+}
+
+// see https://docs.cloud.coinbase.com/exchange/reference/exchangerestapi_getproductcandles
+// time, low, high, open, close, volume
+type RawCandlestickMsg = [f64; 6];
+
+pub(crate) fn extract_symbol(_market_type: MarketType, msg: &str) -> Result<String, ParseError> {
+    if serde_json::from_str::<Vec<RawCandlestickMsg>>(msg).is_ok() {
+        // RESTful candles have no symbol
+        return Ok("NONE".to_string());
+    }
     let json_obj = serde_json::from_str::<HashMap<String, Value>>(msg)
-        .map_err(|_e| SimpleError::new(format!("Failed to parse the JSON string {msg}")))?;
+        .map_err(|_e| ParseError::malformed("invalid JSON"))?;
     if let Some(product_id) = json_obj.get("product_id") {
-        Ok(product_id.as_str().unwrap().to_string())
+        Ok(json_str(product_id, "product_id")?.to_string())
     } else if json_obj.contains_key("asks") && json_obj.contains_key("bids") {
         Ok("NONE".to_string())
     } else {
-        Err(SimpleError::new(format!("Failed to extract symbol from {msg}")))
+        Err(ParseError::unsupported("Failed to extract symbol"))
     }
 }
 
 pub(crate) fn extract_timestamp(
     _market_type: MarketType,
     msg: &str,
-) -> Result<Option<i64>, SimpleError> {
+) -> Result<Option<i64>, ParseError> {
+    if let Ok(raw_candlesticks) = serde_json::from_str::<Vec<RawCandlestickMsg>>(msg) {
+        return Ok(raw_candlesticks.iter().map(|x| x[0] as i64 * 1000).max());
+    }
     let json_obj = serde_json::from_str::<HashMap<String, Value>>(msg)
-        .map_err(|_e| SimpleError::new(format!("Failed to parse the JSON string {msg}")))?;
-    if json_obj.contains_key("type") && json_obj["type"].is_string() {
-        let type_ = json_obj["type"].as_str().unwrap();
+        .map_err(|_e| ParseError::malformed("invalid JSON"))?;
+    if let Some(type_) = json_obj.get("type").and_then(|x| x.as_str()) {
         if type_ == "snapshot" {
             Ok(None) // orderbook snapshot doesn't have a timestamp
         } else if let Some(time) = json_obj.get("time") {
-            let time_str = time.as_str().unwrap();
+            let time_str = json_str(time, "time")?;
             if time_str.starts_with("0001-01-01T00:00:00") {
                 Ok(None)
             } else {
-                Ok(Some(DateTime::parse_from_rfc3339(time_str).unwrap().timestamp_millis()))
+                Ok(Some(DateTime::parse_from_rfc3339(time_str)?.timestamp_millis()))
             }
         } else {
-            Err(SimpleError::new(format!("Failed to extract timestamp from {msg}")))
+            Err(ParseError::missing_timestamp())
         }
     } else if json_obj.contains_key("asks") && json_obj.contains_key("bids") {
         // l2_snapshot doesn't have a timestamp
         Ok(None)
     } else {
-        Err(SimpleError::new(format!("Failed to extract timestamp from {msg}")))
+        Err(ParseError::missing_timestamp())
     }
 }
 
-pub(crate) fn parse_trade(
-    market_type: MarketType,
-    msg: &str,
-) -> Result<Vec<TradeMsg>, SimpleError> {
+pub(crate) fn get_msg_type(msg: &str) -> MessageType {
+    if let Ok(json_obj) = serde_json::from_str::<HashMap<String, Value>>(msg) {
+        if let Some(type_) = json_obj.get("type").and_then(|x| x.as_str()) {
+            match type_ {
+                "match" | "last_match" => MessageType::Trade,
+                "snapshot" | "l2update" => MessageType::L2Event,
+                "ticker" => MessageType::Ticker,
+                // the full channel
+                "received" | "open" | "done" | "change" | "activate" => MessageType::L3Event,
+                _ => MessageType::Other,
+            }
+        } else if json_obj.contains_key("asks") && json_obj.contains_key("bids") {
+            MessageType::L2Snapshot
+        } else {
+            MessageType::Other
+        }
+    } else if serde_json::from_str::<Vec<RawCandlestickMsg>>(msg).is_ok_and(|x| !x.is_empty()) {
+        MessageType::Candlestick
+    } else {
+        MessageType::Other
+    }
+}
+
+pub(crate) fn parse_trade(market_type: MarketType, msg: &str) -> Result<Vec<TradeMsg>, ParseError> {
     let raw_trade = serde_json::from_str::<SpotTradeMsg>(msg)
-        .map_err(|_e| SimpleError::new(format!("Failed to deserialize {msg} to SpotTradeMsg")))?;
-    let timestamp = DateTime::parse_from_rfc3339(&raw_trade.time).unwrap();
-    let price = raw_trade.price.parse::<f64>().unwrap();
-    let quantity = raw_trade.size.parse::<f64>().unwrap();
+        .map_err(|_e| ParseError::malformed("expected SpotTradeMsg"))?;
+    let timestamp = DateTime::parse_from_rfc3339(&raw_trade.time)?;
+    let price = raw_trade.price.parse::<f64>()?;
+    let quantity = raw_trade.size.parse::<f64>()?;
 
     let trade = TradeMsg {
         exchange: EXCHANGE_NAME.to_string(),
         market_type,
         symbol: raw_trade.product_id.clone(),
-        pair: crypto_pair::normalize_pair(&raw_trade.product_id, EXCHANGE_NAME).ok_or_else(
-            || {
-                SimpleError::new(format!(
-                    "Failed to normalize {} from {}",
-                    raw_trade.product_id, msg
-                ))
-            },
-        )?,
+        pair: crypto_pair::normalize_pair(&raw_trade.product_id, EXCHANGE_NAME)
+            .ok_or_else(|| ParseError::symbol_normalization(&raw_trade.product_id))?,
         msg_type: MessageType::Trade,
         timestamp: timestamp.timestamp_millis(),
         price,
         quantity_base: quantity,
         quantity_quote: price * quantity,
         quantity_contract: None,
+        price_exact: parse_exact(&raw_trade.price),
+        quantity_exact: parse_exact(&raw_trade.size),
         side: if raw_trade.side == "sell" { TradeSide::Sell } else { TradeSide::Buy },
         trade_id: raw_trade.trade_id.to_string(),
         json: msg.to_string(),
@@ -129,39 +198,50 @@ pub(crate) fn parse_trade(
     Ok(vec![trade])
 }
 
-fn parse_order(raw_order: &[String; 2]) -> Order {
-    let price = raw_order[0].parse::<f64>().unwrap();
-    let quantity_base = raw_order[1].parse::<f64>().unwrap();
+fn parse_order(raw_order: &[String; 2]) -> Result<Order, ParseError> {
+    let price = raw_order[0].parse::<f64>()?;
+    let quantity_base = raw_order[1].parse::<f64>()?;
 
-    Order { price, quantity_base, quantity_quote: price * quantity_base, quantity_contract: None }
+    Ok(Order {
+        price,
+        quantity_base,
+        quantity_quote: price * quantity_base,
+        quantity_contract: None,
+        price_exact: parse_exact(&raw_order[0]),
+        quantity_exact: parse_exact(&raw_order[1]),
+    })
 }
 
-fn parse_change(raw_order: &[String; 3]) -> Order {
-    let price = raw_order[1].parse::<f64>().unwrap();
-    let quantity_base = raw_order[2].parse::<f64>().unwrap();
+fn parse_change(raw_order: &[String; 3]) -> Result<Order, ParseError> {
+    let price = raw_order[1].parse::<f64>()?;
+    let quantity_base = raw_order[2].parse::<f64>()?;
 
-    Order { price, quantity_base, quantity_quote: price * quantity_base, quantity_contract: None }
+    Ok(Order {
+        price,
+        quantity_base,
+        quantity_quote: price * quantity_base,
+        quantity_contract: None,
+        price_exact: parse_exact(&raw_order[1]),
+        quantity_exact: parse_exact(&raw_order[2]),
+    })
 }
 
 pub(crate) fn parse_l2(
     market_type: MarketType,
     msg: &str,
     timestamp: Option<i64>,
-) -> Result<Vec<OrderBookMsg>, SimpleError> {
+) -> Result<Vec<OrderBookMsg>, ParseError> {
     let snapshot = {
-        let obj = serde_json::from_str::<HashMap<String, Value>>(msg).map_err(|_e| {
-            SimpleError::new(format!("Failed to deserialize {msg} to HashMap<String, Value>"))
-        })?;
-        obj.get("type").unwrap().as_str().unwrap() == "snapshot"
+        let obj = serde_json::from_str::<HashMap<String, Value>>(msg)
+            .map_err(|_e| ParseError::malformed("expected HashMap<String, Value>"))?;
+        obj.get("type").and_then(|x| x.as_str()) == Some("snapshot")
     };
     if snapshot {
-        let orderbook_snapshot =
-            serde_json::from_str::<OrderbookSnapshotMsg>(msg).map_err(|_e| {
-                SimpleError::new(format!("Failed to deserialize {msg} to OrderbookSnapshotMsg"))
-            })?;
+        let orderbook_snapshot = serde_json::from_str::<OrderbookSnapshotMsg>(msg)
+            .map_err(|_e| ParseError::malformed("expected OrderbookSnapshotMsg"))?;
         let symbol = orderbook_snapshot.product_id;
         let pair = crypto_pair::normalize_pair(&symbol, EXCHANGE_NAME)
-            .ok_or_else(|| SimpleError::new(format!("Failed to normalize {symbol} from {msg}")))?;
+            .ok_or_else(|| ParseError::symbol_normalization(&symbol))?;
 
         let orderbook = OrderBookMsg {
             exchange: EXCHANGE_NAME.to_string(),
@@ -169,24 +249,32 @@ pub(crate) fn parse_l2(
             symbol,
             pair,
             msg_type: MessageType::L2Event,
-            timestamp: timestamp.expect("Coinbase level2 snapshot messages don't have timestamp"),
+            // Coinbase level2 snapshot messages don't have timestamp
+            timestamp: timestamp.ok_or_else(ParseError::missing_timestamp)?,
             seq_id: None,
             prev_seq_id: None,
-            asks: orderbook_snapshot.asks.iter().map(parse_order).collect(),
-            bids: orderbook_snapshot.bids.iter().map(parse_order).collect(),
+            asks: orderbook_snapshot
+                .asks
+                .iter()
+                .map(parse_order)
+                .collect::<Result<Vec<Order>, ParseError>>()?,
+            bids: orderbook_snapshot
+                .bids
+                .iter()
+                .map(parse_order)
+                .collect::<Result<Vec<Order>, ParseError>>()?,
             snapshot,
             json: msg.to_string(),
         };
 
         Ok(vec![orderbook])
     } else {
-        let orderbook_updates = serde_json::from_str::<OrderbookUpdateMsg>(msg).map_err(|_e| {
-            SimpleError::new(format!("Failed to deserialize {msg} to OrderbookUpdateMsg"))
-        })?;
+        let orderbook_updates = serde_json::from_str::<OrderbookUpdateMsg>(msg)
+            .map_err(|_e| ParseError::malformed("expected OrderbookUpdateMsg"))?;
         let symbol = orderbook_updates.product_id;
         let pair = crypto_pair::normalize_pair(&symbol, EXCHANGE_NAME)
-            .ok_or_else(|| SimpleError::new(format!("Failed to normalize {symbol} from {msg}")))?;
-        let timestamp = DateTime::parse_from_rfc3339(&orderbook_updates.time).unwrap();
+            .ok_or_else(|| ParseError::symbol_normalization(&symbol))?;
+        let timestamp = DateTime::parse_from_rfc3339(&orderbook_updates.time)?;
 
         let orderbook = OrderBookMsg {
             exchange: EXCHANGE_NAME.to_string(),
@@ -202,13 +290,13 @@ pub(crate) fn parse_l2(
                 .iter()
                 .filter(|x| x[0] == "sell")
                 .map(parse_change)
-                .collect(),
+                .collect::<Result<Vec<Order>, ParseError>>()?,
             bids: orderbook_updates
                 .changes
                 .iter()
                 .filter(|x| x[0] == "buy")
                 .map(parse_change)
-                .collect(),
+                .collect::<Result<Vec<Order>, ParseError>>()?,
             snapshot,
             json: msg.to_string(),
         };
@@ -216,3 +304,135 @@ pub(crate) fn parse_l2(
         Ok(vec![orderbook])
     }
 }
+
+pub(crate) fn parse_l3(market_type: MarketType, msg: &str) -> Result<Vec<L3Msg>, ParseError> {
+    let raw_order = serde_json::from_str::<SpotOrderMsg>(msg)
+        .map_err(|_e| ParseError::malformed("expected SpotOrderMsg"))?;
+    let (action, order_id, size) = match raw_order.type_.as_str() {
+        "open" => (L3Action::Open, raw_order.order_id, raw_order.remaining_size),
+        "change" => (L3Action::Change, raw_order.order_id, raw_order.new_size),
+        "done" => (L3Action::Done, raw_order.order_id, raw_order.remaining_size),
+        "match" => (L3Action::Match, raw_order.maker_order_id, raw_order.size),
+        // received and activate orders are not on the book yet
+        _ => return Ok(Vec::new()),
+    };
+    let price = match raw_order.price {
+        Some(price) => price.parse::<f64>()?,
+        // market orders never rest on the book
+        None => return Ok(Vec::new()),
+    };
+    let quantity = match size {
+        Some(size) => size.parse::<f64>()?,
+        None => 0.0,
+    };
+    let order_id = order_id.ok_or_else(|| ParseError::malformed("missing order_id"))?;
+    let timestamp = DateTime::parse_from_rfc3339(&raw_order.time)
+        .map_err(|_e| ParseError::malformed("invalid time"))?;
+
+    let l3_msg = L3Msg {
+        exchange: EXCHANGE_NAME.to_string(),
+        market_type,
+        symbol: raw_order.product_id.clone(),
+        pair: crypto_pair::normalize_pair(&raw_order.product_id, EXCHANGE_NAME)
+            .ok_or_else(|| ParseError::symbol_normalization(&raw_order.product_id))?,
+        msg_type: MessageType::L3Event,
+        timestamp: timestamp.timestamp_millis(),
+        order_id,
+        action,
+        // the maker side for matches
+        side: Some(if raw_order.side == "sell" { TradeSide::Sell } else { TradeSide::Buy }),
+        price: Some(price),
+        quantity_base: quantity,
+        quantity_quote: price * quantity,
+        quantity_contract: None,
+        seq_id: Some(raw_order.sequence),
+        json: msg.to_string(),
+    };
+
+    Ok(vec![l3_msg])
+}
+
+pub(crate) fn parse_ticker(
+    market_type: MarketType,
+    msg: &str,
+) -> Result<Vec<TickerMsg>, ParseError> {
+    let raw_ticker = serde_json::from_str::<SpotTickerMsg>(msg)
+        .map_err(|_e| ParseError::malformed("expected SpotTickerMsg"))?;
+    let timestamp = DateTime::parse_from_rfc3339(&raw_ticker.time)?;
+    let close = raw_ticker.price.parse::<f64>()?;
+    let volume = raw_ticker.volume_24h.parse::<f64>()?;
+
+    let ticker_msg = TickerMsg {
+        exchange: EXCHANGE_NAME.to_string(),
+        market_type,
+        symbol: raw_ticker.product_id.clone(),
+        pair: crypto_pair::normalize_pair(&raw_ticker.product_id, EXCHANGE_NAME)
+            .ok_or_else(|| ParseError::symbol_normalization(&raw_ticker.product_id))?,
+        msg_type: MessageType::Ticker,
+        timestamp: timestamp.timestamp_millis(),
+        open: raw_ticker.open_24h.parse::<f64>()?,
+        high: raw_ticker.high_24h.parse::<f64>()?,
+        low: raw_ticker.low_24h.parse::<f64>()?,
+        close,
+        volume,
+        // Coinbase doesn't provide quote volume, so it is approximated by the last price
+        quote_volume: close * volume,
+        last_quantity: raw_ticker.last_size.map(|x| x.parse::<f64>()).transpose()?,
+        best_bid_price: Some(raw_ticker.best_bid.parse::<f64>()?),
+        best_bid_quantity: None,
+        best_ask_price: Some(raw_ticker.best_ask.parse::<f64>()?),
+        best_ask_quantity: None,
+        open_interest: None,
+        open_interest_quote: None,
+        json: msg.to_string(),
+    };
+
+    Ok(vec![ticker_msg])
+}
+
+pub(crate) fn parse_candlestick(
+    market_type: MarketType,
+    msg: &str,
+    symbol: Option<&str>,
+) -> Result<Vec<CandlestickMsg>, ParseError> {
+    let symbol = symbol.ok_or_else(|| ParseError::unsupported("symbol is required"))?;
+    let pair = crypto_pair::normalize_pair(symbol, EXCHANGE_NAME)
+        .ok_or_else(|| ParseError::symbol_normalization(symbol))?;
+    let raw_candlesticks = serde_json::from_str::<Vec<RawCandlestickMsg>>(msg)
+        .map_err(|_e| ParseError::malformed("expected Vec<RawCandlestickMsg>"))?;
+    // granularity in seconds is a request parameter only
+    let period = infer_candle_period(
+        &raw_candlesticks
+            .iter()
+            .map(|raw_candlestick| raw_candlestick[0] as i64 * 1000)
+            .collect::<Vec<i64>>(),
+    )?;
+
+    let candlesticks = raw_candlesticks
+        .into_iter()
+        .map(|raw_candlestick| {
+            let begin_time = raw_candlestick[0] as i64 * 1000;
+            CandlestickMsg {
+                exchange: EXCHANGE_NAME.to_string(),
+                market_type,
+                msg_type: MessageType::Candlestick,
+                symbol: symbol.to_string(),
+                pair: pair.clone(),
+                timestamp: begin_time,
+                period,
+                begin_time,
+                end_time: period.end_time(begin_time),
+                open: raw_candlestick[3],
+                high: raw_candlestick[2],
+                low: raw_candlestick[1],
+                close: raw_candlestick[4],
+                volume: raw_candlestick[5],
+                quote_volume: None,
+                is_closed: None,
+                json: serde_json::to_string(&raw_candlestick).unwrap(),
+            }
+        })
+        .collect();
+
+    Ok(candlesticks)
+}
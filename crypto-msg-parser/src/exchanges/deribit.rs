@@ -1,14 +1,22 @@
 use crypto_market_type::MarketType;
 use crypto_msg_type::MessageType;
 
-use crypto_message::{BboMsg, CandlestickMsg, Order, OrderBookMsg, TradeMsg, TradeSide};
+use crypto_message::{
+    BboMsg, CandlestickMsg, FundingRateMsg, IndexPriceMsg, MarkPriceMsg, OpenInterestMsg,
+    OptionTickerMsg, Order, OrderBookMsg, TickerMsg, TradeMsg, TradeSide,
+};
 
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
-use simple_error::SimpleError;
 use std::collections::HashMap;
 
-use crate::exchanges::utils::calc_quantity_and_volume;
+use crate::{
+    ParseError,
+    exchanges::utils::{
+        annualize_funding_rate, calc_open_interest, calc_quantity_and_volume, json_array, json_i64,
+        json_str, parse_candle_period, round,
+    },
+};
 
 const EXCHANGE_NAME: &str = "deribit";
 
@@ -92,81 +100,197 @@ struct RawCandlestickMsg {
     close: f64,
 }
 
-pub(crate) fn extract_symbol(_market_type: MarketType, msg: &str) -> Result<String, SimpleError> {
+#[derive(Serialize, Deserialize)]
+struct TickerStats {
+    volume: Option<f64>,       // volume during last 24h in base currency
+    volume_usd: Option<f64>,   // volume in USD, futures and perpetual swaps only
+    price_change: Option<f64>, // 24-hour price change expressed as a percentage
+    low: Option<f64>,
+    high: Option<f64>,
+    #[serde(flatten)]
+    extra: HashMap<String, Value>,
+}
+
+// See <https://docs.deribit.com/#ticker-instrument_name-interval>
+#[derive(Serialize, Deserialize)]
+struct RawTickerMsg {
+    timestamp: i64,
+    instrument_name: String,
+    stats: TickerStats,
+    last_price: Option<f64>,
+    best_bid_price: Option<f64>,
+    best_bid_amount: f64,
+    best_ask_price: Option<f64>,
+    best_ask_amount: f64,
+    open_interest: f64,
+    #[serde(flatten)]
+    extra: HashMap<String, Value>,
+}
+
+// https://docs.deribit.com/#ticker-instrument_name-interval
+#[derive(Serialize, Deserialize)]
+struct RawFundingRateMsg {
+    timestamp: i64,
+    instrument_name: String,
+    funding_8h: f64,
+    #[serde(flatten)]
+    extra: HashMap<String, Value>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct Greeks {
+    delta: f64,
+    gamma: f64,
+    vega: f64,
+    theta: f64,
+    #[serde(flatten)]
+    extra: HashMap<String, Value>,
+}
+
+// The ticker channel of options, see <https://docs.deribit.com/#ticker-instrument_name-interval>
+#[derive(Serialize, Deserialize)]
+struct RawOptionTickerMsg {
+    timestamp: i64,
+    instrument_name: String,
+    underlying_price: f64,
+    open_interest: f64,
+    mark_iv: f64, // in percentage
+    bid_iv: Option<f64>,
+    ask_iv: Option<f64>,
+    greeks: Greeks,
+    #[serde(flatten)]
+    extra: HashMap<String, Value>,
+}
+
+// See <https://docs.deribit.com/#public-get_book_summary_by_currency>
+#[derive(Serialize, Deserialize)]
+struct RawBookSummaryMsg {
+    instrument_name: String,
+    open_interest: f64,
+    mark_price: f64,
+    creation_timestamp: i64,
+    #[serde(flatten)]
+    extra: HashMap<String, Value>,
+}
+
+// See <https://docs.deribit.com/#markprice-options-index_name>
+#[derive(Serialize, Deserialize)]
+struct RawMarkPriceMsg {
+    instrument_name: String,
+    mark_price: f64,
+    timestamp: i64,
+    #[serde(flatten)]
+    extra: HashMap<String, Value>,
+}
+
+// See <https://docs.deribit.com/#deribit_price_index-index_name>
+#[derive(Serialize, Deserialize)]
+struct RawIndexPriceMsg {
+    index_name: String, // e.g., btc_usd
+    price: f64,
+    timestamp: i64,
+    #[serde(flatten)]
+    extra: HashMap<String, Value>,
+}
+
+pub(crate) fn extract_symbol(_market_type: MarketType, msg: &str) -> Result<String, ParseError> {
     if let Ok(ws_msg) = serde_json::from_str::<WebsocketMsg<Value>>(msg) {
         let channel = ws_msg.params.channel.as_str();
         let data = ws_msg.params.data;
 
         if channel.starts_with("chart.trades.") {
-            let symbol = channel.split('.').nth(2).unwrap();
+            let symbol = channel
+                .split('.')
+                .nth(2)
+                .ok_or_else(|| ParseError::unsupported(format!("Invalid channel {channel}")))?;
             Ok(symbol.to_string())
+        } else if channel.starts_with("markprice.options.") {
+            Ok("ALL".to_string())
+        } else if channel.starts_with("deribit_price_index.") {
+            Ok(json_str(&data["index_name"], "index_name")?.to_string())
         } else if data.is_object() {
-            Ok(data["instrument_name"].as_str().unwrap().to_string())
+            Ok(json_str(&data["instrument_name"], "instrument_name")?.to_string())
         } else if data.is_array() {
-            let arr = data.as_array().unwrap();
-            let symbol = arr.iter().map(|v| v["instrument_name"].as_str().unwrap()).next().unwrap();
+            let arr = json_array(&data, "data")?;
+            let symbol = arr
+                .first()
+                .ok_or_else(|| ParseError::unsupported("data is empty"))
+                .and_then(|v| json_str(&v["instrument_name"], "instrument_name"))?;
             Ok(symbol.to_string())
         } else {
-            Err(SimpleError::new(format!("Unknown websocket message format: {msg}")))
+            Err(ParseError::unsupported("Unknown websocket message format"))
         }
     } else if let Ok(rest_resp) = serde_json::from_str::<RestfulResp<Value>>(msg) {
-        if let Some(json_obj) = rest_resp.result.as_object() {
-            Ok(json_obj["instrument_name"].as_str().unwrap().to_string())
+        if rest_resp.result.is_object() {
+            Ok(json_str(&rest_resp.result["instrument_name"], "instrument_name")?.to_string())
         } else if let Some(arr) = rest_resp.result.as_array() {
             // open interest
-            debug_assert!(msg.contains("open_interest"));
+            if !msg.contains("open_interest") {
+                return Err(ParseError::unsupported("Not an open interest response"));
+            }
             #[allow(clippy::comparison_chain)]
             if arr.len() > 1 {
                 Ok("ALL".to_string())
             } else if arr.len() == 1 {
-                Ok(arr[0]["instrument_name"].as_str().unwrap().to_string())
+                Ok(json_str(&arr[0]["instrument_name"], "instrument_name")?.to_string())
             } else {
                 Ok("NONE".to_string())
             }
         } else {
-            Err(SimpleError::new(format!("Unknown HTTP message format: {msg}")))
+            Err(ParseError::unsupported("Unknown HTTP message format"))
         }
     } else {
-        Err(SimpleError::new(format!("Unsupported message format {msg}")))
+        Err(ParseError::unsupported("Unsupported message format"))
     }
 }
 
 pub(crate) fn extract_timestamp(
     _market_type: MarketType,
     msg: &str,
-) -> Result<Option<i64>, SimpleError> {
+) -> Result<Option<i64>, ParseError> {
     if let Ok(ws_msg) = serde_json::from_str::<WebsocketMsg<Value>>(msg) {
         let channel = ws_msg.params.channel.as_str();
         let data = ws_msg.params.data;
         if channel.starts_with("chart.trades.") {
-            Ok(Some(data["tick"].as_i64().unwrap()))
+            Ok(Some(json_i64(&data["tick"], "tick")?))
         } else if data.is_object() {
-            Ok(Some(data["timestamp"].as_i64().unwrap()))
+            Ok(Some(json_i64(&data["timestamp"], "timestamp")?))
         } else if data.is_array() {
-            let arr = data.as_array().unwrap();
-            let timestamp = arr.iter().map(|x| x["timestamp"].as_i64().unwrap()).max();
+            let timestamp = json_array(&data, "data")?
+                .iter()
+                .map(|x| json_i64(&x["timestamp"], "timestamp"))
+                .collect::<Result<Vec<i64>, ParseError>>()?
+                .into_iter()
+                .max();
 
             if timestamp.is_none() {
-                Err(SimpleError::new(format!("data is empty in {msg}")))
+                Err(ParseError::unsupported("data is empty"))
             } else {
                 Ok(timestamp)
             }
         } else {
-            Err(SimpleError::new(format!("Unsupported websocket message format: {msg}")))
+            Err(ParseError::unsupported("Unsupported websocket message format"))
         }
     } else if let Ok(rest_resp) = serde_json::from_str::<RestfulResp<Value>>(msg) {
-        if let Some(json_obj) = rest_resp.result.as_object() {
-            Ok(Some(json_obj["timestamp"].as_i64().unwrap()))
+        if rest_resp.result.is_object() {
+            Ok(Some(json_i64(&rest_resp.result["timestamp"], "timestamp")?))
         } else if let Some(arr) = rest_resp.result.as_array() {
             // open interest
-            debug_assert!(msg.contains("open_interest"));
-            let timestamp = arr.iter().map(|x| x["creation_timestamp"].as_i64().unwrap()).max();
+            if !msg.contains("open_interest") {
+                return Err(ParseError::unsupported("Not an open interest response"));
+            }
+            let timestamp = arr
+                .iter()
+                .map(|x| json_i64(&x["creation_timestamp"], "creation_timestamp"))
+                .collect::<Result<Vec<i64>, ParseError>>()?
+                .into_iter()
+                .max();
             Ok(timestamp)
         } else {
-            Err(SimpleError::new(format!("Unknown HTTP message format: {msg}")))
+            Err(ParseError::unsupported("Unknown HTTP message format"))
         }
     } else {
-        Err(SimpleError::new(format!("Unsupported message format {msg}")))
+        Err(ParseError::unsupported("Unsupported message format"))
     }
 }
 
@@ -198,29 +322,26 @@ pub(crate) fn get_msg_type(msg: &str) -> MessageType {
     }
 }
 
-pub(crate) fn parse_trade(
-    market_type: MarketType,
-    msg: &str,
-) -> Result<Vec<TradeMsg>, SimpleError> {
-    let ws_msg = serde_json::from_str::<WebsocketMsg<Vec<RawTradeMsg>>>(msg).map_err(|_e| {
-        SimpleError::new(format!("Failed to deserialize {msg} to WebsocketMsg<Vec<RawTradeMsg>>"))
-    })?;
+pub(crate) fn parse_trade(market_type: MarketType, msg: &str) -> Result<Vec<TradeMsg>, ParseError> {
+    let ws_msg = serde_json::from_str::<WebsocketMsg<Vec<RawTradeMsg>>>(msg)
+        .map_err(|_e| ParseError::malformed("expected WebsocketMsg<Vec<RawTradeMsg>>"))?;
     let mut trades: Vec<TradeMsg> = ws_msg
         .params
         .data
         .into_iter()
-        .map(|raw_trade| {
-            let pair =
-                crypto_pair::normalize_pair(&raw_trade.instrument_name, EXCHANGE_NAME).unwrap();
+        .map(|raw_trade| -> Result<TradeMsg, ParseError> {
+            let pair = crypto_pair::normalize_pair(&raw_trade.instrument_name, EXCHANGE_NAME)
+                .ok_or_else(|| ParseError::symbol_normalization(&raw_trade.instrument_name))?;
             let (quantity_base, quantity_quote, quantity_contract) = calc_quantity_and_volume(
                 EXCHANGE_NAME,
                 market_type,
                 &pair,
                 raw_trade.price,
                 raw_trade.amount,
-            );
+                raw_trade.timestamp,
+            )?;
 
-            TradeMsg {
+            Ok(TradeMsg {
                 exchange: EXCHANGE_NAME.to_string(),
                 market_type,
                 symbol: raw_trade.instrument_name.clone(),
@@ -231,12 +352,14 @@ pub(crate) fn parse_trade(
                 quantity_base,
                 quantity_quote,
                 quantity_contract,
+                price_exact: None,
+                quantity_exact: None,
                 side: if raw_trade.direction == "sell" { TradeSide::Sell } else { TradeSide::Buy },
                 trade_id: raw_trade.trade_id.to_string(),
-                json: serde_json::to_string(&raw_trade).unwrap(),
-            }
+                json: serde_json::to_string(&raw_trade)?,
+            })
         })
-        .collect();
+        .collect::<Result<Vec<TradeMsg>, ParseError>>()?;
 
     if trades.len() == 1 {
         trades[0].json = msg.to_string();
@@ -247,11 +370,15 @@ pub(crate) fn parse_trade(
 pub(crate) fn parse_l2(
     market_type: MarketType,
     msg: &str,
-) -> Result<Vec<OrderBookMsg>, SimpleError> {
-    let ws_msg = serde_json::from_str::<WebsocketMsg<RawOrderbookMsg>>(msg).map_err(|_e| {
-        SimpleError::new(format!("Failed to deserialize {msg} to WebsocketMsg<RawOrderbookMsg>"))
-    })?;
-    debug_assert!(ws_msg.params.channel.starts_with("book."));
+) -> Result<Vec<OrderBookMsg>, ParseError> {
+    let ws_msg = serde_json::from_str::<WebsocketMsg<RawOrderbookMsg>>(msg)
+        .map_err(|_e| ParseError::malformed("expected WebsocketMsg<RawOrderbookMsg>"))?;
+    if !ws_msg.params.channel.starts_with("book.") {
+        return Err(ParseError::unsupported(format!(
+            "Unsupported channel {}",
+            ws_msg.params.channel
+        )));
+    }
     let msg_type = if ws_msg.params.channel.matches('.').count() == 2 {
         MessageType::L2Event
     } else {
@@ -259,26 +386,41 @@ pub(crate) fn parse_l2(
     };
     let raw_orderbook = ws_msg.params.data;
     let snapshot = if msg_type == MessageType::L2Event {
-        raw_orderbook.type_.unwrap() == "snapshot"
+        raw_orderbook.type_.as_deref() == Some("snapshot")
     } else {
         true
     };
     let timestamp = raw_orderbook.timestamp;
     let symbol = raw_orderbook.instrument_name;
     let pair = crypto_pair::normalize_pair(&symbol, EXCHANGE_NAME)
-        .ok_or_else(|| SimpleError::new(format!("Failed to normalize {symbol} from {msg}")))?;
-
-    let parse_order = |raw_order: &[Value]| -> Order {
-        let (price, quantity) = if raw_order.len() == 3 {
-            (raw_order[1].as_f64().unwrap(), raw_order[2].as_f64().unwrap())
-        } else {
-            debug_assert_eq!(2, raw_order.len());
-            (raw_order[0].as_f64().unwrap(), raw_order[1].as_f64().unwrap())
+        .ok_or_else(|| ParseError::symbol_normalization(&symbol))?;
+
+    let parse_order = |raw_order: &[Value]| -> Result<Order, ParseError> {
+        let offset = if raw_order.len() == 3 { 1 } else { 0 };
+        let (price, quantity) = match (
+            raw_order.get(offset).and_then(|x| x.as_f64()),
+            raw_order.get(offset + 1).and_then(|x| x.as_f64()),
+        ) {
+            (Some(price), Some(quantity)) => (price, quantity),
+            _ => return Err(ParseError::malformed("expected [price, amount] in order")),
         };
-        let (quantity_base, quantity_quote, quantity_contract) =
-            calc_quantity_and_volume(EXCHANGE_NAME, market_type, &pair, price, quantity);
-
-        Order { price, quantity_base, quantity_quote, quantity_contract }
+        let (quantity_base, quantity_quote, quantity_contract) = calc_quantity_and_volume(
+            EXCHANGE_NAME,
+            market_type,
+            &pair,
+            price,
+            quantity,
+            timestamp,
+        )?;
+
+        Ok(Order {
+            price,
+            quantity_base,
+            quantity_quote,
+            quantity_contract,
+            price_exact: None,
+            quantity_exact: None,
+        })
     };
 
     let orderbook = OrderBookMsg {
@@ -290,8 +432,16 @@ pub(crate) fn parse_l2(
         timestamp,
         seq_id: Some(raw_orderbook.change_id),
         prev_seq_id: raw_orderbook.prev_change_id,
-        asks: raw_orderbook.asks.iter().map(|x| parse_order(x)).collect(),
-        bids: raw_orderbook.bids.iter().map(|x| parse_order(x)).collect(),
+        asks: raw_orderbook
+            .asks
+            .iter()
+            .map(|x| parse_order(x))
+            .collect::<Result<Vec<Order>, ParseError>>()?,
+        bids: raw_orderbook
+            .bids
+            .iter()
+            .map(|x| parse_order(x))
+            .collect::<Result<Vec<Order>, ParseError>>()?,
         snapshot,
         json: msg.to_string(),
     };
@@ -302,18 +452,24 @@ pub(crate) fn parse_l2(
 pub(crate) fn parse_l2_topk(
     market_type: MarketType,
     msg: &str,
-) -> Result<Vec<OrderBookMsg>, SimpleError> {
+) -> Result<Vec<OrderBookMsg>, ParseError> {
     parse_l2(market_type, msg)
 }
 
-pub(crate) fn parse_bbo(market_type: MarketType, msg: &str) -> Result<Vec<BboMsg>, SimpleError> {
-    let ws_msg = serde_json::from_str::<WebsocketMsg<RawBboMsg>>(msg).map_err(SimpleError::from)?;
+pub(crate) fn parse_bbo(market_type: MarketType, msg: &str) -> Result<Vec<BboMsg>, ParseError> {
+    let ws_msg = serde_json::from_str::<WebsocketMsg<RawBboMsg>>(msg).map_err(ParseError::from)?;
 
-    debug_assert!(ws_msg.params.channel.starts_with("quote"));
+    if !ws_msg.params.channel.starts_with("quote") {
+        return Err(ParseError::unsupported(format!(
+            "Unsupported channel {}",
+            ws_msg.params.channel
+        )));
+    }
     let timestamp = ws_msg.params.data.timestamp;
 
     let symbol = ws_msg.params.data.instrument_name.as_str();
-    let pair = crypto_pair::normalize_pair(symbol, EXCHANGE_NAME).unwrap();
+    let pair = crypto_pair::normalize_pair(symbol, EXCHANGE_NAME)
+        .ok_or_else(|| ParseError::symbol_normalization(symbol))?;
 
     let (ask_quantity_base, ask_quantity_quote, ask_quantity_contract) = calc_quantity_and_volume(
         EXCHANGE_NAME,
@@ -321,7 +477,8 @@ pub(crate) fn parse_bbo(market_type: MarketType, msg: &str) -> Result<Vec<BboMsg
         &pair,
         ws_msg.params.data.best_ask_price,
         ws_msg.params.data.best_ask_amount,
-    );
+        timestamp,
+    )?;
 
     let (bid_quantity_base, bid_quantity_quote, bid_quantity_contract) = calc_quantity_and_volume(
         EXCHANGE_NAME,
@@ -329,7 +486,8 @@ pub(crate) fn parse_bbo(market_type: MarketType, msg: &str) -> Result<Vec<BboMsg
         &pair,
         ws_msg.params.data.best_bid_price,
         ws_msg.params.data.best_bid_amount,
-    );
+        timestamp,
+    )?;
 
     let bbo_msg = BboMsg {
         exchange: EXCHANGE_NAME.to_string(),
@@ -346,6 +504,10 @@ pub(crate) fn parse_bbo(market_type: MarketType, msg: &str) -> Result<Vec<BboMsg
         bid_quantity_base,
         bid_quantity_quote,
         bid_quantity_contract,
+        bid_price_exact: None,
+        bid_quantity_exact: None,
+        ask_price_exact: None,
+        ask_quantity_exact: None,
         id: None,
         json: msg.to_string(),
     };
@@ -353,23 +515,343 @@ pub(crate) fn parse_bbo(market_type: MarketType, msg: &str) -> Result<Vec<BboMsg
     Ok(vec![bbo_msg])
 }
 
-pub(crate) fn parse_candlestick(
+pub(crate) fn parse_ticker(
     market_type: MarketType,
     msg: &str,
-) -> Result<Vec<CandlestickMsg>, SimpleError> {
-    let ws_msg =
-        serde_json::from_str::<WebsocketMsg<RawCandlestickMsg>>(msg).map_err(SimpleError::from)?;
-    debug_assert!(ws_msg.params.channel.starts_with("chart."));
-    let period = ws_msg.params.channel.split('.').last().unwrap();
-    let period_value = if period.ends_with('D') {
-        period.strip_suffix('D').unwrap().parse::<i64>().unwrap() * 24 * 60
+) -> Result<Vec<TickerMsg>, ParseError> {
+    let ws_msg = serde_json::from_str::<WebsocketMsg<RawTickerMsg>>(msg)
+        .map_err(|_e| ParseError::malformed("expected WebsocketMsg<RawTickerMsg>"))?;
+    if !ws_msg.params.channel.starts_with("ticker.") {
+        return Err(ParseError::unsupported(format!(
+            "Unsupported channel {}",
+            ws_msg.params.channel
+        )));
+    }
+    let raw_ticker = ws_msg.params.data;
+    let symbol = raw_ticker.instrument_name.as_str();
+    let pair = crypto_pair::normalize_pair(symbol, EXCHANGE_NAME)
+        .ok_or_else(|| ParseError::symbol_normalization(symbol))?;
+    let timestamp = raw_ticker.timestamp;
+
+    let missing = |field: &str| ParseError::unsupported(format!("{field} is null"));
+    let close = raw_ticker.last_price.ok_or_else(|| missing("last_price"))?;
+    let price_change = raw_ticker.stats.price_change.ok_or_else(|| missing("price_change"))?;
+    let volume = raw_ticker.stats.volume.ok_or_else(|| missing("volume"))?;
+    // Option prices are quoted in the base currency, so there is no volume_usd
+    let quote_volume = raw_ticker.stats.volume_usd.unwrap_or(volume * close);
+    let to_base = |price: Option<f64>, quantity: f64| -> Result<Option<f64>, ParseError> {
+        price
+            .map(|price| {
+                calc_quantity_and_volume(
+                    EXCHANGE_NAME,
+                    market_type,
+                    &pair,
+                    price,
+                    quantity,
+                    timestamp,
+                )
+                .map(|x| x.0)
+            })
+            .transpose()
+    };
+    let (open_interest, open_interest_quote, _) = calc_quantity_and_volume(
+        EXCHANGE_NAME,
+        market_type,
+        &pair,
+        close,
+        raw_ticker.open_interest,
+        timestamp,
+    )?;
+
+    let ticker_msg = TickerMsg {
+        exchange: EXCHANGE_NAME.to_string(),
+        market_type,
+        symbol: symbol.to_string(),
+        pair: pair.clone(),
+        msg_type: MessageType::Ticker,
+        timestamp,
+        open: close / (1.0 + price_change / 100.0),
+        high: raw_ticker.stats.high.ok_or_else(|| missing("high"))?,
+        low: raw_ticker.stats.low.ok_or_else(|| missing("low"))?,
+        close,
+        volume,
+        quote_volume,
+        last_quantity: None,
+        best_bid_price: raw_ticker.best_bid_price,
+        best_bid_quantity: to_base(raw_ticker.best_bid_price, raw_ticker.best_bid_amount)?,
+        best_ask_price: raw_ticker.best_ask_price,
+        best_ask_quantity: to_base(raw_ticker.best_ask_price, raw_ticker.best_ask_amount)?,
+        open_interest: Some(open_interest),
+        open_interest_quote: Some(open_interest_quote),
+        json: msg.to_string(),
+    };
+
+    Ok(vec![ticker_msg])
+}
+
+// funding_8h is the rate of 8 hours
+const FUNDING_INTERVAL: i64 = 8 * 3600 * 1000;
+
+/// Parses the `ticker` channel and the `public/ticker` RESTful API of
+/// perpetual swaps.
+///
+/// Deribit charges funding continuously rather than at fixed times, so
+/// `funding_rate` is `funding_8h`, the rate accrued in the past 8 hours, and
+/// `funding_time` is the message time.
+pub(crate) fn parse_funding_rate(
+    market_type: MarketType,
+    msg: &str,
+) -> Result<Vec<FundingRateMsg>, ParseError> {
+    let raw_msg = if let Ok(ws_msg) = serde_json::from_str::<WebsocketMsg<RawFundingRateMsg>>(msg) {
+        if !ws_msg.params.channel.starts_with("ticker.") {
+            return Err(ParseError::unsupported(format!(
+                "Unsupported channel {}",
+                ws_msg.params.channel
+            )));
+        }
+        ws_msg.params.data
+    } else {
+        serde_json::from_str::<RestfulResp<RawFundingRateMsg>>(msg)
+            .map_err(|_e| ParseError::malformed("expected RestfulResp<RawFundingRateMsg>"))?
+            .result
+    };
+    let symbol = raw_msg.instrument_name.as_str();
+    let pair = crypto_pair::normalize_pair(symbol, EXCHANGE_NAME)
+        .ok_or_else(|| ParseError::symbol_normalization(symbol))?;
+
+    Ok(vec![FundingRateMsg {
+        exchange: EXCHANGE_NAME.to_string(),
+        market_type,
+        symbol: symbol.to_string(),
+        pair,
+        msg_type: MessageType::FundingRate,
+        timestamp: raw_msg.timestamp,
+        funding_rate: raw_msg.funding_8h,
+        funding_time: raw_msg.timestamp,
+        estimated_rate: None,
+        funding_interval: FUNDING_INTERVAL,
+        funding_rate_cap: None,
+        funding_rate_floor: None,
+        annualized_rate: annualize_funding_rate(raw_msg.funding_8h, FUNDING_INTERVAL),
+        json: msg.to_string(),
+    }])
+}
+
+pub(crate) fn parse_option_ticker(msg: &str) -> Result<Vec<OptionTickerMsg>, ParseError> {
+    let ws_msg = serde_json::from_str::<WebsocketMsg<RawOptionTickerMsg>>(msg)
+        .map_err(|_e| ParseError::malformed("expected WebsocketMsg<RawOptionTickerMsg>"))?;
+    if !ws_msg.params.channel.starts_with("ticker.") {
+        return Err(ParseError::unsupported(format!(
+            "Unsupported channel {}",
+            ws_msg.params.channel
+        )));
+    }
+    let raw_msg = ws_msg.params.data;
+    let pair = crypto_pair::normalize_pair(&raw_msg.instrument_name, EXCHANGE_NAME)
+        .ok_or_else(|| ParseError::symbol_normalization(&raw_msg.instrument_name))?;
+    // implied volatilities are percentages, and zero if there is no order on that side
+    let to_fraction = |iv: f64| round(iv / 100.0);
+
+    Ok(vec![OptionTickerMsg {
+        exchange: EXCHANGE_NAME.to_string(),
+        market_type: MarketType::EuropeanOption,
+        symbol: raw_msg.instrument_name.clone(),
+        pair,
+        timestamp: raw_msg.timestamp,
+        mark_iv: to_fraction(raw_msg.mark_iv),
+        bid_iv: raw_msg.bid_iv.filter(|x| *x > 0.0).map(to_fraction),
+        ask_iv: raw_msg.ask_iv.filter(|x| *x > 0.0).map(to_fraction),
+        delta: raw_msg.greeks.delta,
+        gamma: raw_msg.greeks.gamma,
+        vega: raw_msg.greeks.vega,
+        theta: raw_msg.greeks.theta,
+        underlying_price: Some(raw_msg.underlying_price),
+        open_interest: Some(raw_msg.open_interest),
+        json: msg.to_string(),
+    }])
+}
+
+/// Parse open interest from the ticker channel or book summaries.
+///
+/// Book summaries of one currency mix futures and options, so the market type
+/// is derived from each instrument name.
+pub(crate) fn parse_open_interest(
+    market_type: MarketType,
+    msg: &str,
+) -> Result<Vec<OpenInterestMsg>, ParseError> {
+    let raw_msgs = if let Ok(ws_msg) = serde_json::from_str::<WebsocketMsg<RawTickerMsg>>(msg) {
+        if !ws_msg.params.channel.starts_with("ticker.") {
+            return Err(ParseError::unsupported(format!(
+                "Unsupported channel {}",
+                ws_msg.params.channel
+            )));
+        }
+        let raw_ticker = ws_msg.params.data;
+        let mark_price = raw_ticker.extra.get("mark_price").and_then(|x| x.as_f64());
+        vec![(
+            raw_ticker.instrument_name,
+            market_type,
+            raw_ticker.open_interest,
+            mark_price.or(raw_ticker.last_price),
+            raw_ticker.timestamp,
+            msg.to_string(),
+        )]
     } else {
-        period.parse::<i64>().unwrap()
+        let rest_resp = serde_json::from_str::<RestfulResp<Vec<RawBookSummaryMsg>>>(msg)
+            .map_err(|_e| ParseError::malformed("expected RestfulResp<Vec<RawBookSummaryMsg>>"))?;
+        rest_resp
+            .result
+            .into_iter()
+            .map(|raw_msg| {
+                let market_type = match crypto_pair::get_market_type(
+                    &raw_msg.instrument_name,
+                    EXCHANGE_NAME,
+                    None,
+                ) {
+                    MarketType::Unknown => market_type,
+                    x => x,
+                };
+                let json = serde_json::to_string(&raw_msg).unwrap();
+                (
+                    raw_msg.instrument_name,
+                    market_type,
+                    raw_msg.open_interest,
+                    Some(raw_msg.mark_price),
+                    raw_msg.creation_timestamp,
+                    json,
+                )
+            })
+            .collect()
     };
 
+    let mut open_interests = raw_msgs
+        .into_iter()
+        .map(|(symbol, market_type, open_interest, price, timestamp, json)| {
+            let pair = crypto_pair::normalize_pair(&symbol, EXCHANGE_NAME)
+                .ok_or_else(|| ParseError::symbol_normalization(&symbol))?;
+            // option prices are quoted in the base currency
+            let price = if market_type == MarketType::EuropeanOption { None } else { price };
+            let (quantity_base, quantity_quote) = calc_open_interest(
+                EXCHANGE_NAME,
+                market_type,
+                &pair,
+                price,
+                open_interest,
+                timestamp,
+            )?;
+            Ok(OpenInterestMsg {
+                exchange: EXCHANGE_NAME.to_string(),
+                market_type,
+                symbol,
+                pair,
+                msg_type: MessageType::OpenInterest,
+                timestamp,
+                quantity_contract: open_interest,
+                quantity_base,
+                quantity_quote,
+                json,
+            })
+        })
+        .collect::<Result<Vec<OpenInterestMsg>, ParseError>>()?;
+
+    if open_interests.len() == 1 {
+        open_interests[0].json = msg.to_string();
+    }
+    Ok(open_interests)
+}
+
+/// All options of one index are in the same message, and their mark prices
+/// are quoted in the base currency.
+pub(crate) fn parse_mark_price(
+    market_type: MarketType,
+    msg: &str,
+) -> Result<Vec<MarkPriceMsg>, ParseError> {
+    let ws_msg = serde_json::from_str::<WebsocketMsg<Vec<RawMarkPriceMsg>>>(msg)
+        .map_err(|_e| ParseError::malformed("expected WebsocketMsg<Vec<RawMarkPriceMsg>>"))?;
+    if !ws_msg.params.channel.starts_with("markprice.options.") {
+        return Err(ParseError::unsupported(format!(
+            "Unsupported channel {}",
+            ws_msg.params.channel
+        )));
+    }
+
+    let mut mark_prices = ws_msg
+        .params
+        .data
+        .into_iter()
+        .map(|raw_msg| -> Result<MarkPriceMsg, ParseError> {
+            let pair = crypto_pair::normalize_pair(&raw_msg.instrument_name, EXCHANGE_NAME)
+                .ok_or_else(|| ParseError::symbol_normalization(&raw_msg.instrument_name))?;
+            Ok(MarkPriceMsg {
+                exchange: EXCHANGE_NAME.to_string(),
+                market_type,
+                symbol: raw_msg.instrument_name.clone(),
+                pair,
+                timestamp: raw_msg.timestamp,
+                price: raw_msg.mark_price,
+                json: serde_json::to_string(&raw_msg).unwrap(),
+            })
+        })
+        .collect::<Result<Vec<MarkPriceMsg>, ParseError>>()?;
+
+    if mark_prices.len() == 1 {
+        mark_prices[0].json = msg.to_string();
+    }
+    Ok(mark_prices)
+}
+
+pub(crate) fn parse_index_price(
+    market_type: MarketType,
+    msg: &str,
+) -> Result<Vec<IndexPriceMsg>, ParseError> {
+    let ws_msg = serde_json::from_str::<WebsocketMsg<RawIndexPriceMsg>>(msg)
+        .map_err(|_e| ParseError::malformed("expected WebsocketMsg<RawIndexPriceMsg>"))?;
+    if !ws_msg.params.channel.starts_with("deribit_price_index.") {
+        return Err(ParseError::unsupported(format!(
+            "Unsupported channel {}",
+            ws_msg.params.channel
+        )));
+    }
+    let raw_msg = ws_msg.params.data;
+    // index names are not tradable symbols, e.g., btc_usd
+    let pair = raw_msg.index_name.to_uppercase().replace('_', "/");
+
+    Ok(vec![IndexPriceMsg {
+        exchange: EXCHANGE_NAME.to_string(),
+        market_type,
+        symbol: raw_msg.index_name,
+        pair,
+        timestamp: raw_msg.timestamp,
+        price: raw_msg.price,
+        json: msg.to_string(),
+    }])
+}
+
+pub(crate) fn parse_candlestick(
+    market_type: MarketType,
+    msg: &str,
+) -> Result<Vec<CandlestickMsg>, ParseError> {
+    let ws_msg =
+        serde_json::from_str::<WebsocketMsg<RawCandlestickMsg>>(msg).map_err(ParseError::from)?;
+    if !ws_msg.params.channel.starts_with("chart.") {
+        return Err(ParseError::unsupported(format!(
+            "Unsupported channel {}",
+            ws_msg.params.channel
+        )));
+    }
+    let resolution = ws_msg.params.channel.split('.').next_back().ok_or_else(|| {
+        ParseError::unsupported(format!("Invalid channel {}", ws_msg.params.channel))
+    })?;
+    let period = parse_candle_period(EXCHANGE_NAME, market_type, resolution)?;
+    // resolutions are 1 minute to 1 day
+    let period_millis = period
+        .as_millis()
+        .ok_or_else(|| ParseError::unsupported(format!("Unsupported resolution {resolution}")))?;
+
     let raw_candlestick_msg = ws_msg.params.data;
-    let symbol = extract_symbol(market_type, msg).unwrap();
-    let pair = crypto_pair::normalize_pair(&symbol, EXCHANGE_NAME).unwrap();
+    let symbol = extract_symbol(market_type, msg)?;
+    let pair = crypto_pair::normalize_pair(&symbol, EXCHANGE_NAME)
+        .ok_or_else(|| ParseError::symbol_normalization(&symbol))?;
     let candlestick_msg = CandlestickMsg {
         exchange: EXCHANGE_NAME.to_string(),
         market_type,
@@ -377,14 +859,16 @@ pub(crate) fn parse_candlestick(
         symbol,
         pair,
         timestamp: raw_candlestick_msg.tick,
-        period: period.to_string(),
-        begin_time: raw_candlestick_msg.tick - period_value * 60000,
+        period,
+        begin_time: raw_candlestick_msg.tick - period_millis,
+        end_time: raw_candlestick_msg.tick,
         open: raw_candlestick_msg.open,
         high: raw_candlestick_msg.high,
         low: raw_candlestick_msg.low,
         close: raw_candlestick_msg.close,
         volume: raw_candlestick_msg.volume,
         quote_volume: Some(raw_candlestick_msg.cost),
+        is_closed: None,
         json: msg.to_string(),
     };
     Ok(vec![candlestick_msg])
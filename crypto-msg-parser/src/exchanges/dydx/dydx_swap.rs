@@ -2,12 +2,17 @@ use chrono::DateTime;
 use crypto_market_type::MarketType;
 use crypto_msg_type::MessageType;
 
-use crypto_message::{Order, OrderBookMsg, TradeMsg, TradeSide};
+use crypto_message::{CandlestickMsg, FundingRateMsg, Order, OrderBookMsg, TradeMsg, TradeSide};
 
+use crate::{
+    ParseError,
+    exchanges::utils::{
+        annualize_funding_rate, next_funding_time, parse_candle_period, parse_exact,
+    },
+};
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
-use simple_error::SimpleError;
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 
 use super::message::{L2SnapshotRawMsg, WebsocketMsg};
 
@@ -60,64 +65,131 @@ struct RawOrderBookUpdateMsg {
     extra: HashMap<String, Value>,
 }
 
-pub(super) fn extract_timestamp(msg: &str) -> Result<Option<i64>, SimpleError> {
+// https://docs.dydx.exchange/#get-markets
+#[derive(Serialize, Deserialize)]
+#[allow(non_snake_case)]
+struct RawMarketMsg {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    nextFundingRate: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    nextFundingAt: Option<String>,
+    #[serde(flatten)]
+    extra: HashMap<String, Value>,
+}
+
+// https://docs.dydx.exchange/#markets
+#[derive(Serialize, Deserialize)]
+struct MarketsWebsocketMsg {
+    #[serde(rename = "type")]
+    type_: String, // subscribed, channel_data
+    channel: String,
+    contents: Value,
+    #[serde(flatten)]
+    extra: HashMap<String, Value>,
+}
+
+// https://docs.dydx.exchange/#get-historical-funding
+#[derive(Serialize, Deserialize)]
+#[allow(non_snake_case)]
+struct RawHistoricalFundingMsg {
+    market: String,
+    rate: String,
+    effectiveAt: String,
+    #[serde(flatten)]
+    extra: HashMap<String, Value>,
+}
+
+#[derive(Serialize, Deserialize)]
+#[allow(non_snake_case)]
+struct HistoricalFundingMsg {
+    historicalFunding: Vec<RawHistoricalFundingMsg>,
+}
+
+// https://docs.dydx.exchange/#get-candles-for-market
+#[derive(Serialize, Deserialize)]
+#[allow(non_snake_case)]
+struct RawCandleMsg {
+    startedAt: String,
+    updatedAt: String,
+    market: String,
+    resolution: String,
+    low: String,
+    high: String,
+    open: String,
+    close: String,
+    baseTokenVolume: String,
+    usdVolume: String,
+    #[serde(flatten)]
+    extra: HashMap<String, Value>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub(super) struct CandlesMsg {
+    candles: Vec<RawCandleMsg>,
+}
+
+impl CandlesMsg {
+    pub(super) fn market(&self) -> Option<&str> {
+        self.candles.first().map(|raw_candle| raw_candle.market.as_str())
+    }
+}
+
+pub(super) fn extract_timestamp(msg: &str) -> Result<Option<i64>, ParseError> {
     if let Ok(ws_msg) = serde_json::from_str::<WebsocketMsg<Value>>(msg) {
         let channel = ws_msg.channel.as_str();
         match channel {
             "v3_trades" => {
-                let ws_msg =
-                    serde_json::from_str::<WebsocketMsg<RawTradesMsg>>(msg).map_err(|_e| {
-                        SimpleError::new(format!(
-                            "Failed to deserialize {msg} to WebsocketMsg<RawTradesMsg>"
-                        ))
-                    })?;
+                let ws_msg = serde_json::from_str::<WebsocketMsg<RawTradesMsg>>(msg)
+                    .map_err(|_e| ParseError::malformed("expected WebsocketMsg<RawTradesMsg>"))?;
                 let timestamp = ws_msg
                     .contents
                     .trades
                     .iter()
-                    .map(|raw_trade| {
-                        DateTime::parse_from_rfc3339(&raw_trade.createdAt)
-                            .unwrap()
-                            .timestamp_millis()
-                    })
+                    .map(|raw_trade| parse_time(&raw_trade.createdAt))
+                    .collect::<Result<Vec<i64>, ParseError>>()?
+                    .into_iter()
                     .max();
                 Ok(timestamp) // contents.trades can be an empty array sometimes
             }
             "v3_orderbook" => Ok(None),
-            _ => Err(SimpleError::new(format!("Failed to extract timestamp from {msg}"))),
+            _ => Err(ParseError::missing_timestamp()),
         }
+    } else if let Ok(rest_msg) = serde_json::from_str::<CandlesMsg>(msg) {
+        let timestamps = rest_msg
+            .candles
+            .iter()
+            .map(|raw_candle| parse_time(&raw_candle.updatedAt))
+            .collect::<Result<Vec<i64>, ParseError>>()?;
+        Ok(timestamps.into_iter().max())
     } else if msg.starts_with(r#"{"markets":"#)
         || serde_json::from_str::<L2SnapshotRawMsg>(msg).is_ok()
     {
         // e.g., https://api.dydx.exchange/v3/markets
         Ok(None)
     } else {
-        Err(SimpleError::new(format!("Unsupported message format {msg}")))
+        Err(ParseError::unsupported("Unsupported message format"))
     }
 }
 
-pub(crate) fn parse_trade(
-    market_type: MarketType,
-    msg: &str,
-) -> Result<Vec<TradeMsg>, SimpleError> {
-    let ws_msg = serde_json::from_str::<WebsocketMsg<RawTradesMsg>>(msg).map_err(|_e| {
-        SimpleError::new(format!("Failed to deserialize {msg} to WebsocketMsg<RawTradesMsg>"))
-    })?;
+pub(crate) fn parse_trade(market_type: MarketType, msg: &str) -> Result<Vec<TradeMsg>, ParseError> {
+    let ws_msg = serde_json::from_str::<WebsocketMsg<RawTradesMsg>>(msg)
+        .map_err(|_e| ParseError::malformed("expected WebsocketMsg<RawTradesMsg>"))?;
     let symbol = ws_msg.id;
     let pair = crypto_pair::normalize_pair(&symbol, EXCHANGE_NAME)
-        .ok_or_else(|| SimpleError::new(format!("Failed to normalize {symbol} from {msg}")))?;
-    debug_assert_eq!("v3_trades", ws_msg.channel);
+        .ok_or_else(|| ParseError::symbol_normalization(&symbol))?;
+    if ws_msg.channel != "v3_trades" {
+        return Err(ParseError::unsupported(format!("Unsupported channel {}", ws_msg.channel)));
+    }
 
     let mut trades: Vec<TradeMsg> = ws_msg
         .contents
         .trades
         .into_iter()
-        .map(|raw_trade| {
-            let timestamp =
-                DateTime::parse_from_rfc3339(&raw_trade.createdAt).unwrap().timestamp_millis();
-            let price = raw_trade.price.parse::<f64>().unwrap();
-            let size = raw_trade.size.parse::<f64>().unwrap();
-            TradeMsg {
+        .map(|raw_trade| -> Result<TradeMsg, ParseError> {
+            let timestamp = parse_time(&raw_trade.createdAt)?;
+            let price = raw_trade.price.parse::<f64>()?;
+            let size = raw_trade.size.parse::<f64>()?;
+            Ok(TradeMsg {
                 exchange: EXCHANGE_NAME.to_string(),
                 market_type,
                 symbol: symbol.to_string(),
@@ -128,12 +200,14 @@ pub(crate) fn parse_trade(
                 quantity_base: size,
                 quantity_quote: price * size,
                 quantity_contract: Some(size),
+                price_exact: parse_exact(&raw_trade.price),
+                quantity_exact: parse_exact(&raw_trade.size),
                 side: if raw_trade.side == "SELL" { TradeSide::Sell } else { TradeSide::Buy },
                 trade_id: timestamp.to_string(),
                 json: serde_json::to_string(&raw_trade).unwrap(),
-            }
+            })
         })
-        .collect();
+        .collect::<Result<Vec<TradeMsg>, ParseError>>()?;
 
     if trades.len() == 1 {
         trades[0].json = msg.to_string();
@@ -141,64 +215,84 @@ pub(crate) fn parse_trade(
     Ok(trades)
 }
 
-fn parse_order_update(raw_order: &[String; 2]) -> Order {
-    let price = raw_order[0].parse::<f64>().unwrap();
-    let size = raw_order[1].parse::<f64>().unwrap();
+fn parse_order_update(raw_order: &[String; 2]) -> Result<Order, ParseError> {
+    let price = raw_order[0].parse::<f64>()?;
+    let size = raw_order[1].parse::<f64>()?;
 
-    Order {
+    Ok(Order {
         price,
         quantity_base: size,
         quantity_quote: price * size,
         quantity_contract: Some(size),
-    }
+        price_exact: parse_exact(&raw_order[0]),
+        quantity_exact: parse_exact(&raw_order[1]),
+    })
 }
 
-fn parse_order_snapshot(raw_order: &RawOrder) -> Order {
-    let price = raw_order.price.parse::<f64>().unwrap();
-    let size = raw_order.size.parse::<f64>().unwrap();
+fn parse_order_snapshot(raw_order: &RawOrder) -> Result<Order, ParseError> {
+    let price = raw_order.price.parse::<f64>()?;
+    let size = raw_order.size.parse::<f64>()?;
 
-    Order {
+    Ok(Order {
         price,
         quantity_base: size,
         quantity_quote: price * size,
         quantity_contract: Some(size),
-    }
+        price_exact: parse_exact(&raw_order.price),
+        quantity_exact: parse_exact(&raw_order.size),
+    })
 }
 
 pub(crate) fn parse_l2(
     market_type: MarketType,
     msg: &str,
     timestamp: i64,
-) -> Result<Vec<OrderBookMsg>, SimpleError> {
+) -> Result<Vec<OrderBookMsg>, ParseError> {
     let ws_msg = serde_json::from_str::<WebsocketMsg<Value>>(msg)
-        .map_err(|_e| SimpleError::new(format!("Failed to deserialize {msg} to WebsocketMsg")))?;
+        .map_err(|_e| ParseError::malformed("expected WebsocketMsg"))?;
     let symbol = ws_msg.id;
     let pair = crypto_pair::normalize_pair(&symbol, EXCHANGE_NAME)
-        .ok_or_else(|| SimpleError::new(format!("Failed to normalize {symbol} from {msg}")))?;
+        .ok_or_else(|| ParseError::symbol_normalization(&symbol))?;
     let snapshot = ws_msg.type_ == "subscribed";
-    debug_assert_eq!("v3_orderbook", ws_msg.channel);
+    if ws_msg.channel != "v3_orderbook" {
+        return Err(ParseError::unsupported(format!("Unsupported channel {}", ws_msg.channel)));
+    }
 
     let (asks, bids) = if snapshot {
         let ws_msg =
             serde_json::from_str::<WebsocketMsg<RawOrderBookSnapshotMsg>>(msg).map_err(|_e| {
-                SimpleError::new(format!(
-                    "Failed to deserialize {msg} to WebsocketMsg<RawOrderBookSnapshotMsg>"
-                ))
+                ParseError::malformed("expected WebsocketMsg<RawOrderBookSnapshotMsg>")
             })?;
         (
-            ws_msg.contents.asks.into_iter().map(|x| parse_order_snapshot(&x)).collect(),
-            ws_msg.contents.bids.into_iter().map(|x| parse_order_snapshot(&x)).collect(),
+            ws_msg
+                .contents
+                .asks
+                .iter()
+                .map(parse_order_snapshot)
+                .collect::<Result<Vec<Order>, ParseError>>()?,
+            ws_msg
+                .contents
+                .bids
+                .iter()
+                .map(parse_order_snapshot)
+                .collect::<Result<Vec<Order>, ParseError>>()?,
         )
     } else {
-        let ws_msg =
-            serde_json::from_str::<WebsocketMsg<RawOrderBookUpdateMsg>>(msg).map_err(|_e| {
-                SimpleError::new(format!(
-                    "Failed to deserialize {msg} to WebsocketMsg<RawOrderBookUpdateMsg>"
-                ))
-            })?;
+        let ws_msg = serde_json::from_str::<WebsocketMsg<RawOrderBookUpdateMsg>>(msg)
+            .map_err(|_e| ParseError::malformed("expected WebsocketMsg<RawOrderBookUpdateMsg>"))?;
         (
-            ws_msg.contents.asks.into_iter().map(|x| parse_order_update(&x)).collect(),
-            ws_msg.contents.bids.into_iter().map(|x| parse_order_update(&x)).collect(),
+            ws_msg
+                .contents
+                .asks
+                .iter()
+                .map(parse_order_update)
+                .collect::<Result<Vec<Order>, ParseError>>()?,
+            ws_msg
+                .contents
+                .bids
+                .iter()
+                .map(parse_order_update)
+                .collect::<Result<Vec<Order>, ParseError>>()?,
         )
     };
 
@@ -219,3 +313,154 @@ pub(crate) fn parse_l2(
 
     Ok(vec![orderbook])
 }
+
+// Perpetual swaps settle funding every hour
+fn parse_time(s: &str) -> Result<i64, ParseError> {
+    Ok(DateTime::parse_from_rfc3339(s)
+        .map_err(|_e| ParseError::malformed(format!("invalid time {s}")))?
+        .timestamp_millis())
+}
+
+const FUNDING_INTERVAL: i64 = 3600 * 1000;
+
+/// Parses the `v3_markets` channel and the `markets` and `historical-funding`
+/// RESTful APIs.
+///
+/// Markets have no timestamp, so `received_at` is required for them. Updates
+/// of `v3_markets` contain changed fields only, markets without
+/// `nextFundingRate` are skipped.
+pub(super) fn parse_funding_rate(
+    market_type: MarketType,
+    msg: &str,
+    received_at: Option<i64>,
+) -> Result<Vec<FundingRateMsg>, ParseError> {
+    let mut rates = if let Ok(history) = serde_json::from_str::<HistoricalFundingMsg>(msg) {
+        // Each historical rate took effect at effectiveAt
+        history
+            .historicalFunding
+            .into_iter()
+            .map(|raw_msg| -> Result<FundingRateMsg, ParseError> {
+                let timestamp = parse_time(&raw_msg.effectiveAt)?;
+                let funding_rate = raw_msg.rate.parse::<f64>()?;
+                Ok(FundingRateMsg {
+                    exchange: EXCHANGE_NAME.to_string(),
+                    market_type,
+                    symbol: raw_msg.market.clone(),
+                    pair: crypto_pair::normalize_pair(&raw_msg.market, EXCHANGE_NAME)
+                        .ok_or_else(|| ParseError::symbol_normalization(&raw_msg.market))?,
+                    msg_type: MessageType::FundingRate,
+                    timestamp,
+                    funding_rate,
+                    funding_time: timestamp,
+                    estimated_rate: None,
+                    funding_interval: FUNDING_INTERVAL,
+                    funding_rate_cap: None,
+                    funding_rate_floor: None,
+                    annualized_rate: annualize_funding_rate(funding_rate, FUNDING_INTERVAL),
+                    json: serde_json::to_string(&raw_msg).unwrap(),
+                })
+            })
+            .collect::<Result<Vec<FundingRateMsg>, ParseError>>()?
+    } else {
+        let json_obj = serde_json::from_str::<HashMap<String, Value>>(msg)
+            .map_err(|_e| ParseError::malformed("expected HashMap<String, Value>"))?;
+        let markets = if let Some(markets) = json_obj.get("markets") {
+            // RESTful markets
+            markets.clone()
+        } else {
+            let ws_msg = serde_json::from_str::<MarketsWebsocketMsg>(msg)
+                .map_err(|_e| ParseError::malformed("expected MarketsWebsocketMsg"))?;
+            if ws_msg.channel != "v3_markets" {
+                return Err(ParseError::unsupported(format!(
+                    "Unsupported channel {}",
+                    ws_msg.channel
+                )));
+            }
+            // a snapshot wraps markets in contents.markets, updates don't
+            match ws_msg.contents.get("markets") {
+                Some(markets) => markets.clone(),
+                None => ws_msg.contents,
+            }
+        };
+        let markets = serde_json::from_value::<BTreeMap<String, RawMarketMsg>>(markets)
+            .map_err(|_e| ParseError::malformed("expected BTreeMap<String, RawMarketMsg>"))?;
+        let timestamp = received_at.ok_or_else(ParseError::missing_timestamp)?;
+        markets
+            .into_iter()
+            .filter(|(_, raw_msg)| raw_msg.nextFundingRate.is_some())
+            .map(|(symbol, raw_msg)| -> Result<FundingRateMsg, ParseError> {
+                let funding_time = match raw_msg.nextFundingAt.as_deref() {
+                    Some(s) => parse_time(s)?,
+                    None => next_funding_time(timestamp, FUNDING_INTERVAL),
+                };
+                let funding_rate = raw_msg.nextFundingRate.as_deref().unwrap().parse::<f64>()?;
+                Ok(FundingRateMsg {
+                    exchange: EXCHANGE_NAME.to_string(),
+                    market_type,
+                    symbol: symbol.clone(),
+                    pair: crypto_pair::normalize_pair(&symbol, EXCHANGE_NAME)
+                        .ok_or_else(|| ParseError::symbol_normalization(&symbol))?,
+                    msg_type: MessageType::FundingRate,
+                    timestamp,
+                    funding_rate,
+                    funding_time,
+                    estimated_rate: None,
+                    funding_interval: FUNDING_INTERVAL,
+                    funding_rate_cap: None,
+                    funding_rate_floor: None,
+                    annualized_rate: annualize_funding_rate(funding_rate, FUNDING_INTERVAL),
+                    json: serde_json::to_string(&raw_msg).unwrap(),
+                })
+            })
+            .collect::<Result<Vec<FundingRateMsg>, ParseError>>()?
+    };
+
+    if rates.len() == 1 {
+        rates[0].json = msg.to_string();
+    }
+    Ok(rates)
+}
+
+pub(super) fn parse_candlestick(
+    market_type: MarketType,
+    msg: &str,
+) -> Result<Vec<CandlestickMsg>, ParseError> {
+    let rest_msg = serde_json::from_str::<CandlesMsg>(msg)
+        .map_err(|_e| ParseError::malformed("expected CandlesMsg"))?;
+
+    let mut candlesticks = rest_msg
+        .candles
+        .into_iter()
+        .map(|raw_candle| -> Result<CandlestickMsg, ParseError> {
+            let symbol = raw_candle.market.as_str();
+            let pair = crypto_pair::normalize_pair(symbol, EXCHANGE_NAME)
+                .ok_or_else(|| ParseError::symbol_normalization(symbol))?;
+            let period = parse_candle_period(EXCHANGE_NAME, market_type, &raw_candle.resolution)?;
+            let begin_time = parse_time(&raw_candle.startedAt)?;
+            Ok(CandlestickMsg {
+                exchange: EXCHANGE_NAME.to_string(),
+                market_type,
+                msg_type: MessageType::Candlestick,
+                symbol: symbol.to_string(),
+                pair,
+                timestamp: parse_time(&raw_candle.updatedAt)?,
+                period,
+                begin_time,
+                end_time: period.end_time(begin_time),
+                open: raw_candle.open.parse::<f64>()?,
+                high: raw_candle.high.parse::<f64>()?,
+                low: raw_candle.low.parse::<f64>()?,
+                close: raw_candle.close.parse::<f64>()?,
+                volume: raw_candle.baseTokenVolume.parse::<f64>()?,
+                quote_volume: Some(raw_candle.usdVolume.parse::<f64>()?),
+                is_closed: None,
+                json: serde_json::to_string(&raw_candle).unwrap(),
+            })
+        })
+        .collect::<Result<Vec<CandlestickMsg>, ParseError>>()?;
+    if candlesticks.len() == 1 {
+        candlesticks[0].json = msg.to_string();
+    }
+
+    Ok(candlesticks)
+}
@@ -1,15 +1,18 @@
 mod dydx_swap;
 mod message;
 
-use crate::{OrderBookMsg, TradeMsg};
+use crate::{CandlestickMsg, FundingRateMsg, OrderBookMsg, ParseError, TradeMsg};
 
 use crypto_market_type::MarketType;
+use crypto_msg_type::MessageType;
 use serde_json::Value;
-use simple_error::SimpleError;
 
-use self::message::{L2SnapshotRawMsg, WebsocketMsg};
+use self::{
+    dydx_swap::CandlesMsg,
+    message::{L2SnapshotRawMsg, WebsocketMsg},
+};
 
-pub(crate) fn extract_symbol(msg: &str) -> Result<String, SimpleError> {
+pub(crate) fn extract_symbol(msg: &str) -> Result<String, ParseError> {
     if let Ok(ws_msg) = serde_json::from_str::<WebsocketMsg<Value>>(msg) {
         Ok(ws_msg.id)
     } else if serde_json::from_str::<L2SnapshotRawMsg>(msg).is_ok() {
@@ -17,28 +20,46 @@ pub(crate) fn extract_symbol(msg: &str) -> Result<String, SimpleError> {
     } else if msg.starts_with(r#"{"markets":"#) {
         // https://api.dydx.exchange/v3/markets
         Ok("ALL".to_string())
+    } else if let Ok(rest_msg) = serde_json::from_str::<CandlesMsg>(msg) {
+        // https://api.dydx.exchange/v3/candles/BTC-USD
+        Ok(rest_msg.market().unwrap_or("NONE").to_string())
     } else {
-        Err(SimpleError::new(format!("Unsupported message format {msg}")))
+        Err(ParseError::unsupported("Unsupported message format"))
     }
 }
 
 pub(crate) fn extract_timestamp(
     market_type: MarketType,
     msg: &str,
-) -> Result<Option<i64>, SimpleError> {
+) -> Result<Option<i64>, ParseError> {
     match market_type {
         MarketType::LinearSwap => dydx_swap::extract_timestamp(msg),
-        _ => Err(SimpleError::new(format!("Unknown dYdX market type {market_type}"))),
+        _ => Err(ParseError::unsupported(format!("Unknown dYdX market type {market_type}"))),
     }
 }
 
-pub(crate) fn parse_trade(
-    market_type: MarketType,
-    msg: &str,
-) -> Result<Vec<TradeMsg>, SimpleError> {
+pub(crate) fn get_msg_type(msg: &str) -> MessageType {
+    if let Ok(ws_msg) = serde_json::from_str::<WebsocketMsg<Value>>(msg) {
+        match ws_msg.channel.as_str() {
+            "v3_trades" => MessageType::Trade,
+            "v3_orderbook" => MessageType::L2Event,
+            _ => MessageType::Other,
+        }
+    } else if serde_json::from_str::<L2SnapshotRawMsg>(msg).is_ok() {
+        MessageType::L2Snapshot
+    } else if msg.starts_with(r#"{"historicalFunding":"#) {
+        MessageType::FundingRate
+    } else if msg.starts_with(r#"{"candles":"#) {
+        MessageType::Candlestick
+    } else {
+        MessageType::Other
+    }
+}
+
+pub(crate) fn parse_trade(market_type: MarketType, msg: &str) -> Result<Vec<TradeMsg>, ParseError> {
     match market_type {
         MarketType::LinearSwap => dydx_swap::parse_trade(market_type, msg),
-        _ => Err(SimpleError::new(format!("Unknown dYdX market type {market_type}"))),
+        _ => Err(ParseError::unsupported(format!("Unknown dYdX market type {market_type}"))),
     }
 }
 
@@ -46,9 +67,30 @@ pub(crate) fn parse_l2(
     market_type: MarketType,
     msg: &str,
     timestamp: i64,
-) -> Result<Vec<OrderBookMsg>, SimpleError> {
+) -> Result<Vec<OrderBookMsg>, ParseError> {
     match market_type {
         MarketType::LinearSwap => dydx_swap::parse_l2(market_type, msg, timestamp),
-        _ => Err(SimpleError::new(format!("Unknown dYdX market type {market_type}"))),
+        _ => Err(ParseError::unsupported(format!("Unknown dYdX market type {market_type}"))),
+    }
+}
+
+pub(crate) fn parse_funding_rate(
+    market_type: MarketType,
+    msg: &str,
+    received_at: Option<i64>,
+) -> Result<Vec<FundingRateMsg>, ParseError> {
+    match market_type {
+        MarketType::LinearSwap => dydx_swap::parse_funding_rate(market_type, msg, received_at),
+        _ => Err(ParseError::unsupported(format!("Unknown dYdX market type {market_type}"))),
+    }
+}
+
+pub(crate) fn parse_candlestick(
+    market_type: MarketType,
+    msg: &str,
+) -> Result<Vec<CandlestickMsg>, ParseError> {
+    match market_type {
+        MarketType::LinearSwap => dydx_swap::parse_candlestick(market_type, msg),
+        _ => Err(ParseError::unsupported(format!("Unknown dYdX market type {market_type}"))),
     }
 }
@@ -1,13 +1,19 @@
 use crypto_market_type::MarketType;
 use crypto_msg_type::MessageType;
 
-use crate::exchanges::utils::calc_quantity_and_volume;
-use crypto_message::{BboMsg, Order, OrderBookMsg, TradeMsg, TradeSide};
+use crate::{
+    ParseError,
+    exchanges::utils::{
+        calc_quantity_and_volume, infer_candle_period, json_array, json_f64, json_str, round,
+    },
+};
+use crypto_message::{
+    BboMsg, CandlestickMsg, LiquidationMsg, Order, OrderBookMsg, TickerMsg, TradeMsg, TradeSide,
+};
 
 use chrono::DateTime;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
-use simple_error::SimpleError;
 use std::collections::HashMap;
 
 const EXCHANGE_NAME: &str = "ftx";
@@ -50,6 +56,21 @@ struct RawBboMsg {
     extra: HashMap<String, Value>,
 }
 
+// https://docs.ftx.com/#get-historical-prices
+#[derive(Serialize, Deserialize)]
+#[allow(non_snake_case)]
+struct RawCandlestickMsg {
+    startTime: String,
+    time: f64, // Unix timestamp in milliseconds
+    open: f64,
+    high: f64,
+    low: f64,
+    close: f64,
+    volume: f64, // in quote currency
+    #[serde(flatten)]
+    extra: HashMap<String, Value>,
+}
+
 #[derive(Serialize, Deserialize)]
 struct WebsocketMsg<T: Sized> {
     channel: String,
@@ -65,75 +86,76 @@ struct RestMsg<T: Sized> {
     result: T,
 }
 
-pub(crate) fn extract_symbol(_market_type: MarketType, msg: &str) -> Result<String, SimpleError> {
+pub(crate) fn extract_symbol(_market_type: MarketType, msg: &str) -> Result<String, ParseError> {
     if let Ok(ws_msg) = serde_json::from_str::<WebsocketMsg<Value>>(msg) {
         Ok(ws_msg.market)
     } else if let Ok(rest_msg) = serde_json::from_str::<RestMsg<Value>>(msg) {
         if !rest_msg.success {
-            return Err(SimpleError::new(format!("Error http response {msg}")));
+            return Err(ParseError::unsupported("Error http response"));
         }
         if let Some(result) = rest_msg.result.as_object() {
             if result.contains_key("asks") && result.contains_key("bids") {
                 Ok("NONE".to_string())
             } else {
-                Err(SimpleError::new(format!("Unsupported message format {msg}")))
+                Err(ParseError::unsupported("Unsupported message format"))
             }
         } else if let Some(result) = rest_msg.result.as_array() {
             #[allow(clippy::comparison_chain)]
-            if result.len() > 1 {
+            if result.iter().any(|x| x.get("startTime").is_some()) {
+                // candles have no market name
+                Ok("NONE".to_string())
+            } else if result.len() > 1 {
                 Ok("ALL".to_string())
             } else if result.len() == 1 {
-                Ok(result[0]["name"].as_str().unwrap().to_string())
+                Ok(json_str(&result[0]["name"], "name")?.to_string())
             } else {
                 Ok("NONE".to_string())
             }
         } else {
-            Err(SimpleError::new(format!("Unsupported message format {msg}")))
+            Err(ParseError::unsupported("Unsupported message format"))
         }
     } else {
-        Err(SimpleError::new(format!("Unsupported message format {msg}")))
+        Err(ParseError::unsupported("Unsupported message format"))
     }
 }
 
 pub(crate) fn extract_timestamp(
     _market_type: MarketType,
     msg: &str,
-) -> Result<Option<i64>, SimpleError> {
+) -> Result<Option<i64>, ParseError> {
     if let Ok(ws_msg) = serde_json::from_str::<WebsocketMsg<Value>>(msg) {
         let channel = ws_msg.channel.as_str();
         match channel {
             "trades" => {
-                let timestamp = ws_msg
-                    .data
-                    .as_array()
-                    .unwrap()
+                let timestamp = json_array(&ws_msg.data, "data")?
                     .iter()
-                    .map(|x| {
-                        DateTime::parse_from_rfc3339(x["time"].as_str().unwrap())
-                            .unwrap()
-                            .timestamp_millis()
+                    .map(|x| -> Result<i64, ParseError> {
+                        Ok(DateTime::parse_from_rfc3339(json_str(&x["time"], "time")?)?
+                            .timestamp_millis())
                     })
+                    .collect::<Result<Vec<i64>, ParseError>>()?
+                    .into_iter()
                     .max();
 
                 if timestamp.is_none() {
-                    Err(SimpleError::new(format!("data is empty in {msg}")))
+                    Err(ParseError::unsupported("data is empty"))
                 } else {
                     Ok(timestamp)
                 }
             }
             "orderbook" | "ticker" => {
-                Ok(Some((ws_msg.data["time"].as_f64().unwrap() * 1000.0) as i64))
+                Ok(Some((json_f64(&ws_msg.data["time"], "time")? * 1000.0) as i64))
             }
-            _ => Err(SimpleError::new(format!("unknown channel {channel} in {msg}"))),
+            _ => Err(ParseError::unsupported(format!("unknown channel {channel}"))),
         }
     } else if let Ok(rest_msg) = serde_json::from_str::<RestMsg<Value>>(msg) {
         if !rest_msg.success {
-            Err(SimpleError::new(format!("Error http response {msg}")))
+            Err(ParseError::unsupported("Error http response"))
         } else {
             Ok(None)
         }
     } else {
-        Err(SimpleError::new(format!("Unsupported message format {msg}")))
+        Err(ParseError::unsupported("Unsupported message format"))
     }
 }
 
@@ -149,51 +171,56 @@ pub(crate) fn get_msg_type(msg: &str) -> MessageType {
         } else {
             MessageType::Other
         }
+    } else if let Ok(rest_msg) = serde_json::from_str::<RestMsg<Vec<RawCandlestickMsg>>>(msg) {
+        if rest_msg.success && !rest_msg.result.is_empty() {
+            MessageType::Candlestick
+        } else {
+            MessageType::Other
+        }
     } else {
         MessageType::Other
     }
 }
 
-pub(crate) fn parse_trade(
-    market_type: MarketType,
-    msg: &str,
-) -> Result<Vec<TradeMsg>, SimpleError> {
-    let ws_msg = serde_json::from_str::<WebsocketMsg<Vec<RawTradeMsg>>>(msg).map_err(|_e| {
-        SimpleError::new(format!("Failed to deserialize {msg} to WebsocketMsg<Vec<RawTradeMsg>>"))
-    })?;
+pub(crate) fn parse_trade(market_type: MarketType, msg: &str) -> Result<Vec<TradeMsg>, ParseError> {
+    let ws_msg = serde_json::from_str::<WebsocketMsg<Vec<RawTradeMsg>>>(msg)
+        .map_err(|_e| ParseError::malformed("expected WebsocketMsg<Vec<RawTradeMsg>>"))?;
     let symbol = ws_msg.market.as_str();
     let pair = crypto_pair::normalize_pair(symbol, EXCHANGE_NAME)
-        .ok_or_else(|| SimpleError::new(format!("Failed to normalize {symbol} from {msg}")))?;
+        .ok_or_else(|| ParseError::symbol_normalization(symbol))?;
 
     let mut trades: Vec<TradeMsg> = ws_msg
         .data
         .into_iter()
-        .map(|raw_trade| {
-            let timestamp = DateTime::parse_from_rfc3339(&raw_trade.time).unwrap();
+        .map(|raw_trade| -> Result<TradeMsg, ParseError> {
+            let timestamp = DateTime::parse_from_rfc3339(&raw_trade.time)?.timestamp_millis();
             let (quantity_base, quantity_quote, quantity_contract) = calc_quantity_and_volume(
                 EXCHANGE_NAME,
                 market_type,
                 &pair,
                 raw_trade.price,
                 raw_trade.size,
-            );
-            TradeMsg {
+                timestamp,
+            )?;
+            Ok(TradeMsg {
                 exchange: EXCHANGE_NAME.to_string(),
                 market_type,
                 symbol: symbol.to_string(),
                 pair: pair.clone(),
                 msg_type: MessageType::Trade,
-                timestamp: timestamp.timestamp_millis(),
+                timestamp,
                 price: raw_trade.price,
                 quantity_base,
                 quantity_quote,
                 quantity_contract,
+                price_exact: None,
+                quantity_exact: None,
                 side: if raw_trade.side == "sell" { TradeSide::Sell } else { TradeSide::Buy },
                 trade_id: raw_trade.id.to_string(),
                 json: serde_json::to_string(&raw_trade).unwrap(),
-            }
+            })
         })
-        .collect();
+        .collect::<Result<Vec<TradeMsg>, ParseError>>()?;
 
     if trades.len() == 1 {
         trades[0].json = msg.to_string();
@@ -201,27 +228,89 @@ pub(crate) fn parse_trade(
     Ok(trades)
 }
 
+/// Liquidations are trades with `liquidation: true`, the side is the taker
+/// side, i.e., the side of the liquidation order.
+pub(crate) fn parse_liquidation(
+    market_type: MarketType,
+    msg: &str,
+) -> Result<Vec<LiquidationMsg>, ParseError> {
+    let ws_msg = serde_json::from_str::<WebsocketMsg<Vec<RawTradeMsg>>>(msg)
+        .map_err(|_e| ParseError::malformed("expected WebsocketMsg<Vec<RawTradeMsg>>"))?;
+    let symbol = ws_msg.market.as_str();
+    let pair = crypto_pair::normalize_pair(symbol, EXCHANGE_NAME)
+        .ok_or_else(|| ParseError::symbol_normalization(symbol))?;
+
+    let mut liquidations: Vec<LiquidationMsg> = ws_msg
+        .data
+        .into_iter()
+        .filter(|raw_trade| raw_trade.liquidation)
+        .map(|raw_trade| -> Result<LiquidationMsg, ParseError> {
+            let timestamp = DateTime::parse_from_rfc3339(&raw_trade.time)?.timestamp_millis();
+            let (quantity_base, quantity_quote, quantity_contract) = calc_quantity_and_volume(
+                EXCHANGE_NAME,
+                market_type,
+                &pair,
+                raw_trade.price,
+                raw_trade.size,
+                timestamp,
+            )?;
+            Ok(LiquidationMsg {
+                exchange: EXCHANGE_NAME.to_string(),
+                market_type,
+                symbol: symbol.to_string(),
+                pair: pair.clone(),
+                timestamp,
+                side: if raw_trade.side == "sell" { TradeSide::Sell } else { TradeSide::Buy },
+                price: raw_trade.price,
+                quantity_base,
+                quantity_quote,
+                quantity_contract,
+                json: serde_json::to_string(&raw_trade).unwrap(),
+            })
+        })
+        .collect::<Result<Vec<LiquidationMsg>, ParseError>>()?;
+
+    if liquidations.len() == 1 {
+        liquidations[0].json = msg.to_string();
+    }
+    Ok(liquidations)
+}
+
 pub(crate) fn parse_l2(
     market_type: MarketType,
     msg: &str,
-) -> Result<Vec<OrderBookMsg>, SimpleError> {
-    let ws_msg = serde_json::from_str::<WebsocketMsg<RawOrderbookMsg>>(msg).map_err(|_e| {
-        SimpleError::new(format!("Failed to deserialize {msg} to WebsocketMsg<RawOrderbookMsg>"))
-    })?;
-    debug_assert_eq!(ws_msg.channel, "orderbook");
+) -> Result<Vec<OrderBookMsg>, ParseError> {
+    let ws_msg = serde_json::from_str::<WebsocketMsg<RawOrderbookMsg>>(msg)
+        .map_err(|_e| ParseError::malformed("expected WebsocketMsg<RawOrderbookMsg>"))?;
+    if ws_msg.channel != "orderbook" {
+        return Err(ParseError::unsupported(format!("Unsupported channel {}", ws_msg.channel)));
+    }
     let symbol = ws_msg.market.as_str();
     let pair = crypto_pair::normalize_pair(symbol, EXCHANGE_NAME)
-        .ok_or_else(|| SimpleError::new(format!("Failed to normalize {symbol} from {msg}")))?;
+        .ok_or_else(|| ParseError::symbol_normalization(symbol))?;
     let snapshot = ws_msg.data.action == "partial";
     let timestamp = (ws_msg.data.time * 1000.0) as i64;
 
-    let parse_order = |raw_order: &[f64; 2]| -> Order {
+    let parse_order = |raw_order: &[f64; 2]| -> Result<Order, ParseError> {
         let price = raw_order[0];
         let quantity = raw_order[1];
-        let (quantity_base, quantity_quote, quantity_contract) =
-            calc_quantity_and_volume(EXCHANGE_NAME, market_type, &pair, price, quantity);
+        let (quantity_base, quantity_quote, quantity_contract) = calc_quantity_and_volume(
+            EXCHANGE_NAME,
+            market_type,
+            &pair,
+            price,
+            quantity,
+            timestamp,
+        )?;
 
-        Order { price, quantity_base, quantity_quote, quantity_contract }
+        Ok(Order {
+            price,
+            quantity_base,
+            quantity_quote,
+            quantity_contract,
+            price_exact: None,
+            quantity_exact: None,
+        })
     };
 
     let orderbook = OrderBookMsg {
@@ -233,8 +322,18 @@ pub(crate) fn parse_l2(
         timestamp,
         seq_id: None,
         prev_seq_id: None,
-        asks: ws_msg.data.asks.iter().map(parse_order).collect(),
-        bids: ws_msg.data.bids.iter().map(parse_order).collect(),
+        asks: ws_msg
+            .data
+            .asks
+            .iter()
+            .map(parse_order)
+            .collect::<Result<Vec<Order>, ParseError>>()?,
+        bids: ws_msg
+            .data
+            .bids
+            .iter()
+            .map(parse_order)
+            .collect::<Result<Vec<Order>, ParseError>>()?,
         snapshot,
         json: msg.to_string(),
     };
@@ -242,13 +341,18 @@ pub(crate) fn parse_l2(
     Ok(vec![orderbook])
 }
 
-pub(crate) fn parse_bbo(market_type: MarketType, msg: &str) -> Result<Vec<BboMsg>, SimpleError> {
-    let ws_msg = serde_json::from_str::<WebsocketMsg<RawBboMsg>>(msg).map_err(SimpleError::from)?;
-    debug_assert_eq!("ticker", ws_msg.channel);
-    debug_assert_eq!("update", ws_msg.type_);
+pub(crate) fn parse_bbo(market_type: MarketType, msg: &str) -> Result<Vec<BboMsg>, ParseError> {
+    let ws_msg = serde_json::from_str::<WebsocketMsg<RawBboMsg>>(msg).map_err(ParseError::from)?;
+    if ws_msg.channel != "ticker" {
+        return Err(ParseError::unsupported(format!("Unsupported channel {}", ws_msg.channel)));
+    }
+    if ws_msg.type_ != "update" {
+        return Err(ParseError::unsupported(format!("Unsupported type {}", ws_msg.type_)));
+    }
 
     let symbol = ws_msg.market.as_str();
-    let pair = crypto_pair::normalize_pair(symbol, EXCHANGE_NAME).unwrap();
+    let pair = crypto_pair::normalize_pair(symbol, EXCHANGE_NAME)
+        .ok_or_else(|| ParseError::symbol_normalization(symbol))?;
     let timestamp = (ws_msg.data.time * 1000.0) as i64;
 
     let (ask_quantity_base, ask_quantity_quote, ask_quantity_contract) = calc_quantity_and_volume(
@@ -257,7 +361,8 @@ pub(crate) fn parse_bbo(market_type: MarketType, msg: &str) -> Result<Vec<BboMsg
         &pair,
         ws_msg.data.ask,
         ws_msg.data.askSize,
-    );
+        timestamp,
+    )?;
 
     let (bid_quantity_base, bid_quantity_quote, bid_quantity_contract) = calc_quantity_and_volume(
         EXCHANGE_NAME,
@@ -265,7 +370,8 @@ pub(crate) fn parse_bbo(market_type: MarketType, msg: &str) -> Result<Vec<BboMsg
         &pair,
         ws_msg.data.bid,
         ws_msg.data.bidSize,
-    );
+        timestamp,
+    )?;
 
     let bbo_msg = BboMsg {
         exchange: EXCHANGE_NAME.to_string(),
@@ -282,9 +388,125 @@ pub(crate) fn parse_bbo(market_type: MarketType, msg: &str) -> Result<Vec<BboMsg
         bid_quantity_base,
         bid_quantity_quote,
         bid_quantity_contract,
+        bid_price_exact: None,
+        bid_quantity_exact: None,
+        ask_price_exact: None,
+        ask_quantity_exact: None,
         id: None,
         json: msg.to_string(),
     };
 
     Ok(vec![bbo_msg])
 }
+
+pub(crate) fn parse_ticker(
+    market_type: MarketType,
+    msg: &str,
+) -> Result<Vec<TickerMsg>, ParseError> {
+    let ws_msg = serde_json::from_str::<WebsocketMsg<RawBboMsg>>(msg).map_err(ParseError::from)?;
+    if ws_msg.channel != "ticker" || ws_msg.type_ != "update" {
+        return Err(ParseError::unsupported(format!("Unsupported channel {}", ws_msg.channel)));
+    }
+
+    let symbol = ws_msg.market.as_str();
+    let pair = crypto_pair::normalize_pair(symbol, EXCHANGE_NAME)
+        .ok_or_else(|| ParseError::symbol_normalization(symbol))?;
+    let timestamp = (ws_msg.data.time * 1000.0) as i64;
+
+    let (best_bid_quantity, _, _) = calc_quantity_and_volume(
+        EXCHANGE_NAME,
+        market_type,
+        &pair,
+        ws_msg.data.bid,
+        ws_msg.data.bidSize,
+        timestamp,
+    )?;
+    let (best_ask_quantity, _, _) = calc_quantity_and_volume(
+        EXCHANGE_NAME,
+        market_type,
+        &pair,
+        ws_msg.data.ask,
+        ws_msg.data.askSize,
+        timestamp,
+    )?;
+
+    // The ticker channel of FTX carries only the last price and the best bid
+    // and ask, so open, high and low are the last price and volumes are zero
+    let last = ws_msg.data.last;
+    let ticker_msg = TickerMsg {
+        exchange: EXCHANGE_NAME.to_string(),
+        market_type,
+        symbol: symbol.to_string(),
+        pair,
+        msg_type: MessageType::Ticker,
+        timestamp,
+        open: last,
+        high: last,
+        low: last,
+        close: last,
+        volume: 0.0,
+        quote_volume: 0.0,
+        last_quantity: None,
+        best_bid_price: Some(ws_msg.data.bid),
+        best_bid_quantity: Some(best_bid_quantity),
+        best_ask_price: Some(ws_msg.data.ask),
+        best_ask_quantity: Some(best_ask_quantity),
+        open_interest: None,
+        open_interest_quote: None,
+        json: msg.to_string(),
+    };
+
+    Ok(vec![ticker_msg])
+}
+
+pub(crate) fn parse_candlestick(
+    market_type: MarketType,
+    msg: &str,
+    symbol: Option<&str>,
+) -> Result<Vec<CandlestickMsg>, ParseError> {
+    let symbol = symbol.ok_or_else(|| ParseError::unsupported("symbol is required"))?;
+    let pair = crypto_pair::normalize_pair(symbol, EXCHANGE_NAME)
+        .ok_or_else(|| ParseError::symbol_normalization(symbol))?;
+    let rest_msg = serde_json::from_str::<RestMsg<Vec<RawCandlestickMsg>>>(msg)
+        .map_err(|_e| ParseError::malformed("expected RestMsg<Vec<RawCandlestickMsg>>"))?;
+    if !rest_msg.success {
+        return Err(ParseError::unsupported("Error http response"));
+    }
+    let begin_times = rest_msg
+        .result
+        .iter()
+        .map(|raw_candlestick| raw_candlestick.time as i64)
+        .collect::<Vec<i64>>();
+    // resolution in seconds is a request parameter only
+    let period = infer_candle_period(&begin_times)?;
+
+    let candlesticks = rest_msg
+        .result
+        .into_iter()
+        .map(|raw_candlestick| {
+            let timestamp = raw_candlestick.time as i64;
+            CandlestickMsg {
+                exchange: EXCHANGE_NAME.to_string(),
+                market_type,
+                msg_type: MessageType::Candlestick,
+                symbol: symbol.to_string(),
+                pair: pair.clone(),
+                timestamp,
+                period,
+                begin_time: timestamp,
+                end_time: period.end_time(timestamp),
+                open: raw_candlestick.open,
+                high: raw_candlestick.high,
+                low: raw_candlestick.low,
+                close: raw_candlestick.close,
+                // FTX reports volume in quote currency only
+                volume: round(raw_candlestick.volume / raw_candlestick.close),
+                quote_volume: Some(raw_candlestick.volume),
+                is_closed: None,
+                json: serde_json::to_string(&raw_candlestick).unwrap(),
+            }
+        })
+        .collect();
+
+    Ok(candlesticks)
+}
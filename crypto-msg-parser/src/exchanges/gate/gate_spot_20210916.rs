@@ -3,9 +3,12 @@ use crypto_msg_type::MessageType;
 
 use crypto_message::{BboMsg, CandlestickMsg, Order, OrderBookMsg, TradeMsg, TradeSide};
 
+use crate::{
+    ParseError,
+    exchanges::utils::{json_array, json_f64, json_str, parse_exact},
+};
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
-use simple_error::SimpleError;
 use std::collections::HashMap;
 
 const EXCHANGE_NAME: &str = "gate";
@@ -35,57 +38,57 @@ struct SpotOrderbookMsg {
 #[derive(Serialize, Deserialize)]
 struct SpotWebsocketMsg {
     method: String,
-    params: Vec<Value>,
+    // an array, indexing a `Value` returns `Null` rather than panic if it's too short
+    params: Value,
     #[serde(flatten)]
     extra: HashMap<String, Value>,
 }
 
-pub(super) fn extract_symbol(msg: &str) -> Result<String, SimpleError> {
-    let ws_msg = serde_json::from_str::<SpotWebsocketMsg>(msg).map_err(|_e| {
-        SimpleError::new(format!("Failed to deserialize {msg} to SpotWebsocketMsg"))
-    })?;
+pub(super) fn extract_symbol(msg: &str) -> Result<String, ParseError> {
+    let ws_msg = serde_json::from_str::<SpotWebsocketMsg>(msg)
+        .map_err(|_e| ParseError::malformed("expected SpotWebsocketMsg"))?;
     if ws_msg.method == "trades.update" || ws_msg.method == "ticker.update" {
-        Ok(ws_msg.params[0].as_str().unwrap().to_string())
+        Ok(json_str(&ws_msg.params[0], "params[0]")?.to_string())
     } else if ws_msg.method == "depth.update" {
-        Ok(ws_msg.params[2].as_str().unwrap().to_string())
+        Ok(json_str(&ws_msg.params[2], "params[2]")?.to_string())
     } else {
-        Err(SimpleError::new(format!("Unknown message format: {msg}")))
+        Err(ParseError::unsupported("Unknown message format"))
     }
 }
 
-pub(super) fn extract_timestamp(msg: &str) -> Result<Option<i64>, SimpleError> {
-    let ws_msg = serde_json::from_str::<SpotWebsocketMsg>(msg).map_err(|_e| {
-        SimpleError::new(format!("Failed to deserialize {msg} to SpotWebsocketMsg"))
-    })?;
+pub(super) fn extract_timestamp(msg: &str) -> Result<Option<i64>, ParseError> {
+    let ws_msg = serde_json::from_str::<SpotWebsocketMsg>(msg)
+        .map_err(|_e| ParseError::malformed("expected SpotWebsocketMsg"))?;
     if ws_msg.method == "trades.update" {
-        let raw_trades = ws_msg.params[1].as_array().unwrap();
+        let raw_trades = json_array(&ws_msg.params[1], "params[1]")?;
         let timestamp = raw_trades
             .iter()
-            .map(|raw_trade| (raw_trade.get("time").unwrap().as_f64().unwrap() * 1000.0) as i64)
+            .map(|raw_trade| Ok((json_f64(&raw_trade["time"], "time")? * 1000.0) as i64))
+            .collect::<Result<Vec<i64>, ParseError>>()?
+            .into_iter()
             .max();
         if timestamp.is_none() {
-            Err(SimpleError::new(format!("as and bs are empty in {msg}")))
+            Err(ParseError::unsupported("as and bs are empty"))
         } else {
             Ok(timestamp)
         }
     } else if ws_msg.method == "depth.update" || ws_msg.method == "ticker.update" {
         Ok(None)
     } else {
-        Err(SimpleError::new(format!("Unknown message format: {msg}")))
+        Err(ParseError::unsupported("Unknown message format"))
     }
 }
 
 #[deprecated(since = "1.3.7", note = "Gate has new data format since 2021-09-16")]
-pub(super) fn parse_trade(msg: &str) -> Result<Vec<TradeMsg>, SimpleError> {
-    let ws_msg = serde_json::from_str::<SpotWebsocketMsg>(msg).map_err(|_e| {
-        SimpleError::new(format!("Failed to deserialize {msg} to SpotWebsocketMsg"))
-    })?;
-    let symbol = ws_msg.params[0].as_str().unwrap();
+pub(super) fn parse_trade(msg: &str) -> Result<Vec<TradeMsg>, ParseError> {
+    let ws_msg = serde_json::from_str::<SpotWebsocketMsg>(msg)
+        .map_err(|_e| ParseError::malformed("expected SpotWebsocketMsg"))?;
+    let symbol = json_str(&ws_msg.params[0], "params[0]")?;
     let pair = crypto_pair::normalize_pair(symbol, EXCHANGE_NAME)
-        .ok_or_else(|| SimpleError::new(format!("Failed to normalize {symbol} from {msg}")))?;
+        .ok_or_else(|| ParseError::symbol_normalization(symbol))?;
     let raw_trades: Vec<SpotTradeMsg> =
         serde_json::from_value(ws_msg.params[1].clone()).map_err(|_e| {
-            SimpleError::new(format!(
+            ParseError::malformed(format!(
                 "Failed to deserialize {} to Vec<SpotTradeMsg>",
                 ws_msg.params[1]
             ))
@@ -93,11 +96,11 @@ pub(super) fn parse_trade(msg: &str) -> Result<Vec<TradeMsg>, SimpleError> {
 
     let mut trades: Vec<TradeMsg> = raw_trades
         .into_iter()
-        .map(|raw_trade| {
-            let price = raw_trade.price.parse::<f64>().unwrap();
-            let quantity = raw_trade.amount.parse::<f64>().unwrap();
+        .map(|raw_trade| -> Result<TradeMsg, ParseError> {
+            let price = raw_trade.price.parse::<f64>()?;
+            let quantity = raw_trade.amount.parse::<f64>()?;
 
-            TradeMsg {
+            Ok(TradeMsg {
                 exchange: EXCHANGE_NAME.to_string(),
                 market_type: MarketType::Spot,
                 symbol: symbol.to_string(),
@@ -108,12 +111,14 @@ pub(super) fn parse_trade(msg: &str) -> Result<Vec<TradeMsg>, SimpleError> {
                 quantity_base: quantity,
                 quantity_quote: price * quantity,
                 quantity_contract: None,
+                price_exact: parse_exact(&raw_trade.price),
+                quantity_exact: parse_exact(&raw_trade.amount),
                 side: if raw_trade.type_ == "sell" { TradeSide::Sell } else { TradeSide::Buy },
                 trade_id: raw_trade.id.to_string(),
                 json: serde_json::to_string(&raw_trade).unwrap(),
-            }
+            })
         })
-        .collect();
+        .collect::<Result<Vec<TradeMsg>, ParseError>>()?;
 
     if trades.len() == 1 {
         trades[0].json = msg.to_string();
@@ -122,32 +127,37 @@ pub(super) fn parse_trade(msg: &str) -> Result<Vec<TradeMsg>, SimpleError> {
 }
 
 #[deprecated(since = "1.3.7", note = "Gate has new data format since 2021-09-16")]
-pub(crate) fn parse_l2(msg: &str, timestamp: i64) -> Result<Vec<OrderBookMsg>, SimpleError> {
-    let ws_msg = serde_json::from_str::<SpotWebsocketMsg>(msg).map_err(|_e| {
-        SimpleError::new(format!("Failed to deserialize {msg} to SpotWebsocketMsg"))
-    })?;
-    debug_assert_eq!(ws_msg.params.len(), 3);
-    let snapshot = ws_msg.params[0].as_bool().unwrap();
-    let symbol = ws_msg.params[2].as_str().unwrap();
+pub(crate) fn parse_l2(msg: &str, timestamp: i64) -> Result<Vec<OrderBookMsg>, ParseError> {
+    let ws_msg = serde_json::from_str::<SpotWebsocketMsg>(msg)
+        .map_err(|_e| ParseError::malformed("expected SpotWebsocketMsg"))?;
+    if ws_msg.params.as_array().map(|x| x.len()) != Some(3) {
+        return Err(ParseError::malformed("params should have 3 elements"));
+    }
+    let snapshot = ws_msg.params[0]
+        .as_bool()
+        .ok_or_else(|| ParseError::malformed("params[0] is not a bool"))?;
+    let symbol = json_str(&ws_msg.params[2], "params[2]")?;
     let pair = crypto_pair::normalize_pair(symbol, EXCHANGE_NAME)
-        .ok_or_else(|| SimpleError::new(format!("Failed to normalize {symbol} from {msg}")))?;
+        .ok_or_else(|| ParseError::symbol_normalization(symbol))?;
     let raw_orderbook = serde_json::from_value::<SpotOrderbookMsg>(ws_msg.params[1].clone())
         .map_err(|_e| {
-            SimpleError::new(format!(
+            ParseError::malformed(format!(
                 "Failed to deserialize {} to SpotOrderbookMsg",
                 ws_msg.params[1]
             ))
         })?;
 
-    let parse_order = |raw_order: &[String; 2]| -> Order {
-        let price = raw_order[0].parse::<f64>().unwrap();
-        let quantity_base = raw_order[1].parse::<f64>().unwrap();
-        Order {
+    let parse_order = |raw_order: &[String; 2]| -> Result<Order, ParseError> {
+        let price = raw_order[0].parse::<f64>()?;
+        let quantity_base = raw_order[1].parse::<f64>()?;
+        Ok(Order {
             price,
             quantity_base,
             quantity_quote: price * quantity_base,
             quantity_contract: None,
-        }
+            price_exact: parse_exact(&raw_order[0]),
+            quantity_exact: parse_exact(&raw_order[1]),
+        })
     };
 
     let orderbook = OrderBookMsg {
@@ -160,12 +170,12 @@ pub(crate) fn parse_l2(msg: &str, timestamp: i64) -> Result<Vec<OrderBookMsg>, S
         seq_id: None,
         prev_seq_id: None,
         asks: if let Some(asks) = raw_orderbook.asks {
-            asks.iter().map(parse_order).collect()
+            asks.iter().map(parse_order).collect::<Result<Vec<Order>, ParseError>>()?
         } else {
             Vec::new()
         },
         bids: if let Some(bids) = raw_orderbook.bids {
-            bids.iter().map(parse_order).collect()
+            bids.iter().map(parse_order).collect::<Result<Vec<Order>, ParseError>>()?
         } else {
             Vec::new()
         },
@@ -176,14 +186,14 @@ pub(crate) fn parse_l2(msg: &str, timestamp: i64) -> Result<Vec<OrderBookMsg>, S
     Ok(vec![orderbook])
 }
 
-pub(super) fn parse_l2_topk(_msg: &str) -> Result<Vec<OrderBookMsg>, SimpleError> {
-    todo!()
+pub(super) fn parse_l2_topk(_msg: &str) -> Result<Vec<OrderBookMsg>, ParseError> {
+    Err(ParseError::unsupported("Not implemented"))
 }
 
-pub(super) fn parse_bbo(_msg: &str) -> Result<Vec<BboMsg>, SimpleError> {
-    todo!()
+pub(super) fn parse_bbo(_msg: &str) -> Result<Vec<BboMsg>, ParseError> {
+    Err(ParseError::unsupported("Not implemented"))
 }
 
-pub(super) fn parse_candlestick(_msg: &str) -> Result<Vec<CandlestickMsg>, SimpleError> {
-    todo!()
+pub(super) fn parse_candlestick(_msg: &str) -> Result<Vec<CandlestickMsg>, ParseError> {
+    Err(ParseError::unsupported("Not implemented"))
 }
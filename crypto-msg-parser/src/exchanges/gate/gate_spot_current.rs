@@ -2,10 +2,13 @@ use crypto_market_type::MarketType;
 use crypto_msg_type::MessageType;
 
 use super::messages::WebsocketMsg;
-use crypto_message::{BboMsg, CandlestickMsg, Order, OrderBookMsg, TradeMsg, TradeSide};
+use crate::{
+    ParseError,
+    exchanges::utils::{json_i64, json_str, parse_candle_period, parse_exact},
+};
+use crypto_message::{BboMsg, CandlestickMsg, Order, OrderBookMsg, TickerMsg, TradeMsg, TradeSide};
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
-use simple_error::SimpleError;
 use std::collections::HashMap;
 
 const EXCHANGE_NAME: &str = "gate";
@@ -89,61 +92,83 @@ struct RawCandlestickMsg {
     o: String, // Open price
     n: String, // Name of the subscription, in the format of <interval>_<cp>
     a: String, // Total volume in quote currency
+    #[serde(skip_serializing_if = "Option::is_none")]
+    w: Option<bool>, // Whether the window is closed, absent in old messages
 }
 
-pub(super) fn extract_symbol(msg: &str) -> Result<String, SimpleError> {
+// https://www.gate.io/docs/developers/apiv4/ws/en/#tickers-channel
+#[derive(Serialize, Deserialize)]
+struct RawTickerMsg {
+    currency_pair: String,
+    last: String,
+    lowest_ask: String,
+    highest_bid: String,
+    change_percentage: String,
+    base_volume: String,
+    quote_volume: String,
+    high_24h: String,
+    low_24h: String,
+    #[serde(flatten)]
+    extra: HashMap<String, Value>,
+}
+
+pub(super) fn extract_symbol(msg: &str) -> Result<String, ParseError> {
     if let Ok(ws_msg) = serde_json::from_str::<WebsocketMsg<HashMap<String, Value>>>(msg) {
         if let Some(symbol) = ws_msg.result.get("currency_pair") {
-            Ok(symbol.as_str().unwrap().to_string())
+            Ok(json_str(symbol, "currency_pair")?.to_string())
         } else if let Some(symbol) = ws_msg.result.get("s") {
-            Ok(symbol.as_str().unwrap().to_string())
+            Ok(json_str(symbol, "s")?.to_string())
         } else if let Some(symbol) = ws_msg.result.get("n") {
-            let n = symbol.as_str().unwrap();
-            let pos = n.find('_').unwrap();
+            let n = json_str(symbol, "n")?;
+            let pos = n.find('_').ok_or_else(|| ParseError::malformed(format!("Invalid n {n}")))?;
             let symbol = &n[(pos + 1)..];
             Ok(symbol.to_string())
         } else {
-            Err(SimpleError::new(format!("Unsupported websocket message format {msg}")))
+            Err(ParseError::unsupported("Unsupported websocket message format"))
         }
     } else if serde_json::from_str::<SpotRestL2SnapshotMsg>(msg).is_ok() {
         Ok("NONE".to_string())
     } else {
-        Err(SimpleError::new(format!("Unsupported message format {msg}")))
+        Err(ParseError::unsupported("Unsupported message format"))
     }
 }
 
-pub(super) fn extract_timestamp(msg: &str) -> Result<Option<i64>, SimpleError> {
+pub(super) fn extract_timestamp(msg: &str) -> Result<Option<i64>, ParseError> {
     if let Ok(ws_msg) = serde_json::from_str::<WebsocketMsg<HashMap<String, Value>>>(msg) {
         if ws_msg.channel == "spot.trades" {
-            Ok(Some(
-                ws_msg.result["create_time_ms"].as_str().unwrap().parse::<f64>().unwrap() as i64
-            ))
+            let create_time_ms =
+                ws_msg.result.get("create_time_ms").ok_or_else(ParseError::missing_timestamp)?;
+            Ok(Some(json_str(create_time_ms, "create_time_ms")?.parse::<f64>()? as i64))
         } else if ws_msg.channel.starts_with("spot.order_book")
             || ws_msg.channel == "spot.book_ticker"
         {
-            Ok(Some(ws_msg.result["t"].as_i64().unwrap()))
+            let t = ws_msg.result.get("t").ok_or_else(ParseError::missing_timestamp)?;
+            Ok(Some(json_i64(t, "t")?))
         } else {
             Ok(Some(ws_msg.time * 1000))
         }
     } else if let Ok(l2_snapshot) = serde_json::from_str::<SpotRestL2SnapshotMsg>(msg) {
         Ok(Some(l2_snapshot.current))
     } else {
-        Err(SimpleError::new(format!("Unsupported message format {msg}")))
+        Err(ParseError::unsupported("Unsupported message format"))
     }
 }
 
-pub(super) fn parse_trade(msg: &str) -> Result<Vec<TradeMsg>, SimpleError> {
-    let ws_msg = serde_json::from_str::<WebsocketMsg<SpotTradeMsg>>(msg).map_err(|_e| {
-        SimpleError::new(format!("Failed to deserialize {msg} to WebsocketMsg<SpotTradeMsg>"))
-    })?;
-    debug_assert_eq!(ws_msg.channel, "spot.trades");
-    debug_assert_eq!(ws_msg.event, "update");
+pub(super) fn parse_trade(msg: &str) -> Result<Vec<TradeMsg>, ParseError> {
+    let ws_msg = serde_json::from_str::<WebsocketMsg<SpotTradeMsg>>(msg)
+        .map_err(|_e| ParseError::malformed("expected WebsocketMsg<SpotTradeMsg>"))?;
+    if ws_msg.channel != "spot.trades" {
+        return Err(ParseError::unsupported(format!("Unsupported channel {}", ws_msg.channel)));
+    }
+    if ws_msg.event != "update" {
+        return Err(ParseError::unsupported(format!("Unsupported event {}", ws_msg.event)));
+    }
     let result = ws_msg.result;
     let symbol = result.currency_pair;
     let pair = crypto_pair::normalize_pair(&symbol, EXCHANGE_NAME)
-        .ok_or_else(|| SimpleError::new(format!("Failed to normalize {symbol} from {msg}")))?;
-    let price = result.price.parse::<f64>().unwrap();
-    let quantity_base = result.amount.parse::<f64>().unwrap();
+        .ok_or_else(|| ParseError::symbol_normalization(&symbol))?;
+    let price = result.price.parse::<f64>()?;
+    let quantity_base = result.amount.parse::<f64>()?;
 
     let trade = TradeMsg {
         exchange: EXCHANGE_NAME.to_string(),
@@ -151,11 +176,13 @@ pub(super) fn parse_trade(msg: &str) -> Result<Vec<TradeMsg>, SimpleError> {
         symbol,
         pair,
         msg_type: MessageType::Trade,
-        timestamp: result.create_time_ms.parse::<f64>().unwrap() as i64,
-        price: result.price.parse::<f64>().unwrap(),
+        timestamp: result.create_time_ms.parse::<f64>()? as i64,
+        price: result.price.parse::<f64>()?,
         quantity_base,
         quantity_quote: price * quantity_base,
         quantity_contract: None,
+        price_exact: parse_exact(&result.price),
+        quantity_exact: parse_exact(&result.amount),
         side: if result.side == "sell" { TradeSide::Sell } else { TradeSide::Buy },
         trade_id: result.id.to_string(),
         json: msg.to_string(),
@@ -164,24 +191,29 @@ pub(super) fn parse_trade(msg: &str) -> Result<Vec<TradeMsg>, SimpleError> {
     Ok(vec![trade])
 }
 
-fn parse_order(raw_order: &[String; 2]) -> Order {
-    let price = raw_order[0].parse::<f64>().unwrap();
-    let quantity_base = raw_order[1].parse::<f64>().unwrap();
-    Order { price, quantity_base, quantity_quote: price * quantity_base, quantity_contract: None }
+fn parse_order(raw_order: &[String; 2]) -> Result<Order, ParseError> {
+    let price = raw_order[0].parse::<f64>()?;
+    let quantity_base = raw_order[1].parse::<f64>()?;
+    Ok(Order {
+        price,
+        quantity_base,
+        quantity_quote: price * quantity_base,
+        quantity_contract: None,
+        price_exact: parse_exact(&raw_order[0]),
+        quantity_exact: parse_exact(&raw_order[1]),
+    })
 }
 
-pub(super) fn parse_l2(msg: &str) -> Result<Vec<OrderBookMsg>, SimpleError> {
-    let ws_msg =
-        serde_json::from_str::<WebsocketMsg<SpotOrderbookUpdateMsg>>(msg).map_err(|_e| {
-            SimpleError::new(format!(
-                "Failed to deserialize {msg} to WebsocketMsg<SpotOrderbookUpdateMsg>"
-            ))
-        })?;
-    debug_assert_eq!(ws_msg.channel, "spot.order_book_update");
+pub(super) fn parse_l2(msg: &str) -> Result<Vec<OrderBookMsg>, ParseError> {
+    let ws_msg = serde_json::from_str::<WebsocketMsg<SpotOrderbookUpdateMsg>>(msg)
+        .map_err(|_e| ParseError::malformed("expected WebsocketMsg<SpotOrderbookUpdateMsg>"))?;
+    if ws_msg.channel != "spot.order_book_update" {
+        return Err(ParseError::unsupported(format!("Unsupported channel {}", ws_msg.channel)));
+    }
     let result = ws_msg.result;
     let symbol = result.s;
     let pair = crypto_pair::normalize_pair(&symbol, EXCHANGE_NAME)
-        .ok_or_else(|| SimpleError::new(format!("Failed to normalize {symbol} from {msg}")))?;
+        .ok_or_else(|| ParseError::symbol_normalization(&symbol))?;
 
     let orderbook = OrderBookMsg {
         exchange: EXCHANGE_NAME.to_string(),
@@ -191,14 +223,14 @@ pub(super) fn parse_l2(msg: &str) -> Result<Vec<OrderBookMsg>, SimpleError> {
         msg_type: MessageType::L2Event,
         timestamp: result.t,
         seq_id: Some(result.u as u64),
-        prev_seq_id: Some(result.U as u64 - 1),
+        prev_seq_id: (result.U as u64).checked_sub(1),
         asks: if let Some(asks) = result.a {
-            asks.iter().map(parse_order).collect()
+            asks.iter().map(parse_order).collect::<Result<Vec<Order>, ParseError>>()?
         } else {
             Vec::new()
         },
         bids: if let Some(bids) = result.b {
-            bids.iter().map(parse_order).collect()
+            bids.iter().map(parse_order).collect::<Result<Vec<Order>, ParseError>>()?
         } else {
             Vec::new()
         },
@@ -209,30 +241,19 @@ pub(super) fn parse_l2(msg: &str) -> Result<Vec<OrderBookMsg>, SimpleError> {
     Ok(vec![orderbook])
 }
 
-pub(super) fn parse_l2_topk(msg: &str) -> Result<Vec<OrderBookMsg>, SimpleError> {
-    let ws_msg =
-        serde_json::from_str::<WebsocketMsg<SpotOrderbookSnapshotMsg>>(msg).map_err(|_e| {
-            SimpleError::new(format!(
-                "Failed to deserialize {msg} to WebsocketMsg<SpotOrderbookSnapshotMsg>"
-            ))
-        })?;
-    debug_assert_eq!(ws_msg.channel, "spot.order_book");
-    debug_assert_eq!(ws_msg.event, "update");
+pub(super) fn parse_l2_topk(msg: &str) -> Result<Vec<OrderBookMsg>, ParseError> {
+    let ws_msg = serde_json::from_str::<WebsocketMsg<SpotOrderbookSnapshotMsg>>(msg)
+        .map_err(|_e| ParseError::malformed("expected WebsocketMsg<SpotOrderbookSnapshotMsg>"))?;
+    if ws_msg.channel != "spot.order_book" {
+        return Err(ParseError::unsupported(format!("Unsupported channel {}", ws_msg.channel)));
+    }
+    if ws_msg.event != "update" {
+        return Err(ParseError::unsupported(format!("Unsupported event {}", ws_msg.event)));
+    }
     let result = ws_msg.result;
     let symbol = result.s;
     let pair = crypto_pair::normalize_pair(&symbol, EXCHANGE_NAME)
-        .ok_or_else(|| SimpleError::new(format!("Failed to normalize {symbol} from {msg}")))?;
-
-    let parse_order = |raw_order: &[String; 2]| -> Order {
-        let price = raw_order[0].parse::<f64>().unwrap();
-        let quantity_base = raw_order[1].parse::<f64>().unwrap();
-        Order {
-            price,
-            quantity_base,
-            quantity_quote: price * quantity_base,
-            quantity_contract: None,
-        }
-    };
+        .ok_or_else(|| ParseError::symbol_normalization(&symbol))?;
 
     let orderbook = OrderBookMsg {
         exchange: EXCHANGE_NAME.to_string(),
@@ -244,12 +265,12 @@ pub(super) fn parse_l2_topk(msg: &str) -> Result<Vec<OrderBookMsg>, SimpleError>
         seq_id: Some(result.lastUpdateId),
         prev_seq_id: None,
         asks: if let Some(asks) = result.asks {
-            asks.iter().map(parse_order).collect()
+            asks.iter().map(parse_order).collect::<Result<Vec<Order>, ParseError>>()?
         } else {
             Vec::new()
         },
         bids: if let Some(bids) = result.bids {
-            bids.iter().map(parse_order).collect()
+            bids.iter().map(parse_order).collect::<Result<Vec<Order>, ParseError>>()?
         } else {
             Vec::new()
         },
@@ -260,20 +281,25 @@ pub(super) fn parse_l2_topk(msg: &str) -> Result<Vec<OrderBookMsg>, SimpleError>
     Ok(vec![orderbook])
 }
 
-pub(super) fn parse_bbo(msg: &str) -> Result<Vec<BboMsg>, SimpleError> {
-    let ws_msg = serde_json::from_str::<WebsocketMsg<RawBboMsg>>(msg).map_err(SimpleError::from)?;
-    debug_assert_eq!("spot.book_ticker", ws_msg.channel);
-    debug_assert_eq!("update", ws_msg.event);
+pub(super) fn parse_bbo(msg: &str) -> Result<Vec<BboMsg>, ParseError> {
+    let ws_msg = serde_json::from_str::<WebsocketMsg<RawBboMsg>>(msg).map_err(ParseError::from)?;
+    if ws_msg.channel != "spot.book_ticker" {
+        return Err(ParseError::unsupported(format!("Unsupported channel {}", ws_msg.channel)));
+    }
+    if ws_msg.event != "update" {
+        return Err(ParseError::unsupported(format!("Unsupported event {}", ws_msg.event)));
+    }
 
     let symbol = ws_msg.result.s.as_str();
-    let pair = crypto_pair::normalize_pair(symbol, EXCHANGE_NAME).unwrap();
+    let pair = crypto_pair::normalize_pair(symbol, EXCHANGE_NAME)
+        .ok_or_else(|| ParseError::symbol_normalization(symbol))?;
     let timestamp = ws_msg.result.t;
 
-    let ask_price = ws_msg.result.a.parse::<f64>().unwrap();
-    let ask_size = ws_msg.result.A.parse::<f64>().unwrap();
+    let ask_price = ws_msg.result.a.parse::<f64>()?;
+    let ask_size = ws_msg.result.A.parse::<f64>()?;
 
-    let bid_price = ws_msg.result.b.parse::<f64>().unwrap();
-    let bid_size = ws_msg.result.B.parse::<f64>().unwrap();
+    let bid_price = ws_msg.result.b.parse::<f64>()?;
+    let bid_size = ws_msg.result.B.parse::<f64>()?;
 
     let bbo_msg = BboMsg {
         exchange: EXCHANGE_NAME.to_string(),
@@ -290,6 +316,10 @@ pub(super) fn parse_bbo(msg: &str) -> Result<Vec<BboMsg>, SimpleError> {
         bid_quantity_base: bid_size,
         bid_quantity_quote: bid_price * bid_size,
         bid_quantity_contract: None,
+        bid_price_exact: parse_exact(&ws_msg.result.b),
+        bid_quantity_exact: parse_exact(&ws_msg.result.B),
+        ask_price_exact: parse_exact(&ws_msg.result.a),
+        ask_quantity_exact: parse_exact(&ws_msg.result.A),
         id: None,
         json: msg.to_string(),
     };
@@ -297,18 +327,28 @@ pub(super) fn parse_bbo(msg: &str) -> Result<Vec<BboMsg>, SimpleError> {
     Ok(vec![bbo_msg])
 }
 
-pub(super) fn parse_candlestick(msg: &str) -> Result<Vec<CandlestickMsg>, SimpleError> {
+pub(super) fn parse_candlestick(msg: &str) -> Result<Vec<CandlestickMsg>, ParseError> {
     let ws_msg =
-        serde_json::from_str::<WebsocketMsg<RawCandlestickMsg>>(msg).map_err(SimpleError::from)?;
-    debug_assert_eq!(ws_msg.channel, "spot.candlesticks");
-    debug_assert_eq!(ws_msg.event, "update");
+        serde_json::from_str::<WebsocketMsg<RawCandlestickMsg>>(msg).map_err(ParseError::from)?;
+    if ws_msg.channel != "spot.candlesticks" {
+        return Err(ParseError::unsupported(format!("Unsupported channel {}", ws_msg.channel)));
+    }
+    if ws_msg.event != "update" {
+        return Err(ParseError::unsupported(format!("Unsupported event {}", ws_msg.event)));
+    }
     let result = ws_msg.result;
 
     let (period, symbol) = {
-        let pos = result.n.find('_').unwrap();
+        let pos = result
+            .n
+            .find('_')
+            .ok_or_else(|| ParseError::malformed(format!("Invalid n {}", result.n)))?;
         (&result.n[..pos], &result.n[pos + 1..])
     };
-    let pair = crypto_pair::normalize_pair(symbol, EXCHANGE_NAME).unwrap();
+    let pair = crypto_pair::normalize_pair(symbol, EXCHANGE_NAME)
+        .ok_or_else(|| ParseError::symbol_normalization(symbol))?;
+    let period = parse_candle_period(EXCHANGE_NAME, MarketType::Spot, period)?;
+    let begin_time = result.t.parse::<i64>()? * 1000;
 
     let candlestick_msg = CandlestickMsg {
         exchange: EXCHANGE_NAME.to_string(),
@@ -317,16 +357,62 @@ pub(super) fn parse_candlestick(msg: &str) -> Result<Vec<CandlestickMsg>, Simple
         symbol: symbol.to_string(),
         pair,
         timestamp: ws_msg.time * 1000,
-        period: period.to_string(),
-        begin_time: result.t.parse().unwrap(),
-        open: result.o.parse().unwrap(),
-        high: result.h.parse().unwrap(),
-        low: result.l.parse().unwrap(),
-        close: result.c.parse().unwrap(),
-        volume: result.a.parse().unwrap(),
+        period,
+        begin_time,
+        end_time: period.end_time(begin_time),
+        open: result.o.parse()?,
+        high: result.h.parse()?,
+        low: result.l.parse()?,
+        close: result.c.parse()?,
+        volume: result.a.parse()?,
         quote_volume: result.v.parse().ok(),
+        is_closed: result.w,
         json: msg.to_string(),
     };
 
     Ok(vec![candlestick_msg])
 }
+
+pub(super) fn parse_ticker(msg: &str) -> Result<Vec<TickerMsg>, ParseError> {
+    let ws_msg = serde_json::from_str::<WebsocketMsg<RawTickerMsg>>(msg)
+        .map_err(|_e| ParseError::malformed("expected WebsocketMsg<RawTickerMsg>"))?;
+    if ws_msg.channel != "spot.tickers" {
+        return Err(ParseError::unsupported(format!("Unsupported channel {}", ws_msg.channel)));
+    }
+    let result = ws_msg.result;
+    let symbol = result.currency_pair.as_str();
+    let pair = crypto_pair::normalize_pair(symbol, EXCHANGE_NAME)
+        .ok_or_else(|| ParseError::symbol_normalization(symbol))?;
+    let parse_f64 = |s: &str| -> Result<f64, ParseError> {
+        s.parse::<f64>().map_err(|_e| ParseError::malformed(format!("Failed to parse {s} as f64")))
+    };
+
+    let close = parse_f64(&result.last)?;
+    // The tickers channel has no open price, derive it from the 24h price change
+    let open = close / (1.0 + parse_f64(&result.change_percentage)? / 100.0);
+
+    let ticker_msg = TickerMsg {
+        exchange: EXCHANGE_NAME.to_string(),
+        market_type: MarketType::Spot,
+        symbol: symbol.to_string(),
+        pair,
+        msg_type: MessageType::Ticker,
+        timestamp: ws_msg.time * 1000,
+        open,
+        high: parse_f64(&result.high_24h)?,
+        low: parse_f64(&result.low_24h)?,
+        close,
+        volume: parse_f64(&result.base_volume)?,
+        quote_volume: parse_f64(&result.quote_volume)?,
+        last_quantity: None,
+        best_bid_price: result.highest_bid.parse::<f64>().ok(),
+        best_bid_quantity: None,
+        best_ask_price: result.lowest_ask.parse::<f64>().ok(),
+        best_ask_quantity: None,
+        open_interest: None,
+        open_interest_quote: None,
+        json: msg.to_string(),
+    };
+
+    Ok(vec![ticker_msg])
+}
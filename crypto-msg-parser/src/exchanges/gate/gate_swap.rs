@@ -1,13 +1,23 @@
 use crypto_market_type::MarketType;
 use crypto_msg_type::MessageType;
 
-use super::{super::utils::calc_quantity_and_volume, messages::WebsocketMsg};
-
-use crypto_message::{BboMsg, CandlestickMsg, Order, OrderBookMsg, TradeMsg, TradeSide};
-
+use super::{
+    super::utils::{
+        annualize_funding_rate, calc_open_interest, calc_quantity_and_volume, json_array,
+        json_exact, json_f64, json_i64, json_object, json_str, next_funding_time,
+        parse_candle_period, parse_exact, round,
+    },
+    messages::WebsocketMsg,
+};
+
+use crypto_message::{
+    BboMsg, CandlestickMsg, FundingRateMsg, OpenInterestMsg, Order, OrderBookMsg, TickerMsg,
+    TradeMsg, TradeSide,
+};
+
+use crate::ParseError;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
-use simple_error::SimpleError;
 use std::{cell::RefCell, collections::HashMap};
 
 const EXCHANGE_NAME: &str = "gate";
@@ -89,71 +99,86 @@ struct RawCandlestickMsg {
     l: String, // Lowest price
     o: String, // Open price
     n: String, // <interval>_<contract>
+    #[serde(skip_serializing_if = "Option::is_none")]
+    w: Option<bool>, // Whether the window is closed, absent in old messages
 }
 
-pub(super) fn extract_symbol(_market_type_: MarketType, msg: &str) -> Result<String, SimpleError> {
+// https://www.gate.io/docs/developers/delivery/ws/en/#tickers-api
+// https://www.gate.io/docs/developers/futures/ws/en/#tickers-api
+#[derive(Serialize, Deserialize)]
+struct RawTickerMsg {
+    contract: String,
+    last: String,
+    change_percentage: String,
+    total_size: String, // open interest in contracts
+    volume_24h: String, // volume in contracts
+    high_24h: String,
+    low_24h: String,
+    #[serde(flatten)]
+    extra: HashMap<String, Value>,
+}
+
+pub(super) fn extract_symbol(_market_type_: MarketType, msg: &str) -> Result<String, ParseError> {
     if let Ok(ws_msg) = serde_json::from_str::<WebsocketMsg<Value>>(msg) {
         let v = if ws_msg.result.is_array() {
-            ws_msg.result.as_array().unwrap()[0].as_object().unwrap()
+            json_object(&ws_msg.result[0], "result")?
         } else {
-            ws_msg.result.as_object().unwrap()
+            json_object(&ws_msg.result, "result")?
         };
         if let Some(symbol) = v.get("contract") {
-            Ok(symbol.as_str().unwrap().to_string())
-        } else if v.contains_key("s") && v["s"].is_string() {
-            Ok(v["s"].as_str().unwrap().to_string())
-        } else if v.contains_key("n") && v["n"].is_string() {
-            let n = v["n"].as_str().unwrap();
-            let pos = n.find('_').unwrap();
+            Ok(json_str(symbol, "contract")?.to_string())
+        } else if let Some(s) = v.get("s").and_then(|x| x.as_str()) {
+            Ok(s.to_string())
+        } else if let Some(n) = v.get("n").and_then(|x| x.as_str()) {
+            let pos = n.find('_').ok_or_else(|| ParseError::malformed(format!("Invalid n {n}")))?;
             let symbol = &n[(pos + 1)..];
             Ok(symbol.to_string())
-        } else if v.contains_key("c") && v["c"].is_string() {
-            Ok(v["c"].as_str().unwrap().to_string())
+        } else if let Some(c) = v.get("c").and_then(|x| x.as_str()) {
+            Ok(c.to_string())
         } else {
-            Err(SimpleError::new(format!("Unsupported websocket message format  {msg}")))
+            Err(ParseError::unsupported("Unsupported websocket message format"))
         }
     } else if msg.contains("open_interest")
         || serde_json::from_str::<SwapRestL2SnapshotMsg>(msg).is_ok()
     {
         Ok("NONE".to_string())
     } else {
-        Err(SimpleError::new(format!("Unsupported message format  {msg}")))
+        Err(ParseError::unsupported("Unsupported message format"))
     }
 }
 
-pub(super) fn extract_timestamp(msg: &str) -> Result<Option<i64>, SimpleError> {
+pub(super) fn extract_timestamp(msg: &str) -> Result<Option<i64>, ParseError> {
     if let Ok(ws_msg) = serde_json::from_str::<WebsocketMsg<Value>>(msg) {
         let result = ws_msg.result;
         if ws_msg.channel == "futures.trades" {
-            let timestamp = result
-                .as_array()
-                .unwrap()
+            let timestamp = json_array(&result, "result")?
                 .iter()
-                .map(|x| x.as_object().unwrap())
                 .map(|x| {
-                    if x.contains_key("create_time_ms") {
-                        x["create_time_ms"].as_i64().unwrap()
+                    if let Some(create_time_ms) = x.get("create_time_ms") {
+                        json_i64(create_time_ms, "create_time_ms")
                     } else {
-                        x["create_time"].as_i64().unwrap() * 1000
+                        Ok(json_i64(&x["create_time"], "create_time")? * 1000)
                     }
                 })
+                .collect::<Result<Vec<i64>, ParseError>>()?
+                .into_iter()
                 .max();
 
             if timestamp.is_none() {
-                Err(SimpleError::new(format!("result is empty in {msg}")))
+                Err(ParseError::unsupported("result is empty"))
             } else {
                 Ok(timestamp)
             }
         } else if ws_msg.channel == "futures.order_book" {
             if let Some(x) = result.get("t") {
-                Ok(Some(x.as_i64().unwrap()))
+                Ok(Some(json_i64(x, "t")?))
             } else {
                 Ok(Some(ws_msg.time * 1000))
             }
         } else if ws_msg.channel == "futures.order_book_update"
             || ws_msg.channel == "futures.book_ticker"
         {
-            Ok(Some(result["t"].as_i64().unwrap()))
+            Ok(Some(json_i64(&result["t"], "t")?))
         } else {
             Ok(Some(ws_msg.time * 1000))
         }
@@ -162,26 +187,24 @@ pub(super) fn extract_timestamp(msg: &str) -> Result<Option<i64>, SimpleError> {
     } else if msg.contains("open_interest") {
         Ok(None)
     } else {
-        Err(SimpleError::new(format!("Unsupported message format  {msg}")))
+        Err(ParseError::unsupported("Unsupported message format"))
     }
 }
 
-pub(super) fn parse_trade(
-    market_type: MarketType,
-    msg: &str,
-) -> Result<Vec<TradeMsg>, SimpleError> {
+pub(super) fn parse_trade(market_type: MarketType, msg: &str) -> Result<Vec<TradeMsg>, ParseError> {
     match market_type {
         MarketType::InverseFuture | MarketType::LinearFuture => {
             let ws_msg = serde_json::from_str::<WebsocketMsg<Vec<FutureTradeMsg>>>(msg)
-                .map_err(SimpleError::from)?;
+                .map_err(ParseError::from)?;
 
             let mut trades: Vec<TradeMsg> = ws_msg
                 .result
                 .into_iter()
-                .map(|raw_trade| {
+                .map(|raw_trade| -> Result<TradeMsg, ParseError> {
                     let symbol = raw_trade.contract.as_str();
-                    let pair = crypto_pair::normalize_pair(symbol, EXCHANGE_NAME).unwrap();
-                    let price = raw_trade.price.parse::<f64>().unwrap();
+                    let pair = crypto_pair::normalize_pair(symbol, EXCHANGE_NAME)
+                        .ok_or_else(|| ParseError::symbol_normalization(symbol))?;
+                    let price = raw_trade.price.parse::<f64>()?;
                     let quantity = f64::abs(raw_trade.size);
                     let (quantity_base, quantity_quote, quantity_contract) =
                         calc_quantity_and_volume(
@@ -190,9 +213,10 @@ pub(super) fn parse_trade(
                             &pair,
                             price,
                             quantity,
-                        );
+                            raw_trade.create_time * 1000,
+                        )?;
 
-                    TradeMsg {
+                    Ok(TradeMsg {
                         exchange: EXCHANGE_NAME.to_string(),
                         market_type,
                         symbol: symbol.to_string(),
@@ -203,12 +227,14 @@ pub(super) fn parse_trade(
                         quantity_base,
                         quantity_quote,
                         quantity_contract,
+                        price_exact: parse_exact(&raw_trade.price),
+                        quantity_exact: None,
                         side: if raw_trade.size < 0.0 { TradeSide::Sell } else { TradeSide::Buy },
                         trade_id: raw_trade.id.to_string(),
                         json: serde_json::to_string(&raw_trade).unwrap(),
-                    }
+                    })
                 })
-                .collect();
+                .collect::<Result<Vec<TradeMsg>, ParseError>>()?;
             if trades.len() == 1 {
                 trades[0].json = msg.to_string();
             }
@@ -216,19 +242,16 @@ pub(super) fn parse_trade(
         }
         MarketType::InverseSwap | MarketType::LinearSwap => {
             let ws_msg = serde_json::from_str::<WebsocketMsg<Vec<SwapTradeMsg>>>(msg)
-                .map_err(SimpleError::from)?;
+                .map_err(ParseError::from)?;
 
             let mut trades: Vec<TradeMsg> = ws_msg
                 .result
                 .into_iter()
-                .map(|raw_trade| {
+                .map(|raw_trade| -> Result<TradeMsg, ParseError> {
                     let symbol = raw_trade.contract.as_str();
-                    let pair = crypto_pair::normalize_pair(symbol, EXCHANGE_NAME).unwrap();
-                    let price = if let Some(p) = raw_trade.price.as_str() {
-                        p.parse::<f64>().unwrap()
-                    } else {
-                        raw_trade.price.as_f64().unwrap()
-                    };
+                    let pair = crypto_pair::normalize_pair(symbol, EXCHANGE_NAME)
+                        .ok_or_else(|| ParseError::symbol_normalization(symbol))?;
+                    let price = json_f64(&raw_trade.price, "price")?;
                     let quantity = f64::abs(raw_trade.size);
                     let (quantity_base, quantity_quote, quantity_contract) =
                         calc_quantity_and_volume(
@@ -237,9 +260,10 @@ pub(super) fn parse_trade(
                             &pair,
                             price,
                             quantity,
-                        );
+                            raw_trade.create_time_ms,
+                        )?;
 
-                    TradeMsg {
+                    Ok(TradeMsg {
                         exchange: EXCHANGE_NAME.to_string(),
                         market_type,
                         symbol: symbol.to_string(),
@@ -250,18 +274,20 @@ pub(super) fn parse_trade(
                         quantity_base,
                         quantity_quote,
                         quantity_contract,
+                        price_exact: json_exact(&raw_trade.price),
+                        quantity_exact: None,
                         side: if raw_trade.size < 0.0 { TradeSide::Sell } else { TradeSide::Buy },
                         trade_id: raw_trade.id.to_string(),
                         json: serde_json::to_string(&raw_trade).unwrap(),
-                    }
+                    })
                 })
-                .collect();
+                .collect::<Result<Vec<TradeMsg>, ParseError>>()?;
             if trades.len() == 1 {
                 trades[0].json = msg.to_string();
             }
             Ok(trades)
         }
-        _ => Err(SimpleError::new(format!("Unknown gate market type {market_type}"))),
+        _ => Err(ParseError::unsupported(format!("Unknown gate market type {market_type}"))),
     }
 }
 
@@ -273,37 +299,51 @@ thread_local! {
 pub(super) fn parse_l2_topk(
     market_type: MarketType,
     msg: &str,
-) -> Result<Vec<OrderBookMsg>, SimpleError> {
-    let ws_msg = serde_json::from_str::<WebsocketMsg<Value>>(msg).map_err(|_e| {
-        SimpleError::new(format!("Failed to deserialize {msg} to WebsocketMsg<Value>"))
-    })?;
-    debug_assert_eq!(ws_msg.channel, "futures.order_book");
+) -> Result<Vec<OrderBookMsg>, ParseError> {
+    let ws_msg = serde_json::from_str::<WebsocketMsg<Value>>(msg)
+        .map_err(|_e| ParseError::malformed("expected WebsocketMsg<Value>"))?;
+    if ws_msg.channel != "futures.order_book" {
+        return Err(ParseError::unsupported(format!("Unsupported channel {}", ws_msg.channel)));
+    }
     let snapshot = ws_msg.event == "all";
 
     let orderbook = if snapshot {
         let raw_orderbook = serde_json::from_value::<RawOrderbookSnapshot>(ws_msg.result.clone())
             .map_err(|_e| {
-            SimpleError::new(format!(
+            ParseError::malformed(format!(
                 "Failed to deserialize {} to RawOrderbookSnapshot",
                 ws_msg.result
             ))
         })?;
         let symbol = raw_orderbook.contract;
         let pair = crypto_pair::normalize_pair(&symbol, EXCHANGE_NAME)
-            .ok_or_else(|| SimpleError::new(format!("Failed to normalize {symbol} from {msg}")))?;
+            .ok_or_else(|| ParseError::symbol_normalization(&symbol))?;
         let timestamp = if market_type != MarketType::LinearFuture {
-            raw_orderbook.t.unwrap()
+            raw_orderbook.t.ok_or_else(ParseError::missing_timestamp)?
         } else {
             ws_msg.time * 1000
         };
 
-        let parse_order = |raw_order: &RawOrderLegacy| -> Order {
-            let price = raw_order.p.parse::<f64>().unwrap();
+        let parse_order = |raw_order: &RawOrderLegacy| -> Result<Order, ParseError> {
+            let price = raw_order.p.parse::<f64>()?;
             let quantity = raw_order.s;
 
-            let (quantity_base, quantity_quote, quantity_contract) =
-                calc_quantity_and_volume(EXCHANGE_NAME, market_type, &pair, price, quantity);
-            Order { price, quantity_base, quantity_quote, quantity_contract }
+            let (quantity_base, quantity_quote, quantity_contract) = calc_quantity_and_volume(
+                EXCHANGE_NAME,
+                market_type,
+                &pair,
+                price,
+                quantity,
+                timestamp,
+            )?;
+            Ok(Order {
+                price,
+                quantity_base,
+                quantity_quote,
+                quantity_contract,
+                price_exact: parse_exact(&raw_order.p),
+                quantity_exact: None,
+            })
         };
 
         OrderBookMsg {
@@ -319,8 +359,16 @@ pub(super) fn parse_l2_topk(
                 MessageType::L2Event
             },
             timestamp,
-            asks: raw_orderbook.asks.iter().map(parse_order).collect(),
-            bids: raw_orderbook.bids.iter().map(parse_order).collect(),
+            asks: raw_orderbook
+                .asks
+                .iter()
+                .map(parse_order)
+                .collect::<Result<Vec<Order>, ParseError>>()?,
+            bids: raw_orderbook
+                .bids
+                .iter()
+                .map(parse_order)
+                .collect::<Result<Vec<Order>, ParseError>>()?,
             seq_id: raw_orderbook.id,
             prev_seq_id: None,
             snapshot,
@@ -329,41 +377,54 @@ pub(super) fn parse_l2_topk(
     } else {
         let raw_orderbook = serde_json::from_value::<Vec<RawOrderLegacy>>(ws_msg.result.clone())
             .map_err(|_e| {
-                SimpleError::new(format!(
+                ParseError::malformed(format!(
                     "Failed to deserialize {} to Vec<RawOrderLegacy>",
                     ws_msg.result
                 ))
             })?;
+        let first =
+            raw_orderbook.first().ok_or_else(|| ParseError::malformed("empty orderbook update"))?;
         let symbol = if market_type == MarketType::LinearFuture {
-            raw_orderbook[0].c.clone().unwrap()
+            first.c.clone()
         } else {
-            raw_orderbook[0].contract.clone().unwrap()
-        };
+            first.contract.clone()
+        }
+        .ok_or_else(|| ParseError::malformed("no contract"))?;
         let pair = crypto_pair::normalize_pair(&symbol, EXCHANGE_NAME)
-            .ok_or_else(|| SimpleError::new(format!("Failed to normalize {symbol} from {msg}")))?;
+            .ok_or_else(|| ParseError::symbol_normalization(&symbol))?;
         let timestamp = ws_msg.time * 1000;
 
-        let parse_order = |raw_order: &RawOrderLegacy| -> Order {
-            let price = raw_order.p.parse::<f64>().unwrap();
+        let parse_order = |raw_order: &RawOrderLegacy| -> Result<Order, ParseError> {
+            let price = raw_order.p.parse::<f64>()?;
             let quantity = f64::abs(raw_order.s);
 
-            let (quantity_base, quantity_quote, quantity_contract) =
-                calc_quantity_and_volume(EXCHANGE_NAME, market_type, &pair, price, quantity);
-            Order { price, quantity_base, quantity_quote, quantity_contract }
+            let (quantity_base, quantity_quote, quantity_contract) = calc_quantity_and_volume(
+                EXCHANGE_NAME,
+                market_type,
+                &pair,
+                price,
+                quantity,
+                timestamp,
+            )?;
+            Ok(Order {
+                price,
+                quantity_base,
+                quantity_quote,
+                quantity_contract,
+                price_exact: parse_exact(&raw_order.p),
+                quantity_exact: None,
+            })
         };
 
-        PRICE_HASHMAP.with(|slf| {
+        PRICE_HASHMAP.with(|slf| -> Result<OrderBookMsg, ParseError> {
             let mut tmp = slf.borrow_mut();
-            if !tmp.contains_key(&symbol) {
-                tmp.insert(symbol.clone(), HashMap::new());
-            }
-            let price_map = tmp.get_mut(&symbol).unwrap();
+            let price_map = tmp.entry(symbol.clone()).or_default();
 
             let mut asks: Vec<Order> = Vec::new();
             let mut bids: Vec<Order> = Vec::new();
             for x in raw_orderbook.iter() {
                 let price = x.p.clone();
-                let order = parse_order(x);
+                let order = parse_order(x)?;
                 if x.s < 0.0 {
                     asks.push(order);
                     price_map.insert(price, true);
@@ -379,7 +440,7 @@ pub(super) fn parse_l2_topk(
                 }
             }
 
-            OrderBookMsg {
+            Ok(OrderBookMsg {
                 exchange: EXCHANGE_NAME.to_string(),
                 market_type,
                 symbol,
@@ -392,8 +453,8 @@ pub(super) fn parse_l2_topk(
                 bids,
                 snapshot,
                 json: msg.to_string(),
-            }
-        })
+            })
+        })?
     };
 
     Ok(vec![orderbook])
@@ -433,27 +494,40 @@ struct RawBboMsg {
     extra: HashMap<String, Value>,
 }
 
-fn parse_order(market_type: MarketType, raw_order: &RawOrderNew, pair: &str) -> Order {
-    let price = raw_order.p.parse::<f64>().unwrap();
+fn parse_order(
+    market_type: MarketType,
+    raw_order: &RawOrderNew,
+    pair: &str,
+    timestamp: i64,
+) -> Result<Order, ParseError> {
+    let price = raw_order.p.parse::<f64>()?;
     let quantity = raw_order.s;
 
     let (quantity_base, quantity_quote, quantity_contract) =
-        calc_quantity_and_volume(EXCHANGE_NAME, market_type, pair, price, quantity);
-    Order { price, quantity_base, quantity_quote, quantity_contract }
+        calc_quantity_and_volume(EXCHANGE_NAME, market_type, pair, price, quantity, timestamp)?;
+    Ok(Order {
+        price,
+        quantity_base,
+        quantity_quote,
+        quantity_contract,
+        price_exact: parse_exact(&raw_order.p),
+        quantity_exact: None,
+    })
 }
 
 pub(super) fn parse_l2(
     market_type: MarketType,
     msg: &str,
-) -> Result<Vec<OrderBookMsg>, SimpleError> {
-    let ws_msg = serde_json::from_str::<WebsocketMsg<OrderbookUpdateMsg>>(msg).map_err(|_e| {
-        SimpleError::new(format!("Failed to deserialize {msg} to WebsocketMsg<OrderbookUpdateMsg>"))
-    })?;
-    debug_assert_eq!(ws_msg.channel, "futures.order_book_update");
+) -> Result<Vec<OrderBookMsg>, ParseError> {
+    let ws_msg = serde_json::from_str::<WebsocketMsg<OrderbookUpdateMsg>>(msg)
+        .map_err(|_e| ParseError::malformed("expected WebsocketMsg<OrderbookUpdateMsg>"))?;
+    if ws_msg.channel != "futures.order_book_update" {
+        return Err(ParseError::unsupported(format!("Unsupported channel {}", ws_msg.channel)));
+    }
     let result = ws_msg.result;
     let symbol = result.s;
     let pair = crypto_pair::normalize_pair(&symbol, EXCHANGE_NAME)
-        .ok_or_else(|| SimpleError::new(format!("Failed to normalize {symbol} from {msg}")))?;
+        .ok_or_else(|| ParseError::symbol_normalization(&symbol))?;
 
     let orderbook = OrderBookMsg {
         exchange: EXCHANGE_NAME.to_string(),
@@ -464,8 +538,16 @@ pub(super) fn parse_l2(
         timestamp: result.t,
         seq_id: result.extra.get("u").and_then(|v| v.as_u64()),
         prev_seq_id: result.extra.get("U").and_then(|v| v.as_u64().map(|v| v - 1)),
-        asks: result.a.iter().map(|x| parse_order(market_type, x, &pair)).collect(),
-        bids: result.b.iter().map(|x| parse_order(market_type, x, &pair)).collect(),
+        asks: result
+            .a
+            .iter()
+            .map(|x| parse_order(market_type, x, &pair, result.t))
+            .collect::<Result<Vec<Order>, ParseError>>()?,
+        bids: result
+            .b
+            .iter()
+            .map(|x| parse_order(market_type, x, &pair, result.t))
+            .collect::<Result<Vec<Order>, ParseError>>()?,
         snapshot: ws_msg.event == "all",
         json: msg.to_string(),
     };
@@ -473,26 +555,43 @@ pub(super) fn parse_l2(
     Ok(vec![orderbook])
 }
 
-pub(super) fn parse_bbo(market_type: MarketType, msg: &str) -> Result<Vec<BboMsg>, SimpleError> {
-    let ws_msg = serde_json::from_str::<WebsocketMsg<RawBboMsg>>(msg).map_err(SimpleError::from)?;
-    debug_assert_eq!("futures.book_ticker", ws_msg.channel);
-    debug_assert_eq!("update", ws_msg.event);
+pub(super) fn parse_bbo(market_type: MarketType, msg: &str) -> Result<Vec<BboMsg>, ParseError> {
+    let ws_msg = serde_json::from_str::<WebsocketMsg<RawBboMsg>>(msg).map_err(ParseError::from)?;
+    if ws_msg.channel != "futures.book_ticker" {
+        return Err(ParseError::unsupported(format!("Unsupported channel {}", ws_msg.channel)));
+    }
+    if ws_msg.event != "update" {
+        return Err(ParseError::unsupported(format!("Unsupported event {}", ws_msg.event)));
+    }
 
     let symbol = ws_msg.result.s.as_str();
-    let pair = crypto_pair::normalize_pair(symbol, EXCHANGE_NAME).unwrap();
+    let pair = crypto_pair::normalize_pair(symbol, EXCHANGE_NAME)
+        .ok_or_else(|| ParseError::symbol_normalization(symbol))?;
     let timestamp = ws_msg.result.t;
 
-    let ask_price = ws_msg.result.a.parse::<f64>().unwrap();
+    let ask_price = ws_msg.result.a.parse::<f64>()?;
     let ask_size = ws_msg.result.A;
 
-    let bid_price = ws_msg.result.b.parse::<f64>().unwrap();
+    let bid_price = ws_msg.result.b.parse::<f64>()?;
     let bid_size = ws_msg.result.B;
 
-    let (ask_quantity_base, ask_quantity_quote, ask_quantity_contract) =
-        calc_quantity_and_volume(EXCHANGE_NAME, market_type, &pair, ask_price, ask_size);
+    let (ask_quantity_base, ask_quantity_quote, ask_quantity_contract) = calc_quantity_and_volume(
+        EXCHANGE_NAME,
+        market_type,
+        &pair,
+        ask_price,
+        ask_size,
+        timestamp,
+    )?;
 
-    let (bid_quantity_base, bid_quantity_quote, bid_quantity_contract) =
-        calc_quantity_and_volume(EXCHANGE_NAME, market_type, &pair, bid_price, bid_size);
+    let (bid_quantity_base, bid_quantity_quote, bid_quantity_contract) = calc_quantity_and_volume(
+        EXCHANGE_NAME,
+        market_type,
+        &pair,
+        bid_price,
+        bid_size,
+        timestamp,
+    )?;
 
     let bbo_msg = BboMsg {
         exchange: EXCHANGE_NAME.to_string(),
@@ -509,6 +608,10 @@ pub(super) fn parse_bbo(market_type: MarketType, msg: &str) -> Result<Vec<BboMsg
         bid_quantity_base,
         bid_quantity_quote,
         bid_quantity_contract,
+        bid_price_exact: parse_exact(&ws_msg.result.b),
+        bid_quantity_exact: None,
+        ask_price_exact: parse_exact(&ws_msg.result.a),
+        ask_quantity_exact: None,
         id: None,
         json: msg.to_string(),
     };
@@ -519,30 +622,43 @@ pub(super) fn parse_bbo(market_type: MarketType, msg: &str) -> Result<Vec<BboMsg
 pub(super) fn parse_candlestick(
     market_type: MarketType,
     msg: &str,
-) -> Result<Vec<CandlestickMsg>, SimpleError> {
+) -> Result<Vec<CandlestickMsg>, ParseError> {
     let ws_msg = serde_json::from_str::<WebsocketMsg<Vec<RawCandlestickMsg>>>(msg)
-        .map_err(SimpleError::from)?;
-    debug_assert_eq!(ws_msg.channel, "futures.candlesticks");
-    debug_assert_eq!(ws_msg.event, "update");
+        .map_err(ParseError::from)?;
+    if ws_msg.channel != "futures.candlesticks" {
+        return Err(ParseError::unsupported(format!("Unsupported channel {}", ws_msg.channel)));
+    }
+    if ws_msg.event != "update" {
+        return Err(ParseError::unsupported(format!("Unsupported event {}", ws_msg.event)));
+    }
     let result = ws_msg.result;
 
     let candlestick_messages = result
         .into_iter()
-        .map(|raw_candlestick| {
+        .map(|raw_candlestick| -> Result<CandlestickMsg, ParseError> {
             let (period, symbol) = {
-                let pos = raw_candlestick.n.find('_').unwrap();
+                let pos = raw_candlestick.n.find('_').ok_or_else(|| {
+                    ParseError::malformed(format!("Invalid n {}", raw_candlestick.n))
+                })?;
                 (&raw_candlestick.n[..pos], &raw_candlestick.n[pos + 1..])
             };
-            let pair = crypto_pair::normalize_pair(symbol, EXCHANGE_NAME).unwrap();
-
-            let open = raw_candlestick.o.parse::<f64>().unwrap();
-            let high = raw_candlestick.h.parse::<f64>().unwrap();
-            let low = raw_candlestick.l.parse::<f64>().unwrap();
-            let close = raw_candlestick.c.parse::<f64>().unwrap();
-
-            let contract_value =
-                crypto_contract_value::get_contract_value(EXCHANGE_NAME, market_type, &pair)
-                    .unwrap();
+            let pair = crypto_pair::normalize_pair(symbol, EXCHANGE_NAME)
+                .ok_or_else(|| ParseError::symbol_normalization(symbol))?;
+            let period = parse_candle_period(EXCHANGE_NAME, market_type, period)?;
+            let begin_time = raw_candlestick.t * 1000;
+
+            let open = raw_candlestick.o.parse::<f64>()?;
+            let high = raw_candlestick.h.parse::<f64>()?;
+            let low = raw_candlestick.l.parse::<f64>()?;
+            let close = raw_candlestick.c.parse::<f64>()?;
+
+            let contract_value = crypto_contract_value::get_contract_value_at(
+                EXCHANGE_NAME,
+                market_type,
+                &pair,
+                ws_msg.time * 1000,
+            )
+            .ok_or_else(|| ParseError::unsupported(format!("No contract value for {pair}")))?;
 
             let (volume, quote_volume) = if market_type == MarketType::InverseFuture
                 || market_type == MarketType::InverseSwap
@@ -555,25 +671,266 @@ pub(super) fn parse_candlestick(
                 (base_volume, None)
             };
 
-            CandlestickMsg {
+            Ok(CandlestickMsg {
                 exchange: EXCHANGE_NAME.to_string(),
                 market_type,
                 msg_type: MessageType::Candlestick,
                 symbol: symbol.to_string(),
                 pair,
                 timestamp: ws_msg.time * 1000,
-                period: period.to_string(),
-                begin_time: raw_candlestick.t,
+                period,
+                begin_time,
+                end_time: period.end_time(begin_time),
                 open,
                 high,
                 low,
                 close,
                 volume,
                 quote_volume,
+                is_closed: raw_candlestick.w,
                 json: msg.to_string(),
-            }
+            })
         })
-        .collect();
+        .collect::<Result<Vec<CandlestickMsg>, ParseError>>()?;
 
     Ok(candlestick_messages)
 }
+
+pub(super) fn parse_ticker(
+    market_type: MarketType,
+    msg: &str,
+) -> Result<Vec<TickerMsg>, ParseError> {
+    let ws_msg = serde_json::from_str::<WebsocketMsg<Vec<RawTickerMsg>>>(msg)
+        .map_err(|_e| ParseError::malformed("expected WebsocketMsg<Vec<RawTickerMsg>>"))?;
+    if ws_msg.channel != "futures.tickers" {
+        return Err(ParseError::unsupported(format!("Unsupported channel {}", ws_msg.channel)));
+    }
+    let parse_f64 = |s: &str| -> Result<f64, ParseError> {
+        s.parse::<f64>().map_err(|_e| ParseError::malformed(format!("Failed to parse {s} as f64")))
+    };
+
+    let mut tickers = Vec::with_capacity(ws_msg.result.len());
+    for raw_ticker in ws_msg.result.into_iter() {
+        let pair = crypto_pair::normalize_pair(&raw_ticker.contract, EXCHANGE_NAME)
+            .ok_or_else(|| ParseError::symbol_normalization(&raw_ticker.contract))?;
+        let close = parse_f64(&raw_ticker.last)?;
+        let (volume, quote_volume, _) = calc_quantity_and_volume(
+            EXCHANGE_NAME,
+            market_type,
+            &pair,
+            close,
+            parse_f64(&raw_ticker.volume_24h)?,
+            ws_msg.time * 1000,
+        )?;
+        let (open_interest, open_interest_quote, _) = calc_quantity_and_volume(
+            EXCHANGE_NAME,
+            market_type,
+            &pair,
+            close,
+            parse_f64(&raw_ticker.total_size)?,
+            ws_msg.time * 1000,
+        )?;
+
+        tickers.push(TickerMsg {
+            exchange: EXCHANGE_NAME.to_string(),
+            market_type,
+            symbol: raw_ticker.contract.clone(),
+            pair,
+            msg_type: MessageType::Ticker,
+            timestamp: ws_msg.time * 1000,
+            open: close / (1.0 + parse_f64(&raw_ticker.change_percentage)? / 100.0),
+            high: parse_f64(&raw_ticker.high_24h)?,
+            low: parse_f64(&raw_ticker.low_24h)?,
+            close,
+            volume,
+            quote_volume,
+            last_quantity: None,
+            best_bid_price: None,
+            best_bid_quantity: None,
+            best_ask_price: None,
+            best_ask_quantity: None,
+            open_interest: Some(open_interest),
+            open_interest_quote: Some(open_interest_quote),
+            json: serde_json::to_string(&raw_ticker).unwrap(),
+        });
+    }
+
+    if tickers.len() == 1 {
+        tickers[0].json = msg.to_string();
+    }
+    Ok(tickers)
+}
+
+// https://www.gate.io/docs/developers/apiv4/en/#futures-stats
+#[derive(Serialize, Deserialize)]
+struct RawContractStatsMsg {
+    time: i64,
+    open_interest: f64, // contracts
+    open_interest_usd: f64,
+    mark_price: f64,
+    #[serde(flatten)]
+    extra: HashMap<String, Value>,
+}
+
+pub(super) fn parse_open_interest(
+    market_type: MarketType,
+    msg: &str,
+    symbol: &str,
+) -> Result<Vec<OpenInterestMsg>, ParseError> {
+    let raw_msgs = serde_json::from_str::<Vec<RawContractStatsMsg>>(msg)
+        .map_err(|_e| ParseError::malformed("expected Vec<RawContractStatsMsg>"))?;
+    let pair = crypto_pair::normalize_pair(symbol, EXCHANGE_NAME)
+        .ok_or_else(|| ParseError::symbol_normalization(symbol))?;
+
+    let mut open_interests = raw_msgs
+        .into_iter()
+        .map(|raw_msg| -> Result<OpenInterestMsg, ParseError> {
+            let timestamp = raw_msg.time * 1000;
+            let (quantity_base, _) = calc_open_interest(
+                EXCHANGE_NAME,
+                market_type,
+                &pair,
+                Some(raw_msg.mark_price),
+                raw_msg.open_interest,
+                timestamp,
+            )?;
+            Ok(OpenInterestMsg {
+                exchange: EXCHANGE_NAME.to_string(),
+                market_type,
+                symbol: symbol.to_string(),
+                pair: pair.clone(),
+                msg_type: MessageType::OpenInterest,
+                timestamp,
+                quantity_contract: raw_msg.open_interest,
+                quantity_base,
+                quantity_quote: Some(raw_msg.open_interest_usd),
+                json: serde_json::to_string(&raw_msg).unwrap(),
+            })
+        })
+        .collect::<Result<Vec<OpenInterestMsg>, ParseError>>()?;
+
+    if open_interests.len() == 1 {
+        open_interests[0].json = msg.to_string();
+    }
+    Ok(open_interests)
+}
+
+// https://www.gate.io/docs/developers/apiv4/en/#list-all-futures-contracts
+// https://www.gate.io/docs/developers/apiv4/en/#get-a-single-contract
+#[derive(Serialize, Deserialize)]
+struct RawContractMsg {
+    name: String,
+    funding_rate: String,
+    funding_rate_indicative: String,
+    funding_next_apply: i64, // seconds
+    #[serde(skip_serializing_if = "Option::is_none")]
+    funding_interval: Option<i64>, // seconds
+    #[serde(flatten)]
+    extra: HashMap<String, Value>,
+}
+
+// Perpetual swaps settle funding every 8 hours
+const FUNDING_INTERVAL: i64 = 8 * 3600 * 1000;
+
+/// Parses the `futures.tickers` channel and contracts from the RESTful API.
+///
+/// Tickers have no funding time, which is the next 8-hour boundary after the
+/// message time. The funding cap of contracts is `(1/leverage_max -
+/// maintenance_rate) * funding_cap_ratio`, see the RESTful API document.
+pub(super) fn parse_funding_rate(
+    market_type: MarketType,
+    msg: &str,
+    received_at: Option<i64>,
+) -> Result<Vec<FundingRateMsg>, ParseError> {
+    let mut rates = if let Ok(ws_msg) = serde_json::from_str::<WebsocketMsg<Vec<RawTickerMsg>>>(msg)
+    {
+        if ws_msg.channel != "futures.tickers" {
+            return Err(ParseError::unsupported(format!("Unsupported channel {}", ws_msg.channel)));
+        }
+        let timestamp = ws_msg.time * 1000;
+        ws_msg
+            .result
+            .into_iter()
+            .map(|raw_ticker| -> Result<FundingRateMsg, ParseError> {
+                let get_f64 = |field: &str| -> Option<f64> {
+                    raw_ticker.extra.get(field)?.as_str()?.parse::<f64>().ok()
+                };
+                let funding_rate = get_f64("funding_rate")
+                    .ok_or_else(|| ParseError::unsupported("Failed to extract funding_rate"))?;
+                let estimated_rate = get_f64("funding_rate_indicative");
+                Ok(FundingRateMsg {
+                    exchange: EXCHANGE_NAME.to_string(),
+                    market_type,
+                    symbol: raw_ticker.contract.clone(),
+                    pair: crypto_pair::normalize_pair(&raw_ticker.contract, EXCHANGE_NAME)
+                        .ok_or_else(|| ParseError::symbol_normalization(&raw_ticker.contract))?,
+                    msg_type: MessageType::FundingRate,
+                    timestamp,
+                    funding_rate,
+                    funding_time: next_funding_time(timestamp, FUNDING_INTERVAL),
+                    estimated_rate,
+                    funding_interval: FUNDING_INTERVAL,
+                    funding_rate_cap: None,
+                    funding_rate_floor: None,
+                    annualized_rate: annualize_funding_rate(funding_rate, FUNDING_INTERVAL),
+                    json: serde_json::to_string(&raw_ticker).unwrap(),
+                })
+            })
+            .collect::<Result<Vec<FundingRateMsg>, ParseError>>()?
+    } else {
+        let raw_msgs = if let Ok(raw_msg) = serde_json::from_str::<RawContractMsg>(msg) {
+            vec![raw_msg]
+        } else {
+            serde_json::from_str::<Vec<RawContractMsg>>(msg)
+                .map_err(|_e| ParseError::malformed("expected Vec<RawContractMsg>"))?
+        };
+        // Contracts from the RESTful API have no timestamp
+        let timestamp = received_at.ok_or_else(ParseError::missing_timestamp)?;
+        raw_msgs
+            .into_iter()
+            .map(|raw_msg| -> Result<FundingRateMsg, ParseError> {
+                let get_f64 = |field: &str| -> Option<f64> {
+                    raw_msg.extra.get(field)?.as_str()?.parse::<f64>().ok()
+                };
+                let funding_rate = raw_msg.funding_rate.parse::<f64>()?;
+                let funding_interval = raw_msg
+                    .funding_interval
+                    .map(|x| x * 1000)
+                    .filter(|x| *x > 0)
+                    .unwrap_or(FUNDING_INTERVAL);
+                let funding_rate_cap = match (
+                    get_f64("funding_cap_ratio"),
+                    get_f64("leverage_max"),
+                    get_f64("maintenance_rate"),
+                ) {
+                    (Some(ratio), Some(leverage_max), Some(maintenance_rate)) => {
+                        Some(round((1.0 / leverage_max - maintenance_rate) * ratio))
+                    }
+                    _ => None,
+                };
+                Ok(FundingRateMsg {
+                    exchange: EXCHANGE_NAME.to_string(),
+                    market_type,
+                    symbol: raw_msg.name.clone(),
+                    pair: crypto_pair::normalize_pair(&raw_msg.name, EXCHANGE_NAME)
+                        .ok_or_else(|| ParseError::symbol_normalization(&raw_msg.name))?,
+                    msg_type: MessageType::FundingRate,
+                    timestamp,
+                    funding_rate,
+                    funding_time: raw_msg.funding_next_apply * 1000,
+                    estimated_rate: Some(raw_msg.funding_rate_indicative.parse::<f64>()?),
+                    funding_interval,
+                    funding_rate_cap,
+                    funding_rate_floor: funding_rate_cap.map(|x| -x),
+                    annualized_rate: annualize_funding_rate(funding_rate, funding_interval),
+                    json: serde_json::to_string(&raw_msg).unwrap(),
+                })
+            })
+            .collect::<Result<Vec<FundingRateMsg>, ParseError>>()?
+    };
+
+    if rates.len() == 1 {
+        rates[0].json = msg.to_string();
+    }
+    Ok(rates)
+}
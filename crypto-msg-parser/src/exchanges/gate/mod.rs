@@ -5,13 +5,14 @@ mod gate_swap;
 mod messages;
 
 use crypto_market_type::MarketType;
-use crypto_message::{BboMsg, CandlestickMsg};
+use crypto_message::{BboMsg, CandlestickMsg, FundingRateMsg, OpenInterestMsg, TickerMsg};
+use crypto_msg_type::MessageType;
+use serde_json::Value;
+use std::collections::HashMap;
 
-use crate::{OrderBookMsg, TradeMsg};
+use crate::{OrderBookMsg, ParseError, TradeMsg};
 
-use simple_error::SimpleError;
-
-pub(crate) fn extract_symbol(market_type: MarketType, msg: &str) -> Result<String, SimpleError> {
+pub(crate) fn extract_symbol(market_type: MarketType, msg: &str) -> Result<String, ParseError> {
     if market_type == MarketType::Spot {
         gate_spot::extract_symbol(msg)
     } else {
@@ -22,7 +23,7 @@ pub(crate) fn extract_symbol(market_type: MarketType, msg: &str) -> Result<Strin
 pub(crate) fn extract_timestamp(
     market_type: MarketType,
     msg: &str,
-) -> Result<Option<i64>, SimpleError> {
+) -> Result<Option<i64>, ParseError> {
     if market_type == MarketType::Spot {
         gate_spot::extract_timestamp(msg)
     } else {
@@ -30,10 +31,71 @@ pub(crate) fn extract_timestamp(
     }
 }
 
-pub(crate) fn parse_trade(
-    market_type: MarketType,
-    msg: &str,
-) -> Result<Vec<TradeMsg>, SimpleError> {
+pub(crate) fn get_msg_type(msg: &str) -> MessageType {
+    if let Ok(json_obj) = serde_json::from_str::<HashMap<String, Value>>(msg) {
+        if let Some(channel) = json_obj.get("channel").and_then(|x| x.as_str()) {
+            let event = json_obj.get("event").and_then(|x| x.as_str()).unwrap_or_default();
+            if event != "update" && event != "all" {
+                // subscribe and unsubscribe responses
+                return MessageType::Other;
+            }
+            match channel {
+                "spot.trades" | "futures.trades" => MessageType::Trade,
+                "spot.order_book_update" | "futures.order_book_update" => MessageType::L2Event,
+                "spot.order_book" => MessageType::L2TopK,
+                "futures.order_book" => {
+                    // Delivery futures have no order_book_update channel, their
+                    // order_book channel sends a full snapshot followed by updates
+                    let contract = json_obj
+                        .get("result")
+                        .and_then(|x| x.get("contract"))
+                        .and_then(|x| x.as_str())
+                        .unwrap_or_default();
+                    let is_delivery = contract.rsplit('_').next().is_some_and(|suffix| {
+                        suffix.len() == 8 && suffix.chars().all(|c| c.is_ascii_digit())
+                    });
+                    if event == "update" || is_delivery {
+                        MessageType::L2Event
+                    } else {
+                        MessageType::L2TopK
+                    }
+                }
+                "spot.book_ticker" | "futures.book_ticker" => MessageType::BBO,
+                "spot.tickers" | "futures.tickers" => MessageType::Ticker,
+                "spot.candlesticks" | "futures.candlesticks" => MessageType::Candlestick,
+                _ => MessageType::Other,
+            }
+        } else if let Some(method) = json_obj.get("method").and_then(|x| x.as_str()) {
+            // the spot websocket API before 2021-09-16
+            match method {
+                "trades.update" => MessageType::Trade,
+                "depth.update" => MessageType::L2Event,
+                "ticker.update" => MessageType::Ticker,
+                "kline.update" => MessageType::Candlestick,
+                _ => MessageType::Other,
+            }
+        } else if json_obj.contains_key("asks") && json_obj.contains_key("bids") {
+            MessageType::L2Snapshot
+        } else if json_obj.contains_key("funding_next_apply") {
+            MessageType::FundingRate
+        } else {
+            MessageType::Other
+        }
+    } else if let Ok(arr) = serde_json::from_str::<Vec<HashMap<String, Value>>>(msg) {
+        // RESTful contract stats and contracts
+        if arr.first().is_some_and(|x| x.contains_key("open_interest")) {
+            MessageType::OpenInterest
+        } else if arr.first().is_some_and(|x| x.contains_key("funding_next_apply")) {
+            MessageType::FundingRate
+        } else {
+            MessageType::Other
+        }
+    } else {
+        MessageType::Other
+    }
+}
+
+pub(crate) fn parse_trade(market_type: MarketType, msg: &str) -> Result<Vec<TradeMsg>, ParseError> {
     if market_type == MarketType::Spot {
         gate_spot::parse_trade(msg)
     } else {
@@ -45,24 +107,24 @@ pub(crate) fn parse_l2(
     market_type: MarketType,
     msg: &str,
     timestamp: Option<i64>,
-) -> Result<Vec<OrderBookMsg>, SimpleError> {
+) -> Result<Vec<OrderBookMsg>, ParseError> {
     match market_type {
-        MarketType::Spot => gate_spot::parse_l2(
-            msg,
-            timestamp.expect("Gate spot orderbook messages don't have timestamp"),
-        ),
+        // Gate spot orderbook messages don't have timestamp
+        MarketType::Spot => {
+            gate_spot::parse_l2(msg, timestamp.ok_or_else(ParseError::missing_timestamp)?)
+        }
         MarketType::InverseFuture | MarketType::LinearFuture => {
             gate_swap::parse_l2_topk(market_type, msg)
         }
         MarketType::InverseSwap | MarketType::LinearSwap => gate_swap::parse_l2(market_type, msg),
-        _ => Err(SimpleError::new(format!("Unsupported market type: {market_type:?}"))),
+        _ => Err(ParseError::unsupported(format!("Unsupported market type: {market_type:?}"))),
     }
 }
 
 pub(crate) fn parse_l2_topk(
     market_type: MarketType,
     msg: &str,
-) -> Result<Vec<OrderBookMsg>, SimpleError> {
+) -> Result<Vec<OrderBookMsg>, ParseError> {
     if market_type == MarketType::Spot {
         gate_spot::parse_l2_topk(msg)
     } else {
@@ -70,7 +132,7 @@ pub(crate) fn parse_l2_topk(
     }
 }
 
-pub(crate) fn parse_bbo(market_type: MarketType, msg: &str) -> Result<Vec<BboMsg>, SimpleError> {
+pub(crate) fn parse_bbo(market_type: MarketType, msg: &str) -> Result<Vec<BboMsg>, ParseError> {
     if market_type == MarketType::Spot {
         gate_spot::parse_bbo(msg)
     } else {
@@ -78,13 +140,52 @@ pub(crate) fn parse_bbo(market_type: MarketType, msg: &str) -> Result<Vec<BboMsg
     }
 }
 
+pub(crate) fn parse_ticker(
+    market_type: MarketType,
+    msg: &str,
+) -> Result<Vec<TickerMsg>, ParseError> {
+    if market_type == MarketType::Spot {
+        gate_spot::parse_ticker(msg)
+    } else {
+        gate_swap::parse_ticker(market_type, msg)
+    }
+}
+
 pub(crate) fn parse_candlestick(
     market_type: MarketType,
     msg: &str,
-) -> Result<Vec<CandlestickMsg>, SimpleError> {
+) -> Result<Vec<CandlestickMsg>, ParseError> {
     if market_type == MarketType::Spot {
         gate_spot::parse_candlestick(msg)
     } else {
         gate_swap::parse_candlestick(market_type, msg)
     }
 }
+
+/// Contract stats from the RESTful API have no symbol in the message.
+pub(crate) fn parse_open_interest(
+    market_type: MarketType,
+    msg: &str,
+    symbol: Option<&str>,
+) -> Result<Vec<OpenInterestMsg>, ParseError> {
+    if market_type == MarketType::Spot {
+        Err(ParseError::unsupported("Spot markets have no open interest"))
+    } else {
+        let symbol = symbol.ok_or_else(|| ParseError::unsupported("symbol is required"))?;
+        gate_swap::parse_open_interest(market_type, msg, symbol)
+    }
+}
+
+/// Contracts from the RESTful API have no timestamp, so `received_at` is
+/// required for them.
+pub(crate) fn parse_funding_rate(
+    market_type: MarketType,
+    msg: &str,
+    received_at: Option<i64>,
+) -> Result<Vec<FundingRateMsg>, ParseError> {
+    if market_type == MarketType::Spot {
+        Err(ParseError::unsupported("Spot markets have no funding rate"))
+    } else {
+        gate_swap::parse_funding_rate(market_type, msg, received_at)
+    }
+}
@@ -1,11 +1,10 @@
 use crypto_market_type::MarketType;
 use crypto_msg_type::MessageType;
 
-use crate::FundingRateMsg;
+use crate::{FundingRateMsg, ParseError, exchanges::utils::annualize_funding_rate};
 
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
-use simple_error::SimpleError;
 use std::collections::HashMap;
 
 #[derive(Serialize, Deserialize)]
@@ -30,28 +29,39 @@ pub(super) struct WebsocketMsg {
     data: Vec<RawFundingRateMsg>,
 }
 
+// Perpetual swaps settle funding every 8 hours
+const FUNDING_INTERVAL: i64 = 8 * 3600 * 1000;
+
 pub(super) fn parse_funding_rate(
     market_type: MarketType,
     msg: &str,
-) -> Result<Vec<FundingRateMsg>, SimpleError> {
+) -> Result<Vec<FundingRateMsg>, ParseError> {
     let ws_msg = serde_json::from_str::<WebsocketMsg>(msg)
-        .map_err(|_e| SimpleError::new(format!("Failed to deserialize {msg} to WebsocketMsg")))?;
+        .map_err(|_e| ParseError::malformed("expected WebsocketMsg"))?;
     let mut funding_rates: Vec<FundingRateMsg> = ws_msg
         .data
         .into_iter()
-        .map(|raw_msg| FundingRateMsg {
-            exchange: "huobi".to_string(),
-            market_type,
-            symbol: raw_msg.contract_code.clone(),
-            pair: crypto_pair::normalize_pair(&raw_msg.contract_code, "huobi").unwrap(),
-            msg_type: MessageType::FundingRate,
-            timestamp: ws_msg.ts,
-            funding_rate: raw_msg.funding_rate.parse::<f64>().unwrap(),
-            funding_time: raw_msg.settlement_time.parse::<i64>().unwrap(),
-            estimated_rate: Some(raw_msg.estimated_rate.parse::<f64>().unwrap()),
-            json: serde_json::to_string(&raw_msg).unwrap(),
+        .map(|raw_msg| -> Result<FundingRateMsg, ParseError> {
+            let funding_rate = raw_msg.funding_rate.parse::<f64>()?;
+            Ok(FundingRateMsg {
+                exchange: "huobi".to_string(),
+                market_type,
+                symbol: raw_msg.contract_code.clone(),
+                pair: crypto_pair::normalize_pair(&raw_msg.contract_code, "huobi")
+                    .ok_or_else(|| ParseError::symbol_normalization(&raw_msg.contract_code))?,
+                msg_type: MessageType::FundingRate,
+                timestamp: ws_msg.ts,
+                funding_rate,
+                funding_time: raw_msg.settlement_time.parse::<i64>()?,
+                estimated_rate: Some(raw_msg.estimated_rate.parse::<f64>()?),
+                funding_interval: FUNDING_INTERVAL,
+                funding_rate_cap: None,
+                funding_rate_floor: None,
+                annualized_rate: annualize_funding_rate(funding_rate, FUNDING_INTERVAL),
+                json: serde_json::to_string(&raw_msg).unwrap(),
+            })
         })
-        .collect();
+        .collect::<Result<Vec<FundingRateMsg>, ParseError>>()?;
     if funding_rates.len() == 1 {
         funding_rates[0].json = msg.to_string();
     }
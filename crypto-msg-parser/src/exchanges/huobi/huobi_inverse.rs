@@ -1,13 +1,15 @@
 use crypto_market_type::MarketType;
 use crypto_msg_type::MessageType;
 
-use crate::exchanges::utils::{calc_quantity_and_volume, deserialize_null_default};
+use crate::{
+    ParseError,
+    exchanges::utils::{calc_quantity_and_volume, deserialize_null_default, parse_candle_period},
+};
 use crypto_message::{BboMsg, CandlestickMsg, Order, OrderBookMsg, TradeMsg, TradeSide};
 
-use super::message::WebsocketMsg;
+use super::message::{WebsocketMsg, symbol_of_channel};
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
-use simple_error::SimpleError;
 use std::collections::HashMap;
 
 const EXCHANGE_NAME: &str = "huobi";
@@ -92,29 +94,27 @@ struct RawCandlestickMsg {
     extra: HashMap<String, Value>,
 }
 
-pub(crate) fn parse_trade(
-    market_type: MarketType,
-    msg: &str,
-) -> Result<Vec<TradeMsg>, SimpleError> {
-    let ws_msg = serde_json::from_str::<WebsocketMsg<TradeTick>>(msg).map_err(SimpleError::from)?;
+pub(crate) fn parse_trade(market_type: MarketType, msg: &str) -> Result<Vec<TradeMsg>, ParseError> {
+    let ws_msg = serde_json::from_str::<WebsocketMsg<TradeTick>>(msg).map_err(ParseError::from)?;
 
-    let symbol = ws_msg.ch.split('.').nth(1).unwrap();
+    let symbol = symbol_of_channel(&ws_msg.ch)?;
     let pair = crypto_pair::normalize_pair(symbol, EXCHANGE_NAME)
-        .ok_or_else(|| SimpleError::new(format!("Failed to normalize {symbol} from {msg}")))?;
+        .ok_or_else(|| ParseError::symbol_normalization(symbol))?;
 
     let mut trades: Vec<TradeMsg> = ws_msg
         .tick
         .data
         .into_iter()
-        .map(|raw_trade| {
+        .map(|raw_trade| -> Result<TradeMsg, ParseError> {
             let (_, quantity_quote, _) = calc_quantity_and_volume(
                 EXCHANGE_NAME,
                 market_type,
                 &pair,
                 raw_trade.price,
                 raw_trade.amount,
-            );
-            TradeMsg {
+                raw_trade.ts,
+            )?;
+            Ok(TradeMsg {
                 exchange: EXCHANGE_NAME.to_string(),
                 market_type,
                 symbol: symbol.to_string(),
@@ -125,12 +125,14 @@ pub(crate) fn parse_trade(
                 quantity_base: raw_trade.quantity,
                 quantity_quote,
                 quantity_contract: Some(raw_trade.amount),
+                price_exact: None,
+                quantity_exact: None,
                 side: if raw_trade.direction == "sell" { TradeSide::Sell } else { TradeSide::Buy },
                 trade_id: raw_trade.id.to_string(),
                 json: serde_json::to_string(&raw_trade).unwrap(),
-            }
+            })
         })
-        .collect();
+        .collect::<Result<Vec<TradeMsg>, ParseError>>()?;
 
     if trades.len() == 1 {
         trades[0].json = msg.to_string();
@@ -138,24 +140,36 @@ pub(crate) fn parse_trade(
     Ok(trades)
 }
 
-fn parse_order(market_type: MarketType, pair: &str, raw_order: &[f64; 2]) -> Order {
+fn parse_order(
+    market_type: MarketType,
+    pair: &str,
+    raw_order: &[f64; 2],
+    timestamp: i64,
+) -> Result<Order, ParseError> {
     let price = raw_order[0];
     let quantity = raw_order[1];
 
     let (quantity_base, quantity_quote, quantity_contract) =
-        calc_quantity_and_volume(EXCHANGE_NAME, market_type, pair, price, quantity);
-    Order { price, quantity_base, quantity_quote, quantity_contract }
+        calc_quantity_and_volume(EXCHANGE_NAME, market_type, pair, price, quantity, timestamp)?;
+    Ok(Order {
+        price,
+        quantity_base,
+        quantity_quote,
+        quantity_contract,
+        price_exact: None,
+        quantity_exact: None,
+    })
 }
 
 pub(crate) fn parse_l2(
     market_type: MarketType,
     msg: &str,
-) -> Result<Vec<OrderBookMsg>, SimpleError> {
-    let ws_msg = serde_json::from_str::<WebsocketMsg<InverseOrderbookMsg>>(msg)
-        .map_err(SimpleError::from)?;
-    let symbol = ws_msg.ch.split('.').nth(1).unwrap();
+) -> Result<Vec<OrderBookMsg>, ParseError> {
+    let ws_msg =
+        serde_json::from_str::<WebsocketMsg<InverseOrderbookMsg>>(msg).map_err(ParseError::from)?;
+    let symbol = symbol_of_channel(&ws_msg.ch)?;
     let pair = crypto_pair::normalize_pair(symbol, EXCHANGE_NAME)
-        .ok_or_else(|| SimpleError::new(format!("Failed to normalize {symbol} from {msg}")))?;
+        .ok_or_else(|| ParseError::symbol_normalization(symbol))?;
     let timestamp = ws_msg.ts;
 
     let msg_type = if ws_msg.ch.ends_with(".high_freq") {
@@ -163,11 +177,11 @@ pub(crate) fn parse_l2(
     } else if ws_msg.ch.contains(".depth.step") {
         MessageType::L2TopK
     } else {
-        panic!("Unsupported channel {}", ws_msg.ch);
+        return Err(ParseError::unsupported(format!("Unsupported channel {}", ws_msg.ch)));
     };
 
     let snapshot = if msg_type == MessageType::L2Event {
-        ws_msg.tick.event.unwrap() == "snapshot"
+        ws_msg.tick.event.as_deref() == Some("snapshot")
     } else {
         true
     };
@@ -181,8 +195,18 @@ pub(crate) fn parse_l2(
         timestamp,
         seq_id: Some(ws_msg.tick.mrid),
         prev_seq_id: None,
-        asks: ws_msg.tick.asks.iter().map(|x| parse_order(market_type, &pair, x)).collect(),
-        bids: ws_msg.tick.bids.iter().map(|x| parse_order(market_type, &pair, x)).collect(),
+        asks: ws_msg
+            .tick
+            .asks
+            .iter()
+            .map(|x| parse_order(market_type, &pair, x, timestamp))
+            .collect::<Result<Vec<Order>, ParseError>>()?,
+        bids: ws_msg
+            .tick
+            .bids
+            .iter()
+            .map(|x| parse_order(market_type, &pair, x, timestamp))
+            .collect::<Result<Vec<Order>, ParseError>>()?,
         snapshot,
         json: msg.to_string(),
     };
@@ -190,15 +214,17 @@ pub(crate) fn parse_l2(
     Ok(vec![orderbook])
 }
 
-pub(super) fn parse_bbo(market_type: MarketType, msg: &str) -> Result<Vec<BboMsg>, SimpleError> {
-    let ws_msg = serde_json::from_str::<WebsocketMsg<RawBboMsg>>(msg).map_err(SimpleError::from)?;
-    debug_assert!(ws_msg.ch.ends_with(".bbo"));
-    let symbol = ws_msg.ch.split('.').nth(1).unwrap();
+pub(super) fn parse_bbo(market_type: MarketType, msg: &str) -> Result<Vec<BboMsg>, ParseError> {
+    let ws_msg = serde_json::from_str::<WebsocketMsg<RawBboMsg>>(msg).map_err(ParseError::from)?;
+    if !ws_msg.ch.ends_with(".bbo") {
+        return Err(ParseError::unsupported(format!("Unsupported channel {}", ws_msg.ch)));
+    }
+    let symbol = symbol_of_channel(&ws_msg.ch)?;
     let pair = crypto_pair::normalize_pair(symbol, EXCHANGE_NAME)
-        .ok_or_else(|| SimpleError::new(format!("Failed to normalize {symbol} from {msg}")))?;
+        .ok_or_else(|| ParseError::symbol_normalization(symbol))?;
 
-    let best_ask = parse_order(market_type, &pair, &ws_msg.tick.ask);
-    let best_bid = parse_order(market_type, &pair, &ws_msg.tick.bid);
+    let best_ask = parse_order(market_type, &pair, &ws_msg.tick.ask, ws_msg.ts)?;
+    let best_bid = parse_order(market_type, &pair, &ws_msg.tick.bid, ws_msg.ts)?;
 
     let bbo_msg = BboMsg {
         exchange: EXCHANGE_NAME.to_string(),
@@ -215,6 +241,10 @@ pub(super) fn parse_bbo(market_type: MarketType, msg: &str) -> Result<Vec<BboMsg
         bid_quantity_base: best_bid.quantity_base,
         bid_quantity_quote: best_bid.quantity_quote,
         bid_quantity_contract: best_bid.quantity_contract,
+        bid_price_exact: None,
+        bid_quantity_exact: None,
+        ask_price_exact: None,
+        ask_quantity_exact: None,
         id: Some(ws_msg.tick.mrid),
         json: msg.to_string(),
     };
@@ -225,22 +255,33 @@ pub(super) fn parse_bbo(market_type: MarketType, msg: &str) -> Result<Vec<BboMsg
 pub(super) fn parse_candlestick(
     market_type: MarketType,
     msg: &str,
-) -> Result<Vec<CandlestickMsg>, SimpleError> {
+) -> Result<Vec<CandlestickMsg>, ParseError> {
     let ws_msg =
-        serde_json::from_str::<WebsocketMsg<RawCandlestickMsg>>(msg).map_err(SimpleError::from)?;
-    debug_assert!(ws_msg.ch.contains(".kline."));
+        serde_json::from_str::<WebsocketMsg<RawCandlestickMsg>>(msg).map_err(ParseError::from)?;
+    if !ws_msg.ch.contains(".kline.") {
+        return Err(ParseError::unsupported(format!("Unsupported channel {}", ws_msg.ch)));
+    }
 
     let (symbol, period) = {
         let arr: Vec<&str> = ws_msg.ch.split('.').collect();
+        if arr.len() != 4 {
+            return Err(ParseError::unsupported(format!("Invalid channel {}", ws_msg.ch)));
+        }
         let symbol = arr[1];
-        let period = arr[3];
+        let period = parse_candle_period(EXCHANGE_NAME, market_type, arr[3])?;
         (symbol, period)
     };
-    let pair = crypto_pair::normalize_pair(symbol, EXCHANGE_NAME).unwrap();
+    let pair = crypto_pair::normalize_pair(symbol, EXCHANGE_NAME)
+        .ok_or_else(|| ParseError::symbol_normalization(symbol))?;
 
     let quote_volume = {
-        let contract_value =
-            crypto_contract_value::get_contract_value(EXCHANGE_NAME, market_type, &pair).unwrap();
+        let contract_value = crypto_contract_value::get_contract_value_at(
+            EXCHANGE_NAME,
+            market_type,
+            &pair,
+            ws_msg.ts,
+        )
+        .ok_or_else(|| ParseError::unsupported(format!("No contract value for {pair}")))?;
         contract_value * ws_msg.tick.vol
     };
 
@@ -251,14 +292,16 @@ pub(super) fn parse_candlestick(
         symbol: symbol.to_string(),
         pair,
         timestamp: ws_msg.ts,
-        begin_time: ws_msg.tick.id,
+        begin_time: ws_msg.tick.id * 1000,
+        end_time: period.end_time(ws_msg.tick.id * 1000),
         open: ws_msg.tick.open,
         high: ws_msg.tick.high,
         low: ws_msg.tick.low,
         close: ws_msg.tick.close,
         volume: ws_msg.tick.amount,
         quote_volume: Some(quote_volume),
-        period: period.to_string(),
+        period,
+        is_closed: None,
         json: msg.to_string(),
     };
 
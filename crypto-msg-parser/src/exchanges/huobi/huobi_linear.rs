@@ -5,12 +5,14 @@ use crypto_message::{CandlestickMsg, TradeMsg, TradeSide};
 
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
-use simple_error::SimpleError;
 use std::collections::HashMap;
 
-use crate::exchanges::utils::calc_quantity_and_volume;
+use crate::{
+    ParseError,
+    exchanges::utils::{calc_quantity_and_volume, parse_candle_period},
+};
 
-use super::message::WebsocketMsg;
+use super::message::{WebsocketMsg, symbol_of_channel};
 
 const EXCHANGE_NAME: &str = "huobi";
 
@@ -55,29 +57,27 @@ struct RawCandlestickMsg {
     extra: HashMap<String, Value>,
 }
 
-pub(crate) fn parse_trade(
-    market_type: MarketType,
-    msg: &str,
-) -> Result<Vec<TradeMsg>, SimpleError> {
-    let ws_msg = serde_json::from_str::<WebsocketMsg<TradeTick>>(msg).map_err(SimpleError::from)?;
+pub(crate) fn parse_trade(market_type: MarketType, msg: &str) -> Result<Vec<TradeMsg>, ParseError> {
+    let ws_msg = serde_json::from_str::<WebsocketMsg<TradeTick>>(msg).map_err(ParseError::from)?;
 
-    let symbol = ws_msg.ch.split('.').nth(1).unwrap();
+    let symbol = symbol_of_channel(&ws_msg.ch)?;
     let pair = crypto_pair::normalize_pair(symbol, EXCHANGE_NAME)
-        .ok_or_else(|| SimpleError::new(format!("Failed to normalize {symbol} from {msg}")))?;
+        .ok_or_else(|| ParseError::symbol_normalization(symbol))?;
 
     let mut trades: Vec<TradeMsg> = ws_msg
         .tick
         .data
         .into_iter()
-        .map(|raw_trade| {
+        .map(|raw_trade| -> Result<TradeMsg, ParseError> {
             let (_, quantity_quote, _) = calc_quantity_and_volume(
                 EXCHANGE_NAME,
                 market_type,
                 &pair,
                 raw_trade.price,
                 raw_trade.amount,
-            );
-            TradeMsg {
+                raw_trade.ts,
+            )?;
+            Ok(TradeMsg {
                 exchange: EXCHANGE_NAME.to_string(),
                 market_type,
                 symbol: symbol.to_string(),
@@ -92,12 +92,14 @@ pub(crate) fn parse_trade(
                     quantity_quote
                 },
                 quantity_contract: Some(raw_trade.amount),
+                price_exact: None,
+                quantity_exact: None,
                 side: if raw_trade.direction == "sell" { TradeSide::Sell } else { TradeSide::Buy },
                 trade_id: raw_trade.id.to_string(),
                 json: serde_json::to_string(&raw_trade).unwrap(),
-            }
+            })
         })
-        .collect();
+        .collect::<Result<Vec<TradeMsg>, ParseError>>()?;
 
     if trades.len() == 1 {
         trades[0].json = msg.to_string();
@@ -108,18 +110,24 @@ pub(crate) fn parse_trade(
 pub(super) fn parse_candlestick(
     market_type: MarketType,
     msg: &str,
-) -> Result<Vec<CandlestickMsg>, SimpleError> {
+) -> Result<Vec<CandlestickMsg>, ParseError> {
     let ws_msg =
-        serde_json::from_str::<WebsocketMsg<RawCandlestickMsg>>(msg).map_err(SimpleError::from)?;
-    debug_assert!(ws_msg.ch.contains(".kline."));
+        serde_json::from_str::<WebsocketMsg<RawCandlestickMsg>>(msg).map_err(ParseError::from)?;
+    if !ws_msg.ch.contains(".kline.") {
+        return Err(ParseError::unsupported(format!("Unsupported channel {}", ws_msg.ch)));
+    }
 
     let (symbol, period) = {
         let arr: Vec<&str> = ws_msg.ch.split('.').collect();
+        if arr.len() != 4 {
+            return Err(ParseError::unsupported(format!("Invalid channel {}", ws_msg.ch)));
+        }
         let symbol = arr[1];
-        let period = arr[3];
+        let period = parse_candle_period(EXCHANGE_NAME, market_type, arr[3])?;
         (symbol, period)
     };
-    let pair = crypto_pair::normalize_pair(symbol, EXCHANGE_NAME).unwrap();
+    let pair = crypto_pair::normalize_pair(symbol, EXCHANGE_NAME)
+        .ok_or_else(|| ParseError::symbol_normalization(symbol))?;
 
     let kline_msg = CandlestickMsg {
         exchange: EXCHANGE_NAME.to_string(),
@@ -128,14 +136,16 @@ pub(super) fn parse_candlestick(
         symbol: symbol.to_string(),
         pair,
         timestamp: ws_msg.ts,
-        begin_time: ws_msg.tick.id,
+        begin_time: ws_msg.tick.id * 1000,
+        end_time: period.end_time(ws_msg.tick.id * 1000),
         open: ws_msg.tick.open,
         high: ws_msg.tick.high,
         low: ws_msg.tick.low,
         close: ws_msg.tick.close,
         volume: ws_msg.tick.amount,
         quote_volume: Some(ws_msg.tick.trade_turnover),
-        period: period.to_string(),
+        period,
+        is_closed: None,
         json: msg.to_string(),
     };
 
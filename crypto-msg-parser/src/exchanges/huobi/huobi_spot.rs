@@ -3,12 +3,12 @@ use crypto_msg_type::MessageType;
 
 use crypto_message::{BboMsg, CandlestickMsg, Order, OrderBookMsg, TradeMsg, TradeSide};
 
+use crate::{ParseError, exchanges::utils::parse_candle_period};
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
-use simple_error::SimpleError;
 use std::collections::HashMap;
 
-use super::message::WebsocketMsg;
+use super::message::{WebsocketMsg, symbol_of_channel};
 
 const EXCHANGE_NAME: &str = "huobi";
 
@@ -77,14 +77,13 @@ struct RawCandlestickMsg {
     extra: HashMap<String, Value>,
 }
 
-pub(super) fn parse_trade(msg: &str) -> Result<Vec<TradeMsg>, SimpleError> {
-    let ws_msg = serde_json::from_str::<WebsocketMsg<TradeTick>>(msg).map_err(|_e| {
-        SimpleError::new(format!("Failed to deserialize {msg} to WebsocketMsg<TradeTick>"))
-    })?;
+pub(super) fn parse_trade(msg: &str) -> Result<Vec<TradeMsg>, ParseError> {
+    let ws_msg = serde_json::from_str::<WebsocketMsg<TradeTick>>(msg)
+        .map_err(|_e| ParseError::malformed("expected WebsocketMsg<TradeTick>"))?;
 
-    let symbol = ws_msg.ch.split('.').nth(1).unwrap();
+    let symbol = symbol_of_channel(&ws_msg.ch)?;
     let pair = crypto_pair::normalize_pair(symbol, EXCHANGE_NAME)
-        .ok_or_else(|| SimpleError::new(format!("Failed to normalize {symbol} from {msg}")))?;
+        .ok_or_else(|| ParseError::symbol_normalization(symbol))?;
 
     let mut trades: Vec<TradeMsg> = ws_msg
         .tick
@@ -101,6 +100,8 @@ pub(super) fn parse_trade(msg: &str) -> Result<Vec<TradeMsg>, SimpleError> {
             quantity_base: raw_trade.amount,
             quantity_quote: raw_trade.price * raw_trade.amount,
             quantity_contract: None,
+            price_exact: None,
+            quantity_exact: None,
             side: if raw_trade.direction == "sell" { TradeSide::Sell } else { TradeSide::Buy },
             trade_id: raw_trade.tradeId.to_string(),
             json: serde_json::to_string(&raw_trade).unwrap(),
@@ -113,13 +114,12 @@ pub(super) fn parse_trade(msg: &str) -> Result<Vec<TradeMsg>, SimpleError> {
     Ok(trades)
 }
 
-pub(crate) fn parse_l2(msg: &str) -> Result<Vec<OrderBookMsg>, SimpleError> {
-    let ws_msg = serde_json::from_str::<WebsocketMsg<SpotOrderbookMsg>>(msg).map_err(|_e| {
-        SimpleError::new(format!("Failed to deserialize {msg} to WebsocketMsg<SpotOrderbookMsg>"))
-    })?;
-    let symbol = ws_msg.ch.split('.').nth(1).unwrap();
+pub(crate) fn parse_l2(msg: &str) -> Result<Vec<OrderBookMsg>, ParseError> {
+    let ws_msg = serde_json::from_str::<WebsocketMsg<SpotOrderbookMsg>>(msg)
+        .map_err(|_e| ParseError::malformed("expected WebsocketMsg<SpotOrderbookMsg>"))?;
+    let symbol = symbol_of_channel(&ws_msg.ch)?;
     let pair = crypto_pair::normalize_pair(symbol, EXCHANGE_NAME)
-        .ok_or_else(|| SimpleError::new(format!("Failed to normalize {symbol} from {msg}")))?;
+        .ok_or_else(|| ParseError::symbol_normalization(symbol))?;
     let timestamp = ws_msg.ts;
 
     let parse_order = |raw_order: &[f64; 2]| -> Order {
@@ -131,6 +131,8 @@ pub(crate) fn parse_l2(msg: &str) -> Result<Vec<OrderBookMsg>, SimpleError> {
             quantity_base,
             quantity_quote: price * quantity_base,
             quantity_contract: None,
+            price_exact: None,
+            quantity_exact: None,
         }
     };
     let msg_type = if ws_msg.ch.contains(".mbp.") {
@@ -138,7 +140,7 @@ pub(crate) fn parse_l2(msg: &str) -> Result<Vec<OrderBookMsg>, SimpleError> {
     } else if ws_msg.ch.contains(".depth.step") {
         MessageType::L2TopK
     } else {
-        panic!("Unsupported channel {}", ws_msg.ch);
+        return Err(ParseError::unsupported(format!("Unsupported channel {}", ws_msg.ch)));
     };
 
     let orderbook = OrderBookMsg {
@@ -159,13 +161,15 @@ pub(crate) fn parse_l2(msg: &str) -> Result<Vec<OrderBookMsg>, SimpleError> {
     Ok(vec![orderbook])
 }
 
-pub(super) fn parse_bbo(msg: &str) -> Result<Vec<BboMsg>, SimpleError> {
-    let ws_msg = serde_json::from_str::<WebsocketMsg<RawBboMsg>>(msg).map_err(SimpleError::from)?;
-    debug_assert!(ws_msg.ch.ends_with(".bbo"));
+pub(super) fn parse_bbo(msg: &str) -> Result<Vec<BboMsg>, ParseError> {
+    let ws_msg = serde_json::from_str::<WebsocketMsg<RawBboMsg>>(msg).map_err(ParseError::from)?;
+    if !ws_msg.ch.ends_with(".bbo") {
+        return Err(ParseError::unsupported(format!("Unsupported channel {}", ws_msg.ch)));
+    }
 
-    let symbol = ws_msg.ch.split('.').nth(1).unwrap();
+    let symbol = symbol_of_channel(&ws_msg.ch)?;
     let pair = crypto_pair::normalize_pair(symbol, EXCHANGE_NAME)
-        .ok_or_else(|| SimpleError::new(format!("Failed to normalize {symbol} from {msg}")))?;
+        .ok_or_else(|| ParseError::symbol_normalization(symbol))?;
 
     let bbo_msg = BboMsg {
         exchange: EXCHANGE_NAME.to_string(),
@@ -182,6 +186,10 @@ pub(super) fn parse_bbo(msg: &str) -> Result<Vec<BboMsg>, SimpleError> {
         bid_quantity_base: ws_msg.tick.bidSize,
         bid_quantity_quote: ws_msg.tick.bid * ws_msg.tick.bidSize,
         bid_quantity_contract: None,
+        bid_price_exact: None,
+        bid_quantity_exact: None,
+        ask_price_exact: None,
+        ask_quantity_exact: None,
         id: ws_msg.tick.seqId,
         json: msg.to_string(),
     };
@@ -192,18 +200,24 @@ pub(super) fn parse_bbo(msg: &str) -> Result<Vec<BboMsg>, SimpleError> {
 pub(super) fn parse_candlestick(
     market_type: MarketType,
     msg: &str,
-) -> Result<Vec<CandlestickMsg>, SimpleError> {
+) -> Result<Vec<CandlestickMsg>, ParseError> {
     let ws_msg =
-        serde_json::from_str::<WebsocketMsg<RawCandlestickMsg>>(msg).map_err(SimpleError::from)?;
-    debug_assert!(ws_msg.ch.contains(".kline."));
+        serde_json::from_str::<WebsocketMsg<RawCandlestickMsg>>(msg).map_err(ParseError::from)?;
+    if !ws_msg.ch.contains(".kline.") {
+        return Err(ParseError::unsupported(format!("Unsupported channel {}", ws_msg.ch)));
+    }
 
     let (symbol, period) = {
         let arr: Vec<&str> = ws_msg.ch.split('.').collect();
+        if arr.len() != 4 {
+            return Err(ParseError::unsupported(format!("Invalid channel {}", ws_msg.ch)));
+        }
         let symbol = arr[1];
-        let period = arr[3];
+        let period = parse_candle_period(EXCHANGE_NAME, market_type, arr[3])?;
         (symbol, period)
     };
-    let pair = crypto_pair::normalize_pair(symbol, EXCHANGE_NAME).unwrap();
+    let pair = crypto_pair::normalize_pair(symbol, EXCHANGE_NAME)
+        .ok_or_else(|| ParseError::symbol_normalization(symbol))?;
 
     let kline_msg = CandlestickMsg {
         exchange: EXCHANGE_NAME.to_string(),
@@ -212,14 +226,16 @@ pub(super) fn parse_candlestick(
         symbol: symbol.to_string(),
         pair,
         timestamp: ws_msg.ts,
-        begin_time: ws_msg.tick.id,
+        begin_time: ws_msg.tick.id * 1000,
+        end_time: period.end_time(ws_msg.tick.id * 1000),
         open: ws_msg.tick.open,
         high: ws_msg.tick.high,
         low: ws_msg.tick.low,
         close: ws_msg.tick.close,
         volume: ws_msg.tick.amount,
         quote_volume: Some(ws_msg.tick.vol),
-        period: period.to_string(),
+        period,
+        is_closed: None,
         json: msg.to_string(),
     };
 
@@ -1,8 +1,18 @@
 use serde::{Deserialize, Serialize};
 
+use crate::ParseError;
+
 #[derive(Serialize, Deserialize)]
 pub(super) struct WebsocketMsg<T: Sized> {
     pub ch: String,
     pub ts: i64,
     pub tick: T,
 }
+
+// The symbol in a channel, e.g., `btcusdt` in `market.btcusdt.trade.detail`
+pub(super) fn symbol_of_channel(channel: &str) -> Result<&str, ParseError> {
+    channel
+        .split('.')
+        .nth(1)
+        .ok_or_else(|| ParseError::malformed(format!("No symbol in channel {channel}")))
+}
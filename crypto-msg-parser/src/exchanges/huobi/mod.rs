@@ -3,48 +3,47 @@ mod huobi_inverse;
 mod huobi_linear;
 mod huobi_spot;
 mod message;
+mod open_interest;
+mod ticker;
 
 use std::collections::HashMap;
 
 use crypto_market_type::MarketType;
-use crypto_message::{BboMsg, CandlestickMsg};
+use crypto_message::{BboMsg, CandlestickMsg, OpenInterestMsg, TickerMsg};
 use crypto_msg_type::MessageType;
 
-use crate::{FundingRateMsg, OrderBookMsg, TradeMsg};
+use crate::{FundingRateMsg, OrderBookMsg, ParseError, TradeMsg};
 
 use serde_json::Value;
-use simple_error::SimpleError;
 
-use message::WebsocketMsg;
+use message::{WebsocketMsg, symbol_of_channel};
 
-pub(crate) fn extract_symbol(msg: &str) -> Result<String, SimpleError> {
-    let json_obj = serde_json::from_str::<HashMap<String, Value>>(msg).unwrap();
-    if json_obj.contains_key("data")
-        && json_obj["data"].is_array()
-        && json_obj["data"].as_array().unwrap().len() > 1
-    {
+pub(crate) fn extract_symbol(msg: &str) -> Result<String, ParseError> {
+    let json_obj = serde_json::from_str::<HashMap<String, Value>>(msg)?;
+    if json_obj.get("data").and_then(|x| x.as_array()).is_some_and(|arr| arr.len() > 1) {
         // open interest from RESTful API
         return Ok("ALL".to_string());
     }
 
-    let channel = if json_obj.contains_key("ch") {
-        json_obj["ch"].as_str().unwrap()
-    } else if json_obj.contains_key("topic") {
-        json_obj["topic"].as_str().unwrap()
+    let channel = if let Some(ch) = json_obj.get("ch") {
+        ch.as_str().ok_or_else(|| ParseError::malformed("ch is not a string"))?
+    } else if let Some(topic) = json_obj.get("topic") {
+        topic.as_str().ok_or_else(|| ParseError::malformed("topic is not a string"))?
     } else {
-        return Err(SimpleError::new(format!("No channel or topic found in {msg}")));
+        return Err(ParseError::unsupported("No channel or topic found"));
     };
     if channel == "public.*.funding_rate" {
         Ok("ALL".to_string())
     } else {
-        let symbol = channel.split('.').nth(1).unwrap();
+        let symbol = symbol_of_channel(channel)?;
         Ok(symbol.to_string())
     }
 }
 
-pub(crate) fn extract_timestamp(msg: &str) -> Result<Option<i64>, SimpleError> {
-    let json_obj = serde_json::from_str::<HashMap<String, Value>>(msg).unwrap();
-    json_obj.get("ts").map_or(Err(SimpleError::new(msg)), |ts| Ok(Some(ts.as_i64().unwrap())))
+pub(crate) fn extract_timestamp(msg: &str) -> Result<Option<i64>, ParseError> {
+    let json_obj = serde_json::from_str::<HashMap<String, Value>>(msg)?;
+    let ts = json_obj.get("ts").ok_or_else(ParseError::missing_timestamp)?;
+    ts.as_i64().map(Some).ok_or_else(|| ParseError::malformed("ts is not an integer"))
 }
 
 pub(crate) fn get_msg_type(msg: &str) -> MessageType {
@@ -76,15 +75,14 @@ pub(crate) fn get_msg_type(msg: &str) -> MessageType {
         } else {
             MessageType::Other
         }
+    } else if serde_json::from_str::<open_interest::RestfulMsg>(msg).is_ok() {
+        MessageType::OpenInterest
     } else {
         MessageType::Other
     }
 }
 
-pub(crate) fn parse_trade(
-    market_type: MarketType,
-    msg: &str,
-) -> Result<Vec<TradeMsg>, SimpleError> {
+pub(crate) fn parse_trade(market_type: MarketType, msg: &str) -> Result<Vec<TradeMsg>, ParseError> {
     match market_type {
         MarketType::Spot => huobi_spot::parse_trade(msg),
         MarketType::InverseFuture | MarketType::InverseSwap => {
@@ -93,25 +91,36 @@ pub(crate) fn parse_trade(
         MarketType::LinearFuture | MarketType::LinearSwap | MarketType::EuropeanOption => {
             huobi_linear::parse_trade(market_type, msg)
         }
-        _ => Err(SimpleError::new(format!("Unknown huobi market type {market_type}"))),
+        _ => Err(ParseError::unsupported(format!("Unknown huobi market type {market_type}"))),
     }
 }
 
 pub(crate) fn parse_funding_rate(
     market_type: MarketType,
     msg: &str,
-) -> Result<Vec<FundingRateMsg>, SimpleError> {
+) -> Result<Vec<FundingRateMsg>, ParseError> {
     if market_type == MarketType::InverseSwap || market_type == MarketType::LinearSwap {
         funding_rate::parse_funding_rate(market_type, msg)
     } else {
-        Err(SimpleError::new(format!("Huobi {market_type} does NOT have funding rates")))
+        Err(ParseError::unsupported(format!("Huobi {market_type} does NOT have funding rates")))
+    }
+}
+
+pub(crate) fn parse_open_interest(
+    market_type: MarketType,
+    msg: &str,
+) -> Result<Vec<OpenInterestMsg>, ParseError> {
+    if market_type == MarketType::Spot {
+        Err(ParseError::unsupported("Spot markets have no open interest"))
+    } else {
+        open_interest::parse_open_interest(market_type, msg)
     }
 }
 
 pub(crate) fn parse_l2(
     market_type: MarketType,
     msg: &str,
-) -> Result<Vec<OrderBookMsg>, SimpleError> {
+) -> Result<Vec<OrderBookMsg>, ParseError> {
     match market_type {
         MarketType::Spot => huobi_spot::parse_l2(msg),
         MarketType::InverseFuture | MarketType::InverseSwap => {
@@ -120,18 +129,18 @@ pub(crate) fn parse_l2(
         MarketType::LinearFuture | MarketType::LinearSwap | MarketType::EuropeanOption => {
             huobi_inverse::parse_l2(market_type, msg)
         }
-        _ => Err(SimpleError::new(format!("Unknown huobi market type {market_type}"))),
+        _ => Err(ParseError::unsupported(format!("Unknown huobi market type {market_type}"))),
     }
 }
 
 pub(crate) fn parse_l2_topk(
     market_type: MarketType,
     msg: &str,
-) -> Result<Vec<OrderBookMsg>, SimpleError> {
+) -> Result<Vec<OrderBookMsg>, ParseError> {
     parse_l2(market_type, msg)
 }
 
-pub(crate) fn parse_bbo(market_type: MarketType, msg: &str) -> Result<Vec<BboMsg>, SimpleError> {
+pub(crate) fn parse_bbo(market_type: MarketType, msg: &str) -> Result<Vec<BboMsg>, ParseError> {
     match market_type {
         MarketType::Spot => huobi_spot::parse_bbo(msg),
         MarketType::InverseFuture | MarketType::InverseSwap => {
@@ -140,20 +149,33 @@ pub(crate) fn parse_bbo(market_type: MarketType, msg: &str) -> Result<Vec<BboMsg
         MarketType::LinearFuture | MarketType::LinearSwap | MarketType::EuropeanOption => {
             huobi_inverse::parse_bbo(market_type, msg)
         }
-        _ => Err(SimpleError::new(format!("Unknown huobi market type {market_type}"))),
+        _ => Err(ParseError::unsupported(format!("Unknown huobi market type {market_type}"))),
+    }
+}
+
+pub(crate) fn parse_ticker(
+    market_type: MarketType,
+    msg: &str,
+) -> Result<Vec<TickerMsg>, ParseError> {
+    match market_type {
+        MarketType::Spot
+        | MarketType::InverseFuture
+        | MarketType::InverseSwap
+        | MarketType::LinearSwap => ticker::parse_ticker(market_type, msg),
+        _ => Err(ParseError::unsupported(format!("Unknown huobi market type {market_type}"))),
     }
 }
 
 pub(crate) fn parse_candlestick(
     market_type: MarketType,
     msg: &str,
-) -> Result<Vec<CandlestickMsg>, SimpleError> {
+) -> Result<Vec<CandlestickMsg>, ParseError> {
     match market_type {
         MarketType::Spot => huobi_spot::parse_candlestick(market_type, msg),
         MarketType::InverseSwap | MarketType::InverseFuture => {
             huobi_inverse::parse_candlestick(market_type, msg)
         }
         MarketType::LinearSwap => huobi_linear::parse_candlestick(market_type, msg),
-        _ => Err(SimpleError::new(format!("Unknown huobi market type {market_type}"))),
+        _ => Err(ParseError::unsupported(format!("Unknown huobi market type {market_type}"))),
     }
 }
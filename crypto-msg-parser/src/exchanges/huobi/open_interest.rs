@@ -0,0 +1,80 @@
+use crypto_market_type::MarketType;
+use crypto_msg_type::MessageType;
+
+use crate::{OpenInterestMsg, ParseError, exchanges::utils::calc_open_interest};
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashMap;
+
+const EXCHANGE_NAME: &str = "huobi";
+
+// See:
+// * https://huobiapi.github.io/docs/dm/v1/en/#get-contract-open-interest-information
+// * https://huobiapi.github.io/docs/coin_margined_swap/v1/en/#get-swap-open-interest-information
+// * https://huobiapi.github.io/docs/usdt_swap/v1/en/#general-get-swap-open-interest-information
+#[derive(Serialize, Deserialize)]
+struct RawOpenInterestMsg {
+    contract_code: String,
+    volume: f64,        // contracts
+    amount: f64,        // base coins
+    value: Option<f64>, // quote coins, linear markets only
+    #[serde(flatten)]
+    extra: HashMap<String, Value>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub(super) struct RestfulMsg {
+    status: String,
+    data: Vec<RawOpenInterestMsg>,
+    ts: i64,
+}
+
+pub(super) fn parse_open_interest(
+    market_type: MarketType,
+    msg: &str,
+) -> Result<Vec<OpenInterestMsg>, ParseError> {
+    let rest_msg = serde_json::from_str::<RestfulMsg>(msg)
+        .map_err(|_e| ParseError::malformed("expected RestfulMsg"))?;
+    if rest_msg.status != "ok" {
+        return Err(ParseError::unsupported("Error HTTP response"));
+    }
+    let mut open_interests = rest_msg
+        .data
+        .into_iter()
+        .map(|raw_msg| -> Result<OpenInterestMsg, ParseError> {
+            let pair = crypto_pair::normalize_pair(&raw_msg.contract_code, EXCHANGE_NAME)
+                .ok_or_else(|| ParseError::symbol_normalization(&raw_msg.contract_code))?;
+            let quantity_quote = match raw_msg.value {
+                Some(value) => Some(value),
+                None => {
+                    calc_open_interest(
+                        EXCHANGE_NAME,
+                        market_type,
+                        &pair,
+                        None,
+                        raw_msg.volume,
+                        rest_msg.ts,
+                    )?
+                    .1
+                }
+            };
+            Ok(OpenInterestMsg {
+                exchange: EXCHANGE_NAME.to_string(),
+                market_type,
+                symbol: raw_msg.contract_code.clone(),
+                pair,
+                msg_type: MessageType::OpenInterest,
+                timestamp: rest_msg.ts,
+                quantity_contract: raw_msg.volume,
+                quantity_base: Some(raw_msg.amount),
+                quantity_quote,
+                json: serde_json::to_string(&raw_msg).unwrap(),
+            })
+        })
+        .collect::<Result<Vec<OpenInterestMsg>, ParseError>>()?;
+    if open_interests.len() == 1 {
+        open_interests[0].json = msg.to_string();
+    }
+    Ok(open_interests)
+}
@@ -0,0 +1,110 @@
+use crypto_market_type::MarketType;
+use crypto_message::TickerMsg;
+use crypto_msg_type::MessageType;
+
+use super::{super::utils::calc_quantity_and_volume, message::WebsocketMsg};
+
+use crate::ParseError;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashMap;
+
+const EXCHANGE_NAME: &str = "huobi";
+
+// See:
+// * https://huobiapi.github.io/docs/spot/v1/en/#market-detail
+// * https://huobiapi.github.io/docs/dm/v1/en/#subscribe-market-detail-data
+// * https://huobiapi.github.io/docs/coin_margined_swap/v1/en/#subscribe-market-detail-data
+// * https://huobiapi.github.io/docs/usdt_swap/v1/en/#general-subscribe-market-detail-data
+#[derive(Serialize, Deserialize)]
+#[allow(non_snake_case)]
+struct RawTickerMsg {
+    open: f64,
+    close: f64,
+    high: f64,
+    low: f64,
+    amount: f64,                 // volume in base currency
+    vol: f64,                    // quote volume in spot, number of contracts in contract markets
+    trade_turnover: Option<f64>, // quote volume, linear swap only
+    ask: Option<[f64; 2]>,       // best ask price and contracts, contract markets only
+    bid: Option<[f64; 2]>,       // best bid price and contracts, contract markets only
+    #[serde(flatten)]
+    extra: HashMap<String, Value>,
+}
+
+pub(super) fn parse_ticker(
+    market_type: MarketType,
+    msg: &str,
+) -> Result<Vec<TickerMsg>, ParseError> {
+    let ws_msg = serde_json::from_str::<WebsocketMsg<RawTickerMsg>>(msg)
+        .map_err(|_e| ParseError::malformed("expected WebsocketMsg<RawTickerMsg>"))?;
+    if !ws_msg.ch.ends_with(".detail") {
+        return Err(ParseError::unsupported(format!("Unsupported ticker channel {}", ws_msg.ch)));
+    }
+    let symbol = ws_msg
+        .ch
+        .split('.')
+        .nth(1)
+        .ok_or_else(|| ParseError::unsupported("Failed to extract symbol"))?;
+    let pair = crypto_pair::normalize_pair(symbol, EXCHANGE_NAME)
+        .ok_or_else(|| ParseError::symbol_normalization(symbol))?;
+
+    let raw_ticker = ws_msg.tick;
+    let quote_volume = if market_type == MarketType::Spot {
+        raw_ticker.vol
+    } else if let Some(trade_turnover) = raw_ticker.trade_turnover {
+        trade_turnover
+    } else {
+        calc_quantity_and_volume(
+            EXCHANGE_NAME,
+            market_type,
+            &pair,
+            raw_ticker.close,
+            raw_ticker.vol,
+            ws_msg.ts,
+        )?
+        .1
+    };
+    let parse_order = |order: Option<[f64; 2]>| -> Result<(Option<f64>, Option<f64>), ParseError> {
+        Ok(if let Some([price, quantity]) = order {
+            let (quantity_base, _, _) = calc_quantity_and_volume(
+                EXCHANGE_NAME,
+                market_type,
+                &pair,
+                price,
+                quantity,
+                ws_msg.ts,
+            )?;
+            (Some(price), Some(quantity_base))
+        } else {
+            (None, None)
+        })
+    };
+    let (best_bid_price, best_bid_quantity) = parse_order(raw_ticker.bid)?;
+    let (best_ask_price, best_ask_quantity) = parse_order(raw_ticker.ask)?;
+
+    let ticker_msg = TickerMsg {
+        exchange: EXCHANGE_NAME.to_string(),
+        market_type,
+        symbol: symbol.to_string(),
+        pair,
+        msg_type: MessageType::Ticker,
+        timestamp: ws_msg.ts,
+        open: raw_ticker.open,
+        high: raw_ticker.high,
+        low: raw_ticker.low,
+        close: raw_ticker.close,
+        volume: raw_ticker.amount,
+        quote_volume,
+        last_quantity: None,
+        best_bid_price,
+        best_bid_quantity,
+        best_ask_price,
+        best_ask_quantity,
+        open_interest: None,
+        open_interest_quote: None,
+        json: msg.to_string(),
+    };
+
+    Ok(vec![ticker_msg])
+}
@@ -1,13 +1,18 @@
 use crypto_market_type::MarketType;
 use crypto_msg_type::MessageType;
 
-use crate::exchanges::utils::calc_quantity_and_volume;
-use crypto_message::{Order, OrderBookMsg, TradeMsg, TradeSide};
+use crate::{
+    ParseError,
+    exchanges::utils::{
+        annualize_funding_rate, calc_quantity_and_volume, json_array, json_i64, json_str,
+        parse_candle_period,
+    },
+};
+use crypto_message::{CandlestickMsg, FundingRateMsg, Order, OrderBookMsg, TradeMsg, TradeSide};
 
 use chrono::DateTime;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
-use simple_error::SimpleError;
 use std::collections::HashMap;
 
 const EXCHANGE_NAME: &str = "kraken";
@@ -69,56 +74,97 @@ struct OrderbookUpdate {
     extra: HashMap<String, Value>,
 }
 
-pub(super) fn extract_symbol(msg: &str) -> Result<String, SimpleError> {
-    let obj = serde_json::from_str::<HashMap<String, Value>>(msg).map_err(|_e| {
-        SimpleError::new(format!("Failed to deserialize {msg} to HashMap<String, Value>"))
-    })?;
-    if obj.contains_key("product_id") {
-        Ok(obj.get("product_id").unwrap().as_str().unwrap().to_string())
+// https://support.kraken.com/hc/en-us/articles/360022635872-Ticker
+#[derive(Serialize, Deserialize)]
+struct RawFundingRateMsg {
+    feed: String,
+    product_id: String,
+    time: i64,
+    relative_funding_rate: f64,
+    relative_funding_rate_prediction: Option<f64>,
+    next_funding_rate_time: i64,
+    #[serde(flatten)]
+    extra: HashMap<String, Value>,
+}
+
+// https://docs.futures.kraken.com/#websocket-api-public-feeds-candles
+#[derive(Serialize, Deserialize)]
+struct RawCandle {
+    time: i64,
+    open: String,
+    high: String,
+    low: String,
+    close: String,
+    volume: f64, // in contracts
+    #[serde(flatten)]
+    extra: HashMap<String, Value>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct RawCandlestickMsg {
+    feed: String, // e.g., candles_trade_1m
+    product_id: String,
+    candle: RawCandle,
+    #[serde(flatten)]
+    extra: HashMap<String, Value>,
+}
+
+pub(super) fn extract_symbol(msg: &str) -> Result<String, ParseError> {
+    let obj = serde_json::from_str::<HashMap<String, Value>>(msg)
+        .map_err(|_e| ParseError::malformed("expected HashMap<String, Value>"))?;
+    if let Some(product_id) = obj.get("product_id") {
+        Ok(json_str(product_id, "product_id")?.to_string())
     } else if obj.contains_key("serverTime") && obj.contains_key("result") {
         // RESTful API
-        if obj["result"].as_str().unwrap() != "success" {
-            Err(SimpleError::new(format!("Error HTTP response {msg}")))
+        if obj["result"].as_str() != Some("success") {
+            Err(ParseError::unsupported("Error HTTP response"))
         } else if obj.contains_key("orderBook") {
             Ok("NONE".to_string())
         } else {
-            Err(SimpleError::new(format!("Unsupported HTTP message {msg}")))
+            Err(ParseError::unsupported("Unsupported HTTP message"))
         }
     } else {
-        Err(SimpleError::new(format!("No product_id found in {msg}")))
+        Err(ParseError::unsupported("No product_id found"))
     }
 }
 
-pub(super) fn extract_timestamp(msg: &str) -> Result<Option<i64>, SimpleError> {
-    let obj = serde_json::from_str::<HashMap<String, Value>>(msg)
-        .map_err(|_e| SimpleError::new(format!("Failed to parse the JSON string {msg}")))?;
-    if obj.contains_key("serverTime") && obj.contains_key("result") {
+pub(super) fn extract_timestamp(msg: &str) -> Result<Option<i64>, ParseError> {
+    // Value returns Null for missing keys instead of panicking like HashMap
+    let obj =
+        serde_json::from_str::<Value>(msg).map_err(|_e| ParseError::malformed("invalid JSON"))?;
+    if !obj["serverTime"].is_null() && !obj["result"].is_null() {
         // RESTful API
         return Ok(Some(
-            DateTime::parse_from_rfc3339(obj["serverTime"].as_str().unwrap())
-                .unwrap()
+            DateTime::parse_from_rfc3339(json_str(&obj["serverTime"], "serverTime")?)?
                 .timestamp_millis(),
         ));
     }
-    let feed = obj["feed"].as_str().unwrap();
+    let feed = obj.get("feed").map_or(Ok(""), |x| json_str(x, "feed"))?;
     match feed {
-        "trade" | "ticker" => Ok(Some(obj["time"].as_i64().unwrap())),
+        "trade" | "ticker" => Ok(Some(json_i64(&obj["time"], "time")?)),
         "trade_snapshot" => {
-            let trades = obj["trades"].as_array().unwrap();
-            let timestamp =
-                trades.iter().map(|raw_trade| raw_trade["time"].as_i64().unwrap()).max();
+            let trades = json_array(&obj["trades"], "trades")?;
+            let timestamp = trades
+                .iter()
+                .map(|raw_trade| json_i64(&raw_trade["time"], "time"))
+                .collect::<Result<Vec<i64>, ParseError>>()?
+                .into_iter()
+                .max();
             if timestamp.is_none() {
-                Err(SimpleError::new(format!("trades is empty in {msg}")))
+                Err(ParseError::unsupported("trades is empty"))
             } else {
                 Ok(timestamp)
             }
         }
-        "book" | "book_snapshot" => Ok(Some(obj["timestamp"].as_i64().unwrap())),
-        _ => Err(SimpleError::new(format!("Unknown feed in {msg}"))),
+        "book" | "book_snapshot" => Ok(Some(json_i64(&obj["timestamp"], "timestamp")?)),
+        _ if feed.starts_with("candles_trade_") => {
+            Ok(Some(json_i64(&obj["candle"]["time"], "candle.time")?))
+        }
+        _ => Err(ParseError::unsupported("Unknown feed")),
     }
 }
 
-fn convert_trade(raw_trade: Trade) -> TradeMsg {
+fn convert_trade(raw_trade: Trade) -> Result<TradeMsg, ParseError> {
     let market_type = if raw_trade.product_id.starts_with("PI_") {
         MarketType::InverseSwap
     } else if raw_trade.product_id.starts_with("FI_") {
@@ -126,8 +172,9 @@ fn convert_trade(raw_trade: Trade) -> TradeMsg {
     } else {
         MarketType::Unknown
     };
-    let pair = crypto_pair::normalize_pair(&raw_trade.product_id, EXCHANGE_NAME).unwrap();
-    TradeMsg {
+    let pair = crypto_pair::normalize_pair(&raw_trade.product_id, EXCHANGE_NAME)
+        .ok_or_else(|| ParseError::symbol_normalization(&raw_trade.product_id))?;
+    Ok(TradeMsg {
         exchange: EXCHANGE_NAME.to_string(),
         market_type,
         symbol: raw_trade.product_id.clone(),
@@ -138,23 +185,25 @@ fn convert_trade(raw_trade: Trade) -> TradeMsg {
         quantity_base: raw_trade.qty / raw_trade.price,
         quantity_quote: raw_trade.qty,
         quantity_contract: Some(raw_trade.qty),
+        price_exact: None,
+        quantity_exact: None,
         side: if raw_trade.side == "sell" { TradeSide::Sell } else { TradeSide::Buy },
         trade_id: raw_trade.seq.to_string(),
         json: serde_json::to_string(&raw_trade).unwrap(),
-    }
+    })
 }
 
-pub(crate) fn parse_trade(msg: &str) -> Result<Vec<TradeMsg>, SimpleError> {
+pub(crate) fn parse_trade(msg: &str) -> Result<Vec<TradeMsg>, ParseError> {
     if let Ok(trade) = serde_json::from_str::<Trade>(msg) {
-        Ok(vec![convert_trade(trade)])
+        Ok(vec![convert_trade(trade)?])
     } else if let Ok(trade_snapshot) = serde_json::from_str::<TradeSnapshot>(msg) {
-        Ok(trade_snapshot.trades.into_iter().map(convert_trade).collect())
+        trade_snapshot.trades.into_iter().map(convert_trade).collect()
     } else {
-        Err(SimpleError::new(format!("Failed to parse {msg}")))
+        Err(ParseError::malformed("invalid JSON"))
     }
 }
 
-pub(crate) fn parse_l2(msg: &str) -> Result<Vec<OrderBookMsg>, SimpleError> {
+pub(crate) fn parse_l2(msg: &str) -> Result<Vec<OrderBookMsg>, ParseError> {
     if let Ok(orderbook_update) = serde_json::from_str::<OrderbookUpdate>(msg) {
         let market_type = if orderbook_update.product_id.starts_with("PI_") {
             MarketType::InverseSwap
@@ -163,8 +212,8 @@ pub(crate) fn parse_l2(msg: &str) -> Result<Vec<OrderBookMsg>, SimpleError> {
         } else {
             MarketType::Unknown
         };
-        let pair =
-            crypto_pair::normalize_pair(&orderbook_update.product_id, EXCHANGE_NAME).unwrap();
+        let pair = crypto_pair::normalize_pair(&orderbook_update.product_id, EXCHANGE_NAME)
+            .ok_or_else(|| ParseError::symbol_normalization(&orderbook_update.product_id))?;
         let orders = {
             let (quantity_base, quantity_quote, quantity_contract) = calc_quantity_and_volume(
                 EXCHANGE_NAME,
@@ -172,12 +221,15 @@ pub(crate) fn parse_l2(msg: &str) -> Result<Vec<OrderBookMsg>, SimpleError> {
                 &pair,
                 orderbook_update.price,
                 orderbook_update.qty,
-            );
+                orderbook_update.timestamp,
+            )?;
             vec![Order {
                 price: orderbook_update.price,
                 quantity_base,
                 quantity_quote,
                 quantity_contract,
+                price_exact: None,
+                quantity_exact: None,
             }]
         };
         let mut orderbook = OrderBookMsg {
@@ -199,7 +251,10 @@ pub(crate) fn parse_l2(msg: &str) -> Result<Vec<OrderBookMsg>, SimpleError> {
         } else if orderbook_update.side == "sell" {
             orderbook.asks = orders;
         } else {
-            panic!("Unexpected side {}", orderbook_update.side);
+            return Err(ParseError::malformed(format!(
+                "Unexpected side {}",
+                orderbook_update.side
+            )));
         }
         Ok(vec![orderbook])
     } else if let Ok(orderbook_snapshot) = serde_json::from_str::<OrderbookSnapshot>(msg) {
@@ -210,17 +265,25 @@ pub(crate) fn parse_l2(msg: &str) -> Result<Vec<OrderBookMsg>, SimpleError> {
         } else {
             MarketType::Unknown
         };
-        let pair =
-            crypto_pair::normalize_pair(&orderbook_snapshot.product_id, EXCHANGE_NAME).unwrap();
-        let parse_order = |raw_order: &RawOrder| -> Order {
+        let pair = crypto_pair::normalize_pair(&orderbook_snapshot.product_id, EXCHANGE_NAME)
+            .ok_or_else(|| ParseError::symbol_normalization(&orderbook_snapshot.product_id))?;
+        let parse_order = |raw_order: &RawOrder| -> Result<Order, ParseError> {
             let (quantity_base, quantity_quote, quantity_contract) = calc_quantity_and_volume(
                 EXCHANGE_NAME,
                 market_type,
                 &pair,
                 raw_order.price,
                 raw_order.qty,
-            );
-            Order { price: raw_order.price, quantity_base, quantity_quote, quantity_contract }
+                orderbook_snapshot.timestamp,
+            )?;
+            Ok(Order {
+                price: raw_order.price,
+                quantity_base,
+                quantity_quote,
+                quantity_contract,
+                price_exact: None,
+                quantity_exact: None,
+            })
         };
 
         let orderbook = OrderBookMsg {
@@ -232,13 +295,102 @@ pub(crate) fn parse_l2(msg: &str) -> Result<Vec<OrderBookMsg>, SimpleError> {
             timestamp: orderbook_snapshot.timestamp,
             seq_id: Some(orderbook_snapshot.seq as u64),
             prev_seq_id: None,
-            asks: orderbook_snapshot.asks.iter().map(parse_order).collect::<Vec<Order>>(),
-            bids: orderbook_snapshot.bids.iter().map(parse_order).collect::<Vec<Order>>(),
+            asks: orderbook_snapshot
+                .asks
+                .iter()
+                .map(parse_order)
+                .collect::<Result<Vec<Order>, ParseError>>()?,
+            bids: orderbook_snapshot
+                .bids
+                .iter()
+                .map(parse_order)
+                .collect::<Result<Vec<Order>, ParseError>>()?,
             snapshot: true,
             json: msg.to_string(),
         };
         Ok(vec![orderbook])
     } else {
-        Err(SimpleError::new(format!("Failed to parse {msg}")))
+        Err(ParseError::malformed("invalid JSON"))
     }
 }
+
+// Perpetual swaps settle funding every 4 hours
+const FUNDING_INTERVAL: i64 = 4 * 3600 * 1000;
+
+/// Parses the `ticker` feed of perpetual swaps.
+///
+/// `funding_rate` is absolute, i.e., in USD per contract, so the relative
+/// rates are returned instead.
+pub(crate) fn parse_funding_rate(
+    market_type: MarketType,
+    msg: &str,
+) -> Result<Vec<FundingRateMsg>, ParseError> {
+    let raw_msg = serde_json::from_str::<RawFundingRateMsg>(msg)
+        .map_err(|_e| ParseError::malformed("expected RawFundingRateMsg"))?;
+    if raw_msg.feed != "ticker" {
+        return Err(ParseError::unsupported(format!("Unsupported feed {}", raw_msg.feed)));
+    }
+    let pair = crypto_pair::normalize_pair(&raw_msg.product_id, EXCHANGE_NAME)
+        .ok_or_else(|| ParseError::symbol_normalization(&raw_msg.product_id))?;
+
+    Ok(vec![FundingRateMsg {
+        exchange: EXCHANGE_NAME.to_string(),
+        market_type,
+        symbol: raw_msg.product_id.clone(),
+        pair,
+        msg_type: MessageType::FundingRate,
+        timestamp: raw_msg.time,
+        funding_rate: raw_msg.relative_funding_rate,
+        funding_time: raw_msg.next_funding_rate_time,
+        estimated_rate: raw_msg.relative_funding_rate_prediction,
+        funding_interval: FUNDING_INTERVAL,
+        funding_rate_cap: None,
+        funding_rate_floor: None,
+        annualized_rate: annualize_funding_rate(raw_msg.relative_funding_rate, FUNDING_INTERVAL),
+        json: msg.to_string(),
+    }])
+}
+
+pub(crate) fn parse_candlestick(
+    market_type: MarketType,
+    msg: &str,
+) -> Result<Vec<CandlestickMsg>, ParseError> {
+    let raw_msg = serde_json::from_str::<RawCandlestickMsg>(msg)
+        .map_err(|_e| ParseError::malformed("expected RawCandlestickMsg"))?;
+    let period = raw_msg
+        .feed
+        .strip_prefix("candles_trade_")
+        .ok_or_else(|| ParseError::unsupported(format!("Unsupported feed {}", raw_msg.feed)))?;
+    let period = parse_candle_period(EXCHANGE_NAME, market_type, period)?;
+    let pair = crypto_pair::normalize_pair(&raw_msg.product_id, EXCHANGE_NAME)
+        .ok_or_else(|| ParseError::symbol_normalization(&raw_msg.product_id))?;
+    let close = raw_msg.candle.close.parse::<f64>()?;
+    let (volume, quote_volume, _) = calc_quantity_and_volume(
+        EXCHANGE_NAME,
+        market_type,
+        &pair,
+        close,
+        raw_msg.candle.volume,
+        raw_msg.candle.time,
+    )?;
+
+    Ok(vec![CandlestickMsg {
+        exchange: EXCHANGE_NAME.to_string(),
+        market_type,
+        msg_type: MessageType::Candlestick,
+        symbol: raw_msg.product_id.clone(),
+        pair,
+        timestamp: raw_msg.candle.time,
+        period,
+        begin_time: raw_msg.candle.time,
+        end_time: period.end_time(raw_msg.candle.time),
+        open: raw_msg.candle.open.parse::<f64>()?,
+        high: raw_msg.candle.high.parse::<f64>()?,
+        low: raw_msg.candle.low.parse::<f64>()?,
+        close,
+        volume,
+        quote_volume: Some(quote_volume),
+        is_closed: None,
+        json: msg.to_string(),
+    }])
+}
@@ -1,34 +1,21 @@
 use crypto_market_type::MarketType;
 use crypto_msg_type::MessageType;
 
-use crate::exchanges::utils::calc_quantity_and_volume;
-use crypto_message::{BboMsg, CandlestickMsg, Order, OrderBookMsg, TradeMsg, TradeSide};
+use crate::{
+    ParseError,
+    checksum::L2Levels,
+    exchanges::utils::{
+        calc_quantity_and_volume, json_object, json_str, parse_candle_period, parse_exact,
+    },
+};
+use crypto_message::{BboMsg, CandlestickMsg, Order, OrderBookMsg, TickerMsg, TradeMsg, TradeSide};
 
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
-use simple_error::SimpleError;
 use std::collections::HashMap;
 
 const EXCHANGE_NAME: &str = "kraken";
 
-// https://docs.kraken.com/websockets/#message-trade
-#[derive(Serialize, Deserialize)]
-struct SpotTradeMsg {
-    #[serde(rename = "type")]
-    type_: String,
-    trade_id: i64,
-    sequence: i64,
-    maker_order_id: String,
-    taker_order_id: String,
-    time: String,
-    product_id: String,
-    size: String,
-    price: String,
-    side: String, // b, s
-    #[serde(flatten)]
-    extra: HashMap<String, Value>,
-}
-
 // https://docs.kraken.com/websockets/#message-book
 #[derive(Serialize, Deserialize)]
 struct OrderbookSnapshot {
@@ -57,77 +44,106 @@ struct RestResp {
     extra: HashMap<String, Value>,
 }
 
-pub(super) fn extract_symbol(msg: &str) -> Result<String, SimpleError> {
+// Websocket messages are arrays of [channelID, payload..., channelName, pair]
+fn parse_ws_msg(msg: &str) -> Result<Vec<Value>, ParseError> {
+    let arr = serde_json::from_str::<Vec<Value>>(msg)
+        .map_err(|_e| ParseError::malformed("expected Vec<Value>"))?;
+    if arr.len() < 4 { Err(ParseError::unsupported("Unknown message format")) } else { Ok(arr) }
+}
+
+fn extract_channel(arr: &[Value]) -> Result<&str, ParseError> {
+    json_str(&arr[arr.len() - 2], "channelName")
+}
+
+fn extract_pair(arr: &[Value]) -> Result<&str, ParseError> {
+    json_str(&arr[arr.len() - 1], "pair")
+}
+
+// The timestamp of a price level [price, volume, timestamp]
+fn parse_order_time(raw_order: &[String]) -> Result<i64, ParseError> {
+    let t =
+        raw_order.get(2).ok_or_else(|| ParseError::malformed("missing timestamp of a level"))?;
+    Ok((t.parse::<f64>()? * 1000.0) as i64)
+}
+
+pub(super) fn extract_symbol(msg: &str) -> Result<String, ParseError> {
     if let Ok(rest_resp) = serde_json::from_str::<RestResp>(msg) {
         // RESTful API
         if !rest_resp.error.is_empty() {
-            Err(SimpleError::new(format!("Error http response {msg}")))
+            Err(ParseError::unsupported("Error http response"))
         } else if rest_resp.result.len() > 1 {
             Ok("ALL".to_string())
         } else {
-            Ok(rest_resp.result.keys().next().unwrap().to_string())
+            rest_resp
+                .result
+                .keys()
+                .next()
+                .map(|x| x.to_string())
+                .ok_or_else(|| ParseError::unsupported("result is empty"))
         }
     } else {
         // websocket
-        let arr = serde_json::from_str::<Vec<Value>>(msg)
-            .map_err(|_e| SimpleError::new(format!("Failed to deserialize {msg} to Vec<Value>")))?;
-        let symbol = arr[arr.len() - 1].as_str().unwrap();
+        let arr = parse_ws_msg(msg)?;
+        let symbol = extract_pair(&arr)?;
         Ok(symbol.to_string())
     }
 }
 
-pub(super) fn extract_timestamp(msg: &str) -> Result<Option<i64>, SimpleError> {
+pub(super) fn extract_timestamp(msg: &str) -> Result<Option<i64>, ParseError> {
     if msg.contains("error") && msg.contains("result") {
         // RESTful API
         return Ok(None);
     }
-    let arr = serde_json::from_str::<Vec<Value>>(msg)
-        .map_err(|_e| SimpleError::new(format!("Failed to deserialize {msg} to Vec<Value>")))?;
-    debug_assert_eq!(arr.len(), 4);
-    let channel = arr[arr.len() - 2].as_str().unwrap();
+    let arr = parse_ws_msg(msg)?;
+    let channel = extract_channel(&arr)?;
     if channel == "trade" {
         let raw_trades: Vec<Vec<String>> =
             serde_json::from_value(arr[1].clone()).map_err(|_e| {
-                SimpleError::new(format!("Failed to deserialize {} to Vec<Vec<String>>", arr[1]))
+                ParseError::malformed(format!(
+                    "Failed to deserialize {} to Vec<Vec<String>>",
+                    arr[1]
+                ))
             })?;
-        Ok(Some((raw_trades[0][2].parse::<f64>().unwrap() * 1000.0) as i64))
+        let raw_trade =
+            raw_trades.first().ok_or_else(|| ParseError::unsupported("data is empty"))?;
+        Ok(Some(parse_order_time(raw_trade)?))
     } else if channel == "spread" {
-        let arr: Vec<String> = serde_json::from_value(arr[1].clone()).map_err(SimpleError::from)?;
-        Ok(Some((arr[2].parse::<f64>().unwrap() * 1000.0) as i64))
+        let arr: Vec<String> = serde_json::from_value(arr[1].clone()).map_err(ParseError::from)?;
+        Ok(Some(parse_order_time(&arr)?))
     } else if channel == "ticker" {
         Ok(None)
     } else if channel.starts_with("ohlc-") {
-        let timestamp = (arr[1][0].as_str().unwrap().parse::<f64>().unwrap() * 1000.0) as i64;
+        let timestamp = (json_str(&arr[1][0], "time")?.parse::<f64>()? * 1000.0) as i64;
         Ok(Some(timestamp))
     } else if channel.starts_with("book-") {
         let snapshot = {
-            let obj = arr[1].as_object().unwrap();
+            let obj = json_object(&arr[1], "book")?;
             obj.contains_key("as") || obj.contains_key("bs")
         };
         if snapshot {
             let orderbook_snapshot = serde_json::from_value::<OrderbookSnapshot>(arr[1].clone())
                 .map_err(|_e| {
-                    SimpleError::new(format!(
+                    ParseError::malformed(format!(
                         "Failed to deserialize {} to OrderbookSnapshot",
                         arr[1]
                     ))
                 })?;
-            let mut timestamp = std::i64::MIN;
+            let mut timestamp = i64::MIN;
             {
                 for ask in orderbook_snapshot.asks.iter() {
-                    let t = (ask[2].parse::<f64>().unwrap() * 1000.0) as i64;
+                    let t = (ask[2].parse::<f64>()? * 1000.0) as i64;
                     if t > timestamp {
                         timestamp = t;
                     }
                 }
                 for bid in orderbook_snapshot.bids.iter() {
-                    let t = (bid[2].parse::<f64>().unwrap() * 1000.0) as i64;
+                    let t = (bid[2].parse::<f64>()? * 1000.0) as i64;
                     if t > timestamp {
                         timestamp = t;
                     }
                 }
             };
-            if timestamp == std::i64::MIN {
+            if timestamp == i64::MIN {
                 // as and bs can be both empty, e.g.,
                 // [6960,{"as":[],"bs":[]},"book-25","LINK/JPY"]
                 Ok(None)
@@ -135,11 +151,11 @@ pub(super) fn extract_timestamp(msg: &str) -> Result<Option<i64>, SimpleError> {
                 Ok(Some(timestamp))
             }
         } else {
-            let mut timestamp = std::i64::MIN;
-            let mut process_update = |update: OrderbookUpdate| {
+            let mut timestamp = i64::MIN;
+            let mut process_update = |update: OrderbookUpdate| -> Result<(), ParseError> {
                 if let Some(a) = update.a {
                     for raw_order in a.iter() {
-                        let t = (raw_order[2].parse::<f64>().unwrap() * 1000.0) as i64;
+                        let t = parse_order_time(raw_order)?;
                         if t > timestamp {
                             timestamp = t;
                         }
@@ -147,75 +163,81 @@ pub(super) fn extract_timestamp(msg: &str) -> Result<Option<i64>, SimpleError> {
                 }
                 if let Some(b) = update.b {
                     for raw_order in b.iter() {
-                        let t = (raw_order[2].parse::<f64>().unwrap() * 1000.0) as i64;
+                        let t = parse_order_time(raw_order)?;
                         if t > timestamp {
                             timestamp = t;
                         }
                     }
                 }
+                Ok(())
             };
             if arr.len() == 4 {
                 let update =
                     serde_json::from_value::<OrderbookUpdate>(arr[1].clone()).map_err(|_e| {
-                        SimpleError::new(format!(
+                        ParseError::malformed(format!(
                             "Failed to deserialize {} to OrderbookUpdate",
                             arr[1]
                         ))
                     })?;
-                process_update(update);
+                process_update(update)?;
             } else if arr.len() == 5 {
                 let update =
                     serde_json::from_value::<OrderbookUpdate>(arr[1].clone()).map_err(|_e| {
-                        SimpleError::new(format!(
+                        ParseError::malformed(format!(
                             "Failed to deserialize {} to OrderbookUpdate",
                             arr[1]
                         ))
                     })?;
-                process_update(update);
+                process_update(update)?;
                 let update =
                     serde_json::from_value::<OrderbookUpdate>(arr[2].clone()).map_err(|_e| {
-                        SimpleError::new(format!(
+                        ParseError::malformed(format!(
                             "Failed to deserialize {} to OrderbookUpdate",
                             arr[2]
                         ))
                     })?;
-                process_update(update);
+                process_update(update)?;
             } else {
-                return Err(SimpleError::new(format!("Unknown message format {msg}")));
+                return Err(ParseError::unsupported("Unknown message format"));
             };
 
-            if timestamp == std::i64::MIN {
-                Err(SimpleError::new(format!("Neither a nor b exists in {msg}")))
+            if timestamp == i64::MIN {
+                Err(ParseError::unsupported("Neither a nor b exists"))
             } else {
                 Ok(Some(timestamp))
             }
         }
     } else {
-        Err(SimpleError::new(format!("Unknown channel: {channel}")))
+        Err(ParseError::unsupported(format!("Unknown channel: {channel}")))
     }
 }
 
-pub(crate) fn parse_trade(msg: &str) -> Result<Vec<TradeMsg>, SimpleError> {
-    let arr = serde_json::from_str::<Vec<Value>>(msg)
-        .map_err(|_e| SimpleError::new(format!("Failed to deserialize {msg} to Vec<Value>")))?;
-    debug_assert_eq!(arr[2].as_str().unwrap(), "trade");
-    debug_assert_eq!(arr.len(), 4);
-    let symbol = arr[arr.len() - 1].as_str().unwrap();
+pub(crate) fn parse_trade(msg: &str) -> Result<Vec<TradeMsg>, ParseError> {
+    let arr = parse_ws_msg(msg)?;
+    let channel = extract_channel(&arr)?;
+    if channel != "trade" {
+        return Err(ParseError::unsupported(format!("Unsupported channel {channel}")));
+    }
+    let symbol = extract_pair(&arr)?;
     let pair = crypto_pair::normalize_pair(symbol, EXCHANGE_NAME)
-        .ok_or_else(|| SimpleError::new(format!("Failed to normalize {symbol} from {msg}")))?;
+        .ok_or_else(|| ParseError::symbol_normalization(symbol))?;
     let raw_trades: Vec<Vec<String>> = serde_json::from_value(arr[1].clone()).map_err(|_e| {
-        SimpleError::new(format!("Failed to deserialize {} to Vec<Vec<String>>", arr[1]))
+        ParseError::malformed(format!("Failed to deserialize {} to Vec<Vec<String>>", arr[1]))
     })?;
 
     // trade format https://docs.kraken.com/websockets/#message-trade
     let mut trades: Vec<TradeMsg> = raw_trades
         .into_iter()
-        .map(|raw_trade| {
-            let price = raw_trade[0].parse::<f64>().unwrap();
-            let quantity = raw_trade[1].parse::<f64>().unwrap();
-            let timestamp = (raw_trade[2].parse::<f64>().unwrap() * 1000.0) as i64;
+        .map(|raw_trade| -> Result<TradeMsg, ParseError> {
+            let (price, quantity, side) = match raw_trade.as_slice() {
+                [price, quantity, _time, side, ..] => {
+                    (price.parse::<f64>()?, quantity.parse::<f64>()?, side.as_str())
+                }
+                _ => return Err(ParseError::malformed("expected [price, volume, time, side]")),
+            };
+            let timestamp = parse_order_time(&raw_trade)?;
 
-            TradeMsg {
+            Ok(TradeMsg {
                 exchange: EXCHANGE_NAME.to_string(),
                 market_type: MarketType::Spot,
                 symbol: symbol.to_string(),
@@ -226,12 +248,14 @@ pub(crate) fn parse_trade(msg: &str) -> Result<Vec<TradeMsg>, SimpleError> {
                 quantity_base: quantity,
                 quantity_quote: price * quantity,
                 quantity_contract: None,
-                side: if raw_trade[3] == "s" { TradeSide::Sell } else { TradeSide::Buy },
+                price_exact: parse_exact(&raw_trade[0]),
+                quantity_exact: parse_exact(&raw_trade[1]),
+                side: if side == "s" { TradeSide::Sell } else { TradeSide::Buy },
                 trade_id: timestamp.to_string(),
                 json: serde_json::to_string(&raw_trade).unwrap(),
-            }
+            })
         })
-        .collect();
+        .collect::<Result<Vec<TradeMsg>, ParseError>>()?;
 
     if trades.len() == 1 {
         trades[0].json = msg.to_string();
@@ -239,52 +263,56 @@ pub(crate) fn parse_trade(msg: &str) -> Result<Vec<TradeMsg>, SimpleError> {
     Ok(trades)
 }
 
-pub(crate) fn parse_l2(msg: &str) -> Result<Vec<OrderBookMsg>, SimpleError> {
-    let arr = serde_json::from_str::<Vec<Value>>(msg)
-        .map_err(|_e| SimpleError::new(format!("Failed to deserialize {msg} to Vec<Value>")))?;
-    debug_assert_eq!(arr[arr.len() - 2].as_str().unwrap(), "book-25");
-    let symbol = arr[arr.len() - 1].as_str().unwrap().to_string();
+pub(crate) fn parse_l2(msg: &str) -> Result<Vec<OrderBookMsg>, ParseError> {
+    let arr = parse_ws_msg(msg)?;
+    let channel = extract_channel(&arr)?;
+    if !channel.starts_with("book-") {
+        return Err(ParseError::unsupported(format!("Unsupported channel {channel}")));
+    }
+    let symbol = extract_pair(&arr)?.to_string();
     let pair = crypto_pair::normalize_pair(&symbol, EXCHANGE_NAME)
-        .ok_or_else(|| SimpleError::new(format!("Failed to normalize {symbol} from {msg}")))?;
+        .ok_or_else(|| ParseError::symbol_normalization(&symbol))?;
     let snapshot = {
-        let obj = arr[1].as_object().unwrap();
+        let obj = json_object(&arr[1], "book")?;
         obj.contains_key("as") || obj.contains_key("bs")
     };
 
-    let parse_order = |raw_order: &[String]| -> Order {
-        let price = raw_order[0].parse::<f64>().unwrap();
-        let quantity_base = raw_order[1].parse::<f64>().unwrap();
+    let parse_order = |raw_order: &[String]| -> Result<Order, ParseError> {
+        let (price, quantity_base) = match raw_order {
+            [price, volume, ..] => (price.parse::<f64>()?, volume.parse::<f64>()?),
+            _ => return Err(ParseError::malformed("expected [price, volume, timestamp]")),
+        };
 
-        Order {
+        Ok(Order {
             price,
             quantity_base,
             quantity_quote: price * quantity_base,
             quantity_contract: None,
-        }
+            price_exact: parse_exact(&raw_order[0]),
+            quantity_exact: parse_exact(&raw_order[1]),
+        })
     };
 
     let orderbooks = if snapshot {
         let orderbook_snapshot = serde_json::from_value::<OrderbookSnapshot>(arr[1].clone())
             .map_err(|_e| {
-                SimpleError::new(format!("Failed to deserialize {} to OrderbookSnapshot", arr[1]))
+                ParseError::malformed(format!(
+                    "Failed to deserialize {} to OrderbookSnapshot",
+                    arr[1]
+                ))
             })?;
 
         let timestamp = {
             let mut timestamps: Vec<i64> = Vec::new();
             for ask in orderbook_snapshot.asks.iter() {
-                let t = (ask[2].parse::<f64>().unwrap() * 1000.0) as i64;
+                let t = (ask[2].parse::<f64>()? * 1000.0) as i64;
                 timestamps.push(t);
             }
             for bid in orderbook_snapshot.bids.iter() {
-                let t = (bid[2].parse::<f64>().unwrap() * 1000.0) as i64;
+                let t = (bid[2].parse::<f64>()? * 1000.0) as i64;
                 timestamps.push(t);
             }
-            if timestamps.is_empty() {
-                None
-            } else {
-                let max = *timestamps.iter().max().unwrap();
-                Some(max)
-            }
+            timestamps.into_iter().max()
         };
 
         if let Some(timestamp) = timestamp {
@@ -297,8 +325,16 @@ pub(crate) fn parse_l2(msg: &str) -> Result<Vec<OrderBookMsg>, SimpleError> {
                 timestamp,
                 seq_id: None,
                 prev_seq_id: None,
-                asks: orderbook_snapshot.asks.iter().map(|x| parse_order(x)).collect(),
-                bids: orderbook_snapshot.bids.iter().map(|x| parse_order(x)).collect(),
+                asks: orderbook_snapshot
+                    .asks
+                    .iter()
+                    .map(|x| parse_order(x))
+                    .collect::<Result<Vec<Order>, ParseError>>()?,
+                bids: orderbook_snapshot
+                    .bids
+                    .iter()
+                    .map(|x| parse_order(x))
+                    .collect::<Result<Vec<Order>, ParseError>>()?,
                 snapshot,
                 json: msg.to_string(),
             }]
@@ -309,51 +345,56 @@ pub(crate) fn parse_l2(msg: &str) -> Result<Vec<OrderBookMsg>, SimpleError> {
         let mut asks: Vec<Order> = Vec::new();
         let mut bids: Vec<Order> = Vec::new();
         let mut timestamps: Vec<i64> = Vec::new();
-        let mut process_update = |update: OrderbookUpdate| {
+        let mut process_update = |update: OrderbookUpdate| -> Result<(), ParseError> {
             if let Some(a) = update.a {
                 for raw_order in a.iter() {
-                    let order = parse_order(raw_order);
+                    let order = parse_order(raw_order)?;
                     asks.push(order);
-                    let t = (raw_order[2].parse::<f64>().unwrap() * 1000.0) as i64;
+                    let t = parse_order_time(raw_order)?;
                     timestamps.push(t);
                 }
             }
             if let Some(b) = update.b {
                 for raw_order in b.iter() {
-                    let order = parse_order(raw_order);
+                    let order = parse_order(raw_order)?;
                     bids.push(order);
-                    let t = (raw_order[2].parse::<f64>().unwrap() * 1000.0) as i64;
+                    let t = parse_order_time(raw_order)?;
                     timestamps.push(t);
                 }
             }
+            Ok(())
         };
         if arr.len() == 4 {
             let update =
                 serde_json::from_value::<OrderbookUpdate>(arr[1].clone()).map_err(|_e| {
-                    SimpleError::new(format!("Failed to deserialize {} to OrderbookUpdate", arr[1]))
+                    ParseError::malformed(format!(
+                        "Failed to deserialize {} to OrderbookUpdate",
+                        arr[1]
+                    ))
                 })?;
-            process_update(update);
+            process_update(update)?;
         } else if arr.len() == 5 {
             let update =
                 serde_json::from_value::<OrderbookUpdate>(arr[1].clone()).map_err(|_e| {
-                    SimpleError::new(format!("Failed to deserialize {} to OrderbookUpdate", arr[1]))
+                    ParseError::malformed(format!(
+                        "Failed to deserialize {} to OrderbookUpdate",
+                        arr[1]
+                    ))
                 })?;
-            process_update(update);
+            process_update(update)?;
             let update =
                 serde_json::from_value::<OrderbookUpdate>(arr[2].clone()).map_err(|_e| {
-                    SimpleError::new(format!("Failed to deserialize {} to OrderbookUpdate", arr[2]))
+                    ParseError::malformed(format!(
+                        "Failed to deserialize {} to OrderbookUpdate",
+                        arr[2]
+                    ))
                 })?;
-            process_update(update);
+            process_update(update)?;
         } else {
-            return Err(SimpleError::new(format!("Unknown message format {msg}")));
+            return Err(ParseError::unsupported("Unknown message format"));
         };
 
-        let timestamp = if timestamps.is_empty() {
-            None
-        } else {
-            let max = *timestamps.iter().max().unwrap();
-            Some(max)
-        };
+        let timestamp = timestamps.into_iter().max();
         if let Some(timestamp) = timestamp {
             vec![OrderBookMsg {
                 exchange: EXCHANGE_NAME.to_string(),
@@ -377,6 +418,60 @@ pub(crate) fn parse_l2(msg: &str) -> Result<Vec<OrderBookMsg>, SimpleError> {
     Ok(orderbooks)
 }
 
+pub(super) fn parse_l2_levels(msg: &str) -> Result<L2Levels, ParseError> {
+    let arr = serde_json::from_str::<Vec<Value>>(msg)
+        .map_err(|_e| ParseError::malformed("expected Vec<Value>"))?;
+    if arr.len() < 4 {
+        return Err(ParseError::unsupported("Unknown message format"));
+    }
+    let channel = arr[arr.len() - 2].as_str().unwrap_or_default();
+    let parse_levels = |raw_levels: Option<&Value>| -> Result<Vec<[String; 2]>, ParseError> {
+        if let Some(raw_levels) = raw_levels {
+            let raw_levels = serde_json::from_value::<Vec<Vec<String>>>(raw_levels.clone())?;
+            raw_levels
+                .into_iter()
+                .map(|x| match x.as_slice() {
+                    [price, volume, ..] => Ok([price.clone(), volume.clone()]),
+                    _ => Err(ParseError::malformed("expected [price, volume, timestamp]")),
+                })
+                .collect()
+        } else {
+            Ok(Vec::new())
+        }
+    };
+
+    let mut levels = L2Levels {
+        snapshot: false,
+        asks: Vec::new(),
+        bids: Vec::new(),
+        checksum: None,
+        depth: channel.strip_prefix("book-").and_then(|x| x.parse::<usize>().ok()),
+    };
+    for obj in arr[1..arr.len() - 2].iter() {
+        let obj = obj.as_object().ok_or_else(|| ParseError::malformed("expected an object"))?;
+        if obj.contains_key("as") || obj.contains_key("bs") {
+            levels.snapshot = true;
+        }
+        levels.asks.extend(parse_levels(obj.get("as").or_else(|| obj.get("a")))?);
+        levels.bids.extend(parse_levels(obj.get("bs").or_else(|| obj.get("b")))?);
+        if let Some(checksum) = obj.get("c").and_then(|x| x.as_str()) {
+            levels.checksum = Some(checksum.parse::<u32>()?);
+        }
+    }
+    Ok(levels)
+}
+
+// https://docs.kraken.com/websockets/#book-checksum
+pub(super) fn calc_checksum(asks: &[&[String; 2]], bids: &[&[String; 2]]) -> u32 {
+    let mut hasher = crc32fast::Hasher::new();
+    for level in asks.iter().take(10).chain(bids.iter().take(10)) {
+        for field in level.iter() {
+            hasher.update(field.replace('.', "").trim_start_matches('0').as_bytes());
+        }
+    }
+    hasher.finalize()
+}
+
 #[derive(Serialize, Deserialize)]
 struct RawBboMsgSpot {
     bid_price: String,
@@ -386,31 +481,34 @@ struct RawBboMsgSpot {
     ask_volume: String,
 }
 
-pub(super) fn parse_bbo(msg: &str, _received_at: Option<i64>) -> Result<Vec<BboMsg>, SimpleError> {
-    let ws_msg = serde_json::from_str::<Vec<Value>>(msg).map_err(SimpleError::from)?;
+pub(super) fn parse_bbo(msg: &str, _received_at: Option<i64>) -> Result<Vec<BboMsg>, ParseError> {
+    let ws_msg = parse_ws_msg(msg)?;
 
-    let raw_bbo_msg_spot = serde_json::from_value::<RawBboMsgSpot>(ws_msg[1].clone()).unwrap();
-    let timestamp = (ws_msg[1][2].as_str().unwrap().parse::<f64>().unwrap() * 1000.0) as i64;
+    let raw_bbo_msg_spot = serde_json::from_value::<RawBboMsgSpot>(ws_msg[1].clone())?;
+    let timestamp = (raw_bbo_msg_spot.timestamp.parse::<f64>()? * 1000.0) as i64;
 
-    let symbol = ws_msg[ws_msg.len() - 1].as_str().unwrap();
+    let symbol = extract_pair(&ws_msg)?;
 
-    let pair = crypto_pair::normalize_pair(symbol, EXCHANGE_NAME).unwrap();
+    let pair = crypto_pair::normalize_pair(symbol, EXCHANGE_NAME)
+        .ok_or_else(|| ParseError::symbol_normalization(symbol))?;
 
     let (ask_quantity_base, ask_quantity_quote, ask_quantity_contract) = calc_quantity_and_volume(
         EXCHANGE_NAME,
         MarketType::Spot,
         &pair,
-        raw_bbo_msg_spot.ask_price.parse::<f64>().unwrap(),
-        raw_bbo_msg_spot.ask_volume.parse::<f64>().unwrap(),
-    );
+        raw_bbo_msg_spot.ask_price.parse::<f64>()?,
+        raw_bbo_msg_spot.ask_volume.parse::<f64>()?,
+        timestamp,
+    )?;
 
     let (bid_quantity_base, bid_quantity_quote, bid_quantity_contract) = calc_quantity_and_volume(
         EXCHANGE_NAME,
         MarketType::Spot,
         &pair,
-        raw_bbo_msg_spot.bid_price.parse::<f64>().unwrap(),
-        raw_bbo_msg_spot.bid_volume.parse::<f64>().unwrap(),
-    );
+        raw_bbo_msg_spot.bid_price.parse::<f64>()?,
+        raw_bbo_msg_spot.bid_volume.parse::<f64>()?,
+        timestamp,
+    )?;
 
     let bbo_msg = BboMsg {
         exchange: EXCHANGE_NAME.to_string(),
@@ -419,14 +517,18 @@ pub(super) fn parse_bbo(msg: &str, _received_at: Option<i64>) -> Result<Vec<BboM
         pair,
         msg_type: MessageType::BBO,
         timestamp,
-        ask_price: raw_bbo_msg_spot.ask_price.parse::<f64>().unwrap(),
+        ask_price: raw_bbo_msg_spot.ask_price.parse::<f64>()?,
         ask_quantity_base,
         ask_quantity_quote,
         ask_quantity_contract,
-        bid_price: raw_bbo_msg_spot.bid_price.parse::<f64>().unwrap(),
+        bid_price: raw_bbo_msg_spot.bid_price.parse::<f64>()?,
         bid_quantity_base,
         bid_quantity_quote,
         bid_quantity_contract,
+        bid_price_exact: parse_exact(&raw_bbo_msg_spot.bid_price),
+        bid_quantity_exact: parse_exact(&raw_bbo_msg_spot.bid_volume),
+        ask_price_exact: parse_exact(&raw_bbo_msg_spot.ask_price),
+        ask_quantity_exact: parse_exact(&raw_bbo_msg_spot.ask_volume),
         id: None,
         json: msg.to_string(),
     };
@@ -434,6 +536,78 @@ pub(super) fn parse_bbo(msg: &str, _received_at: Option<i64>) -> Result<Vec<BboM
     Ok(vec![bbo_msg])
 }
 
+// https://docs.kraken.com/websockets/#message-ticker
+//
+// Each field is an array of today's value and last 24 hours value, except a, b and c.
+#[derive(Serialize, Deserialize)]
+struct RawTickerMsg {
+    a: Vec<Value>,  // ask, [price, wholeLotVolume, lotVolume]
+    b: Vec<Value>,  // bid, [price, wholeLotVolume, lotVolume]
+    c: [String; 2], // close, [price, lot volume]
+    v: [String; 2], // volume
+    p: [String; 2], // volume weighted average price
+    l: [String; 2], // low
+    h: [String; 2], // high
+    o: [String; 2], // open
+    #[serde(flatten)]
+    extra: HashMap<String, Value>,
+}
+
+/// Ticker messages have no timestamp, so `received_at` is required.
+pub(super) fn parse_ticker(
+    msg: &str,
+    received_at: Option<i64>,
+) -> Result<Vec<TickerMsg>, ParseError> {
+    let arr = serde_json::from_str::<Vec<Value>>(msg).map_err(ParseError::from)?;
+    if arr.len() != 4 {
+        return Err(ParseError::unsupported("Invalid ticker message"));
+    }
+    if arr[2].as_str() != Some("ticker") {
+        return Err(ParseError::unsupported(format!("Unsupported channel {}", arr[2])));
+    }
+    let raw_ticker = serde_json::from_value::<RawTickerMsg>(arr[1].clone())
+        .map_err(|_e| ParseError::malformed("expected RawTickerMsg"))?;
+    let symbol =
+        arr[3].as_str().ok_or_else(|| ParseError::unsupported("Failed to extract symbol"))?;
+    let pair = crypto_pair::normalize_pair(symbol, EXCHANGE_NAME)
+        .ok_or_else(|| ParseError::symbol_normalization(symbol))?;
+    let timestamp = received_at.ok_or_else(ParseError::missing_timestamp)?;
+
+    let parse_f64 = |s: &str| -> Result<f64, ParseError> {
+        s.parse::<f64>().map_err(|_e| ParseError::malformed(format!("Failed to parse {s} as f64")))
+    };
+    let parse_value = |v: Option<&Value>| -> Option<f64> {
+        v.and_then(|x| x.as_str()).and_then(|x| x.parse::<f64>().ok())
+    };
+    let volume = parse_f64(&raw_ticker.v[1])?;
+    let vwap = parse_f64(&raw_ticker.p[1])?;
+
+    let ticker_msg = TickerMsg {
+        exchange: EXCHANGE_NAME.to_string(),
+        market_type: MarketType::Spot,
+        symbol: symbol.to_string(),
+        pair,
+        msg_type: MessageType::Ticker,
+        timestamp,
+        open: parse_f64(&raw_ticker.o[1])?,
+        high: parse_f64(&raw_ticker.h[1])?,
+        low: parse_f64(&raw_ticker.l[1])?,
+        close: parse_f64(&raw_ticker.c[0])?,
+        volume,
+        quote_volume: vwap * volume,
+        last_quantity: Some(parse_f64(&raw_ticker.c[1])?),
+        best_bid_price: parse_value(raw_ticker.b.first()),
+        best_bid_quantity: parse_value(raw_ticker.b.get(2)),
+        best_ask_price: parse_value(raw_ticker.a.first()),
+        best_ask_quantity: parse_value(raw_ticker.a.get(2)),
+        open_interest: None,
+        open_interest_quote: None,
+        json: msg.to_string(),
+    };
+
+    Ok(vec![ticker_msg])
+}
+
 // https://docs.kraken.com/websockets/#message-ohlc
 #[derive(Serialize, Deserialize)]
 struct RawCandlestickMsg {
@@ -448,21 +622,30 @@ struct RawCandlestickMsg {
     count: i64,
 }
 
-pub(super) fn parse_candlestick(msg: &str) -> Result<Vec<CandlestickMsg>, SimpleError> {
-    let arr = serde_json::from_str::<Vec<Value>>(msg).map_err(SimpleError::from)?;
-
-    let raw_candlestick_msg = serde_json::from_value::<RawCandlestickMsg>(arr[1].clone()).unwrap();
-    let timestamp = (raw_candlestick_msg.time.parse::<f64>().unwrap() * 1000.0) as i64;
-
-    let end_time = (raw_candlestick_msg.etime.parse::<f64>().unwrap()) as i64;
-    let period = arr[arr.len() - 2].as_str().unwrap().strip_prefix("ohlc-").unwrap();
-    let begin_time = end_time - period.parse::<i64>().unwrap() * 60;
-
-    let symbol = arr[arr.len() - 1].as_str().unwrap();
-    let pair = crypto_pair::normalize_pair(symbol, EXCHANGE_NAME).unwrap();
+pub(super) fn parse_candlestick(msg: &str) -> Result<Vec<CandlestickMsg>, ParseError> {
+    let arr = parse_ws_msg(msg)?;
+
+    let raw_candlestick_msg = serde_json::from_value::<RawCandlestickMsg>(arr[1].clone())?;
+    let timestamp = (raw_candlestick_msg.time.parse::<f64>()? * 1000.0) as i64;
+
+    let end_time = (raw_candlestick_msg.etime.parse::<f64>()? * 1000.0) as i64;
+    let channel = extract_channel(&arr)?;
+    let interval = channel
+        .strip_prefix("ohlc-")
+        .ok_or_else(|| ParseError::unsupported(format!("Unsupported channel {channel}")))?;
+    let period = parse_candle_period(EXCHANGE_NAME, MarketType::Spot, interval)?;
+    // intervals are 1 minute to 15 days
+    let period_millis = period
+        .as_millis()
+        .ok_or_else(|| ParseError::unsupported(format!("Unsupported interval {interval}")))?;
+    let begin_time = end_time - period_millis;
+
+    let symbol = extract_pair(&arr)?;
+    let pair = crypto_pair::normalize_pair(symbol, EXCHANGE_NAME)
+        .ok_or_else(|| ParseError::symbol_normalization(symbol))?;
 
-    let vwap = raw_candlestick_msg.vwap.parse::<f64>().unwrap();
-    let volume = raw_candlestick_msg.volume.parse::<f64>().unwrap();
+    let vwap = raw_candlestick_msg.vwap.parse::<f64>()?;
+    let volume = raw_candlestick_msg.volume.parse::<f64>()?;
 
     let candlestick_msg = CandlestickMsg {
         exchange: EXCHANGE_NAME.to_string(),
@@ -471,14 +654,16 @@ pub(super) fn parse_candlestick(msg: &str) -> Result<Vec<CandlestickMsg>, Simple
         symbol: symbol.to_string(),
         pair,
         timestamp,
-        period: period.to_string(),
+        period,
         begin_time,
-        open: raw_candlestick_msg.open.parse::<f64>().unwrap(),
-        high: raw_candlestick_msg.high.parse::<f64>().unwrap(),
-        low: raw_candlestick_msg.low.parse::<f64>().unwrap(),
-        close: raw_candlestick_msg.close.parse::<f64>().unwrap(),
+        end_time,
+        open: raw_candlestick_msg.open.parse::<f64>()?,
+        high: raw_candlestick_msg.high.parse::<f64>()?,
+        low: raw_candlestick_msg.low.parse::<f64>()?,
+        close: raw_candlestick_msg.close.parse::<f64>()?,
         volume,
         quote_volume: Some(vwap * volume),
+        is_closed: None,
         json: msg.to_string(),
     };
 
@@ -6,36 +6,39 @@ use std::collections::HashMap;
 use crypto_market_type::MarketType;
 use crypto_msg_type::MessageType;
 
-use crate::{BboMsg, CandlestickMsg, OrderBookMsg, TradeMsg};
+use crate::{
+    checksum::L2Levels, BboMsg, CandlestickMsg, FundingRateMsg, OrderBookMsg, ParseError,
+    TickerMsg, TradeMsg,
+};
 
 use serde_json::Value;
-use simple_error::SimpleError;
 
-pub(crate) fn extract_symbol(market_type: MarketType, msg: &str) -> Result<String, SimpleError> {
+pub(crate) fn extract_symbol(market_type: MarketType, msg: &str) -> Result<String, ParseError> {
     match market_type {
         MarketType::Spot => kraken_spot::extract_symbol(msg),
         MarketType::InverseFuture | MarketType::InverseSwap => kraken_futures::extract_symbol(msg),
-        _ => panic!("Kraken unknown market_type: {market_type}"),
+        _ => Err(ParseError::unsupported(format!("Kraken unknown market_type: {market_type}"))),
     }
 }
 
 pub(crate) fn extract_timestamp(
     market_type: MarketType,
     msg: &str,
-) -> Result<Option<i64>, SimpleError> {
+) -> Result<Option<i64>, ParseError> {
     match market_type {
         MarketType::Spot => kraken_spot::extract_timestamp(msg),
         MarketType::InverseFuture | MarketType::InverseSwap => {
             kraken_futures::extract_timestamp(msg)
         }
-        _ => panic!("Kraken unknown market_type: {market_type}"),
+        _ => Err(ParseError::unsupported(format!("Kraken unknown market_type: {market_type}"))),
     }
 }
 
 pub(crate) fn get_msg_type(msg: &str) -> MessageType {
     if let Ok(arr) = serde_json::from_str::<Vec<Value>>(msg) {
         // spot
-        let channel = arr[arr.len() - 2].as_str().unwrap();
+        let channel = if arr.len() >= 2 { arr[arr.len() - 2].as_str() } else { None };
+        let channel = channel.unwrap_or_default();
         if channel == "ticker" {
             MessageType::Ticker
         } else if channel == "trade" {
@@ -52,10 +55,11 @@ pub(crate) fn get_msg_type(msg: &str) -> MessageType {
     } else if let Ok(obj) = serde_json::from_str::<HashMap<String, Value>>(msg) {
         // futures
         if let Some(feed) = obj.get("feed") {
-            match feed.as_str().unwrap() {
+            match feed.as_str().unwrap_or_default() {
                 "trade" | "trade_snapshot" => MessageType::Trade,
                 "ticker" => MessageType::Ticker,
                 "book" | "book_snapshot" => MessageType::L2Event,
+                feed if feed.starts_with("candles_trade_") => MessageType::Candlestick,
                 _ => MessageType::Other,
             }
         } else {
@@ -66,45 +70,75 @@ pub(crate) fn get_msg_type(msg: &str) -> MessageType {
     }
 }
 
-pub(crate) fn parse_trade(
-    market_type: MarketType,
-    msg: &str,
-) -> Result<Vec<TradeMsg>, SimpleError> {
+pub(crate) fn parse_trade(market_type: MarketType, msg: &str) -> Result<Vec<TradeMsg>, ParseError> {
     match market_type {
         MarketType::Spot => kraken_spot::parse_trade(msg),
         MarketType::InverseFuture | MarketType::InverseSwap => kraken_futures::parse_trade(msg),
-        _ => panic!("Kraken unknown market_type: {market_type}"),
+        _ => Err(ParseError::unsupported(format!("Kraken unknown market_type: {market_type}"))),
     }
 }
 
 pub(crate) fn parse_l2(
     market_type: MarketType,
     msg: &str,
-) -> Result<Vec<OrderBookMsg>, SimpleError> {
+) -> Result<Vec<OrderBookMsg>, ParseError> {
     match market_type {
         MarketType::Spot => kraken_spot::parse_l2(msg),
         MarketType::InverseFuture | MarketType::InverseSwap => kraken_futures::parse_l2(msg),
-        _ => panic!("Kraken unknown market_type: {market_type}"),
+        _ => Err(ParseError::unsupported(format!("Kraken unknown market_type: {market_type}"))),
     }
 }
 
+pub(crate) fn parse_l2_levels(msg: &str) -> Result<L2Levels, ParseError> {
+    kraken_spot::parse_l2_levels(msg)
+}
+
+pub(crate) fn calc_checksum(asks: &[&[String; 2]], bids: &[&[String; 2]]) -> u32 {
+    kraken_spot::calc_checksum(asks, bids)
+}
+
 pub(crate) fn parse_bbo(
     market_type: MarketType,
     msg: &str,
     received_at: Option<i64>,
-) -> Result<Vec<BboMsg>, SimpleError> {
+) -> Result<Vec<BboMsg>, ParseError> {
     match market_type {
         MarketType::Spot => kraken_spot::parse_bbo(msg, received_at),
-        _ => Err(SimpleError::new("Not implemented")),
+        _ => Err(ParseError::unsupported("Not implemented")),
+    }
+}
+
+pub(crate) fn parse_ticker(
+    market_type: MarketType,
+    msg: &str,
+    received_at: Option<i64>,
+) -> Result<Vec<TickerMsg>, ParseError> {
+    match market_type {
+        MarketType::Spot => kraken_spot::parse_ticker(msg, received_at),
+        // Kraken Futures tickers have no 24h open, high and low prices
+        _ => Err(ParseError::unsupported("Not implemented")),
     }
 }
 
 pub(crate) fn parse_candlestick(
     market_type: MarketType,
     msg: &str,
-) -> Result<Vec<CandlestickMsg>, SimpleError> {
+) -> Result<Vec<CandlestickMsg>, ParseError> {
     match market_type {
         MarketType::Spot => kraken_spot::parse_candlestick(msg),
-        _ => Err(SimpleError::new("Not implemented")),
+        MarketType::InverseFuture | MarketType::InverseSwap => {
+            kraken_futures::parse_candlestick(market_type, msg)
+        }
+        _ => Err(ParseError::unsupported(format!("Unsupported market_type {market_type}"))),
+    }
+}
+
+pub(crate) fn parse_funding_rate(
+    market_type: MarketType,
+    msg: &str,
+) -> Result<Vec<FundingRateMsg>, ParseError> {
+    match market_type {
+        MarketType::InverseSwap => kraken_futures::parse_funding_rate(market_type, msg),
+        _ => Err(ParseError::unsupported(format!("{market_type} markets have no funding rate"))),
     }
 }
@@ -1,11 +1,11 @@
 use crypto_market_type::MarketType;
-use crypto_message::{BboMsg, CandlestickMsg, Order, OrderBookMsg, TradeMsg, TradeSide};
+use crypto_message::{BboMsg, CandlestickMsg, Order, OrderBookMsg, TickerMsg, TradeMsg, TradeSide};
 use crypto_msg_type::MessageType;
 
-use super::super::utils::calc_quantity_and_volume;
+use super::super::utils::{calc_quantity_and_volume, parse_candle_period, parse_exact};
+use crate::ParseError;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
-use simple_error::SimpleError;
 use std::collections::HashMap;
 
 use super::message::WebsocketMsg;
@@ -69,6 +69,30 @@ struct RawBboMsg {
     time: i64,
 }
 
+// See <https://docs.kucoin.com/#symbol-snapshot>
+#[derive(Serialize, Deserialize)]
+#[allow(non_snake_case)]
+struct RawTickerMsg {
+    symbol: String,
+    open: f64,
+    high: f64,
+    low: f64,
+    close: f64,
+    vol: f64,      // volume in base currency
+    volValue: f64, // volume in quote currency
+    buy: Option<f64>,
+    sell: Option<f64>,
+    datetime: i64,
+    #[serde(flatten)]
+    extra: HashMap<String, Value>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct RawSnapshotMsg {
+    sequence: String,
+    data: RawTickerMsg,
+}
+
 // See <https://docs.kucoin.com/#klines>
 #[derive(Serialize, Deserialize)]
 struct RawCandlestickMsg {
@@ -77,29 +101,33 @@ struct RawCandlestickMsg {
     candles: Vec<String>,
 }
 
-pub(super) fn parse_trade(msg: &str) -> Result<Vec<TradeMsg>, SimpleError> {
-    let ws_msg = serde_json::from_str::<WebsocketMsg<SpotTradeMsg>>(msg).map_err(|_e| {
-        SimpleError::new(format!("Failed to deserialize {msg} to WebsocketMsg<SpotOrderbookMsg>"))
-    })?;
-    debug_assert_eq!(ws_msg.subject, "trade.l3match");
-    debug_assert!(ws_msg.topic.starts_with("/market/match:"));
+pub(super) fn parse_trade(msg: &str) -> Result<Vec<TradeMsg>, ParseError> {
+    let ws_msg = serde_json::from_str::<WebsocketMsg<SpotTradeMsg>>(msg)
+        .map_err(|_e| ParseError::malformed("expected WebsocketMsg<SpotOrderbookMsg>"))?;
+    if ws_msg.subject != "trade.l3match" {
+        return Err(ParseError::unsupported(format!("Unsupported subject {}", ws_msg.subject)));
+    }
+    if !ws_msg.topic.starts_with("/market/match:") {
+        return Err(ParseError::unsupported(format!("Unsupported topic {}", ws_msg.topic)));
+    }
     let raw_trade = ws_msg.data;
-    let price = raw_trade.price.parse::<f64>().unwrap();
-    let quantity = raw_trade.size.parse::<f64>().unwrap();
+    let price = raw_trade.price.parse::<f64>()?;
+    let quantity = raw_trade.size.parse::<f64>()?;
 
     let trade = TradeMsg {
         exchange: EXCHANGE_NAME.to_string(),
         market_type: MarketType::Spot,
         symbol: raw_trade.symbol.clone(),
-        pair: crypto_pair::normalize_pair(&raw_trade.symbol, EXCHANGE_NAME).ok_or_else(|| {
-            SimpleError::new(format!("Failed to normalize {} from {}", raw_trade.symbol, msg))
-        })?,
+        pair: crypto_pair::normalize_pair(&raw_trade.symbol, EXCHANGE_NAME)
+            .ok_or_else(|| ParseError::symbol_normalization(&raw_trade.symbol))?,
         msg_type: MessageType::Trade,
-        timestamp: raw_trade.time.parse::<i64>().unwrap() / 1000000,
+        timestamp: raw_trade.time.parse::<i64>()? / 1000000,
         price,
         quantity_base: quantity,
         quantity_quote: price * quantity,
         quantity_contract: None,
+        price_exact: parse_exact(&raw_trade.price),
+        quantity_exact: parse_exact(&raw_trade.size),
         side: if raw_trade.side == "sell" { TradeSide::Sell } else { TradeSide::Buy },
         trade_id: raw_trade.sequence.to_string(),
         json: msg.to_string(),
@@ -108,26 +136,31 @@ pub(super) fn parse_trade(msg: &str) -> Result<Vec<TradeMsg>, SimpleError> {
     Ok(vec![trade])
 }
 
-pub(super) fn parse_l2(msg: &str, timestamp: i64) -> Result<Vec<OrderBookMsg>, SimpleError> {
-    let ws_msg = serde_json::from_str::<WebsocketMsg<SpotOrderbookMsg>>(msg).map_err(|_e| {
-        SimpleError::new(format!("Failed to deserialize {msg} to WebsocketMsg<SpotOrderbookMsg>"))
-    })?;
-    debug_assert_eq!(ws_msg.subject, "trade.l2update");
-    debug_assert!(ws_msg.topic.starts_with("/market/level2:"));
+pub(super) fn parse_l2(msg: &str, timestamp: i64) -> Result<Vec<OrderBookMsg>, ParseError> {
+    let ws_msg = serde_json::from_str::<WebsocketMsg<SpotOrderbookMsg>>(msg)
+        .map_err(|_e| ParseError::malformed("expected WebsocketMsg<SpotOrderbookMsg>"))?;
+    if ws_msg.subject != "trade.l2update" {
+        return Err(ParseError::unsupported(format!("Unsupported subject {}", ws_msg.subject)));
+    }
+    if !ws_msg.topic.starts_with("/market/level2:") {
+        return Err(ParseError::unsupported(format!("Unsupported topic {}", ws_msg.topic)));
+    }
     let symbol = ws_msg.data.symbol;
     let pair = crypto_pair::normalize_pair(&symbol, EXCHANGE_NAME)
-        .ok_or_else(|| SimpleError::new(format!("Failed to normalize {symbol} from {msg}")))?;
+        .ok_or_else(|| ParseError::symbol_normalization(&symbol))?;
 
-    let parse_order = |raw_order: &[String; 3]| -> Order {
-        let price = raw_order[0].parse::<f64>().unwrap();
-        let quantity_base = raw_order[1].parse::<f64>().unwrap();
+    let parse_order = |raw_order: &[String; 3]| -> Result<Order, ParseError> {
+        let price = raw_order[0].parse::<f64>()?;
+        let quantity_base = raw_order[1].parse::<f64>()?;
 
-        Order {
+        Ok(Order {
             price,
             quantity_base,
             quantity_quote: price * quantity_base,
             quantity_contract: None,
-        }
+            price_exact: parse_exact(&raw_order[0]),
+            quantity_exact: parse_exact(&raw_order[1]),
+        })
     };
 
     let orderbook = OrderBookMsg {
@@ -137,10 +170,23 @@ pub(super) fn parse_l2(msg: &str, timestamp: i64) -> Result<Vec<OrderBookMsg>, S
         pair,
         msg_type: MessageType::L2Event,
         timestamp: ws_msg.data.time.unwrap_or(timestamp),
-        seq_id: Some(ws_msg.data.sequenceStart as u64),
-        prev_seq_id: None,
-        asks: ws_msg.data.changes.asks.iter().map(parse_order).collect(),
-        bids: ws_msg.data.changes.bids.iter().map(parse_order).collect(),
+        seq_id: Some(ws_msg.data.sequenceEnd as u64),
+        // the next message starts from sequenceEnd + 1
+        prev_seq_id: Some(ws_msg.data.sequenceStart as u64 - 1),
+        asks: ws_msg
+            .data
+            .changes
+            .asks
+            .iter()
+            .map(parse_order)
+            .collect::<Result<Vec<Order>, ParseError>>()?,
+        bids: ws_msg
+            .data
+            .changes
+            .bids
+            .iter()
+            .map(parse_order)
+            .collect::<Result<Vec<Order>, ParseError>>()?,
         snapshot: false,
         json: msg.to_string(),
     };
@@ -148,26 +194,36 @@ pub(super) fn parse_l2(msg: &str, timestamp: i64) -> Result<Vec<OrderBookMsg>, S
     Ok(vec![orderbook])
 }
 
-pub(super) fn parse_l2_topk(msg: &str) -> Result<Vec<OrderBookMsg>, SimpleError> {
-    let ws_msg = serde_json::from_str::<WebsocketMsg<SpotL2TopKMsg>>(msg).map_err(|_e| {
-        SimpleError::new(format!("Failed to deserialize {msg} to WebsocketMsg<SpotOrderbookMsg>"))
-    })?;
-    debug_assert_eq!(ws_msg.subject, "level2");
-    debug_assert!(ws_msg.topic.starts_with("/spotMarket/level2Depth5:"));
-    let symbol = ws_msg.topic.split(':').last().unwrap();
-    let pair = crypto_pair::normalize_pair(symbol, EXCHANGE_NAME).unwrap();
+pub(super) fn parse_l2_topk(msg: &str) -> Result<Vec<OrderBookMsg>, ParseError> {
+    let ws_msg = serde_json::from_str::<WebsocketMsg<SpotL2TopKMsg>>(msg)
+        .map_err(|_e| ParseError::malformed("expected WebsocketMsg<SpotOrderbookMsg>"))?;
+    if ws_msg.subject != "level2" {
+        return Err(ParseError::unsupported(format!("Unsupported subject {}", ws_msg.subject)));
+    }
+    if !ws_msg.topic.starts_with("/spotMarket/level2Depth5:") {
+        return Err(ParseError::unsupported(format!("Unsupported topic {}", ws_msg.topic)));
+    }
+    let symbol = ws_msg
+        .topic
+        .split(':')
+        .next_back()
+        .ok_or_else(|| ParseError::unsupported(format!("Invalid topic {}", ws_msg.topic)))?;
+    let pair = crypto_pair::normalize_pair(symbol, EXCHANGE_NAME)
+        .ok_or_else(|| ParseError::symbol_normalization(symbol))?;
     let timestamp = ws_msg.data.timestamp;
 
-    let parse_order = |raw_order: &[String; 2]| -> Order {
-        let price = raw_order[0].parse::<f64>().unwrap();
-        let quantity_base = raw_order[1].parse::<f64>().unwrap();
+    let parse_order = |raw_order: &[String; 2]| -> Result<Order, ParseError> {
+        let price = raw_order[0].parse::<f64>()?;
+        let quantity_base = raw_order[1].parse::<f64>()?;
 
-        Order {
+        Ok(Order {
             price,
             quantity_base,
             quantity_quote: price * quantity_base,
             quantity_contract: None,
-        }
+            price_exact: parse_exact(&raw_order[0]),
+            quantity_exact: parse_exact(&raw_order[1]),
+        })
     };
 
     let orderbook = OrderBookMsg {
@@ -179,8 +235,18 @@ pub(super) fn parse_l2_topk(msg: &str) -> Result<Vec<OrderBookMsg>, SimpleError>
         timestamp,
         seq_id: None,
         prev_seq_id: None,
-        asks: ws_msg.data.asks.iter().map(parse_order).collect(),
-        bids: ws_msg.data.bids.iter().map(parse_order).collect(),
+        asks: ws_msg
+            .data
+            .asks
+            .iter()
+            .map(parse_order)
+            .collect::<Result<Vec<Order>, ParseError>>()?,
+        bids: ws_msg
+            .data
+            .bids
+            .iter()
+            .map(parse_order)
+            .collect::<Result<Vec<Order>, ParseError>>()?,
         snapshot: true,
         json: msg.to_string(),
     };
@@ -188,32 +254,38 @@ pub(super) fn parse_l2_topk(msg: &str) -> Result<Vec<OrderBookMsg>, SimpleError>
     Ok(vec![orderbook])
 }
 
-pub(super) fn parse_bbo(msg: &str) -> Result<Vec<BboMsg>, SimpleError> {
-    let ws_msg = serde_json::from_str::<WebsocketMsg<RawBboMsg>>(msg).map_err(SimpleError::from)?;
+pub(super) fn parse_bbo(msg: &str) -> Result<Vec<BboMsg>, ParseError> {
+    let ws_msg = serde_json::from_str::<WebsocketMsg<RawBboMsg>>(msg).map_err(ParseError::from)?;
     let topic = ws_msg.topic.as_str();
 
     let symbol = if topic == "/market/ticker:all" {
         ws_msg.subject.as_str()
     } else {
-        topic.split(':').last().unwrap()
+        topic
+            .split(':')
+            .next_back()
+            .ok_or_else(|| ParseError::unsupported(format!("Invalid topic {topic}")))?
     };
-    let pair = crypto_pair::normalize_pair(symbol, EXCHANGE_NAME).unwrap();
+    let pair = crypto_pair::normalize_pair(symbol, EXCHANGE_NAME)
+        .ok_or_else(|| ParseError::symbol_normalization(symbol))?;
 
     let (ask_quantity_base, ask_quantity_quote, ask_quantity_contract) = calc_quantity_and_volume(
         EXCHANGE_NAME,
         MarketType::Spot,
         &pair,
-        ws_msg.data.bestAsk.parse::<f64>().unwrap(),
-        ws_msg.data.bestAskSize.parse::<f64>().unwrap(),
-    );
+        ws_msg.data.bestAsk.parse::<f64>()?,
+        ws_msg.data.bestAskSize.parse::<f64>()?,
+        ws_msg.data.time,
+    )?;
 
     let (bid_quantity_base, bid_quantity_quote, bid_quantity_contract) = calc_quantity_and_volume(
         EXCHANGE_NAME,
         MarketType::Spot,
         &pair,
-        ws_msg.data.bestBid.parse::<f64>().unwrap(),
-        ws_msg.data.bestBidSize.parse::<f64>().unwrap(),
-    );
+        ws_msg.data.bestBid.parse::<f64>()?,
+        ws_msg.data.bestBidSize.parse::<f64>()?,
+        ws_msg.data.time,
+    )?;
 
     let bbo_msg = BboMsg {
         exchange: EXCHANGE_NAME.to_string(),
@@ -222,36 +294,129 @@ pub(super) fn parse_bbo(msg: &str) -> Result<Vec<BboMsg>, SimpleError> {
         pair,
         msg_type: MessageType::BBO,
         timestamp: ws_msg.data.time,
-        ask_price: ws_msg.data.bestAsk.parse::<f64>().unwrap(),
+        ask_price: ws_msg.data.bestAsk.parse::<f64>()?,
         ask_quantity_base,
         ask_quantity_quote,
         ask_quantity_contract,
-        bid_price: ws_msg.data.bestBid.parse::<f64>().unwrap(),
+        bid_price: ws_msg.data.bestBid.parse::<f64>()?,
         bid_quantity_base,
         bid_quantity_quote,
         bid_quantity_contract,
-        id: Some(ws_msg.data.sequence.as_str().parse::<u64>().unwrap()),
+        bid_price_exact: parse_exact(&ws_msg.data.bestBid),
+        bid_quantity_exact: parse_exact(&ws_msg.data.bestBidSize),
+        ask_price_exact: parse_exact(&ws_msg.data.bestAsk),
+        ask_quantity_exact: parse_exact(&ws_msg.data.bestAskSize),
+        id: Some(ws_msg.data.sequence.as_str().parse::<u64>()?),
         json: msg.to_string(),
     };
     Ok(vec![bbo_msg])
 }
 
-pub(super) fn parse_candlestick(msg: &str) -> Result<Vec<CandlestickMsg>, SimpleError> {
-    let ws_msg =
-        serde_json::from_str::<WebsocketMsg<RawCandlestickMsg>>(msg).map_err(SimpleError::from)?;
+// Both `/market/snapshot:{symbol}` and `/market/snapshot:{market}` share the same
+// format, one symbol per message, and so does `/market/ticker:all`.
+pub(super) fn parse_ticker(msg: &str) -> Result<Vec<TickerMsg>, ParseError> {
+    let ws_msg = serde_json::from_str::<WebsocketMsg<Value>>(msg)
+        .map_err(|_e| ParseError::malformed("expected WebsocketMsg<Value>"))?;
+    if ws_msg.topic == "/market/ticker:all" {
+        return parse_all_tickers(msg);
+    }
+    let ws_msg = serde_json::from_str::<WebsocketMsg<RawSnapshotMsg>>(msg)
+        .map_err(|_e| ParseError::malformed("expected WebsocketMsg<RawSnapshotMsg>"))?;
+    if ws_msg.subject != "trade.snapshot" {
+        return Err(ParseError::unsupported(format!("Unsupported subject {}", ws_msg.subject)));
+    }
+    let raw_ticker = ws_msg.data.data;
+    let symbol = raw_ticker.symbol.as_str();
+    let pair = crypto_pair::normalize_pair(symbol, EXCHANGE_NAME)
+        .ok_or_else(|| ParseError::symbol_normalization(symbol))?;
+
+    let ticker_msg = TickerMsg {
+        exchange: EXCHANGE_NAME.to_string(),
+        market_type: MarketType::Spot,
+        symbol: symbol.to_string(),
+        pair,
+        msg_type: MessageType::Ticker,
+        timestamp: raw_ticker.datetime,
+        open: raw_ticker.open,
+        high: raw_ticker.high,
+        low: raw_ticker.low,
+        close: raw_ticker.close,
+        volume: raw_ticker.vol,
+        quote_volume: raw_ticker.volValue,
+        last_quantity: None,
+        best_bid_price: raw_ticker.buy,
+        best_bid_quantity: None,
+        best_ask_price: raw_ticker.sell,
+        best_ask_quantity: None,
+        open_interest: None,
+        open_interest_quote: None,
+        json: msg.to_string(),
+    };
+    Ok(vec![ticker_msg])
+}
 
-    let symbol = ws_msg.data.symbol.as_str();
-    let pair = crypto_pair::normalize_pair(symbol, EXCHANGE_NAME).unwrap();
+// `/market/ticker:all` carries only the last trade and the best bid and ask,
+// so open, high and low are the last price and volumes are zero
+fn parse_all_tickers(msg: &str) -> Result<Vec<TickerMsg>, ParseError> {
+    let ws_msg = serde_json::from_str::<WebsocketMsg<RawBboMsg>>(msg)
+        .map_err(|_e| ParseError::malformed("expected WebsocketMsg<RawBboMsg>"))?;
+    let symbol = ws_msg.subject.as_str();
+    let pair = crypto_pair::normalize_pair(symbol, EXCHANGE_NAME)
+        .ok_or_else(|| ParseError::symbol_normalization(symbol))?;
+    let last = ws_msg.data.price.parse::<f64>()?;
+
+    let ticker_msg = TickerMsg {
+        exchange: EXCHANGE_NAME.to_string(),
+        market_type: MarketType::Spot,
+        symbol: symbol.to_string(),
+        pair,
+        msg_type: MessageType::Ticker,
+        timestamp: ws_msg.data.time,
+        open: last,
+        high: last,
+        low: last,
+        close: last,
+        volume: 0.0,
+        quote_volume: 0.0,
+        last_quantity: Some(ws_msg.data.size.parse::<f64>()?),
+        best_bid_price: Some(ws_msg.data.bestBid.parse::<f64>()?),
+        best_bid_quantity: Some(ws_msg.data.bestBidSize.parse::<f64>()?),
+        best_ask_price: Some(ws_msg.data.bestAsk.parse::<f64>()?),
+        best_ask_quantity: Some(ws_msg.data.bestAskSize.parse::<f64>()?),
+        open_interest: None,
+        open_interest_quote: None,
+        json: msg.to_string(),
+    };
+    Ok(vec![ticker_msg])
+}
 
-    let begin_time = ws_msg.data.candles[0].parse::<i64>().unwrap();
-    let open: f64 = ws_msg.data.candles[1].parse().unwrap();
-    let close: f64 = ws_msg.data.candles[2].parse().unwrap();
-    let high: f64 = ws_msg.data.candles[3].parse().unwrap();
-    let low: f64 = ws_msg.data.candles[4].parse().unwrap();
-    let volume: f64 = ws_msg.data.candles[5].parse().unwrap();
-    let quote_volume: f64 = ws_msg.data.candles[6].parse().unwrap();
+pub(super) fn parse_candlestick(msg: &str) -> Result<Vec<CandlestickMsg>, ParseError> {
+    let ws_msg =
+        serde_json::from_str::<WebsocketMsg<RawCandlestickMsg>>(msg).map_err(ParseError::from)?;
 
-    let period = ws_msg.topic.split(':').last().unwrap().split('_').last().unwrap().to_string();
+    let symbol = ws_msg.data.symbol.as_str();
+    let pair = crypto_pair::normalize_pair(symbol, EXCHANGE_NAME)
+        .ok_or_else(|| ParseError::symbol_normalization(symbol))?;
+
+    if ws_msg.data.candles.len() < 7 {
+        return Err(ParseError::malformed("expected 7 fields in candles"));
+    }
+    let begin_time = ws_msg.data.candles[0].parse::<i64>()? * 1000;
+    let open: f64 = ws_msg.data.candles[1].parse()?;
+    let close: f64 = ws_msg.data.candles[2].parse()?;
+    let high: f64 = ws_msg.data.candles[3].parse()?;
+    let low: f64 = ws_msg.data.candles[4].parse()?;
+    let volume: f64 = ws_msg.data.candles[5].parse()?;
+    let quote_volume: f64 = ws_msg.data.candles[6].parse()?;
+
+    // e.g., /market/candles:BTC-USDT_1hour
+    let interval = ws_msg
+        .topic
+        .split(':')
+        .next_back()
+        .and_then(|x| x.split('_').next_back())
+        .ok_or_else(|| ParseError::unsupported(format!("Invalid topic {}", ws_msg.topic)))?;
+    let period = parse_candle_period(EXCHANGE_NAME, MarketType::Spot, interval)?;
 
     let kline_msg = CandlestickMsg {
         exchange: EXCHANGE_NAME.to_string(),
@@ -262,6 +427,7 @@ pub(super) fn parse_candlestick(msg: &str) -> Result<Vec<CandlestickMsg>, Simple
         timestamp: ws_msg.data.time / 1000000,
 
         begin_time,
+        end_time: period.end_time(begin_time),
         open,
         high,
         low,
@@ -269,6 +435,7 @@ pub(super) fn parse_candlestick(msg: &str) -> Result<Vec<CandlestickMsg>, Simple
         volume,
         quote_volume: Some(quote_volume),
         period,
+        is_closed: None,
         json: msg.to_string(),
     };
 
@@ -1,32 +1,22 @@
 use crypto_market_type::MarketType;
 use crypto_msg_type::MessageType;
 
-use crate::exchanges::{kucoin::message::WebsocketMsg, utils::calc_quantity_and_volume};
-use crypto_message::{BboMsg, CandlestickMsg, Order, OrderBookMsg, TradeMsg, TradeSide};
+use crate::{
+    ParseError,
+    exchanges::{
+        kucoin::message::{RestfulMsg, WebsocketMsg},
+        utils::{annualize_funding_rate, calc_quantity_and_volume, next_funding_time, parse_exact},
+    },
+};
+use crypto_message::{
+    BboMsg, CandlestickMsg, FundingRateMsg, Order, OrderBookMsg, TickerMsg, TradeMsg, TradeSide,
+};
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
-use simple_error::SimpleError;
 use std::collections::HashMap;
 
 const EXCHANGE_NAME: &str = "kucoin";
 
-#[derive(Serialize, Deserialize)]
-#[allow(non_snake_case)]
-struct SwapMarket {
-    baseCurrency: String,
-    multiplier: f64,
-    isInverse: bool,
-    #[serde(flatten)]
-    extra: HashMap<String, Value>,
-}
-
-#[derive(Serialize, Deserialize)]
-#[allow(non_snake_case)]
-struct ResponseMsg {
-    code: String,
-    data: Vec<SwapMarket>,
-}
-
 // https://docs.kucoin.com/futures/#execution-data
 #[derive(Serialize, Deserialize)]
 struct ContractTradeMsg {
@@ -50,24 +40,70 @@ struct ContractOrderbookMsg {
     extra: HashMap<String, Value>,
 }
 
-pub(super) fn parse_trade(
-    market_type: MarketType,
-    msg: &str,
-) -> Result<Vec<TradeMsg>, SimpleError> {
-    let ws_msg = serde_json::from_str::<WebsocketMsg<ContractTradeMsg>>(msg).map_err(|_e| {
-        SimpleError::new(format!("Failed to deserialize {msg} to WebsocketMsg<ContractTradeMsg>"))
-    })?;
-    debug_assert_eq!(ws_msg.subject, "match");
-    debug_assert!(ws_msg.topic.starts_with("/contractMarket/execution:"));
+// https://docs.kucoin.com/futures/#transaction-statistics-timer-event
+#[derive(Serialize, Deserialize)]
+#[allow(non_snake_case)]
+struct RawTickerMsg {
+    symbol: String,
+    volume: f64,   // base volume in linear markets, quote volume in inverse markets
+    turnover: f64, // quote volume in linear markets, base volume in inverse markets
+    lastPrice: f64,
+    lowPrice: f64,
+    highPrice: f64,
+    priceChg: f64,
+    ts: i64, // nanoseconds
+    #[serde(flatten)]
+    extra: HashMap<String, Value>,
+}
+
+// https://docs.kucoin.com/futures/#funding-rate
+#[derive(Serialize, Deserialize)]
+#[allow(non_snake_case)]
+struct RawFundingRateMsg {
+    fundingRate: f64,
+    timestamp: i64,
+    #[serde(flatten)]
+    extra: HashMap<String, Value>,
+}
+
+// https://docs.kucoin.com/futures/#get-open-contract-list
+#[derive(Serialize, Deserialize)]
+#[allow(non_snake_case)]
+struct RawContractMsg {
+    symbol: String,
+    fundingFeeRate: Option<f64>, // null in delivery futures
+    predictedFundingFeeRate: Option<f64>,
+    nextFundingRateTime: Option<i64>, // milliseconds to the next funding
+    #[serde(skip_serializing_if = "Option::is_none")]
+    fundingRateGranularity: Option<i64>, // milliseconds
+    #[serde(skip_serializing_if = "Option::is_none")]
+    fundingRateCap: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    fundingRateFloor: Option<f64>,
+    #[serde(flatten)]
+    extra: HashMap<String, Value>,
+}
+
+pub(super) fn parse_trade(market_type: MarketType, msg: &str) -> Result<Vec<TradeMsg>, ParseError> {
+    let ws_msg = serde_json::from_str::<WebsocketMsg<ContractTradeMsg>>(msg)
+        .map_err(|_e| ParseError::malformed("expected WebsocketMsg<ContractTradeMsg>"))?;
+    if ws_msg.subject != "match" {
+        return Err(ParseError::unsupported(format!("Unsupported subject {}", ws_msg.subject)));
+    }
+    if !ws_msg.topic.starts_with("/contractMarket/execution:") {
+        return Err(ParseError::unsupported(format!("Unsupported topic {}", ws_msg.topic)));
+    }
     let raw_trade = ws_msg.data;
-    let pair = crypto_pair::normalize_pair(&raw_trade.symbol, EXCHANGE_NAME).unwrap();
+    let pair = crypto_pair::normalize_pair(&raw_trade.symbol, EXCHANGE_NAME)
+        .ok_or_else(|| ParseError::symbol_normalization(&raw_trade.symbol))?;
     let (quantity_base, quantity_quote, quantity_contract) = calc_quantity_and_volume(
         EXCHANGE_NAME,
         market_type,
         &pair,
         raw_trade.price,
         raw_trade.size,
-    );
+        raw_trade.ts / 1000000,
+    )?;
 
     let trade = TradeMsg {
         exchange: EXCHANGE_NAME.to_string(),
@@ -80,6 +116,8 @@ pub(super) fn parse_trade(
         quantity_base,
         quantity_quote,
         quantity_contract,
+        price_exact: None,
+        quantity_exact: None,
         side: if raw_trade.side == "sell" { TradeSide::Sell } else { TradeSide::Buy },
         trade_id: raw_trade.sequence.to_string(),
         json: msg.to_string(),
@@ -91,25 +129,48 @@ pub(super) fn parse_trade(
 pub(super) fn parse_l2(
     market_type: MarketType,
     msg: &str,
-) -> Result<Vec<OrderBookMsg>, SimpleError> {
-    let ws_msg = serde_json::from_str::<WebsocketMsg<ContractOrderbookMsg>>(msg).map_err(|_e| {
-        SimpleError::new(format!(
-            "Failed to deserialize {msg} to WebsocketMsg<ContractOrderbookMsg>"
-        ))
-    })?;
-    debug_assert_eq!(ws_msg.subject, "level2");
-    debug_assert!(ws_msg.topic.starts_with("/contractMarket/level2:"));
-    let symbol = ws_msg.topic.split(':').last().unwrap();
-    let pair = crypto_pair::normalize_pair(symbol, EXCHANGE_NAME).unwrap();
+) -> Result<Vec<OrderBookMsg>, ParseError> {
+    let ws_msg = serde_json::from_str::<WebsocketMsg<ContractOrderbookMsg>>(msg)
+        .map_err(|_e| ParseError::malformed("expected WebsocketMsg<ContractOrderbookMsg>"))?;
+    if ws_msg.subject != "level2" {
+        return Err(ParseError::unsupported(format!("Unsupported subject {}", ws_msg.subject)));
+    }
+    if !ws_msg.topic.starts_with("/contractMarket/level2:") {
+        return Err(ParseError::unsupported(format!("Unsupported topic {}", ws_msg.topic)));
+    }
+    let symbol = ws_msg
+        .topic
+        .split(':')
+        .next_back()
+        .ok_or_else(|| ParseError::unsupported(format!("Invalid topic {}", ws_msg.topic)))?;
+    let pair = crypto_pair::normalize_pair(symbol, EXCHANGE_NAME)
+        .ok_or_else(|| ParseError::symbol_normalization(symbol))?;
 
+    // price,side,quantity
     let raw_order: Vec<&str> = ws_msg.data.change.split(',').collect();
+    if raw_order.len() != 3 {
+        return Err(ParseError::malformed(format!("Invalid change {}", ws_msg.data.change)));
+    }
     let order: Order = {
-        let price = raw_order[0].parse::<f64>().unwrap();
-        let quantity = raw_order[2].parse::<f64>().unwrap();
+        let price = raw_order[0].parse::<f64>()?;
+        let quantity = raw_order[2].parse::<f64>()?;
 
-        let (quantity_base, quantity_quote, quantity_contract) =
-            calc_quantity_and_volume(EXCHANGE_NAME, market_type, &pair, price, quantity);
-        Order { price, quantity_base, quantity_quote, quantity_contract }
+        let (quantity_base, quantity_quote, quantity_contract) = calc_quantity_and_volume(
+            EXCHANGE_NAME,
+            market_type,
+            &pair,
+            price,
+            quantity,
+            ws_msg.data.timestamp,
+        )?;
+        Order {
+            price,
+            quantity_base,
+            quantity_quote,
+            quantity_contract,
+            price_exact: parse_exact(raw_order[0]),
+            quantity_exact: parse_exact(raw_order[2]),
+        }
     };
 
     let mut asks: Vec<Order> = Vec::new();
@@ -153,22 +214,38 @@ struct SwapL2TopKMsg {
 pub(super) fn parse_l2_topk(
     market_type: MarketType,
     msg: &str,
-) -> Result<Vec<OrderBookMsg>, SimpleError> {
-    let ws_msg = serde_json::from_str::<WebsocketMsg<SwapL2TopKMsg>>(msg).map_err(|_e| {
-        SimpleError::new(format!("Failed to deserialize {msg} to WebsocketMsg<SwapL2TopKMsg>"))
-    })?;
-    debug_assert_eq!(ws_msg.subject, "level2");
-    debug_assert!(ws_msg.topic.starts_with("/contractMarket/level2Depth"));
-    let symbol = ws_msg.topic.split(':').last().unwrap();
-    let pair = crypto_pair::normalize_pair(symbol, EXCHANGE_NAME).unwrap();
-
-    let parse_order = |raw_order: &[f64; 2], pair: &str| -> Order {
+) -> Result<Vec<OrderBookMsg>, ParseError> {
+    let ws_msg = serde_json::from_str::<WebsocketMsg<SwapL2TopKMsg>>(msg)
+        .map_err(|_e| ParseError::malformed("expected WebsocketMsg<SwapL2TopKMsg>"))?;
+    if ws_msg.subject != "level2" {
+        return Err(ParseError::unsupported(format!("Unsupported subject {}", ws_msg.subject)));
+    }
+    if !ws_msg.topic.starts_with("/contractMarket/level2Depth") {
+        return Err(ParseError::unsupported(format!("Unsupported topic {}", ws_msg.topic)));
+    }
+    let symbol = ws_msg
+        .topic
+        .split(':')
+        .next_back()
+        .ok_or_else(|| ParseError::unsupported(format!("Invalid topic {}", ws_msg.topic)))?;
+    let pair = crypto_pair::normalize_pair(symbol, EXCHANGE_NAME)
+        .ok_or_else(|| ParseError::symbol_normalization(symbol))?;
+
+    let timestamp = ws_msg.data.timestamp;
+    let parse_order = |raw_order: &[f64; 2], pair: &str| -> Result<Order, ParseError> {
         let price = raw_order[0];
         let quantity = raw_order[1];
 
         let (quantity_base, quantity_quote, quantity_contract) =
-            calc_quantity_and_volume(EXCHANGE_NAME, market_type, pair, price, quantity);
-        Order { price, quantity_base, quantity_quote, quantity_contract }
+            calc_quantity_and_volume(EXCHANGE_NAME, market_type, pair, price, quantity, timestamp)?;
+        Ok(Order {
+            price,
+            quantity_base,
+            quantity_quote,
+            quantity_contract,
+            price_exact: None,
+            quantity_exact: None,
+        })
     };
 
     let orderbook = OrderBookMsg {
@@ -177,7 +254,7 @@ pub(super) fn parse_l2_topk(
         symbol: symbol.to_string(),
         pair: pair.clone(),
         msg_type: MessageType::L2TopK,
-        timestamp: ws_msg.data.timestamp,
+        timestamp,
         seq_id: Some(ws_msg.data.sequence),
         prev_seq_id: None,
         asks: ws_msg
@@ -185,13 +262,13 @@ pub(super) fn parse_l2_topk(
             .asks
             .iter()
             .map(|raw_order| parse_order(raw_order, pair.as_str()))
-            .collect(),
+            .collect::<Result<Vec<Order>, ParseError>>()?,
         bids: ws_msg
             .data
             .bids
             .iter()
             .map(|raw_order| parse_order(raw_order, pair.as_str()))
-            .collect(),
+            .collect::<Result<Vec<Order>, ParseError>>()?,
         snapshot: true,
         json: msg.to_string(),
     };
@@ -199,13 +276,157 @@ pub(super) fn parse_l2_topk(
     Ok(vec![orderbook])
 }
 
-pub(super) fn parse_bbo(_market_type: MarketType, _msg: &str) -> Result<Vec<BboMsg>, SimpleError> {
-    todo!()
+pub(super) fn parse_bbo(_market_type: MarketType, _msg: &str) -> Result<Vec<BboMsg>, ParseError> {
+    Err(ParseError::unsupported("Not implemented"))
+}
+
+pub(super) fn parse_ticker(
+    market_type: MarketType,
+    msg: &str,
+) -> Result<Vec<TickerMsg>, ParseError> {
+    let ws_msg = serde_json::from_str::<WebsocketMsg<RawTickerMsg>>(msg)
+        .map_err(|_e| ParseError::malformed("expected WebsocketMsg<RawTickerMsg>"))?;
+    if ws_msg.subject != "snapshot.24h" {
+        return Err(ParseError::unsupported(format!("Unsupported subject {}", ws_msg.subject)));
+    }
+    let raw_ticker = ws_msg.data;
+    let symbol = raw_ticker.symbol.as_str();
+    let pair = crypto_pair::normalize_pair(symbol, EXCHANGE_NAME)
+        .ok_or_else(|| ParseError::symbol_normalization(symbol))?;
+    let (volume, quote_volume) =
+        if market_type == MarketType::InverseSwap || market_type == MarketType::InverseFuture {
+            (raw_ticker.turnover, raw_ticker.volume)
+        } else {
+            (raw_ticker.volume, raw_ticker.turnover)
+        };
+
+    let ticker_msg = TickerMsg {
+        exchange: EXCHANGE_NAME.to_string(),
+        market_type,
+        symbol: symbol.to_string(),
+        pair,
+        msg_type: MessageType::Ticker,
+        timestamp: raw_ticker.ts / 1000000,
+        open: raw_ticker.lastPrice - raw_ticker.priceChg,
+        high: raw_ticker.highPrice,
+        low: raw_ticker.lowPrice,
+        close: raw_ticker.lastPrice,
+        volume,
+        quote_volume,
+        last_quantity: None,
+        best_bid_price: None,
+        best_bid_quantity: None,
+        best_ask_price: None,
+        best_ask_quantity: None,
+        open_interest: None,
+        open_interest_quote: None,
+        json: msg.to_string(),
+    };
+    Ok(vec![ticker_msg])
 }
 
 pub(super) fn parse_candlestick(
     _market_type: MarketType,
     _msg: &str,
-) -> Result<Vec<CandlestickMsg>, SimpleError> {
-    todo!()
+) -> Result<Vec<CandlestickMsg>, ParseError> {
+    Err(ParseError::unsupported("Not implemented"))
+}
+
+// Perpetual swaps settle funding at 04:00, 12:00 and 20:00 UTC
+const FUNDING_INTERVAL: i64 = 8 * 3600 * 1000;
+const FUNDING_OFFSET: i64 = 4 * 3600 * 1000;
+
+/// Parses the `funding.rate` subject of `/contract/instrument:{symbol}` and
+/// contracts from the RESTful API.
+pub(super) fn parse_funding_rate(
+    market_type: MarketType,
+    msg: &str,
+    received_at: Option<i64>,
+) -> Result<Vec<FundingRateMsg>, ParseError> {
+    if let Ok(ws_msg) = serde_json::from_str::<WebsocketMsg<RawFundingRateMsg>>(msg) {
+        if ws_msg.subject != "funding.rate" {
+            return Err(ParseError::unsupported(format!("Unsupported subject {}", ws_msg.subject)));
+        }
+        let symbol = ws_msg
+            .topic
+            .split(':')
+            .next_back()
+            .ok_or_else(|| ParseError::unsupported("Failed to extract symbol"))?;
+        let pair = crypto_pair::normalize_pair(symbol, EXCHANGE_NAME)
+            .ok_or_else(|| ParseError::symbol_normalization(symbol))?;
+        let timestamp = ws_msg.data.timestamp;
+        let funding_rate = ws_msg.data.fundingRate;
+        return Ok(vec![FundingRateMsg {
+            exchange: EXCHANGE_NAME.to_string(),
+            market_type,
+            symbol: symbol.to_string(),
+            pair,
+            msg_type: MessageType::FundingRate,
+            timestamp,
+            funding_rate,
+            funding_time: next_funding_time(timestamp - FUNDING_OFFSET, FUNDING_INTERVAL)
+                + FUNDING_OFFSET,
+            estimated_rate: None,
+            funding_interval: FUNDING_INTERVAL,
+            funding_rate_cap: None,
+            funding_rate_floor: None,
+            annualized_rate: annualize_funding_rate(funding_rate, FUNDING_INTERVAL),
+            json: msg.to_string(),
+        }]);
+    }
+
+    let rest_msg = if let Ok(rest_msg) = serde_json::from_str::<RestfulMsg<RawContractMsg>>(msg) {
+        RestfulMsg { code: rest_msg.code, data: vec![rest_msg.data] }
+    } else {
+        serde_json::from_str::<RestfulMsg<Vec<RawContractMsg>>>(msg)
+            .map_err(|_e| ParseError::malformed("expected RestfulMsg<Vec<RawContractMsg>>"))?
+    };
+    if rest_msg.code != "200000" {
+        return Err(ParseError::unsupported("Error HTTP response"));
+    }
+    // Contracts from the RESTful API have no timestamp
+    let timestamp = received_at.ok_or_else(ParseError::missing_timestamp)?;
+    let mut rates = rest_msg
+        .data
+        .into_iter()
+        .filter(|raw_msg| raw_msg.fundingFeeRate.is_some())
+        .map(|raw_msg| -> Result<FundingRateMsg, ParseError> {
+            let pair = crypto_pair::normalize_pair(&raw_msg.symbol, EXCHANGE_NAME)
+                .ok_or_else(|| ParseError::symbol_normalization(&raw_msg.symbol))?;
+            let market_type =
+                match crypto_pair::get_market_type(&raw_msg.symbol, EXCHANGE_NAME, None) {
+                    MarketType::Unknown => market_type,
+                    x => x,
+                };
+            let countdown = raw_msg
+                .nextFundingRateTime
+                .ok_or_else(|| ParseError::unsupported("Failed to extract nextFundingRateTime"))?;
+            let funding_rate = raw_msg
+                .fundingFeeRate
+                .ok_or_else(|| ParseError::unsupported("Failed to extract fundingFeeRate"))?;
+            let funding_interval =
+                raw_msg.fundingRateGranularity.filter(|x| *x > 0).unwrap_or(FUNDING_INTERVAL);
+            Ok(FundingRateMsg {
+                exchange: EXCHANGE_NAME.to_string(),
+                market_type,
+                symbol: raw_msg.symbol.clone(),
+                pair,
+                msg_type: MessageType::FundingRate,
+                timestamp,
+                funding_rate,
+                // The countdown is relative to the server time, round to minutes
+                funding_time: (timestamp + countdown + 30_000) / 60_000 * 60_000,
+                estimated_rate: raw_msg.predictedFundingFeeRate,
+                funding_interval,
+                funding_rate_cap: raw_msg.fundingRateCap,
+                funding_rate_floor: raw_msg.fundingRateFloor,
+                annualized_rate: annualize_funding_rate(funding_rate, funding_interval),
+                json: serde_json::to_string(&raw_msg).unwrap(),
+            })
+        })
+        .collect::<Result<Vec<FundingRateMsg>, ParseError>>()?;
+    if rates.len() == 1 {
+        rates[0].json = msg.to_string();
+    }
+    Ok(rates)
 }
@@ -5,15 +5,21 @@ mod message;
 use std::collections::HashMap;
 
 use crypto_market_type::MarketType;
-use crypto_message::CandlestickMsg;
+use crypto_message::{CandlestickMsg, FundingRateMsg, L3Action, L3Msg, TickerMsg, TradeSide};
+use crypto_msg_type::MessageType;
 
-use crate::{BboMsg, OrderBookMsg, TradeMsg};
+use crate::{
+    BboMsg, OrderBookMsg, ParseError, TradeMsg,
+    exchanges::utils::{calc_quantity_and_volume, json_i64, json_str},
+};
+use serde::{Deserialize, Serialize};
 use serde_json::Value;
-use simple_error::SimpleError;
 
 use self::message::{RestfulMsg, WebsocketMsg};
 
-pub(crate) fn extract_symbol(msg: &str) -> Result<String, SimpleError> {
+const EXCHANGE_NAME: &str = "kucoin";
+
+pub(crate) fn extract_symbol(msg: &str) -> Result<String, ParseError> {
     if let Ok(ws_msg) = serde_json::from_str::<WebsocketMsg<Value>>(msg) {
         // websocket
         let topic = ws_msg.topic.as_str();
@@ -21,9 +27,14 @@ pub(crate) fn extract_symbol(msg: &str) -> Result<String, SimpleError> {
             // spot all symbols ticker
             Ok(ws_msg.subject.clone())
         } else {
-            let symbol = topic.split(':').last().unwrap();
+            let symbol = topic
+                .split(':')
+                .next_back()
+                .ok_or_else(|| ParseError::unsupported(format!("Invalid topic {topic}")))?;
             if topic.contains("/candle") {
-                let pos = symbol.rfind('_').unwrap();
+                let pos = symbol
+                    .rfind('_')
+                    .ok_or_else(|| ParseError::malformed(format!("Invalid topic {topic}")))?;
                 Ok((symbol[..pos]).to_string())
             } else {
                 Ok(symbol.to_string())
@@ -32,86 +43,116 @@ pub(crate) fn extract_symbol(msg: &str) -> Result<String, SimpleError> {
     } else if let Ok(rest_msg) = serde_json::from_str::<RestfulMsg<HashMap<String, Value>>>(msg) {
         // RESTful
         if rest_msg.code != "200000" {
-            Err(SimpleError::new(format!("Error HTTP response {msg}")))
+            Err(ParseError::unsupported("Error HTTP response"))
         } else if let Some(symbol) = rest_msg.data.get("symbol") {
-            Ok(symbol.as_str().unwrap().to_string())
+            Ok(json_str(symbol, "symbol")?.to_string())
         } else {
             Ok("NONE".to_string())
         }
     } else if let Ok(rest_msg) = serde_json::from_str::<RestfulMsg<Vec<Value>>>(msg) {
         // RESTful
         if rest_msg.code != "200000" {
-            return Err(SimpleError::new(format!("Error HTTP response {msg}")));
+            return Err(ParseError::unsupported("Error HTTP response"));
         }
         #[allow(clippy::comparison_chain)]
         if rest_msg.data.len() > 1 {
             Ok("ALL".to_string())
         } else if rest_msg.data.len() == 1 {
-            Ok(rest_msg.data[0]["symbol"].as_str().unwrap().to_string())
+            Ok(json_str(&rest_msg.data[0]["symbol"], "symbol")?.to_string())
         } else {
             Ok("NONE".to_string())
         }
     } else {
-        Err(SimpleError::new(format!("Unsupported message format {msg}")))
+        Err(ParseError::unsupported("Unsupported message format"))
     }
 }
 
-pub(crate) fn extract_timestamp(msg: &str) -> Result<Option<i64>, SimpleError> {
+pub(crate) fn extract_timestamp(msg: &str) -> Result<Option<i64>, ParseError> {
     if let Ok(ws_msg) = serde_json::from_str::<WebsocketMsg<HashMap<String, Value>>>(msg) {
         // websocket
         let topic = ws_msg.topic.as_str();
-        if ws_msg.data.contains_key("timestamp") && ws_msg.data["timestamp"].is_i64() {
-            Ok(Some(ws_msg.data["timestamp"].as_i64().unwrap()))
-        } else if ws_msg.data.contains_key("ts") && ws_msg.data["ts"].is_i64() {
-            Ok(Some(ws_msg.data["ts"].as_i64().unwrap() / 1000000))
+        if let Some(timestamp) = ws_msg.data.get("timestamp").and_then(|x| x.as_i64()) {
+            Ok(Some(timestamp))
+        } else if let Some(ts) = ws_msg.data.get("ts").and_then(|x| x.as_i64()) {
+            Ok(Some(ts / 1000000))
         } else if let Some(t) = ws_msg.data.get("time") {
             if topic.starts_with("/market/match:") {
-                Ok(Some(t.as_str().unwrap().parse::<i64>().unwrap() / 1000000))
+                Ok(Some(json_str(t, "time")?.parse::<i64>()? / 1000000))
             } else if topic.starts_with("/market/ticker")
                 || topic.starts_with("/contractMarket/candle:")
             {
-                Ok(Some(t.as_i64().unwrap()))
+                Ok(Some(json_i64(t, "time")?))
             } else if topic.starts_with("/market/candles:") {
-                Ok(Some(t.as_i64().unwrap() / 1000000))
+                Ok(Some(json_i64(t, "time")? / 1000000))
             } else if topic.starts_with("/market/level2:") {
-                Ok(Some(t.as_i64().unwrap()))
+                Ok(Some(json_i64(t, "time")?))
             } else {
-                Err(SimpleError::new(format!("Failed to extract timestamp from {msg}")))
+                Err(ParseError::missing_timestamp())
             }
         } else if topic.starts_with("/market/level2:") {
             Ok(None)
         } else if topic.starts_with("/market/snapshot:") {
-            Ok(Some(ws_msg.data["data"]["datetime"].as_i64().unwrap()))
+            Ok(Some(json_i64(&ws_msg.data["data"]["datetime"], "datetime")?))
         } else {
-            Err(SimpleError::new(format!("Failed to extract timestamp from {msg}")))
+            Err(ParseError::missing_timestamp())
         }
     } else if let Ok(rest_msg) = serde_json::from_str::<RestfulMsg<HashMap<String, Value>>>(msg) {
         // RESTful
         if rest_msg.code != "200000" {
-            Err(SimpleError::new(format!("Error HTTP response {msg}")))
+            Err(ParseError::unsupported("Error HTTP response"))
         } else if let Some(t) = rest_msg.data.get("time") {
-            Ok(Some(t.as_i64().unwrap()))
+            Ok(Some(json_i64(t, "time")?))
         } else if let Some(t) = rest_msg.data.get("ts") {
-            Ok(Some(t.as_i64().unwrap() / 1000000))
+            Ok(Some(json_i64(t, "ts")? / 1000000))
         } else {
-            Err(SimpleError::new(format!("Unsupported message format {msg}")))
+            Err(ParseError::unsupported("Unsupported message format"))
         }
     } else if let Ok(rest_msg) = serde_json::from_str::<RestfulMsg<Vec<Value>>>(msg) {
         // RESTful
         if rest_msg.code != "200000" {
-            Err(SimpleError::new(format!("Error HTTP response {msg}")))
+            Err(ParseError::unsupported("Error HTTP response"))
         } else {
             Ok(None)
         }
     } else {
-        Err(SimpleError::new(format!("Unsupported message format {msg}")))
+        Err(ParseError::unsupported("Unsupported message format"))
     }
 }
 
-pub(crate) fn parse_trade(
-    market_type: MarketType,
-    msg: &str,
-) -> Result<Vec<TradeMsg>, SimpleError> {
+pub(crate) fn get_msg_type(msg: &str) -> MessageType {
+    if let Ok(ws_msg) = serde_json::from_str::<WebsocketMsg<Value>>(msg) {
+        let channel = ws_msg.topic.split(':').next().unwrap_or_default();
+        match channel {
+            // all symbols, one symbol per message
+            "/market/ticker" if ws_msg.topic == "/market/ticker:all" => MessageType::Ticker,
+            "/market/match" | "/contractMarket/execution" => MessageType::Trade,
+            "/market/level2" | "/contractMarket/level2" => MessageType::L2Event,
+            "/spotMarket/level2Depth5"
+            | "/spotMarket/level2Depth50"
+            | "/contractMarket/level2Depth5"
+            | "/contractMarket/level2Depth50" => MessageType::L2TopK,
+            "/spotMarket/level3" | "/contractMarket/level3v2" => MessageType::L3Event,
+            "/market/ticker" | "/contractMarket/tickerV2" => MessageType::BBO,
+            "/market/snapshot" | "/contractMarket/snapshot" => MessageType::Ticker,
+            "/market/candles" | "/contractMarket/candle" => MessageType::Candlestick,
+            "/contract/instrument" if ws_msg.subject == "funding.rate" => MessageType::FundingRate,
+            _ => MessageType::Other,
+        }
+    } else if let Ok(rest_msg) = serde_json::from_str::<RestfulMsg<HashMap<String, Value>>>(msg) {
+        if rest_msg.code == "200000"
+            && rest_msg.data.contains_key("asks")
+            && rest_msg.data.contains_key("bids")
+        {
+            MessageType::L2Snapshot
+        } else {
+            MessageType::Other
+        }
+    } else {
+        MessageType::Other
+    }
+}
+
+pub(crate) fn parse_trade(market_type: MarketType, msg: &str) -> Result<Vec<TradeMsg>, ParseError> {
     if market_type == MarketType::Spot {
         kucoin_spot::parse_trade(msg)
     } else {
@@ -123,9 +164,9 @@ pub(crate) fn parse_l2(
     market_type: MarketType,
     msg: &str,
     timestamp: Option<i64>,
-) -> Result<Vec<OrderBookMsg>, SimpleError> {
+) -> Result<Vec<OrderBookMsg>, ParseError> {
     if market_type == MarketType::Spot {
-        kucoin_spot::parse_l2(msg, timestamp.unwrap())
+        kucoin_spot::parse_l2(msg, timestamp.ok_or_else(ParseError::missing_timestamp)?)
     } else {
         kucoin_swap::parse_l2(market_type, msg)
     }
@@ -134,7 +175,7 @@ pub(crate) fn parse_l2(
 pub(crate) fn parse_l2_topk(
     market_type: MarketType,
     msg: &str,
-) -> Result<Vec<OrderBookMsg>, SimpleError> {
+) -> Result<Vec<OrderBookMsg>, ParseError> {
     if market_type == MarketType::Spot {
         kucoin_spot::parse_l2_topk(msg)
     } else {
@@ -142,7 +183,7 @@ pub(crate) fn parse_l2_topk(
     }
 }
 
-pub(crate) fn parse_bbo(market_type: MarketType, msg: &str) -> Result<Vec<BboMsg>, SimpleError> {
+pub(crate) fn parse_bbo(market_type: MarketType, msg: &str) -> Result<Vec<BboMsg>, ParseError> {
     if market_type == MarketType::Spot {
         kucoin_spot::parse_bbo(msg)
     } else {
@@ -150,13 +191,117 @@ pub(crate) fn parse_bbo(market_type: MarketType, msg: &str) -> Result<Vec<BboMsg
     }
 }
 
+pub(crate) fn parse_ticker(
+    market_type: MarketType,
+    msg: &str,
+) -> Result<Vec<TickerMsg>, ParseError> {
+    if market_type == MarketType::Spot {
+        kucoin_spot::parse_ticker(msg)
+    } else {
+        kucoin_swap::parse_ticker(market_type, msg)
+    }
+}
+
 pub(crate) fn parse_candlestick(
     market_type: MarketType,
     msg: &str,
-) -> Result<Vec<CandlestickMsg>, SimpleError> {
+) -> Result<Vec<CandlestickMsg>, ParseError> {
     if market_type == MarketType::Spot {
         kucoin_spot::parse_candlestick(msg)
     } else {
         kucoin_swap::parse_candlestick(market_type, msg)
     }
 }
+
+/// Contracts from the RESTful API have no timestamp, so `received_at` is
+/// required for them.
+pub(crate) fn parse_funding_rate(
+    market_type: MarketType,
+    msg: &str,
+    received_at: Option<i64>,
+) -> Result<Vec<FundingRateMsg>, ParseError> {
+    if market_type == MarketType::Spot {
+        Err(ParseError::unsupported("Spot markets have no funding rate"))
+    } else {
+        kucoin_swap::parse_funding_rate(market_type, msg, received_at)
+    }
+}
+
+// See https://docs.kucoin.com/#full-matchengine-data-level-3 and
+// https://docs.kucoin.com/futures/#full-matchengine-data-level-3
+#[derive(Serialize, Deserialize)]
+#[allow(non_snake_case)]
+struct L3Order {
+    symbol: String,
+    sequence: u64,
+    orderId: Option<String>,
+    makerOrderId: Option<String>,
+    side: Option<String>,
+    price: Option<String>,
+    size: Option<String>,
+    ts: i64, // nanoseconds
+    #[serde(flatten)]
+    extra: HashMap<String, Value>,
+}
+
+pub(crate) fn parse_l3(market_type: MarketType, msg: &str) -> Result<Vec<L3Msg>, ParseError> {
+    let ws_msg = serde_json::from_str::<WebsocketMsg<L3Order>>(msg)
+        .map_err(|_e| ParseError::malformed("expected WebsocketMsg<L3Order>"))?;
+    let raw_order = ws_msg.data;
+    let (action, order_id) = match ws_msg.subject.as_str() {
+        "open" => (L3Action::Open, raw_order.orderId),
+        "update" => (L3Action::Change, raw_order.orderId),
+        "match" => (L3Action::Match, raw_order.makerOrderId),
+        "done" => (L3Action::Done, raw_order.orderId),
+        // received orders are not on the book yet
+        _ => return Ok(Vec::new()),
+    };
+    let order_id = order_id.ok_or_else(|| ParseError::malformed("missing orderId"))?;
+    let symbol = raw_order.symbol.as_str();
+    let pair = crypto_pair::normalize_pair(symbol, EXCHANGE_NAME)
+        .ok_or_else(|| ParseError::symbol_normalization(symbol))?;
+    let timestamp = raw_order.ts / 1000000;
+
+    // match messages carry the taker side, the maker is on the other side
+    let side = match (action, raw_order.side.as_deref()) {
+        (L3Action::Open, Some("sell")) | (L3Action::Match, Some("buy")) => Some(TradeSide::Sell),
+        (L3Action::Open, Some("buy")) | (L3Action::Match, Some("sell")) => Some(TradeSide::Buy),
+        _ => None,
+    };
+    let price = match (action, raw_order.price) {
+        (L3Action::Open | L3Action::Match, Some(price)) => Some(price.parse::<f64>()?),
+        _ => None,
+    };
+    let quantity = match (action, raw_order.size) {
+        (L3Action::Done, _) | (_, None) => 0.0,
+        (_, Some(size)) => size.parse::<f64>()?,
+    };
+    let (quantity_base, quantity_quote, quantity_contract) = if let Some(price) = price {
+        calc_quantity_and_volume(EXCHANGE_NAME, market_type, &pair, price, quantity, timestamp)?
+    } else if market_type == MarketType::Spot {
+        (quantity, 0.0, None)
+    } else {
+        // base and quote quantities of contracts can't be computed without a price
+        (0.0, 0.0, Some(quantity))
+    };
+
+    let l3_msg = L3Msg {
+        exchange: EXCHANGE_NAME.to_string(),
+        market_type,
+        symbol: raw_order.symbol.clone(),
+        pair,
+        msg_type: MessageType::L3Event,
+        timestamp,
+        order_id,
+        action,
+        side,
+        price,
+        quantity_base,
+        quantity_quote,
+        quantity_contract,
+        seq_id: Some(raw_order.sequence),
+        json: msg.to_string(),
+    };
+
+    Ok(vec![l3_msg])
+}
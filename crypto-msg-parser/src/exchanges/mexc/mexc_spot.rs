@@ -3,9 +3,12 @@ use crypto_msg_type::MessageType;
 
 use crypto_message::{CandlestickMsg, Order, OrderBookMsg, TradeMsg, TradeSide};
 
+use crate::{
+    ParseError,
+    exchanges::utils::{parse_candle_period, parse_exact},
+};
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
-use simple_error::SimpleError;
 use std::collections::HashMap;
 
 // https://github.com/mxcdevelop/APIDoc/blob/master/websocket/spot/websocket-api.md#成交记录
@@ -48,12 +51,14 @@ struct WebsocketMsg<T: Sized> {
     extra: HashMap<String, Value>,
 }
 
-pub(super) fn parse_trade(msg: &str) -> Result<Vec<TradeMsg>, SimpleError> {
+pub(super) fn parse_trade(msg: &str) -> Result<Vec<TradeMsg>, ParseError> {
     let ws_msg: WebsocketMsg<PushSymbolData> =
         if let Ok(arr) = serde_json::from_str::<Vec<Value>>(msg) {
-            assert_eq!(arr.len(), 2);
+            if arr.len() != 2 {
+                return Err(ParseError::malformed("expected [channel, data]"));
+            }
             serde_json::from_value(arr[1].clone()).map_err(|_e| {
-                SimpleError::new(format!(
+                ParseError::malformed(format!(
                     "Failed to deserialize {} to WebsocketMsg<PushSymbolData>",
                     arr[1]
                 ))
@@ -61,24 +66,23 @@ pub(super) fn parse_trade(msg: &str) -> Result<Vec<TradeMsg>, SimpleError> {
         } else if let Ok(ws_msg) = serde_json::from_str::<WebsocketMsg<PushSymbolData>>(msg) {
             ws_msg
         } else {
-            return Err(SimpleError::new(format!("Failed to parse {msg}")));
+            return Err(ParseError::malformed("invalid JSON"));
         };
-    if ws_msg.data.deals.is_none() {
-        return Ok(Vec::new());
-    }
-
-    let raw_trades = ws_msg.data.deals.unwrap();
+    let raw_trades = match ws_msg.data.deals {
+        Some(deals) => deals,
+        None => return Ok(Vec::new()),
+    };
     let symbol = ws_msg.symbol.as_str();
     let pair = crypto_pair::normalize_pair(symbol, super::EXCHANGE_NAME)
-        .ok_or_else(|| SimpleError::new(format!("Failed to normalize {symbol} from {msg}")))?;
+        .ok_or_else(|| ParseError::symbol_normalization(symbol))?;
 
     let mut trades: Vec<TradeMsg> = raw_trades
         .into_iter()
-        .map(|raw_trade| {
-            let price = raw_trade.p.parse::<f64>().unwrap();
-            let quantity = raw_trade.q.parse::<f64>().unwrap();
+        .map(|raw_trade| -> Result<TradeMsg, ParseError> {
+            let price = raw_trade.p.parse::<f64>()?;
+            let quantity = raw_trade.q.parse::<f64>()?;
 
-            TradeMsg {
+            Ok(TradeMsg {
                 exchange: super::EXCHANGE_NAME.to_string(),
                 market_type: MarketType::Spot,
                 symbol: symbol.to_string(),
@@ -89,12 +93,14 @@ pub(super) fn parse_trade(msg: &str) -> Result<Vec<TradeMsg>, SimpleError> {
                 quantity_base: quantity,
                 quantity_quote: price * quantity,
                 quantity_contract: None,
+                price_exact: parse_exact(&raw_trade.p),
+                quantity_exact: parse_exact(&raw_trade.q),
                 side: if raw_trade.T == 2 { TradeSide::Sell } else { TradeSide::Buy },
                 trade_id: raw_trade.t.to_string(),
                 json: serde_json::to_string(&raw_trade).unwrap(),
-            }
+            })
         })
-        .collect();
+        .collect::<Result<Vec<TradeMsg>, ParseError>>()?;
 
     if trades.len() == 1 {
         trades[0].json = msg.to_string();
@@ -102,20 +108,29 @@ pub(super) fn parse_trade(msg: &str) -> Result<Vec<TradeMsg>, SimpleError> {
     Ok(trades)
 }
 
-fn parse_order(raw_order: &RawOrder) -> Order {
-    let price = raw_order.p.parse::<f64>().unwrap();
-    let quantity_base = raw_order.q.parse::<f64>().unwrap();
-    let quantity_quote = raw_order.a.parse::<f64>().unwrap();
+fn parse_order(raw_order: &RawOrder) -> Result<Order, ParseError> {
+    let price = raw_order.p.parse::<f64>()?;
+    let quantity_base = raw_order.q.parse::<f64>()?;
+    let quantity_quote = raw_order.a.parse::<f64>()?;
 
-    Order { price, quantity_base, quantity_quote, quantity_contract: None }
+    Ok(Order {
+        price,
+        quantity_base,
+        quantity_quote,
+        quantity_contract: None,
+        price_exact: parse_exact(&raw_order.p),
+        quantity_exact: parse_exact(&raw_order.q),
+    })
 }
 
-pub(super) fn parse_l2(msg: &str, timestamp: i64) -> Result<Vec<OrderBookMsg>, SimpleError> {
+pub(super) fn parse_l2(msg: &str, timestamp: i64) -> Result<Vec<OrderBookMsg>, ParseError> {
     let ws_msg: WebsocketMsg<PushSymbolData> =
         if let Ok(arr) = serde_json::from_str::<Vec<Value>>(msg) {
-            assert_eq!(arr.len(), 2);
+            if arr.len() != 2 {
+                return Err(ParseError::malformed("expected [channel, data]"));
+            }
             serde_json::from_value(arr[1].clone()).map_err(|_e| {
-                SimpleError::new(format!(
+                ParseError::malformed(format!(
                     "Failed to deserialize {} to WebsocketMsg<PushSymbolData>",
                     arr[1]
                 ))
@@ -123,7 +138,7 @@ pub(super) fn parse_l2(msg: &str, timestamp: i64) -> Result<Vec<OrderBookMsg>, S
         } else if let Ok(ws_msg) = serde_json::from_str::<WebsocketMsg<PushSymbolData>>(msg) {
             ws_msg
         } else {
-            return Err(SimpleError::new(format!("Failed to parse {msg}")));
+            return Err(ParseError::malformed("invalid JSON"));
         };
     if ws_msg.data.asks.is_none() && ws_msg.data.bids.is_none() {
         return Ok(Vec::new());
@@ -131,7 +146,7 @@ pub(super) fn parse_l2(msg: &str, timestamp: i64) -> Result<Vec<OrderBookMsg>, S
 
     let symbol = ws_msg.symbol.as_str();
     let pair = crypto_pair::normalize_pair(symbol, super::EXCHANGE_NAME)
-        .ok_or_else(|| SimpleError::new(format!("Failed to normalize {symbol} from {msg}")))?;
+        .ok_or_else(|| ParseError::symbol_normalization(symbol))?;
 
     let orderbook = OrderBookMsg {
         exchange: super::EXCHANGE_NAME.to_string(),
@@ -140,15 +155,15 @@ pub(super) fn parse_l2(msg: &str, timestamp: i64) -> Result<Vec<OrderBookMsg>, S
         pair,
         msg_type: MessageType::L2Event,
         timestamp,
-        seq_id: ws_msg.data.version.map(|v| v.parse::<u64>().unwrap()),
+        seq_id: ws_msg.data.version.map(|v| v.parse::<u64>()).transpose()?,
         prev_seq_id: None,
         asks: if let Some(asks) = ws_msg.data.asks {
-            asks.iter().map(parse_order).collect::<Vec<Order>>()
+            asks.iter().map(parse_order).collect::<Result<Vec<Order>, ParseError>>()?
         } else {
             Vec::new()
         },
         bids: if let Some(bids) = ws_msg.data.bids {
-            bids.iter().map(parse_order).collect::<Vec<Order>>()
+            bids.iter().map(parse_order).collect::<Result<Vec<Order>, ParseError>>()?
         } else {
             Vec::new()
         },
@@ -167,24 +182,26 @@ struct RawL2TopKMsg {
     extra: HashMap<String, Value>,
 }
 
-pub(super) fn parse_l2_topk(msg: &str, timestamp: i64) -> Result<Vec<OrderBookMsg>, SimpleError> {
+pub(super) fn parse_l2_topk(msg: &str, timestamp: i64) -> Result<Vec<OrderBookMsg>, ParseError> {
     let ws_msg =
-        serde_json::from_str::<WebsocketMsg<RawL2TopKMsg>>(msg).map_err(SimpleError::from)?;
+        serde_json::from_str::<WebsocketMsg<RawL2TopKMsg>>(msg).map_err(ParseError::from)?;
 
     let symbol = ws_msg.symbol.as_str();
     let pair = crypto_pair::normalize_pair(symbol, super::EXCHANGE_NAME)
-        .ok_or_else(|| SimpleError::new(format!("Failed to normalize {symbol} from {msg}")))?;
+        .ok_or_else(|| ParseError::symbol_normalization(symbol))?;
 
-    let parse_order = |raw_order: &[String; 2]| -> Order {
-        let price = raw_order[0].parse::<f64>().unwrap();
-        let quantity_base = raw_order[1].parse::<f64>().unwrap();
+    let parse_order = |raw_order: &[String; 2]| -> Result<Order, ParseError> {
+        let price = raw_order[0].parse::<f64>()?;
+        let quantity_base = raw_order[1].parse::<f64>()?;
 
-        Order {
+        Ok(Order {
             price,
             quantity_base,
             quantity_quote: price * quantity_base,
             quantity_contract: None,
-        }
+            price_exact: parse_exact(&raw_order[0]),
+            quantity_exact: parse_exact(&raw_order[1]),
+        })
     };
 
     let orderbook = OrderBookMsg {
@@ -194,10 +211,20 @@ pub(super) fn parse_l2_topk(msg: &str, timestamp: i64) -> Result<Vec<OrderBookMs
         pair,
         msg_type: MessageType::L2TopK,
         timestamp,
-        seq_id: ws_msg.version.map(|v| v.parse::<u64>().unwrap()),
+        seq_id: ws_msg.version.map(|v| v.parse::<u64>()).transpose()?,
         prev_seq_id: None,
-        asks: ws_msg.data.asks.iter().map(parse_order).collect::<Vec<Order>>(),
-        bids: ws_msg.data.bids.iter().map(parse_order).collect::<Vec<Order>>(),
+        asks: ws_msg
+            .data
+            .asks
+            .iter()
+            .map(parse_order)
+            .collect::<Result<Vec<Order>, ParseError>>()?,
+        bids: ws_msg
+            .data
+            .bids
+            .iter()
+            .map(parse_order)
+            .collect::<Result<Vec<Order>, ParseError>>()?,
         snapshot: true,
         json: msg.to_string(),
     };
@@ -224,12 +251,14 @@ struct RawCandlestickMsg {
 pub(super) fn parse_candlestick(
     msg: &str,
     received_at: Option<i64>,
-) -> Result<Vec<CandlestickMsg>, SimpleError> {
+) -> Result<Vec<CandlestickMsg>, ParseError> {
     let ws_msg: WebsocketMsg<RawCandlestickMsg> =
         if let Ok(arr) = serde_json::from_str::<Vec<Value>>(msg) {
-            assert_eq!(arr.len(), 2);
+            if arr.len() != 2 {
+                return Err(ParseError::malformed("expected [channel, data]"));
+            }
             serde_json::from_value(arr[1].clone()).map_err(|_e| {
-                SimpleError::new(format!(
+                ParseError::malformed(format!(
                     "Failed to deserialize {} to WebsocketMsg<PushSymbolData>",
                     arr[1]
                 ))
@@ -237,45 +266,16 @@ pub(super) fn parse_candlestick(
         } else if let Ok(ws_msg) = serde_json::from_str::<WebsocketMsg<RawCandlestickMsg>>(msg) {
             ws_msg
         } else {
-            return Err(SimpleError::new(format!("Failed to parse {msg}")));
+            return Err(ParseError::malformed("invalid JSON"));
         };
 
     let symbol = ws_msg.symbol.as_str();
     let pair = crypto_pair::normalize_pair(symbol, super::EXCHANGE_NAME)
-        .ok_or_else(|| SimpleError::new(format!("Failed to normalize {symbol} from {msg}")))?;
+        .ok_or_else(|| ParseError::symbol_normalization(symbol))?;
 
-    let mut interval_in_seconds = 0;
-    if ws_msg.data.interval.as_str().starts_with("Min") {
-        interval_in_seconds =
-            ws_msg.data.interval.as_str().strip_prefix("Min").unwrap().parse::<i64>().unwrap() * 60;
-    } else if ws_msg.data.interval.as_str().starts_with("Hour") {
-        interval_in_seconds =
-            ws_msg.data.interval.as_str().strip_prefix("Hour").unwrap().parse::<i64>().unwrap()
-                * 60
-                * 60;
-    } else if ws_msg.data.interval.as_str().starts_with("Day") {
-        interval_in_seconds =
-            ws_msg.data.interval.as_str().strip_prefix("Day").unwrap().parse::<i64>().unwrap()
-                * 60
-                * 60
-                * 24;
-    } else if ws_msg.data.interval.as_str().starts_with("Week") {
-        interval_in_seconds =
-            ws_msg.data.interval.as_str().strip_prefix("Week").unwrap().parse::<i64>().unwrap()
-                * 60
-                * 60
-                * 24
-                * 7;
-    } else if ws_msg.data.interval.as_str().starts_with("Month") {
-        //how to calculate Month intervals in milliseconds?
-        interval_in_seconds =
-            ws_msg.data.interval.as_str().strip_prefix("Month").unwrap().parse::<i64>().unwrap()
-                * 60
-                * 60
-                * 24
-                * 7
-                * 30;
-    }
+    let period =
+        parse_candle_period(super::EXCHANGE_NAME, MarketType::Spot, &ws_msg.data.interval)?;
+    let begin_time = ws_msg.data.t * 1000;
 
     let candlestick_msg = CandlestickMsg {
         exchange: super::EXCHANGE_NAME.to_string(),
@@ -283,15 +283,17 @@ pub(super) fn parse_candlestick(
         symbol: symbol.to_string(),
         pair,
         msg_type: MessageType::Candlestick,
-        timestamp: ws_msg.data.tdt.unwrap_or(received_at.unwrap()),
-        begin_time: ws_msg.data.t * 1000 - interval_in_seconds * 1000,
+        timestamp: ws_msg.data.tdt.or(received_at).ok_or_else(ParseError::missing_timestamp)?,
+        begin_time,
+        end_time: period.end_time(begin_time),
         open: ws_msg.data.o,
         high: ws_msg.data.h,
         low: ws_msg.data.l,
         close: ws_msg.data.c,
         volume: ws_msg.data.q,
-        period: ws_msg.data.interval,
+        period,
         quote_volume: Some(ws_msg.data.v),
+        is_closed: None,
         json: msg.to_string(),
     };
 
@@ -3,12 +3,16 @@ use crypto_msg_type::MessageType;
 
 use super::EXCHANGE_NAME;
 
-use super::super::utils::calc_quantity_and_volume;
-use crypto_message::{CandlestickMsg, Order, OrderBookMsg, TradeMsg, TradeSide};
+use super::super::utils::{
+    annualize_funding_rate, calc_quantity_and_volume, next_funding_time, parse_candle_period,
+};
+use crypto_message::{
+    CandlestickMsg, FundingRateMsg, Order, OrderBookMsg, TickerMsg, TradeMsg, TradeSide,
+};
 
+use crate::ParseError;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
-use simple_error::SimpleError;
 use std::collections::HashMap;
 
 // https://mxcdevelop.github.io/APIDoc/contract.api.cn.html#4483df6e28
@@ -51,6 +55,82 @@ struct RawCandlestickMsg {
     extra: HashMap<String, Value>,
 }
 
+// https://mexcdevelop.github.io/apidocs/contract_v1_en/#public-channels
+#[derive(Serialize, Deserialize)]
+#[allow(non_snake_case)]
+struct RawTickerMsg {
+    symbol: String,
+    lastPrice: f64,
+    riseFallValue: f64,
+    high24Price: f64,
+    lower24Price: f64,
+    volume24: f64, // number of contracts
+    holdVol: f64,  // open interest in contracts
+    ask1: Option<f64>,
+    bid1: Option<f64>,
+    timestamp: i64,
+    #[serde(flatten)]
+    extra: HashMap<String, Value>,
+}
+
+// https://mexcdevelop.github.io/apidocs/contract_v1_en/#public-channels
+#[derive(Serialize, Deserialize)]
+#[allow(non_snake_case)]
+struct RawFundingRateMsg {
+    symbol: String,
+    rate: f64,
+    nextSettleTime: Option<i64>,
+    #[serde(flatten)]
+    extra: HashMap<String, Value>,
+}
+
+// https://mexcdevelop.github.io/apidocs/contract_v1_en/#get-contract-funding-rate
+#[derive(Serialize, Deserialize)]
+#[allow(non_snake_case)]
+struct RawFundingRateSnapshot {
+    symbol: String,
+    fundingRate: f64,
+    nextSettleTime: i64,
+    timestamp: i64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    collectCycle: Option<i64>, // hours
+    #[serde(skip_serializing_if = "Option::is_none")]
+    maxFundingRate: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    minFundingRate: Option<f64>,
+    #[serde(flatten)]
+    extra: HashMap<String, Value>,
+}
+
+// https://mexcdevelop.github.io/apidocs/contract_v1_en/#get-contract-funding-rate-history
+#[derive(Serialize, Deserialize)]
+#[allow(non_snake_case)]
+struct RawFundingRateHistory {
+    symbol: String,
+    fundingRate: f64,
+    settleTime: i64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    collectCycle: Option<i64>, // hours
+    #[serde(flatten)]
+    extra: HashMap<String, Value>,
+}
+
+#[derive(Serialize, Deserialize)]
+#[allow(non_snake_case)]
+struct FundingRateHistoryPage {
+    resultList: Vec<RawFundingRateHistory>,
+    #[serde(flatten)]
+    extra: HashMap<String, Value>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct RestfulMsg<T: Sized> {
+    code: i64,
+    data: T,
+    #[serde(flatten)]
+    extra: HashMap<String, Value>,
+}
+
 #[derive(Serialize, Deserialize)]
 struct WebsocketMsg<T: Sized> {
     channel: String,
@@ -61,16 +141,12 @@ struct WebsocketMsg<T: Sized> {
     extra: HashMap<String, Value>,
 }
 
-pub(super) fn parse_trade(
-    market_type: MarketType,
-    msg: &str,
-) -> Result<Vec<TradeMsg>, SimpleError> {
-    let ws_msg = serde_json::from_str::<WebsocketMsg<RawTradeMsg>>(msg).map_err(|_e| {
-        SimpleError::new(format!("Failed to deserialize {msg} to WebsocketMsg<RawTradeMsg>"))
-    })?;
+pub(super) fn parse_trade(market_type: MarketType, msg: &str) -> Result<Vec<TradeMsg>, ParseError> {
+    let ws_msg = serde_json::from_str::<WebsocketMsg<RawTradeMsg>>(msg)
+        .map_err(|_e| ParseError::malformed("expected WebsocketMsg<RawTradeMsg>"))?;
     let symbol = ws_msg.symbol.as_str();
     let pair = crypto_pair::normalize_pair(symbol, super::EXCHANGE_NAME)
-        .ok_or_else(|| SimpleError::new(format!("Failed to normalize {symbol} from {msg}")))?;
+        .ok_or_else(|| ParseError::symbol_normalization(symbol))?;
     let raw_trade = ws_msg.data;
 
     let (quantity_base, quantity_quote, _) = calc_quantity_and_volume(
@@ -79,7 +155,8 @@ pub(super) fn parse_trade(
         &pair,
         raw_trade.p,
         raw_trade.v,
-    );
+        raw_trade.t,
+    )?;
 
     let trade = TradeMsg {
         exchange: super::EXCHANGE_NAME.to_string(),
@@ -92,6 +169,8 @@ pub(super) fn parse_trade(
         quantity_base,
         quantity_quote,
         quantity_contract: Some(raw_trade.v),
+        price_exact: None,
+        quantity_exact: None,
         side: if raw_trade.T == 2 { TradeSide::Sell } else { TradeSide::Buy },
         trade_id: raw_trade.t.to_string(),
         json: msg.to_string(),
@@ -103,26 +182,40 @@ pub(super) fn parse_trade(
 pub(crate) fn parse_l2(
     market_type: MarketType,
     msg: &str,
-) -> Result<Vec<OrderBookMsg>, SimpleError> {
-    let ws_msg = serde_json::from_str::<WebsocketMsg<RawOrderbookMsg>>(msg).map_err(|_e| {
-        SimpleError::new(format!("Failed to deserialize {msg} to WebsocketMsg<RawOrderbookMsg>"))
-    })?;
+) -> Result<Vec<OrderBookMsg>, ParseError> {
+    let ws_msg = serde_json::from_str::<WebsocketMsg<RawOrderbookMsg>>(msg)
+        .map_err(|_e| ParseError::malformed("expected WebsocketMsg<RawOrderbookMsg>"))?;
     let symbol = ws_msg.symbol.as_str();
     let pair = crypto_pair::normalize_pair(symbol, super::EXCHANGE_NAME)
-        .ok_or_else(|| SimpleError::new(format!("Failed to normalize {symbol} from {msg}")))?;
+        .ok_or_else(|| ParseError::symbol_normalization(symbol))?;
 
     let msg_type = match ws_msg.channel.as_str() {
         "push.depth.full" => MessageType::L2TopK,
         "push.depth" => MessageType::L2Event,
-        _ => panic!("Unsupported channel {}", ws_msg.channel),
+        _ => {
+            return Err(ParseError::unsupported(format!("Unsupported channel {}", ws_msg.channel)));
+        }
     };
 
-    let parse_order = |raw_order: &[f64; 3]| -> Order {
+    let parse_order = |raw_order: &[f64; 3]| -> Result<Order, ParseError> {
         let price = raw_order[0];
         let quantity = raw_order[1];
-        let (quantity_base, quantity_quote, quantity_contract) =
-            calc_quantity_and_volume(super::EXCHANGE_NAME, market_type, &pair, price, quantity);
-        Order { price, quantity_base, quantity_quote, quantity_contract }
+        let (quantity_base, quantity_quote, quantity_contract) = calc_quantity_and_volume(
+            super::EXCHANGE_NAME,
+            market_type,
+            &pair,
+            price,
+            quantity,
+            ws_msg.ts,
+        )?;
+        Ok(Order {
+            price,
+            quantity_base,
+            quantity_quote,
+            quantity_contract,
+            price_exact: None,
+            quantity_exact: None,
+        })
     };
 
     let orderbook = OrderBookMsg {
@@ -134,8 +227,18 @@ pub(crate) fn parse_l2(
         timestamp: ws_msg.ts,
         seq_id: ws_msg.data.version,
         prev_seq_id: None,
-        asks: ws_msg.data.asks.iter().map(parse_order).collect::<Vec<Order>>(),
-        bids: ws_msg.data.bids.iter().map(parse_order).collect::<Vec<Order>>(),
+        asks: ws_msg
+            .data
+            .asks
+            .iter()
+            .map(parse_order)
+            .collect::<Result<Vec<Order>, ParseError>>()?,
+        bids: ws_msg
+            .data
+            .bids
+            .iter()
+            .map(parse_order)
+            .collect::<Result<Vec<Order>, ParseError>>()?,
         snapshot: msg_type == MessageType::L2TopK,
         json: msg.to_string(),
     };
@@ -146,39 +249,26 @@ pub(crate) fn parse_l2(
 pub(crate) fn parse_candlestick(
     market_type: MarketType,
     msg: &str,
-) -> Result<Vec<CandlestickMsg>, SimpleError> {
+) -> Result<Vec<CandlestickMsg>, ParseError> {
     let ws_msg =
-        serde_json::from_str::<WebsocketMsg<RawCandlestickMsg>>(msg).map_err(SimpleError::from)?;
+        serde_json::from_str::<WebsocketMsg<RawCandlestickMsg>>(msg).map_err(ParseError::from)?;
     let symbol = ws_msg.symbol.as_str();
     let pair = crypto_pair::normalize_pair(symbol, super::EXCHANGE_NAME)
-        .ok_or_else(|| SimpleError::new(format!("Failed to normalize {symbol} from {msg}")))?;
-    let mut interval_in_seconds = 0;
-
-    let internal = ws_msg.data.interval.as_str();
-    if internal.starts_with("Min") {
-        interval_in_seconds = internal.strip_prefix("Min").unwrap().parse::<i64>().unwrap() * 60;
-    } else if internal.starts_with("Hour") {
-        interval_in_seconds =
-            internal.strip_prefix("Hour").unwrap().parse::<i64>().unwrap() * 60 * 60;
-    } else if internal.starts_with("Day") {
-        interval_in_seconds =
-            internal.strip_prefix("Day").unwrap().parse::<i64>().unwrap() * 60 * 60 * 24;
-    } else if internal.starts_with("Week") {
-        interval_in_seconds =
-            internal.strip_prefix("Week").unwrap().parse::<i64>().unwrap() * 60 * 60 * 24 * 7;
-    } else if internal.starts_with("Month") {
-        //how to calculate Month intervals in milliseconds?
-        interval_in_seconds =
-            internal.strip_prefix("Month").unwrap().parse::<i64>().unwrap() * 60 * 60 * 24 * 7 * 30;
-    }
+        .ok_or_else(|| ParseError::symbol_normalization(symbol))?;
+    let period = parse_candle_period(super::EXCHANGE_NAME, market_type, &ws_msg.data.interval)?;
+    let begin_time = ws_msg.data.t * 1000;
 
-    let contract_value =
-        crypto_contract_value::get_contract_value(EXCHANGE_NAME, market_type, pair.as_str())
-            .unwrap();
+    let contract_value = crypto_contract_value::get_contract_value_at(
+        EXCHANGE_NAME,
+        market_type,
+        pair.as_str(),
+        ws_msg.data.t * 1000,
+    )
+    .ok_or_else(|| ParseError::unsupported(format!("No contract value for {pair}")))?;
     let (volume, quote_volume) = match market_type {
         MarketType::InverseSwap => (ws_msg.data.a, ws_msg.data.q * contract_value),
         MarketType::LinearSwap => (ws_msg.data.q * contract_value, ws_msg.data.a),
-        _ => panic!("Unknown market_type: {}", market_type),
+        _ => return Err(ParseError::unsupported(format!("Unknown market_type {market_type}"))),
     };
 
     let candlestick_msg = CandlestickMsg {
@@ -188,16 +278,175 @@ pub(crate) fn parse_candlestick(
         symbol: symbol.to_string(),
         pair,
         timestamp: ws_msg.data.t * 1000,
-        period: ws_msg.data.interval,
-        begin_time: ws_msg.data.t * 1000 - interval_in_seconds * 1000,
+        period,
+        begin_time,
+        end_time: period.end_time(begin_time),
         open: ws_msg.data.o,
         high: ws_msg.data.h,
         low: ws_msg.data.l,
         close: ws_msg.data.c,
         volume,
         quote_volume: Some(quote_volume),
+        is_closed: None,
         json: msg.to_string(),
     };
 
     Ok(vec![candlestick_msg])
 }
+
+pub(super) fn parse_ticker(
+    market_type: MarketType,
+    msg: &str,
+) -> Result<Vec<TickerMsg>, ParseError> {
+    let ws_msg = serde_json::from_str::<WebsocketMsg<RawTickerMsg>>(msg)
+        .map_err(|_e| ParseError::malformed("expected WebsocketMsg<RawTickerMsg>"))?;
+    if ws_msg.channel != "push.ticker" {
+        return Err(ParseError::unsupported(format!("Unsupported channel {}", ws_msg.channel)));
+    }
+    let raw_ticker = ws_msg.data;
+    let symbol = raw_ticker.symbol.as_str();
+    let pair = crypto_pair::normalize_pair(symbol, EXCHANGE_NAME)
+        .ok_or_else(|| ParseError::symbol_normalization(symbol))?;
+    let close = raw_ticker.lastPrice;
+    let timestamp = raw_ticker.timestamp;
+    let (volume, quote_volume, _) = calc_quantity_and_volume(
+        EXCHANGE_NAME,
+        market_type,
+        &pair,
+        close,
+        raw_ticker.volume24,
+        timestamp,
+    )?;
+    let (open_interest, open_interest_quote, _) = calc_quantity_and_volume(
+        EXCHANGE_NAME,
+        market_type,
+        &pair,
+        close,
+        raw_ticker.holdVol,
+        timestamp,
+    )?;
+
+    let ticker_msg = TickerMsg {
+        exchange: EXCHANGE_NAME.to_string(),
+        market_type,
+        symbol: symbol.to_string(),
+        pair,
+        msg_type: MessageType::Ticker,
+        timestamp,
+        open: close - raw_ticker.riseFallValue,
+        high: raw_ticker.high24Price,
+        low: raw_ticker.lower24Price,
+        close,
+        volume,
+        quote_volume,
+        last_quantity: None,
+        best_bid_price: raw_ticker.bid1,
+        best_bid_quantity: None,
+        best_ask_price: raw_ticker.ask1,
+        best_ask_quantity: None,
+        open_interest: Some(open_interest),
+        open_interest_quote: Some(open_interest_quote),
+        json: msg.to_string(),
+    };
+
+    Ok(vec![ticker_msg])
+}
+
+// Perpetual swaps settle funding every 8 hours unless collectCycle says otherwise
+const FUNDING_INTERVAL: i64 = 8 * 3600 * 1000;
+
+/// Parses the `push.funding.rate` channel and the `funding_rate` and
+/// `funding_rate/history` RESTful APIs.
+pub(super) fn parse_funding_rate(
+    market_type: MarketType,
+    msg: &str,
+) -> Result<Vec<FundingRateMsg>, ParseError> {
+    let funding_rate_msg = |symbol: &str,
+                            timestamp: i64,
+                            funding_rate: f64,
+                            funding_time: i64,
+                            collect_cycle: Option<i64>,
+                            json: String|
+     -> Result<FundingRateMsg, ParseError> {
+        let funding_interval =
+            collect_cycle.map(|x| x * 3600 * 1000).filter(|x| *x > 0).unwrap_or(FUNDING_INTERVAL);
+        let pair = crypto_pair::normalize_pair(symbol, EXCHANGE_NAME)
+            .ok_or_else(|| ParseError::symbol_normalization(symbol))?;
+        Ok(FundingRateMsg {
+            exchange: EXCHANGE_NAME.to_string(),
+            market_type,
+            symbol: symbol.to_string(),
+            pair,
+            msg_type: MessageType::FundingRate,
+            timestamp,
+            funding_rate,
+            funding_time,
+            estimated_rate: None,
+            funding_interval,
+            funding_rate_cap: None,
+            funding_rate_floor: None,
+            annualized_rate: annualize_funding_rate(funding_rate, funding_interval),
+            json,
+        })
+    };
+
+    if let Ok(ws_msg) = serde_json::from_str::<WebsocketMsg<RawFundingRateMsg>>(msg) {
+        if ws_msg.channel != "push.funding.rate" {
+            return Err(ParseError::unsupported(format!("Unsupported channel {}", ws_msg.channel)));
+        }
+        let raw_msg = ws_msg.data;
+        let funding_time = raw_msg
+            .nextSettleTime
+            .unwrap_or_else(|| next_funding_time(ws_msg.ts, FUNDING_INTERVAL));
+        Ok(vec![funding_rate_msg(
+            &raw_msg.symbol,
+            ws_msg.ts,
+            raw_msg.rate,
+            funding_time,
+            None,
+            msg.to_string(),
+        )?])
+    } else if let Ok(rest_msg) = serde_json::from_str::<RestfulMsg<RawFundingRateSnapshot>>(msg) {
+        if rest_msg.code != 0 && rest_msg.code != 200 {
+            return Err(ParseError::unsupported("Error HTTP response"));
+        }
+        let raw_msg = rest_msg.data;
+        let mut rate = funding_rate_msg(
+            &raw_msg.symbol,
+            raw_msg.timestamp,
+            raw_msg.fundingRate,
+            raw_msg.nextSettleTime,
+            raw_msg.collectCycle,
+            msg.to_string(),
+        )?;
+        rate.funding_rate_cap = raw_msg.maxFundingRate;
+        rate.funding_rate_floor = raw_msg.minFundingRate;
+        Ok(vec![rate])
+    } else {
+        let rest_msg = serde_json::from_str::<RestfulMsg<FundingRateHistoryPage>>(msg)
+            .map_err(|_e| ParseError::malformed("expected RestfulMsg<FundingRateHistoryPage>"))?;
+        if rest_msg.code != 0 && rest_msg.code != 200 {
+            return Err(ParseError::unsupported("Error HTTP response"));
+        }
+        // Each historical rate was settled at settleTime
+        let mut rates = rest_msg
+            .data
+            .resultList
+            .into_iter()
+            .map(|raw_msg| {
+                funding_rate_msg(
+                    &raw_msg.symbol,
+                    raw_msg.settleTime,
+                    raw_msg.fundingRate,
+                    raw_msg.settleTime,
+                    raw_msg.collectCycle,
+                    serde_json::to_string(&raw_msg).unwrap(),
+                )
+            })
+            .collect::<Result<Vec<FundingRateMsg>, ParseError>>()?;
+        if rates.len() == 1 {
+            rates[0].json = msg.to_string();
+        }
+        Ok(rates)
+    }
+}
@@ -4,53 +4,60 @@ mod mexc_swap;
 use std::collections::HashMap;
 
 use crypto_market_type::MarketType;
+use crypto_msg_type::MessageType;
 
-use crate::{CandlestickMsg, OrderBookMsg, TradeMsg};
+use crate::{
+    exchanges::utils::{json_array, json_i64, json_object, json_str},
+    CandlestickMsg, FundingRateMsg, OrderBookMsg, ParseError, TickerMsg, TradeMsg,
+};
 
 use serde_json::Value;
-use simple_error::SimpleError;
 
 pub(super) const EXCHANGE_NAME: &str = "mexc";
 
-pub(crate) fn extract_symbol(msg: &str) -> Result<String, SimpleError> {
+pub(crate) fn extract_symbol(msg: &str) -> Result<String, ParseError> {
     if let Ok(arr) = serde_json::from_str::<Vec<Value>>(msg) {
-        Ok(arr[1]["symbol"].as_str().unwrap().to_string())
+        let symbol = arr.get(1).map_or(&Value::Null, |x| &x["symbol"]);
+        Ok(json_str(symbol, "symbol")?.to_string())
     } else if let Ok(json_obj) = serde_json::from_str::<HashMap<String, Value>>(msg) {
         if json_obj.contains_key("code") && json_obj.contains_key("data") {
             // RESTful
-            let code = json_obj["code"].as_i64().unwrap();
+            let code = json_i64(&json_obj["code"], "code")?;
             if code != 0 && code != 200 {
-                return Err(SimpleError::new(format!("Error HTTP response {msg}")));
+                return Err(ParseError::unsupported("Error HTTP response"));
             }
-            let data = json_obj["data"].as_object().unwrap();
+            let data = json_object(&json_obj["data"], "data")?;
             if let Some(symbol) = data.get("symbol") {
-                Ok(symbol.as_str().unwrap().to_string())
+                Ok(json_str(symbol, "symbol")?.to_string())
             } else {
                 Ok("NONE".to_string())
             }
         } else if let Some(symbol) = json_obj.get("symbol") {
-            Ok(symbol.as_str().unwrap().to_string())
+            Ok(json_str(symbol, "symbol")?.to_string())
         } else {
-            Err(SimpleError::new(format!("Unknown message format {msg}")))
+            Err(ParseError::unsupported("Unknown message format"))
         }
     } else {
-        Err(SimpleError::new(format!("Unknown message format {msg}")))
+        Err(ParseError::unsupported("Unknown message format"))
     }
 }
 
-pub(crate) fn extract_timestamp(msg: &str) -> Result<Option<i64>, SimpleError> {
+pub(crate) fn extract_timestamp(msg: &str) -> Result<Option<i64>, ParseError> {
     if let Ok(arr) = serde_json::from_str::<Vec<Value>>(msg) {
-        let channel = arr[0].as_str().unwrap();
+        let channel = arr.first().and_then(|x| x.as_str()).unwrap_or_default();
         match channel {
             "push.symbol" => {
-                let data = arr[1]["data"].as_object().unwrap();
+                let data = json_object(&arr[1]["data"], "data")?;
                 if let Some(deals) = data.get("deals") {
-                    let raw_trades = deals.as_array().unwrap();
-                    let timestamp =
-                        raw_trades.iter().map(|raw_trade| raw_trade["t"].as_i64().unwrap()).max();
+                    let timestamp = json_array(deals, "deals")?
+                        .iter()
+                        .map(|raw_trade| json_i64(&raw_trade["t"], "t"))
+                        .collect::<Result<Vec<i64>, ParseError>>()?
+                        .into_iter()
+                        .max();
 
                     if timestamp.is_none() {
-                        Err(SimpleError::new(format!("deals is empty in {msg}")))
+                        Err(ParseError::unsupported("deals is empty"))
                     } else {
                         Ok(timestamp)
                     }
@@ -59,42 +66,42 @@ pub(crate) fn extract_timestamp(msg: &str) -> Result<Option<i64>, SimpleError> {
                 }
             }
             "push.kline" => {
-                let data = arr[1]["data"].as_object().unwrap();
-                let t = data["t"].as_i64().unwrap();
+                let t = json_i64(&arr[1]["data"]["t"], "t")?;
                 Ok(Some(t * 1000))
             }
-            _ => Err(SimpleError::new(format!("Unknown channel {channel} in {msg}"))),
+            _ => Err(ParseError::unsupported(format!("Unknown channel {channel}"))),
         }
     } else if let Ok(json_obj) = serde_json::from_str::<HashMap<String, Value>>(msg) {
         if json_obj.contains_key("code") && json_obj.contains_key("data") {
             // RESTful
-            let code = json_obj["code"].as_i64().unwrap();
+            let code = json_i64(&json_obj["code"], "code")?;
             if code != 0 && code != 200 {
-                return Err(SimpleError::new(format!("Error HTTP response {msg}")));
+                return Err(ParseError::unsupported("Error HTTP response"));
             }
-            let data = json_obj["data"].as_object().unwrap();
-            Ok(data.get("timestamp").map(|x| x.as_i64().unwrap()))
+            let data = json_object(&json_obj["data"], "data")?;
+            data.get("timestamp").map(|x| json_i64(x, "timestamp")).transpose()
         } else {
             // websocket
-            let channel = json_obj["channel"].as_str().unwrap();
+            let channel =
+                json_str(json_obj.get("channel").unwrap_or(&Value::Null), "channel")?;
             if let Some(x) = json_obj.get("ts") {
-                Ok(Some(x.as_i64().unwrap()))
+                Ok(Some(json_i64(x, "ts")?))
             } else if channel == "push.kline" {
-                let data = json_obj["data"].as_object().unwrap();
+                let data = json_object(json_obj.get("data").unwrap_or(&Value::Null), "data")?;
                 if let Some(tdt) = data.get("tdt") {
-                    Ok(Some(tdt.as_i64().unwrap()))
+                    Ok(Some(json_i64(tdt, "tdt")?))
                 } else {
-                    Ok(Some(data["t"].as_i64().unwrap() * 1000))
+                    Ok(Some(json_i64(&data["t"], "t")? * 1000))
                 }
-            } else if let Some(deals) = json_obj["data"].get("deals") {
-                let timestamp = deals
-                    .as_array()
-                    .unwrap()
+            } else if let Some(deals) = json_obj.get("data").and_then(|x| x.get("deals")) {
+                let timestamp = json_array(deals, "deals")?
                     .iter()
-                    .map(|raw_trade| raw_trade["t"].as_i64().unwrap())
+                    .map(|raw_trade| json_i64(&raw_trade["t"], "t"))
+                    .collect::<Result<Vec<i64>, ParseError>>()?
+                    .into_iter()
                     .max();
                 if timestamp.is_none() {
-                    Err(SimpleError::new(format!("deals is empty in {msg}")))
+                    Err(ParseError::unsupported("deals is empty"))
                 } else {
                     Ok(timestamp)
                 }
@@ -103,14 +110,61 @@ pub(crate) fn extract_timestamp(msg: &str) -> Result<Option<i64>, SimpleError> {
             }
         }
     } else {
-        Err(SimpleError::new(format!("Failed to extract symbol from {msg}")))
+        Err(ParseError::unsupported("Failed to extract symbol"))
     }
 }
 
-pub(crate) fn parse_trade(
-    market_type: MarketType,
-    msg: &str,
-) -> Result<Vec<TradeMsg>, SimpleError> {
+pub(crate) fn get_msg_type(msg: &str) -> MessageType {
+    if let Ok(arr) = serde_json::from_str::<Vec<Value>>(msg) {
+        // spot websocket messages before 2022-03-11
+        let channel = arr.first().and_then(|x| x.as_str()).unwrap_or_default();
+        let data = arr.get(1).and_then(|x| x.get("data"));
+        match channel {
+            "push.symbol" => {
+                if data.is_some_and(|x| x.get("deals").is_some()) {
+                    MessageType::Trade
+                } else if data.is_some_and(|x| x.get("asks").is_some() || x.get("bids").is_some()) {
+                    MessageType::L2Event
+                } else {
+                    MessageType::Other
+                }
+            }
+            "push.kline" => MessageType::Candlestick,
+            _ => MessageType::Other,
+        }
+    } else if let Ok(json_obj) = serde_json::from_str::<HashMap<String, Value>>(msg) {
+        if json_obj.contains_key("code") && json_obj.contains_key("data") {
+            // RESTful
+            let data = &json_obj["data"];
+            if data.get("asks").is_some() && data.get("bids").is_some() {
+                MessageType::L2Snapshot
+            } else if data.get("fundingRate").is_some()
+                || data
+                    .get("resultList")
+                    .and_then(|x| x.get(0))
+                    .is_some_and(|x| x.get("fundingRate").is_some())
+            {
+                MessageType::FundingRate
+            } else {
+                MessageType::Other
+            }
+        } else {
+            match json_obj.get("channel").and_then(|x| x.as_str()).unwrap_or_default() {
+                "push.deal" => MessageType::Trade,
+                "push.depth" => MessageType::L2Event,
+                "push.limit.depth" | "push.depth.full" => MessageType::L2TopK,
+                "push.kline" => MessageType::Candlestick,
+                "push.ticker" => MessageType::Ticker,
+                "push.funding.rate" => MessageType::FundingRate,
+                _ => MessageType::Other,
+            }
+        }
+    } else {
+        MessageType::Other
+    }
+}
+
+pub(crate) fn parse_trade(market_type: MarketType, msg: &str) -> Result<Vec<TradeMsg>, ParseError> {
     if market_type == MarketType::Spot {
         mexc_spot::parse_trade(msg)
     } else {
@@ -122,12 +176,10 @@ pub(crate) fn parse_l2(
     market_type: MarketType,
     msg: &str,
     timestamp: Option<i64>,
-) -> Result<Vec<OrderBookMsg>, SimpleError> {
+) -> Result<Vec<OrderBookMsg>, ParseError> {
     if market_type == MarketType::Spot {
-        mexc_spot::parse_l2(
-            msg,
-            timestamp.expect("MEXC Spot orderbook messages don't have timestamp"),
-        )
+        // MEXC Spot orderbook messages don't have timestamp
+        mexc_spot::parse_l2(msg, timestamp.ok_or_else(ParseError::missing_timestamp)?)
     } else {
         mexc_swap::parse_l2(market_type, msg)
     }
@@ -137,25 +189,45 @@ pub(crate) fn parse_l2_topk(
     market_type: MarketType,
     msg: &str,
     timestamp: Option<i64>,
-) -> Result<Vec<OrderBookMsg>, SimpleError> {
+) -> Result<Vec<OrderBookMsg>, ParseError> {
     if market_type == MarketType::Spot {
-        mexc_spot::parse_l2_topk(
-            msg,
-            timestamp.expect("MEXC Spot L2TopK messages don't have timestamp"),
-        )
+        // MEXC Spot L2TopK messages don't have timestamp
+        mexc_spot::parse_l2_topk(msg, timestamp.ok_or_else(ParseError::missing_timestamp)?)
     } else {
         mexc_swap::parse_l2(market_type, msg)
     }
 }
 
+pub(crate) fn parse_ticker(
+    market_type: MarketType,
+    msg: &str,
+) -> Result<Vec<TickerMsg>, ParseError> {
+    if market_type == MarketType::Spot {
+        Err(ParseError::unsupported("Not implemented"))
+    } else {
+        mexc_swap::parse_ticker(market_type, msg)
+    }
+}
+
 pub(crate) fn parse_candlestick(
     market_type: MarketType,
     msg: &str,
     received_at: Option<i64>,
-) -> Result<Vec<CandlestickMsg>, SimpleError> {
+) -> Result<Vec<CandlestickMsg>, ParseError> {
     if market_type == MarketType::Spot {
         mexc_spot::parse_candlestick(msg, received_at)
     } else {
         mexc_swap::parse_candlestick(market_type, msg)
     }
 }
+
+pub(crate) fn parse_funding_rate(
+    market_type: MarketType,
+    msg: &str,
+) -> Result<Vec<FundingRateMsg>, ParseError> {
+    if market_type == MarketType::Spot {
+        Err(ParseError::unsupported("Spot markets have no funding rate"))
+    } else {
+        mexc_swap::parse_funding_rate(market_type, msg)
+    }
+}
@@ -1,19 +1,21 @@
 mod okx_v3;
 mod okx_v5;
 
-use crypto_message::{BboMsg, CandlestickMsg};
-use simple_error::SimpleError;
+use crypto_message::{
+    BboMsg, CandlestickMsg, IndexPriceMsg, LiquidationMsg, MarkPriceMsg, OpenInterestMsg,
+    OptionTickerMsg, TickerMsg,
+};
 use std::collections::HashMap;
 
-use crate::{FundingRateMsg, OrderBookMsg, TradeMsg};
+use crate::{checksum::L2Levels, FundingRateMsg, OrderBookMsg, ParseError, TradeMsg};
 use crypto_market_type::MarketType;
 use crypto_msg_type::MessageType;
 use serde_json::Value;
 
 pub(super) const EXCHANGE_NAME: &str = "okx";
 
-pub(crate) fn extract_symbol(_market_type: MarketType, msg: &str) -> Result<String, SimpleError> {
-    let obj = serde_json::from_str::<HashMap<String, Value>>(msg).map_err(SimpleError::from)?;
+pub(crate) fn extract_symbol(_market_type: MarketType, msg: &str) -> Result<String, ParseError> {
+    let obj = serde_json::from_str::<HashMap<String, Value>>(msg).map_err(ParseError::from)?;
     if obj.contains_key("arg") && obj.contains_key("data") {
         okx_v5::extract_symbol(msg)
     } else if obj.contains_key("table") && obj.contains_key("data") {
@@ -30,8 +32,8 @@ pub(crate) fn extract_symbol(_market_type: MarketType, msg: &str) -> Result<Stri
 pub(crate) fn extract_timestamp(
     _market_type: MarketType,
     msg: &str,
-) -> Result<Option<i64>, SimpleError> {
-    let obj = serde_json::from_str::<HashMap<String, Value>>(msg).map_err(SimpleError::from)?;
+) -> Result<Option<i64>, ParseError> {
+    let obj = serde_json::from_str::<HashMap<String, Value>>(msg).map_err(ParseError::from)?;
     if obj.contains_key("arg") && obj.contains_key("data") {
         // websocket v5
         okx_v5::extract_timestamp(msg)
@@ -48,8 +50,10 @@ pub(crate) fn extract_timestamp(
 }
 
 pub(crate) fn get_msg_type(msg: &str) -> MessageType {
-    let obj =
-        serde_json::from_str::<HashMap<String, Value>>(msg).map_err(SimpleError::from).unwrap();
+    let obj = match serde_json::from_str::<HashMap<String, Value>>(msg) {
+        Ok(obj) => obj,
+        Err(_) => return MessageType::Other,
+    };
     if obj.contains_key("arg") && obj.contains_key("data") {
         // websocket v5
         okx_v5::get_msg_type(msg)
@@ -65,40 +69,48 @@ pub(crate) fn get_msg_type(msg: &str) -> MessageType {
     }
 }
 
-pub(crate) fn parse_trade(
-    market_type: MarketType,
-    msg: &str,
-) -> Result<Vec<TradeMsg>, SimpleError> {
-    let obj =
-        serde_json::from_str::<HashMap<String, Value>>(msg).map_err(SimpleError::from).unwrap();
+pub(crate) fn parse_trade(market_type: MarketType, msg: &str) -> Result<Vec<TradeMsg>, ParseError> {
+    let obj = serde_json::from_str::<HashMap<String, Value>>(msg).map_err(ParseError::from)?;
     if obj.contains_key("arg") && obj.contains_key("data") {
         okx_v5::parse_trade(market_type, msg)
     } else if obj.contains_key("table") && obj.contains_key("data") {
         okx_v3::parse_trade(market_type, msg)
     } else {
-        panic!("Unknown msg format {msg}")
+        Err(ParseError::unsupported("Unknown msg format"))
     }
 }
 
 pub(crate) fn parse_l2(
     market_type: MarketType,
     msg: &str,
-) -> Result<Vec<OrderBookMsg>, SimpleError> {
-    let obj =
-        serde_json::from_str::<HashMap<String, Value>>(msg).map_err(SimpleError::from).unwrap();
+) -> Result<Vec<OrderBookMsg>, ParseError> {
+    let obj = serde_json::from_str::<HashMap<String, Value>>(msg).map_err(ParseError::from)?;
     if obj.contains_key("arg") && obj.contains_key("data") {
         okx_v5::parse_l2(market_type, msg)
     } else if obj.contains_key("table") && obj.contains_key("data") {
         okx_v3::parse_l2(market_type, msg)
     } else {
-        panic!("Unknown msg format {msg}")
+        Err(ParseError::unsupported("Unknown msg format"))
+    }
+}
+
+pub(crate) fn parse_l2_levels(msg: &str) -> Result<L2Levels, ParseError> {
+    let obj = serde_json::from_str::<HashMap<String, Value>>(msg).map_err(ParseError::from)?;
+    if obj.contains_key("arg") && obj.contains_key("data") {
+        okx_v5::parse_l2_levels(msg)
+    } else {
+        Err(ParseError::unsupported("Only v5 orderbooks have checksums"))
     }
 }
 
+pub(crate) fn calc_checksum(asks: &[&[String; 2]], bids: &[&[String; 2]]) -> u32 {
+    okx_v5::calc_checksum(asks, bids)
+}
+
 pub(crate) fn parse_l2_topk(
     market_type: MarketType,
     msg: &str,
-) -> Result<Vec<OrderBookMsg>, SimpleError> {
+) -> Result<Vec<OrderBookMsg>, ParseError> {
     parse_l2(market_type, msg)
 }
 
@@ -106,25 +118,95 @@ pub(crate) fn parse_funding_rate(
     market_type: MarketType,
     msg: &str,
     received_at: i64,
-) -> Result<Vec<FundingRateMsg>, SimpleError> {
-    let obj = serde_json::from_str::<HashMap<String, Value>>(msg).map_err(SimpleError::from)?;
+) -> Result<Vec<FundingRateMsg>, ParseError> {
+    let obj = serde_json::from_str::<HashMap<String, Value>>(msg).map_err(ParseError::from)?;
     if obj.contains_key("arg") && obj.contains_key("data") {
         okx_v5::parse_funding_rate(market_type, msg, received_at)
     } else if obj.contains_key("table") && obj.contains_key("data") {
         okx_v3::parse_funding_rate(market_type, msg, received_at)
     } else {
-        panic!("Unknown msg format {msg}")
+        Err(ParseError::unsupported("Unknown msg format"))
+    }
+}
+
+pub(crate) fn parse_liquidation(
+    market_type: MarketType,
+    msg: &str,
+) -> Result<Vec<LiquidationMsg>, ParseError> {
+    let obj = serde_json::from_str::<HashMap<String, Value>>(msg).map_err(ParseError::from)?;
+    if obj.contains_key("arg") && obj.contains_key("data") {
+        okx_v5::parse_liquidation(market_type, msg)
+    } else {
+        Err(ParseError::unsupported("Only v5 has the liquidation-orders channel"))
+    }
+}
+
+pub(crate) fn parse_mark_price(
+    market_type: MarketType,
+    msg: &str,
+) -> Result<Vec<MarkPriceMsg>, ParseError> {
+    let obj = serde_json::from_str::<HashMap<String, Value>>(msg).map_err(ParseError::from)?;
+    if obj.contains_key("arg") && obj.contains_key("data") {
+        okx_v5::parse_mark_price(market_type, msg)
+    } else {
+        Err(ParseError::unsupported("Only v5 has the mark-price channel"))
+    }
+}
+
+pub(crate) fn parse_index_price(
+    market_type: MarketType,
+    msg: &str,
+) -> Result<Vec<IndexPriceMsg>, ParseError> {
+    let obj = serde_json::from_str::<HashMap<String, Value>>(msg).map_err(ParseError::from)?;
+    if obj.contains_key("arg") && obj.contains_key("data") {
+        okx_v5::parse_index_price(market_type, msg)
+    } else {
+        Err(ParseError::unsupported("Only v5 has the index-tickers channel"))
+    }
+}
+
+pub(crate) fn parse_open_interest(
+    market_type: MarketType,
+    msg: &str,
+) -> Result<Vec<OpenInterestMsg>, ParseError> {
+    let obj = serde_json::from_str::<HashMap<String, Value>>(msg).map_err(ParseError::from)?;
+    if obj.contains_key("table") {
+        Err(ParseError::unsupported("Only v5 open interest is supported"))
+    } else {
+        // websocket or restful v5
+        okx_v5::parse_open_interest(market_type, msg)
     }
 }
 
-pub(crate) fn parse_bbo(market_type: MarketType, msg: &str) -> Result<Vec<BboMsg>, SimpleError> {
-    let obj = serde_json::from_str::<HashMap<String, Value>>(msg).map_err(SimpleError::from)?;
+pub(crate) fn parse_bbo(market_type: MarketType, msg: &str) -> Result<Vec<BboMsg>, ParseError> {
+    let obj = serde_json::from_str::<HashMap<String, Value>>(msg).map_err(ParseError::from)?;
     if obj.contains_key("arg") && obj.contains_key("data") {
         okx_v5::parse_bbo(market_type, msg)
     } else if obj.contains_key("table") && obj.contains_key("data") {
         okx_v3::parse_bbo(market_type, msg)
     } else {
-        panic!("Unknown msg format {msg}")
+        Err(ParseError::unsupported("Unknown msg format"))
+    }
+}
+
+pub(crate) fn parse_ticker(
+    market_type: MarketType,
+    msg: &str,
+) -> Result<Vec<TickerMsg>, ParseError> {
+    let obj = serde_json::from_str::<HashMap<String, Value>>(msg).map_err(ParseError::from)?;
+    if obj.contains_key("arg") && obj.contains_key("data") {
+        okx_v5::parse_ticker(market_type, msg)
+    } else {
+        Err(ParseError::unsupported("Unsupported ticker message"))
+    }
+}
+
+pub(crate) fn parse_option_ticker(msg: &str) -> Result<Vec<OptionTickerMsg>, ParseError> {
+    let obj = serde_json::from_str::<HashMap<String, Value>>(msg).map_err(ParseError::from)?;
+    if obj.contains_key("arg") && obj.contains_key("data") {
+        okx_v5::parse_option_ticker(msg)
+    } else {
+        Err(ParseError::unsupported("Only v5 has the opt-summary channel"))
     }
 }
 
@@ -132,13 +214,13 @@ pub(crate) fn parse_candlestick(
     market_type: MarketType,
     msg: &str,
     received_at: i64,
-) -> Result<Vec<CandlestickMsg>, SimpleError> {
-    let obj = serde_json::from_str::<HashMap<String, Value>>(msg).map_err(SimpleError::from)?;
+) -> Result<Vec<CandlestickMsg>, ParseError> {
+    let obj = serde_json::from_str::<HashMap<String, Value>>(msg).map_err(ParseError::from)?;
     if obj.contains_key("arg") && obj.contains_key("data") {
         okx_v5::parse_candlestick(market_type, msg, received_at)
     } else if obj.contains_key("table") && obj.contains_key("data") {
         okx_v3::parse_candlestick(market_type, msg)
     } else {
-        panic!("Unknown msg format {msg}")
+        Err(ParseError::unsupported("Unknown msg format"))
     }
 }
@@ -1,16 +1,18 @@
 use crypto_market_type::MarketType;
 use crypto_msg_type::MessageType;
 
-use super::super::utils::calc_quantity_and_volume;
+use super::super::utils::{
+    annualize_funding_rate, calc_quantity_and_volume, json_str, parse_exact,
+};
 use crypto_message::{
     BboMsg, CandlestickMsg, FundingRateMsg, Order, OrderBookMsg, TradeMsg, TradeSide,
 };
 
 use super::EXCHANGE_NAME;
+use crate::ParseError;
 use chrono::DateTime;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
-use simple_error::SimpleError;
 use std::collections::HashMap;
 
 // https://www.okex.com/docs/en/#spot_ws-trade
@@ -65,52 +67,44 @@ struct WebsocketMsg<T: Sized> {
     extra: HashMap<String, Value>,
 }
 
-pub(super) fn extract_symbol(msg: &str) -> Result<String, SimpleError> {
-    let ws_msg = serde_json::from_str::<WebsocketMsg<Value>>(msg).map_err(SimpleError::from)?;
-    let symbol = ws_msg.data.iter().map(|v| v["instrument_id"].as_str().unwrap()).next();
-    if let Some(symbol) = symbol {
-        Ok(symbol.to_string())
+pub(super) fn extract_symbol(msg: &str) -> Result<String, ParseError> {
+    let ws_msg = serde_json::from_str::<WebsocketMsg<Value>>(msg).map_err(ParseError::from)?;
+    if let Some(v) = ws_msg.data.first() {
+        Ok(json_str(&v["instrument_id"], "instrument_id")?.to_string())
     } else {
-        Err(SimpleError::new("empty array"))
+        Err(ParseError::unsupported("empty array"))
     }
 }
 
-pub(super) fn extract_timestamp(msg: &str) -> Result<Option<i64>, SimpleError> {
-    let ws_msg = serde_json::from_str::<WebsocketMsg<Value>>(msg).map_err(SimpleError::from)?;
+pub(super) fn extract_timestamp(msg: &str) -> Result<Option<i64>, ParseError> {
+    let ws_msg = serde_json::from_str::<WebsocketMsg<Value>>(msg).map_err(ParseError::from)?;
     if ws_msg.table == "swap/funding_rate" {
         return Ok(None);
     }
     let timestamp = ws_msg
         .data
         .iter()
-        .map(|x| {
-            if let Some(timestamp) = x.get("timestamp") {
-                DateTime::parse_from_rfc3339(timestamp.as_str().unwrap())
-                    .unwrap()
-                    .timestamp_millis()
+        .map(|x| -> Result<i64, ParseError> {
+            let timestamp = if let Some(timestamp) = x.get("timestamp") {
+                json_str(timestamp, "timestamp")?
             } else if let Some(candle) = x.get("candle") {
-                let arr = candle.as_array().unwrap();
-                DateTime::parse_from_rfc3339(arr[0].as_str().unwrap()).unwrap().timestamp_millis()
+                json_str(&candle[0], "candle[0]")?
             } else {
-                panic!("Unknown message format: {msg}");
-            }
+                return Err(ParseError::missing_timestamp());
+            };
+            Ok(DateTime::parse_from_rfc3339(timestamp)?.timestamp_millis())
         })
+        .collect::<Result<Vec<i64>, ParseError>>()?
+        .into_iter()
         .max();
 
-    if timestamp.is_none() {
-        Err(SimpleError::new(format!("data is empty in {msg}")))
-    } else {
-        Ok(timestamp)
-    }
+    if timestamp.is_none() { Err(ParseError::unsupported("data is empty")) } else { Ok(timestamp) }
 }
 
 pub(super) fn get_msg_type(msg: &str) -> MessageType {
     if let Ok(ws_msg) = serde_json::from_str::<WebsocketMsg<Value>>(msg) {
         let table = ws_msg.table;
-        let channel = {
-            let arr = table.split('/').collect::<Vec<&str>>();
-            arr[1]
-        };
+        let channel = table.split('/').nth(1).unwrap_or_default();
         if channel == "trade" {
             MessageType::Trade
         } else if channel == "depth_l2_tbt" {
@@ -131,31 +125,34 @@ pub(super) fn get_msg_type(msg: &str) -> MessageType {
     }
 }
 
-pub(super) fn parse_trade(
-    market_type: MarketType,
-    msg: &str,
-) -> Result<Vec<TradeMsg>, SimpleError> {
-    let ws_msg = serde_json::from_str::<WebsocketMsg<RawTradeMsg>>(msg).map_err(|_e| {
-        SimpleError::new(format!("Failed to deserialize {msg} to WebsocketMsg<RawTradeMsg>"))
-    })?;
-    let mut trades: Vec<Result<TradeMsg, SimpleError>> = ws_msg
+pub(super) fn parse_trade(market_type: MarketType, msg: &str) -> Result<Vec<TradeMsg>, ParseError> {
+    let ws_msg = serde_json::from_str::<WebsocketMsg<RawTradeMsg>>(msg)
+        .map_err(|_e| ParseError::malformed("expected WebsocketMsg<RawTradeMsg>"))?;
+    let mut trades: Vec<Result<TradeMsg, ParseError>> = ws_msg
         .data
         .into_iter()
-        .map(|raw_trade| {
-            let timestamp = DateTime::parse_from_rfc3339(&raw_trade.timestamp).unwrap();
-            let price = raw_trade.price.parse::<f64>().unwrap();
-            let size = if raw_trade.qty.is_some() {
-                raw_trade.qty.clone().unwrap().parse::<f64>().unwrap()
-            } else if raw_trade.size.is_some() {
-                raw_trade.size.clone().unwrap().parse::<f64>().unwrap()
+        .map(|raw_trade| -> Result<TradeMsg, ParseError> {
+            let timestamp = DateTime::parse_from_rfc3339(&raw_trade.timestamp)?.timestamp_millis();
+            let price = raw_trade.price.parse::<f64>()?;
+            let size = if let Some(qty) = raw_trade.qty.as_ref() {
+                qty.parse::<f64>()?
+            } else if let Some(size) = raw_trade.size.as_ref() {
+                size.parse::<f64>()?
             } else {
-                return Err(SimpleError::new("qty and size are both missing".to_string()));
+                return Err(ParseError::unsupported("qty and size are both missing"));
             };
-            let side = raw_trade.side.clone().unwrap();
-            let pair =
-                crypto_pair::normalize_pair(&raw_trade.instrument_id, EXCHANGE_NAME).unwrap();
-            let (quantity_base, quantity_quote, _) =
-                calc_quantity_and_volume(EXCHANGE_NAME, market_type, &pair, price, size);
+            let side =
+                raw_trade.side.clone().ok_or_else(|| ParseError::malformed("side is missing"))?;
+            let pair = crypto_pair::normalize_pair(&raw_trade.instrument_id, EXCHANGE_NAME)
+                .ok_or_else(|| ParseError::symbol_normalization(&raw_trade.instrument_id))?;
+            let (quantity_base, quantity_quote, _) = calc_quantity_and_volume(
+                EXCHANGE_NAME,
+                market_type,
+                &pair,
+                price,
+                size,
+                timestamp,
+            )?;
 
             Ok(TradeMsg {
                 exchange: EXCHANGE_NAME.to_string(),
@@ -163,11 +160,17 @@ pub(super) fn parse_trade(
                 symbol: raw_trade.instrument_id.clone(),
                 pair,
                 msg_type: MessageType::Trade,
-                timestamp: timestamp.timestamp_millis(),
+                timestamp,
                 price,
                 quantity_base,
                 quantity_quote,
                 quantity_contract: if market_type == MarketType::Spot { None } else { Some(size) },
+                price_exact: parse_exact(&raw_trade.price),
+                quantity_exact: raw_trade
+                    .qty
+                    .as_deref()
+                    .or(raw_trade.size.as_deref())
+                    .and_then(parse_exact),
                 side: if side.as_str() == "sell" { TradeSide::Sell } else { TradeSide::Buy },
                 trade_id: raw_trade.trade_id.to_string(),
                 json: serde_json::to_string(&raw_trade).unwrap(),
@@ -183,34 +186,47 @@ pub(super) fn parse_trade(
     trades.into_iter().collect()
 }
 
+// Perpetual swaps settle funding every 8 hours unless settlement_time says otherwise
+const FUNDING_INTERVAL: i64 = 8 * 3600 * 1000;
+
 pub(super) fn parse_funding_rate(
     market_type: MarketType,
     msg: &str,
     received_at: i64,
-) -> Result<Vec<FundingRateMsg>, SimpleError> {
-    let ws_msg = serde_json::from_str::<WebsocketMsg<RawFundingRateMsg>>(msg).map_err(|_e| {
-        SimpleError::new(format!("Failed to deserialize {msg} to WebsocketMsg<RawFundingRateMsg>"))
-    })?;
+) -> Result<Vec<FundingRateMsg>, ParseError> {
+    let ws_msg = serde_json::from_str::<WebsocketMsg<RawFundingRateMsg>>(msg)
+        .map_err(|_e| ParseError::malformed("expected WebsocketMsg<RawFundingRateMsg>"))?;
 
     let mut rates: Vec<FundingRateMsg> = ws_msg
         .data
         .into_iter()
-        .map(|raw_msg| {
-            let funding_time = DateTime::parse_from_rfc3339(&raw_msg.funding_time).unwrap();
-            FundingRateMsg {
+        .map(|raw_msg| -> Result<FundingRateMsg, ParseError> {
+            let funding_time = DateTime::parse_from_rfc3339(&raw_msg.funding_time)?;
+            let settlement_time = DateTime::parse_from_rfc3339(&raw_msg.settlement_time)?;
+            let funding_rate = raw_msg.funding_rate.parse::<f64>()?;
+            let funding_interval =
+                Some(settlement_time.timestamp_millis() - funding_time.timestamp_millis())
+                    .filter(|x| *x > 0)
+                    .unwrap_or(FUNDING_INTERVAL);
+            Ok(FundingRateMsg {
                 exchange: EXCHANGE_NAME.to_string(),
                 market_type,
                 symbol: raw_msg.instrument_id.clone(),
-                pair: crypto_pair::normalize_pair(&raw_msg.instrument_id, EXCHANGE_NAME).unwrap(),
+                pair: crypto_pair::normalize_pair(&raw_msg.instrument_id, EXCHANGE_NAME)
+                    .ok_or_else(|| ParseError::symbol_normalization(&raw_msg.instrument_id))?,
                 msg_type: MessageType::FundingRate,
                 timestamp: received_at,
-                funding_rate: raw_msg.funding_rate.parse::<f64>().unwrap(),
+                funding_rate,
                 funding_time: funding_time.timestamp_millis(),
-                estimated_rate: Some(raw_msg.estimated_rate.parse::<f64>().unwrap()),
+                estimated_rate: Some(raw_msg.estimated_rate.parse::<f64>()?),
+                funding_interval,
+                funding_rate_cap: None,
+                funding_rate_floor: None,
+                annualized_rate: annualize_funding_rate(funding_rate, funding_interval),
                 json: serde_json::to_string(&raw_msg).unwrap(),
-            }
+            })
         })
-        .collect();
+        .collect::<Result<Vec<FundingRateMsg>, ParseError>>()?;
 
     if rates.len() == 1 {
         rates[0].json = msg.to_string();
@@ -221,11 +237,12 @@ pub(super) fn parse_funding_rate(
 pub(super) fn parse_l2(
     market_type: MarketType,
     msg: &str,
-) -> Result<Vec<OrderBookMsg>, SimpleError> {
-    let ws_msg = serde_json::from_str::<WebsocketMsg<RawOrderbookMsg>>(msg).map_err(|_e| {
-        SimpleError::new(format!("Failed to deserialize {msg} to WebsocketMsg<RawOrderbookMsg>"))
-    })?;
-    debug_assert_eq!(ws_msg.data.len(), 1);
+) -> Result<Vec<OrderBookMsg>, ParseError> {
+    let ws_msg = serde_json::from_str::<WebsocketMsg<RawOrderbookMsg>>(msg)
+        .map_err(|_e| ParseError::malformed("expected WebsocketMsg<RawOrderbookMsg>"))?;
+    if ws_msg.data.len() != 1 {
+        return Err(ParseError::malformed("data should have exactly one orderbook"));
+    }
 
     let msg_type =
         if ws_msg.table.ends_with("/depth5") { MessageType::L2TopK } else { MessageType::L2Event };
@@ -238,36 +255,62 @@ pub(super) fn parse_l2(
     let mut orderbooks = ws_msg
         .data
         .iter()
-        .map(|raw_orderbook| {
+        .map(|raw_orderbook| -> Result<OrderBookMsg, ParseError> {
             let symbol = raw_orderbook.instrument_id.clone();
-            let pair = crypto_pair::normalize_pair(&symbol, EXCHANGE_NAME).unwrap();
-            let timestamp = DateTime::parse_from_rfc3339(&raw_orderbook.timestamp).unwrap();
+            let pair = crypto_pair::normalize_pair(&symbol, EXCHANGE_NAME)
+                .ok_or_else(|| ParseError::symbol_normalization(&symbol))?;
+            let timestamp = DateTime::parse_from_rfc3339(&raw_orderbook.timestamp)
+                .map_err(|err| ParseError::malformed(err.to_string()))?
+                .timestamp_millis();
 
-            let parse_order = |raw_order: &[String]| -> Order {
-                let price = raw_order[0].parse::<f64>().unwrap();
-                let quantity = raw_order[1].parse::<f64>().unwrap();
-                let (quantity_base, quantity_quote, quantity_contract) =
-                    calc_quantity_and_volume(EXCHANGE_NAME, market_type, &pair, price, quantity);
+            let parse_order = |raw_order: &[String]| -> Result<Order, ParseError> {
+                let (price, quantity) = match raw_order {
+                    [price, quantity, ..] => (price.parse::<f64>()?, quantity.parse::<f64>()?),
+                    _ => return Err(ParseError::malformed("expected [price, quantity, ...]")),
+                };
+                let (quantity_base, quantity_quote, quantity_contract) = calc_quantity_and_volume(
+                    EXCHANGE_NAME,
+                    market_type,
+                    &pair,
+                    price,
+                    quantity,
+                    timestamp,
+                )?;
 
-                Order { price, quantity_base, quantity_quote, quantity_contract }
+                Ok(Order {
+                    price,
+                    quantity_base,
+                    quantity_quote,
+                    quantity_contract,
+                    price_exact: parse_exact(&raw_order[0]),
+                    quantity_exact: parse_exact(&raw_order[1]),
+                })
             };
 
-            OrderBookMsg {
+            Ok(OrderBookMsg {
                 exchange: EXCHANGE_NAME.to_string(),
                 market_type,
                 symbol,
                 pair: pair.clone(),
                 msg_type,
-                timestamp: timestamp.timestamp_millis(),
+                timestamp,
                 seq_id: None,
                 prev_seq_id: None,
-                asks: raw_orderbook.asks.iter().map(|x| parse_order(x)).collect::<Vec<Order>>(),
-                bids: raw_orderbook.bids.iter().map(|x| parse_order(x)).collect::<Vec<Order>>(),
+                asks: raw_orderbook
+                    .asks
+                    .iter()
+                    .map(|x| parse_order(x))
+                    .collect::<Result<Vec<Order>, ParseError>>()?,
+                bids: raw_orderbook
+                    .bids
+                    .iter()
+                    .map(|x| parse_order(x))
+                    .collect::<Result<Vec<Order>, ParseError>>()?,
                 snapshot,
-                json: serde_json::to_string(raw_orderbook).unwrap(),
-            }
+                json: serde_json::to_string(raw_orderbook)?,
+            })
         })
-        .collect::<Vec<OrderBookMsg>>();
+        .collect::<Result<Vec<OrderBookMsg>, ParseError>>()?;
 
     if orderbooks.len() == 1 {
         orderbooks[0].json = msg.to_string();
@@ -275,13 +318,13 @@ pub(super) fn parse_l2(
     Ok(orderbooks)
 }
 
-pub(super) fn parse_bbo(_market_type: MarketType, _msg: &str) -> Result<Vec<BboMsg>, SimpleError> {
-    todo!()
+pub(super) fn parse_bbo(_market_type: MarketType, _msg: &str) -> Result<Vec<BboMsg>, ParseError> {
+    Err(ParseError::unsupported("Not implemented"))
 }
 
 pub(super) fn parse_candlestick(
     _market_type: MarketType,
     _msg: &str,
-) -> Result<Vec<CandlestickMsg>, SimpleError> {
-    todo!("not implemented")
+) -> Result<Vec<CandlestickMsg>, ParseError> {
+    Err(ParseError::unsupported("Not implemented"))
 }
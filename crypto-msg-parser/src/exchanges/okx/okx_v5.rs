@@ -1,15 +1,19 @@
 use crypto_market_type::MarketType;
 use crypto_msg_type::MessageType;
 
-use super::super::utils::calc_quantity_and_volume;
+use super::super::utils::{
+    annualize_funding_rate, calc_open_interest, calc_quantity_and_volume, json_str,
+    parse_candle_period, parse_exact,
+};
 use crypto_message::{
-    BboMsg, CandlestickMsg, FundingRateMsg, Order, OrderBookMsg, TradeMsg, TradeSide,
+    BboMsg, CandlestickMsg, FundingRateMsg, IndexPriceMsg, LiquidationMsg, MarkPriceMsg,
+    OpenInterestMsg, OptionTickerMsg, Order, OrderBookMsg, TickerMsg, TradeMsg, TradeSide,
 };
 
 use super::EXCHANGE_NAME;
+use crate::{ParseError, checksum::L2Levels};
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
-use simple_error::SimpleError;
 use std::collections::HashMap;
 
 // https://www.okx.com/docs-v5/en/#websocket-api-public-channel-trades-channel
@@ -44,8 +48,14 @@ struct RawFundingRateMsg {
     instType: String,
     instId: String,
     fundingRate: String,
-    nextFundingRate: String,
+    nextFundingRate: String, // empty if the estimated rate is unavailable
     fundingTime: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    nextFundingTime: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    maxFundingRate: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    minFundingRate: Option<String>,
     #[serde(flatten)]
     extra: HashMap<String, Value>,
 }
@@ -61,6 +71,134 @@ struct RawCandlestickMsg {
     close: String,  // Close price
     vol: String,    // Trading volume, with a unit of contract
     volCcy: String, // Trading volume, with a unit of currency
+    // The two fields below are absent in old messages
+    #[serde(default)]
+    volCcyQuote: Option<String>, // Trading volume, with a unit of quote currency
+    #[serde(default)]
+    confirm: Option<String>, // 0, uncompleted; 1, completed
+}
+
+// https://www.okx.com/docs-v5/en/#websocket-api-public-channel-tickers-channel
+#[derive(Serialize, Deserialize)]
+#[allow(non_snake_case)]
+struct RawTickerMsg {
+    instType: String,
+    instId: String,
+    last: String,
+    lastSz: String,
+    askPx: String,
+    askSz: String,
+    bidPx: String,
+    bidSz: String,
+    open24h: String,
+    high24h: String,
+    low24h: String,
+    volCcy24h: String, // quote volume in spot, base volume in derivatives
+    vol24h: String,    // base volume in spot, contracts in derivatives
+    ts: String,
+    #[serde(flatten)]
+    extra: HashMap<String, Value>,
+}
+
+// See:
+// * https://www.okx.com/docs-v5/en/#public-data-websocket-open-interest-channel
+// * https://www.okx.com/docs-v5/en/#public-data-rest-api-get-open-interest
+#[derive(Serialize, Deserialize)]
+#[allow(non_snake_case)]
+struct RawOpenInterestMsg {
+    instId: String,
+    instType: String,
+    oi: String,    // Open interest in contracts
+    oiCcy: String, // Open interest in base coins
+    ts: String,
+    #[serde(flatten)]
+    extra: HashMap<String, Value>,
+}
+
+// Both the websocket channel and the RESTful API
+#[derive(Serialize, Deserialize)]
+struct OpenInterestResp {
+    data: Vec<RawOpenInterestMsg>,
+    #[serde(flatten)]
+    extra: HashMap<String, Value>,
+}
+
+// https://www.okx.com/docs-v5/en/#public-data-websocket-mark-price-channel
+#[derive(Serialize, Deserialize)]
+#[allow(non_snake_case)]
+struct RawMarkPriceMsg {
+    instId: String,
+    instType: String,
+    markPx: String,
+    ts: String,
+    #[serde(flatten)]
+    extra: HashMap<String, Value>,
+}
+
+// https://www.okx.com/docs-v5/en/#public-data-websocket-index-tickers-channel
+#[derive(Serialize, Deserialize)]
+#[allow(non_snake_case)]
+struct RawIndexTickerMsg {
+    instId: String, // Index, e.g., BTC-USDT
+    idxPx: String,
+    ts: String,
+    #[serde(flatten)]
+    extra: HashMap<String, Value>,
+}
+
+// https://www.okx.com/docs-v5/en/#public-data-websocket-option-summary-channel
+#[derive(Serialize, Deserialize)]
+#[allow(non_snake_case)]
+struct RawOptionSummaryMsg {
+    instId: String,
+    uly: String,     // Underlying, e.g., BTC-USD
+    markVol: String, // Implied volatility of the mark price
+    bidVol: String,  // Implied volatility of the best bid, empty if there is no bid
+    askVol: String,  // Implied volatility of the best ask, empty if there is no ask
+    deltaBS: String, // Black-Scholes greeks in dollars
+    gammaBS: String,
+    vegaBS: String,
+    thetaBS: String,
+    ts: String,
+    #[serde(flatten)]
+    extra: HashMap<String, Value>,
+}
+
+// The opt-summary channel has no instId in arg
+#[derive(Serialize, Deserialize)]
+struct OptionSummaryWebsocketMsg {
+    arg: Value,
+    data: Vec<RawOptionSummaryMsg>,
+}
+
+// https://www.okx.com/docs-v5/en/#public-data-websocket-liquidation-orders-channel
+#[derive(Serialize, Deserialize)]
+#[allow(non_snake_case)]
+struct RawLiquidationDetail {
+    side: String,    // Order side, buy, sell
+    posSide: String, // Position side, long, short
+    bkPx: String,    // Bankruptcy price
+    sz: String,      // Quantity, contracts for derivatives, base coins for margin
+    ts: String,
+    #[serde(flatten)]
+    extra: HashMap<String, Value>,
+}
+
+#[derive(Serialize, Deserialize)]
+#[allow(non_snake_case)]
+struct RawLiquidationMsg {
+    instId: String,
+    instType: String,
+    details: Vec<RawLiquidationDetail>,
+    #[serde(flatten)]
+    extra: HashMap<String, Value>,
+}
+
+// The liquidation-orders channel has no instId in arg
+#[derive(Serialize, Deserialize)]
+struct LiquidationWebsocketMsg {
+    arg: Value,
+    data: Vec<RawLiquidationMsg>,
 }
 
 #[derive(Serialize, Deserialize)]
@@ -90,12 +228,12 @@ struct RestfulMsg<T: Sized> {
     extra: HashMap<String, Value>,
 }
 
-pub(super) fn extract_symbol(msg: &str) -> Result<String, SimpleError> {
+pub(super) fn extract_symbol(msg: &str) -> Result<String, ParseError> {
     if let Ok(ws_msg) = serde_json::from_str::<WebsocketMsg<Value>>(msg) {
         Ok(ws_msg.arg.instId)
     } else if let Ok(rest_msg) = serde_json::from_str::<RestfulMsg<HashMap<String, Value>>>(msg) {
         if rest_msg.code != "0" {
-            return Err(SimpleError::new(format!("Error HTTP response {msg}")));
+            return Err(ParseError::unsupported("Error HTTP response"));
         }
         #[allow(clippy::comparison_chain)]
         if rest_msg.data.len() > 1 {
@@ -103,7 +241,7 @@ pub(super) fn extract_symbol(msg: &str) -> Result<String, SimpleError> {
         } else if rest_msg.data.len() == 1 {
             let first_elem = &rest_msg.data[0];
             if let Some(inst_id) = first_elem.get("instId") {
-                Ok(inst_id.as_str().unwrap().to_string())
+                Ok(json_str(inst_id, "instId")?.to_string())
             } else {
                 Ok("NONE".to_string())
             }
@@ -111,11 +249,11 @@ pub(super) fn extract_symbol(msg: &str) -> Result<String, SimpleError> {
             Ok("NONE".to_string())
         }
     } else {
-        Err(SimpleError::new(format!("Unsupported message format {msg}")))
+        Err(ParseError::unsupported("Unsupported message format"))
     }
 }
 
-pub(super) fn extract_timestamp(msg: &str) -> Result<Option<i64>, SimpleError> {
+pub(super) fn extract_timestamp(msg: &str) -> Result<Option<i64>, ParseError> {
     if let Ok(ws_msg) = serde_json::from_str::<WebsocketMsg<Value>>(msg) {
         if ws_msg.arg.channel == "funding-rate" {
             return Ok(None);
@@ -124,27 +262,38 @@ pub(super) fn extract_timestamp(msg: &str) -> Result<Option<i64>, SimpleError> {
         if channel.starts_with("candle") {
             Ok(None)
         } else {
-            let timestamp =
-                ws_msg.data.iter().map(|x| x["ts"].as_str().unwrap().parse::<i64>().unwrap()).max();
+            let timestamp = ws_msg
+                .data
+                .iter()
+                .map(|x| -> Result<i64, ParseError> {
+                    Ok(json_str(&x["ts"], "ts")?.parse::<i64>()?)
+                })
+                .collect::<Result<Vec<i64>, ParseError>>()?
+                .into_iter()
+                .max();
 
             if timestamp.is_none() {
-                Err(SimpleError::new(format!("data is empty in {msg}")))
+                Err(ParseError::unsupported("data is empty"))
             } else {
                 Ok(timestamp)
             }
         }
     } else if let Ok(rest_msg) = serde_json::from_str::<RestfulMsg<HashMap<String, Value>>>(msg) {
         if rest_msg.code != "0" {
-            return Err(SimpleError::new(format!("Error HTTP response {msg}")));
+            return Err(ParseError::unsupported("Error HTTP response"));
         }
         let timestamp = rest_msg
             .data
             .iter()
-            .map(|obj| obj["ts"].as_str().unwrap().parse::<i64>().unwrap())
+            .map(|obj| -> Result<i64, ParseError> {
+                Ok(json_str(obj.get("ts").unwrap_or(&Value::Null), "ts")?.parse::<i64>()?)
+            })
+            .collect::<Result<Vec<i64>, ParseError>>()?
+            .into_iter()
             .max();
         Ok(timestamp)
     } else {
-        Err(SimpleError::new(format!("Unsupported message format {msg}")))
+        Err(ParseError::unsupported("Unsupported message format"))
     }
 }
 
@@ -158,6 +307,7 @@ pub(super) fn get_msg_type(msg: &str) -> MessageType {
             "bbo-tbt" => MessageType::BBO,
             "tickers" => MessageType::Ticker,
             "funding-rate" => MessageType::FundingRate,
+            "open-interest" => MessageType::OpenInterest,
             _ => {
                 if channel.starts_with("candle") {
                     MessageType::Candlestick
@@ -171,24 +321,27 @@ pub(super) fn get_msg_type(msg: &str) -> MessageType {
     }
 }
 
-pub(super) fn parse_trade(
-    market_type: MarketType,
-    msg: &str,
-) -> Result<Vec<TradeMsg>, SimpleError> {
-    let ws_msg = serde_json::from_str::<WebsocketMsg<RawTradeMsg>>(msg).map_err(|_e| {
-        SimpleError::new(format!("Failed to deserialize {msg} to WebsocketMsg<RawTradeMsg>"))
-    })?;
+pub(super) fn parse_trade(market_type: MarketType, msg: &str) -> Result<Vec<TradeMsg>, ParseError> {
+    let ws_msg = serde_json::from_str::<WebsocketMsg<RawTradeMsg>>(msg)
+        .map_err(|_e| ParseError::malformed("expected WebsocketMsg<RawTradeMsg>"))?;
 
-    let mut trades: Vec<Result<TradeMsg, SimpleError>> = ws_msg
+    let mut trades: Vec<Result<TradeMsg, ParseError>> = ws_msg
         .data
         .into_iter()
-        .map(|raw_trade| {
-            let timestamp = raw_trade.ts.parse::<i64>().unwrap();
-            let price = raw_trade.px.parse::<f64>().unwrap();
-            let size = raw_trade.sz.parse::<f64>().unwrap();
-            let pair = crypto_pair::normalize_pair(&raw_trade.instId, EXCHANGE_NAME).unwrap();
-            let (quantity_base, quantity_quote, _) =
-                calc_quantity_and_volume(EXCHANGE_NAME, market_type, &pair, price, size);
+        .map(|raw_trade| -> Result<TradeMsg, ParseError> {
+            let timestamp = raw_trade.ts.parse::<i64>()?;
+            let price = raw_trade.px.parse::<f64>()?;
+            let size = raw_trade.sz.parse::<f64>()?;
+            let pair = crypto_pair::normalize_pair(&raw_trade.instId, EXCHANGE_NAME)
+                .ok_or_else(|| ParseError::symbol_normalization(&raw_trade.instId))?;
+            let (quantity_base, quantity_quote, _) = calc_quantity_and_volume(
+                EXCHANGE_NAME,
+                market_type,
+                &pair,
+                price,
+                size,
+                timestamp,
+            )?;
 
             Ok(TradeMsg {
                 exchange: EXCHANGE_NAME.to_string(),
@@ -201,6 +354,8 @@ pub(super) fn parse_trade(
                 quantity_base,
                 quantity_quote,
                 quantity_contract: if market_type == MarketType::Spot { None } else { Some(size) },
+                price_exact: parse_exact(&raw_trade.px),
+                quantity_exact: parse_exact(&raw_trade.sz),
                 side: if raw_trade.side == "sell" { TradeSide::Sell } else { TradeSide::Buy },
                 trade_id: raw_trade.tradeId.clone(),
                 json: serde_json::to_string(&raw_trade).unwrap(),
@@ -216,34 +371,52 @@ pub(super) fn parse_trade(
     trades.into_iter().collect()
 }
 
+// Perpetual swaps settle funding every 8 hours unless nextFundingTime says otherwise
+const FUNDING_INTERVAL: i64 = 8 * 3600 * 1000;
+
 pub(super) fn parse_funding_rate(
     market_type: MarketType,
     msg: &str,
     received_at: i64,
-) -> Result<Vec<FundingRateMsg>, SimpleError> {
-    let ws_msg = serde_json::from_str::<WebsocketMsg<RawFundingRateMsg>>(msg).map_err(|_e| {
-        SimpleError::new(format!("Failed to deserialize {msg} to WebsocketMsg<RawFundingRateMsg>"))
-    })?;
+) -> Result<Vec<FundingRateMsg>, ParseError> {
+    let ws_msg = serde_json::from_str::<WebsocketMsg<RawFundingRateMsg>>(msg)
+        .map_err(|_e| ParseError::malformed("expected WebsocketMsg<RawFundingRateMsg>"))?;
 
     let mut rates: Vec<FundingRateMsg> = ws_msg
         .data
         .into_iter()
-        .map(|raw_msg| {
-            let pair = crypto_pair::normalize_pair(&raw_msg.instId, EXCHANGE_NAME).unwrap();
-            FundingRateMsg {
+        .map(|raw_msg| -> Result<FundingRateMsg, ParseError> {
+            let pair = crypto_pair::normalize_pair(&raw_msg.instId, EXCHANGE_NAME)
+                .ok_or_else(|| ParseError::symbol_normalization(&raw_msg.instId))?;
+            let funding_rate = raw_msg.fundingRate.parse::<f64>()?;
+            let funding_time = raw_msg.fundingTime.parse::<i64>()?;
+            let parse_rate =
+                |s: Option<&str>| s.filter(|x| !x.is_empty()).and_then(|x| x.parse::<f64>().ok());
+            let funding_interval = raw_msg
+                .nextFundingTime
+                .as_deref()
+                .and_then(|x| x.parse::<i64>().ok())
+                .map(|next_funding_time| next_funding_time - funding_time)
+                .filter(|x| *x > 0)
+                .unwrap_or(FUNDING_INTERVAL);
+            Ok(FundingRateMsg {
                 exchange: EXCHANGE_NAME.to_string(),
                 market_type,
                 symbol: raw_msg.instId.clone(),
                 pair,
                 msg_type: MessageType::FundingRate,
                 timestamp: received_at,
-                funding_rate: raw_msg.fundingRate.parse::<f64>().unwrap(),
-                funding_time: raw_msg.fundingTime.parse::<i64>().unwrap(),
-                estimated_rate: Some(raw_msg.nextFundingRate.parse::<f64>().unwrap()),
+                funding_rate,
+                funding_time,
+                estimated_rate: parse_rate(Some(&raw_msg.nextFundingRate)),
+                funding_interval,
+                funding_rate_cap: parse_rate(raw_msg.maxFundingRate.as_deref()),
+                funding_rate_floor: parse_rate(raw_msg.minFundingRate.as_deref()),
+                annualized_rate: annualize_funding_rate(funding_rate, funding_interval),
                 json: serde_json::to_string(&raw_msg).unwrap(),
-            }
+            })
         })
-        .collect();
+        .collect::<Result<Vec<FundingRateMsg>, ParseError>>()?;
 
     if rates.len() == 1 {
         rates[0].json = msg.to_string();
@@ -251,40 +424,276 @@ pub(super) fn parse_funding_rate(
     Ok(rates)
 }
 
-fn parse_order(market_type: MarketType, pair: &str, raw_order: &[String; 4]) -> Order {
-    let price = raw_order[0].parse::<f64>().unwrap();
-    let quantity = raw_order[1].parse::<f64>().unwrap();
-    let (quantity_base, quantity_quote, quantity_contract) =
-        calc_quantity_and_volume(EXCHANGE_NAME, market_type, pair, price, quantity);
+/// The RESTful API returns all instruments of an `instType`, so the market
+/// type is derived from each `instId`.
+pub(super) fn parse_open_interest(
+    market_type: MarketType,
+    msg: &str,
+) -> Result<Vec<OpenInterestMsg>, ParseError> {
+    let resp = serde_json::from_str::<OpenInterestResp>(msg)
+        .map_err(|_e| ParseError::malformed("expected OpenInterestResp"))?;
+
+    let mut open_interests = resp
+        .data
+        .into_iter()
+        .map(|raw_msg| -> Result<OpenInterestMsg, ParseError> {
+            let pair = crypto_pair::normalize_pair(&raw_msg.instId, EXCHANGE_NAME)
+                .ok_or_else(|| ParseError::symbol_normalization(&raw_msg.instId))?;
+            let market_type =
+                match crypto_pair::get_market_type(&raw_msg.instId, EXCHANGE_NAME, None) {
+                    MarketType::Unknown => market_type,
+                    x => x,
+                };
+            let timestamp = raw_msg.ts.parse::<i64>()?;
+            let quantity_contract = raw_msg.oi.parse::<f64>()?;
+            let (_, quantity_quote) = calc_open_interest(
+                EXCHANGE_NAME,
+                market_type,
+                &pair,
+                None,
+                quantity_contract,
+                timestamp,
+            )?;
+            Ok(OpenInterestMsg {
+                exchange: EXCHANGE_NAME.to_string(),
+                market_type,
+                symbol: raw_msg.instId.clone(),
+                pair,
+                msg_type: MessageType::OpenInterest,
+                timestamp,
+                quantity_contract,
+                quantity_base: Some(raw_msg.oiCcy.parse::<f64>()?),
+                quantity_quote,
+                json: serde_json::to_string(&raw_msg).unwrap(),
+            })
+        })
+        .collect::<Result<Vec<OpenInterestMsg>, ParseError>>()?;
+
+    if open_interests.len() == 1 {
+        open_interests[0].json = msg.to_string();
+    }
+    Ok(open_interests)
+}
+
+pub(super) fn parse_mark_price(
+    market_type: MarketType,
+    msg: &str,
+) -> Result<Vec<MarkPriceMsg>, ParseError> {
+    let ws_msg = serde_json::from_str::<WebsocketMsg<RawMarkPriceMsg>>(msg)
+        .map_err(|_e| ParseError::malformed("expected WebsocketMsg<RawMarkPriceMsg>"))?;
+    if ws_msg.arg.channel != "mark-price" {
+        return Err(ParseError::unsupported(format!("Unsupported channel {}", ws_msg.arg.channel)));
+    }
+
+    let mut mark_prices = ws_msg
+        .data
+        .into_iter()
+        .map(|raw_msg| -> Result<MarkPriceMsg, ParseError> {
+            let pair = crypto_pair::normalize_pair(&raw_msg.instId, EXCHANGE_NAME)
+                .ok_or_else(|| ParseError::symbol_normalization(&raw_msg.instId))?;
+            Ok(MarkPriceMsg {
+                exchange: EXCHANGE_NAME.to_string(),
+                market_type,
+                symbol: raw_msg.instId.clone(),
+                pair,
+                timestamp: raw_msg.ts.parse::<i64>()?,
+                price: raw_msg.markPx.parse::<f64>()?,
+                json: serde_json::to_string(&raw_msg).unwrap(),
+            })
+        })
+        .collect::<Result<Vec<MarkPriceMsg>, ParseError>>()?;
+
+    if mark_prices.len() == 1 {
+        mark_prices[0].json = msg.to_string();
+    }
+    Ok(mark_prices)
+}
+
+pub(super) fn parse_index_price(
+    market_type: MarketType,
+    msg: &str,
+) -> Result<Vec<IndexPriceMsg>, ParseError> {
+    let ws_msg = serde_json::from_str::<WebsocketMsg<RawIndexTickerMsg>>(msg)
+        .map_err(|_e| ParseError::malformed("expected WebsocketMsg<RawIndexTickerMsg>"))?;
+    if ws_msg.arg.channel != "index-tickers" {
+        return Err(ParseError::unsupported(format!("Unsupported channel {}", ws_msg.arg.channel)));
+    }
+
+    let mut index_prices = ws_msg
+        .data
+        .into_iter()
+        .map(|raw_msg| -> Result<IndexPriceMsg, ParseError> {
+            let pair = crypto_pair::normalize_pair(&raw_msg.instId, EXCHANGE_NAME)
+                .ok_or_else(|| ParseError::symbol_normalization(&raw_msg.instId))?;
+            Ok(IndexPriceMsg {
+                exchange: EXCHANGE_NAME.to_string(),
+                market_type,
+                symbol: raw_msg.instId.clone(),
+                pair,
+                timestamp: raw_msg.ts.parse::<i64>()?,
+                price: raw_msg.idxPx.parse::<f64>()?,
+                json: serde_json::to_string(&raw_msg).unwrap(),
+            })
+        })
+        .collect::<Result<Vec<IndexPriceMsg>, ParseError>>()?;
+
+    if index_prices.len() == 1 {
+        index_prices[0].json = msg.to_string();
+    }
+    Ok(index_prices)
+}
+
+/// One message carries all options of an underlying.
+pub(super) fn parse_option_ticker(msg: &str) -> Result<Vec<OptionTickerMsg>, ParseError> {
+    let ws_msg = serde_json::from_str::<OptionSummaryWebsocketMsg>(msg)
+        .map_err(|_e| ParseError::malformed("expected OptionSummaryWebsocketMsg"))?;
+    if ws_msg.arg["channel"].as_str() != Some("opt-summary") {
+        return Err(ParseError::unsupported(format!(
+            "Unsupported channel {}",
+            ws_msg.arg["channel"]
+        )));
+    }
+    let parse_iv = |iv: &str| -> Result<Option<f64>, ParseError> {
+        if iv.is_empty() { Ok(None) } else { Ok(Some(iv.parse::<f64>()?)) }
+    };
+
+    let mut tickers = ws_msg
+        .data
+        .into_iter()
+        .map(|raw_msg| -> Result<OptionTickerMsg, ParseError> {
+            let pair = crypto_pair::normalize_pair(&raw_msg.instId, EXCHANGE_NAME)
+                .ok_or_else(|| ParseError::symbol_normalization(&raw_msg.instId))?;
+            Ok(OptionTickerMsg {
+                exchange: EXCHANGE_NAME.to_string(),
+                market_type: MarketType::EuropeanOption,
+                symbol: raw_msg.instId.clone(),
+                pair,
+                timestamp: raw_msg.ts.parse::<i64>()?,
+                mark_iv: raw_msg.markVol.parse::<f64>()?,
+                bid_iv: parse_iv(&raw_msg.bidVol)?,
+                ask_iv: parse_iv(&raw_msg.askVol)?,
+                delta: raw_msg.deltaBS.parse::<f64>()?,
+                gamma: raw_msg.gammaBS.parse::<f64>()?,
+                vega: raw_msg.vegaBS.parse::<f64>()?,
+                theta: raw_msg.thetaBS.parse::<f64>()?,
+                underlying_price: None,
+                open_interest: None,
+                json: serde_json::to_string(&raw_msg).unwrap(),
+            })
+        })
+        .collect::<Result<Vec<OptionTickerMsg>, ParseError>>()?;
+
+    if tickers.len() == 1 {
+        tickers[0].json = msg.to_string();
+    }
+    Ok(tickers)
+}
+
+/// One channel carries all instruments of an `instType`, e.g., both inverse
+/// and linear swaps, so the market type is derived from each `instId`.
+pub(super) fn parse_liquidation(
+    market_type: MarketType,
+    msg: &str,
+) -> Result<Vec<LiquidationMsg>, ParseError> {
+    let ws_msg = serde_json::from_str::<LiquidationWebsocketMsg>(msg)
+        .map_err(|_e| ParseError::malformed("expected LiquidationWebsocketMsg"))?;
+    if ws_msg.arg["channel"].as_str() != Some("liquidation-orders") {
+        return Err(ParseError::unsupported(format!(
+            "Unsupported channel {}",
+            ws_msg.arg["channel"]
+        )));
+    }
+
+    let mut liquidations = Vec::new();
+    for raw_msg in ws_msg.data.iter() {
+        let pair = crypto_pair::normalize_pair(&raw_msg.instId, EXCHANGE_NAME)
+            .ok_or_else(|| ParseError::symbol_normalization(&raw_msg.instId))?;
+        let market_type = match crypto_pair::get_market_type(&raw_msg.instId, EXCHANGE_NAME, None) {
+            MarketType::Unknown => market_type,
+            x => x,
+        };
+        for detail in raw_msg.details.iter() {
+            let timestamp = detail.ts.parse::<i64>()?;
+            let price = detail.bkPx.parse::<f64>()?;
+            let size = detail.sz.parse::<f64>()?;
+            let (quantity_base, quantity_quote, quantity_contract) = calc_quantity_and_volume(
+                EXCHANGE_NAME,
+                market_type,
+                &pair,
+                price,
+                size,
+                timestamp,
+            )?;
+            liquidations.push(LiquidationMsg {
+                exchange: EXCHANGE_NAME.to_string(),
+                market_type,
+                symbol: raw_msg.instId.clone(),
+                pair: pair.clone(),
+                timestamp,
+                side: if detail.side == "sell" { TradeSide::Sell } else { TradeSide::Buy },
+                price,
+                quantity_base,
+                quantity_quote,
+                quantity_contract,
+                json: serde_json::to_string(&detail).unwrap(),
+            });
+        }
+    }
 
-    Order { price, quantity_base, quantity_quote, quantity_contract }
+    if liquidations.len() == 1 {
+        liquidations[0].json = msg.to_string();
+    }
+    Ok(liquidations)
+}
+
+fn parse_order(
+    market_type: MarketType,
+    pair: &str,
+    raw_order: &[String; 4],
+    timestamp: i64,
+) -> Result<Order, ParseError> {
+    let price = raw_order[0].parse::<f64>()?;
+    let quantity = raw_order[1].parse::<f64>()?;
+    let (quantity_base, quantity_quote, quantity_contract) =
+        calc_quantity_and_volume(EXCHANGE_NAME, market_type, pair, price, quantity, timestamp)?;
+
+    Ok(Order {
+        price,
+        quantity_base,
+        quantity_quote,
+        quantity_contract,
+        price_exact: parse_exact(&raw_order[0]),
+        quantity_exact: parse_exact(&raw_order[1]),
+    })
 }
 
 pub(super) fn parse_l2(
     market_type: MarketType,
     msg: &str,
-) -> Result<Vec<OrderBookMsg>, SimpleError> {
-    let ws_msg = serde_json::from_str::<WebsocketMsg<RawOrderbookMsg>>(msg).map_err(|_e| {
-        SimpleError::new(format!("Failed to deserialize {msg} to WebsocketMsg<RawOrderbookMsg>"))
-    })?;
+) -> Result<Vec<OrderBookMsg>, ParseError> {
+    let ws_msg = serde_json::from_str::<WebsocketMsg<RawOrderbookMsg>>(msg)
+        .map_err(|_e| ParseError::malformed("expected WebsocketMsg<RawOrderbookMsg>"))?;
 
     let channel = ws_msg.arg.channel.as_str();
     let msg_type = if channel == "books5" { MessageType::L2TopK } else { MessageType::L2Event };
     let snapshot = {
         if let Some(action) = ws_msg.action { action == "snapshot" } else { channel == "books5" }
     };
-    debug_assert_eq!(ws_msg.data.len(), 1);
+    if ws_msg.data.len() != 1 {
+        return Err(ParseError::malformed("data should have exactly one orderbook"));
+    }
 
     let symbol = ws_msg.arg.instId.as_str();
-    let pair = crypto_pair::normalize_pair(symbol, EXCHANGE_NAME).unwrap();
+    let pair = crypto_pair::normalize_pair(symbol, EXCHANGE_NAME)
+        .ok_or_else(|| ParseError::symbol_normalization(symbol))?;
 
     let mut orderbooks = ws_msg
         .data
         .iter()
-        .map(|raw_orderbook| {
-            let timestamp = raw_orderbook.ts.parse::<i64>().unwrap();
+        .map(|raw_orderbook| -> Result<OrderBookMsg, ParseError> {
+            let timestamp = raw_orderbook.ts.parse::<i64>()?;
 
-            OrderBookMsg {
+            Ok(OrderBookMsg {
                 exchange: EXCHANGE_NAME.to_string(),
                 market_type,
                 symbol: symbol.to_string(),
@@ -296,18 +705,18 @@ pub(super) fn parse_l2(
                 asks: raw_orderbook
                     .asks
                     .iter()
-                    .map(|x| parse_order(market_type, &pair, x))
-                    .collect::<Vec<Order>>(),
+                    .map(|x| parse_order(market_type, &pair, x, timestamp))
+                    .collect::<Result<Vec<Order>, ParseError>>()?,
                 bids: raw_orderbook
                     .bids
                     .iter()
-                    .map(|x| parse_order(market_type, &pair, x))
-                    .collect::<Vec<Order>>(),
+                    .map(|x| parse_order(market_type, &pair, x, timestamp))
+                    .collect::<Result<Vec<Order>, ParseError>>()?,
                 snapshot,
-                json: serde_json::to_string(raw_orderbook).unwrap(),
-            }
+                json: serde_json::to_string(raw_orderbook)?,
+            })
         })
-        .collect::<Vec<OrderBookMsg>>();
+        .collect::<Result<Vec<OrderBookMsg>, ParseError>>()?;
 
     if orderbooks.len() == 1 {
         orderbooks[0].json = msg.to_string();
@@ -315,26 +724,72 @@ pub(super) fn parse_l2(
     Ok(orderbooks)
 }
 
-pub(crate) fn parse_bbo(market_type: MarketType, msg: &str) -> Result<Vec<BboMsg>, SimpleError> {
-    let ws_msg = serde_json::from_str::<WebsocketMsg<RawOrderbookMsg>>(msg).map_err(|_e| {
-        SimpleError::new(format!("Failed to deserialize {msg} to WebsocketMsg<RawOrderbookMsg>"))
-    })?;
+pub(super) fn parse_l2_levels(msg: &str) -> Result<L2Levels, ParseError> {
+    let ws_msg = serde_json::from_str::<WebsocketMsg<RawOrderbookMsg>>(msg)
+        .map_err(|_e| ParseError::malformed("expected WebsocketMsg<RawOrderbookMsg>"))?;
+    let snapshot = {
+        if let Some(action) = ws_msg.action {
+            action == "snapshot"
+        } else {
+            ws_msg.arg.channel == "books5"
+        }
+    };
+    if ws_msg.data.len() != 1 {
+        return Err(ParseError::malformed("data should have exactly one orderbook"));
+    }
+    let raw_orderbook =
+        ws_msg.data.first().ok_or_else(|| ParseError::malformed("empty orderbook data"))?;
+    let to_level = |x: &[String; 4]| [x[0].clone(), x[1].clone()];
+
+    Ok(L2Levels {
+        snapshot,
+        asks: raw_orderbook.asks.iter().map(to_level).collect(),
+        bids: raw_orderbook.bids.iter().map(to_level).collect(),
+        // signed 32-bit integer
+        checksum: raw_orderbook.checksum.map(|x| x as i32 as u32),
+        depth: None,
+    })
+}
+
+// https://www.okx.com/docs-v5/en/#order-book-trading-market-data-ws-order-book-channel
+pub(super) fn calc_checksum(asks: &[&[String; 2]], bids: &[&[String; 2]]) -> u32 {
+    let mut fields: Vec<&str> = Vec::new();
+    for i in 0..25 {
+        if let Some(bid) = bids.get(i) {
+            fields.push(&bid[0]);
+            fields.push(&bid[1]);
+        }
+        if let Some(ask) = asks.get(i) {
+            fields.push(&ask[0]);
+            fields.push(&ask[1]);
+        }
+    }
+    crc32fast::hash(fields.join(":").as_bytes())
+}
+
+pub(crate) fn parse_bbo(market_type: MarketType, msg: &str) -> Result<Vec<BboMsg>, ParseError> {
+    let ws_msg = serde_json::from_str::<WebsocketMsg<RawOrderbookMsg>>(msg)
+        .map_err(|_e| ParseError::malformed("expected WebsocketMsg<RawOrderbookMsg>"))?;
 
     let channel = ws_msg.arg.channel.as_str();
-    debug_assert_eq!("bbo-tbt", channel);
-    debug_assert_eq!(1, ws_msg.data.len());
+    if channel != "bbo-tbt" {
+        return Err(ParseError::unsupported(format!("Unsupported channel {channel}")));
+    }
 
-    let raw_orderbook = &ws_msg.data[0];
+    let raw_orderbook =
+        ws_msg.data.first().ok_or_else(|| ParseError::unsupported("data is empty"))?;
     if raw_orderbook.asks.is_empty() || raw_orderbook.bids.is_empty() {
         // Drop incomplete BBO
         return Ok(vec![]);
     }
 
     let symbol = ws_msg.arg.instId.as_str();
-    let pair = crypto_pair::normalize_pair(symbol, EXCHANGE_NAME).unwrap();
+    let pair = crypto_pair::normalize_pair(symbol, EXCHANGE_NAME)
+        .ok_or_else(|| ParseError::symbol_normalization(symbol))?;
 
-    let best_ask = parse_order(market_type, &pair, &raw_orderbook.asks[0]);
-    let best_bid = parse_order(market_type, &pair, &raw_orderbook.bids[0]);
+    let timestamp = raw_orderbook.ts.parse::<i64>()?;
+    let best_ask = parse_order(market_type, &pair, &raw_orderbook.asks[0], timestamp)?;
+    let best_bid = parse_order(market_type, &pair, &raw_orderbook.bids[0], timestamp)?;
 
     let bbo_msg = BboMsg {
         exchange: EXCHANGE_NAME.to_string(),
@@ -342,7 +797,7 @@ pub(crate) fn parse_bbo(market_type: MarketType, msg: &str) -> Result<Vec<BboMsg
         symbol: symbol.to_string(),
         pair,
         msg_type: MessageType::BBO,
-        timestamp: raw_orderbook.ts.parse::<i64>().unwrap(),
+        timestamp,
         ask_price: best_ask.price,
         ask_quantity_base: best_ask.quantity_base,
         ask_quantity_quote: best_ask.quantity_quote,
@@ -351,6 +806,10 @@ pub(crate) fn parse_bbo(market_type: MarketType, msg: &str) -> Result<Vec<BboMsg
         bid_quantity_base: best_bid.quantity_base,
         bid_quantity_quote: best_bid.quantity_quote,
         bid_quantity_contract: best_bid.quantity_contract,
+        bid_price_exact: best_bid.price_exact,
+        bid_quantity_exact: best_bid.quantity_exact,
+        ask_price_exact: best_ask.price_exact,
+        ask_quantity_exact: best_ask.quantity_exact,
         id: None,
         json: msg.to_string(),
     };
@@ -368,38 +827,41 @@ pub(super) fn parse_candlestick(
     market_type: MarketType,
     msg: &str,
     received_at: i64,
-) -> Result<Vec<CandlestickMsg>, SimpleError> {
+) -> Result<Vec<CandlestickMsg>, ParseError> {
     let ws_msg =
-        serde_json::from_str::<WebKlineMsg<RawCandlestickMsg>>(msg).map_err(SimpleError::from)?;
+        serde_json::from_str::<WebKlineMsg<RawCandlestickMsg>>(msg).map_err(ParseError::from)?;
 
     let channel = ws_msg.arg.channel.as_str();
-    debug_assert!(channel.starts_with("candle"));
-    debug_assert_eq!(1, ws_msg.data.len());
-    let obj = &ws_msg.data[0];
+    if !channel.starts_with("candle") {
+        return Err(ParseError::unsupported(format!("Unsupported channel {channel}")));
+    }
+    let obj = ws_msg.data.first().ok_or_else(|| ParseError::unsupported("data is empty"))?;
 
     let symbol = ws_msg.arg.instId.as_str();
-    let pair = crypto_pair::normalize_pair(symbol, EXCHANGE_NAME).unwrap();
+    let pair = crypto_pair::normalize_pair(symbol, EXCHANGE_NAME)
+        .ok_or_else(|| ParseError::symbol_normalization(symbol))?;
 
-    let period = if let Some(tmp) = channel.strip_suffix("utc") {
-        tmp.strip_prefix("candle").unwrap()
-    } else {
-        channel.strip_prefix("candle").unwrap()
-    };
+    let period = parse_candle_period(EXCHANGE_NAME, market_type, channel)?;
+    let begin_time = obj.ts.parse::<i64>()?;
 
     let (volume, quote_volume) = match market_type {
-        MarketType::Spot => (obj.vol.parse().unwrap(), Some(obj.volCcy.parse().unwrap())),
+        MarketType::Spot => (obj.vol.parse()?, Some(obj.volCcy.parse()?)),
         MarketType::InverseFuture | MarketType::InverseSwap => {
-            let vol = obj.vol.parse::<f64>().unwrap();
-            let vol_ccy = obj.volCcy.parse::<f64>().unwrap();
-            let contract_value =
-                crypto_contract_value::get_contract_value(EXCHANGE_NAME, market_type, &pair)
-                    .unwrap();
+            let vol = obj.vol.parse::<f64>()?;
+            let vol_ccy = obj.volCcy.parse::<f64>()?;
+            let contract_value = crypto_contract_value::get_contract_value_at(
+                EXCHANGE_NAME,
+                market_type,
+                &pair,
+                begin_time,
+            )
+            .ok_or_else(|| ParseError::unsupported(format!("No contract value for {pair}")))?;
             (vol_ccy, Some(vol * contract_value))
         }
-        MarketType::LinearFuture | MarketType::LinearSwap => (obj.volCcy.parse().unwrap(), None),
-        _ => {
-            Err(SimpleError::new(format!("Unknown market type: {market_type} of {EXCHANGE_NAME}")))?
-        }
+        MarketType::LinearFuture | MarketType::LinearSwap => (obj.volCcy.parse()?, None),
+        _ => Err(ParseError::unsupported(format!(
+            "Unknown market type: {market_type} of {EXCHANGE_NAME}"
+        )))?,
     };
 
     let candlestick_msg = CandlestickMsg {
@@ -409,16 +871,104 @@ pub(super) fn parse_candlestick(
         symbol: symbol.to_string(),
         pair,
         timestamp: received_at,
-        period: period.to_string(),
-        begin_time: obj.ts.parse::<i64>().unwrap() / 1000,
-        open: obj.open.parse().unwrap(),
-        high: obj.high.parse().unwrap(),
-        low: obj.low.parse().unwrap(),
-        close: obj.close.parse().unwrap(),
+        period,
+        begin_time,
+        end_time: period.end_time(begin_time),
+        open: obj.open.parse()?,
+        high: obj.high.parse()?,
+        low: obj.low.parse()?,
+        close: obj.close.parse()?,
         volume,
         quote_volume,
+        is_closed: obj.confirm.as_ref().map(|confirm| confirm == "1"),
         json: msg.to_string(),
     };
 
     Ok(vec![candlestick_msg])
 }
+
+pub(super) fn parse_ticker(
+    market_type: MarketType,
+    msg: &str,
+) -> Result<Vec<TickerMsg>, ParseError> {
+    let ws_msg = serde_json::from_str::<WebsocketMsg<RawTickerMsg>>(msg)
+        .map_err(|_e| ParseError::malformed("expected WebsocketMsg<RawTickerMsg>"))?;
+    if ws_msg.arg.channel != "tickers" {
+        return Err(ParseError::unsupported(format!("Unsupported channel {}", ws_msg.arg.channel)));
+    }
+    // OKX sends empty strings when there is no value, e.g., no bids
+    let parse_f64 = |s: &str| -> Result<f64, ParseError> {
+        s.parse::<f64>().map_err(|_e| ParseError::malformed(format!("Failed to parse {s} as f64")))
+    };
+    let parse_opt_f64 = |s: &str| -> Option<f64> { s.parse::<f64>().ok() };
+
+    let mut tickers = Vec::with_capacity(ws_msg.data.len());
+    for raw_ticker in ws_msg.data.into_iter() {
+        let pair = crypto_pair::normalize_pair(&raw_ticker.instId, EXCHANGE_NAME)
+            .ok_or_else(|| ParseError::symbol_normalization(&raw_ticker.instId))?;
+        let timestamp = raw_ticker.ts.parse::<i64>().map_err(|_e| {
+            ParseError::malformed(format!("Failed to parse {} as i64", raw_ticker.ts))
+        })?;
+        let close = parse_f64(&raw_ticker.last)?;
+        let (volume, quote_volume) = if market_type == MarketType::Spot {
+            (parse_f64(&raw_ticker.vol24h)?, parse_f64(&raw_ticker.volCcy24h)?)
+        } else {
+            let (_, quote_volume, _) = calc_quantity_and_volume(
+                EXCHANGE_NAME,
+                market_type,
+                &pair,
+                close,
+                parse_f64(&raw_ticker.vol24h)?,
+                timestamp,
+            )?;
+            (parse_f64(&raw_ticker.volCcy24h)?, quote_volume)
+        };
+        let to_base =
+            |price: Option<f64>, quantity: Option<f64>| -> Result<Option<f64>, ParseError> {
+                Ok(match (price, quantity) {
+                    (Some(price), Some(quantity)) => Some(
+                        calc_quantity_and_volume(
+                            EXCHANGE_NAME,
+                            market_type,
+                            &pair,
+                            price,
+                            quantity,
+                            timestamp,
+                        )?
+                        .0,
+                    ),
+                    _ => None,
+                })
+            };
+        let best_bid_price = parse_opt_f64(&raw_ticker.bidPx);
+        let best_ask_price = parse_opt_f64(&raw_ticker.askPx);
+
+        tickers.push(TickerMsg {
+            exchange: EXCHANGE_NAME.to_string(),
+            market_type,
+            symbol: raw_ticker.instId.clone(),
+            pair: pair.clone(),
+            msg_type: MessageType::Ticker,
+            timestamp,
+            open: parse_f64(&raw_ticker.open24h)?,
+            high: parse_f64(&raw_ticker.high24h)?,
+            low: parse_f64(&raw_ticker.low24h)?,
+            close,
+            volume,
+            quote_volume,
+            last_quantity: to_base(Some(close), parse_opt_f64(&raw_ticker.lastSz))?,
+            best_bid_price,
+            best_bid_quantity: to_base(best_bid_price, parse_opt_f64(&raw_ticker.bidSz))?,
+            best_ask_price,
+            best_ask_quantity: to_base(best_ask_price, parse_opt_f64(&raw_ticker.askSz))?,
+            open_interest: None,
+            open_interest_quote: None,
+            json: serde_json::to_string(&raw_ticker).unwrap(),
+        });
+    }
+
+    if tickers.len() == 1 {
+        tickers[0].json = msg.to_string();
+    }
+    Ok(tickers)
+}
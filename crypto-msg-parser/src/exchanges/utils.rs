@@ -1,10 +1,16 @@
+#[cfg(all(feature = "online", not(feature = "offline")))]
 use std::time::Duration;
 
 use crypto_market_type::MarketType;
+use crypto_message::{CandlePeriod, Decimal};
+#[cfg(all(feature = "online", not(feature = "offline")))]
 use reqwest::{header, Result};
 use serde::{Deserialize, Deserializer};
 use serde_json::Value;
 
+use crate::ParseError;
+
+#[cfg(all(feature = "online", not(feature = "offline")))]
 pub(super) fn http_get(url: &str) -> Result<String> {
     let mut headers = header::HeaderMap::new();
     headers.insert(header::CONTENT_TYPE, header::HeaderValue::from_static("application/json"));
@@ -23,6 +29,12 @@ pub(super) fn http_get(url: &str) -> Result<String> {
     }
 }
 
+/// Network access is disabled, callers fall back to the embedded tables.
+#[cfg(not(all(feature = "online", not(feature = "offline"))))]
+pub(super) fn http_get(_url: &str) -> std::result::Result<String, &'static str> {
+    Err("Network access is disabled")
+}
+
 const PRECISION: f64 = 1000000000.0; // 9 decimals
 
 pub fn round(f: f64) -> f64 {
@@ -36,10 +48,12 @@ pub(super) fn calc_quantity_and_volume(
     pair: &str,
     price: f64,
     quantity: f64,
-) -> (f64, f64, Option<f64>) {
+    timestamp: i64,
+) -> std::result::Result<(f64, f64, Option<f64>), ParseError> {
     let contract_value =
-        crypto_contract_value::get_contract_value(exchange, market_type, pair).unwrap();
-    match market_type {
+        crypto_contract_value::get_contract_value_at(exchange, market_type, pair, timestamp)
+            .ok_or_else(|| ParseError::unsupported(format!("No contract value for {pair}")))?;
+    let ret = match market_type {
         MarketType::Spot => (quantity, round(quantity * price), None),
         MarketType::InverseSwap | MarketType::InverseFuture => {
             let quantity_quote = quantity * contract_value;
@@ -53,8 +67,42 @@ pub(super) fn calc_quantity_and_volume(
             let quantity_base = quantity * contract_value;
             (quantity_base, quantity_base * price, Some(quantity))
         }
-        _ => panic!("Unknown market_type {market_type}"),
-    }
+        _ => return Err(ParseError::unsupported(format!("Unknown market_type {market_type}"))),
+    };
+    Ok(ret)
+}
+
+// Convert open interest in contracts to (quantity_base, quantity_quote).
+//
+// Inverse contracts have a fixed quote value and linear contracts a fixed base
+// value, the other one needs a price.
+pub(super) fn calc_open_interest(
+    exchange: &str,
+    market_type: MarketType,
+    pair: &str,
+    price: Option<f64>,
+    quantity_contract: f64,
+    timestamp: i64,
+) -> std::result::Result<(Option<f64>, Option<f64>), ParseError> {
+    let contract_value =
+        crypto_contract_value::get_contract_value_at(exchange, market_type, pair, timestamp)
+            .ok_or_else(|| ParseError::unsupported(format!("No contract value for {pair}")))?;
+    let ret = match market_type {
+        MarketType::InverseSwap | MarketType::InverseFuture => {
+            let quantity_quote = quantity_contract * contract_value;
+            (price.map(|price| quantity_quote / price), Some(quantity_quote))
+        }
+        MarketType::LinearSwap
+        | MarketType::LinearFuture
+        | MarketType::Move
+        | MarketType::BVOL
+        | MarketType::EuropeanOption => {
+            let quantity_base = round(quantity_contract * contract_value);
+            (Some(quantity_base), price.map(|price| round(quantity_base * price)))
+        }
+        _ => return Err(ParseError::unsupported(format!("Unknown market_type {market_type}"))),
+    };
+    Ok(ret)
 }
 
 const MAX_UNIX_TIMESTAMP: i64 = 10_i64.pow(10) - 1;
@@ -71,20 +119,107 @@ const fn convert_unix_timestamp_if_needed(ts: i64) -> i64 {
     }
 }
 
+// The next funding time of exchanges that settle funding every `interval`
+// milliseconds, counted from 00:00 UTC.
+pub(super) fn next_funding_time(timestamp: i64, interval: i64) -> i64 {
+    (timestamp / interval + 1) * interval
+}
+
+const MILLIS_PER_YEAR: i64 = 365 * 24 * 3600 * 1000;
+
+// Scale a funding rate of one `interval` to 365 days, callers fall back to the
+// default interval of the exchange if the message has no positive one.
+pub(super) fn annualize_funding_rate(funding_rate: f64, interval: i64) -> f64 {
+    debug_assert!(interval > 0, "funding interval {interval} is not positive");
+    round(funding_rate * (MILLIS_PER_YEAR as f64 / interval as f64))
+}
+
+// The typed period of a candlestick, see `parse_candle_period()`.
+pub(super) fn parse_candle_period(
+    exchange: &str,
+    market_type: MarketType,
+    period: &str,
+) -> std::result::Result<CandlePeriod, ParseError> {
+    crate::parse_candle_period(exchange, market_type, period)
+        .ok_or_else(|| ParseError::unsupported(format!("Unknown candlestick period {period}")))
+}
+
+// The period of REST candlesticks that don't carry one, i.e., the smallest gap
+// between two begin times in milliseconds.
+pub(super) fn infer_candle_period(
+    begin_times: &[i64],
+) -> std::result::Result<CandlePeriod, ParseError> {
+    let gap = begin_times
+        .windows(2)
+        .map(|w| (w[1] - w[0]).abs())
+        .filter(|gap| *gap > 0)
+        .min()
+        .ok_or_else(|| ParseError::unsupported("Can't infer the period of a single candlestick"))?;
+    CandlePeriod::from_millis(gap)
+        .ok_or_else(|| ParseError::unsupported(format!("Unknown candlestick period {gap}ms")))
+}
+
 // Convert a JSON value to a timestamp in milliseconds.
 pub(super) fn convert_timestamp(v: &Value) -> Option<i64> {
-    if v.is_i64() {
-        let ts = v.as_i64().unwrap();
-        Some(convert_unix_timestamp_if_needed(ts))
-    } else if v.is_string() {
-        let s = v.as_str().unwrap();
-        let ts = s.parse::<i64>().unwrap_or_else(|_| panic!("{}", v.to_string()));
+    if let Some(ts) = v.as_i64() {
         Some(convert_unix_timestamp_if_needed(ts))
+    } else if let Some(s) = v.as_str() {
+        s.parse::<i64>().ok().map(convert_unix_timestamp_if_needed)
     } else {
         None
     }
 }
 
+// Accessors of untyped JSON values, which return `MalformedJson` rather than
+// panic if `v` doesn't have the expected type. `name` goes into the error.
+pub(super) fn json_str<'a>(v: &'a Value, name: &str) -> std::result::Result<&'a str, ParseError> {
+    v.as_str().ok_or_else(|| ParseError::malformed(format!("{name} is not a string")))
+}
+
+// Like `json_f64`, accepts both an integer and a string of digits.
+pub(super) fn json_i64(v: &Value, name: &str) -> std::result::Result<i64, ParseError> {
+    if let Some(s) = v.as_str() {
+        Ok(s.parse::<i64>()?)
+    } else {
+        v.as_i64().ok_or_else(|| ParseError::malformed(format!("{name} is not an integer")))
+    }
+}
+
+pub(super) fn json_array<'a>(
+    v: &'a Value,
+    name: &str,
+) -> std::result::Result<&'a Vec<Value>, ParseError> {
+    v.as_array().ok_or_else(|| ParseError::malformed(format!("{name} is not an array")))
+}
+
+pub(super) fn json_object<'a>(
+    v: &'a Value,
+    name: &str,
+) -> std::result::Result<&'a serde_json::Map<String, Value>, ParseError> {
+    v.as_object().ok_or_else(|| ParseError::malformed(format!("{name} is not an object")))
+}
+
+// A number that some exchanges send as a string and others as a number.
+pub(super) fn json_f64(v: &Value, name: &str) -> std::result::Result<f64, ParseError> {
+    if let Some(s) = v.as_str() {
+        Ok(s.parse::<f64>()?)
+    } else {
+        v.as_f64().ok_or_else(|| ParseError::malformed(format!("{name} is not a number")))
+    }
+}
+
+// The exact decimal of a price or quantity string, None if it doesn't fit in a
+// `Decimal`, e.g., more than 28 decimals.
+pub(super) fn parse_exact(s: &str) -> Option<Decimal> {
+    s.parse::<Decimal>().or_else(|_| Decimal::from_scientific(s)).ok()
+}
+
+// Like `parse_exact`, None if the exchange sent a JSON number, whose digits
+// are lost during deserialization.
+pub(super) fn json_exact(v: &Value) -> Option<Decimal> {
+    v.as_str().and_then(parse_exact)
+}
+
 // copied from https://github.com/serde-rs/serde/issues/1098
 pub(super) fn deserialize_null_default<'de, D, T>(
     deserializer: D,
@@ -4,21 +4,23 @@ mod zb_swap;
 use std::collections::HashMap;
 
 use crypto_market_type::MarketType;
-use crypto_message::CandlestickMsg;
+use crypto_message::{CandlestickMsg, TickerMsg};
+use crypto_msg_type::MessageType;
 use serde_json::Value;
 
-use crate::{OrderBookMsg, TradeMsg};
-
-use simple_error::SimpleError;
+use crate::{
+    OrderBookMsg, ParseError, TradeMsg,
+    exchanges::utils::{json_object, json_str},
+};
 
 const EXCHANGE_NAME: &str = "zb";
 
-pub(crate) fn extract_symbol(_market_type: MarketType, msg: &str) -> Result<String, SimpleError> {
+pub(crate) fn extract_symbol(_market_type: MarketType, msg: &str) -> Result<String, ParseError> {
     let obj = serde_json::from_str::<HashMap<String, Value>>(msg)
-        .map_err(|_e| SimpleError::new(format!("Failed to parse the JSON string {msg}")))?;
+        .map_err(|_e| ParseError::malformed("invalid JSON"))?;
     if let Some(channel) = obj.get("channel") {
         // websocket
-        let channel = channel.as_str().unwrap();
+        let channel = json_str(channel, "channel")?;
         if channel.contains('.') {
             let symbol = channel.split('.').next().unwrap();
             Ok(symbol.to_string())
@@ -26,28 +28,28 @@ pub(crate) fn extract_symbol(_market_type: MarketType, msg: &str) -> Result<Stri
             let symbol = channel.split('_').next().unwrap();
             Ok(symbol.to_string())
         } else {
-            Err(SimpleError::new(format!("Failed to extract symbol from {msg}")))
+            Err(ParseError::unsupported("Failed to extract symbol"))
         }
     } else if obj.contains_key("asks") && obj.contains_key("bids") {
         // e.g., https://api.zbex.site/data/v1/depth?market=btc_usdt&size=50
         Ok("NONE".to_string())
     } else if obj.contains_key("code") && obj.contains_key("desc") && obj.contains_key("data") {
         // ZB linear_swap RESTful
-        let data = obj["data"].as_object().unwrap();
+        let data = json_object(&obj["data"], "data")?;
         if let Some(symbol) = data.get("symbol") {
-            Ok(symbol.as_str().unwrap().to_string())
+            Ok(json_str(symbol, "symbol")?.to_string())
         } else {
             Ok("NONE".to_string())
         }
     } else {
-        Err(SimpleError::new(format!("Unknown message format {msg}")))
+        Err(ParseError::unsupported("Unknown message format"))
     }
 }
 
 pub(crate) fn extract_timestamp(
     market_type: MarketType,
     msg: &str,
-) -> Result<Option<i64>, SimpleError> {
+) -> Result<Option<i64>, ParseError> {
     if market_type == MarketType::Spot {
         zb_spot::extract_timestamp(msg)
     } else {
@@ -55,10 +57,52 @@ pub(crate) fn extract_timestamp(
     }
 }
 
-pub(crate) fn parse_trade(
-    market_type: MarketType,
-    msg: &str,
-) -> Result<Vec<TradeMsg>, SimpleError> {
+pub(crate) fn get_msg_type(msg: &str) -> MessageType {
+    if let Ok(obj) = serde_json::from_str::<HashMap<String, Value>>(msg) {
+        if let Some(channel) = obj.get("channel").and_then(|x| x.as_str()) {
+            if let Some((_, channel)) = channel.split_once('.') {
+                // swap, e.g., BTC_USDT.Trade
+                match channel {
+                    "Trade" => MessageType::Trade,
+                    "Depth" => MessageType::L2Event,
+                    "DepthWhole" => MessageType::L2TopK,
+                    "Ticker" => MessageType::Ticker,
+                    _ if channel.starts_with("KLine_") => MessageType::Candlestick,
+                    _ => MessageType::Other,
+                }
+            } else if let Some((_, channel)) = channel.split_once('_') {
+                // spot, e.g., btcusdt_trades
+                match channel {
+                    "trades" => MessageType::Trade,
+                    // the spot market only has snapshots of top levels
+                    "depth" => MessageType::L2TopK,
+                    "ticker" => MessageType::Ticker,
+                    _ if channel.starts_with("kline_") => MessageType::Candlestick,
+                    _ => MessageType::Other,
+                }
+            } else {
+                MessageType::Other
+            }
+        } else if obj.contains_key("asks") && obj.contains_key("bids") {
+            // spot RESTful
+            MessageType::L2Snapshot
+        } else if obj.contains_key("code") && obj.contains_key("desc") {
+            // swap RESTful
+            let data = obj.get("data");
+            if data.is_some_and(|x| x.get("asks").is_some() && x.get("bids").is_some()) {
+                MessageType::L2Snapshot
+            } else {
+                MessageType::Other
+            }
+        } else {
+            MessageType::Other
+        }
+    } else {
+        MessageType::Other
+    }
+}
+
+pub(crate) fn parse_trade(market_type: MarketType, msg: &str) -> Result<Vec<TradeMsg>, ParseError> {
     if market_type == MarketType::Spot {
         zb_spot::parse_trade(msg)
     } else {
@@ -69,7 +113,7 @@ pub(crate) fn parse_trade(
 pub(crate) fn parse_l2(
     market_type: MarketType,
     msg: &str,
-) -> Result<Vec<OrderBookMsg>, SimpleError> {
+) -> Result<Vec<OrderBookMsg>, ParseError> {
     if market_type == MarketType::Spot {
         zb_spot::parse_l2(msg)
     } else {
@@ -80,7 +124,7 @@ pub(crate) fn parse_l2(
 pub(crate) fn parse_l2_topk(
     market_type: MarketType,
     msg: &str,
-) -> Result<Vec<OrderBookMsg>, SimpleError> {
+) -> Result<Vec<OrderBookMsg>, ParseError> {
     if market_type == MarketType::Spot {
         zb_spot::parse_l2_topk(msg)
     } else {
@@ -88,10 +132,21 @@ pub(crate) fn parse_l2_topk(
     }
 }
 
+pub(crate) fn parse_ticker(
+    market_type: MarketType,
+    msg: &str,
+) -> Result<Vec<TickerMsg>, ParseError> {
+    if market_type == MarketType::Spot {
+        zb_spot::parse_ticker(msg)
+    } else {
+        zb_swap::parse_ticker(market_type, msg)
+    }
+}
+
 pub(crate) fn parse_candlestick(
     market_type: MarketType,
     msg: &str,
-) -> Result<Vec<CandlestickMsg>, SimpleError> {
+) -> Result<Vec<CandlestickMsg>, ParseError> {
     if market_type == MarketType::Spot {
         zb_spot::parse_candlestick(msg)
     } else {
@@ -1,56 +1,66 @@
 use crypto_market_type::MarketType;
-use crypto_message::{CandlestickMsg, Order, OrderBookMsg, TradeMsg, TradeSide};
+use crypto_message::{CandlestickMsg, Order, OrderBookMsg, TickerMsg, TradeMsg, TradeSide};
 use crypto_msg_type::MessageType;
 use serde_json::Value;
-use simple_error::SimpleError;
 use std::collections::HashMap;
 
-use crate::exchanges::utils::calc_quantity_and_volume;
+use crate::{
+    ParseError,
+    exchanges::utils::{
+        calc_quantity_and_volume, json_array, json_f64, json_i64, json_str, parse_candle_period,
+        parse_exact,
+    },
+};
 
 use super::EXCHANGE_NAME;
 use serde::{Deserialize, Serialize};
 
-pub(super) fn extract_timestamp(msg: &str) -> Result<Option<i64>, SimpleError> {
-    let obj = serde_json::from_str::<HashMap<String, Value>>(msg)
-        .map_err(|_e| SimpleError::new(format!("Failed to parse the JSON string {msg}")))?;
+pub(super) fn extract_timestamp(msg: &str) -> Result<Option<i64>, ParseError> {
+    // a Value rather than a HashMap, so that missing fields index to null
+    let obj = serde_json::from_str::<Value>(msg)
+        .ok()
+        .filter(|x| x.is_object())
+        .ok_or_else(|| ParseError::malformed("invalid JSON"))?;
     if let Some(raw_channel) = obj.get("channel") {
         // websocket
-        let raw_channel = raw_channel.as_str().unwrap();
-        let channel = raw_channel.split('_').nth(1).unwrap();
+        let raw_channel = json_str(raw_channel, "channel")?;
+        let channel = raw_channel.split('_').nth(1).unwrap_or_default();
         match channel {
-            "ticker" => Ok(Some(obj["date"].as_str().unwrap().parse::<i64>().unwrap())),
-            "depth" => Ok(obj.get("timestamp").map(|x| x.as_i64().unwrap() * 1000)),
+            "ticker" => Ok(Some(json_str(&obj["date"], "date")?.parse::<i64>()?)),
+            "depth" => {
+                obj.get("timestamp").map(|x| Ok(json_i64(x, "timestamp")? * 1000)).transpose()
+            }
             "trades" => {
-                let timestamp = obj["data"]
-                    .as_array()
-                    .unwrap()
+                let timestamp = json_array(&obj["data"], "data")?
                     .iter()
-                    .map(|x| x["date"].as_i64().unwrap())
+                    .map(|x| json_i64(&x["date"], "date"))
+                    .collect::<Result<Vec<i64>, ParseError>>()?
+                    .into_iter()
                     .max();
                 if let Some(timestamp) = timestamp {
                     Ok(Some(timestamp * 1000))
                 } else {
-                    Err(SimpleError::new(format!("data is empty in {msg}")))
+                    Err(ParseError::unsupported("data is empty"))
                 }
             }
             "kline" => {
-                let timestamp = obj["datas"]["data"]
-                    .as_array()
-                    .unwrap()
+                let timestamp = json_array(&obj["datas"]["data"], "data")?
                     .iter()
-                    .map(|x| x[0].as_i64().unwrap())
+                    .map(|x| json_i64(&x[0], "data[0]"))
+                    .collect::<Result<Vec<i64>, ParseError>>()?
+                    .into_iter()
                     .max();
                 if timestamp.is_none() {
-                    Err(SimpleError::new(format!("data is empty in {msg}")))
+                    Err(ParseError::unsupported("data is empty"))
                 } else {
                     Ok(timestamp)
                 }
             }
-            _ => Err(SimpleError::new(format!("Failed to extract timestamp from {msg}"))),
+            _ => Err(ParseError::missing_timestamp()),
         }
     } else {
         // RESTful
-        Ok(obj.get("timestamp").map(|x| x.as_i64().unwrap() * 1000))
+        obj.get("timestamp").map(|x| Ok(json_i64(x, "timestamp")? * 1000)).transpose()
     }
 }
 
@@ -90,6 +100,31 @@ struct L2TopKMsg {
     extra: HashMap<String, Value>,
 }
 
+#[derive(Serialize, Deserialize)]
+#[allow(non_snake_case)]
+struct RawTicker {
+    high: String,
+    vol: String, // volume in base currency
+    last: String,
+    low: String,
+    buy: String,
+    sell: String,
+    turnover: String, // volume in quote currency
+    open: String,
+    #[serde(flatten)]
+    extra: HashMap<String, Value>,
+}
+
+#[derive(Serialize, Deserialize)]
+#[allow(non_snake_case)]
+struct RawTickerMsg {
+    date: String,
+    ticker: RawTicker,
+    channel: String,
+    #[serde(flatten)]
+    extra: HashMap<String, Value>,
+}
+
 #[derive(Serialize, Deserialize)]
 #[allow(non_snake_case)]
 struct RawCandlestickMsg {
@@ -104,22 +139,26 @@ struct Data {
     data: Vec<[Value; 6]>,
 }
 
-pub(super) fn parse_trade(msg: &str) -> Result<Vec<TradeMsg>, SimpleError> {
-    let ws_msg = serde_json::from_str::<WebsocketMsg<RawTradeMsg>>(msg).map_err(|_e| {
-        SimpleError::new(format!("Failed to deserialize {msg} to WebsocketMsg<RawTradeMsg>"))
-    })?;
-    debug_assert_eq!("trades", ws_msg.dataType);
-    debug_assert!(ws_msg.channel.ends_with("_trades"));
+pub(super) fn parse_trade(msg: &str) -> Result<Vec<TradeMsg>, ParseError> {
+    let ws_msg = serde_json::from_str::<WebsocketMsg<RawTradeMsg>>(msg)
+        .map_err(|_e| ParseError::malformed("expected WebsocketMsg<RawTradeMsg>"))?;
+    if ws_msg.dataType != "trades" {
+        return Err(ParseError::unsupported(format!("Unsupported dataType {}", ws_msg.dataType)));
+    }
+    if !ws_msg.channel.ends_with("_trades") {
+        return Err(ParseError::unsupported(format!("Unsupported channel {}", ws_msg.channel)));
+    }
     let symbol = ws_msg.channel.split('_').next().unwrap();
-    let pair = crypto_pair::normalize_pair(symbol, EXCHANGE_NAME).unwrap();
+    let pair = crypto_pair::normalize_pair(symbol, EXCHANGE_NAME)
+        .ok_or_else(|| ParseError::symbol_normalization(symbol))?;
 
     let trades: Vec<TradeMsg> = ws_msg
         .data
         .into_iter()
-        .map(|raw_trade| {
-            let price = raw_trade.price.parse::<f64>().unwrap();
-            let quantity_base = raw_trade.amount.parse::<f64>().unwrap();
-            TradeMsg {
+        .map(|raw_trade| -> Result<TradeMsg, ParseError> {
+            let price = raw_trade.price.parse::<f64>()?;
+            let quantity_base = raw_trade.amount.parse::<f64>()?;
+            Ok(TradeMsg {
                 exchange: EXCHANGE_NAME.to_string(),
                 market_type: MarketType::Spot,
                 symbol: symbol.to_string(),
@@ -130,28 +169,35 @@ pub(super) fn parse_trade(msg: &str) -> Result<Vec<TradeMsg>, SimpleError> {
                 quantity_base,
                 quantity_quote: price * quantity_base,
                 quantity_contract: None,
+                price_exact: parse_exact(&raw_trade.price),
+                quantity_exact: parse_exact(&raw_trade.amount),
                 side: if raw_trade.type_ == "sell" { TradeSide::Sell } else { TradeSide::Buy },
                 trade_id: raw_trade.tid.to_string(),
                 json: serde_json::to_string(&raw_trade).unwrap(),
-            }
+            })
         })
-        .collect();
+        .collect::<Result<Vec<TradeMsg>, ParseError>>()?;
 
     Ok(trades)
 }
 
-pub(super) fn parse_l2(_msg: &str) -> Result<Vec<OrderBookMsg>, SimpleError> {
-    Err(SimpleError::new("ZB spor market doesn't provide incrememtal level2 channel"))
+pub(super) fn parse_l2(_msg: &str) -> Result<Vec<OrderBookMsg>, ParseError> {
+    Err(ParseError::unsupported("ZB spor market doesn't provide incrememtal level2 channel"))
 }
 
-pub(super) fn parse_l2_topk(msg: &str) -> Result<Vec<OrderBookMsg>, SimpleError> {
+pub(super) fn parse_l2_topk(msg: &str) -> Result<Vec<OrderBookMsg>, ParseError> {
     let ws_msg = serde_json::from_str::<L2TopKMsg>(msg)
-        .map_err(|_e| SimpleError::new(format!("Failed to deserialize {msg} to L2TopKMsg")))?;
-    debug_assert_eq!("depth", ws_msg.dataType);
-    debug_assert!(ws_msg.channel.ends_with("_depth"));
+        .map_err(|_e| ParseError::malformed("expected L2TopKMsg"))?;
+    if ws_msg.dataType != "depth" {
+        return Err(ParseError::unsupported(format!("Unsupported dataType {}", ws_msg.dataType)));
+    }
+    if !ws_msg.channel.ends_with("_depth") {
+        return Err(ParseError::unsupported(format!("Unsupported channel {}", ws_msg.channel)));
+    }
     let symbol = ws_msg.channel.split('_').next().unwrap();
     let timestamp = ws_msg.timestamp * 1000;
-    let pair = crypto_pair::normalize_pair(symbol, EXCHANGE_NAME).unwrap();
+    let pair = crypto_pair::normalize_pair(symbol, EXCHANGE_NAME)
+        .ok_or_else(|| ParseError::symbol_normalization(symbol))?;
 
     let parse_order = |raw_order: &[f64; 2]| -> Order {
         let price = raw_order[0];
@@ -162,6 +208,8 @@ pub(super) fn parse_l2_topk(msg: &str) -> Result<Vec<OrderBookMsg>, SimpleError>
             quantity_base,
             quantity_quote: price * quantity_base,
             quantity_contract: None,
+            price_exact: None,
+            quantity_exact: None,
         }
     };
 
@@ -182,51 +230,91 @@ pub(super) fn parse_l2_topk(msg: &str) -> Result<Vec<OrderBookMsg>, SimpleError>
     Ok(vec![orderbook])
 }
 
+// * https://www.zb.com/en/api #Market GetTicker
+pub(super) fn parse_ticker(msg: &str) -> Result<Vec<TickerMsg>, ParseError> {
+    let ws_msg = serde_json::from_str::<RawTickerMsg>(msg)
+        .map_err(|_e| ParseError::malformed("expected RawTickerMsg"))?;
+    let symbol = ws_msg.channel.strip_suffix("_ticker").ok_or_else(|| {
+        ParseError::unsupported(format!("Unsupported ticker channel {}", ws_msg.channel))
+    })?;
+    let pair = crypto_pair::normalize_pair(symbol, EXCHANGE_NAME)
+        .ok_or_else(|| ParseError::symbol_normalization(symbol))?;
+    let parse_f64 = |s: &str| -> Result<f64, ParseError> {
+        s.parse::<f64>().map_err(|_e| ParseError::malformed(format!("Failed to parse {s} as f64")))
+    };
+    let timestamp =
+        ws_msg.date.parse::<i64>().map_err(|_e| ParseError::malformed("Failed to parse date"))?;
+    let raw_ticker = &ws_msg.ticker;
+
+    let ticker_msg = TickerMsg {
+        exchange: EXCHANGE_NAME.to_string(),
+        market_type: MarketType::Spot,
+        symbol: symbol.to_string(),
+        pair,
+        msg_type: MessageType::Ticker,
+        timestamp,
+        open: parse_f64(&raw_ticker.open)?,
+        high: parse_f64(&raw_ticker.high)?,
+        low: parse_f64(&raw_ticker.low)?,
+        close: parse_f64(&raw_ticker.last)?,
+        volume: parse_f64(&raw_ticker.vol)?,
+        quote_volume: parse_f64(&raw_ticker.turnover)?,
+        last_quantity: None,
+        best_bid_price: Some(parse_f64(&raw_ticker.buy)?),
+        best_bid_quantity: None,
+        best_ask_price: Some(parse_f64(&raw_ticker.sell)?),
+        best_ask_quantity: None,
+        open_interest: None,
+        open_interest_quote: None,
+        json: msg.to_string(),
+    };
+    Ok(vec![ticker_msg])
+}
+
 // * https://www.zb.com/en/api #Market GetKline
-pub(super) fn parse_candlestick(msg: &str) -> Result<Vec<CandlestickMsg>, SimpleError> {
-    let ws_msg = serde_json::from_str::<RawCandlestickMsg>(msg).map_err(SimpleError::from)?;
+pub(super) fn parse_candlestick(msg: &str) -> Result<Vec<CandlestickMsg>, ParseError> {
+    let ws_msg = serde_json::from_str::<RawCandlestickMsg>(msg).map_err(ParseError::from)?;
 
     let (symbol, period) = {
         let mut arr = ws_msg.channel.split('_');
-        (arr.next().unwrap(), arr.last().unwrap())
+        let symbol = arr.next().unwrap_or_default();
+        let period = arr.next_back().ok_or_else(|| {
+            ParseError::unsupported(format!("Unknown channel {}", ws_msg.channel))
+        })?;
+        (symbol, period)
     };
-    let pair = crypto_pair::normalize_pair(symbol, EXCHANGE_NAME).unwrap();
-
-    let mut m_seconds = 0;
-    if period.ends_with("min") {
-        m_seconds = period.strip_suffix("min").unwrap().parse::<i64>().unwrap() * 60 * 1000;
-    } else if period.ends_with("hour") {
-        m_seconds = period.strip_suffix("hour").unwrap().parse::<i64>().unwrap() * 60 * 60 * 1000;
-    } else if period.ends_with("day") {
-        m_seconds =
-            period.strip_suffix("day").unwrap().parse::<i64>().unwrap() * 60 * 60 * 24 * 1000;
-    } else if period.ends_with("week") {
-        m_seconds =
-            period.strip_suffix("week").unwrap().parse::<i64>().unwrap() * 60 * 60 * 24 * 7 * 1000;
-    }
+    let pair = crypto_pair::normalize_pair(symbol, EXCHANGE_NAME)
+        .ok_or_else(|| ParseError::symbol_normalization(symbol))?;
+
+    let candle_period = parse_candle_period(EXCHANGE_NAME, MarketType::Spot, period)?;
+    // periods are 1 minute to 1 week
+    let period_millis = candle_period
+        .as_millis()
+        .ok_or_else(|| ParseError::unsupported(format!("Unsupported period {period}")))?;
 
     let arr = ws_msg.datas.data;
     let mut candlestick_msgs: Vec<CandlestickMsg> = arr
         .into_iter()
-        .map(|candlestick_msg| {
-            let timestamp = candlestick_msg[0].as_i64().unwrap();
-            let begin_time = timestamp - m_seconds;
-
-            let open = candlestick_msg[1].as_f64().unwrap();
-            let high = candlestick_msg[2].as_f64().unwrap();
-            let low = candlestick_msg[3].as_f64().unwrap();
-            let close = candlestick_msg[4].as_f64().unwrap();
+        .map(|candlestick_msg| -> Result<CandlestickMsg, ParseError> {
+            let timestamp = json_i64(&candlestick_msg[0], "timestamp")?;
+            let begin_time = timestamp - period_millis;
+
+            let open = json_f64(&candlestick_msg[1], "open")?;
+            let high = json_f64(&candlestick_msg[2], "high")?;
+            let low = json_f64(&candlestick_msg[3], "low")?;
+            let close = json_f64(&candlestick_msg[4], "close")?;
             let price = (open + high + low + close) / 4.0;
-            let quantity = candlestick_msg[5].as_f64().unwrap();
+            let quantity = json_f64(&candlestick_msg[5], "volume")?;
             let (volume, quote_volume, _none) = calc_quantity_and_volume(
                 EXCHANGE_NAME,
                 MarketType::Spot,
                 pair.as_str(),
                 price,
                 quantity,
-            );
+                timestamp,
+            )?;
 
-            CandlestickMsg {
+            Ok(CandlestickMsg {
                 exchange: super::EXCHANGE_NAME.to_string(),
                 market_type: MarketType::Spot,
                 symbol: symbol.to_string(),
@@ -234,17 +322,19 @@ pub(super) fn parse_candlestick(msg: &str) -> Result<Vec<CandlestickMsg>, Simple
                 msg_type: MessageType::Candlestick,
                 timestamp,
                 begin_time,
+                end_time: timestamp,
                 open,
                 high,
                 low,
                 close,
                 volume,
-                period: period.to_string(),
+                period: candle_period,
                 quote_volume: Some(crate::round(quote_volume)),
+                is_closed: None,
                 json: msg.to_string(),
-            }
+            })
         })
-        .collect();
+        .collect::<Result<Vec<CandlestickMsg>, ParseError>>()?;
 
     if candlestick_msgs.len() == 1 {
         candlestick_msgs[0].json = msg.to_string();
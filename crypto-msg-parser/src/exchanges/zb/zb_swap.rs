@@ -1,101 +1,123 @@
 use crypto_market_type::MarketType;
-use crypto_message::{CandlestickMsg, Order, OrderBookMsg, TradeMsg, TradeSide};
+use crypto_message::{CandlestickMsg, Order, OrderBookMsg, TickerMsg, TradeMsg, TradeSide};
 use crypto_msg_type::MessageType;
 
 use super::EXCHANGE_NAME;
-use crate::exchanges::utils::calc_quantity_and_volume;
+use crate::{
+    ParseError,
+    exchanges::utils::{
+        calc_quantity_and_volume, json_array, json_f64, json_i64, json_object, json_str,
+        parse_candle_period,
+    },
+};
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
-use simple_error::SimpleError;
 use std::collections::HashMap;
 
 pub(super) fn extract_timestamp(
     _market_type: MarketType,
     msg: &str,
-) -> Result<Option<i64>, SimpleError> {
-    let obj = serde_json::from_str::<HashMap<String, Value>>(msg)
-        .map_err(|_e| SimpleError::new(format!("Failed to parse the JSON string {msg}")))?;
+) -> Result<Option<i64>, ParseError> {
+    // a Value rather than a HashMap, so that missing fields index to null
+    let obj = serde_json::from_str::<Value>(msg)
+        .ok()
+        .filter(|x| x.is_object())
+        .ok_or_else(|| ParseError::malformed("invalid JSON"))?;
     if let Some(raw_channel) = obj.get("channel") {
         // websocket
-        let raw_channel = raw_channel.as_str().unwrap();
-        let channel = raw_channel.split('.').nth(1).unwrap();
+        let raw_channel = json_str(raw_channel, "channel")?;
+        let channel = raw_channel.split('.').nth(1).unwrap_or_default();
         match channel {
             "Trade" => {
-                let timestamp =
-                    obj["data"].as_array().unwrap().iter().map(|x| x[3].as_i64().unwrap()).max();
+                let timestamp = json_array(&obj["data"], "data")?
+                    .iter()
+                    .map(|x| json_i64(&x[3], "data[3]"))
+                    .collect::<Result<Vec<i64>, ParseError>>()?
+                    .into_iter()
+                    .max();
                 if let Some(timestamp) = timestamp {
                     Ok(Some(timestamp * 1000))
                 } else {
-                    Err(SimpleError::new(format!("data is empty in {msg}")))
+                    Err(ParseError::unsupported("data is empty"))
                 }
             }
             "Depth" | "DepthWhole" => {
-                Ok(obj["data"].get("time").map(|x| x.as_str().unwrap().parse::<i64>().unwrap()))
+                obj["data"].get("time").map(|x| json_i64(x, "time")).transpose()
             }
             "Ticker" => {
                 if raw_channel == "All.Ticker" {
-                    let m = obj["data"].as_object().unwrap();
-                    let timestamp = m.values().map(|x| x[6].as_i64().unwrap()).max();
+                    let m = json_object(&obj["data"], "data")?;
+                    let timestamp = m
+                        .values()
+                        .map(|x| json_i64(&x[6], "data[6]"))
+                        .collect::<Result<Vec<i64>, ParseError>>()?
+                        .into_iter()
+                        .max();
                     if let Some(timestamp) = timestamp {
                         Ok(Some(timestamp * 1000))
                     } else {
-                        Err(SimpleError::new(format!("data is empty in {msg}")))
+                        Err(ParseError::unsupported("data is empty"))
                     }
                 } else {
-                    let timestamp = obj["data"].as_array().unwrap()[6].as_i64().unwrap();
+                    let timestamp = json_i64(&obj["data"][6], "data[6]")?;
                     Ok(Some(timestamp * 1000))
                 }
             }
             _ => {
                 if channel.starts_with("KLine_") {
-                    let timestamp = obj["data"]
-                        .as_array()
-                        .unwrap()
+                    let timestamp = json_array(&obj["data"], "data")?
                         .iter()
-                        .map(|x| x.as_array().unwrap()[5].as_i64().unwrap())
+                        .map(|x| json_i64(&x[5], "data[5]"))
+                        .collect::<Result<Vec<i64>, ParseError>>()?
+                        .into_iter()
                         .max();
                     if let Some(timestamp) = timestamp {
                         Ok(Some(timestamp * 1000))
                     } else {
-                        Err(SimpleError::new(format!("data is empty in {msg}")))
+                        Err(ParseError::unsupported("data is empty"))
                     }
                 } else {
-                    Err(SimpleError::new(format!("Failed to extract timestamp from {msg}")))
+                    Err(ParseError::missing_timestamp())
                 }
             }
         }
-    } else if obj.contains_key("code") && obj.contains_key("desc") && obj.contains_key("data") {
+    } else if obj.get("code").is_some() && obj.get("desc").is_some() && obj.get("data").is_some() {
         // ZB linear_swap RESTful
-        Ok(obj["data"].get("time").map(|x| x.as_i64().unwrap()))
+        obj["data"].get("time").map(|x| json_i64(x, "time")).transpose()
     } else {
-        Err(SimpleError::new(format!("Unknown message format {msg}")))
+        Err(ParseError::unsupported("Unknown message format"))
     }
 }
 
 // doc: https://github.com/ZBFuture/docs/blob/main/API%20V2%20_en.md#86-trade
-pub(super) fn parse_trade(
-    market_type: MarketType,
-    msg: &str,
-) -> Result<Vec<TradeMsg>, SimpleError> {
-    let ws_msg = serde_json::from_str::<WebsocketMsg<Vec<[f64; 4]>>>(msg).map_err(|_e| {
-        SimpleError::new(format!("Failed to deserialize {msg} to WebsocketMsg<Vec<[f64; 4]>>"))
-    })?;
-    debug_assert!(ws_msg.channel.ends_with(".Trade"));
+pub(super) fn parse_trade(market_type: MarketType, msg: &str) -> Result<Vec<TradeMsg>, ParseError> {
+    let ws_msg = serde_json::from_str::<WebsocketMsg<Vec<[f64; 4]>>>(msg)
+        .map_err(|_e| ParseError::malformed("expected WebsocketMsg<Vec<[f64; 4]>>"))?;
+    if !ws_msg.channel.ends_with(".Trade") {
+        return Err(ParseError::unsupported(format!("Unsupported channel {}", ws_msg.channel)));
+    }
     let symbol = ws_msg.channel.split('.').next().unwrap();
-    let pair = crypto_pair::normalize_pair(symbol, EXCHANGE_NAME).unwrap();
+    let pair = crypto_pair::normalize_pair(symbol, EXCHANGE_NAME)
+        .ok_or_else(|| ParseError::symbol_normalization(symbol))?;
 
     let trades: Vec<TradeMsg> = ws_msg
         .data
         .into_iter()
-        .map(|raw_trade| {
+        .map(|raw_trade| -> Result<TradeMsg, ParseError> {
             let price = raw_trade[0];
             let quantity = raw_trade[1];
             let timestamp = (raw_trade[3] as i64) * 1000;
 
-            let (quantity_base, quantity_quote, quantity_contract) =
-                calc_quantity_and_volume(EXCHANGE_NAME, market_type, &pair, price, quantity);
+            let (quantity_base, quantity_quote, quantity_contract) = calc_quantity_and_volume(
+                EXCHANGE_NAME,
+                market_type,
+                &pair,
+                price,
+                quantity,
+                timestamp,
+            )?;
 
-            TradeMsg {
+            Ok(TradeMsg {
                 exchange: EXCHANGE_NAME.to_string(),
                 market_type,
                 symbol: symbol.to_string(),
@@ -106,12 +128,14 @@ pub(super) fn parse_trade(
                 quantity_base,
                 quantity_quote,
                 quantity_contract,
+                price_exact: None,
+                quantity_exact: None,
                 side: if raw_trade[3] < 0.0 { TradeSide::Sell } else { TradeSide::Buy },
                 trade_id: timestamp.to_string(),
                 json: serde_json::to_string(&raw_trade).unwrap(),
-            }
+            })
         })
-        .collect();
+        .collect::<Result<Vec<TradeMsg>, ParseError>>()?;
 
     Ok(trades)
 }
@@ -153,16 +177,15 @@ struct RawCandlestickMsg {
 pub(super) fn parse_l2(
     market_type: MarketType,
     msg: &str,
-) -> Result<Vec<OrderBookMsg>, SimpleError> {
-    let ws_msg = serde_json::from_str::<WebsocketMsg<Level2Msg>>(msg).map_err(|_e| {
-        SimpleError::new(format!("Failed to deserialize {msg} to WebsocketMsg<Level2Msg>"))
-    })?;
+) -> Result<Vec<OrderBookMsg>, ParseError> {
+    let ws_msg = serde_json::from_str::<WebsocketMsg<Level2Msg>>(msg)
+        .map_err(|_e| ParseError::malformed("expected WebsocketMsg<Level2Msg>"))?;
     let msg_type = if ws_msg.channel.ends_with(".DepthWhole") {
         MessageType::L2TopK
     } else if ws_msg.channel.ends_with(".Depth") {
         MessageType::L2Event
     } else {
-        panic!("Unsupported channel: {}", ws_msg.channel);
+        return Err(ParseError::unsupported(format!("Unsupported channel: {}", ws_msg.channel)));
     };
     let snapshot = if msg_type == MessageType::L2TopK {
         true
@@ -173,16 +196,30 @@ pub(super) fn parse_l2(
     };
 
     let symbol = ws_msg.channel.split('.').next().unwrap();
-    let timestamp = ws_msg.data.time.parse::<i64>().unwrap();
-    let pair = crypto_pair::normalize_pair(symbol, EXCHANGE_NAME).unwrap();
+    let timestamp = ws_msg.data.time.parse::<i64>()?;
+    let pair = crypto_pair::normalize_pair(symbol, EXCHANGE_NAME)
+        .ok_or_else(|| ParseError::symbol_normalization(symbol))?;
 
-    let parse_order = |raw_order: &[f64; 2]| -> Order {
+    let parse_order = |raw_order: &[f64; 2]| -> Result<Order, ParseError> {
         let price = raw_order[0];
         let quantity = raw_order[1];
 
-        let (quantity_base, quantity_quote, quantity_contract) =
-            calc_quantity_and_volume(EXCHANGE_NAME, market_type, &pair, price, quantity);
-        Order { price, quantity_base, quantity_quote, quantity_contract }
+        let (quantity_base, quantity_quote, quantity_contract) = calc_quantity_and_volume(
+            EXCHANGE_NAME,
+            market_type,
+            &pair,
+            price,
+            quantity,
+            timestamp,
+        )?;
+        Ok(Order {
+            price,
+            quantity_base,
+            quantity_quote,
+            quantity_contract,
+            price_exact: None,
+            quantity_exact: None,
+        })
     };
 
     let orderbook = OrderBookMsg {
@@ -194,8 +231,18 @@ pub(super) fn parse_l2(
         timestamp,
         seq_id: None,
         prev_seq_id: None,
-        asks: ws_msg.data.asks.iter().map(parse_order).collect::<Vec<Order>>(),
-        bids: ws_msg.data.bids.iter().map(parse_order).collect::<Vec<Order>>(),
+        asks: ws_msg
+            .data
+            .asks
+            .iter()
+            .map(parse_order)
+            .collect::<Result<Vec<Order>, ParseError>>()?,
+        bids: ws_msg
+            .data
+            .bids
+            .iter()
+            .map(parse_order)
+            .collect::<Result<Vec<Order>, ParseError>>()?,
         snapshot,
         json: msg.to_string(),
     };
@@ -207,38 +254,39 @@ pub(super) fn parse_l2(
 pub(super) fn parse_candlestick(
     market_type: MarketType,
     msg: &str,
-) -> Result<Vec<CandlestickMsg>, SimpleError> {
-    let ws_msg = serde_json::from_str::<RawCandlestickMsg>(msg).map_err(|_e| {
-        SimpleError::new(format!("Failed to deserialize {msg} to WebsocketMsg<RawCandlestickMsg>"))
-    })?;
+) -> Result<Vec<CandlestickMsg>, ParseError> {
+    let ws_msg = serde_json::from_str::<RawCandlestickMsg>(msg)
+        .map_err(|_e| ParseError::malformed("expected WebsocketMsg<RawCandlestickMsg>"))?;
 
     let (symbol, period) = {
         let mut arr = ws_msg.channel.split('.');
-        (arr.next().unwrap(), arr.last().unwrap().split('_').last().unwrap())
+        let symbol = arr.next().unwrap_or_default();
+        let period = arr.next_back().ok_or_else(|| {
+            ParseError::unsupported(format!("Unknown channel {}", ws_msg.channel))
+        })?;
+        (symbol, period.split('_').next_back().unwrap_or_default())
     };
-    let pair = crypto_pair::normalize_pair(symbol, EXCHANGE_NAME).unwrap();
+    let pair = crypto_pair::normalize_pair(symbol, EXCHANGE_NAME)
+        .ok_or_else(|| ParseError::symbol_normalization(symbol))?;
 
-    let mut m_seconds = 0;
-    if period.ends_with('M') {
-        m_seconds = period.strip_suffix('M').unwrap().parse::<i64>().unwrap() * 60 * 1000;
-    } else if period.ends_with('H') {
-        m_seconds = period.strip_suffix('H').unwrap().parse::<i64>().unwrap() * 60 * 60 * 1000;
-    } else if period.ends_with('D') {
-        m_seconds = period.strip_suffix('D').unwrap().parse::<i64>().unwrap() * 60 * 60 * 24 * 1000;
-    }
+    let candle_period = parse_candle_period(EXCHANGE_NAME, market_type, period)?;
+    // periods are 1 minute to 1 week
+    let period_millis = candle_period
+        .as_millis()
+        .ok_or_else(|| ParseError::unsupported(format!("Unsupported period {period}")))?;
 
     let arr = ws_msg.data;
     let mut candlestick_msgs: Vec<CandlestickMsg> = arr
         .into_iter()
-        .map(|candlestick_msg| {
-            let timestamp = candlestick_msg[5].as_i64().unwrap() * 1000;
-            let begin_time = timestamp - m_seconds;
-            let open = candlestick_msg[0].as_f64().unwrap();
-            let high = candlestick_msg[1].as_f64().unwrap();
-            let low = candlestick_msg[2].as_f64().unwrap();
-            let close = candlestick_msg[3].as_f64().unwrap();
+        .map(|candlestick_msg| -> Result<CandlestickMsg, ParseError> {
+            let timestamp = json_i64(&candlestick_msg[5], "timestamp")? * 1000;
+            let begin_time = timestamp - period_millis;
+            let open = json_f64(&candlestick_msg[0], "open")?;
+            let high = json_f64(&candlestick_msg[1], "high")?;
+            let low = json_f64(&candlestick_msg[2], "low")?;
+            let close = json_f64(&candlestick_msg[3], "close")?;
             let price = (open + high + low + close) / 4.0;
-            let quantity = candlestick_msg[4].as_f64().unwrap();
+            let quantity = json_f64(&candlestick_msg[4], "volume")?;
 
             let (volume, quote_volume, _none) = calc_quantity_and_volume(
                 EXCHANGE_NAME,
@@ -246,9 +294,10 @@ pub(super) fn parse_candlestick(
                 pair.as_str(),
                 price,
                 quantity,
-            );
+                timestamp,
+            )?;
 
-            CandlestickMsg {
+            Ok(CandlestickMsg {
                 exchange: super::EXCHANGE_NAME.to_string(),
                 market_type,
                 symbol: symbol.to_string(),
@@ -256,20 +305,101 @@ pub(super) fn parse_candlestick(
                 msg_type: MessageType::Candlestick,
                 timestamp,
                 begin_time,
+                end_time: timestamp,
                 open,
                 high,
                 low,
                 close,
                 volume,
-                period: period.to_string(),
+                period: candle_period,
                 quote_volume: Some(crate::round(quote_volume)),
+                is_closed: None,
                 json: msg.to_string(),
-            }
+            })
         })
-        .collect();
+        .collect::<Result<Vec<CandlestickMsg>, ParseError>>()?;
 
     if candlestick_msgs.len() == 1 {
         candlestick_msgs[0].json = msg.to_string();
     }
     Ok(candlestick_msgs)
 }
+
+// [open, high, low, close, volume, rise rate, timestamp in seconds, turnover]
+fn parse_one_ticker(
+    market_type: MarketType,
+    symbol: &str,
+    raw_ticker: &[f64; 8],
+) -> Result<TickerMsg, ParseError> {
+    let pair = crypto_pair::normalize_pair(symbol, EXCHANGE_NAME)
+        .ok_or_else(|| ParseError::symbol_normalization(symbol))?;
+    let close = raw_ticker[3];
+    let timestamp = (raw_ticker[6] as i64) * 1000;
+    let (volume, quote_volume, _) = calc_quantity_and_volume(
+        EXCHANGE_NAME,
+        market_type,
+        &pair,
+        close,
+        raw_ticker[4],
+        timestamp,
+    )?;
+
+    Ok(TickerMsg {
+        exchange: EXCHANGE_NAME.to_string(),
+        market_type,
+        symbol: symbol.to_string(),
+        pair,
+        msg_type: MessageType::Ticker,
+        timestamp,
+        open: raw_ticker[0],
+        high: raw_ticker[1],
+        low: raw_ticker[2],
+        close,
+        volume,
+        quote_volume,
+        last_quantity: None,
+        best_bid_price: None,
+        best_bid_quantity: None,
+        best_ask_price: None,
+        best_ask_quantity: None,
+        open_interest: None,
+        open_interest_quote: None,
+        json: serde_json::to_string(raw_ticker).unwrap(),
+    })
+}
+
+// doc: https://github.com/ZBFuture/docs/blob/main/API%20V2%20_en.md#83-ticker
+pub(super) fn parse_ticker(
+    market_type: MarketType,
+    msg: &str,
+) -> Result<Vec<TickerMsg>, ParseError> {
+    let obj = serde_json::from_str::<HashMap<String, Value>>(msg)
+        .map_err(|_e| ParseError::malformed("invalid JSON"))?;
+    let channel = obj
+        .get("channel")
+        .and_then(|x| x.as_str())
+        .ok_or_else(|| ParseError::unsupported("No channel"))?;
+    let data = obj.get("data").ok_or_else(|| ParseError::unsupported("No data"))?;
+
+    if channel == "All.Ticker" {
+        let m = serde_json::from_value::<HashMap<String, [f64; 8]>>(data.clone())
+            .map_err(|_e| ParseError::malformed("expected HashMap<String, [f64; 8]>"))?;
+        let mut tickers = m
+            .iter()
+            .map(|(symbol, raw_ticker)| parse_one_ticker(market_type, symbol, raw_ticker))
+            .collect::<Result<Vec<TickerMsg>, ParseError>>()?;
+        tickers.sort_by(|a, b| a.symbol.cmp(&b.symbol));
+        if tickers.len() == 1 {
+            tickers[0].json = msg.to_string();
+        }
+        Ok(tickers)
+    } else if let Some(symbol) = channel.strip_suffix(".Ticker") {
+        let raw_ticker = serde_json::from_value::<[f64; 8]>(data.clone())
+            .map_err(|_e| ParseError::malformed("expected [f64; 8]"))?;
+        let mut ticker = parse_one_ticker(market_type, symbol, &raw_ticker)?;
+        ticker.json = msg.to_string();
+        Ok(vec![ticker])
+    } else {
+        Err(ParseError::unsupported(format!("Unsupported ticker channel {channel}")))
+    }
+}
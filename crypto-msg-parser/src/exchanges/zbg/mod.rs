@@ -2,14 +2,15 @@ mod zbg_spot;
 mod zbg_swap;
 
 use crypto_market_type::MarketType;
+use crypto_msg_type::MessageType;
+use serde_json::Value;
+use std::collections::HashMap;
 
-use crate::{CandlestickMsg, OrderBookMsg, TradeMsg};
-
-use simple_error::SimpleError;
+use crate::{CandlestickMsg, FundingRateMsg, OrderBookMsg, ParseError, TickerMsg, TradeMsg};
 
 const EXCHANGE_NAME: &str = "zbg";
 
-pub(crate) fn extract_symbol(market_type: MarketType, msg: &str) -> Result<String, SimpleError> {
+pub(crate) fn extract_symbol(market_type: MarketType, msg: &str) -> Result<String, ParseError> {
     if market_type == MarketType::Spot {
         zbg_spot::extract_symbol(msg)
     } else {
@@ -20,7 +21,7 @@ pub(crate) fn extract_symbol(market_type: MarketType, msg: &str) -> Result<Strin
 pub(crate) fn extract_timestamp(
     market_type: MarketType,
     msg: &str,
-) -> Result<Option<i64>, SimpleError> {
+) -> Result<Option<i64>, ParseError> {
     if market_type == MarketType::Spot {
         zbg_spot::extract_timestamp(msg)
     } else {
@@ -28,10 +29,43 @@ pub(crate) fn extract_timestamp(
     }
 }
 
-pub(crate) fn parse_trade(
-    market_type: MarketType,
-    msg: &str,
-) -> Result<Vec<TradeMsg>, SimpleError> {
+pub(crate) fn get_msg_type(msg: &str) -> MessageType {
+    if let Ok(obj) = serde_json::from_str::<HashMap<String, Value>>(msg) {
+        if obj.contains_key("trade_statistic") {
+            MessageType::Ticker
+        } else if let Some(datas) = obj.get("datas") {
+            // RESTful
+            if datas.get("openInterestUSD").is_some() {
+                MessageType::OpenInterest
+            } else if datas.get("asks").is_some() && datas.get("bids").is_some() {
+                MessageType::L2Snapshot
+            } else {
+                MessageType::Other
+            }
+        } else {
+            MessageType::Other
+        }
+    } else if let Ok(arr) = serde_json::from_str::<Vec<Value>>(msg) {
+        // spot messages may contain multiple records, e.g., [["T",...],["T",...]]
+        let first = arr.first().map(|x| if x.is_array() { &x[0] } else { x });
+        match first.and_then(|x| x.as_str()).unwrap_or_default() {
+            // spot
+            "T" => MessageType::Trade,
+            "AE" | "E" => MessageType::L2Event,
+            "K" => MessageType::Candlestick,
+            // swap
+            "future_tick" => MessageType::Trade,
+            "future_snapshot_depth" => MessageType::L2Event,
+            "future_kline" => MessageType::Candlestick,
+            "future_snapshot_indicator" => MessageType::Ticker,
+            _ => MessageType::Other,
+        }
+    } else {
+        MessageType::Other
+    }
+}
+
+pub(crate) fn parse_trade(market_type: MarketType, msg: &str) -> Result<Vec<TradeMsg>, ParseError> {
     if market_type == MarketType::Spot {
         zbg_spot::parse_trade(msg)
     } else {
@@ -42,7 +76,7 @@ pub(crate) fn parse_trade(
 pub(crate) fn parse_l2(
     market_type: MarketType,
     msg: &str,
-) -> Result<Vec<OrderBookMsg>, SimpleError> {
+) -> Result<Vec<OrderBookMsg>, ParseError> {
     if market_type == MarketType::Spot {
         zbg_spot::parse_l2(msg)
     } else {
@@ -53,10 +87,34 @@ pub(crate) fn parse_l2(
 pub(crate) fn parse_candlestick(
     market_type: MarketType,
     msg: &str,
-) -> Result<Vec<CandlestickMsg>, SimpleError> {
+) -> Result<Vec<CandlestickMsg>, ParseError> {
     if market_type == MarketType::Spot {
         zbg_spot::parse_candlestick(msg)
     } else {
         zbg_swap::parse_candlestick(market_type, msg)
     }
 }
+
+/// Spot tickers have no timestamp, so `received_at` is required for them.
+pub(crate) fn parse_ticker(
+    market_type: MarketType,
+    msg: &str,
+    received_at: Option<i64>,
+) -> Result<Vec<TickerMsg>, ParseError> {
+    if market_type == MarketType::Spot {
+        zbg_spot::parse_ticker(msg, received_at.ok_or_else(ParseError::missing_timestamp)?)
+    } else {
+        zbg_swap::parse_ticker(market_type, msg)
+    }
+}
+
+pub(crate) fn parse_funding_rate(
+    market_type: MarketType,
+    msg: &str,
+) -> Result<Vec<FundingRateMsg>, ParseError> {
+    if market_type == MarketType::Spot {
+        Err(ParseError::unsupported("Spot markets have no funding rate"))
+    } else {
+        zbg_swap::parse_funding_rate(market_type, msg)
+    }
+}
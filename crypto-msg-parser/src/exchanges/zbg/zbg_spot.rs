@@ -1,15 +1,18 @@
 use std::collections::{BTreeMap, HashMap};
 
-use super::super::utils::{convert_timestamp, http_get};
+use super::super::utils::{
+    convert_timestamp, http_get, json_array, json_exact, json_f64, json_object, json_str,
+    parse_candle_period, parse_exact,
+};
 use crypto_market_type::MarketType;
 use crypto_msg_type::MessageType;
 
-use crypto_message::{CandlestickMsg, Order, OrderBookMsg, TradeMsg, TradeSide};
+use crypto_message::{CandlestickMsg, Order, OrderBookMsg, TickerMsg, TradeMsg, TradeSide};
 
+use crate::ParseError;
 use once_cell::sync::Lazy;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
-use simple_error::SimpleError;
 
 use super::EXCHANGE_NAME;
 
@@ -231,29 +234,39 @@ fn fetch_symbol_info() -> BTreeMap<i64, String> {
     }
 
     let mut mapping = BTreeMap::<i64, String>::new();
-    if let Ok(txt) = http_get("https://www.zbg.com/exchange/api/v1/common/symbols") {
-        let resp = serde_json::from_str::<Response>(&txt).unwrap();
-        let spot_markets = resp.datas;
-
-        for spot_market in spot_markets.iter() {
-            mapping.insert(spot_market.id.parse::<i64>().unwrap(), spot_market.symbol.clone());
+    if let Some(resp) = http_get("https://www.zbg.com/exchange/api/v1/common/symbols")
+        .ok()
+        .and_then(|txt| serde_json::from_str::<Response>(&txt).ok())
+    {
+        for spot_market in resp.datas.iter() {
+            if let Ok(id) = spot_market.id.parse::<i64>() {
+                mapping.insert(id, spot_market.symbol.clone());
+            }
         }
     }
 
     mapping
 }
 
+// The instrument registry takes precedence over the embedded table
+fn get_symbol(symbol_id: i64) -> Result<String, ParseError> {
+    crypto_pair::instrument_registry()
+        .symbol_by_id(EXCHANGE_NAME, MarketType::Spot, symbol_id)
+        .or_else(|| SYMBOL_MAP.get(&symbol_id).cloned())
+        .ok_or_else(|| ParseError::unsupported(format!("{symbol_id} NOT found in SYMBOL_MAP")))
+}
+
 // NOTE:zbg spot websocket sometimes returns lowercase symbols, and sometimes
 // returns uppercase, which is very annoying, thus we unify to lowercase here
-pub(super) fn extract_symbol(msg: &str) -> Result<String, SimpleError> {
+pub(super) fn extract_symbol(msg: &str) -> Result<String, ParseError> {
     if msg == "[]" {
         return Ok("NONE".to_string());
     }
     if msg.contains("datas") && msg.contains("resMsg") {
         // RESTful
-        let obj = serde_json::from_str::<HashMap<String, Value>>(msg).unwrap();
+        let obj = serde_json::from_str::<HashMap<String, Value>>(msg)?;
         return if let Some(symbol) = obj.get("symbol") {
-            Ok(symbol.as_str().unwrap().to_string())
+            Ok(json_str(symbol, "symbol")?.to_string())
         } else {
             Ok("NONE".to_string())
         };
@@ -261,40 +274,36 @@ pub(super) fn extract_symbol(msg: &str) -> Result<String, SimpleError> {
 
     // websocket
     if msg.starts_with(r#"{"trade_statistic":[["#) {
-        let obj = serde_json::from_str::<HashMap<String, Value>>(msg).map_err(SimpleError::from)?;
-        let trade_statistic = obj["trade_statistic"].as_array().unwrap();
+        let obj = serde_json::from_str::<HashMap<String, Value>>(msg).map_err(ParseError::from)?;
+        let trade_statistic = json_array(&obj["trade_statistic"], "trade_statistic")?;
         let ret = if trade_statistic.len() > 1 {
             Ok("ALL".to_string())
         } else {
-            let symbol_id = trade_statistic[0][0].as_str().unwrap().parse::<i64>().unwrap();
-            if let Some(symbol) = SYMBOL_MAP.get(&symbol_id) {
-                Ok(symbol.to_string())
-            } else {
-                Err(SimpleError::new(format!("{symbol_id} NOT found in SYMBOL_MAP")))
-            }
+            let symbol_id = json_str(&obj["trade_statistic"][0][0], "symbol_id")?.parse::<i64>()?;
+            get_symbol(symbol_id)
         };
         return ret;
     }
     let arr = if let Ok(list) = serde_json::from_str::<Vec<Vec<Value>>>(msg) {
-        list[0].clone()
+        list.into_iter().next().unwrap_or_default()
     } else if let Ok(arr) = serde_json::from_str::<Vec<Value>>(msg) {
         arr
     } else {
-        return Err(SimpleError::new(format!("Failed to extract symbol from {msg}")));
+        return Err(ParseError::unsupported("Failed to extract symbol"));
     };
-    let msg_type = arr[0].as_str().unwrap();
+    let msg_type = arr.first().and_then(|x| x.as_str()).unwrap_or_default();
     match msg_type {
-        "T" | "E" => Ok(arr[3].as_str().unwrap().to_lowercase()),
-        "K" | "AE" => Ok(arr[2].as_str().unwrap().to_lowercase()),
-        _ => Err(SimpleError::new(format!("Unsupported msg_type {msg_type} in {msg}"))),
+        "T" | "E" => Ok(json_str(arr.get(3).unwrap_or(&Value::Null), "symbol")?.to_lowercase()),
+        "K" | "AE" => Ok(json_str(arr.get(2).unwrap_or(&Value::Null), "symbol")?.to_lowercase()),
+        _ => Err(ParseError::unsupported(format!("Unsupported msg_type {msg_type}"))),
     }
 }
 
-pub(super) fn extract_timestamp(msg: &str) -> Result<Option<i64>, SimpleError> {
+pub(super) fn extract_timestamp(msg: &str) -> Result<Option<i64>, ParseError> {
     if msg.contains("datas") && msg.contains("resMsg") {
         // RESTful
-        let obj = serde_json::from_str::<HashMap<String, Value>>(msg).unwrap();
-        return if let Some(timestamp) = obj["datas"].get("timestamp") {
+        let obj = serde_json::from_str::<HashMap<String, Value>>(msg)?;
+        return if let Some(timestamp) = obj.get("datas").and_then(|x| x.get("timestamp")) {
             Ok(convert_timestamp(timestamp))
         } else {
             Ok(None)
@@ -310,26 +319,20 @@ pub(super) fn extract_timestamp(msg: &str) -> Result<Option<i64>, SimpleError> {
     } else if let Ok(list) = serde_json::from_str::<Vec<Value>>(msg) {
         vec![list]
     } else {
-        return Err(SimpleError::new(format!("Failed to extract symbol from {msg}")));
+        return Err(ParseError::unsupported("Failed to extract symbol"));
     };
 
     let timestamp = arr_2d
         .iter()
-        .filter(|arr| {
-            let msg_type = arr[0].as_str().unwrap();
-            match msg_type {
-                "T" | "E" => arr[2].is_string() && arr[2].as_str().unwrap().parse::<i64>().is_ok(),
-                "K" | "AE" => arr[3].is_string() && arr[3].as_str().unwrap().parse::<i64>().is_ok(),
-                _ => false,
-            }
-        })
-        .map(|arr| {
-            let msg_type = arr[0].as_str().unwrap();
-            match msg_type {
-                "T" | "E" => convert_timestamp(&arr[2]).unwrap(),
-                "K" | "AE" => convert_timestamp(&arr[3]).unwrap(),
-                _ => panic!("Not possible {msg}"),
-            }
+        .filter_map(|arr| {
+            let msg_type = arr.first().and_then(|x| x.as_str())?;
+            // timestamps are strings, skip null ones
+            let ts = match msg_type {
+                "T" | "E" => arr.get(2),
+                "K" | "AE" => arr.get(3),
+                _ => None,
+            }?;
+            if ts.is_string() { convert_timestamp(ts) } else { None }
         })
         .max();
     Ok(timestamp)
@@ -339,47 +342,50 @@ pub(super) fn extract_timestamp(msg: &str) -> Result<Option<i64>, SimpleError> {
 
 // https://zbgapi.github.io/docs/spot/v1/en/#market-trade
 // [T, symbol-id, symbol, timestamp, ask/bid, price, quantity]
-pub(super) fn parse_trade(msg: &str) -> Result<Vec<TradeMsg>, SimpleError> {
+pub(super) fn parse_trade(msg: &str) -> Result<Vec<TradeMsg>, ParseError> {
     let arr = if msg.starts_with(r#"[["T","#) {
-        serde_json::from_str::<Vec<Vec<String>>>(msg).map_err(|_e| {
-            SimpleError::new(format!("Failed to deserialize {msg} to Vec<Vec<String>>"))
-        })?
+        serde_json::from_str::<Vec<Vec<String>>>(msg)
+            .map_err(|_e| ParseError::malformed("expected Vec<Vec<String>>"))?
     } else if msg.starts_with(r#"["T","#) {
-        let tmp = serde_json::from_str::<Vec<String>>(msg).map_err(|_e| {
-            SimpleError::new(format!("Failed to deserialize {msg} to Vec<String>"))
-        })?;
+        let tmp = serde_json::from_str::<Vec<String>>(msg)
+            .map_err(|_e| ParseError::malformed("expected Vec<String>"))?;
         vec![tmp]
     } else {
-        return Err(SimpleError::new(format!("Invalid trade msg {msg}")));
+        return Err(ParseError::unsupported("Invalid trade msg"));
     };
 
     let mut trades: Vec<TradeMsg> = arr
         .into_iter()
-        .map(|raw_trade| {
-            assert_eq!(raw_trade[0], "T");
-            let timestamp = raw_trade[2].parse::<i64>().unwrap() * 1000;
+        .map(|raw_trade| -> Result<TradeMsg, ParseError> {
+            if raw_trade.len() < 7 || raw_trade[0] != "T" {
+                return Err(ParseError::malformed("expected [T, symbol-id, symbol, ...]"));
+            }
+            let timestamp = raw_trade[2].parse::<i64>()? * 1000;
             let symbol = raw_trade[3].as_str();
             let side = if raw_trade[4] == "ask" { TradeSide::Sell } else { TradeSide::Buy };
-            let price = raw_trade[5].parse::<f64>().unwrap();
-            let quantity = raw_trade[6].parse::<f64>().unwrap();
+            let price = raw_trade[5].parse::<f64>()?;
+            let quantity = raw_trade[6].parse::<f64>()?;
 
-            TradeMsg {
+            Ok(TradeMsg {
                 exchange: EXCHANGE_NAME.to_string(),
                 market_type: MarketType::Spot,
                 symbol: symbol.to_lowercase(),
-                pair: crypto_pair::normalize_pair(symbol, EXCHANGE_NAME).unwrap(),
+                pair: crypto_pair::normalize_pair(symbol, EXCHANGE_NAME)
+                    .ok_or_else(|| ParseError::symbol_normalization(symbol))?,
                 msg_type: MessageType::Trade,
                 timestamp,
                 price,
                 quantity_base: quantity,
                 quantity_quote: price * quantity,
                 quantity_contract: None,
+                price_exact: parse_exact(&raw_trade[5]),
+                quantity_exact: parse_exact(&raw_trade[6]),
                 side,
                 trade_id: timestamp.to_string(),
                 json: serde_json::to_string(&raw_trade).unwrap(),
-            }
+            })
         })
-        .collect();
+        .collect::<Result<Vec<TradeMsg>, ParseError>>()?;
 
     if trades.len() == 1 {
         trades[0].json = msg.to_string();
@@ -387,74 +393,61 @@ pub(super) fn parse_trade(msg: &str) -> Result<Vec<TradeMsg>, SimpleError> {
     Ok(trades)
 }
 
-#[derive(Serialize, Deserialize)]
-struct OrderbookSnapshot {
-    asks: Vec<[String; 2]>,
-    bids: Vec<[String; 2]>,
-}
-
 // https://zbgapi.github.io/docs/spot/v1/en/#market-depth
 // snapshot：
 // [AE, symbol-id, symbol, timestamp, asks:[[price, quantity]], bids[[price,
 // quantity]]] update:
 // [E, symbol-id, timestamp, symbol, ask/bid, price, quantity]
-pub(crate) fn parse_l2(msg: &str) -> Result<Vec<OrderBookMsg>, SimpleError> {
+pub(crate) fn parse_l2(msg: &str) -> Result<Vec<OrderBookMsg>, ParseError> {
     let snapshot = msg.starts_with(r#"[["AE","#);
 
     let orderbooks = if snapshot {
-        let arr = serde_json::from_str::<Vec<Vec<Value>>>(msg).map_err(|_e| {
-            SimpleError::new(format!("Failed to deserialize {msg} to Vec<Vec<Value>>"))
-        })?;
-
-        let parse_order = |raw_order: &[Value; 2]| -> Order {
-            let price: f64 = if raw_order[0].is_string() {
-                raw_order[0].as_str().unwrap().parse::<f64>().unwrap()
-            } else if raw_order[0].is_f64() || raw_order[0].is_i64() || raw_order[0].is_u64() {
-                raw_order[0].as_f64().unwrap()
-            } else {
-                panic!("Unknown format {msg}");
-            };
-
-            let quantity_base: f64 = if raw_order[1].is_string() {
-                raw_order[1].as_str().unwrap().parse::<f64>().unwrap()
-            } else if raw_order[1].is_f64() || raw_order[1].is_i64() || raw_order[1].is_u64() {
-                raw_order[1].as_f64().unwrap()
-            } else {
-                panic!("Unknown format {msg}");
-            };
+        let arr = serde_json::from_str::<Vec<Vec<Value>>>(msg)
+            .map_err(|_e| ParseError::malformed("expected Vec<Vec<Value>>"))?;
 
-            Order {
+        let parse_order = |raw_order: &[Value; 2]| -> Result<Order, ParseError> {
+            let price = json_f64(&raw_order[0], "price")?;
+            let quantity_base = json_f64(&raw_order[1], "quantity")?;
+
+            Ok(Order {
                 price,
                 quantity_base,
                 quantity_quote: price * quantity_base,
                 quantity_contract: None,
-            }
+                price_exact: json_exact(&raw_order[0]),
+                quantity_exact: json_exact(&raw_order[1]),
+            })
         };
 
         let mut v = arr
             .iter()
-            .filter(|raw_orderbook| !raw_orderbook[3].is_null())
-            .map(|raw_orderbook| {
-                let symbol = raw_orderbook[2].as_str().unwrap();
-                let pair = crypto_pair::normalize_pair(symbol, EXCHANGE_NAME).unwrap();
-                let timestamp = raw_orderbook[3].as_str().unwrap().parse::<i64>().unwrap() * 1000;
+            .filter(|raw_orderbook| raw_orderbook.len() > 5 && !raw_orderbook[3].is_null())
+            .map(|raw_orderbook| -> Result<OrderBookMsg, ParseError> {
+                let symbol = json_str(&raw_orderbook[2], "symbol")?;
+                let pair = crypto_pair::normalize_pair(symbol, EXCHANGE_NAME)
+                    .ok_or_else(|| ParseError::symbol_normalization(symbol))?;
+                let timestamp = json_str(&raw_orderbook[3], "timestamp")?.parse::<i64>()? * 1000;
 
                 let asks = serde_json::from_value::<Vec<[Value; 2]>>(
-                    raw_orderbook[4].as_object().unwrap().get("asks").unwrap().clone(),
-                )
-                .unwrap()
+                    json_object(&raw_orderbook[4], "asks")?
+                        .get("asks")
+                        .cloned()
+                        .unwrap_or_default(),
+                )?
                 .iter()
                 .map(parse_order)
-                .collect::<Vec<Order>>();
+                .collect::<Result<Vec<Order>, ParseError>>()?;
                 let bids = serde_json::from_value::<Vec<[Value; 2]>>(
-                    raw_orderbook[5].as_object().unwrap().get("bids").unwrap().clone(),
-                )
-                .unwrap()
+                    json_object(&raw_orderbook[5], "bids")?
+                        .get("bids")
+                        .cloned()
+                        .unwrap_or_default(),
+                )?
                 .iter()
                 .map(parse_order)
-                .collect::<Vec<Order>>();
+                .collect::<Result<Vec<Order>, ParseError>>()?;
 
-                OrderBookMsg {
+                Ok(OrderBookMsg {
                     exchange: EXCHANGE_NAME.to_string(),
                     market_type: MarketType::Spot,
                     symbol: symbol.to_lowercase(),
@@ -467,35 +460,39 @@ pub(crate) fn parse_l2(msg: &str) -> Result<Vec<OrderBookMsg>, SimpleError> {
                     bids,
                     snapshot,
                     json: serde_json::to_string(raw_orderbook).unwrap().as_str().to_string(),
-                }
+                })
             })
-            .collect::<Vec<OrderBookMsg>>();
+            .collect::<Result<Vec<OrderBookMsg>, ParseError>>()?;
 
         if v.len() == 1 {
             v[0].json = msg.to_string();
         }
         v
     } else {
-        let arr = serde_json::from_str::<Vec<String>>(msg).map_err(|_e| {
-            SimpleError::new(format!("Failed to deserialize {msg} to Vec<String>"))
-        })?;
+        let arr = serde_json::from_str::<Vec<String>>(msg)
+            .map_err(|_e| ParseError::malformed("expected Vec<String>"))?;
+        if arr.len() < 7 {
+            return Err(ParseError::malformed("expected [E, symbol-id, timestamp, symbol, ...]"));
+        }
         let symbol = arr[3].to_lowercase();
         let pair = crypto_pair::normalize_pair(&symbol, EXCHANGE_NAME)
-            .ok_or_else(|| SimpleError::new(format!("Failed to normalize {symbol} from {msg}")))?;
-        let timestamp = arr[2].parse::<i64>().unwrap() * 1000;
+            .ok_or_else(|| ParseError::symbol_normalization(&symbol))?;
+        let timestamp = arr[2].parse::<i64>()? * 1000;
 
         let mut asks: Vec<Order> = Vec::new();
         let mut bids: Vec<Order> = Vec::new();
 
         let order: Order = {
-            let price = arr[5].parse::<f64>().unwrap();
-            let quantity_base = arr[6].parse::<f64>().unwrap();
+            let price = arr[5].parse::<f64>()?;
+            let quantity_base = arr[6].parse::<f64>()?;
 
             Order {
                 price,
                 quantity_base,
                 quantity_quote: quantity_base * price,
                 quantity_contract: None,
+                price_exact: parse_exact(&arr[5]),
+                quantity_exact: parse_exact(&arr[6]),
             }
         };
 
@@ -524,53 +521,97 @@ pub(crate) fn parse_l2(msg: &str) -> Result<Vec<OrderBookMsg>, SimpleError> {
     Ok(orderbooks)
 }
 
+// https://zbgapi.github.io/docs/spot/v1/en/#market-ticker
+// [symbol-id, close, high, low, volume, change, trend, bid, ask, amount]
+//
+// Tickers have no timestamp, so `received_at` is required.
+pub(super) fn parse_ticker(msg: &str, received_at: i64) -> Result<Vec<TickerMsg>, ParseError> {
+    #[derive(Serialize, Deserialize)]
+    struct TradeStatistic {
+        trade_statistic: Vec<Vec<String>>,
+    }
+    let raw_msg = serde_json::from_str::<TradeStatistic>(msg)
+        .map_err(|_e| ParseError::malformed("expected TradeStatistic"))?;
+
+    let mut tickers = raw_msg
+        .trade_statistic
+        .into_iter()
+        .map(|raw_ticker| -> Result<TickerMsg, ParseError> {
+            if raw_ticker.len() < 9 {
+                return Err(ParseError::malformed("expected [symbol-id, close, high, ...]"));
+            }
+            let symbol = get_symbol(raw_ticker[0].parse::<i64>()?)?;
+            let pair = crypto_pair::normalize_pair(&symbol, EXCHANGE_NAME)
+                .ok_or_else(|| ParseError::symbol_normalization(&symbol))?;
+            let close = raw_ticker[1].parse::<f64>()?;
+            let volume = raw_ticker[4].parse::<f64>()?;
+
+            Ok(TickerMsg {
+                exchange: EXCHANGE_NAME.to_string(),
+                market_type: MarketType::Spot,
+                symbol,
+                pair,
+                msg_type: MessageType::Ticker,
+                timestamp: received_at,
+                // the change is a percentage
+                open: close / (1.0 + raw_ticker[5].parse::<f64>()? / 100.0),
+                high: raw_ticker[2].parse::<f64>()?,
+                low: raw_ticker[3].parse::<f64>()?,
+                close,
+                volume,
+                // the amount is often zero, so quote volume is approximated by the last price
+                quote_volume: close * volume,
+                last_quantity: None,
+                best_bid_price: Some(raw_ticker[7].parse::<f64>()?),
+                best_bid_quantity: None,
+                best_ask_price: Some(raw_ticker[8].parse::<f64>()?),
+                best_ask_quantity: None,
+                open_interest: None,
+                open_interest_quote: None,
+                json: serde_json::to_string(&raw_ticker)?,
+            })
+        })
+        .collect::<Result<Vec<TickerMsg>, ParseError>>()?;
+
+    if tickers.len() == 1 {
+        tickers[0].json = msg.to_string();
+    }
+    Ok(tickers)
+}
+
 // https://zbgapi.github.io/docs/spot/v1/en/#market-candlestick
 // [K, symbol-id, symbol, timestamp, open, high, low, close, volume, change,
 // dollar-rate, period, conversion, amount.
-pub(crate) fn parse_candlestick(msg: &str) -> Result<Vec<CandlestickMsg>, SimpleError> {
+pub(crate) fn parse_candlestick(msg: &str) -> Result<Vec<CandlestickMsg>, ParseError> {
     let arr = if msg.starts_with(r#"[["K","#) {
-        serde_json::from_str::<Vec<Vec<String>>>(msg).map_err(SimpleError::from)?
+        serde_json::from_str::<Vec<Vec<String>>>(msg).map_err(ParseError::from)?
     } else if msg.starts_with(r#"["K","#) {
-        let tmp = serde_json::from_str::<Vec<String>>(msg).map_err(|_e| {
-            SimpleError::new(format!("Failed to deserialize {msg} to Vec<String>"))
-        })?;
+        let tmp = serde_json::from_str::<Vec<String>>(msg)
+            .map_err(|_e| ParseError::malformed("expected Vec<String>"))?;
         vec![tmp]
     } else {
-        return Err(SimpleError::new(format!("Invalid trade msg {msg}")));
+        return Err(ParseError::unsupported("Invalid trade msg"));
     };
 
     let mut candlestick_msgs: Vec<CandlestickMsg> = arr
         .into_iter()
-        .map(|candlestick_msg| {
-            assert_eq!(candlestick_msg[0], "K");
-            let timestamp = candlestick_msg[3].parse::<i64>().unwrap() * 1000;
+        .map(|candlestick_msg| -> Result<CandlestickMsg, ParseError> {
+            if candlestick_msg.len() < 14 || candlestick_msg[0] != "K" {
+                return Err(ParseError::malformed("expected [K, symbol-id, symbol, ...]"));
+            }
+            let timestamp = candlestick_msg[3].parse::<i64>()? * 1000;
             let symbol = candlestick_msg[2].as_str();
-            let pair = crypto_pair::normalize_pair(symbol, EXCHANGE_NAME).unwrap();
-            let period = candlestick_msg[11].as_str();
-            let m_seconds = match period.to_string().pop().unwrap() {
-                'M' => {
-                    period.to_string().strip_suffix('M').unwrap().parse::<i64>().unwrap()
-                        * 1000
-                        * 60
-                }
-                'H' => {
-                    period.to_string().strip_suffix('H').unwrap().parse::<i64>().unwrap()
-                        * 1000
-                        * 60
-                        * 60
-                }
-                'D' => {
-                    period.to_string().strip_suffix('D').unwrap().parse::<i64>().unwrap()
-                        * 1000
-                        * 24
-                        * 60
-                        * 60
-                }
-                _ => 0,
-            };
-            let begin_time = timestamp - m_seconds;
-
-            CandlestickMsg {
+            let pair = crypto_pair::normalize_pair(symbol, EXCHANGE_NAME)
+                .ok_or_else(|| ParseError::symbol_normalization(symbol))?;
+            let period =
+                parse_candle_period(EXCHANGE_NAME, MarketType::Spot, &candlestick_msg[11])?;
+            // periods are 1 minute to 1 week
+            let period_millis = period.as_millis().ok_or_else(|| {
+                ParseError::unsupported(format!("Unsupported period {}", candlestick_msg[11]))
+            })?;
+            let begin_time = timestamp - period_millis;
+
+            Ok(CandlestickMsg {
                 exchange: super::EXCHANGE_NAME.to_string(),
                 market_type: MarketType::Spot,
                 symbol: symbol.to_string(),
@@ -578,17 +619,19 @@ pub(crate) fn parse_candlestick(msg: &str) -> Result<Vec<CandlestickMsg>, Simple
                 msg_type: MessageType::Candlestick,
                 timestamp,
                 begin_time,
-                open: candlestick_msg[4].parse::<f64>().unwrap(),
-                high: candlestick_msg[5].parse::<f64>().unwrap(),
-                low: candlestick_msg[6].parse::<f64>().unwrap(),
-                close: candlestick_msg[7].parse::<f64>().unwrap(),
-                volume: candlestick_msg[8].parse::<f64>().unwrap(),
-                period: period.to_string(),
-                quote_volume: Some(candlestick_msg[13].parse::<f64>().unwrap()),
+                end_time: timestamp,
+                open: candlestick_msg[4].parse::<f64>()?,
+                high: candlestick_msg[5].parse::<f64>()?,
+                low: candlestick_msg[6].parse::<f64>()?,
+                close: candlestick_msg[7].parse::<f64>()?,
+                volume: candlestick_msg[8].parse::<f64>()?,
+                period,
+                quote_volume: Some(candlestick_msg[13].parse::<f64>()?),
+                is_closed: None,
                 json: msg.to_string(),
-            }
+            })
         })
-        .collect();
+        .collect::<Result<Vec<CandlestickMsg>, ParseError>>()?;
 
     if candlestick_msgs.len() == 1 {
         candlestick_msgs[0].json = msg.to_string();
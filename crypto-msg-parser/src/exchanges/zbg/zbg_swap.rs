@@ -1,13 +1,18 @@
 use crypto_market_type::MarketType;
 use crypto_msg_type::MessageType;
 
-use super::super::utils::{convert_timestamp, http_get};
-use crypto_message::{CandlestickMsg, Order, OrderBookMsg, TradeMsg, TradeSide};
-
+use super::super::utils::{
+    annualize_funding_rate, convert_timestamp, http_get, json_array, json_exact, json_f64,
+    json_i64, json_str, next_funding_time, parse_candle_period, parse_exact,
+};
+use crypto_message::{
+    CandlestickMsg, FundingRateMsg, Order, OrderBookMsg, TickerMsg, TradeMsg, TradeSide,
+};
+
+use crate::ParseError;
 use once_cell::sync::Lazy;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
-use simple_error::SimpleError;
 use std::collections::{BTreeMap, HashMap};
 
 use super::EXCHANGE_NAME;
@@ -96,17 +101,19 @@ fn fetch_swap_contracts() -> BTreeMap<i64, SwapContractInfo> {
     }
 
     let mut mapping = BTreeMap::<i64, SwapContractInfo>::new();
-    if let Ok(txt) = http_get("https://www.zbg.com/exchange/api/v1/future/common/contracts") {
-        let resp = serde_json::from_str::<Response>(&txt).unwrap();
-        let swap_markets = resp.datas;
-
-        for swap_market in swap_markets.iter() {
-            let contract_info = SwapContractInfo {
-                symbol: swap_market.symbol.clone(),
-                contract_id: swap_market.contractId,
-                contract_unit: swap_market.contractUnit.parse::<f64>().unwrap(),
-            };
-            mapping.insert(contract_info.contract_id, contract_info);
+    if let Some(resp) = http_get("https://www.zbg.com/exchange/api/v1/future/common/contracts")
+        .ok()
+        .and_then(|txt| serde_json::from_str::<Response>(&txt).ok())
+    {
+        for swap_market in resp.datas.iter() {
+            if let Ok(contract_unit) = swap_market.contractUnit.parse::<f64>() {
+                let contract_info = SwapContractInfo {
+                    symbol: swap_market.symbol.clone(),
+                    contract_id: swap_market.contractId,
+                    contract_unit,
+                };
+                mapping.insert(contract_info.contract_id, contract_info);
+            }
         }
     }
 
@@ -146,27 +153,58 @@ struct RawCandlestickMsg {
     extra: HashMap<String, Value>,
 }
 
-pub(super) fn extract_symbol(_market_type: MarketType, msg: &str) -> Result<String, SimpleError> {
+// https://zbgapi.github.io/docs/future/v1/en/#public-get-ticker
+// https://zbgapi.github.io/docs/future/v1/en/#ticker-all
+#[derive(Serialize, Deserialize)]
+struct RawIndicatorMsg {
+    symbol: String,
+    te: i64,     // microseconds
+    fr: String,  // funding rate
+    pfr: String, // predicted funding rate
+    #[serde(flatten)]
+    extra: HashMap<String, Value>,
+}
+
+// https://zbgapi.github.io/docs/future/v1/en/#ticker-all
+#[derive(Serialize, Deserialize)]
+struct RawTickerMsg {
+    symbol: String,
+    ci: i64,    // contract id
+    te: i64,    // microseconds
+    lp: String, // last price
+    op24: f64,
+    hgp24: f64,
+    lwp24: f64,
+    tv: String, // 24h volume in contracts
+    pv: String, // open interest in contracts
+    #[serde(flatten)]
+    extra: HashMap<String, Value>,
+}
+
+#[derive(Serialize, Deserialize)]
+#[allow(non_snake_case)]
+struct RestfulMsg<T: Sized> {
+    datas: T,
+    resMsg: Value,
+}
+
+pub(super) fn extract_symbol(market_type: MarketType, msg: &str) -> Result<String, ParseError> {
     if msg.contains("datas") && msg.contains("resMsg") {
         // RESTful
-        let obj = serde_json::from_str::<HashMap<String, Value>>(msg).unwrap();
+        let obj = serde_json::from_str::<HashMap<String, Value>>(msg)?;
         return if let Some(symbol) = obj.get("symbol") {
-            Ok(symbol.as_str().unwrap().to_string())
-        } else if let Some(symbol) = obj["datas"].get("sb") {
-            Ok(symbol.as_str().unwrap().to_string())
+            Ok(json_str(symbol, "symbol")?.to_string())
+        } else if let Some(symbol) = obj.get("datas").and_then(|x| x.get("sb")) {
+            Ok(json_str(symbol, "sb")?.to_string())
         } else {
             Ok("NONE".to_string())
         };
     }
 
-    // websocket
-    let ws_msg = serde_json::from_str::<Vec<Value>>(msg).unwrap();
-    let contract_id = if ws_msg[1]["contractId"].is_i64() {
-        ws_msg[1]["contractId"].as_i64().unwrap()
-    } else {
-        ws_msg[1]["contractId"].as_str().unwrap().parse::<i64>().unwrap()
-    };
-    let contract_info = SWAP_CONTRACT_MAP.get(&contract_id).unwrap();
+    // websocket, an array, indexing a `Value` returns `Null` rather than panic if it's too short
+    let ws_msg = serde_json::from_str::<Value>(msg)?;
+    let contract_id = json_i64(&ws_msg[1]["contractId"], "contractId")?;
+    let contract_info = get_contract_info(market_type, contract_id)?;
     let symbol = contract_info.symbol.as_str();
     Ok(symbol.to_string())
 }
@@ -174,13 +212,13 @@ pub(super) fn extract_symbol(_market_type: MarketType, msg: &str) -> Result<Stri
 pub(super) fn extract_timestamp(
     _market_type: MarketType,
     msg: &str,
-) -> Result<Option<i64>, SimpleError> {
+) -> Result<Option<i64>, ParseError> {
     if msg.contains("datas") && msg.contains("resMsg") {
         // RESTful
-        let obj = serde_json::from_str::<HashMap<String, Value>>(msg).unwrap();
-        return if let Some(t) = obj["datas"].get("timestamp") {
+        let obj = serde_json::from_str::<HashMap<String, Value>>(msg)?;
+        return if let Some(t) = obj.get("datas").and_then(|x| x.get("timestamp")) {
             Ok(convert_timestamp(t))
-        } else if let Some(t) = obj["datas"].get("te") {
+        } else if let Some(t) = obj.get("datas").and_then(|x| x.get("te")) {
             Ok(convert_timestamp(t))
         } else {
             Ok(None)
@@ -188,70 +226,112 @@ pub(super) fn extract_timestamp(
     }
 
     // websocket
-    let ws_msg = serde_json::from_str::<Vec<Value>>(msg).unwrap();
-    let channel = ws_msg[0].as_str().unwrap();
+    let ws_msg = serde_json::from_str::<Value>(msg)?;
+    let channel = json_str(&ws_msg[0], "channel")?;
     match channel {
-        "future_tick" => {
-            let raw_trade = ws_msg[1]["trades"].as_array().unwrap();
-            Ok(convert_timestamp(&raw_trade[0]))
-        }
+        "future_tick" => Ok(convert_timestamp(&ws_msg[1]["trades"][0])),
         "future_kline" => {
-            let lines = ws_msg[1]["lines"].as_array().unwrap();
-            let timestamp = lines.iter().map(|line| convert_timestamp(&line[0]).unwrap()).max();
+            let lines = json_array(&ws_msg[1]["lines"], "lines")?;
+            let timestamp = lines
+                .iter()
+                .map(|line| convert_timestamp(&line[0]).ok_or_else(ParseError::missing_timestamp))
+                .collect::<Result<Vec<i64>, ParseError>>()?
+                .into_iter()
+                .max();
             Ok(timestamp)
         }
         "future_snapshot_depth" => Ok(convert_timestamp(&ws_msg[1]["time"])),
         "future_snapshot_indicator" => Ok(convert_timestamp(&ws_msg[1]["te"])),
-        _ => Err(SimpleError::new(format!("Unknown channel {channel} in  {msg}"))),
+        _ => Err(ParseError::unsupported(format!("Unknown channel {channel}"))),
     }
 }
 
+// The instrument registry takes precedence over the embedded table
+fn get_contract_info(
+    market_type: MarketType,
+    contract_id: i64,
+) -> Result<SwapContractInfo, ParseError> {
+    let registry = crypto_pair::instrument_registry();
+    let embedded = SWAP_CONTRACT_MAP.get(&contract_id);
+    let symbol = registry
+        .symbol_by_id(EXCHANGE_NAME, market_type, contract_id)
+        .or_else(|| embedded.map(|x| x.symbol.clone()))
+        .ok_or_else(|| ParseError::unsupported(format!("Unknown contract id {contract_id}")))?;
+    let contract_unit = crypto_pair::normalize_pair(&symbol, EXCHANGE_NAME)
+        .and_then(|pair| registry.contract_value(EXCHANGE_NAME, market_type, &pair))
+        .or_else(|| embedded.map(|x| x.contract_unit))
+        .ok_or_else(|| ParseError::unsupported(format!("Unknown contract id {contract_id}")))?;
+    Ok(SwapContractInfo { contract_id, symbol, contract_unit })
+}
+
+// Contract values in the history take precedence over the current ones
 fn calc_quantity_and_volume(
     market_type: MarketType,
     contract_id: i64,
     price: f64,
     size: f64,
-) -> (f64, f64) {
-    match market_type {
-        MarketType::InverseSwap => {
-            let contract_unit = SWAP_CONTRACT_MAP.get(&contract_id).unwrap().contract_unit;
-            let volume = size * contract_unit;
-
-            (volume / price, volume)
-        }
-        MarketType::LinearSwap => {
-            let contract_unit = SWAP_CONTRACT_MAP.get(&contract_id).unwrap().contract_unit;
-            let quantity = size * contract_unit;
-
-            (quantity, quantity * price)
-        }
-        _ => panic!("Unknown market_type {market_type}"),
+    timestamp: i64,
+) -> Result<(f64, f64), ParseError> {
+    if market_type != MarketType::InverseSwap && market_type != MarketType::LinearSwap {
+        return Err(ParseError::unsupported(format!("Unknown market_type {market_type}")));
+    }
+    let contract_info = get_contract_info(market_type, contract_id)?;
+    let contract_unit = crypto_pair::normalize_pair(&contract_info.symbol, EXCHANGE_NAME)
+        .and_then(|pair| {
+            crypto_contract_value::get_contract_value_at(
+                EXCHANGE_NAME,
+                market_type,
+                &pair,
+                timestamp,
+            )
+        })
+        .unwrap_or(contract_info.contract_unit);
+    if market_type == MarketType::InverseSwap {
+        let volume = size * contract_unit;
+        Ok((volume / price, volume))
+    } else {
+        let quantity = size * contract_unit;
+        Ok((quantity, quantity * price))
     }
 }
 
-pub(super) fn parse_trade(
-    market_type: MarketType,
-    msg: &str,
-) -> Result<Vec<TradeMsg>, SimpleError> {
+pub(super) fn parse_trade(market_type: MarketType, msg: &str) -> Result<Vec<TradeMsg>, ParseError> {
     let ws_msg = serde_json::from_str::<Vec<Value>>(msg)
-        .map_err(|_e| SimpleError::new(format!("Failed to deserialize {msg} to Vec<Value>")))?;
-    assert_eq!(ws_msg[0].as_str().unwrap(), "future_tick");
+        .map_err(|_e| ParseError::malformed("expected Vec<Value>"))?;
+    if ws_msg.len() < 2 || ws_msg[0].as_str() != Some("future_tick") {
+        return Err(ParseError::malformed(format!("expected a future_tick message, got {msg}")));
+    }
     let raw_trade: RawTradeMsg = serde_json::from_value(ws_msg[1].clone()).map_err(|_e| {
-        SimpleError::new(format!("Failed to deserialize {} to RawTradeMsg", ws_msg[1]))
+        ParseError::malformed(format!("Failed to deserialize {} to RawTradeMsg", ws_msg[1]))
     })?;
 
-    let contract_info = SWAP_CONTRACT_MAP.get(&raw_trade.contractId).unwrap();
+    let contract_info = get_contract_info(market_type, raw_trade.contractId)?;
     let symbol = contract_info.symbol.as_str();
-    let pair = crypto_pair::normalize_pair(symbol, EXCHANGE_NAME).unwrap();
-
-    let timestamp = raw_trade.trades[0].as_i64().unwrap();
-    let price = raw_trade.trades[1].as_str().unwrap().parse::<f64>().unwrap();
-    let size = raw_trade.trades[2].as_str().unwrap().parse::<f64>().unwrap();
-    let side =
-        if raw_trade.trades[3].as_i64().unwrap() == -1 { TradeSide::Sell } else { TradeSide::Buy };
+    let pair = crypto_pair::normalize_pair(symbol, EXCHANGE_NAME)
+        .ok_or_else(|| ParseError::symbol_normalization(symbol))?;
+
+    if raw_trade.trades.len() < 4 {
+        return Err(ParseError::malformed(format!(
+            "Too few fields in trades {:?}",
+            raw_trade.trades
+        )));
+    }
+    let timestamp = json_i64(&raw_trade.trades[0], "timestamp")?;
+    let price = json_f64(&raw_trade.trades[1], "price")?;
+    let size = json_f64(&raw_trade.trades[2], "size")?;
+    let side = if json_i64(&raw_trade.trades[3], "side")? == -1 {
+        TradeSide::Sell
+    } else {
+        TradeSide::Buy
+    };
 
-    let (quantity_base, quantity_quote) =
-        calc_quantity_and_volume(market_type, contract_info.contract_id, price, size);
+    let (quantity_base, quantity_quote) = calc_quantity_and_volume(
+        market_type,
+        contract_info.contract_id,
+        price,
+        size,
+        timestamp / 1000,
+    )?;
 
     let trade = TradeMsg {
         exchange: EXCHANGE_NAME.to_string(),
@@ -264,6 +344,8 @@ pub(super) fn parse_trade(
         quantity_base,
         quantity_quote,
         quantity_contract: Some(size),
+        price_exact: json_exact(&raw_trade.trades[1]),
+        quantity_exact: json_exact(&raw_trade.trades[2]),
         side,
         trade_id: timestamp.to_string(),
         json: msg.to_string(),
@@ -275,26 +357,43 @@ pub(super) fn parse_trade(
 pub(crate) fn parse_l2(
     market_type: MarketType,
     msg: &str,
-) -> Result<Vec<OrderBookMsg>, SimpleError> {
+) -> Result<Vec<OrderBookMsg>, ParseError> {
     let ws_msg = serde_json::from_str::<Vec<Value>>(msg)
-        .map_err(|_e| SimpleError::new(format!("Failed to deserialize {msg} to Vec<Value>")))?;
-    assert_eq!(ws_msg[0].as_str().unwrap(), "future_snapshot_depth");
+        .map_err(|_e| ParseError::malformed("expected Vec<Value>"))?;
+    if ws_msg.len() < 2 || ws_msg[0].as_str() != Some("future_snapshot_depth") {
+        return Err(ParseError::malformed(format!(
+            "expected a future_snapshot_depth message, got {msg}"
+        )));
+    }
     let raw_orderbook: RawOrderbookMsg =
         serde_json::from_value(ws_msg[1].clone()).map_err(|_e| {
-            SimpleError::new(format!("Failed to deserialize {} to RawOrderbookMsg", ws_msg[1]))
+            ParseError::malformed(format!("Failed to deserialize {} to RawOrderbookMsg", ws_msg[1]))
         })?;
 
-    let contract_info = SWAP_CONTRACT_MAP.get(&raw_orderbook.contractId).unwrap();
+    let contract_info = get_contract_info(market_type, raw_orderbook.contractId)?;
     let symbol = contract_info.symbol.as_str();
-    let pair = crypto_pair::normalize_pair(symbol, EXCHANGE_NAME).unwrap();
-
-    let parse_order = |raw_order: &[String; 2]| -> Order {
-        let price = raw_order[0].parse::<f64>().unwrap();
-        let quantity = raw_order[1].parse::<f64>().unwrap();
-        let (quantity_base, quantity_quote) =
-            calc_quantity_and_volume(market_type, contract_info.contract_id, price, quantity);
-
-        Order { price, quantity_base, quantity_quote, quantity_contract: Some(quantity) }
+    let pair = crypto_pair::normalize_pair(symbol, EXCHANGE_NAME)
+        .ok_or_else(|| ParseError::symbol_normalization(symbol))?;
+
+    let parse_order = |raw_order: &[String; 2]| -> Result<Order, ParseError> {
+        let price = raw_order[0].parse::<f64>()?;
+        let quantity = raw_order[1].parse::<f64>()?;
+        let (quantity_base, quantity_quote) = calc_quantity_and_volume(
+            market_type,
+            contract_info.contract_id,
+            price,
+            quantity,
+            raw_orderbook.time / 1000,
+        )?;
+
+        Ok(Order {
+            price,
+            quantity_base,
+            quantity_quote,
+            quantity_contract: Some(quantity),
+            price_exact: parse_exact(&raw_order[0]),
+            quantity_exact: parse_exact(&raw_order[1]),
+        })
     };
 
     let orderbook = OrderBookMsg {
@@ -306,8 +405,16 @@ pub(crate) fn parse_l2(
         timestamp: raw_orderbook.time / 1000,
         seq_id: None,
         prev_seq_id: None,
-        asks: raw_orderbook.asks.iter().map(parse_order).collect::<Vec<Order>>(),
-        bids: raw_orderbook.bids.iter().map(parse_order).collect::<Vec<Order>>(),
+        asks: raw_orderbook
+            .asks
+            .iter()
+            .map(parse_order)
+            .collect::<Result<Vec<Order>, ParseError>>()?,
+        bids: raw_orderbook
+            .bids
+            .iter()
+            .map(parse_order)
+            .collect::<Result<Vec<Order>, ParseError>>()?,
         snapshot: false,
         json: msg.to_string(),
     };
@@ -319,54 +426,172 @@ pub(crate) fn parse_l2(
 pub(crate) fn parse_candlestick(
     market_type: MarketType,
     msg: &str,
-) -> Result<Vec<CandlestickMsg>, SimpleError> {
-    let ws_msg = serde_json::from_str::<Vec<Value>>(msg).map_err(SimpleError::from)?;
-    assert_eq!(ws_msg[0].as_str().unwrap(), "future_kline");
+) -> Result<Vec<CandlestickMsg>, ParseError> {
+    let ws_msg = serde_json::from_str::<Vec<Value>>(msg).map_err(ParseError::from)?;
+    if ws_msg.len() < 2 || ws_msg[0].as_str() != Some("future_kline") {
+        return Err(ParseError::malformed(format!("expected a future_kline message, got {msg}")));
+    }
     let raw_candlestick_msg: RawCandlestickMsg = serde_json::from_value(ws_msg[1].clone())
         .map_err(|_e| {
-            SimpleError::new(format!("Failed to deserialize {} to RawCandlestickMsg", ws_msg[1]))
+            ParseError::malformed(format!(
+                "Failed to deserialize {} to RawCandlestickMsg",
+                ws_msg[1]
+            ))
         })?;
-    let contract_info = SWAP_CONTRACT_MAP.get(&raw_candlestick_msg.contractId).unwrap();
+    let contract_info = get_contract_info(market_type, raw_candlestick_msg.contractId)?;
     let symbol = contract_info.symbol.as_str();
-    let pair = crypto_pair::normalize_pair(symbol, EXCHANGE_NAME).unwrap();
-    let range = raw_candlestick_msg.range;
+    let pair = crypto_pair::normalize_pair(symbol, EXCHANGE_NAME)
+        .ok_or_else(|| ParseError::symbol_normalization(symbol))?;
+    // the range is in milliseconds
+    let period = parse_candle_period(EXCHANGE_NAME, market_type, &raw_candlestick_msg.range)?;
+    let period_millis = period.as_millis().ok_or_else(|| {
+        ParseError::unsupported(format!("Unsupported range {}", raw_candlestick_msg.range))
+    })?;
 
     let candlestick_msgs: Vec<CandlestickMsg> = raw_candlestick_msg
         .lines
         .into_iter()
-        .map(|line| {
-            //let timestamp = line[0].clone().as_i64().unwrap();
-            let timestamp = line[0].as_i64().unwrap();
-            let open = line[1].as_str().unwrap().parse::<f64>().unwrap();
-            let high = line[2].as_str().unwrap().parse::<f64>().unwrap();
-            let low = line[3].as_str().unwrap().parse::<f64>().unwrap();
-            let close = line[4].as_str().unwrap().parse::<f64>().unwrap();
-            let size = line[5].as_str().unwrap().parse::<f64>().unwrap();
-            let (volume, quote_volume) =
-                calc_quantity_and_volume(market_type, contract_info.contract_id, open, size);
-
-            CandlestickMsg {
+        .map(|line| -> Result<CandlestickMsg, ParseError> {
+            let timestamp = json_i64(&line[0], "timestamp")?;
+            let open = json_f64(&line[1], "open")?;
+            let high = json_f64(&line[2], "high")?;
+            let low = json_f64(&line[3], "low")?;
+            let close = json_f64(&line[4], "close")?;
+            let size = json_f64(&line[5], "size")?;
+            let (volume, quote_volume) = calc_quantity_and_volume(
+                market_type,
+                contract_info.contract_id,
+                open,
+                size,
+                timestamp,
+            )?;
+
+            Ok(CandlestickMsg {
                 exchange: EXCHANGE_NAME.to_string(),
                 market_type,
                 msg_type: MessageType::Candlestick,
                 symbol: symbol.to_string(),
                 pair: pair.clone(),
                 timestamp,
-                period: range.clone(),
-                begin_time: timestamp - range.parse::<i64>().unwrap(),
+                period,
+                begin_time: timestamp - period_millis,
+                end_time: timestamp,
                 open,
                 high,
                 low,
                 close,
                 volume,
                 quote_volume: Some(quote_volume),
+                is_closed: None,
                 json: msg.to_string(),
-            }
+            })
         })
-        .collect();
+        .collect::<Result<Vec<CandlestickMsg>, ParseError>>()?;
     Ok(candlestick_msgs)
 }
 
+/// Parses the `future_snapshot_indicator` channel.
+pub(super) fn parse_ticker(
+    market_type: MarketType,
+    msg: &str,
+) -> Result<Vec<TickerMsg>, ParseError> {
+    let (channel, raw_ticker) = serde_json::from_str::<(String, RawTickerMsg)>(msg)
+        .map_err(|_e| ParseError::malformed("expected (String, RawTickerMsg)"))?;
+    if channel != "future_snapshot_indicator" {
+        return Err(ParseError::unsupported(format!("Unsupported channel {channel}")));
+    }
+    let pair = crypto_pair::normalize_pair(&raw_ticker.symbol, EXCHANGE_NAME)
+        .ok_or_else(|| ParseError::symbol_normalization(&raw_ticker.symbol))?;
+    let close = raw_ticker.lp.parse::<f64>()?;
+    let timestamp = raw_ticker.te / 1000;
+    let (volume, quote_volume) = calc_quantity_and_volume(
+        market_type,
+        raw_ticker.ci,
+        close,
+        raw_ticker.tv.parse::<f64>()?,
+        timestamp,
+    )?;
+    let (open_interest, open_interest_quote) = calc_quantity_and_volume(
+        market_type,
+        raw_ticker.ci,
+        close,
+        raw_ticker.pv.parse::<f64>()?,
+        timestamp,
+    )?;
+
+    let ticker_msg = TickerMsg {
+        exchange: EXCHANGE_NAME.to_string(),
+        market_type,
+        symbol: raw_ticker.symbol.clone(),
+        pair,
+        msg_type: MessageType::Ticker,
+        timestamp,
+        open: raw_ticker.op24,
+        high: raw_ticker.hgp24,
+        low: raw_ticker.lwp24,
+        close,
+        volume,
+        quote_volume,
+        last_quantity: None,
+        best_bid_price: None,
+        best_bid_quantity: None,
+        best_ask_price: None,
+        best_ask_quantity: None,
+        open_interest: Some(open_interest),
+        open_interest_quote: Some(open_interest_quote),
+        json: msg.to_string(),
+    };
+
+    Ok(vec![ticker_msg])
+}
+
+// Perpetual swaps settle funding every 8 hours
+const FUNDING_INTERVAL: i64 = 8 * 3600 * 1000;
+
+/// Parses the `future_snapshot_indicator` channel and the ticker RESTful API.
+///
+/// Neither has a funding time, which is the next 8-hour boundary after the
+/// message time.
+pub(super) fn parse_funding_rate(
+    market_type: MarketType,
+    msg: &str,
+) -> Result<Vec<FundingRateMsg>, ParseError> {
+    let raw_msg = if let Ok(rest_msg) = serde_json::from_str::<RestfulMsg<RawIndicatorMsg>>(msg) {
+        if rest_msg.resMsg["code"].as_str() != Some("1") {
+            return Err(ParseError::unsupported("Error HTTP response"));
+        }
+        rest_msg.datas
+    } else {
+        let (channel, raw_msg) = serde_json::from_str::<(String, RawIndicatorMsg)>(msg)
+            .map_err(|_e| ParseError::malformed("expected (String, RawIndicatorMsg)"))?;
+        if channel != "future_snapshot_indicator" {
+            return Err(ParseError::unsupported(format!("Unsupported channel {channel}")));
+        }
+        raw_msg
+    };
+    let pair = crypto_pair::normalize_pair(&raw_msg.symbol, EXCHANGE_NAME)
+        .ok_or_else(|| ParseError::symbol_normalization(&raw_msg.symbol))?;
+    let timestamp = raw_msg.te / 1000;
+    let funding_rate = raw_msg.fr.parse::<f64>()?;
+
+    Ok(vec![FundingRateMsg {
+        exchange: EXCHANGE_NAME.to_string(),
+        market_type,
+        symbol: raw_msg.symbol.clone(),
+        pair,
+        msg_type: MessageType::FundingRate,
+        timestamp,
+        funding_rate,
+        funding_time: next_funding_time(timestamp, FUNDING_INTERVAL),
+        estimated_rate: Some(raw_msg.pfr.parse::<f64>()?),
+        funding_interval: FUNDING_INTERVAL,
+        funding_rate_cap: None,
+        funding_rate_floor: None,
+        annualized_rate: annualize_funding_rate(funding_rate, FUNDING_INTERVAL),
+        json: msg.to_string(),
+    }])
+}
+
 #[cfg(test)]
 mod tests {
     use super::fetch_swap_contracts;
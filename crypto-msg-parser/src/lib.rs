@@ -1,11 +1,23 @@
+mod bbo;
+mod candle;
+mod checksum;
+mod depth_sync;
+mod error;
 pub mod exchanges;
 use crypto_market_type::MarketType;
 use crypto_message::{
-    BboMsg, CandlestickMsg, FundingRateMsg, Order, OrderBookMsg, TradeMsg, TradeSide,
+    BboMsg, CandlestickMsg, FundingRateMsg, IndexPriceMsg, L3Action, L3Msg, LiquidationMsg,
+    MarkPriceMsg, Message, OpenInterestMsg, OptionTickerMsg, Order, OrderBookMsg, TickerMsg,
+    TradeMsg, TradeSide,
 };
 use crypto_msg_type::MessageType;
+use error::catch_panic;
+pub use bbo::{derive_bbo, derive_bbo_from_book, BboDeriver};
+pub use candle::parse_candle_period;
+pub use checksum::ChecksumBook;
+pub use depth_sync::{BinanceDepthSync, DepthSyncStatus};
+pub use error::{ErrorContext, ParseError};
 pub use exchanges::utils::round; // for test only
-use simple_error::SimpleError;
 
 /// Extract the symbol from the message.
 ///
@@ -15,8 +27,8 @@ pub fn extract_symbol(
     exchange: &str,
     market_type: MarketType,
     msg: &str,
-) -> Result<String, SimpleError> {
-    match exchange {
+) -> Result<String, ParseError> {
+    let ret = catch_panic(|| match exchange {
         "binance" => exchanges::binance::extract_symbol(msg),
         "bitfinex" => exchanges::bitfinex::extract_symbol(msg),
         "bitget" => exchanges::bitget::extract_symbol(market_type, msg),
@@ -37,8 +49,9 @@ pub fn extract_symbol(
         "okex" | "okx" => exchanges::okx::extract_symbol(market_type, msg),
         "zb" => exchanges::zb::extract_symbol(market_type, msg),
         "zbg" => exchanges::zbg::extract_symbol(market_type, msg),
-        _ => Err(SimpleError::new(format!("Unknown exchange {exchange}"))),
-    }
+        _ => Err(ParseError::unknown_exchange(exchange)),
+    });
+    ret.map_err(|err| err.with_context(exchange, market_type, msg))
 }
 
 /// Extract the timestamp from the message.
@@ -46,8 +59,8 @@ pub fn extract_timestamp(
     exchange: &str,
     market_type: MarketType,
     msg: &str,
-) -> Result<Option<i64>, SimpleError> {
-    match exchange {
+) -> Result<Option<i64>, ParseError> {
+    let ret = catch_panic(|| match exchange {
         "binance" => exchanges::binance::extract_timestamp(msg),
         "bitfinex" => exchanges::bitfinex::extract_timestamp(msg),
         "bitget" => exchanges::bitget::extract_timestamp(market_type, msg),
@@ -68,8 +81,9 @@ pub fn extract_timestamp(
         "okex" | "okx" => exchanges::okx::extract_timestamp(market_type, msg),
         "zb" => exchanges::zb::extract_timestamp(market_type, msg),
         "zbg" => exchanges::zbg::extract_timestamp(market_type, msg),
-        _ => Err(SimpleError::new(format!("Unknown exchange {exchange}"))),
-    }
+        _ => Err(ParseError::unknown_exchange(exchange)),
+    });
+    ret.map_err(|err| err.with_context(exchange, market_type, msg))
 }
 
 /// Parse trade messages.
@@ -77,8 +91,8 @@ pub fn parse_trade(
     exchange: &str,
     market_type: MarketType,
     msg: &str,
-) -> Result<Vec<TradeMsg>, SimpleError> {
-    match exchange {
+) -> Result<Vec<TradeMsg>, ParseError> {
+    let ret = catch_panic(|| match exchange {
         "binance" => exchanges::binance::parse_trade(market_type, msg),
         "bitfinex" => exchanges::bitfinex::parse_trade(market_type, msg),
         "bitget" => exchanges::bitget::parse_trade(market_type, msg),
@@ -99,8 +113,9 @@ pub fn parse_trade(
         "okex" | "okx" => exchanges::okx::parse_trade(market_type, msg),
         "zb" => exchanges::zb::parse_trade(market_type, msg),
         "zbg" => exchanges::zbg::parse_trade(market_type, msg),
-        _ => Err(SimpleError::new(format!("Unknown exchange {exchange}"))),
-    }
+        _ => Err(unsupported_exchange(exchange, "trade messages")),
+    });
+    ret.map_err(|err| err.with_context(exchange, market_type, msg))
 }
 
 /// Parse level2 orderbook messages.
@@ -109,31 +124,25 @@ pub fn parse_l2(
     market_type: MarketType,
     msg: &str,
     received_at: Option<i64>,
-) -> Result<Vec<OrderBookMsg>, SimpleError> {
-    let ret = match exchange {
+) -> Result<Vec<OrderBookMsg>, ParseError> {
+    let ret = catch_panic(|| match exchange {
         "binance" => exchanges::binance::parse_l2(market_type, msg),
-        "bitfinex" => exchanges::bitfinex::parse_l2(
-            market_type,
-            msg,
-            received_at.expect("Bitfinex orderbook messages doesn't have timestamp"),
-        ),
+        "bitfinex" => received_at
+            .ok_or_else(ParseError::missing_timestamp)
+            .and_then(|received_at| exchanges::bitfinex::parse_l2(market_type, msg, received_at)),
         "bitget" => exchanges::bitget::parse_l2(market_type, msg),
         "bithumb" => exchanges::bithumb::parse_l2(market_type, msg),
-        "bitmex" => exchanges::bitmex::parse_l2(
-            market_type,
-            msg,
-            received_at.expect("BitMEX orderbook messages don't have timestamp"),
-        ),
+        "bitmex" => received_at
+            .ok_or_else(ParseError::missing_timestamp)
+            .and_then(|received_at| exchanges::bitmex::parse_l2(market_type, msg, received_at)),
         "bitstamp" => exchanges::bitstamp::parse_l2(market_type, msg),
         "bitz" => exchanges::bitz::parse_l2(market_type, msg),
         "bybit" => exchanges::bybit::parse_l2(market_type, msg),
         "coinbase_pro" => exchanges::coinbase_pro::parse_l2(market_type, msg, received_at),
         "deribit" => exchanges::deribit::parse_l2(market_type, msg),
-        "dydx" => exchanges::dydx::parse_l2(
-            market_type,
-            msg,
-            received_at.expect("dYdX orderbook messages don't have timestamp"),
-        ),
+        "dydx" => received_at
+            .ok_or_else(ParseError::missing_timestamp)
+            .and_then(|received_at| exchanges::dydx::parse_l2(market_type, msg, received_at)),
         "ftx" => exchanges::ftx::parse_l2(market_type, msg),
         "gate" => exchanges::gate::parse_l2(market_type, msg, received_at),
         "huobi" => exchanges::huobi::parse_l2(market_type, msg),
@@ -143,21 +152,21 @@ pub fn parse_l2(
         "okex" | "okx" => exchanges::okx::parse_l2(market_type, msg),
         "zb" => exchanges::zb::parse_l2(market_type, msg),
         "zbg" => exchanges::zbg::parse_l2(market_type, msg),
-        _ => Err(SimpleError::new(format!("Unknown exchange {exchange}"))),
-    };
+        _ => Err(unsupported_exchange(exchange, "level2 messages")),
+    });
     match ret {
         Ok(mut orderbooks) => {
             for orderbook in orderbooks.iter_mut() {
                 if orderbook.snapshot {
                     // sorted in ascending order by price
-                    orderbook.asks.sort_by(|a, b| a.price.partial_cmp(&b.price).unwrap());
+                    orderbook.asks.sort_by(|a, b| a.price.total_cmp(&b.price));
                     // sorted in descending order by price
-                    orderbook.bids.sort_by(|a, b| b.price.partial_cmp(&a.price).unwrap());
+                    orderbook.bids.sort_by(|a, b| b.price.total_cmp(&a.price));
                 }
             }
             Ok(orderbooks)
         }
-        Err(_) => ret,
+        Err(err) => Err(err.with_context(exchange, market_type, msg)),
     }
 }
 
@@ -167,8 +176,8 @@ pub fn parse_l2_topk(
     market_type: MarketType,
     msg: &str,
     received_at: Option<i64>,
-) -> Result<Vec<OrderBookMsg>, SimpleError> {
-    let ret = match exchange {
+) -> Result<Vec<OrderBookMsg>, ParseError> {
+    let ret = catch_panic(|| match exchange {
         "binance" => exchanges::binance::parse_l2_topk(market_type, msg, received_at),
         "bitget" => exchanges::bitget::parse_l2_topk(market_type, msg),
         "bitmex" => exchanges::bitmex::parse_l2_topk(market_type, msg),
@@ -180,32 +189,54 @@ pub fn parse_l2_topk(
         "mexc" => exchanges::mexc::parse_l2_topk(market_type, msg, received_at),
         "okx" => exchanges::okx::parse_l2_topk(market_type, msg),
         "zb" => exchanges::zb::parse_l2_topk(market_type, msg),
-        _ => Err(SimpleError::new(format!("Unknown exchange {exchange}"))),
-    };
+        _ => Err(unsupported_exchange(exchange, "level2 top-k snapshots")),
+    });
     match ret {
         Ok(mut orderbooks) => {
             for orderbook in orderbooks.iter_mut() {
                 if orderbook.snapshot {
                     // sorted in ascending order by price
-                    orderbook.asks.sort_by(|a, b| a.price.partial_cmp(&b.price).unwrap());
+                    orderbook.asks.sort_by(|a, b| a.price.total_cmp(&b.price));
                     // sorted in descending order by price
-                    orderbook.bids.sort_by(|a, b| b.price.partial_cmp(&a.price).unwrap());
+                    orderbook.bids.sort_by(|a, b| b.price.total_cmp(&a.price));
                 }
             }
             Ok(orderbooks)
         }
-        Err(_) => ret,
+        Err(err) => Err(err.with_context(exchange, market_type, msg)),
     }
 }
 
+/// Parse level3 orderbook messages.
+///
+/// Each message carries one order, received orders and market orders which
+/// never rest on the book are skipped.
+pub fn parse_l3(
+    exchange: &str,
+    market_type: MarketType,
+    msg: &str,
+    received_at: Option<i64>,
+) -> Result<Vec<L3Msg>, ParseError> {
+    let ret = catch_panic(|| match exchange {
+        "bitfinex" => received_at
+            .ok_or_else(ParseError::missing_timestamp)
+            .and_then(|received_at| exchanges::bitfinex::parse_l3(market_type, msg, received_at)),
+        "bitstamp" => exchanges::bitstamp::parse_l3(market_type, msg),
+        "coinbase_pro" => exchanges::coinbase_pro::parse_l3(market_type, msg),
+        "kucoin" => exchanges::kucoin::parse_l3(market_type, msg),
+        _ => Err(unsupported_exchange(exchange, "level3 messages")),
+    });
+    ret.map_err(|err| err.with_context(exchange, market_type, msg))
+}
+
 /// Parse BBO(best bid&offer) messages.
 pub fn parse_bbo(
     exchange: &str,
     market_type: MarketType,
     msg: &str,
     received_at: Option<i64>,
-) -> Result<Vec<BboMsg>, SimpleError> {
-    match exchange {
+) -> Result<Vec<BboMsg>, ParseError> {
+    let ret = catch_panic(|| match exchange {
         "binance" => exchanges::binance::parse_bbo(market_type, msg, received_at),
         "bitmex" => exchanges::bitmex::parse_bbo(market_type, msg),
         "deribit" => exchanges::deribit::parse_bbo(market_type, msg),
@@ -215,71 +246,244 @@ pub fn parse_bbo(
         "kraken" => exchanges::kraken::parse_bbo(market_type, msg, received_at),
         "kucoin" => exchanges::kucoin::parse_bbo(market_type, msg),
         "okx" => exchanges::okx::parse_bbo(market_type, msg),
-        _ => Err(SimpleError::new(format!("Unknown exchange {exchange}"))),
-    }
+        _ => Err(unsupported_exchange(exchange, "BBO messages")),
+    });
+    ret.map_err(|err| err.with_context(exchange, market_type, msg))
 }
 
 /// Parse funding rate messages.
+///
+/// `received_at` is required by messages without a timestamp, i.e., bitmex and
+/// okx websocket messages, as well as RESTful contracts of gate and kucoin and
+/// markets of dydx.
 pub fn parse_funding_rate(
     exchange: &str,
     market_type: MarketType,
     msg: &str,
     received_at: Option<i64>,
-) -> Result<Vec<FundingRateMsg>, SimpleError> {
+) -> Result<Vec<FundingRateMsg>, ParseError> {
     if market_type != MarketType::InverseSwap
         && market_type != MarketType::LinearSwap
         && market_type != MarketType::QuantoSwap
         && market_type != MarketType::Unknown
     {
-        return Err(SimpleError::new(
+        return Err(ParseError::unsupported(
             "Only InverseSwap, LinearSwap and QuantoSwap markets have funding rate.",
-        ));
+        )
+        .with_context(exchange, market_type, msg));
     }
-    match exchange {
+    let ret = catch_panic(|| match exchange {
         "binance" => exchanges::binance::parse_funding_rate(market_type, msg),
+        "bitfinex" => exchanges::bitfinex::parse_funding_rate(market_type, msg),
         "bitget" => exchanges::bitget::parse_funding_rate(market_type, msg),
-        "bitmex" => exchanges::bitmex::parse_funding_rate(
-            market_type,
-            msg,
-            received_at.expect("BitMEX funding rate messages don't have timestamp"),
-        ),
+        "bitmex" => received_at.ok_or_else(ParseError::missing_timestamp).and_then(|received_at| {
+            exchanges::bitmex::parse_funding_rate(market_type, msg, received_at)
+        }),
+        "bybit" => exchanges::bybit::parse_funding_rate(market_type, msg),
+        "deribit" => exchanges::deribit::parse_funding_rate(market_type, msg),
+        "dydx" => exchanges::dydx::parse_funding_rate(market_type, msg, received_at),
+        "gate" => exchanges::gate::parse_funding_rate(market_type, msg, received_at),
         "huobi" => exchanges::huobi::parse_funding_rate(market_type, msg),
-        "okex" | "okx" => exchanges::okx::parse_funding_rate(
-            market_type,
-            msg,
-            received_at.expect("OKX funding rate messages don't have timestamp"),
-        ),
-        _ => Err(SimpleError::new(format!("{exchange} does NOT have perpetual swap market"))),
-    }
+        "kraken" => exchanges::kraken::parse_funding_rate(market_type, msg),
+        "kucoin" => exchanges::kucoin::parse_funding_rate(market_type, msg, received_at),
+        "mexc" => exchanges::mexc::parse_funding_rate(market_type, msg),
+        "okex" | "okx" => {
+            received_at.ok_or_else(ParseError::missing_timestamp).and_then(|received_at| {
+                exchanges::okx::parse_funding_rate(market_type, msg, received_at)
+            })
+        }
+        "zbg" => exchanges::zbg::parse_funding_rate(market_type, msg),
+        _ => Err(unsupported_exchange(exchange, "perpetual swap market")),
+    });
+    ret.map_err(|err| err.with_context(exchange, market_type, msg))
+}
+
+/// Parse liquidation messages.
+///
+/// Supported channels are binance `forceOrder`, bitmex `liquidation` (needs
+/// `received_at`), bybit `liquidation`, okx v5 `liquidation-orders` and ftx
+/// `trades`, of which only liquidation trades are returned.
+pub fn parse_liquidation(
+    exchange: &str,
+    market_type: MarketType,
+    msg: &str,
+    received_at: Option<i64>,
+) -> Result<Vec<LiquidationMsg>, ParseError> {
+    let ret = catch_panic(|| match exchange {
+        "binance" => exchanges::binance::parse_liquidation(market_type, msg),
+        "bitmex" => received_at.ok_or_else(ParseError::missing_timestamp).and_then(|received_at| {
+            exchanges::bitmex::parse_liquidation(market_type, msg, received_at)
+        }),
+        "bybit" => exchanges::bybit::parse_liquidation(market_type, msg),
+        "ftx" => exchanges::ftx::parse_liquidation(market_type, msg),
+        "okex" | "okx" => exchanges::okx::parse_liquidation(market_type, msg),
+        _ => Err(unsupported_exchange(exchange, "liquidation messages")),
+    });
+    ret.map_err(|err| err.with_context(exchange, market_type, msg))
+}
+
+/// Parse mark price messages.
+///
+/// Supported channels are binance `markPrice`, bitmex `instrument`, okx v5
+/// `mark-price` and deribit `markprice.options`. BitMEX instruments without a
+/// timestamp need `received_at`.
+pub fn parse_mark_price(
+    exchange: &str,
+    market_type: MarketType,
+    msg: &str,
+    received_at: Option<i64>,
+) -> Result<Vec<MarkPriceMsg>, ParseError> {
+    let ret = catch_panic(|| match exchange {
+        "binance" => exchanges::binance::parse_mark_price(market_type, msg),
+        "bitmex" => exchanges::bitmex::parse_mark_price(market_type, msg, received_at),
+        "deribit" => exchanges::deribit::parse_mark_price(market_type, msg),
+        "okex" | "okx" => exchanges::okx::parse_mark_price(market_type, msg),
+        _ => Err(unsupported_exchange(exchange, "mark price messages")),
+    });
+    ret.map_err(|err| err.with_context(exchange, market_type, msg))
+}
+
+/// Parse index price messages.
+///
+/// Supported channels are binance `markPrice`, bitmex `instrument`
+/// (`indicativeSettlePrice`), okx v5 `index-tickers` and deribit
+/// `deribit_price_index`. BitMEX instruments without a timestamp need
+/// `received_at`.
+pub fn parse_index_price(
+    exchange: &str,
+    market_type: MarketType,
+    msg: &str,
+    received_at: Option<i64>,
+) -> Result<Vec<IndexPriceMsg>, ParseError> {
+    let ret = catch_panic(|| match exchange {
+        "binance" => exchanges::binance::parse_index_price(market_type, msg),
+        "bitmex" => exchanges::bitmex::parse_index_price(market_type, msg, received_at),
+        "deribit" => exchanges::deribit::parse_index_price(market_type, msg),
+        "okex" | "okx" => exchanges::okx::parse_index_price(market_type, msg),
+        _ => Err(unsupported_exchange(exchange, "index price messages")),
+    });
+    ret.map_err(|err| err.with_context(exchange, market_type, msg))
+}
+
+/// Parse option tickers with implied volatilities and greeks.
+///
+/// Supported channels are deribit `ticker`, okx v5 `opt-summary` and binance
+/// options `ticker`.
+pub fn parse_option_ticker(
+    exchange: &str,
+    market_type: MarketType,
+    msg: &str,
+) -> Result<Vec<OptionTickerMsg>, ParseError> {
+    let ret = catch_panic(|| {
+        if market_type != MarketType::EuropeanOption {
+            return Err(ParseError::unsupported("Only option markets have greeks"));
+        }
+        match exchange {
+            "binance" => exchanges::binance::parse_option_ticker(msg),
+            "deribit" => exchanges::deribit::parse_option_ticker(msg),
+            "okex" | "okx" => exchanges::okx::parse_option_ticker(msg),
+            _ => Err(unsupported_exchange(exchange, "option tickers")),
+        }
+    });
+    ret.map_err(|err| err.with_context(exchange, market_type, msg))
+}
+
+/// Parse open interest messages.
+///
+/// Gate contract stats have no symbol in the message, so `symbol` is required
+/// for gate and ignored by other exchanges. Values in base or quote coins are
+/// `None` if the message has no price to convert contracts.
+pub fn parse_open_interest(
+    exchange: &str,
+    market_type: MarketType,
+    msg: &str,
+    symbol: Option<&str>,
+    received_at: Option<i64>,
+) -> Result<Vec<OpenInterestMsg>, ParseError> {
+    let ret = catch_panic(|| match exchange {
+        "binance" => exchanges::binance::parse_open_interest(market_type, msg),
+        "bitmex" => exchanges::bitmex::parse_open_interest(market_type, msg, received_at),
+        "bybit" => exchanges::bybit::parse_open_interest(market_type, msg),
+        "deribit" => exchanges::deribit::parse_open_interest(market_type, msg),
+        "gate" => exchanges::gate::parse_open_interest(market_type, msg, symbol),
+        "huobi" => exchanges::huobi::parse_open_interest(market_type, msg),
+        "okex" | "okx" => exchanges::okx::parse_open_interest(market_type, msg),
+        _ => Err(unsupported_exchange(exchange, "open interest messages")),
+    });
+    ret.map_err(|err| err.with_context(exchange, market_type, msg))
 }
 
+/// Parse 24hr rolling window ticker messages.
+///
+/// Multi-symbol messages, e.g., binance `!ticker@arr`, are expanded into one
+/// `TickerMsg` per symbol. Bitfinex, kraken and zbg spot tickers have no
+/// timestamp, so `received_at` is required for them.
+pub fn parse_ticker(
+    exchange: &str,
+    market_type: MarketType,
+    msg: &str,
+    received_at: Option<i64>,
+) -> Result<Vec<TickerMsg>, ParseError> {
+    let ret = catch_panic(|| match exchange {
+        "binance" => exchanges::binance::parse_ticker(market_type, msg),
+        "bitfinex" => exchanges::bitfinex::parse_ticker(market_type, msg, received_at),
+        "bitget" => exchanges::bitget::parse_ticker(market_type, msg),
+        "bithumb" => exchanges::bithumb::parse_ticker(market_type, msg),
+        "bitz" => exchanges::bitz::parse_ticker(market_type, msg),
+        "bybit" => exchanges::bybit::parse_ticker(market_type, msg),
+        "coinbase_pro" => exchanges::coinbase_pro::parse_ticker(market_type, msg),
+        "deribit" => exchanges::deribit::parse_ticker(market_type, msg),
+        "ftx" => exchanges::ftx::parse_ticker(market_type, msg),
+        "gate" => exchanges::gate::parse_ticker(market_type, msg),
+        "huobi" => exchanges::huobi::parse_ticker(market_type, msg),
+        "kraken" => exchanges::kraken::parse_ticker(market_type, msg, received_at),
+        "kucoin" => exchanges::kucoin::parse_ticker(market_type, msg),
+        "mxc" | "mexc" => exchanges::mexc::parse_ticker(market_type, msg),
+        "okex" | "okx" => exchanges::okx::parse_ticker(market_type, msg),
+        "zb" => exchanges::zb::parse_ticker(market_type, msg),
+        "zbg" => exchanges::zbg::parse_ticker(market_type, msg, received_at),
+        _ => Err(unsupported_exchange(exchange, "ticker messages")),
+    });
+    ret.map_err(|err| err.with_context(exchange, market_type, msg))
+}
+
+/// Parse candlestick messages.
+///
+/// RESTful candles of Coinbase Pro, Bithumb and FTX have no symbol in the
+/// message, so `symbol` is required for them and ignored by other exchanges.
 pub fn parse_candlestick(
     exchange: &str,
     market_type: MarketType,
     msg: &str,
+    symbol: Option<&str>,
     received_at: Option<i64>,
-) -> Result<Vec<CandlestickMsg>, SimpleError> {
-    match exchange {
+) -> Result<Vec<CandlestickMsg>, ParseError> {
+    let ret = catch_panic(|| match exchange {
         "binance" => exchanges::binance::parse_candlestick(market_type, msg),
         "bitfinex" => exchanges::bitfinex::parse_candlestick(market_type, msg),
         "bitget" => exchanges::bitget::parse_candlestick(market_type, msg),
+        "bithumb" => exchanges::bithumb::parse_candlestick(market_type, msg, symbol),
         "bitmex" => exchanges::bitmex::parse_candlestick(market_type, msg),
+        "bitstamp" => exchanges::bitstamp::parse_candlestick(market_type, msg),
+        "bitz" => exchanges::bitz::parse_candlestick(market_type, msg),
         "bybit" => exchanges::bybit::parse_candlestick(market_type, msg),
+        "coinbase_pro" => exchanges::coinbase_pro::parse_candlestick(market_type, msg, symbol),
         "deribit" => exchanges::deribit::parse_candlestick(market_type, msg),
+        "dydx" => exchanges::dydx::parse_candlestick(market_type, msg),
+        "ftx" => exchanges::ftx::parse_candlestick(market_type, msg, symbol),
         "gate" => exchanges::gate::parse_candlestick(market_type, msg),
         "huobi" => exchanges::huobi::parse_candlestick(market_type, msg),
         "kraken" => exchanges::kraken::parse_candlestick(market_type, msg),
         "kucoin" => exchanges::kucoin::parse_candlestick(market_type, msg),
         "mexc" => exchanges::mexc::parse_candlestick(market_type, msg, received_at),
-        "okx" => exchanges::okx::parse_candlestick(
-            market_type,
-            msg,
-            received_at.expect("OKX candlestick messages don't have timestamp"),
-        ),
+        "okx" => received_at.ok_or_else(ParseError::missing_timestamp).and_then(|received_at| {
+            exchanges::okx::parse_candlestick(market_type, msg, received_at)
+        }),
         "zb" => exchanges::zb::parse_candlestick(market_type, msg),
         "zbg" => exchanges::zbg::parse_candlestick(market_type, msg),
-        _ => Err(SimpleError::new(format!("Unknown exchange {exchange}"))),
-    }
+        _ => Err(unsupported_exchange(exchange, "candlestick messages")),
+    });
+    ret.map_err(|err| err.with_context(exchange, market_type, msg))
 }
 
 /// Parse level2 snapshot orderbook messages.
@@ -289,41 +493,260 @@ pub fn parse_l2_snapshot(
     msg: &str,
     symbol: Option<&str>,
     received_at: Option<i64>,
-) -> Result<Vec<OrderBookMsg>, SimpleError> {
-    let ret = match exchange {
+) -> Result<Vec<OrderBookMsg>, ParseError> {
+    let ret = catch_panic(|| match exchange {
         "binance" => exchanges::binance::parse_l2_snapshot(market_type, msg, symbol, received_at),
         "bitfinex" => exchanges::bitfinex::parse_l2_snapshot(market_type, msg, symbol, received_at),
         "bitget" => exchanges::bitget::parse_l2_snapshot(market_type, msg, symbol),
-        _ => Err(SimpleError::new(format!("Unknown exchange {exchange}"))),
-    };
+        _ => Err(unsupported_exchange(exchange, "level2 snapshots")),
+    });
     match ret {
         Ok(mut orderbooks) => {
             for orderbook in orderbooks.iter_mut() {
                 if orderbook.snapshot {
                     // sorted in ascending order by price
-                    orderbook.asks.sort_by(|a, b| a.price.partial_cmp(&b.price).unwrap());
+                    orderbook.asks.sort_by(|a, b| a.price.total_cmp(&b.price));
                     // sorted in descending order by price
-                    orderbook.bids.sort_by(|a, b| b.price.partial_cmp(&a.price).unwrap());
+                    orderbook.bids.sort_by(|a, b| b.price.total_cmp(&a.price));
                 }
             }
             Ok(orderbooks)
         }
-        Err(_) => ret,
+        Err(err) => Err(err.with_context(exchange, market_type, msg)),
     }
 }
 
 /// Infer the message type from the message.
 pub fn get_msg_type(exchange: &str, msg: &str) -> MessageType {
-    match exchange {
+    std::panic::catch_unwind(|| match exchange {
         "binance" => exchanges::binance::get_msg_type(msg),
+        "bitfinex" => exchanges::bitfinex::get_msg_type(msg),
         "bitget" => exchanges::bitget::get_msg_type(msg),
+        "bithumb" => exchanges::bithumb::get_msg_type(msg),
         "bitmex" => exchanges::bitmex::get_msg_type(msg),
+        "bitstamp" => exchanges::bitstamp::get_msg_type(msg),
+        "bitz" => exchanges::bitz::get_msg_type(msg),
         "bybit" => exchanges::bybit::get_msg_type(msg),
+        "coinbase_pro" => exchanges::coinbase_pro::get_msg_type(msg),
         "deribit" => exchanges::deribit::get_msg_type(msg),
+        "dydx" => exchanges::dydx::get_msg_type(msg),
         "ftx" => exchanges::ftx::get_msg_type(msg),
+        "gate" => exchanges::gate::get_msg_type(msg),
         "huobi" => exchanges::huobi::get_msg_type(msg),
         "kraken" => exchanges::kraken::get_msg_type(msg),
+        "kucoin" => exchanges::kucoin::get_msg_type(msg),
+        "mxc" | "mexc" => exchanges::mexc::get_msg_type(msg),
         "okex" | "okx" => exchanges::okx::get_msg_type(msg),
+        "zb" => exchanges::zb::get_msg_type(msg),
+        "zbg" => exchanges::zbg::get_msg_type(msg),
         _ => MessageType::Other,
+    })
+    .unwrap_or(MessageType::Other)
+}
+
+/// Parse a message of any type.
+///
+/// The message type is inferred by `get_msg_type()`, then the message is
+/// dispatched to the corresponding `parse_*` function. Heartbeats and
+/// subscription responses produce an empty vector, so that a raw websocket
+/// stream can be consumed with one call, any other message that can't be
+/// parsed is an error.
+///
+/// REST snapshots that don't carry a symbol, e.g., Binance depth snapshots,
+/// need to go through `parse_l2_snapshot()` with an explicit symbol, the same
+/// goes for `parse_candlestick()`.
+pub fn parse(
+    exchange: &str,
+    market_type: MarketType,
+    msg: &str,
+    received_at: Option<i64>,
+) -> Result<Vec<Message>, ParseError> {
+    if !is_supported(exchange) {
+        return Err(ParseError::unknown_exchange(exchange));
+    }
+    // not every parse_* function accepts the legacy names
+    let exchange = match exchange {
+        "mxc" => "mexc",
+        "okex" => "okx",
+        _ => exchange,
+    };
+    let messages = match get_msg_type(exchange, msg) {
+        MessageType::Trade => {
+            parse_trade(exchange, market_type, msg)?.into_iter().map(Message::Trade).collect()
+        }
+        MessageType::L2Event => parse_l2(exchange, market_type, msg, received_at)?
+            .into_iter()
+            .map(Message::Level2)
+            .collect(),
+        MessageType::L2TopK => parse_l2_topk(exchange, market_type, msg, received_at)?
+            .into_iter()
+            .map(Message::Level2)
+            .collect(),
+        MessageType::L2Snapshot => {
+            parse_l2_snapshot(exchange, market_type, msg, None, received_at)?
+                .into_iter()
+                .map(Message::Level2)
+                .collect()
+        }
+        MessageType::L3Event | MessageType::L3Snapshot => {
+            parse_l3(exchange, market_type, msg, received_at)?
+                .into_iter()
+                .map(Message::Level3)
+                .collect()
+        }
+        MessageType::BBO => parse_bbo(exchange, market_type, msg, received_at)?
+            .into_iter()
+            .map(Message::Bbo)
+            .collect(),
+        MessageType::FundingRate => parse_funding_rate(exchange, market_type, msg, received_at)?
+            .into_iter()
+            .map(Message::FundingRate)
+            .collect(),
+        MessageType::Candlestick => {
+            parse_candlestick(exchange, market_type, msg, None, received_at)?
+                .into_iter()
+                .map(Message::Candlestick)
+                .collect()
+        }
+        MessageType::Ticker => parse_ticker(exchange, market_type, msg, received_at)?
+            .into_iter()
+            .map(Message::Ticker)
+            .collect(),
+        MessageType::OpenInterest => {
+            parse_open_interest(exchange, market_type, msg, None, received_at)?
+                .into_iter()
+                .map(Message::OpenInterest)
+                .collect()
+        }
+        MessageType::Other => parse_other(exchange, market_type, msg, received_at)?,
+        msg_type => {
+            return Err(ParseError::unsupported(format!("Unsupported message type {msg_type}"))
+                .with_context(exchange, market_type, msg));
+        }
+    };
+    Ok(messages)
+}
+
+// MessageType has no variants for liquidations, mark prices, index prices and
+// option tickers, so get_msg_type() returns Other for them. Each of these
+// parsers rejects messages of other channels, the first one that accepts the
+// message wins.
+fn parse_other(
+    exchange: &str,
+    market_type: MarketType,
+    msg: &str,
+    received_at: Option<i64>,
+) -> Result<Vec<Message>, ParseError> {
+    let value = serde_json::from_str::<serde_json::Value>(msg)
+        .map_err(|err| ParseError::from(err).with_context(exchange, market_type, msg))?;
+    if is_control_message(&value) {
+        return Ok(Vec::new());
+    }
+    if let Ok(liquidations) = parse_liquidation(exchange, market_type, msg, received_at) {
+        return Ok(liquidations.into_iter().map(Message::Liquidation).collect());
     }
+    if let Ok(mark_prices) = parse_mark_price(exchange, market_type, msg, received_at) {
+        return Ok(mark_prices.into_iter().map(Message::MarkPrice).collect());
+    }
+    if let Ok(index_prices) = parse_index_price(exchange, market_type, msg, received_at) {
+        return Ok(index_prices.into_iter().map(Message::IndexPrice).collect());
+    }
+    if let Ok(option_tickers) = parse_option_ticker(exchange, market_type, msg) {
+        return Ok(option_tickers.into_iter().map(Message::OptionTicker).collect());
+    }
+    Err(ParseError::unsupported("Unknown message type").with_context(exchange, market_type, msg))
+}
+
+// Heartbeats, pings and subscription responses, which carry no market data.
+fn is_control_message(value: &serde_json::Value) -> bool {
+    const EVENTS: &[&str] = &[
+        "ack",
+        "bts:heartbeat",
+        "bts:subscription_succeeded",
+        "connected",
+        "heartbeat",
+        "info",
+        "ping",
+        "pong",
+        "subscribe",
+        "subscribed",
+        "subscriptionStatus",
+        "subscriptions",
+        "systemStatus",
+        "unsubscribe",
+        "unsubscribed",
+        "welcome",
+    ];
+    match value {
+        // bitfinex heartbeat, e.g., [17470,"hb"]
+        serde_json::Value::Array(arr) => arr.len() == 2 && arr[1].as_str() == Some("hb"),
+        serde_json::Value::Object(obj) => {
+            let is_event = ["event", "type", "op", "feed", "method"].iter().any(|key| {
+                obj.get(*key).and_then(|x| x.as_str()).is_some_and(|x| EVENTS.contains(&x))
+            });
+            // binance {"result":null,"id":1}, deribit {"id":1,"result":["trades.BTC-PERPETUAL.raw"]}
+            // and gate {"error":null,"result":{"status":"success"},"id":1}
+            let is_response = obj.contains_key("id")
+                && obj.get("result").is_some_and(|result| {
+                    result.is_null()
+                        || result.as_array().is_some_and(|arr| arr.iter().all(|x| x.is_string()))
+                        || result["status"].as_str() == Some("success")
+                });
+            // bitmex {"success":true,"subscribe":"trade:XBTUSD"} and bybit {"success":true,"ret_msg":""},
+            // but not failures like {"success":false,"error":"Unknown table: foo"}
+            let is_success = obj.get("success").and_then(|x| x.as_bool()) == Some(true)
+                && !obj.contains_key("data");
+            // huobi {"ping":1492420473027} and {"id":"id1","status":"ok","subbed":"market.btcusdt.kline.1min"}
+            let is_huobi = obj.contains_key("ping")
+                || obj.contains_key("pong")
+                || obj.contains_key("subbed")
+                || obj.contains_key("unsubbed");
+            // gate {"channel":"spot.pong"} and mexc {"channel":"rs.sub.deal","data":"success"}
+            let is_channel_response = obj
+                .get("channel")
+                .and_then(|x| x.as_str())
+                .is_some_and(|channel| channel.ends_with("pong") || channel.starts_with("rs."));
+            // bitmex welcome message {"info":"Welcome to the BitMEX Realtime API.",...}
+            let is_welcome = obj.contains_key("info") && obj.contains_key("version");
+            is_event || is_response || is_success || is_huobi || is_channel_response || is_welcome
+        }
+        _ => false,
+    }
+}
+
+// Known exchanges without the channel are unsupported rather than unknown.
+fn unsupported_exchange(exchange: &str, channel: &str) -> ParseError {
+    if is_supported(exchange) {
+        ParseError::unsupported(format!("{exchange} does NOT have {channel}"))
+    } else {
+        ParseError::unknown_exchange(exchange)
+    }
+}
+
+fn is_supported(exchange: &str) -> bool {
+    matches!(
+        exchange,
+        "binance"
+            | "bitfinex"
+            | "bitget"
+            | "bithumb"
+            | "bitmex"
+            | "bitstamp"
+            | "bitz"
+            | "bybit"
+            | "coinbase_pro"
+            | "deribit"
+            | "dydx"
+            | "ftx"
+            | "gate"
+            | "huobi"
+            | "kraken"
+            | "kucoin"
+            | "mxc"
+            | "mexc"
+            | "okex"
+            | "okx"
+            | "zb"
+            | "zbg"
+    )
 }
@@ -129,6 +129,9 @@ mod trade {
         assert_eq!(trade.quantity_base, 0.043);
         assert_eq!(trade.quantity_quote, 0.043 * 58665.00);
         assert_eq!(trade.quantity_contract, Some(0.043));
+        // exact decimals keep the digits binance sent, including trailing zeros
+        assert_eq!("58665.00", trade.price_exact.unwrap().to_string());
+        assert_eq!("0.043", trade.quantity_exact.unwrap().to_string());
 
         assert_eq!(trade.side, TradeSide::Buy);
     }
@@ -280,6 +283,134 @@ mod funding_rate {
     }
 }
 
+#[cfg(test)]
+mod liquidation {
+    use super::EXCHANGE_NAME;
+    use crypto_market_type::MarketType;
+    use crypto_message::TradeSide;
+    use crypto_msg_parser::{parse_liquidation, round};
+
+    #[test]
+    fn inverse_swap() {
+        let raw_msg = r#"{"stream":"btcusd_perp@forceOrder","data":{"e":"forceOrder","E":1591154240950,"o":{"s":"BTCUSD_PERP","ps":"BTCUSD","S":"BUY","o":"LIMIT","f":"IOC","q":"2","p":"9425.5","ap":"9496.5","X":"FILLED","l":"2","z":"2","T":1591154240949}}}"#;
+        let liquidations =
+            &parse_liquidation(EXCHANGE_NAME, MarketType::InverseSwap, raw_msg, None).unwrap();
+
+        assert_eq!(liquidations.len(), 1);
+        let liquidation = &liquidations[0];
+        crate::utils::check_liquidation_fields(
+            EXCHANGE_NAME,
+            MarketType::InverseSwap,
+            "BTC/USD".to_string(),
+            liquidation,
+        );
+
+        assert_eq!(liquidation.symbol, "BTCUSD_PERP");
+        assert_eq!(liquidation.timestamp, 1591154240949);
+        assert_eq!(liquidation.side, TradeSide::Buy);
+        assert_eq!(liquidation.price, 9496.5);
+        assert_eq!(liquidation.quantity_contract, Some(2.0));
+        assert_eq!(liquidation.quantity_quote, 200.0);
+        assert_eq!(round(liquidation.quantity_base), round(200.0 / 9496.5));
+    }
+
+    #[test]
+    fn linear_swap() {
+        let raw_msg = r#"{"stream":"btcusdt@forceOrder","data":{"e":"forceOrder","E":1568014460893,"o":{"s":"BTCUSDT","S":"SELL","o":"LIMIT","f":"IOC","q":"0.014","p":"9910","ap":"0","X":"NEW","l":"0","z":"0","T":1568014460893}}}"#;
+        let liquidations =
+            &parse_liquidation(EXCHANGE_NAME, MarketType::LinearSwap, raw_msg, None).unwrap();
+
+        assert_eq!(liquidations.len(), 1);
+        let liquidation = &liquidations[0];
+        crate::utils::check_liquidation_fields(
+            EXCHANGE_NAME,
+            MarketType::LinearSwap,
+            "BTC/USDT".to_string(),
+            liquidation,
+        );
+
+        // falls back to the order price and quantity before the order is filled
+        assert_eq!(liquidation.side, TradeSide::Sell);
+        assert_eq!(liquidation.price, 9910.0);
+        assert_eq!(liquidation.quantity_base, 0.014);
+        assert_eq!(liquidation.quantity_quote, round(9910.0 * 0.014));
+        assert_eq!(liquidation.quantity_contract, Some(0.014));
+    }
+
+    #[test]
+    fn spot() {
+        let raw_msg = r#"{"stream":"btcusdt@forceOrder","data":{"e":"forceOrder","E":1568014460893,"o":{"s":"BTCUSDT","S":"SELL","o":"LIMIT","f":"IOC","q":"0.014","p":"9910","ap":"9910","X":"FILLED","l":"0.014","z":"0.014","T":1568014460893}}}"#;
+        assert!(parse_liquidation(EXCHANGE_NAME, MarketType::Spot, raw_msg, None).is_err());
+    }
+}
+
+#[cfg(test)]
+mod mark_price {
+    use super::EXCHANGE_NAME;
+    use crypto_market_type::MarketType;
+    use crypto_msg_parser::parse_mark_price;
+
+    #[test]
+    fn inverse_swap() {
+        let raw_msg = r#"{"stream":"btcusd_perp@markPrice","data":{"e":"markPriceUpdate","E":1617309477000,"s":"BTCUSD_PERP","p":"59012.56007222","P":"58896.00503145","r":"0.00073689","T":1617321600000}}"#;
+        let mark_prices =
+            &parse_mark_price(EXCHANGE_NAME, MarketType::InverseSwap, raw_msg, None).unwrap();
+
+        assert_eq!(mark_prices.len(), 1);
+        assert_eq!(mark_prices[0].symbol, "BTCUSD_PERP");
+        assert_eq!(mark_prices[0].pair, "BTC/USD");
+        assert_eq!(mark_prices[0].timestamp, 1617309477000);
+        assert_eq!(mark_prices[0].price, 59012.56007222);
+        assert_eq!(mark_prices[0].json, raw_msg);
+    }
+
+    #[test]
+    fn linear_swap_all() {
+        let raw_msg = r#"{"stream":"!markPrice@arr","data":[{"e":"markPriceUpdate","E":1617309024002,"s":"BTCUSDT","p":"59022.53514719","P":"58902.34482833","i":"58936.68384000","r":"0.00058959","T":1617321600000},{"e":"markPriceUpdate","E":1617309024002,"s":"ETHUSDT","p":"1981.15704420","P":"1974.79557094","i":"1978.08197502","r":"0.00059142","T":1617321600000}]}"#;
+        let mark_prices =
+            &parse_mark_price(EXCHANGE_NAME, MarketType::LinearSwap, raw_msg, None).unwrap();
+
+        assert_eq!(mark_prices.len(), 2);
+        assert_eq!(mark_prices[0].pair, "BTC/USDT");
+        assert_eq!(mark_prices[0].price, 59022.53514719);
+        assert_eq!(mark_prices[1].pair, "ETH/USDT");
+        assert_eq!(mark_prices[1].timestamp, 1617309024002);
+        assert_eq!(mark_prices[1].price, 1981.15704420);
+
+        assert!(parse_mark_price(EXCHANGE_NAME, MarketType::Spot, raw_msg, None).is_err());
+    }
+}
+
+#[cfg(test)]
+mod index_price {
+    use super::EXCHANGE_NAME;
+    use crypto_market_type::MarketType;
+    use crypto_msg_parser::parse_index_price;
+
+    #[test]
+    fn inverse_swap() {
+        // no index price in this message
+        let raw_msg = r#"{"stream":"btcusd_perp@markPrice","data":{"e":"markPriceUpdate","E":1617309477000,"s":"BTCUSD_PERP","p":"59012.56007222","P":"58896.00503145","r":"0.00073689","T":1617321600000}}"#;
+        let index_prices =
+            &parse_index_price(EXCHANGE_NAME, MarketType::InverseSwap, raw_msg, None).unwrap();
+        assert!(index_prices.is_empty());
+    }
+
+    #[test]
+    fn linear_swap() {
+        let raw_msg = r#"{"stream":"btcusdt@markPrice","data":{"e":"markPriceUpdate","E":1617308820003,"s":"BTCUSDT","p":"58940.14924532","P":"58905.14663658","i":"58857.26693664","r":"0.00058455","T":1617321600000}}"#;
+        let index_prices =
+            &parse_index_price(EXCHANGE_NAME, MarketType::LinearSwap, raw_msg, None).unwrap();
+
+        assert_eq!(index_prices.len(), 1);
+        assert_eq!(index_prices[0].symbol, "BTCUSDT");
+        assert_eq!(index_prices[0].pair, "BTC/USDT");
+        assert_eq!(index_prices[0].timestamp, 1617308820003);
+        assert_eq!(index_prices[0].price, 58857.26693664);
+        assert_eq!(index_prices[0].json, raw_msg);
+    }
+}
+
 #[cfg(test)]
 mod l2_event {
     use super::EXCHANGE_NAME;
@@ -312,6 +443,7 @@ mod l2_event {
 
         assert_eq!(orderbook.timestamp, 1622363903670);
         assert_eq!(orderbook.seq_id, Some(11294093726));
+        assert_eq!(orderbook.prev_seq_id, Some(11294093709));
 
         assert_eq!(orderbook.bids[0].price, 35743.98);
         assert_eq!(orderbook.bids[0].quantity_base, 0.0);
@@ -989,7 +1121,7 @@ mod bbo {
 mod ticker {
     use super::EXCHANGE_NAME;
     use crypto_market_type::MarketType;
-    use crypto_msg_parser::{extract_symbol, extract_timestamp};
+    use crypto_msg_parser::{extract_symbol, extract_timestamp, parse_ticker};
 
     #[test]
     fn spot() {
@@ -1010,6 +1142,45 @@ mod ticker {
             1653812100450,
             extract_timestamp(EXCHANGE_NAME, MarketType::Spot, raw_msg).unwrap().unwrap()
         );
+
+        let tickers = &parse_ticker(EXCHANGE_NAME, MarketType::Spot, raw_msg, None).unwrap();
+        assert_eq!(1, tickers.len());
+        crate::utils::check_ticker_fields(
+            EXCHANGE_NAME,
+            MarketType::Spot,
+            "ETH/BTC".to_string(),
+            "ETHBTC".to_string(),
+            &tickers[0],
+            raw_msg,
+        );
+    }
+
+    #[test]
+    fn spot_all_tickers() {
+        let raw_msg = r#"{"stream":"!ticker@arr","data":[{"e":"24hrTicker","E":1653812100450,"s":"ETHBTC","p":"0.00031500","P":"0.515","w":"0.06150587","x":"0.06118800","c":"0.06150300","Q":"0.74000000","b":"0.06150300","B":"18.96220000","a":"0.06150400","A":"10.94010000","o":"0.06118800","h":"0.06221700","l":"0.06079900","v":"116854.45230000","q":"7187.23459814","O":1653725700257,"C":1653812100257,"F":342624389,"L":342741830,"n":117442},{"e":"24hrTicker","E":1653812100451,"s":"BTCUSDT","p":"213.70000000","P":"0.740","w":"28973.36000000","x":"28880.20000000","c":"29093.90000000","Q":"0.01700000","b":"29093.89000000","B":"1.20000000","a":"29093.90000000","A":"0.50000000","o":"28880.20000000","h":"29265.70000000","l":"28755.00000000","v":"43254.24100000","q":"1253257418.36000000","O":1653725700257,"C":1653812100257,"F":1390000000,"L":1391000000,"n":1000001}]}"#;
+
+        let tickers = &parse_ticker(EXCHANGE_NAME, MarketType::Spot, raw_msg, None).unwrap();
+        assert_eq!(2, tickers.len());
+        crate::utils::check_ticker_fields(
+            EXCHANGE_NAME,
+            MarketType::Spot,
+            "ETH/BTC".to_string(),
+            "ETHBTC".to_string(),
+            &tickers[0],
+            raw_msg,
+        );
+        crate::utils::check_ticker_fields(
+            EXCHANGE_NAME,
+            MarketType::Spot,
+            "BTC/USDT".to_string(),
+            "BTCUSDT".to_string(),
+            &tickers[1],
+            raw_msg,
+        );
+        assert_eq!(1653812100451, tickers[1].timestamp);
+        assert_eq!(29093.9, tickers[1].close);
+        assert_eq!(43254.241, tickers[1].volume);
+        assert_eq!(Some(29093.89), tickers[1].best_bid_price);
     }
 
     #[test]
@@ -1037,6 +1208,18 @@ mod ticker {
             1653814699950,
             extract_timestamp(EXCHANGE_NAME, MarketType::InverseFuture, raw_msg).unwrap().unwrap()
         );
+
+        let tickers =
+            &parse_ticker(EXCHANGE_NAME, MarketType::InverseFuture, raw_msg, None).unwrap();
+        assert_eq!(1, tickers.len());
+        crate::utils::check_ticker_fields(
+            EXCHANGE_NAME,
+            MarketType::InverseFuture,
+            "AVAX/USD".to_string(),
+            "AVAXUSD_PERP".to_string(),
+            &tickers[0],
+            raw_msg,
+        );
     }
 
     #[test]
@@ -1064,6 +1247,18 @@ mod ticker {
             1653813900353,
             extract_timestamp(EXCHANGE_NAME, MarketType::LinearFuture, raw_msg).unwrap().unwrap()
         );
+
+        let tickers =
+            &parse_ticker(EXCHANGE_NAME, MarketType::LinearFuture, raw_msg, None).unwrap();
+        assert_eq!(1, tickers.len());
+        crate::utils::check_ticker_fields(
+            EXCHANGE_NAME,
+            MarketType::LinearFuture,
+            "BTC/USDT".to_string(),
+            "BTCUSDT".to_string(),
+            &tickers[0],
+            raw_msg,
+        );
     }
 
     #[test]
@@ -1088,6 +1283,17 @@ mod ticker {
             1653814800360,
             extract_timestamp(EXCHANGE_NAME, MarketType::InverseSwap, raw_msg).unwrap().unwrap()
         );
+
+        let tickers = &parse_ticker(EXCHANGE_NAME, MarketType::InverseSwap, raw_msg, None).unwrap();
+        assert_eq!(1, tickers.len());
+        crate::utils::check_ticker_fields(
+            EXCHANGE_NAME,
+            MarketType::InverseSwap,
+            "APE/USD".to_string(),
+            "APEUSD_PERP".to_string(),
+            &tickers[0],
+            raw_msg,
+        );
     }
 
     #[test]
@@ -1112,6 +1318,17 @@ mod ticker {
             1653814800126,
             extract_timestamp(EXCHANGE_NAME, MarketType::LinearSwap, raw_msg).unwrap().unwrap()
         );
+
+        let tickers = &parse_ticker(EXCHANGE_NAME, MarketType::LinearSwap, raw_msg, None).unwrap();
+        assert_eq!(1, tickers.len());
+        crate::utils::check_ticker_fields(
+            EXCHANGE_NAME,
+            MarketType::LinearSwap,
+            "IOTX/USDT".to_string(),
+            "IOTXUSDT".to_string(),
+            &tickers[0],
+            raw_msg,
+        );
     }
 
     #[test]
@@ -1127,6 +1344,35 @@ mod ticker {
     }
 }
 
+#[cfg(test)]
+mod option_ticker {
+    use super::EXCHANGE_NAME;
+    use crypto_market_type::MarketType;
+    use crypto_msg_parser::parse_option_ticker;
+
+    #[test]
+    fn option() {
+        let raw_msg = r#"{"stream":"BTC-220624-30000-C@TICKER","data":{"e":"ticker","E":1654349111400,"s":"BTC-220624-30000-C","o":"1000","h":"1100","l":"950","c":"1050","V":"1.5","A":"1575","p":"0.05","Q":"0.1","F":1,"L":20,"n":20,"bo":"1040","ao":"0","bq":"2.3","aq":"0","b":"0.6521","a":"0","d":"0.4512","t":"-42.73","g":"0.00007","v":"36.28","vo":"0.6578","mp":"1050","hl":"2980","ll":"0.1","eep":"0"}}"#;
+        let tickers =
+            &parse_option_ticker(EXCHANGE_NAME, MarketType::EuropeanOption, raw_msg).unwrap();
+
+        assert_eq!(1, tickers.len());
+        let ticker = &tickers[0];
+        assert_eq!(ticker.symbol, "BTC-220624-30000-C");
+        assert_eq!(ticker.pair, "BTC/USDT");
+        assert_eq!(ticker.timestamp, 1654349111400);
+        assert_eq!(ticker.mark_iv, 0.6578);
+        assert_eq!(ticker.bid_iv, Some(0.6521));
+        // there is no ask
+        assert_eq!(ticker.ask_iv, None);
+        assert_eq!(ticker.delta, 0.4512);
+        assert_eq!(ticker.gamma, 0.00007);
+        assert_eq!(ticker.vega, 36.28);
+        assert_eq!(ticker.theta, -42.73);
+        assert_eq!(ticker.json, raw_msg);
+    }
+}
+
 #[cfg(test)]
 mod candlestick {
     use super::EXCHANGE_NAME;
@@ -1144,15 +1390,17 @@ mod candlestick {
             extract_timestamp(EXCHANGE_NAME, MarketType::Spot, raw_msg).unwrap().unwrap()
         );
 
-        let arr = parse_candlestick(EXCHANGE_NAME, MarketType::Spot, raw_msg, None).unwrap();
+        let arr = parse_candlestick(EXCHANGE_NAME, MarketType::Spot, raw_msg, None, None).unwrap();
         assert_eq!(1, arr.len());
         let candlestick_msg = &arr[0];
 
         assert_eq!("BTCUSDT", candlestick_msg.symbol);
         assert_eq!("BTC/USDT", candlestick_msg.pair);
         assert_eq!(1653818762502, candlestick_msg.timestamp);
-        assert_eq!(1653818760, candlestick_msg.begin_time);
-        assert_eq!("1m", candlestick_msg.period);
+        assert_eq!(1653818760000, candlestick_msg.begin_time);
+        assert_eq!(1653818820000, candlestick_msg.end_time);
+        assert_eq!("1m", candlestick_msg.period.to_string());
+        assert_eq!(Some(false), candlestick_msg.is_closed);
 
         assert_eq!(29038.46, candlestick_msg.open);
         assert_eq!(29038.47, candlestick_msg.high);
@@ -1176,15 +1424,15 @@ mod candlestick {
             extract_timestamp(EXCHANGE_NAME, MarketType::InverseFuture, raw_msg).unwrap().unwrap()
         );
 
-        let arr =
-            parse_candlestick(EXCHANGE_NAME, MarketType::InverseFuture, raw_msg, None).unwrap();
+        let arr = parse_candlestick(EXCHANGE_NAME, MarketType::InverseFuture, raw_msg, None, None)
+            .unwrap();
         assert_eq!(1, arr.len());
         let candlestick_msg = &arr[0];
 
         assert_eq!("BTCUSD_220624", candlestick_msg.symbol);
         assert_eq!(1653818854836, candlestick_msg.timestamp);
-        assert_eq!("1m", candlestick_msg.period);
-        assert_eq!(1653818820, candlestick_msg.begin_time);
+        assert_eq!("1m", candlestick_msg.period.to_string());
+        assert_eq!(1653818820000, candlestick_msg.begin_time);
 
         assert_eq!(29105.5, candlestick_msg.open);
         assert_eq!(29107.9, candlestick_msg.high);
@@ -1208,15 +1456,15 @@ mod candlestick {
             extract_timestamp(EXCHANGE_NAME, MarketType::LinearFuture, raw_msg).unwrap().unwrap()
         );
 
-        let arr =
-            parse_candlestick(EXCHANGE_NAME, MarketType::LinearFuture, raw_msg, None).unwrap();
+        let arr = parse_candlestick(EXCHANGE_NAME, MarketType::LinearFuture, raw_msg, None, None)
+            .unwrap();
         assert_eq!(1, arr.len());
         let candlestick_msg = &arr[0];
 
         assert_eq!("BTCUSDT_220624", candlestick_msg.symbol);
         assert_eq!(1653818905630, candlestick_msg.timestamp);
-        assert_eq!("1m", candlestick_msg.period);
-        assert_eq!(1653818880, candlestick_msg.begin_time);
+        assert_eq!("1m", candlestick_msg.period.to_string());
+        assert_eq!(1653818880000, candlestick_msg.begin_time);
 
         assert_eq!(29135.9, candlestick_msg.open);
         assert_eq!(29149.9, candlestick_msg.high);
@@ -1240,14 +1488,15 @@ mod candlestick {
             extract_timestamp(EXCHANGE_NAME, MarketType::InverseSwap, raw_msg).unwrap().unwrap()
         );
 
-        let arr = parse_candlestick(EXCHANGE_NAME, MarketType::InverseSwap, raw_msg, None).unwrap();
+        let arr =
+            parse_candlestick(EXCHANGE_NAME, MarketType::InverseSwap, raw_msg, None, None).unwrap();
         assert_eq!(1, arr.len());
         let candlestick_msg = &arr[0];
 
         assert_eq!("ETHUSD_PERP", candlestick_msg.symbol);
         assert_eq!(1653818962599, candlestick_msg.timestamp);
-        assert_eq!("1m", candlestick_msg.period);
-        assert_eq!(1653818940, candlestick_msg.begin_time);
+        assert_eq!("1m", candlestick_msg.period.to_string());
+        assert_eq!(1653818940000, candlestick_msg.begin_time);
 
         assert_eq!(1786.56, candlestick_msg.open);
         assert_eq!(1789.24, candlestick_msg.high);
@@ -1271,14 +1520,17 @@ mod candlestick {
             extract_timestamp(EXCHANGE_NAME, MarketType::LinearSwap, raw_msg).unwrap().unwrap()
         );
 
-        let arr = parse_candlestick(EXCHANGE_NAME, MarketType::LinearSwap, raw_msg, None).unwrap();
+        let arr =
+            parse_candlestick(EXCHANGE_NAME, MarketType::LinearSwap, raw_msg, None, None).unwrap();
         assert_eq!(1, arr.len());
         let candlestick_msg = &arr[0];
 
         assert_eq!("BTCUSDT", candlestick_msg.symbol);
         assert_eq!(1653819041520, candlestick_msg.timestamp);
-        assert_eq!("1M", candlestick_msg.period);
-        assert_eq!(1651363200, candlestick_msg.begin_time);
+        assert_eq!("1M", candlestick_msg.period.to_string());
+        assert_eq!(1651363200000, candlestick_msg.begin_time);
+        // 2022-05-01 to 2022-06-01
+        assert_eq!(1654041600000, candlestick_msg.end_time);
 
         assert_eq!(37614.40, candlestick_msg.open);
         assert_eq!(40071.70, candlestick_msg.high);
@@ -1299,7 +1551,7 @@ mod l2_snapshot {
     #[test]
     fn spot() {
         let raw_msg = r#"{"lastUpdateId":33933317943,"bids":[["23171.42000000","0.03000000"],["23171.16000000","0.00070000"],["23109.22000000","0.00050000"]],"asks":[["23171.68000000","0.00067000"],["23171.69000000","0.00047000"],["23224.58000000","0.04353000"]]}"#;
-        let received_at = Some(1677629076348);
+        let received_at = 1677629076348;
 
         assert_eq!("NONE", extract_symbol(EXCHANGE_NAME, MarketType::Spot, raw_msg).unwrap());
 
@@ -1310,7 +1562,7 @@ mod l2_snapshot {
             MarketType::Spot,
             raw_msg,
             Some("BTCUSDT"),
-            received_at,
+            Some(received_at),
         )
         .unwrap()[0];
 
@@ -1328,7 +1580,7 @@ mod l2_snapshot {
             raw_msg,
         );
 
-        assert_eq!(orderbook.timestamp, received_at.unwrap());
+        assert_eq!(orderbook.timestamp, received_at);
         assert_eq!(orderbook.seq_id, Some(33933317943));
         assert_eq!(orderbook.prev_seq_id, None);
 
@@ -1593,7 +1845,8 @@ mod l2_snapshot {
 mod open_interest {
     use super::EXCHANGE_NAME;
     use crypto_market_type::MarketType;
-    use crypto_msg_parser::{extract_symbol, extract_timestamp};
+    use crypto_msg_parser::{extract_symbol, extract_timestamp, get_msg_type, parse_open_interest};
+    use crypto_msg_type::MessageType;
 
     #[test]
     fn inverse_future() {
@@ -1608,6 +1861,16 @@ mod open_interest {
             1654336766113,
             extract_timestamp(EXCHANGE_NAME, MarketType::InverseFuture, raw_msg).unwrap().unwrap()
         );
+
+        let open_interests =
+            &parse_open_interest(EXCHANGE_NAME, MarketType::InverseFuture, raw_msg, None, None)
+                .unwrap();
+        assert_eq!(open_interests.len(), 1);
+        assert_eq!(open_interests[0].msg_type, MessageType::OpenInterest);
+        assert_eq!(open_interests[0].timestamp, 1654336766113);
+        assert_eq!(open_interests[0].quantity_contract, 2470927.0);
+        assert_eq!(open_interests[0].quantity_quote, Some(247092700.0));
+        assert_eq!(open_interests[0].quantity_base, None);
     }
 
     #[test]
@@ -1624,6 +1887,16 @@ mod open_interest {
             1654336785074,
             extract_timestamp(EXCHANGE_NAME, MarketType::LinearFuture, raw_msg).unwrap().unwrap()
         );
+
+        let open_interests =
+            &parse_open_interest(EXCHANGE_NAME, MarketType::LinearFuture, raw_msg, None, None)
+                .unwrap();
+        assert_eq!(open_interests.len(), 1);
+        assert_eq!(open_interests[0].msg_type, MessageType::OpenInterest);
+        assert_eq!(open_interests[0].timestamp, 1654336785074);
+        assert_eq!(open_interests[0].quantity_contract, 1275.028);
+        assert_eq!(open_interests[0].quantity_base, Some(1275.028));
+        assert_eq!(open_interests[0].quantity_quote, None);
     }
 
     #[test]
@@ -1639,6 +1912,15 @@ mod open_interest {
             1654336819740,
             extract_timestamp(EXCHANGE_NAME, MarketType::InverseSwap, raw_msg).unwrap().unwrap()
         );
+
+        let open_interests =
+            &parse_open_interest(EXCHANGE_NAME, MarketType::InverseSwap, raw_msg, None, None)
+                .unwrap();
+        assert_eq!(open_interests.len(), 1);
+        assert_eq!(open_interests[0].msg_type, MessageType::OpenInterest);
+        assert_eq!(open_interests[0].timestamp, 1654336819740);
+        assert_eq!(open_interests[0].quantity_contract, 5827897.0);
+        assert_eq!(open_interests[0].quantity_quote, Some(582789700.0));
     }
 
     #[test]
@@ -1654,5 +1936,242 @@ mod open_interest {
             1654336844754,
             extract_timestamp(EXCHANGE_NAME, MarketType::LinearSwap, raw_msg).unwrap().unwrap()
         );
+
+        let open_interests =
+            &parse_open_interest(EXCHANGE_NAME, MarketType::LinearSwap, raw_msg, None, None)
+                .unwrap();
+        assert_eq!(open_interests.len(), 1);
+        assert_eq!(open_interests[0].msg_type, MessageType::OpenInterest);
+        assert_eq!(open_interests[0].timestamp, 1654336844754);
+        assert_eq!(open_interests[0].quantity_contract, 84617.188);
+        assert_eq!(open_interests[0].quantity_base, Some(84617.188));
+        assert_eq!(MessageType::OpenInterest, get_msg_type(EXCHANGE_NAME, raw_msg));
+    }
+}
+
+#[cfg(test)]
+mod error {
+    use super::EXCHANGE_NAME;
+    use crypto_market_type::MarketType;
+    use crypto_msg_parser::{ParseError, parse_l3, parse_trade};
+
+    #[test]
+    fn malformed_json() {
+        let raw_msg = r#"{"stream":"btcusdt@aggTrade","data":{"e":"aggTrade""#;
+        let err = parse_trade(EXCHANGE_NAME, MarketType::Spot, raw_msg).unwrap_err();
+
+        assert!(matches!(err, ParseError::MalformedJson { .. }));
+        assert_eq!(EXCHANGE_NAME, err.exchange());
+        assert_eq!(MarketType::Spot, err.market_type());
+        assert_eq!(raw_msg, err.excerpt());
+    }
+
+    #[test]
+    fn unknown_exchange() {
+        let err = parse_trade("non-existent", MarketType::Spot, "{}").unwrap_err();
+        assert_eq!(ParseError::UnknownExchange { exchange: "non-existent".to_string() }, err);
+    }
+
+    #[test]
+    fn unsupported_channel() {
+        let err = parse_l3(EXCHANGE_NAME, MarketType::Spot, "{}", None).unwrap_err();
+        assert!(matches!(err, ParseError::UnsupportedMessage { .. }));
+        assert_eq!(EXCHANGE_NAME, err.exchange());
+
+        let err = parse_l3("non-existent", MarketType::Spot, "{}", None).unwrap_err();
+        assert_eq!(ParseError::UnknownExchange { exchange: "non-existent".to_string() }, err);
+    }
+}
+
+#[cfg(test)]
+mod parse {
+    use super::EXCHANGE_NAME;
+    use crypto_market_type::MarketType;
+    use crypto_message::Message;
+    use crypto_msg_parser::{ParseError, parse};
+    use crypto_msg_type::MessageType;
+
+    #[test]
+    fn trade() {
+        let raw_msg = r#"{"stream":"btcusdt@aggTrade","data":{"e":"aggTrade","E":1616176861895,"s":"BTCUSDT","a":640283266,"p":"58942.01000000","q":"0.00035600","f":716849523,"l":716849523,"T":1616176861893,"m":false,"M":true}}"#;
+        let messages = parse(EXCHANGE_NAME, MarketType::Spot, raw_msg, None).unwrap();
+
+        assert_eq!(1, messages.len());
+        assert_eq!(MessageType::Trade, messages[0].get_msg_type());
+        assert_eq!(1616176861895, messages[0].get_timestamp());
+        if let Message::Trade(trade) = &messages[0] {
+            assert_eq!("BTC/USDT", trade.pair);
+        } else {
+            panic!("expected a trade message");
+        }
+    }
+
+    #[test]
+    fn l2_event() {
+        let raw_msg = r#"{"stream":"btcusdt@depth@100ms","data":{"e":"depthUpdate","E":1622363903670,"s":"BTCUSDT","U":11294093710,"u":11294093726,"b":[["35743.98000000","0.00000000"],["35743.87000000","0.00001500"]],"a":[["35743.88000000","0.24000000"],["35743.97000000","0.00000000"]]}}"#;
+        let messages = parse(EXCHANGE_NAME, MarketType::Spot, raw_msg, None).unwrap();
+
+        assert_eq!(1, messages.len());
+        assert_eq!(MessageType::L2Event, messages[0].get_msg_type());
+        assert_eq!(1622363903670, messages[0].get_timestamp());
+        assert!(matches!(messages[0], Message::Level2(_)));
+    }
+
+    #[test]
+    fn subscription_response() {
+        let raw_msg = r#"{"result":null,"id":1}"#;
+        assert!(parse(EXCHANGE_NAME, MarketType::Spot, raw_msg, None).unwrap().is_empty());
+    }
+
+    #[test]
+    fn liquidation() {
+        let raw_msg = r#"{"stream":"btcusdt@forceOrder","data":{"e":"forceOrder","E":1568014460893,"o":{"s":"BTCUSDT","S":"SELL","o":"LIMIT","f":"IOC","q":"0.014","p":"9910","ap":"9910","X":"FILLED","l":"0.014","z":"0.014","T":1568014460893}}}"#;
+        let messages = parse(EXCHANGE_NAME, MarketType::LinearSwap, raw_msg, None).unwrap();
+
+        assert_eq!(1, messages.len());
+        assert_eq!(1568014460893, messages[0].get_timestamp());
+        assert!(matches!(messages[0], Message::Liquidation(_)));
+    }
+
+    #[test]
+    fn unsupported_message() {
+        let raw_msg = r#"{"stream":"btcusdt@unknown","data":{"e":"unknown","E":1568014460893}}"#;
+        let err = parse(EXCHANGE_NAME, MarketType::Spot, raw_msg, None).unwrap_err();
+        assert!(matches!(err, ParseError::UnsupportedMessage { .. }));
+        assert_eq!(EXCHANGE_NAME, err.exchange());
+
+        let err = parse(EXCHANGE_NAME, MarketType::Spot, "not json", None).unwrap_err();
+        assert!(matches!(err, ParseError::MalformedJson { .. }));
+    }
+
+    #[test]
+    fn unknown_exchange() {
+        let err = parse("non-existent", MarketType::Spot, "{}", None).unwrap_err();
+        assert_eq!(ParseError::UnknownExchange { exchange: "non-existent".to_string() }, err);
+    }
+}
+
+#[cfg(test)]
+mod depth_sync {
+    use super::EXCHANGE_NAME;
+    use crypto_market_type::MarketType;
+    use crypto_message::OrderBookMsg;
+    use crypto_msg_parser::{parse_l2, parse_l2_snapshot, BinanceDepthSync, DepthSyncStatus};
+
+    fn spot_diff(first_update_id: u64, last_update_id: u64, bids: &str) -> OrderBookMsg {
+        let raw_msg = format!(
+            r#"{{"stream":"btcusdt@depth@100ms","data":{{"e":"depthUpdate","E":1622363903670,"s":"BTCUSDT","U":{first_update_id},"u":{last_update_id},"b":{bids},"a":[]}}}}"#
+        );
+        parse_l2(EXCHANGE_NAME, MarketType::Spot, &raw_msg, None).unwrap().remove(0)
+    }
+
+    fn spot_snapshot(last_update_id: u64) -> OrderBookMsg {
+        let raw_msg = format!(
+            r#"{{"lastUpdateId":{last_update_id},"bids":[["35000.00000000","1.00000000"]],"asks":[["35001.00000000","1.00000000"]]}}"#
+        );
+        parse_l2_snapshot(
+            EXCHANGE_NAME,
+            MarketType::Spot,
+            &raw_msg,
+            Some("BTCUSDT"),
+            Some(1622363903670),
+        )
+        .unwrap()
+        .remove(0)
+    }
+
+    #[test]
+    fn spot() {
+        let mut sync = BinanceDepthSync::new(MarketType::Spot, "BTCUSDT");
+        assert_eq!(DepthSyncStatus::Buffering, sync.on_diff(spot_diff(95, 99, "[]")));
+        assert_eq!(
+            DepthSyncStatus::Buffering,
+            sync.on_diff(spot_diff(100, 102, r#"[["35000.00000000","2.00000000"]]"#))
+        );
+        assert_eq!(
+            DepthSyncStatus::Buffering,
+            sync.on_diff(spot_diff(103, 105, r#"[["34999.00000000","3.00000000"]]"#))
+        );
+        assert!(sync.book().is_none());
+
+        assert_eq!(DepthSyncStatus::Synced, sync.on_snapshot(&spot_snapshot(100)));
+        let book = sync.book().unwrap();
+        assert_eq!(Some(105), book.seq_id());
+        assert_eq!(2.0, book.best_bid().unwrap().quantity_base);
+        assert_eq!(2, book.top_bids(5).len());
+
+        assert_eq!(
+            DepthSyncStatus::Synced,
+            sync.on_diff(spot_diff(106, 108, r#"[["35000.00000000","0.00000000"]]"#))
+        );
+        assert_eq!(34999.0, sync.book().unwrap().best_bid().unwrap().price);
+
+        // 109 is lost
+        assert_eq!(DepthSyncStatus::ResyncRequired, sync.on_diff(spot_diff(110, 111, "[]")));
+        assert!(sync.book().is_none());
+        assert_eq!(DepthSyncStatus::ResyncRequired, sync.on_diff(spot_diff(112, 112, "[]")));
+        assert_eq!(DepthSyncStatus::Synced, sync.on_snapshot(&spot_snapshot(109)));
+        assert_eq!(Some(112), sync.book().unwrap().seq_id());
+    }
+
+    #[test]
+    fn spot_snapshot_too_old() {
+        let mut sync = BinanceDepthSync::new(MarketType::Spot, "BTCUSDT");
+        sync.on_diff(spot_diff(200, 210, "[]"));
+        assert_eq!(DepthSyncStatus::ResyncRequired, sync.on_snapshot(&spot_snapshot(150)));
+        assert_eq!(DepthSyncStatus::Synced, sync.on_snapshot(&spot_snapshot(205)));
+    }
+
+    #[test]
+    fn spot_buffer_full() {
+        let mut sync = BinanceDepthSync::new(MarketType::Spot, "BTCUSDT");
+        let max = BinanceDepthSync::MAX_BUFFERED_DIFFS as u64;
+        for id in 1..=max {
+            assert_eq!(DepthSyncStatus::Buffering, sync.on_diff(spot_diff(id, id, "[]")));
+        }
+        // the oldest diff is dropped
+        assert_eq!(
+            DepthSyncStatus::ResyncRequired,
+            sync.on_diff(spot_diff(max + 1, max + 1, "[]"))
+        );
+        assert_eq!(DepthSyncStatus::Synced, sync.on_snapshot(&spot_snapshot(1)));
+        assert_eq!(Some(max + 1), sync.book().unwrap().seq_id());
+    }
+
+    #[test]
+    fn first_update_id_zero() {
+        let diff = spot_diff(0, 3, "[]");
+        assert_eq!(None, diff.prev_seq_id);
+        assert_eq!(Some(3), diff.seq_id);
+    }
+
+    #[test]
+    fn linear_swap() {
+        let diff = |first_update_id: u64, last_update_id: u64, prev_update_id: u64| {
+            let raw_msg = format!(
+                r#"{{"stream":"btcusdt@depth@100ms","data":{{"e":"depthUpdate","E":1648785270714,"T":1648785270713,"s":"BTCUSDT","U":{first_update_id},"u":{last_update_id},"pu":{prev_update_id},"b":[],"a":[]}}}}"#
+            );
+            parse_l2(EXCHANGE_NAME, MarketType::LinearSwap, &raw_msg, None).unwrap().remove(0)
+        };
+        let raw_msg = r#"{"lastUpdateId":1000,"E":1648785270714,"T":1648785270713,"bids":[["43633.40","4.515"]],"asks":[["44405.40","1.000"]]}"#;
+        let snapshot = parse_l2_snapshot(
+            EXCHANGE_NAME,
+            MarketType::LinearSwap,
+            raw_msg,
+            Some("BTCUSDT"),
+            None,
+        )
+        .unwrap()
+        .remove(0);
+
+        let mut sync = BinanceDepthSync::new(MarketType::LinearSwap, "BTCUSDT");
+        sync.on_diff(diff(980, 990, 970));
+        // update IDs are not consecutive in futures
+        sync.on_diff(diff(995, 1003, 990));
+        sync.on_diff(diff(1010, 1020, 1003));
+        assert_eq!(DepthSyncStatus::Synced, sync.on_snapshot(&snapshot));
+        assert_eq!(Some(1020), sync.book().unwrap().seq_id());
+        assert_eq!(DepthSyncStatus::Synced, sync.on_diff(diff(1025, 1030, 1020)));
+        assert_eq!(DepthSyncStatus::ResyncRequired, sync.on_diff(diff(1045, 1050, 1040)));
     }
 }
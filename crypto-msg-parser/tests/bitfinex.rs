@@ -308,7 +308,9 @@ mod l2_event {
 mod l3_event {
     use super::EXCHANGE_NAME;
     use crypto_market_type::MarketType;
-    use crypto_msg_parser::{extract_symbol, extract_timestamp};
+    use crypto_message::{L3Action, TradeSide};
+    use crypto_msg_parser::{extract_symbol, extract_timestamp, get_msg_type, parse_l3};
+    use crypto_msg_type::MessageType;
 
     #[test]
     fn spot_snapshot() {
@@ -316,6 +318,19 @@ mod l3_event {
 
         assert_eq!(None, extract_timestamp(EXCHANGE_NAME, MarketType::Spot, raw_msg).unwrap());
         assert_eq!("tBTCUST", extract_symbol(EXCHANGE_NAME, MarketType::Spot, raw_msg).unwrap());
+        assert_eq!(MessageType::L3Event, get_msg_type(EXCHANGE_NAME, raw_msg));
+
+        let l3_msgs =
+            parse_l3(EXCHANGE_NAME, MarketType::Spot, raw_msg, Some(1654074480000)).unwrap();
+        assert_eq!(6, l3_msgs.len());
+        assert_eq!(MessageType::L3Snapshot, l3_msgs[0].msg_type);
+        assert_eq!("BTC/USDT", l3_msgs[0].pair);
+        assert_eq!("96124382782", l3_msgs[0].order_id);
+        assert_eq!(Some(TradeSide::Buy), l3_msgs[0].side);
+        assert_eq!(Some(31534.0), l3_msgs[0].price);
+        assert_eq!(0.0285, l3_msgs[0].quantity_base);
+        assert_eq!(Some(TradeSide::Sell), l3_msgs[5].side);
+        assert_eq!(0.6338, l3_msgs[5].quantity_base);
     }
 
     #[test]
@@ -324,6 +339,37 @@ mod l3_event {
 
         assert_eq!(None, extract_timestamp(EXCHANGE_NAME, MarketType::Spot, raw_msg).unwrap());
         assert_eq!("tBTCUST", extract_symbol(EXCHANGE_NAME, MarketType::Spot, raw_msg).unwrap());
+
+        let l3_msgs =
+            parse_l3(EXCHANGE_NAME, MarketType::Spot, raw_msg, Some(1654074480000)).unwrap();
+        assert_eq!(1, l3_msgs.len());
+        assert_eq!(MessageType::L3Event, l3_msgs[0].msg_type);
+        assert_eq!(L3Action::Open, l3_msgs[0].action);
+        assert_eq!(1654074480000, l3_msgs[0].timestamp);
+        assert_eq!(raw_msg, l3_msgs[0].json);
+
+        let raw_msg = r#"[{"len":"250","symbol":"tBTCUST","channel":"book","prec":"R0","freq":"F0"},[96118584550,0,-1]]"#;
+        let l3_msgs =
+            parse_l3(EXCHANGE_NAME, MarketType::Spot, raw_msg, Some(1654074480000)).unwrap();
+        assert_eq!(L3Action::Done, l3_msgs[0].action);
+        assert_eq!(None, l3_msgs[0].price);
+        assert_eq!(0.0, l3_msgs[0].quantity_base);
+
+        // 2^53 + 1 can't be represented by f64
+        let raw_msg = r#"[{"len":"250","symbol":"tBTCUST","channel":"book","prec":"R0","freq":"F0"},[9007199254740993,31535,0.01586]]"#;
+        let l3_msgs =
+            parse_l3(EXCHANGE_NAME, MarketType::Spot, raw_msg, Some(1654074480000)).unwrap();
+        assert_eq!("9007199254740993", l3_msgs[0].order_id);
+    }
+
+    #[test]
+    fn heartbeat() {
+        let raw_msg = r#"[17082,"hb"]"#;
+        assert!(
+            parse_l3(EXCHANGE_NAME, MarketType::Spot, raw_msg, Some(1654074480000))
+                .unwrap()
+                .is_empty()
+        );
     }
 
     #[test]
@@ -371,14 +417,14 @@ mod candlestick {
         );
         assert_eq!("tBTCUST", extract_symbol(EXCHANGE_NAME, MarketType::Spot, raw_msg).unwrap());
 
-        let arr = parse_candlestick(EXCHANGE_NAME, MarketType::Spot, raw_msg, None).unwrap();
+        let arr = parse_candlestick(EXCHANGE_NAME, MarketType::Spot, raw_msg, None, None).unwrap();
         assert_eq!(3, arr.len());
 
         let candlestick_msg = &arr[0];
 
         assert_eq!("tBTCUST", candlestick_msg.symbol);
         assert_eq!(1654074480000, candlestick_msg.timestamp);
-        assert_eq!("1m", candlestick_msg.period);
+        assert_eq!("1m", candlestick_msg.period.to_string());
 
         assert_eq!(31636.0, candlestick_msg.open);
         assert_eq!(31636.0, candlestick_msg.high);
@@ -397,11 +443,11 @@ mod candlestick {
         );
         assert_eq!("tBTCUST", extract_symbol(EXCHANGE_NAME, MarketType::Spot, raw_msg).unwrap());
         let candlestick_msg =
-            &parse_candlestick(EXCHANGE_NAME, MarketType::Spot, raw_msg, None).unwrap()[0];
+            &parse_candlestick(EXCHANGE_NAME, MarketType::Spot, raw_msg, None, None).unwrap()[0];
 
         assert_eq!("tBTCUST", candlestick_msg.symbol);
         assert_eq!(1654075080000, candlestick_msg.timestamp);
-        assert_eq!("1m", candlestick_msg.period);
+        assert_eq!("1m", candlestick_msg.period.to_string());
 
         assert_eq!(31619.0, candlestick_msg.open);
         assert_eq!(31619.0, candlestick_msg.high);
@@ -423,14 +469,15 @@ mod candlestick {
             extract_symbol(EXCHANGE_NAME, MarketType::LinearSwap, raw_msg).unwrap()
         );
 
-        let arr = parse_candlestick(EXCHANGE_NAME, MarketType::LinearSwap, raw_msg, None).unwrap();
+        let arr =
+            parse_candlestick(EXCHANGE_NAME, MarketType::LinearSwap, raw_msg, None, None).unwrap();
         assert_eq!(3, arr.len());
 
         let candlestick_msg = &arr[0];
 
         assert_eq!("tBTCF0:USTF0", candlestick_msg.symbol);
         assert_eq!(1654076100000, candlestick_msg.timestamp);
-        assert_eq!("1m", candlestick_msg.period);
+        assert_eq!("1m", candlestick_msg.period.to_string());
 
         assert_eq!(31672.0, candlestick_msg.open);
         assert_eq!(31672.0, candlestick_msg.high);
@@ -453,11 +500,12 @@ mod candlestick {
         );
 
         let candlestick_msg =
-            &parse_candlestick(EXCHANGE_NAME, MarketType::LinearSwap, raw_msg, None).unwrap()[0];
+            &parse_candlestick(EXCHANGE_NAME, MarketType::LinearSwap, raw_msg, None, None).unwrap()
+                [0];
 
         assert_eq!("tBTCF0:USTF0", candlestick_msg.symbol);
         assert_eq!(1654076040000, candlestick_msg.timestamp);
-        assert_eq!("1m", candlestick_msg.period);
+        assert_eq!("1m", candlestick_msg.period.to_string());
 
         assert_eq!(31672.0, candlestick_msg.open);
         assert_eq!(31673.0, candlestick_msg.high);
@@ -471,7 +519,7 @@ mod candlestick {
 mod ticker {
     use super::EXCHANGE_NAME;
     use crypto_market_type::MarketType;
-    use crypto_msg_parser::{extract_symbol, extract_timestamp};
+    use crypto_msg_parser::{extract_symbol, extract_timestamp, parse_ticker};
 
     #[test]
     fn spot() {
@@ -479,6 +527,18 @@ mod ticker {
 
         assert_eq!(None, extract_timestamp(EXCHANGE_NAME, MarketType::Spot, raw_msg).unwrap());
         assert_eq!("tBTCUST", extract_symbol(EXCHANGE_NAME, MarketType::Spot, raw_msg).unwrap());
+
+        let tickers =
+            &parse_ticker(EXCHANGE_NAME, MarketType::Spot, raw_msg, Some(1654160360101)).unwrap();
+        assert_eq!(1, tickers.len());
+        crate::utils::check_ticker_fields(
+            EXCHANGE_NAME,
+            MarketType::Spot,
+            "BTC/USDT".to_string(),
+            "tBTCUST".to_string(),
+            &tickers[0],
+            raw_msg,
+        );
     }
 
     #[test]
@@ -493,6 +553,19 @@ mod ticker {
             "tBTCF0:USTF0",
             extract_symbol(EXCHANGE_NAME, MarketType::LinearSwap, raw_msg).unwrap()
         );
+
+        let tickers =
+            &parse_ticker(EXCHANGE_NAME, MarketType::LinearSwap, raw_msg, Some(1654160360101))
+                .unwrap();
+        assert_eq!(1, tickers.len());
+        crate::utils::check_ticker_fields(
+            EXCHANGE_NAME,
+            MarketType::LinearSwap,
+            "BTC/USDT".to_string(),
+            "tBTCF0:USTF0".to_string(),
+            &tickers[0],
+            raw_msg,
+        );
     }
 }
 
@@ -500,13 +573,17 @@ mod ticker {
 mod l2_snapshot {
     use super::EXCHANGE_NAME;
     use crypto_market_type::MarketType;
-    use crypto_msg_parser::{extract_symbol, extract_timestamp, parse_l2_snapshot, round};
+    use crypto_msg_parser::{
+        extract_symbol, extract_timestamp, get_msg_type, parse_l2_snapshot, round,
+    };
     use crypto_msg_type::MessageType;
 
     #[test]
     fn spot() {
         let raw_msg = r#"[[23144,3,0.87789],[23142,3,0.27991505],[23141,4,0.48547974],[23325,5,-0.00297898],[23326,1,-0.00060965],[23327,2,-0.00119078]]"#;
-        let received_at = Some(1677628803683);
+
+        assert_eq!(MessageType::L2Snapshot, get_msg_type(EXCHANGE_NAME, raw_msg));
+        let received_at = 1677628803683;
 
         assert_eq!("NONE", extract_symbol(EXCHANGE_NAME, MarketType::Spot, raw_msg).unwrap());
 
@@ -517,7 +594,7 @@ mod l2_snapshot {
             MarketType::Spot,
             raw_msg,
             Some("tBTCUSD"),
-            received_at,
+            Some(received_at),
         )
         .unwrap()[0];
 
@@ -535,7 +612,7 @@ mod l2_snapshot {
             raw_msg,
         );
 
-        assert_eq!(orderbook.timestamp, received_at.unwrap());
+        assert_eq!(orderbook.timestamp, received_at);
         assert_eq!(orderbook.seq_id, None);
         assert_eq!(orderbook.prev_seq_id, None);
 
@@ -563,7 +640,9 @@ mod l2_snapshot {
     #[test]
     fn linear_swap() {
         let raw_msg = r#"[[23143,5,0.4721613],[23142,3,0.22947044],[23141,2,0.6234],[23906,1,-0.005],[23920,1,-0.0026],[23923,1,-0.02]]"#;
-        let received_at = Some(1677628819111);
+
+        assert_eq!(MessageType::L2Snapshot, get_msg_type(EXCHANGE_NAME, raw_msg));
+        let received_at = 1677628819111;
 
         assert_eq!("NONE", extract_symbol(EXCHANGE_NAME, MarketType::LinearSwap, raw_msg).unwrap());
 
@@ -577,7 +656,7 @@ mod l2_snapshot {
             MarketType::Spot,
             raw_msg,
             Some("tBTCF0:USTF0"),
-            received_at,
+            Some(received_at),
         )
         .unwrap()[0];
 
@@ -595,7 +674,7 @@ mod l2_snapshot {
             raw_msg,
         );
 
-        assert_eq!(orderbook.timestamp, received_at.unwrap());
+        assert_eq!(orderbook.timestamp, received_at);
         assert_eq!(orderbook.seq_id, None);
         assert_eq!(orderbook.prev_seq_id, None);
 
@@ -620,3 +699,73 @@ mod l2_snapshot {
         assert_eq!(orderbook.bids[0].quantity_contract, None);
     }
 }
+
+#[cfg(test)]
+mod funding_rate {
+    use super::EXCHANGE_NAME;
+    use crypto_market_type::MarketType;
+    use crypto_msg_parser::{extract_symbol, extract_timestamp, parse_funding_rate};
+
+    #[test]
+    fn linear_swap() {
+        let raw_msg = r#"[{"channel":"status","key":"deriv:tBTCF0:USTF0"},[1654162706000,null,29887.5,29880.5,null,1186263.2934,null,1654185600000,0.00001452,21,null,0.0001,null,null,29885.34,null,null,1534.94158386,null,null,null,-0.3,0.3]]"#;
+        let funding_rates =
+            &parse_funding_rate(EXCHANGE_NAME, MarketType::LinearSwap, raw_msg, None).unwrap();
+
+        assert_eq!(funding_rates.len(), 1);
+        crate::utils::check_funding_rate_fields(
+            EXCHANGE_NAME,
+            MarketType::LinearSwap,
+            &funding_rates[0],
+            raw_msg,
+        );
+        assert_eq!(
+            "tBTCF0:USTF0",
+            extract_symbol(EXCHANGE_NAME, MarketType::LinearSwap, raw_msg).unwrap()
+        );
+        assert_eq!(
+            1654162706000,
+            extract_timestamp(EXCHANGE_NAME, MarketType::LinearSwap, raw_msg).unwrap().unwrap()
+        );
+
+        assert_eq!(funding_rates[0].pair, "BTC/USDT");
+        assert_eq!(funding_rates[0].funding_rate, 0.0001);
+        assert_eq!(funding_rates[0].estimated_rate, Some(0.00001452));
+        assert_eq!(funding_rates[0].funding_time, 1654185600000);
+        assert_eq!(funding_rates[0].timestamp, 1654162706000);
+    }
+
+    #[test]
+    fn heartbeat() {
+        let raw_msg = r#"[{"channel":"status","key":"deriv:tBTCF0:USTF0"},"hb"]"#;
+        assert!(
+            parse_funding_rate(EXCHANGE_NAME, MarketType::LinearSwap, raw_msg, None)
+                .unwrap()
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn linear_swap_rest() {
+        let raw_msg = r#"[["tBTCF0:USTF0",1654162706000,null,29887.5,29880.5,null,1186263.2934,null,1654185600000,0.00001452,21,null,0.0001,null,null,29885.34,null,null,1534.94158386,null,null,null,-0.3,0.3],["tETHF0:USTF0",1654162706000,null,1812.6,1812.05,null,240093.3473,null,1654185600000,-0.00000537,21,null,-0.00000812,null,null,1812.41,null,null,10245.0237,null,null,null,-0.3,0.3]]"#;
+        let funding_rates =
+            &parse_funding_rate(EXCHANGE_NAME, MarketType::LinearSwap, raw_msg, None).unwrap();
+
+        assert_eq!(funding_rates.len(), 2);
+        for rate in funding_rates.iter() {
+            crate::utils::check_funding_rate_fields(
+                EXCHANGE_NAME,
+                MarketType::LinearSwap,
+                rate,
+                raw_msg,
+            );
+            assert_eq!(rate.funding_time, 1654185600000);
+        }
+
+        assert_eq!(funding_rates[0].pair, "BTC/USDT");
+        assert_eq!(funding_rates[0].funding_rate, 0.0001);
+        assert_eq!(funding_rates[1].pair, "ETH/USDT");
+        assert_eq!(funding_rates[1].funding_rate, -0.00000812);
+        assert_eq!(funding_rates[1].estimated_rate, Some(-0.00000537));
+    }
+}
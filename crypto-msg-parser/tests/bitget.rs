@@ -463,15 +463,15 @@ mod candlestick {
             "BTCUSDT_SPBL",
             extract_symbol(EXCHANGE_NAME, MarketType::Spot, raw_msg).unwrap()
         );
-        let arr = parse_candlestick(EXCHANGE_NAME, MarketType::Spot, raw_msg, None).unwrap();
+        let arr = parse_candlestick(EXCHANGE_NAME, MarketType::Spot, raw_msg, None, None).unwrap();
         assert_eq!(2, arr.len());
         let candlestick_msg = &arr[0];
 
         assert_eq!("BTCUSDT_SPBL", candlestick_msg.symbol);
         assert_eq!("BTC/USDT", candlestick_msg.pair);
         assert_eq!(1654017060000, candlestick_msg.timestamp);
-        assert_eq!(1654017000000, candlestick_msg.begin_time);
-        assert_eq!("1m", candlestick_msg.period);
+        assert_eq!(1654017060000, candlestick_msg.begin_time);
+        assert_eq!("1m", candlestick_msg.period.to_string());
 
         assert_eq!(32173.42, candlestick_msg.open);
         assert_eq!(32173.42, candlestick_msg.high);
@@ -493,15 +493,15 @@ mod candlestick {
             "BTCUSDT_SPBL",
             extract_symbol(EXCHANGE_NAME, MarketType::Spot, raw_msg).unwrap()
         );
-        let arr = parse_candlestick(EXCHANGE_NAME, MarketType::Spot, raw_msg, None).unwrap();
+        let arr = parse_candlestick(EXCHANGE_NAME, MarketType::Spot, raw_msg, None, None).unwrap();
         assert_eq!(1, arr.len());
         let candlestick_msg = &arr[0];
 
         assert_eq!("BTCUSDT_SPBL", candlestick_msg.symbol);
         assert_eq!("BTC/USDT", candlestick_msg.pair);
         assert_eq!(1654077000000, candlestick_msg.timestamp);
-        assert_eq!(1654076940000, candlestick_msg.begin_time);
-        assert_eq!("1m", candlestick_msg.period);
+        assert_eq!(1654077000000, candlestick_msg.begin_time);
+        assert_eq!("1m", candlestick_msg.period.to_string());
 
         assert_eq!(31682.39, candlestick_msg.open);
         assert_eq!(31683.63, candlestick_msg.high);
@@ -523,15 +523,16 @@ mod candlestick {
             "BTCUSD_DMCBL",
             extract_symbol(EXCHANGE_NAME, MarketType::InverseSwap, raw_msg).unwrap()
         );
-        let arr = parse_candlestick(EXCHANGE_NAME, MarketType::InverseSwap, raw_msg, None).unwrap();
+        let arr =
+            parse_candlestick(EXCHANGE_NAME, MarketType::InverseSwap, raw_msg, None, None).unwrap();
         assert_eq!(2, arr.len());
         let candlestick_msg = &arr[0];
 
         assert_eq!("BTCUSD_DMCBL", candlestick_msg.symbol);
         assert_eq!("BTC/USD", candlestick_msg.pair);
         assert_eq!(1654017420000, candlestick_msg.timestamp);
-        assert_eq!(1654017360000, candlestick_msg.begin_time);
-        assert_eq!("1m", candlestick_msg.period);
+        assert_eq!(1654017420000, candlestick_msg.begin_time);
+        assert_eq!("1m", candlestick_msg.period.to_string());
 
         assert_eq!(31974.0, candlestick_msg.open);
         assert_eq!(31992.5, candlestick_msg.high);
@@ -553,15 +554,16 @@ mod candlestick {
             "BTCUSD_DMCBL",
             extract_symbol(EXCHANGE_NAME, MarketType::InverseSwap, raw_msg).unwrap()
         );
-        let arr = parse_candlestick(EXCHANGE_NAME, MarketType::InverseSwap, raw_msg, None).unwrap();
+        let arr =
+            parse_candlestick(EXCHANGE_NAME, MarketType::InverseSwap, raw_msg, None, None).unwrap();
         assert_eq!(1, arr.len());
         let candlestick_msg = &arr[0];
 
         assert_eq!("BTCUSD_DMCBL", candlestick_msg.symbol);
         assert_eq!("BTC/USD", candlestick_msg.pair);
         assert_eq!(1654077360000, candlestick_msg.timestamp);
-        assert_eq!(1654077300000, candlestick_msg.begin_time);
-        assert_eq!("1m", candlestick_msg.period);
+        assert_eq!(1654077360000, candlestick_msg.begin_time);
+        assert_eq!("1m", candlestick_msg.period.to_string());
 
         assert_eq!(31652.0, candlestick_msg.open);
         assert_eq!(31653.5, candlestick_msg.high);
@@ -583,15 +585,16 @@ mod candlestick {
             "BTCUSDT_UMCBL",
             extract_symbol(EXCHANGE_NAME, MarketType::LinearSwap, raw_msg).unwrap()
         );
-        let arr = parse_candlestick(EXCHANGE_NAME, MarketType::LinearSwap, raw_msg, None).unwrap();
+        let arr =
+            parse_candlestick(EXCHANGE_NAME, MarketType::LinearSwap, raw_msg, None, None).unwrap();
         assert_eq!(2, arr.len());
         let candlestick_msg = &arr[0];
 
         assert_eq!("BTCUSDT_UMCBL", candlestick_msg.symbol);
         assert_eq!("BTC/USDT", candlestick_msg.pair);
         assert_eq!(1654017660000, candlestick_msg.timestamp);
-        assert_eq!(1654017600000, candlestick_msg.begin_time);
-        assert_eq!("1m", candlestick_msg.period);
+        assert_eq!(1654017660000, candlestick_msg.begin_time);
+        assert_eq!("1m", candlestick_msg.period.to_string());
 
         assert_eq!(31966.5, candlestick_msg.open);
         assert_eq!(31966.5, candlestick_msg.high);
@@ -613,15 +616,16 @@ mod candlestick {
             "BTCUSDT_UMCBL",
             extract_symbol(EXCHANGE_NAME, MarketType::LinearSwap, raw_msg).unwrap()
         );
-        let arr = parse_candlestick(EXCHANGE_NAME, MarketType::LinearSwap, raw_msg, None).unwrap();
+        let arr =
+            parse_candlestick(EXCHANGE_NAME, MarketType::LinearSwap, raw_msg, None, None).unwrap();
         assert_eq!(1, arr.len());
         let candlestick_msg = &arr[0];
 
         assert_eq!("BTCUSDT_UMCBL", candlestick_msg.symbol);
         assert_eq!("BTC/USDT", candlestick_msg.pair);
         assert_eq!(1654077600000, candlestick_msg.timestamp);
-        assert_eq!(1654077540000, candlestick_msg.begin_time);
-        assert_eq!("1m", candlestick_msg.period);
+        assert_eq!(1654077600000, candlestick_msg.begin_time);
+        assert_eq!("1m", candlestick_msg.period.to_string());
 
         assert_eq!(31676.5, candlestick_msg.open);
         assert_eq!(31676.5, candlestick_msg.high);
@@ -636,7 +640,7 @@ mod candlestick {
 mod ticker {
     use super::EXCHANGE_NAME;
     use crypto_market_type::MarketType;
-    use crypto_msg_parser::{extract_symbol, extract_timestamp};
+    use crypto_msg_parser::{extract_symbol, extract_timestamp, parse_ticker};
 
     #[test]
     fn spot() {
@@ -650,6 +654,17 @@ mod ticker {
             "BTCUSDT_SPBL",
             extract_symbol(EXCHANGE_NAME, MarketType::Spot, raw_msg).unwrap()
         );
+
+        let tickers = &parse_ticker(EXCHANGE_NAME, MarketType::Spot, raw_msg, None).unwrap();
+        assert_eq!(1, tickers.len());
+        crate::utils::check_ticker_fields(
+            EXCHANGE_NAME,
+            MarketType::Spot,
+            "BTC/USDT".to_string(),
+            "BTCUSDT_SPBL".to_string(),
+            &tickers[0],
+            raw_msg,
+        );
     }
 
     #[test]
@@ -664,6 +679,17 @@ mod ticker {
             "BTCUSD_DMCBL",
             extract_symbol(EXCHANGE_NAME, MarketType::Spot, raw_msg).unwrap()
         );
+
+        let tickers = &parse_ticker(EXCHANGE_NAME, MarketType::InverseSwap, raw_msg, None).unwrap();
+        assert_eq!(1, tickers.len());
+        crate::utils::check_ticker_fields(
+            EXCHANGE_NAME,
+            MarketType::InverseSwap,
+            "BTC/USD".to_string(),
+            "BTCUSD_DMCBL".to_string(),
+            &tickers[0],
+            raw_msg,
+        );
     }
 
     #[test]
@@ -678,6 +704,17 @@ mod ticker {
             "BTCUSDT_UMCBL",
             extract_symbol(EXCHANGE_NAME, MarketType::LinearSwap, raw_msg).unwrap()
         );
+
+        let tickers = &parse_ticker(EXCHANGE_NAME, MarketType::LinearSwap, raw_msg, None).unwrap();
+        assert_eq!(1, tickers.len());
+        crate::utils::check_ticker_fields(
+            EXCHANGE_NAME,
+            MarketType::LinearSwap,
+            "BTC/USDT".to_string(),
+            "BTCUSDT_UMCBL".to_string(),
+            &tickers[0],
+            raw_msg,
+        );
     }
 }
 
@@ -1300,15 +1337,16 @@ mod before20220429 {
                     .unwrap()
             );
             let arr =
-                parse_candlestick(EXCHANGE_NAME, MarketType::InverseSwap, raw_msg, None).unwrap();
+                parse_candlestick(EXCHANGE_NAME, MarketType::InverseSwap, raw_msg, None, None)
+                    .unwrap();
             assert_eq!(1, arr.len());
             let candlestick_msg = &arr[0];
 
             assert_eq!("btcusd", candlestick_msg.symbol);
             assert_eq!("BTC/USD", candlestick_msg.pair);
             assert_eq!(1646092800000, candlestick_msg.timestamp);
-            assert_eq!(1646092740000, candlestick_msg.begin_time);
-            assert_eq!("60s", candlestick_msg.period);
+            assert_eq!(1646092800000, candlestick_msg.begin_time);
+            assert_eq!("1m", candlestick_msg.period.to_string());
 
             assert_eq!(43156.0, candlestick_msg.open);
             assert_eq!(43157.5, candlestick_msg.high);
@@ -1330,16 +1368,16 @@ mod before20220429 {
                 1648801800000,
                 extract_timestamp(EXCHANGE_NAME, MarketType::LinearSwap, raw_msg).unwrap().unwrap()
             );
-            let arr =
-                parse_candlestick(EXCHANGE_NAME, MarketType::LinearSwap, raw_msg, None).unwrap();
+            let arr = parse_candlestick(EXCHANGE_NAME, MarketType::LinearSwap, raw_msg, None, None)
+                .unwrap();
             assert_eq!(1, arr.len());
             let candlestick_msg = &arr[0];
 
             assert_eq!("cmt_btcusdt", candlestick_msg.symbol);
             assert_eq!("BTC/USDT", candlestick_msg.pair);
             assert_eq!(1648801800000, candlestick_msg.timestamp);
-            assert_eq!(1648801740000, candlestick_msg.begin_time);
-            assert_eq!("60s", candlestick_msg.period);
+            assert_eq!(1648801800000, candlestick_msg.begin_time);
+            assert_eq!("1m", candlestick_msg.period.to_string());
 
             assert_eq!(45298.5, candlestick_msg.open);
             assert_eq!(45298.5, candlestick_msg.high);
@@ -2,7 +2,10 @@ mod utils;
 
 use crypto_market_type::MarketType;
 use crypto_message::TradeSide;
-use crypto_msg_parser::{extract_symbol, extract_timestamp, parse_l2, parse_trade};
+use crypto_msg_parser::{
+    extract_symbol, extract_timestamp, get_msg_type, parse_candlestick, parse_l2, parse_ticker,
+    parse_trade, round,
+};
 use crypto_msg_type::MessageType;
 
 const EXCHANGE_NAME: &str = "bithumb";
@@ -130,20 +133,74 @@ fn l2_orderbook_update() {
 fn ticker() {
     let raw_msg = r#"{"code":"00007","data":{"p":"-0.0512","symbol":"BTC-USDT","ver":"70013048","vol":"22818095.72371200","c":"29951.93","t":"22818095.72371200","v":"747.110521","h":"32252.34","l":"29250.95"},"topic":"TICKER","timestamp":1654161207269}"#;
 
+    assert_eq!(MessageType::Ticker, get_msg_type(EXCHANGE_NAME, raw_msg));
+
     assert_eq!(
         1654161207269,
         extract_timestamp(EXCHANGE_NAME, MarketType::Spot, raw_msg).unwrap().unwrap()
     );
     assert_eq!("BTC-USDT", extract_symbol(EXCHANGE_NAME, MarketType::Spot, raw_msg).unwrap());
+
+    let tickers = &parse_ticker(EXCHANGE_NAME, MarketType::Spot, raw_msg, None).unwrap();
+    assert_eq!(1, tickers.len());
+    utils::check_ticker_fields(
+        EXCHANGE_NAME,
+        MarketType::Spot,
+        "BTC/USDT".to_string(),
+        "BTC-USDT".to_string(),
+        &tickers[0],
+        raw_msg,
+    );
+    assert_eq!(1654161207269, tickers[0].timestamp);
+    assert_eq!(round(29951.93 / (1.0 - 0.0512)), round(tickers[0].open));
+    assert_eq!(32252.34, tickers[0].high);
+    assert_eq!(29250.95, tickers[0].low);
+    assert_eq!(29951.93, tickers[0].close);
+    assert_eq!(747.110521, tickers[0].volume);
+    assert_eq!(22818095.723712, tickers[0].quote_volume);
 }
 
 #[test]
 fn l2_snapshot() {
     let raw_msg = r#"{"data":{"symbol":"BTC-USDT","b":[["30402.440000000000","0.001458"],["30370.910000000000","0.002482"],["30338.010000000000","0.000540"]],"ver":"876388569","s":[["30651.830000000000","0.003630"],["30686.780000000000","0.003420"],["30698.550000000000","0.004859"]]},"code":"0","msg":"success","timestamp":1654234202305,"startTime":null}"#;
 
+    assert_eq!(MessageType::L2Snapshot, get_msg_type(EXCHANGE_NAME, raw_msg));
+
     assert_eq!(
         1654234202305,
         extract_timestamp(EXCHANGE_NAME, MarketType::Spot, raw_msg).unwrap().unwrap()
     );
     assert_eq!("BTC-USDT", extract_symbol(EXCHANGE_NAME, MarketType::Spot, raw_msg).unwrap());
 }
+
+#[test]
+fn candlestick() {
+    let raw_msg = r#"{"data":[{"c":"29998.5","h":"30008","l":"29712.35","o":"29780.01","s":"3510482.13","t":"1654239600","v":"117.40113219"},{"c":"30018.62","h":"30121.16","l":"29995.12","o":"29998.5","s":"1202743.28","t":"1654243200","v":"40.06672121"}],"code":"0","msg":"success","timestamp":1654243265123,"params":[]}"#;
+
+    assert_eq!(MessageType::Candlestick, get_msg_type(EXCHANGE_NAME, raw_msg));
+    assert_eq!(
+        1654243265123,
+        extract_timestamp(EXCHANGE_NAME, MarketType::Spot, raw_msg).unwrap().unwrap()
+    );
+    assert_eq!("NONE", extract_symbol(EXCHANGE_NAME, MarketType::Spot, raw_msg).unwrap());
+
+    // the symbol is not in the message
+    assert!(parse_candlestick(EXCHANGE_NAME, MarketType::Spot, raw_msg, None, None).is_err());
+    let arr = parse_candlestick(EXCHANGE_NAME, MarketType::Spot, raw_msg, Some("BTC-USDT"), None)
+        .unwrap();
+    assert_eq!(2, arr.len());
+    let candlestick_msg = &arr[1];
+
+    assert_eq!("BTC-USDT", candlestick_msg.symbol);
+    assert_eq!("BTC/USDT", candlestick_msg.pair);
+    assert_eq!(1654243265123, candlestick_msg.timestamp);
+    assert_eq!(1654243200000, candlestick_msg.begin_time);
+    assert_eq!("1h", candlestick_msg.period.to_string());
+
+    assert_eq!(29998.5, candlestick_msg.open);
+    assert_eq!(30121.16, candlestick_msg.high);
+    assert_eq!(29995.12, candlestick_msg.low);
+    assert_eq!(30018.62, candlestick_msg.close);
+    assert_eq!(40.06672121, candlestick_msg.volume);
+    assert_eq!(None, candlestick_msg.quote_volume);
+}
@@ -197,9 +197,22 @@ mod funding_rate {
         assert_eq!(funding_rates[0].pair, "BTC/USD".to_string());
         assert_eq!(funding_rates[0].funding_rate, 0.000817);
         assert_eq!(funding_rates[0].funding_time, 1617307200000);
+        assert_eq!(funding_rates[0].funding_interval, 8 * 3600000);
+        assert_eq!(funding_rates[0].annualized_rate, 0.894615);
         assert_eq!(funding_rates[0].timestamp, received_at);
     }
 
+    #[test]
+    fn zero_interval() {
+        let raw_msg = r#"{"table":"funding","action":"partial","data":[{"timestamp":"2021-04-01T20:00:00.000Z","symbol":"XBTUSD","fundingInterval":"2000-01-01T00:00:00.000Z","fundingRate":0.000817,"fundingRateDaily":0.002451}]}"#;
+        let funding_rates =
+            &parse_funding_rate(EXCHANGE_NAME, MarketType::InverseSwap, raw_msg, Some(0)).unwrap();
+
+        // falls back to 8 hours instead of an infinite annualized rate
+        assert_eq!(funding_rates[0].funding_interval, 8 * 3600000);
+        assert_eq!(funding_rates[0].annualized_rate, 0.894615);
+    }
+
     #[test]
     fn quanto_swap() {
         let raw_msg = r#"{"table":"funding","action":"partial","data":[{"timestamp":"2021-04-01T20:00:00.000Z","symbol":"ETHUSD","fundingInterval":"2000-01-01T08:00:00.000Z","fundingRate":0.002142,"fundingRateDaily":0.006425999999999999}]}"#;
@@ -266,6 +279,53 @@ mod funding_rate {
     }
 }
 
+#[cfg(test)]
+mod liquidation {
+    use super::EXCHANGE_NAME;
+    use crypto_market_type::MarketType;
+    use crypto_message::TradeSide;
+    use crypto_msg_parser::{parse_liquidation, round};
+
+    #[test]
+    fn inverse_swap() {
+        let raw_msg = r#"{"table":"liquidation","action":"insert","data":[{"orderID":"a3d12a4b-6b7c-4d1c-8a2f-0f1d2c3b4a5e","symbol":"XBTUSD","side":"Sell","price":29321.5,"leavesQty":2300}]}"#;
+        let liquidations = &parse_liquidation(
+            EXCHANGE_NAME,
+            MarketType::InverseSwap,
+            raw_msg,
+            Some(1689576534012),
+        )
+        .unwrap();
+
+        assert_eq!(liquidations.len(), 1);
+        let liquidation = &liquidations[0];
+        crate::utils::check_liquidation_fields(
+            EXCHANGE_NAME,
+            MarketType::InverseSwap,
+            "BTC/USD".to_string(),
+            liquidation,
+        );
+
+        assert_eq!(liquidation.timestamp, 1689576534012);
+        assert_eq!(liquidation.side, TradeSide::Sell);
+        assert_eq!(liquidation.price, 29321.5);
+        assert_eq!(liquidation.quantity_contract, Some(2300.0));
+        assert_eq!(liquidation.quantity_quote, 2300.0);
+        assert_eq!(round(liquidation.quantity_base), round(2300.0 / 29321.5));
+    }
+
+    #[test]
+    fn update() {
+        let raw_msg = r#"{"table":"liquidation","action":"update","data":[{"orderID":"a3d12a4b-6b7c-4d1c-8a2f-0f1d2c3b4a5e","leavesQty":1200,"symbol":"XBTUSD"}]}"#;
+        let liquidations =
+            parse_liquidation(EXCHANGE_NAME, MarketType::Unknown, raw_msg, Some(1689576534012))
+                .unwrap();
+        assert!(liquidations.is_empty());
+
+        assert!(parse_liquidation(EXCHANGE_NAME, MarketType::Unknown, raw_msg, None).is_err());
+    }
+}
+
 #[cfg(test)]
 mod order_book_l2_25 {
     use super::EXCHANGE_NAME;
@@ -302,25 +362,25 @@ mod order_book_l2_25 {
         );
 
         assert_eq!(orderbook.bids[0].price, 36145.0);
-        assert_eq!(8796385500, price_to_id("XBTUSD", 36145.0));
+        assert_eq!(8796385500, price_to_id("XBTUSD", 36145.0).unwrap());
         assert_eq!(orderbook.bids[0].quantity_base, 136.0 / 36145.0);
         assert_eq!(orderbook.bids[0].quantity_quote, 136.0);
         assert_eq!(orderbook.bids[0].quantity_contract.unwrap(), 136.0);
 
         assert_eq!(orderbook.bids[2].price, 36142.0);
-        assert_eq!(8796385800, price_to_id("XBTUSD", 36142.0));
+        assert_eq!(8796385800, price_to_id("XBTUSD", 36142.0).unwrap());
         assert_eq!(orderbook.bids[2].quantity_base, 18067.0 / 36142.0);
         assert_eq!(orderbook.bids[2].quantity_quote, 18067.0);
         assert_eq!(orderbook.bids[2].quantity_contract.unwrap(), 18067.0);
 
         assert_eq!(orderbook.asks[2].price, 36190.0);
-        assert_eq!(8796381000, price_to_id("XBTUSD", 36190.0));
+        assert_eq!(8796381000, price_to_id("XBTUSD", 36190.0).unwrap());
         assert_eq!(orderbook.asks[2].quantity_base, 49900.0 / 36190.0);
         assert_eq!(orderbook.asks[2].quantity_quote, 49900.0);
         assert_eq!(orderbook.asks[2].quantity_contract.unwrap(), 49900.0);
 
         assert_eq!(orderbook.asks[0].price, 36189.0);
-        assert_eq!(8796381100, price_to_id("XBTUSD", 36189.0));
+        assert_eq!(8796381100, price_to_id("XBTUSD", 36189.0).unwrap());
         assert_eq!(orderbook.asks[0].quantity_base, 34600.0 / 36189.0);
         assert_eq!(orderbook.asks[0].quantity_quote, 34600.0);
         assert_eq!(orderbook.asks[0].quantity_contract.unwrap(), 34600.0);
@@ -852,7 +912,7 @@ mod candlestick {
             extract_timestamp(EXCHANGE_NAME, MarketType::Spot, raw_msg).unwrap().unwrap()
         );
         assert_eq!("XBT_USDT", extract_symbol(EXCHANGE_NAME, MarketType::Spot, raw_msg).unwrap());
-        let arr = parse_candlestick(EXCHANGE_NAME, MarketType::Spot, raw_msg, None).unwrap();
+        let arr = parse_candlestick(EXCHANGE_NAME, MarketType::Spot, raw_msg, None, None).unwrap();
         assert_eq!(1, arr.len());
         let candlestick_msg = &arr[0];
 
@@ -860,7 +920,9 @@ mod candlestick {
         assert_eq!("BTC/USDT", candlestick_msg.pair);
         assert_eq!(1677629160000, candlestick_msg.timestamp);
         assert_eq!(1677629100000, candlestick_msg.begin_time);
-        assert_eq!("1m", candlestick_msg.period);
+        assert_eq!(1677629160000, candlestick_msg.end_time);
+        assert_eq!("1m", candlestick_msg.period.to_string());
+        assert_eq!(Some(true), candlestick_msg.is_closed);
 
         assert_eq!(23118.0, candlestick_msg.open);
         assert_eq!(23145.5, candlestick_msg.high);
@@ -881,7 +943,8 @@ mod candlestick {
             "XBTUSD",
             extract_symbol(EXCHANGE_NAME, MarketType::InverseSwap, raw_msg).unwrap()
         );
-        let arr = parse_candlestick(EXCHANGE_NAME, MarketType::InverseSwap, raw_msg, None).unwrap();
+        let arr =
+            parse_candlestick(EXCHANGE_NAME, MarketType::InverseSwap, raw_msg, None, None).unwrap();
         assert_eq!(1, arr.len());
         let candlestick_msg = &arr[0];
 
@@ -889,7 +952,7 @@ mod candlestick {
         assert_eq!("BTC/USD", candlestick_msg.pair);
         assert_eq!(1675210020000, candlestick_msg.timestamp);
         assert_eq!(1675209960000, candlestick_msg.begin_time);
-        assert_eq!("1m", candlestick_msg.period);
+        assert_eq!("1m", candlestick_msg.period.to_string());
 
         assert_eq!(23077.5, candlestick_msg.open);
         assert_eq!(23096.5, candlestick_msg.high);
@@ -912,7 +975,8 @@ mod candlestick {
             extract_symbol(EXCHANGE_NAME, MarketType::LinearSwap, raw_msg).unwrap()
         );
 
-        let arr = parse_candlestick(EXCHANGE_NAME, MarketType::LinearSwap, raw_msg, None).unwrap();
+        let arr =
+            parse_candlestick(EXCHANGE_NAME, MarketType::LinearSwap, raw_msg, None, None).unwrap();
         assert_eq!(1, arr.len());
         let candlestick_msg = &arr[0];
 
@@ -920,7 +984,7 @@ mod candlestick {
         assert_eq!("BTC/USDT", candlestick_msg.pair);
         assert_eq!(1677628800000, candlestick_msg.timestamp);
         assert_eq!(1677628500000, candlestick_msg.begin_time);
-        assert_eq!("5m", candlestick_msg.period);
+        assert_eq!("5m", candlestick_msg.period.to_string());
 
         assert_eq!(23111.5, candlestick_msg.open);
         assert_eq!(23132.5, candlestick_msg.high);
@@ -941,8 +1005,8 @@ mod candlestick {
             "XBTH23",
             extract_symbol(EXCHANGE_NAME, MarketType::InverseFuture, raw_msg).unwrap()
         );
-        let arr =
-            parse_candlestick(EXCHANGE_NAME, MarketType::InverseFuture, raw_msg, None).unwrap();
+        let arr = parse_candlestick(EXCHANGE_NAME, MarketType::InverseFuture, raw_msg, None, None)
+            .unwrap();
         assert_eq!(1, arr.len());
         let candlestick_msg = &arr[0];
 
@@ -950,7 +1014,7 @@ mod candlestick {
         assert_eq!("BTC/USD", candlestick_msg.pair);
         assert_eq!(1677629100000, candlestick_msg.timestamp);
         assert_eq!(1677628800000, candlestick_msg.begin_time);
-        assert_eq!("5m", candlestick_msg.period);
+        assert_eq!("5m", candlestick_msg.period.to_string());
 
         assert_eq!(23200.0, candlestick_msg.open);
         assert_eq!(23250.0, candlestick_msg.high);
@@ -973,7 +1037,8 @@ mod candlestick {
             extract_symbol(EXCHANGE_NAME, MarketType::LinearSwap, raw_msg).unwrap()
         );
 
-        let arr = parse_candlestick(EXCHANGE_NAME, MarketType::LinearSwap, raw_msg, None).unwrap();
+        let arr =
+            parse_candlestick(EXCHANGE_NAME, MarketType::LinearSwap, raw_msg, None, None).unwrap();
         assert_eq!(1, arr.len());
         let candlestick_msg = &arr[0];
 
@@ -981,7 +1046,7 @@ mod candlestick {
         assert_eq!("BTC/USDT", candlestick_msg.pair);
         assert_eq!(1680291000000, candlestick_msg.timestamp);
         assert_eq!(1680290700000, candlestick_msg.begin_time);
-        assert_eq!("5m", candlestick_msg.period);
+        assert_eq!("5m", candlestick_msg.period.to_string());
 
         assert_eq!(28656.5, candlestick_msg.open);
         assert_eq!(28667.0, candlestick_msg.high);
@@ -1004,7 +1069,8 @@ mod candlestick {
             extract_symbol(EXCHANGE_NAME, MarketType::LinearSwap, raw_msg).unwrap()
         );
 
-        let arr = parse_candlestick(EXCHANGE_NAME, MarketType::LinearSwap, raw_msg, None).unwrap();
+        let arr =
+            parse_candlestick(EXCHANGE_NAME, MarketType::LinearSwap, raw_msg, None, None).unwrap();
         assert_eq!(1, arr.len());
         let candlestick_msg = &arr[0];
 
@@ -1012,7 +1078,7 @@ mod candlestick {
         assert_eq!("ETH/USD", candlestick_msg.pair);
         assert_eq!(1677629100000, candlestick_msg.timestamp);
         assert_eq!(1677628800000, candlestick_msg.begin_time);
-        assert_eq!("5m", candlestick_msg.period);
+        assert_eq!("5m", candlestick_msg.period.to_string());
 
         assert_eq!(1605.6, candlestick_msg.open);
         assert_eq!(1606.5, candlestick_msg.high);
@@ -1035,7 +1101,8 @@ mod candlestick {
             extract_symbol(EXCHANGE_NAME, MarketType::LinearSwap, raw_msg).unwrap()
         );
 
-        let arr = parse_candlestick(EXCHANGE_NAME, MarketType::LinearSwap, raw_msg, None).unwrap();
+        let arr =
+            parse_candlestick(EXCHANGE_NAME, MarketType::LinearSwap, raw_msg, None, None).unwrap();
         assert_eq!(1, arr.len());
         let candlestick_msg = &arr[0];
 
@@ -1043,7 +1110,7 @@ mod candlestick {
         assert_eq!("ETH/USD", candlestick_msg.pair);
         assert_eq!(1680264000000, candlestick_msg.timestamp);
         assert_eq!(1680263940000, candlestick_msg.begin_time);
-        assert_eq!("1m", candlestick_msg.period);
+        assert_eq!("1m", candlestick_msg.period.to_string());
 
         assert_eq!(1792.5, candlestick_msg.open);
         assert_eq!(1793.23, candlestick_msg.high);
@@ -1088,3 +1155,102 @@ mod l2_snapshot {
         );
     }
 }
+
+#[cfg(test)]
+mod open_interest {
+    use super::EXCHANGE_NAME;
+    use crypto_market_type::MarketType;
+    use crypto_msg_parser::parse_open_interest;
+
+    #[test]
+    fn inverse_swap() {
+        let raw_msg = r#"{"table":"instrument","action":"update","data":[{"symbol":"XBTUSD","openInterest":412823700,"openValue":1389898397900,"timestamp":"2022-06-04T11:08:25.000Z"},{"symbol":"XBTUSD","lastPrice":29701,"timestamp":"2022-06-04T11:08:25.100Z"},{"symbol":"XBTUSD","markPrice":29702.15,"openInterest":412824000,"timestamp":"2022-06-04T11:08:30.000Z"}]}"#;
+        let open_interests =
+            &parse_open_interest(EXCHANGE_NAME, MarketType::InverseSwap, raw_msg, None, None)
+                .unwrap();
+
+        // entries without openInterest are skipped
+        assert_eq!(open_interests.len(), 2);
+        assert_eq!(open_interests[0].symbol, "XBTUSD");
+        assert_eq!(open_interests[0].pair, "BTC/USD");
+        assert_eq!(open_interests[0].timestamp, 1654340905000);
+        assert_eq!(open_interests[0].quantity_contract, 412823700.0);
+        assert_eq!(open_interests[0].quantity_quote, Some(412823700.0));
+        assert_eq!(open_interests[0].quantity_base, None);
+
+        assert_eq!(open_interests[1].timestamp, 1654340910000);
+        assert_eq!(open_interests[1].quantity_quote, Some(412824000.0));
+        assert_eq!(open_interests[1].quantity_base, Some(412824000.0 / 29702.15));
+    }
+
+    #[test]
+    fn quanto_swap() {
+        let raw_msg = r#"{"table":"instrument","action":"update","data":[{"symbol":"ETHUSD","openInterest":12345678,"markPrice":1780.35,"timestamp":"2022-06-04T11:08:30.000Z"}]}"#;
+        let open_interests =
+            &parse_open_interest(EXCHANGE_NAME, MarketType::QuantoSwap, raw_msg, None, None)
+                .unwrap();
+
+        assert_eq!(open_interests.len(), 1);
+        assert_eq!(open_interests[0].quantity_contract, 12345678.0);
+        assert_eq!(open_interests[0].quantity_base, None);
+        assert_eq!(open_interests[0].quantity_quote, None);
+    }
+}
+
+#[cfg(test)]
+mod mark_price {
+    use super::EXCHANGE_NAME;
+    use crypto_market_type::MarketType;
+    use crypto_msg_parser::{parse_index_price, parse_mark_price};
+
+    #[test]
+    fn inverse_swap() {
+        let raw_msg = r#"{"table":"instrument","action":"update","data":[{"symbol":"XBTUSD","markPrice":29702.15,"indicativeSettlePrice":29695.72,"timestamp":"2022-06-04T11:08:30.000Z"},{"symbol":"XBTUSD","lastPrice":29701,"timestamp":"2022-06-04T11:08:30.100Z"},{"symbol":".BXBT","markPrice":29695.72,"timestamp":"2022-06-04T11:08:30.000Z"}]}"#;
+
+        // instruments without prices and indices are skipped
+        let mark_prices =
+            &parse_mark_price(EXCHANGE_NAME, MarketType::InverseSwap, raw_msg, None).unwrap();
+        assert_eq!(mark_prices.len(), 1);
+        assert_eq!(mark_prices[0].symbol, "XBTUSD");
+        assert_eq!(mark_prices[0].pair, "BTC/USD");
+        assert_eq!(mark_prices[0].timestamp, 1654340910000);
+        assert_eq!(mark_prices[0].price, 29702.15);
+        assert_eq!(mark_prices[0].json, raw_msg);
+
+        let index_prices =
+            &parse_index_price(EXCHANGE_NAME, MarketType::InverseSwap, raw_msg, None).unwrap();
+        assert_eq!(index_prices.len(), 1);
+        assert_eq!(index_prices[0].symbol, "XBTUSD");
+        assert_eq!(index_prices[0].price, 29695.72);
+    }
+
+    #[test]
+    fn without_timestamp() {
+        let raw_msg = r#"{"table":"instrument","action":"update","data":[{"symbol":"XBTUSD","markPrice":29702.15}]}"#;
+        assert!(parse_mark_price(EXCHANGE_NAME, MarketType::InverseSwap, raw_msg, None).is_err());
+
+        let mark_prices =
+            &parse_mark_price(EXCHANGE_NAME, MarketType::InverseSwap, raw_msg, Some(1654340910123))
+                .unwrap();
+        assert_eq!(mark_prices[0].timestamp, 1654340910123);
+    }
+}
+
+#[cfg(test)]
+mod parse {
+    use super::EXCHANGE_NAME;
+    use crypto_market_type::MarketType;
+    use crypto_msg_parser::parse;
+
+    #[test]
+    fn subscription_response() {
+        let raw_msg = r#"{"success":true,"subscribe":"trade:XBTUSD","request":{"op":"subscribe","args":["trade:XBTUSD"]}}"#;
+        assert!(parse(EXCHANGE_NAME, MarketType::InverseSwap, raw_msg, None).unwrap().is_empty());
+    }
+
+    #[test]
+    fn subscription_error() {
+        let raw_msg = r#"{"success":false,"error":"Unknown table: foo","request":{"op":"subscribe","args":["foo:XBTUSD"]}}"#;
+        assert!(parse(EXCHANGE_NAME, MarketType::InverseSwap, raw_msg, None).is_err());
+    }
+}
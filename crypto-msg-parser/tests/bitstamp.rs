@@ -1,8 +1,11 @@
 mod utils;
 
 use crypto_market_type::MarketType;
-use crypto_message::TradeSide;
-use crypto_msg_parser::{extract_symbol, extract_timestamp, parse_l2, parse_l2_topk, parse_trade};
+use crypto_message::{L3Action, OrderBook, TradeSide};
+use crypto_msg_parser::{
+    extract_symbol, extract_timestamp, get_msg_type, parse_candlestick, parse_l2, parse_l2_topk,
+    parse_l3, parse_trade, BboDeriver,
+};
 use crypto_msg_type::MessageType;
 
 const EXCHANGE_NAME: &str = "bitstamp";
@@ -123,6 +126,35 @@ fn l2_topk() {
     assert_eq!(orderbook.asks[2].quantity_contract, None);
 }
 
+#[test]
+fn derive_bbo() {
+    let raw_msg = r#"{"data":{"timestamp":"1653978373","microtimestamp":"1653978373164007","bids":[["31524.50","0.36400000"],["31521.05","0.23734197"],["31521.03","0.66028343"]],"asks":[["31535.44","0.31708837"],["31539.38","0.47520104"],["31543.37","0.01071471"]]},"channel":"order_book_btcusd","event":"data"}"#;
+    let orderbook = &parse_l2_topk(EXCHANGE_NAME, MarketType::Spot, raw_msg, None).unwrap()[0];
+
+    let bbo = crypto_msg_parser::derive_bbo(orderbook).unwrap();
+    assert_eq!(bbo.msg_type, MessageType::BBO);
+    assert_eq!(bbo.pair, "BTC/USD");
+    assert_eq!(bbo.timestamp, 1653978373164);
+    assert_eq!(bbo.bid_price, 31524.50);
+    assert_eq!(bbo.bid_quantity_base, 0.364);
+    assert_eq!(bbo.bid_quantity_quote, 31524.50 * 0.364);
+    assert_eq!(bbo.bid_quantity_contract, None);
+    assert_eq!(bbo.ask_price, 31535.44);
+    assert_eq!(bbo.ask_quantity_base, 0.31708837);
+
+    let mut deriver = BboDeriver::new();
+    assert!(deriver.on_orderbook(orderbook).is_some());
+    // the top of book doesn't change
+    assert!(deriver.on_orderbook(orderbook).is_none());
+
+    let mut book = OrderBook::new(EXCHANGE_NAME, MarketType::Spot, &orderbook.symbol);
+    book.apply(orderbook).unwrap();
+    let bbo = crypto_msg_parser::derive_bbo_from_book(&book).unwrap();
+    assert_eq!(bbo.bid_price, 31524.50);
+    assert_eq!(bbo.ask_price, 31535.44);
+    assert!(deriver.on_book(&book).is_none());
+}
+
 #[test]
 fn l3_event() {
     let raw_msg = r#"{"data":{"id":1496011283275781,"id_str":"1496011283275781","order_type":0,"datetime":"1654072104","microtimestamp":"1654072104363000","amount":7.9201,"amount_str":"7.92010000","price":31483.1,"price_str":"31483.10"},"channel":"live_orders_btcusd","event":"order_created"}"#;
@@ -132,15 +164,61 @@ fn l3_event() {
         extract_timestamp(EXCHANGE_NAME, MarketType::Spot, raw_msg).unwrap().unwrap()
     );
     assert_eq!("btcusd", extract_symbol(EXCHANGE_NAME, MarketType::Spot, raw_msg).unwrap());
+
+    let l3_msgs = parse_l3(EXCHANGE_NAME, MarketType::Spot, raw_msg, None).unwrap();
+    assert_eq!(1, l3_msgs.len());
+    let l3_msg = &l3_msgs[0];
+    assert_eq!(MessageType::L3Event, l3_msg.msg_type);
+    assert_eq!("BTC/USD", l3_msg.pair);
+    assert_eq!(1654072104363, l3_msg.timestamp);
+    assert_eq!("1496011283275781", l3_msg.order_id);
+    assert_eq!(L3Action::Open, l3_msg.action);
+    assert_eq!(Some(TradeSide::Buy), l3_msg.side);
+    assert_eq!(Some(31483.1), l3_msg.price);
+    assert_eq!(7.9201, l3_msg.quantity_base);
+    assert_eq!(31483.1 * 7.9201, l3_msg.quantity_quote);
 }
 
 #[test]
 fn l2_snapshot() {
     let raw_msg = r#"{"timestamp": "1654243213", "microtimestamp": "1654243213142992", "bids": [["30415.13", "0.37816633"], ["30415.11", "2.45236394"], ["30415.05", "0.21660771"], ["30413.74", "0.37055100"], ["30413.73", "0.10600000"]], "asks": [["30434.64", "0.26500000"], ["30434.73", "0.10600000"], ["30436.31", "0.19606825"], ["30436.48", "0.32839585"], ["30437.84", "0.19565692"]]}"#;
 
+    assert_eq!(MessageType::L2Snapshot, get_msg_type(EXCHANGE_NAME, raw_msg));
+
     assert_eq!(
         1654243213142,
         extract_timestamp(EXCHANGE_NAME, MarketType::Spot, raw_msg).unwrap().unwrap()
     );
     assert_eq!("NONE", extract_symbol(EXCHANGE_NAME, MarketType::Spot, raw_msg).unwrap());
 }
+
+#[test]
+fn candlestick() {
+    let raw_msg = r#"{"data": {"pair": "BTC/USD", "ohlc": [{"high": "30008", "timestamp": "1654243200", "volume": "3.51227302", "low": "29978.35", "close": "29998.5", "open": "29980.01"}, {"high": "30021.16", "timestamp": "1654243260", "volume": "1.09872140", "low": "29995.12", "close": "30018.62", "open": "29998.5"}]}}"#;
+
+    assert_eq!(MessageType::Candlestick, get_msg_type(EXCHANGE_NAME, raw_msg));
+    assert_eq!(
+        1654243260000,
+        extract_timestamp(EXCHANGE_NAME, MarketType::Spot, raw_msg).unwrap().unwrap()
+    );
+    assert_eq!("btcusd", extract_symbol(EXCHANGE_NAME, MarketType::Spot, raw_msg).unwrap());
+
+    let arr = parse_candlestick(EXCHANGE_NAME, MarketType::Spot, raw_msg, None, None).unwrap();
+    assert_eq!(2, arr.len());
+    let candlestick_msg = &arr[0];
+
+    assert_eq!("btcusd", candlestick_msg.symbol);
+    assert_eq!("BTC/USD", candlestick_msg.pair);
+    assert_eq!(1654243200000, candlestick_msg.timestamp);
+    assert_eq!(1654243200000, candlestick_msg.begin_time);
+    assert_eq!("1m", candlestick_msg.period.to_string());
+
+    assert_eq!(29980.01, candlestick_msg.open);
+    assert_eq!(30008.0, candlestick_msg.high);
+    assert_eq!(29978.35, candlestick_msg.low);
+    assert_eq!(29998.5, candlestick_msg.close);
+    assert_eq!(3.51227302, candlestick_msg.volume);
+    assert_eq!(None, candlestick_msg.quote_volume);
+
+    assert_eq!(1654243260000, arr[1].begin_time);
+}
@@ -2,7 +2,10 @@ mod utils;
 
 use crypto_market_type::MarketType;
 use crypto_message::TradeSide;
-use crypto_msg_parser::{extract_symbol, extract_timestamp, parse_l2, parse_trade};
+use crypto_msg_parser::{
+    extract_symbol, extract_timestamp, get_msg_type, parse_candlestick, parse_l2, parse_ticker,
+    parse_trade,
+};
 use crypto_msg_type::MessageType;
 
 #[test]
@@ -73,3 +76,58 @@ fn l2_orderbook_update() {
     assert_eq!(orderbook.bids[1].quantity_base, 0.2462);
     assert_eq!(orderbook.bids[1].quantity_quote, 9188.7207);
 }
+
+#[test]
+fn candlestick() {
+    let raw_msg = r#"{"msgId":0,"params":{"symbol":"btc_usdt","type":"1min","_CDID":"100002","dataType":"1"},"action":"Pushdata.kline","data":{"1min":["1616486100","53874.97","53890.12","53860.5","53875.82","12.3456","665123.4567","1min","btc_usdt"]},"time":1616486110921,"source":"sub-api"}"#;
+
+    assert_eq!(MessageType::Candlestick, get_msg_type("bitz", raw_msg));
+    assert_eq!(
+        1616486110921,
+        extract_timestamp("bitz", MarketType::Spot, raw_msg).unwrap().unwrap()
+    );
+    assert_eq!("btc_usdt", extract_symbol("bitz", MarketType::Spot, raw_msg).unwrap());
+
+    let arr = parse_candlestick("bitz", MarketType::Spot, raw_msg, None, None).unwrap();
+    assert_eq!(1, arr.len());
+    let candlestick_msg = &arr[0];
+
+    assert_eq!("btc_usdt", candlestick_msg.symbol);
+    assert_eq!("BTC/USDT", candlestick_msg.pair);
+    assert_eq!(1616486110921, candlestick_msg.timestamp);
+    assert_eq!(1616486100000, candlestick_msg.begin_time);
+    assert_eq!("1m", candlestick_msg.period.to_string());
+
+    assert_eq!(53874.97, candlestick_msg.open);
+    assert_eq!(53890.12, candlestick_msg.high);
+    assert_eq!(53860.5, candlestick_msg.low);
+    assert_eq!(53875.82, candlestick_msg.close);
+    assert_eq!(12.3456, candlestick_msg.volume);
+    assert_eq!(Some(665123.4567), candlestick_msg.quote_volume);
+}
+
+#[test]
+fn ticker() {
+    let raw_msg = r#"{"msgId":0,"params":{"symbol":"btc_usdt"},"action":"Pushdata.market","data":{"btc_usdt":{"symbol":"btc_usdt","quoteVolume":"1331853.0586","volume":"24.7283","priceChange":"-0.37","priceChange24h":"-0.37","askPrice":"53875.82","askQty":"0.1144","bidPrice":"53874.97","bidQty":"0.1310","open":"54074.05","high":"54398.41","low":"53021.34","now":"53874.97","firstId":1,"lastId":2,"dealCount":3}},"time":1616486110921,"source":"sub-api"}"#;
+
+    assert_eq!(MessageType::Ticker, get_msg_type("bitz", raw_msg));
+
+    let tickers = parse_ticker("bitz", MarketType::Spot, raw_msg, None).unwrap();
+    assert_eq!(1, tickers.len());
+    utils::check_ticker_fields(
+        "bitz",
+        MarketType::Spot,
+        "BTC/USDT".to_string(),
+        "btc_usdt".to_string(),
+        &tickers[0],
+        raw_msg,
+    );
+    assert_eq!(1616486110921, tickers[0].timestamp);
+    assert_eq!(54074.05, tickers[0].open);
+    assert_eq!(53874.97, tickers[0].close);
+    assert_eq!(24.7283, tickers[0].volume);
+    assert_eq!(1331853.0586, tickers[0].quote_volume);
+    assert_eq!(Some(53874.97), tickers[0].best_bid_price);
+    assert_eq!(Some(0.1144), tickers[0].best_ask_quantity);
+    assert_eq!(raw_msg, tickers[0].json);
+}
@@ -99,6 +99,59 @@ mod trade {
     }
 }
 
+#[cfg(test)]
+mod liquidation {
+    use super::EXCHANGE_NAME;
+    use crypto_market_type::MarketType;
+    use crypto_message::TradeSide;
+    use crypto_msg_parser::{parse_liquidation, round};
+
+    #[test]
+    fn inverse_swap() {
+        let raw_msg = r#"{"topic":"liquidation.BTCUSD","data":{"symbol":"BTCUSD","side":"Buy","price":"42318.50","qty":"1500","time":1643022337851}}"#;
+        let liquidations =
+            &parse_liquidation(EXCHANGE_NAME, MarketType::InverseSwap, raw_msg, None).unwrap();
+
+        assert_eq!(liquidations.len(), 1);
+        let liquidation = &liquidations[0];
+        crate::utils::check_liquidation_fields(
+            EXCHANGE_NAME,
+            MarketType::InverseSwap,
+            "BTC/USD".to_string(),
+            liquidation,
+        );
+
+        assert_eq!(liquidation.timestamp, 1643022337851);
+        // a long position is liquidated by a sell order
+        assert_eq!(liquidation.side, TradeSide::Sell);
+        assert_eq!(liquidation.price, 42318.5);
+        assert_eq!(liquidation.quantity_contract, Some(1500.0));
+        assert_eq!(liquidation.quantity_quote, 1500.0);
+        assert_eq!(round(liquidation.quantity_base), round(1500.0 / 42318.5));
+    }
+
+    #[test]
+    fn linear_swap() {
+        let raw_msg = r#"{"topic":"liquidation.ETHUSDT","data":{"symbol":"ETHUSDT","side":"Sell","price":"2448.55","qty":"0.52","time":1643022425402}}"#;
+        let liquidations =
+            &parse_liquidation(EXCHANGE_NAME, MarketType::LinearSwap, raw_msg, None).unwrap();
+
+        assert_eq!(liquidations.len(), 1);
+        let liquidation = &liquidations[0];
+        crate::utils::check_liquidation_fields(
+            EXCHANGE_NAME,
+            MarketType::LinearSwap,
+            "ETH/USDT".to_string(),
+            liquidation,
+        );
+
+        assert_eq!(liquidation.side, TradeSide::Buy);
+        assert_eq!(liquidation.quantity_base, 0.52);
+        assert_eq!(round(liquidation.quantity_quote), round(2448.55 * 0.52));
+        assert_eq!(liquidation.quantity_contract, Some(0.52));
+    }
+}
+
 #[cfg(test)]
 mod l2_orderbook {
     use super::EXCHANGE_NAME;
@@ -391,16 +444,18 @@ mod candlestick {
             extract_symbol(EXCHANGE_NAME, MarketType::InverseFuture, raw_msg).unwrap()
         );
 
-        let arr =
-            parse_candlestick(EXCHANGE_NAME, MarketType::InverseFuture, raw_msg, None).unwrap();
+        let arr = parse_candlestick(EXCHANGE_NAME, MarketType::InverseFuture, raw_msg, None, None)
+            .unwrap();
         assert_eq!(1, arr.len());
 
         let candlestick_msg = &arr[0];
 
         assert_eq!("BTCUSDM22", candlestick_msg.symbol);
         assert_eq!(1654078470426, candlestick_msg.timestamp);
-        assert_eq!(1654078440, candlestick_msg.begin_time);
-        assert_eq!("1", candlestick_msg.period);
+        assert_eq!(1654078440000, candlestick_msg.begin_time);
+        assert_eq!(1654078500000, candlestick_msg.end_time);
+        assert_eq!("1m", candlestick_msg.period.to_string());
+        assert_eq!(Some(false), candlestick_msg.is_closed);
 
         assert_eq!(31633.0, candlestick_msg.open);
         assert_eq!(31633.0, candlestick_msg.high);
@@ -423,15 +478,16 @@ mod candlestick {
             extract_symbol(EXCHANGE_NAME, MarketType::InverseSwap, raw_msg).unwrap()
         );
 
-        let arr = parse_candlestick(EXCHANGE_NAME, MarketType::InverseSwap, raw_msg, None).unwrap();
+        let arr =
+            parse_candlestick(EXCHANGE_NAME, MarketType::InverseSwap, raw_msg, None, None).unwrap();
         assert_eq!(1, arr.len());
 
         let candlestick_msg = &arr[0];
 
         assert_eq!("BTCUSD", candlestick_msg.symbol);
         assert_eq!(1654078824173, candlestick_msg.timestamp);
-        assert_eq!(1654078800, candlestick_msg.begin_time);
-        assert_eq!("1", candlestick_msg.period);
+        assert_eq!(1654078800000, candlestick_msg.begin_time);
+        assert_eq!("1m", candlestick_msg.period.to_string());
 
         assert_eq!(31570.5, candlestick_msg.open);
         assert_eq!(31571.0, candlestick_msg.high);
@@ -454,15 +510,16 @@ mod candlestick {
             extract_symbol(EXCHANGE_NAME, MarketType::LinearSwap, raw_msg).unwrap()
         );
 
-        let arr = parse_candlestick(EXCHANGE_NAME, MarketType::LinearSwap, raw_msg, None).unwrap();
+        let arr =
+            parse_candlestick(EXCHANGE_NAME, MarketType::LinearSwap, raw_msg, None, None).unwrap();
         assert_eq!(1, arr.len());
 
         let candlestick_msg = &arr[0];
 
         assert_eq!("BTCUSDT", candlestick_msg.symbol);
         assert_eq!(1654078873190, candlestick_msg.timestamp);
-        assert_eq!(1654078860, candlestick_msg.begin_time);
-        assert_eq!("1", candlestick_msg.period);
+        assert_eq!(1654078860000, candlestick_msg.begin_time);
+        assert_eq!("1m", candlestick_msg.period.to_string());
 
         assert_eq!(31604.5, candlestick_msg.open);
         assert_eq!(31605.0, candlestick_msg.high);
@@ -477,7 +534,7 @@ mod candlestick {
 mod ticker {
     use super::EXCHANGE_NAME;
     use crypto_market_type::MarketType;
-    use crypto_msg_parser::{extract_symbol, extract_timestamp};
+    use crypto_msg_parser::{extract_symbol, extract_timestamp, parse_ticker};
 
     #[test]
     fn inverse_future_snapshot() {
@@ -491,6 +548,18 @@ mod ticker {
             "BTCUSDM22",
             extract_symbol(EXCHANGE_NAME, MarketType::InverseFuture, raw_msg).unwrap()
         );
+
+        let tickers =
+            &parse_ticker(EXCHANGE_NAME, MarketType::InverseFuture, raw_msg, None).unwrap();
+        assert_eq!(1, tickers.len());
+        crate::utils::check_ticker_fields(
+            EXCHANGE_NAME,
+            MarketType::InverseFuture,
+            "BTC/USD".to_string(),
+            "BTCUSDM22".to_string(),
+            &tickers[0],
+            raw_msg,
+        );
     }
 
     #[test]
@@ -505,6 +574,12 @@ mod ticker {
             "BTCUSDM22",
             extract_symbol(EXCHANGE_NAME, MarketType::InverseFuture, raw_msg).unwrap()
         );
+
+        assert!(
+            parse_ticker(EXCHANGE_NAME, MarketType::InverseFuture, raw_msg, None)
+                .unwrap()
+                .is_empty()
+        );
     }
 
     #[test]
@@ -519,6 +594,17 @@ mod ticker {
             "BTCUSD",
             extract_symbol(EXCHANGE_NAME, MarketType::InverseSwap, raw_msg).unwrap()
         );
+
+        let tickers = &parse_ticker(EXCHANGE_NAME, MarketType::InverseSwap, raw_msg, None).unwrap();
+        assert_eq!(1, tickers.len());
+        crate::utils::check_ticker_fields(
+            EXCHANGE_NAME,
+            MarketType::InverseSwap,
+            "BTC/USD".to_string(),
+            "BTCUSD".to_string(),
+            &tickers[0],
+            raw_msg,
+        );
     }
 
     #[test]
@@ -533,6 +619,10 @@ mod ticker {
             "BTCUSD",
             extract_symbol(EXCHANGE_NAME, MarketType::InverseSwap, raw_msg).unwrap()
         );
+
+        assert!(
+            parse_ticker(EXCHANGE_NAME, MarketType::InverseSwap, raw_msg, None).unwrap().is_empty()
+        );
     }
 
     #[test]
@@ -547,6 +637,17 @@ mod ticker {
             "BTCUSDT",
             extract_symbol(EXCHANGE_NAME, MarketType::LinearSwap, raw_msg).unwrap()
         );
+
+        let tickers = &parse_ticker(EXCHANGE_NAME, MarketType::LinearSwap, raw_msg, None).unwrap();
+        assert_eq!(1, tickers.len());
+        crate::utils::check_ticker_fields(
+            EXCHANGE_NAME,
+            MarketType::LinearSwap,
+            "BTC/USDT".to_string(),
+            "BTCUSDT".to_string(),
+            &tickers[0],
+            raw_msg,
+        );
     }
 
     #[test]
@@ -561,6 +662,10 @@ mod ticker {
             "BTCUSDT",
             extract_symbol(EXCHANGE_NAME, MarketType::LinearSwap, raw_msg).unwrap()
         );
+
+        assert!(
+            parse_ticker(EXCHANGE_NAME, MarketType::LinearSwap, raw_msg, None).unwrap().is_empty()
+        );
     }
 }
 
@@ -619,7 +724,7 @@ mod l2_snapshot {
 mod open_interest {
     use super::EXCHANGE_NAME;
     use crypto_market_type::MarketType;
-    use crypto_msg_parser::{extract_symbol, extract_timestamp};
+    use crypto_msg_parser::{extract_symbol, extract_timestamp, parse_open_interest};
 
     #[test]
     fn inverse_future() {
@@ -649,6 +754,16 @@ mod open_interest {
             1654338600495,
             extract_timestamp(EXCHANGE_NAME, MarketType::InverseSwap, raw_msg).unwrap().unwrap()
         );
+
+        let open_interests =
+            &parse_open_interest(EXCHANGE_NAME, MarketType::InverseSwap, raw_msg, None, None)
+                .unwrap();
+        assert_eq!(open_interests.len(), 3);
+        assert_eq!(open_interests[0].pair, "BTC/USD");
+        assert_eq!(open_interests[0].timestamp, 1654338300000);
+        assert_eq!(open_interests[0].quantity_contract, 645245219.0);
+        assert_eq!(open_interests[0].quantity_quote, Some(645245219.0));
+        assert_eq!(open_interests[0].quantity_base, None);
     }
 
     #[test]
@@ -665,3 +780,109 @@ mod open_interest {
         );
     }
 }
+
+#[cfg(test)]
+mod funding_rate {
+    use super::EXCHANGE_NAME;
+    use crypto_market_type::MarketType;
+    use crypto_msg_parser::{extract_symbol, extract_timestamp, get_msg_type, parse_funding_rate};
+    use crypto_msg_type::MessageType;
+
+    #[test]
+    fn inverse_swap() {
+        let raw_msg = r#"{"topic":"instrument_info.100ms.BTCUSD","type":"snapshot","data":{"id":1,"symbol":"BTCUSD","last_price_e4":299305000,"last_price":"29930.50","bid1_price_e4":299305000,"bid1_price":"29930.50","ask1_price_e4":299310000,"ask1_price":"29931.00","last_tick_direction":"ZeroMinusTick","prev_price_24h_e4":315895000,"prev_price_24h":"31589.50","price_24h_pcnt_e6":-52517,"high_price_24h_e4":318740000,"high_price_24h":"31874.00","low_price_24h_e4":292780000,"low_price_24h":"29278.00","prev_price_1h_e4":299600000,"prev_price_1h":"29960.00","price_1h_pcnt_e6":-984,"mark_price_e4":299463400,"mark_price":"29946.34","index_price_e4":299461300,"index_price":"29946.13","open_interest":654525102,"open_value_e8":1461680310351,"total_turnover_e8":10586114469373775,"turnover_24h_e8":5730317572180,"total_volume":2747007694755,"volume_24h":1745270625,"funding_rate_e6":8,"predicted_funding_rate_e6":-126,"cross_seq":13458633262,"created_at":"2018-11-14T16:33:26Z","updated_at":"2022-06-02T09:18:34Z","next_funding_time":"2022-06-02T16:00:00Z","countdown_hour":7,"funding_rate_interval":8,"settle_time_e9":0,"delisting_status":"0"},"cross_seq":13458633487,"timestamp_e6":1654161517001968}"#;
+        let funding_rates =
+            &parse_funding_rate(EXCHANGE_NAME, MarketType::InverseSwap, raw_msg, None).unwrap();
+
+        assert_eq!(funding_rates.len(), 1);
+        // funding rates are carried by tickers
+        assert_eq!(MessageType::Ticker, get_msg_type(EXCHANGE_NAME, raw_msg));
+
+        assert_eq!(funding_rates[0].symbol, "BTCUSD");
+        assert_eq!(funding_rates[0].pair, "BTC/USD");
+        assert_eq!(funding_rates[0].funding_rate, 0.000008);
+        assert_eq!(funding_rates[0].estimated_rate, Some(-0.000126));
+        assert_eq!(funding_rates[0].funding_time, 1654185600000);
+        assert_eq!(funding_rates[0].funding_interval, 8 * 3600000);
+        assert_eq!(funding_rates[0].annualized_rate, 0.00876);
+        assert_eq!(funding_rates[0].timestamp, 1654161517001);
+    }
+
+    #[test]
+    fn linear_swap() {
+        let raw_msg = r#"{"topic":"instrument_info.100ms.BTCUSDT","type":"snapshot","data":{"id":1,"symbol":"BTCUSDT","last_price_e4":"299590000","last_price":"29959.00","bid1_price_e4":"299585000","bid1_price":"29958.50","ask1_price_e4":"299590000","ask1_price":"29959.00","last_tick_direction":"ZeroPlusTick","prev_price_24h_e4":"315980000","prev_price_24h":"31598.00","price_24h_pcnt_e6":"-51870","high_price_24h_e4":"318945000","high_price_24h":"31894.50","low_price_24h_e4":"292910000","low_price_24h":"29291.00","prev_price_1h_e4":"299735000","prev_price_1h":"29973.50","price_1h_pcnt_e6":"-483","mark_price_e4":"299770300","mark_price":"29977.03","index_price_e4":"299781200","index_price":"29978.12","open_interest_e8":"2818586500000","total_turnover_e8":"1313857544013650000","turnover_24h_e8":"508351634095750100","total_volume_e8":"3500773405099924","volume_24h_e8":"16697641599999","funding_rate_e6":"-43","predicted_funding_rate_e6":"-87","cross_seq":"12230809673","created_at":"1970-01-01T00:00:00.000Z","updated_at":"2022-06-02T09:19:37.000Z","next_funding_time":"2022-06-02T16:00:00Z","count_down_hour":"7","funding_rate_interval":"8","settle_time_e9":"0","delisting_status":"0"},"cross_seq":"12230809709","timestamp_e6":"1654161577978011"}"#;
+        let funding_rates =
+            &parse_funding_rate(EXCHANGE_NAME, MarketType::LinearSwap, raw_msg, None).unwrap();
+
+        assert_eq!(funding_rates.len(), 1);
+        assert_eq!(funding_rates[0].pair, "BTC/USDT");
+        assert_eq!(funding_rates[0].funding_rate, -0.000043);
+        assert_eq!(funding_rates[0].estimated_rate, Some(-0.000087));
+        assert_eq!(funding_rates[0].funding_time, 1654185600000);
+        assert_eq!(funding_rates[0].timestamp, 1654161577978);
+    }
+
+    #[test]
+    fn linear_swap_update() {
+        let raw_msg = r#"{"topic":"instrument_info.100ms.BTCUSDT","type":"delta","data":{"update":[{"id":1,"symbol":"BTCUSDT","index_price_e4":"299781300","index_price":"29978.13","cross_seq":"12230809673","created_at":"1970-01-01T00:00:00.000Z","updated_at":"2022-06-02T09:19:37.000Z"}]},"cross_seq":"12230809787","timestamp_e6":"1654161578478930"}"#;
+
+        assert!(
+            parse_funding_rate(EXCHANGE_NAME, MarketType::LinearSwap, raw_msg, None)
+                .unwrap()
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn inverse_swap_rest() {
+        let raw_msg = r#"{"ret_code":0,"ret_msg":"OK","ext_code":"","ext_info":"","result":{"symbol":"BTCUSD","funding_rate":"0.00001","funding_rate_timestamp":1654156800},"time_now":"1654161600.123456","rate_limit_status":119,"rate_limit_reset_ms":1654161600125,"rate_limit":120}"#;
+        let funding_rates =
+            &parse_funding_rate(EXCHANGE_NAME, MarketType::InverseSwap, raw_msg, None).unwrap();
+
+        assert_eq!(funding_rates.len(), 1);
+        crate::utils::check_funding_rate_fields(
+            EXCHANGE_NAME,
+            MarketType::InverseSwap,
+            &funding_rates[0],
+            raw_msg,
+        );
+        assert_eq!(
+            "BTCUSD",
+            extract_symbol(EXCHANGE_NAME, MarketType::InverseSwap, raw_msg).unwrap()
+        );
+        assert_eq!(
+            1654161600123,
+            extract_timestamp(EXCHANGE_NAME, MarketType::InverseSwap, raw_msg).unwrap().unwrap()
+        );
+
+        assert_eq!(funding_rates[0].pair, "BTC/USD");
+        assert_eq!(funding_rates[0].funding_rate, 0.00001);
+        assert_eq!(funding_rates[0].estimated_rate, None);
+        assert_eq!(funding_rates[0].funding_time, 1654156800000);
+        assert_eq!(funding_rates[0].timestamp, 1654161600123);
+    }
+
+    #[test]
+    fn linear_swap_rest() {
+        let raw_msg = r#"{"ret_code":0,"ret_msg":"OK","ext_code":"","ext_info":"","result":{"symbol":"BTCUSDT","funding_rate":-0.00004327,"funding_rate_timestamp":"2022-06-02T08:00:00.000Z"},"time_now":"1654161600.223456"}"#;
+        let funding_rates =
+            &parse_funding_rate(EXCHANGE_NAME, MarketType::LinearSwap, raw_msg, None).unwrap();
+
+        assert_eq!(funding_rates.len(), 1);
+        crate::utils::check_funding_rate_fields(
+            EXCHANGE_NAME,
+            MarketType::LinearSwap,
+            &funding_rates[0],
+            raw_msg,
+        );
+        assert_eq!(
+            "BTCUSDT",
+            extract_symbol(EXCHANGE_NAME, MarketType::LinearSwap, raw_msg).unwrap()
+        );
+
+        assert_eq!(funding_rates[0].pair, "BTC/USDT");
+        assert_eq!(funding_rates[0].funding_rate, -0.00004327);
+        assert_eq!(funding_rates[0].funding_time, 1654156800000);
+        assert_eq!(funding_rates[0].timestamp, 1654161600223);
+    }
+}
@@ -0,0 +1,81 @@
+//! `parse_candle_period()` over the interval notations of all exchanges.
+
+use crypto_market_type::MarketType;
+use crypto_msg_parser::parse_candle_period;
+
+fn parse(exchange: &str, market_type: MarketType, period: &str) -> Option<String> {
+    parse_candle_period(exchange, market_type, period).map(|period| period.to_string())
+}
+
+#[test]
+fn binance() {
+    assert_eq!(Some("1s".to_string()), parse("binance", MarketType::Spot, "1s"));
+    assert_eq!(Some("1m".to_string()), parse("binance", MarketType::Spot, "1m"));
+    assert_eq!(Some("4h".to_string()), parse("binance", MarketType::Spot, "4h"));
+    assert_eq!(Some("1w".to_string()), parse("binance", MarketType::LinearSwap, "1w"));
+    assert_eq!(Some("1M".to_string()), parse("binance", MarketType::LinearSwap, "1M"));
+}
+
+#[test]
+fn okx() {
+    assert_eq!(Some("1m".to_string()), parse("okx", MarketType::Spot, "candle1m"));
+    assert_eq!(Some("1h".to_string()), parse("okx", MarketType::Spot, "candle1H"));
+    assert_eq!(Some("1d".to_string()), parse("okx", MarketType::Spot, "candle1Dutc"));
+    assert_eq!(Some("3M".to_string()), parse("okx", MarketType::LinearSwap, "candle3M"));
+    assert_eq!(Some("1m".to_string()), parse("bitget", MarketType::LinearSwap, "candle60s"));
+}
+
+#[test]
+fn bitmex() {
+    assert_eq!(Some("1m".to_string()), parse("bitmex", MarketType::InverseSwap, "tradeBin1m"));
+    assert_eq!(Some("1h".to_string()), parse("bitmex", MarketType::InverseSwap, "tradeBin1h"));
+    assert_eq!(Some("1d".to_string()), parse("bitmex", MarketType::InverseSwap, "tradeBin1d"));
+}
+
+#[test]
+fn bare_numbers() {
+    // minutes
+    assert_eq!(Some("1h".to_string()), parse("kraken", MarketType::Spot, "60"));
+    assert_eq!(Some("15d".to_string()), parse("kraken", MarketType::Spot, "21600"));
+    assert_eq!(Some("3m".to_string()), parse("bybit", MarketType::LinearSwap, "3"));
+    assert_eq!(Some("1d".to_string()), parse("deribit", MarketType::InverseSwap, "1440"));
+    // seconds
+    assert_eq!(Some("1m".to_string()), parse("bitstamp", MarketType::Spot, "60"));
+    assert_eq!(Some("6h".to_string()), parse("coinbase_pro", MarketType::Spot, "21600"));
+    // milliseconds
+    assert_eq!(Some("3m".to_string()), parse("zbg", MarketType::InverseSwap, "180000"));
+    // no unit
+    assert_eq!(None, parse("binance", MarketType::Spot, "60"));
+    assert_eq!(None, parse("kraken", MarketType::InverseFuture, "60"));
+}
+
+#[test]
+fn words() {
+    assert_eq!(Some("1m".to_string()), parse("huobi", MarketType::Spot, "1min"));
+    assert_eq!(Some("1M".to_string()), parse("huobi", MarketType::Spot, "1mon"));
+    assert_eq!(Some("12M".to_string()), parse("huobi", MarketType::Spot, "1year"));
+    assert_eq!(Some("1w".to_string()), parse("kucoin", MarketType::Spot, "1week"));
+    assert_eq!(Some("4h".to_string()), parse("dydx", MarketType::LinearSwap, "4HOURS"));
+    assert_eq!(Some("15m".to_string()), parse("mexc", MarketType::Spot, "Min15"));
+    assert_eq!(Some("1M".to_string()), parse("mexc", MarketType::LinearSwap, "Month1"));
+    assert_eq!(Some("1h".to_string()), parse("bithumb", MarketType::Spot, "h1"));
+    assert_eq!(Some("1d".to_string()), parse("bybit", MarketType::InverseSwap, "D"));
+    assert_eq!(Some("1M".to_string()), parse("bybit", MarketType::InverseSwap, "M"));
+}
+
+#[test]
+fn minutes_in_uppercase() {
+    assert_eq!(Some("1m".to_string()), parse("zb", MarketType::LinearSwap, "1M"));
+    assert_eq!(Some("1h".to_string()), parse("zb", MarketType::LinearSwap, "1H"));
+    assert_eq!(Some("1m".to_string()), parse("zbg", MarketType::Spot, "1M"));
+    assert_eq!(Some("1M".to_string()), parse("bitfinex", MarketType::Spot, "1M"));
+}
+
+#[test]
+fn invalid() {
+    assert_eq!(None, parse("binance", MarketType::Spot, ""));
+    assert_eq!(None, parse("binance", MarketType::Spot, "0m"));
+    assert_eq!(None, parse("binance", MarketType::Spot, "1x"));
+    assert_eq!(None, parse("binance", MarketType::Spot, "1m5"));
+    assert_eq!(None, parse("zbg", MarketType::InverseSwap, "1500"));
+}
@@ -2,8 +2,11 @@ mod utils;
 
 use chrono::prelude::*;
 use crypto_market_type::MarketType;
-use crypto_message::TradeSide;
-use crypto_msg_parser::{extract_symbol, extract_timestamp, parse_l2, parse_trade};
+use crypto_message::{L3Action, TradeSide};
+use crypto_msg_parser::{
+    extract_symbol, extract_timestamp, get_msg_type, parse_candlestick, parse_l2, parse_l3,
+    parse_ticker, parse_trade,
+};
 
 use crypto_msg_type::MessageType;
 
@@ -110,6 +113,30 @@ fn l3_event() {
         extract_timestamp(EXCHANGE_NAME, MarketType::Spot, raw_msg).unwrap().unwrap()
     );
     assert_eq!("BTC-USD", extract_symbol(EXCHANGE_NAME, MarketType::Spot, raw_msg).unwrap());
+
+    let l3_msgs = parse_l3(EXCHANGE_NAME, MarketType::Spot, raw_msg, None).unwrap();
+    assert_eq!(1, l3_msgs.len());
+    let l3_msg = &l3_msgs[0];
+    assert_eq!(MessageType::L3Event, l3_msg.msg_type);
+    assert_eq!("BTC/USD", l3_msg.pair);
+    assert_eq!(1654072341469, l3_msg.timestamp);
+    assert_eq!("5816ff12-61fc-4ab0-877a-fdf88544a4ee", l3_msg.order_id);
+    assert_eq!(L3Action::Open, l3_msg.action);
+    assert_eq!(Some(TradeSide::Sell), l3_msg.side);
+    assert_eq!(Some(31572.35), l3_msg.price);
+    assert_eq!(0.23, l3_msg.quantity_base);
+    assert_eq!(Some(38292760991), l3_msg.seq_id);
+
+    let raw_msg = r#"{"type":"match","trade_id":347875517,"maker_order_id":"5816ff12-61fc-4ab0-877a-fdf88544a4ee","taker_order_id":"a8a1e4a2-7b1e-4d2c-9a5b-3c0a5c1e7c8d","side":"sell","size":"0.1","price":"31572.35","product_id":"BTC-USD","sequence":38292760995,"time":"2022-06-01T08:32:22.469151Z"}"#;
+    let l3_msg = &parse_l3(EXCHANGE_NAME, MarketType::Spot, raw_msg, None).unwrap()[0];
+    assert_eq!("5816ff12-61fc-4ab0-877a-fdf88544a4ee", l3_msg.order_id);
+    assert_eq!(L3Action::Match, l3_msg.action);
+    assert_eq!(0.1, l3_msg.quantity_base);
+
+    let raw_msg = r#"{"order_id":"5816ff12-61fc-4ab0-877a-fdf88544a4ee","reason":"canceled","price":"31572.35","remaining_size":"0.13","type":"done","side":"sell","product_id":"BTC-USD","time":"2022-06-01T08:32:23.469151Z","sequence":38292760999}"#;
+    let l3_msg = &parse_l3(EXCHANGE_NAME, MarketType::Spot, raw_msg, None).unwrap()[0];
+    assert_eq!(L3Action::Done, l3_msg.action);
+    assert_eq!(0.13, l3_msg.quantity_base);
 }
 
 #[test]
@@ -121,13 +148,67 @@ fn ticker() {
         extract_timestamp(EXCHANGE_NAME, MarketType::Spot, raw_msg).unwrap().unwrap()
     );
     assert_eq!("BTC-USD", extract_symbol(EXCHANGE_NAME, MarketType::Spot, raw_msg).unwrap());
+
+    let tickers = &parse_ticker(EXCHANGE_NAME, MarketType::Spot, raw_msg, None).unwrap();
+    assert_eq!(1, tickers.len());
+    utils::check_ticker_fields(
+        EXCHANGE_NAME,
+        MarketType::Spot,
+        "BTC/USD".to_string(),
+        "BTC-USD".to_string(),
+        &tickers[0],
+        raw_msg,
+    );
+    assert_eq!(1654161654127, tickers[0].timestamp);
+    assert_eq!(31677.61, tickers[0].open);
+    assert_eq!(31888.0, tickers[0].high);
+    assert_eq!(29308.01, tickers[0].low);
+    assert_eq!(29940.91, tickers[0].close);
+    assert_eq!(27783.70216674, tickers[0].volume);
+    assert_eq!(Some(0.00061522), tickers[0].last_quantity);
+    assert_eq!(Some(29940.90), tickers[0].best_bid_price);
+    assert_eq!(Some(29940.91), tickers[0].best_ask_price);
 }
 
 #[test]
 fn l2_snapshot() {
     let raw_msg = r#"{"bids": [["0.1135", "35", 1], ["0.1134", "20606.7", 5], ["0.1133", "41561.8", 8], ["0.1132", "51132.8", 4], ["0.1131", "745", 2]], "asks": [["0.1137", "10113.4", 4], ["0.1138", "49781.3", 6], ["0.1139", "34339.9", 6], ["0.114", "34409.1", 4], ["0.1141", "4126.6", 2]], "sequence": 406959136, "auction_mode": false, "auction": null}"#;
 
+    assert_eq!(MessageType::L2Snapshot, get_msg_type(EXCHANGE_NAME, raw_msg));
+
     assert_eq!("NONE", extract_symbol(EXCHANGE_NAME, MarketType::Spot, raw_msg).unwrap());
 
     assert_eq!(None, extract_timestamp(EXCHANGE_NAME, MarketType::Spot, raw_msg).unwrap());
 }
+
+#[test]
+fn candlestick() {
+    let raw_msg = r#"[[1654243260,29995.12,30021.16,29998.5,30018.62,1.0987214],[1654243200,29978.35,30008,29980.01,29998.5,3.51227302]]"#;
+
+    assert_eq!(MessageType::Candlestick, get_msg_type(EXCHANGE_NAME, raw_msg));
+    assert_eq!(
+        1654243260000,
+        extract_timestamp(EXCHANGE_NAME, MarketType::Spot, raw_msg).unwrap().unwrap()
+    );
+    assert_eq!("NONE", extract_symbol(EXCHANGE_NAME, MarketType::Spot, raw_msg).unwrap());
+
+    // the product id is not in the message
+    assert!(parse_candlestick(EXCHANGE_NAME, MarketType::Spot, raw_msg, None, None).is_err());
+    let arr =
+        parse_candlestick(EXCHANGE_NAME, MarketType::Spot, raw_msg, Some("BTC-USD"), None).unwrap();
+    assert_eq!(2, arr.len());
+    let candlestick_msg = &arr[0];
+
+    assert_eq!("BTC-USD", candlestick_msg.symbol);
+    assert_eq!("BTC/USD", candlestick_msg.pair);
+    assert_eq!(1654243260000, candlestick_msg.timestamp);
+    assert_eq!(1654243260000, candlestick_msg.begin_time);
+    assert_eq!("1m", candlestick_msg.period.to_string());
+
+    assert_eq!(29998.5, candlestick_msg.open);
+    assert_eq!(30021.16, candlestick_msg.high);
+    assert_eq!(29995.12, candlestick_msg.low);
+    assert_eq!(30018.62, candlestick_msg.close);
+    assert_eq!(1.0987214, candlestick_msg.volume);
+    assert_eq!(None, candlestick_msg.quote_volume);
+}
@@ -102,6 +102,66 @@ mod trade {
     }
 }
 
+#[cfg(test)]
+mod mark_price {
+    use super::EXCHANGE_NAME;
+    use crypto_market_type::MarketType;
+    use crypto_msg_parser::{extract_symbol, extract_timestamp, parse_mark_price};
+
+    #[test]
+    fn option() {
+        let raw_msg = r#"{"jsonrpc":"2.0","method":"subscription","params":{"channel":"markprice.options.btc_usd","data":[{"timestamp":1654341866165,"mark_price":0.0531,"iv":0.7632,"instrument_name":"BTC-24JUN22-30000-C"},{"timestamp":1654341866165,"mark_price":1.9843,"iv":1.1275,"instrument_name":"BTC-30DEC22-90000-P"}]}}"#;
+        let mark_prices =
+            &parse_mark_price(EXCHANGE_NAME, MarketType::EuropeanOption, raw_msg, None).unwrap();
+
+        assert_eq!(mark_prices.len(), 2);
+        assert_eq!(mark_prices[0].symbol, "BTC-24JUN22-30000-C");
+        assert_eq!(mark_prices[0].pair, "BTC/BTC");
+        assert_eq!(mark_prices[0].timestamp, 1654341866165);
+        assert_eq!(mark_prices[0].price, 0.0531);
+        assert_eq!(mark_prices[1].price, 1.9843);
+
+        assert_eq!(
+            "ALL",
+            extract_symbol(EXCHANGE_NAME, MarketType::EuropeanOption, raw_msg).unwrap()
+        );
+        assert_eq!(
+            1654341866165,
+            extract_timestamp(EXCHANGE_NAME, MarketType::EuropeanOption, raw_msg).unwrap().unwrap()
+        );
+    }
+}
+
+#[cfg(test)]
+mod index_price {
+    use super::EXCHANGE_NAME;
+    use crypto_market_type::MarketType;
+    use crypto_msg_parser::{extract_symbol, extract_timestamp, parse_index_price};
+
+    #[test]
+    fn inverse_swap() {
+        let raw_msg = r#"{"jsonrpc":"2.0","method":"subscription","params":{"channel":"deribit_price_index.btc_usd","data":{"timestamp":1654341866080,"price":29695.49,"index_name":"btc_usd"}}}"#;
+        let index_prices =
+            &parse_index_price(EXCHANGE_NAME, MarketType::InverseSwap, raw_msg, None).unwrap();
+
+        assert_eq!(index_prices.len(), 1);
+        assert_eq!(index_prices[0].symbol, "btc_usd");
+        assert_eq!(index_prices[0].pair, "BTC/USD");
+        assert_eq!(index_prices[0].timestamp, 1654341866080);
+        assert_eq!(index_prices[0].price, 29695.49);
+        assert_eq!(index_prices[0].json, raw_msg);
+
+        assert_eq!(
+            "btc_usd",
+            extract_symbol(EXCHANGE_NAME, MarketType::InverseSwap, raw_msg).unwrap()
+        );
+        assert_eq!(
+            1654341866080,
+            extract_timestamp(EXCHANGE_NAME, MarketType::InverseSwap, raw_msg).unwrap().unwrap()
+        );
+    }
+}
+
 #[cfg(test)]
 mod l2_event {
     use super::EXCHANGE_NAME;
@@ -588,8 +648,8 @@ mod candlestick {
     #[test]
     fn inverse_future() {
         let raw_msg = r#"{"jsonrpc":"2.0","method":"subscription","params":{"channel":"chart.trades.BTC-31MAR23.1","data":{"volume":0.00172302,"tick":1677628800000,"open":23215.0,"low":23215.0,"high":23215.0,"cost":40.0,"close":23215.0}}}"#;
-        let arr =
-            parse_candlestick(EXCHANGE_NAME, MarketType::InverseFuture, raw_msg, None).unwrap();
+        let arr = parse_candlestick(EXCHANGE_NAME, MarketType::InverseFuture, raw_msg, None, None)
+            .unwrap();
 
         assert_eq!(
             1677628800000,
@@ -606,7 +666,7 @@ mod candlestick {
         assert_eq!("BTC/USD", candlestick_msg.pair);
         assert_eq!(1677628800000, candlestick_msg.timestamp);
         assert_eq!(1677628740000, candlestick_msg.begin_time);
-        assert_eq!("1", candlestick_msg.period);
+        assert_eq!("1m", candlestick_msg.period.to_string());
 
         assert_eq!(23215.0, candlestick_msg.open);
         assert_eq!(23215.0, candlestick_msg.high);
@@ -619,8 +679,8 @@ mod candlestick {
     #[test]
     fn inverse_swap() {
         let raw_msg = r#"{"jsonrpc":"2.0","method":"subscription","params":{"channel":"chart.trades.BTC-PERPETUAL.1","data":{"volume":0.02120555,"tick":1654079340000,"open":31595.5,"low":31595.5,"high":31595.5,"cost":670.0,"close":31595.5}}}"#;
-        let arr =
-            parse_candlestick(EXCHANGE_NAME, MarketType::InverseFuture, raw_msg, None).unwrap();
+        let arr = parse_candlestick(EXCHANGE_NAME, MarketType::InverseFuture, raw_msg, None, None)
+            .unwrap();
 
         assert_eq!(
             1654079340000,
@@ -637,7 +697,7 @@ mod candlestick {
         assert_eq!("BTC/USD", candlestick_msg.pair);
         assert_eq!(1654079340000, candlestick_msg.timestamp);
         assert_eq!(1654079280000, candlestick_msg.begin_time);
-        assert_eq!("1", candlestick_msg.period);
+        assert_eq!("1m", candlestick_msg.period.to_string());
 
         assert_eq!(31595.5, candlestick_msg.open);
         assert_eq!(31595.5, candlestick_msg.high);
@@ -650,8 +710,8 @@ mod candlestick {
     #[test]
     fn option() {
         let raw_msg = r#"{"jsonrpc":"2.0","method":"subscription","params":{"channel":"chart.trades.BTC-25MAR23-26500-P.3","data":{"volume":0.1,"tick":1679559480000,"open":0.003,"low":0.003,"high":0.003,"cost":0.0003,"close":0.003}}}"#;
-        let arr =
-            parse_candlestick(EXCHANGE_NAME, MarketType::EuropeanOption, raw_msg, None).unwrap();
+        let arr = parse_candlestick(EXCHANGE_NAME, MarketType::EuropeanOption, raw_msg, None, None)
+            .unwrap();
 
         assert_eq!(
             1679559480000,
@@ -667,7 +727,7 @@ mod candlestick {
         assert_eq!("BTC/BTC", candlestick_msg.pair);
         assert_eq!(1679559480000, candlestick_msg.timestamp);
         assert_eq!(1679559300000, candlestick_msg.begin_time);
-        assert_eq!("3", candlestick_msg.period);
+        assert_eq!("3m", candlestick_msg.period.to_string());
 
         assert_eq!(0.003, candlestick_msg.open);
         assert_eq!(0.003, candlestick_msg.high);
@@ -682,7 +742,7 @@ mod candlestick {
 mod ticker {
     use super::EXCHANGE_NAME;
     use crypto_market_type::MarketType;
-    use crypto_msg_parser::{extract_symbol, extract_timestamp};
+    use crypto_msg_parser::{extract_symbol, extract_timestamp, parse_ticker};
 
     #[test]
     fn inverse_future() {
@@ -696,6 +756,18 @@ mod ticker {
             "BTC-30SEP22",
             extract_symbol(EXCHANGE_NAME, MarketType::Spot, raw_msg).unwrap()
         );
+
+        let tickers =
+            &parse_ticker(EXCHANGE_NAME, MarketType::InverseFuture, raw_msg, None).unwrap();
+        assert_eq!(1, tickers.len());
+        crate::utils::check_ticker_fields(
+            EXCHANGE_NAME,
+            MarketType::InverseFuture,
+            "BTC/USD".to_string(),
+            "BTC-30SEP22".to_string(),
+            &tickers[0],
+            raw_msg,
+        );
     }
 
     #[test]
@@ -710,6 +782,17 @@ mod ticker {
             "BTC-PERPETUAL",
             extract_symbol(EXCHANGE_NAME, MarketType::LinearSwap, raw_msg).unwrap()
         );
+
+        let tickers = &parse_ticker(EXCHANGE_NAME, MarketType::InverseSwap, raw_msg, None).unwrap();
+        assert_eq!(1, tickers.len());
+        crate::utils::check_ticker_fields(
+            EXCHANGE_NAME,
+            MarketType::InverseSwap,
+            "BTC/USD".to_string(),
+            "BTC-PERPETUAL".to_string(),
+            &tickers[0],
+            raw_msg,
+        );
     }
 
     #[test]
@@ -724,6 +807,51 @@ mod ticker {
             "BTC-30SEP22-60000-C",
             extract_symbol(EXCHANGE_NAME, MarketType::LinearSwap, raw_msg).unwrap()
         );
+
+        let tickers =
+            &parse_ticker(EXCHANGE_NAME, MarketType::EuropeanOption, raw_msg, None).unwrap();
+        assert_eq!(1, tickers.len());
+        crate::utils::check_ticker_fields(
+            EXCHANGE_NAME,
+            MarketType::EuropeanOption,
+            "BTC/BTC".to_string(),
+            "BTC-30SEP22-60000-C".to_string(),
+            &tickers[0],
+            raw_msg,
+        );
+    }
+}
+
+#[cfg(test)]
+mod option_ticker {
+    use super::EXCHANGE_NAME;
+    use crypto_market_type::MarketType;
+    use crypto_msg_parser::parse_option_ticker;
+
+    #[test]
+    fn option() {
+        let raw_msg = r#"{"jsonrpc":"2.0","method":"subscription","params":{"channel":"ticker.BTC-30SEP22-60000-C.100ms","data":{"underlying_price":30220.5,"underlying_index":"BTC-30SEP22","timestamp":1654161839367,"stats":{"volume":16.7,"price_change":-18.1818,"low":0.009,"high":0.011},"state":"open","settlement_price":0.01,"open_interest":1767.5,"min_price":0.0001,"max_price":0.038,"mark_price":0.0084,"mark_iv":67.7,"last_price":0.009,"interest_rate":0.0,"instrument_name":"BTC-30SEP22-60000-C","index_price":29939.43,"greeks":{"vega":20.05335,"theta":-5.65962,"rho":4.91491,"gamma":0.00001,"delta":0.05785},"estimated_delivery_price":29939.43,"bid_iv":67.16,"best_bid_price":0.008,"best_bid_amount":2.8,"best_ask_price":0.009,"best_ask_amount":18.5,"ask_iv":68.65}}}"#;
+        let tickers =
+            &parse_option_ticker(EXCHANGE_NAME, MarketType::EuropeanOption, raw_msg).unwrap();
+
+        assert_eq!(1, tickers.len());
+        let ticker = &tickers[0];
+        assert_eq!(ticker.market_type, MarketType::EuropeanOption);
+        assert_eq!(ticker.symbol, "BTC-30SEP22-60000-C");
+        assert_eq!(ticker.pair, "BTC/BTC");
+        assert_eq!(ticker.timestamp, 1654161839367);
+        assert_eq!(ticker.mark_iv, 0.677);
+        assert_eq!(ticker.bid_iv, Some(0.6716));
+        assert_eq!(ticker.ask_iv, Some(0.6865));
+        assert_eq!(ticker.delta, 0.05785);
+        assert_eq!(ticker.gamma, 0.00001);
+        assert_eq!(ticker.vega, 20.05335);
+        assert_eq!(ticker.theta, -5.65962);
+        assert_eq!(ticker.underlying_price, Some(30220.5));
+        assert_eq!(ticker.open_interest, Some(1767.5));
+        assert_eq!(ticker.json, raw_msg);
+
+        assert!(parse_option_ticker(EXCHANGE_NAME, MarketType::InverseFuture, raw_msg).is_err());
     }
 }
 
@@ -782,7 +910,7 @@ mod l2_snapshot {
 mod open_interest {
     use super::EXCHANGE_NAME;
     use crypto_market_type::MarketType;
-    use crypto_msg_parser::{extract_symbol, extract_timestamp};
+    use crypto_msg_parser::{extract_symbol, extract_timestamp, parse_open_interest};
 
     #[test]
     fn inverse_future() {
@@ -827,6 +955,17 @@ mod open_interest {
             1654340303741,
             extract_timestamp(EXCHANGE_NAME, MarketType::LinearSwap, raw_msg).unwrap().unwrap()
         );
+
+        // the market type is derived from instrument_name
+        let open_interests =
+            &parse_open_interest(EXCHANGE_NAME, MarketType::LinearSwap, raw_msg, None, None)
+                .unwrap();
+        assert_eq!(open_interests.len(), 1);
+        assert_eq!(open_interests[0].market_type, MarketType::InverseSwap);
+        assert_eq!(open_interests[0].timestamp, 1654340303741);
+        assert_eq!(open_interests[0].quantity_contract, 560105870.0);
+        assert_eq!(open_interests[0].quantity_quote, Some(10.0 * 560105870.0));
+        assert_eq!(open_interests[0].quantity_base, Some(10.0 * 560105870.0 / 29700.53));
     }
 
     #[test]
@@ -841,5 +980,51 @@ mod open_interest {
             "ETH-24JUN22-1600-P",
             extract_symbol(EXCHANGE_NAME, MarketType::LinearSwap, raw_msg).unwrap()
         );
+
+        let open_interests =
+            &parse_open_interest(EXCHANGE_NAME, MarketType::EuropeanOption, raw_msg, None, None)
+                .unwrap();
+        assert_eq!(open_interests.len(), 1);
+        assert_eq!(open_interests[0].pair, "ETH/ETH");
+        assert_eq!(open_interests[0].quantity_contract, 15135.0);
+        assert_eq!(open_interests[0].quantity_base, Some(15135.0));
+        // option prices are quoted in ETH
+        assert_eq!(open_interests[0].quantity_quote, None);
+    }
+}
+
+#[cfg(test)]
+mod funding_rate {
+    use super::EXCHANGE_NAME;
+    use crypto_market_type::MarketType;
+    use crypto_msg_parser::parse_funding_rate;
+
+    #[test]
+    fn inverse_swap() {
+        let raw_msg = r#"{"jsonrpc":"2.0","method":"subscription","params":{"channel":"ticker.BTC-PERPETUAL.100ms","data":{"timestamp":1654161785818,"stats":{"volume_usd":545442610.0,"volume":17945.19644566,"price_change":-5.4014,"low":29265.5,"high":31903.5},"state":"open","settlement_price":29945.69,"open_interest":559791310,"min_price":29485.31,"max_price":30383.34,"mark_price":29932.79,"last_price":29931.0,"instrument_name":"BTC-PERPETUAL","index_price":29930.44,"funding_8h":0.00000255,"estimated_delivery_price":29930.44,"current_funding":0.0,"best_bid_price":29930.5,"best_bid_amount":149910.0,"best_ask_price":29931.0,"best_ask_amount":62850.0}}}"#;
+        let funding_rates =
+            &parse_funding_rate(EXCHANGE_NAME, MarketType::InverseSwap, raw_msg, None).unwrap();
+
+        assert_eq!(funding_rates.len(), 1);
+        assert_eq!(funding_rates[0].symbol, "BTC-PERPETUAL");
+        assert_eq!(funding_rates[0].pair, "BTC/USD");
+        assert_eq!(funding_rates[0].funding_rate, 0.00000255);
+        assert_eq!(funding_rates[0].estimated_rate, None);
+        // funding is continuous
+        assert_eq!(funding_rates[0].funding_time, 1654161785818);
+        assert_eq!(funding_rates[0].timestamp, 1654161785818);
+    }
+
+    #[test]
+    fn inverse_swap_rest() {
+        let raw_msg = r#"{"jsonrpc":"2.0","result":{"timestamp":1654246806027,"stats":{"volume_usd":278922050.0,"volume":9229.89241729,"price_change":1.721,"low":29583.5,"high":30729.0},"state":"open","settlement_price":30458.76,"open_interest":560520540,"min_price":29980.5,"max_price":30893.61,"mark_price":30436.94,"last_price":30439.5,"instrument_name":"BTC-PERPETUAL","index_price":30418.45,"funding_8h":0.00002085,"estimated_delivery_price":30418.45,"current_funding":0.00010785,"change_id":45305261539,"bids":[[30434.5,600.0],[30433.0,15000.0],[30431.0,15010.0]],"best_bid_price":30434.5,"best_bid_amount":600.0,"best_ask_price":30435.0,"best_ask_amount":198440.0,"asks":[[30435.0,198440.0],[30438.5,1000.0],[30439.5,188330.0]]},"usIn":1654246806051360,"usOut":1654246806055238,"usDiff":3878,"testnet":false}"#;
+        let funding_rates =
+            &parse_funding_rate(EXCHANGE_NAME, MarketType::InverseSwap, raw_msg, None).unwrap();
+
+        assert_eq!(funding_rates.len(), 1);
+        assert_eq!(funding_rates[0].pair, "BTC/USD");
+        assert_eq!(funding_rates[0].funding_rate, 0.00002085);
+        assert_eq!(funding_rates[0].funding_time, 1654246806027);
+        assert_eq!(funding_rates[0].timestamp, 1654246806027);
     }
 }
@@ -123,12 +123,15 @@ mod l2_event {
 mod l2_snapshot {
     use super::EXCHANGE_NAME;
     use crypto_market_type::MarketType;
-    use crypto_msg_parser::{extract_symbol, extract_timestamp};
+    use crypto_msg_parser::{extract_symbol, extract_timestamp, get_msg_type};
+    use crypto_msg_type::MessageType;
 
     #[test]
     fn linear_swap() {
         let raw_msg = r#"{"asks":[{"size":"0.595","price":"30315"},{"size":"1.56","price":"30317"},{"size":"0.345","price":"30318"}],"bids":[{"size":"1.3529","price":"30310"},{"size":"4.0488","price":"30308"},{"size":"0.033","price":"30306"}]}"#;
 
+        assert_eq!(MessageType::L2Snapshot, get_msg_type(EXCHANGE_NAME, raw_msg));
+
         assert_eq!("NONE", extract_symbol(EXCHANGE_NAME, MarketType::LinearSwap, raw_msg).unwrap());
 
         assert_eq!(
@@ -156,3 +159,120 @@ mod open_interest {
         );
     }
 }
+
+#[cfg(test)]
+mod funding_rate {
+    use super::EXCHANGE_NAME;
+    use crypto_market_type::MarketType;
+    use crypto_msg_parser::parse_funding_rate;
+
+    #[test]
+    fn markets() {
+        let raw_msg = r#"{"markets":{"BTC-USD":{"market":"BTC-USD","status":"ONLINE","baseAsset":"BTC","quoteAsset":"USD","stepSize":"0.0001","tickSize":"1","indexPrice":"29718.5600","oraclePrice":"29686.8200","priceChange24H":"-3.120000","nextFundingRate":"0.0000077940","nextFundingAt":"2022-06-04T12:00:00.000Z","minOrderSize":"0.001","type":"PERPETUAL","initialMarginFraction":"0.05","maintenanceMarginFraction":"0.03","volume24H":"172496250.187000","trades24H":"27100","openInterest":"7989.6723","incrementalInitialMarginFraction":"0.01","incrementalPositionSize":"1.5","maxPositionSize":"170","baselinePositionSize":"9","assetResolution":"10000000000","syntheticAssetId":"0x4254432d3130000000000000000000"},"AVAX-USD":{"market":"AVAX-USD","status":"ONLINE","baseAsset":"AVAX","quoteAsset":"USD","stepSize":"0.1","tickSize":"0.01","indexPrice":"23.1026","oraclePrice":"23.0900","priceChange24H":"0.442564","nextFundingRate":"0.0000073995","nextFundingAt":"2022-06-04T12:00:00.000Z","minOrderSize":"1","type":"PERPETUAL","initialMarginFraction":"0.10","maintenanceMarginFraction":"0.05","volume24H":"17097633.955000","trades24H":"6889","openInterest":"988005.4","incrementalInitialMarginFraction":"0.02","incrementalPositionSize":"1800","maxPositionSize":"91000","baselinePositionSize":"9000","assetResolution":"10000000","syntheticAssetId":"0x415641582d37000000000000000000"}}}"#;
+        let received_at = 1654343965123;
+        let funding_rates =
+            &parse_funding_rate(EXCHANGE_NAME, MarketType::LinearSwap, raw_msg, Some(received_at))
+                .unwrap();
+
+        assert_eq!(funding_rates.len(), 2);
+        assert_eq!(funding_rates[0].symbol, "AVAX-USD");
+        assert_eq!(funding_rates[0].pair, "AVAX/USD");
+        assert_eq!(funding_rates[0].funding_rate, 0.0000073995);
+        assert_eq!(funding_rates[0].annualized_rate, 0.06481962);
+        assert_eq!(funding_rates[1].symbol, "BTC-USD");
+        assert_eq!(funding_rates[1].pair, "BTC/USD");
+        assert_eq!(funding_rates[1].funding_rate, 0.000007794);
+        for rate in funding_rates.iter() {
+            assert_eq!(rate.funding_interval, 3600000);
+            assert_eq!(rate.estimated_rate, None);
+            assert_eq!(rate.funding_time, 1654344000000);
+            assert_eq!(rate.timestamp, received_at);
+        }
+
+        assert!(parse_funding_rate(EXCHANGE_NAME, MarketType::LinearSwap, raw_msg, None).is_err());
+    }
+
+    #[test]
+    fn markets_update() {
+        let raw_msg = r#"{"type":"channel_data","connection_id":"bc3cd7a2-3b4a-4b05-a4d4-e2bcbd5e5a07","message_id":3,"channel":"v3_markets","contents":{"ETH-USD":{"nextFundingRate":"0.0000125"},"BTC-USD":{"indexPrice":"29718.5600","oraclePrice":"29686.8200"}}}"#;
+        let received_at = 1654343965123;
+        let funding_rates =
+            &parse_funding_rate(EXCHANGE_NAME, MarketType::LinearSwap, raw_msg, Some(received_at))
+                .unwrap();
+
+        assert_eq!(funding_rates.len(), 1);
+        assert_eq!(funding_rates[0].symbol, "ETH-USD");
+        assert_eq!(funding_rates[0].pair, "ETH/USD");
+        assert_eq!(funding_rates[0].funding_rate, 0.0000125);
+        assert_eq!(funding_rates[0].funding_time, 1654344000000);
+        assert_eq!(funding_rates[0].timestamp, received_at);
+        assert_eq!(funding_rates[0].json, raw_msg);
+    }
+
+    #[test]
+    fn historical_funding() {
+        let raw_msg = r#"{"historicalFunding":[{"market":"BTC-USD","rate":"0.0000077940","price":"29686.820000","effectiveAt":"2022-06-04T11:00:00.000Z"},{"market":"BTC-USD","rate":"-0.0000031254","price":"29633.270000","effectiveAt":"2022-06-04T10:00:00.000Z"}]}"#;
+        let funding_rates =
+            &parse_funding_rate(EXCHANGE_NAME, MarketType::LinearSwap, raw_msg, None).unwrap();
+
+        assert_eq!(funding_rates.len(), 2);
+        for rate in funding_rates.iter() {
+            crate::utils::check_funding_rate_fields(
+                EXCHANGE_NAME,
+                MarketType::LinearSwap,
+                rate,
+                raw_msg,
+            );
+            assert_eq!(rate.pair, "BTC/USD");
+            assert_eq!(rate.funding_time, rate.timestamp);
+        }
+
+        assert_eq!(funding_rates[0].funding_rate, 0.000007794);
+        assert_eq!(funding_rates[0].funding_time, 1654340400000);
+        assert_eq!(funding_rates[1].funding_rate, -0.0000031254);
+        assert_eq!(funding_rates[1].funding_time, 1654336800000);
+    }
+}
+
+#[cfg(test)]
+mod candlestick {
+    use super::EXCHANGE_NAME;
+    use crypto_market_type::MarketType;
+    use crypto_msg_parser::{extract_symbol, extract_timestamp, get_msg_type, parse_candlestick};
+    use crypto_msg_type::MessageType;
+
+    #[test]
+    fn linear_swap() {
+        let raw_msg = r#"{"candles":[{"startedAt":"2022-06-03T08:01:00.000Z","updatedAt":"2022-06-03T08:01:59.871Z","market":"BTC-USD","resolution":"1MIN","low":"29995","high":"30021","open":"29998","close":"30018","baseTokenVolume":"2.5183","trades":"41","usdVolume":"75567.2981","startingOpenInterest":"5218.7361"},{"startedAt":"2022-06-03T08:00:00.000Z","updatedAt":"2022-06-03T08:00:59.512Z","market":"BTC-USD","resolution":"1MIN","low":"29978","high":"30008","open":"29980","close":"29998","baseTokenVolume":"3.5122","trades":"57","usdVolume":"105336.4531","startingOpenInterest":"5216.1032"}]}"#;
+
+        assert_eq!(MessageType::Candlestick, get_msg_type(EXCHANGE_NAME, raw_msg));
+        assert_eq!(
+            1654243319871,
+            extract_timestamp(EXCHANGE_NAME, MarketType::LinearSwap, raw_msg).unwrap().unwrap()
+        );
+        assert_eq!(
+            "BTC-USD",
+            extract_symbol(EXCHANGE_NAME, MarketType::LinearSwap, raw_msg).unwrap()
+        );
+
+        let arr =
+            parse_candlestick(EXCHANGE_NAME, MarketType::LinearSwap, raw_msg, None, None).unwrap();
+        assert_eq!(2, arr.len());
+        let candlestick_msg = &arr[0];
+
+        assert_eq!("BTC-USD", candlestick_msg.symbol);
+        assert_eq!("BTC/USD", candlestick_msg.pair);
+        assert_eq!(1654243319871, candlestick_msg.timestamp);
+        assert_eq!(1654243260000, candlestick_msg.begin_time);
+        assert_eq!("1m", candlestick_msg.period.to_string());
+
+        assert_eq!(29998.0, candlestick_msg.open);
+        assert_eq!(30021.0, candlestick_msg.high);
+        assert_eq!(29995.0, candlestick_msg.low);
+        assert_eq!(30018.0, candlestick_msg.close);
+        assert_eq!(2.5183, candlestick_msg.volume);
+        assert_eq!(Some(75567.2981), candlestick_msg.quote_volume);
+
+        assert_eq!(1654243200000, arr[1].begin_time);
+    }
+}
@@ -120,6 +120,44 @@ mod trade {
     }
 }
 
+#[cfg(test)]
+mod liquidation {
+    use super::EXCHANGE_NAME;
+    use crypto_market_type::MarketType;
+    use crypto_message::TradeSide;
+    use crypto_msg_parser::{parse_liquidation, round};
+
+    #[test]
+    fn linear_swap() {
+        let raw_msg = r#"{"channel": "trades", "market": "BTC-PERP", "type": "update", "data": [{"id": 632137290, "price": 56240.0, "size": 0.5, "side": "sell", "liquidation": true, "time": "2021-03-21T10:58:26.498464+00:00"}, {"id": 632137291, "price": 56239.0, "size": 0.01, "side": "sell", "liquidation": false, "time": "2021-03-21T10:58:26.498464+00:00"}]}"#;
+        let liquidations =
+            &parse_liquidation(EXCHANGE_NAME, MarketType::LinearSwap, raw_msg, None).unwrap();
+
+        assert_eq!(liquidations.len(), 1);
+        let liquidation = &liquidations[0];
+        crate::utils::check_liquidation_fields(
+            EXCHANGE_NAME,
+            MarketType::LinearSwap,
+            "BTC/USD".to_string(),
+            liquidation,
+        );
+
+        assert_eq!(liquidation.timestamp, 1616324306498);
+        assert_eq!(liquidation.side, TradeSide::Sell);
+        assert_eq!(liquidation.price, 56240.0);
+        assert_eq!(liquidation.quantity_base, 0.5);
+        assert_eq!(liquidation.quantity_quote, round(56240.0 * 0.5));
+    }
+
+    #[test]
+    fn no_liquidation() {
+        let raw_msg = r#"{"channel": "trades", "market": "BTC-PERP", "type": "update", "data": [{"id": 632137291, "price": 56239.0, "size": 0.01, "side": "sell", "liquidation": false, "time": "2021-03-21T10:58:26.498464+00:00"}]}"#;
+        let liquidations =
+            parse_liquidation(EXCHANGE_NAME, MarketType::LinearSwap, raw_msg, None).unwrap();
+        assert!(liquidations.is_empty());
+    }
+}
+
 #[cfg(test)]
 mod l2_event {
     use super::EXCHANGE_NAME;
@@ -465,6 +503,50 @@ mod bbo {
     }
 }
 
+#[cfg(test)]
+mod ticker {
+    use super::EXCHANGE_NAME;
+    use crypto_market_type::MarketType;
+    use crypto_msg_parser::parse_ticker;
+
+    #[test]
+    fn spot() {
+        let raw_msg = r#"{"channel":"ticker","market":"BTC/USD","type":"update","data":{"bid":31679.0,"ask":31680.0,"bidSize":1.8434,"askSize":1.1266,"last":31679.0,"time":1654029182.6905813}}"#;
+
+        let tickers = &parse_ticker(EXCHANGE_NAME, MarketType::Spot, raw_msg, None).unwrap();
+        assert_eq!(1, tickers.len());
+        crate::utils::check_ticker_fields(
+            EXCHANGE_NAME,
+            MarketType::Spot,
+            "BTC/USD".to_string(),
+            "BTC/USD".to_string(),
+            &tickers[0],
+            raw_msg,
+        );
+        assert_eq!(1654029182690, tickers[0].timestamp);
+        assert_eq!(31679.0, tickers[0].close);
+        assert_eq!(Some(31679.0), tickers[0].best_bid_price);
+        assert_eq!(Some(1.1266), tickers[0].best_ask_quantity);
+    }
+
+    #[test]
+    fn linear_swap() {
+        let raw_msg = r#"{"channel":"ticker","market":"BTC-PERP","type":"update","data":{"bid":31699.0,"ask":31700.0,"bidSize":14.9905,"askSize":4.6393,"last":31699.0,"time":1654029408.920583}}"#;
+
+        let tickers = &parse_ticker(EXCHANGE_NAME, MarketType::LinearSwap, raw_msg, None).unwrap();
+        assert_eq!(1, tickers.len());
+        crate::utils::check_ticker_fields(
+            EXCHANGE_NAME,
+            MarketType::LinearSwap,
+            "BTC/USD".to_string(),
+            "BTC-PERP".to_string(),
+            &tickers[0],
+            raw_msg,
+        );
+        assert_eq!(Some(14.9905), tickers[0].best_bid_quantity);
+    }
+}
+
 #[cfg(test)]
 mod l2_snapshot {
     use super::EXCHANGE_NAME;
@@ -523,3 +605,53 @@ mod open_interest {
         assert_eq!(None, extract_timestamp(EXCHANGE_NAME, MarketType::Unknown, raw_msg).unwrap());
     }
 }
+
+#[cfg(test)]
+mod candlestick {
+    use super::EXCHANGE_NAME;
+    use crypto_market_type::MarketType;
+    use crypto_msg_parser::{extract_symbol, extract_timestamp, get_msg_type, parse_candlestick};
+    use crypto_msg_type::MessageType;
+
+    #[test]
+    fn linear_swap() {
+        let raw_msg = r#"{"success":true,"result":[{"startTime":"2022-06-03T08:00:00+00:00","time":1654243200000.0,"open":29980.0,"high":30008.0,"low":29978.0,"close":29998.0,"volume":1049930.0},{"startTime":"2022-06-03T08:01:00+00:00","time":1654243260000.0,"open":29998.0,"high":30021.0,"low":29995.0,"close":30018.0,"volume":300180.0}]}"#;
+
+        assert_eq!(MessageType::Candlestick, get_msg_type(EXCHANGE_NAME, raw_msg));
+        assert_eq!(
+            None,
+            extract_timestamp(EXCHANGE_NAME, MarketType::LinearSwap, raw_msg).unwrap()
+        );
+        assert_eq!("NONE", extract_symbol(EXCHANGE_NAME, MarketType::LinearSwap, raw_msg).unwrap());
+
+        // the market name is not in the message
+        assert!(
+            parse_candlestick(EXCHANGE_NAME, MarketType::LinearSwap, raw_msg, None, None).is_err()
+        );
+        let arr = parse_candlestick(
+            EXCHANGE_NAME,
+            MarketType::LinearSwap,
+            raw_msg,
+            Some("BTC-PERP"),
+            None,
+        )
+        .unwrap();
+        assert_eq!(2, arr.len());
+        let candlestick_msg = &arr[1];
+
+        assert_eq!("BTC-PERP", candlestick_msg.symbol);
+        assert_eq!("BTC/USD", candlestick_msg.pair);
+        assert_eq!(1654243260000, candlestick_msg.timestamp);
+        assert_eq!(1654243260000, candlestick_msg.begin_time);
+        assert_eq!("1m", candlestick_msg.period.to_string());
+
+        assert_eq!(29998.0, candlestick_msg.open);
+        assert_eq!(30021.0, candlestick_msg.high);
+        assert_eq!(29995.0, candlestick_msg.low);
+        assert_eq!(30018.0, candlestick_msg.close);
+        assert_eq!(10.0, candlestick_msg.volume);
+        assert_eq!(Some(300180.0), candlestick_msg.quote_volume);
+
+        assert_eq!(35.0, arr[0].volume);
+    }
+}
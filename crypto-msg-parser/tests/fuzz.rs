@@ -0,0 +1,281 @@
+//! Feeds mutated versions of the fixtures in the other test files into every
+//! public function, none of them should panic.
+//!
+//! The public functions turn panics into errors, so a panic hook records every
+//! panic, including caught ones, and fails the test.
+
+use std::{cell::RefCell, panic, sync::Once};
+
+use crypto_market_type::MarketType;
+use crypto_msg_parser::{
+    extract_symbol, extract_timestamp, get_msg_type, parse, parse_bbo, parse_candlestick,
+    parse_funding_rate, parse_index_price, parse_l2, parse_l2_snapshot, parse_l2_topk, parse_l3,
+    parse_liquidation, parse_mark_price, parse_open_interest, parse_option_ticker, parse_ticker,
+    parse_trade, ChecksumBook,
+};
+
+const MARKET_TYPES: &[MarketType] = &[
+    MarketType::Spot,
+    MarketType::LinearFuture,
+    MarketType::InverseFuture,
+    MarketType::LinearSwap,
+    MarketType::InverseSwap,
+    MarketType::EuropeanOption,
+    MarketType::Unknown,
+];
+
+const MUTATIONS_PER_FIXTURE: usize = 8;
+
+/// A tiny xorshift generator, so that failures are reproducible.
+struct Rng(u64);
+
+impl Rng {
+    fn next(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+
+    fn below(&mut self, n: usize) -> usize {
+        if n == 0 { 0 } else { (self.next() % n as u64) as usize }
+    }
+}
+
+/// Extracts all raw string literals from a test file.
+fn fixtures(source: &str) -> Vec<&str> {
+    let mut ret = Vec::new();
+    let mut rest = source;
+    while let Some(begin) = rest.find("r#\"") {
+        rest = &rest[begin + 3..];
+        if let Some(end) = rest.find("\"#") {
+            ret.push(&rest[..end]);
+            rest = &rest[end + 2..];
+        } else {
+            break;
+        }
+    }
+    ret
+}
+
+fn mutate(msg: &str, rng: &mut Rng) -> String {
+    let chars: Vec<char> = msg.chars().collect();
+    if chars.is_empty() {
+        return String::new();
+    }
+    let pos = rng.below(chars.len());
+    match rng.below(6) {
+        // truncate
+        0 => chars[..pos].iter().collect(),
+        // delete one char
+        1 => chars.iter().enumerate().filter(|(i, _)| *i != pos).map(|(_, c)| c).collect(),
+        // duplicate one char
+        2 => {
+            let mut ret = chars.clone();
+            ret.insert(pos, chars[pos]);
+            ret.into_iter().collect()
+        }
+        // turn a digit into a letter
+        3 => {
+            let digits: Vec<usize> = chars
+                .iter()
+                .enumerate()
+                .filter(|(_, c)| c.is_ascii_digit())
+                .map(|(i, _)| i)
+                .collect();
+            let mut ret = chars.clone();
+            if !digits.is_empty() {
+                ret[digits[rng.below(digits.len())]] = 'x';
+            }
+            ret.into_iter().collect()
+        }
+        // replace a number with null
+        4 => {
+            let start = (pos..chars.len()).find(|i| chars[*i].is_ascii_digit()).unwrap_or(pos);
+            let end = (start..chars.len())
+                .find(|i| !(chars[*i].is_ascii_digit() || chars[*i] == '.'))
+                .unwrap_or(chars.len());
+            let mut ret: String = chars[..start].iter().collect();
+            ret.push_str("null");
+            ret.extend(&chars[end..]);
+            ret
+        }
+        // empty an array
+        _ => {
+            let byte_pos = msg.char_indices().nth(pos).map_or(0, |(i, _)| i);
+            let start = msg[byte_pos..].find('[').map(|i| byte_pos + i);
+            let end = start.and_then(|start| msg[start..].find(']').map(|i| start + i));
+            match (start, end) {
+                (Some(start), Some(end)) => format!("{}[]{}", &msg[..start], &msg[end + 1..]),
+                _ => msg.to_string(),
+            }
+        }
+    }
+}
+
+thread_local! {
+    // `None` if panics are not recorded, i.e., outside of `parse_all()`
+    static PANICS: RefCell<Option<Vec<String>>> = const { RefCell::new(None) };
+}
+
+fn record_panics() {
+    static HOOK: Once = Once::new();
+    HOOK.call_once(|| {
+        let default_hook = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            let recorded = PANICS.with(|panics| {
+                if let Some(panics) = panics.borrow_mut().as_mut() {
+                    let location = info.location().map(|l| l.to_string()).unwrap_or_default();
+                    panics.push(format!("{location}: {info}"));
+                    true
+                } else {
+                    false
+                }
+            });
+            if !recorded {
+                default_hook(info);
+            }
+        }));
+    });
+    PANICS.with(|panics| *panics.borrow_mut() = Some(Vec::new()));
+}
+
+fn parse_all(exchange: &str, msg: &str) {
+    record_panics();
+    let _ = get_msg_type(exchange, msg);
+    for market_type in MARKET_TYPES.iter().cloned() {
+        let _ = extract_symbol(exchange, market_type, msg);
+        let _ = extract_timestamp(exchange, market_type, msg);
+        let _ = parse_trade(exchange, market_type, msg);
+        let _ = parse_l2(exchange, market_type, msg, Some(0));
+        let _ = parse_l2_topk(exchange, market_type, msg, Some(0));
+        let _ = parse_l3(exchange, market_type, msg, Some(0));
+        let _ = parse_bbo(exchange, market_type, msg, Some(0));
+        let _ = parse_funding_rate(exchange, market_type, msg, Some(0));
+        let _ = parse_liquidation(exchange, market_type, msg, Some(0));
+        let _ = parse_open_interest(exchange, market_type, msg, None, Some(0));
+        let _ = parse_open_interest(exchange, market_type, msg, Some("BTC_USDT"), Some(0));
+        let _ = parse_mark_price(exchange, market_type, msg, Some(0));
+        let _ = parse_index_price(exchange, market_type, msg, Some(0));
+        let _ = parse_option_ticker(exchange, market_type, msg);
+        let _ = parse_ticker(exchange, market_type, msg, Some(0));
+        let _ = parse_candlestick(exchange, market_type, msg, None, Some(0));
+        let _ = parse_candlestick(exchange, market_type, msg, Some("BTC-USD"), Some(0));
+        let _ = parse_l2_snapshot(exchange, market_type, msg, None, Some(0));
+        let _ = parse_l2_snapshot(exchange, market_type, msg, Some("BTCUSDT"), Some(0));
+        let _ = parse(exchange, market_type, msg, Some(0));
+        let _ = parse(exchange, market_type, msg, None);
+        let _ = ChecksumBook::new(exchange, market_type).update(msg);
+    }
+    let panics = PANICS.with(|panics| panics.take()).unwrap_or_default();
+    assert!(panics.is_empty(), "{exchange} panicked on {msg}\n{}", panics.join("\n"));
+}
+
+fn fuzz(exchange: &str, source: &str) {
+    let mut rng = Rng(0x9E37_79B9_7F4A_7C15);
+    for fixture in fixtures(source) {
+        parse_all(exchange, fixture);
+        for _ in 0..MUTATIONS_PER_FIXTURE {
+            let mutated = mutate(fixture, &mut rng);
+            parse_all(exchange, &mutated);
+        }
+    }
+}
+
+macro_rules! fuzz_exchange {
+    ($name:ident, $exchange:expr, $file:expr) => {
+        #[test]
+        fn $name() {
+            fuzz($exchange, include_str!($file));
+        }
+    };
+}
+
+fuzz_exchange!(binance, "binance", "binance.rs");
+fuzz_exchange!(bitfinex, "bitfinex", "bitfinex.rs");
+fuzz_exchange!(bitget, "bitget", "bitget.rs");
+fuzz_exchange!(bithumb, "bithumb", "bithumb.rs");
+fuzz_exchange!(bitmex, "bitmex", "bitmex.rs");
+fuzz_exchange!(bitstamp, "bitstamp", "bitstamp.rs");
+fuzz_exchange!(bitz, "bitz", "bitz.rs");
+fuzz_exchange!(bybit, "bybit", "bybit.rs");
+fuzz_exchange!(coinbase_pro, "coinbase_pro", "coinbase_pro.rs");
+fuzz_exchange!(deribit, "deribit", "deribit.rs");
+fuzz_exchange!(dydx, "dydx", "dydx.rs");
+fuzz_exchange!(ftx, "ftx", "ftx.rs");
+fuzz_exchange!(gate, "gate", "gate.rs");
+fuzz_exchange!(huobi, "huobi", "huobi.rs");
+fuzz_exchange!(kraken, "kraken", "kraken.rs");
+fuzz_exchange!(kucoin, "kucoin", "kucoin.rs");
+fuzz_exchange!(mexc, "mexc", "mexc.rs");
+fuzz_exchange!(okx, "okx", "okx.rs");
+fuzz_exchange!(okx_v3, "okx", "okx_v3.rs");
+fuzz_exchange!(zb, "zb", "zb.rs");
+fuzz_exchange!(zbg, "zbg", "zbg.rs");
+
+// Mutations that used to panic
+#[test]
+fn regressions() {
+    let msgs = [
+        (
+            "bitfinex",
+            r#"[{"channel":"trades","symbol":"tBTCF0::USTF0"},"te",[647256282,1616219711336,0.00020449,58244]]"#,
+        ),
+        (
+            "bitget",
+            r#"{"data":[{"funding_rate":"0.000258514264","funding_time":"1617346800000","instrument_id":"btcusd"}],"table":"swapfunding_rate"}"#,
+        ),
+        (
+            "bitget",
+            r#"{"data":[{"funding_rate":"0.000106539854","funding_time":"1617346800000","instrument_id":"cmt_btcsdt"}],"table":"swap/funding_rate"}"#,
+        ),
+        ("bitget", r#"{"data":{"candle":[],"instrument_id":"btcusd"},"table":"swap/candle60s"}"#),
+        ("bybit", r#"{"topic":"trade.BTCUSDM21","dta":[]}"#),
+        (
+            "deribit",
+            r#"{"jsonrpc":"2.0","result":{"timestamp":1654245922403,"instrument_namme":"BTC-30SEP22"}}"#,
+        ),
+        (
+            "deribit",
+            r#"{"jsonrpc":"2.0","result":{"tiestamp":1654246806027,"instrument_name":"BTC-PERPETUAL"}}"#,
+        ),
+        (
+            "deribit",
+            r#"{"jsonrpc":"2.0","method":"subscription","params":{"channel":"chart.trades.BTC31MAR23.1","data":{"volume":0.00172302,"tick":1677628800000,"open":23215.0,"low":23215.0,"high":23215.0,"cost":40.0,"close":23215.0}}}"#,
+        ),
+        (
+            "huobi",
+            r#"{"ch":"marketBTC-USD.kline.1min","ts":1662710342762,"tick":{"id":1662710340,"mrid":139336967831,"open":20635.1,"close":20635.1,"high":20635.1,"low":20635.1,"amount":3.3,"vol":682,"count":5}}"#,
+        ),
+        (
+            "huobi",
+            r#"{"ch":"marketBTC-USDT.kline.1min","ts":1654081448870,"tick":{"id":1654081440,"mrid":108782988900,"open":31531.9,"close":31531.9,"high":31532,"low":31531.9,"amount":0.532,"vol":532,"trade_turnover":16774.9728,"count":5}}"#,
+        ),
+        (
+            "kucoin",
+            r#"{"data":{"sequence":1616827077941,"change":"39006.0,sell11450","timestamp":1622719594867},"subject":"level2","topic":"/contractMarket/level2:XBTMM21","type":"message"}"#,
+        ),
+        ("mexc", "[]"),
+        (
+            "okx",
+            r#"{"code":"0","msg":"","data":[{"asks":[["29679","5","0","1"]],"bids":[["29673.5","8","0","1"]]}]}"#,
+        ),
+        ("okx", r#"{"arg":{"hannel":"trades","instId":"BTC-USDT"},"data":[]}"#),
+        (
+            "zbg",
+            r#"{"ddatas":{"asks":[[29763.69,"0.6260"]],"bids":[[29708.13,"0.1500"]],"timestamp":1654331401},"resMsg":{"message":"success !","method":null,"code":"1"}}"#,
+        ),
+    ];
+    for (exchange, msg) in msgs {
+        parse_all(exchange, msg);
+    }
+}
+#[test]
+fn garbage() {
+    let msgs = ["", " ", "null", "[]", "{}", "[null]", "[[]]", "{\"data\":null}", "\u{1F600}"];
+    for exchange in ["binance", "bitmex", "deribit", "gate", "huobi", "okx", "zbg", "unknown"] {
+        for msg in msgs {
+            parse_all(exchange, msg);
+        }
+    }
+}
@@ -6,7 +6,7 @@ const EXCHANGE_NAME: &str = "gate";
 mod trade {
     use super::EXCHANGE_NAME;
     use crypto_market_type::MarketType;
-    use crypto_message::TradeSide;
+    use crypto_message::{TradeMsg, TradeSide};
     use crypto_msg_parser::{extract_symbol, extract_timestamp, parse_trade, round};
 
     #[test]
@@ -62,6 +62,36 @@ mod trade {
         assert_eq!(trades[0].quantity_quote, 0.00052 * 47395.009);
         assert_eq!(trades[0].quantity_contract, None);
         assert_eq!(trades[0].side, TradeSide::Buy);
+        assert_eq!("47395.009", trades[0].price_exact.unwrap().to_string());
+        assert_eq!("0.00052", trades[0].quantity_exact.unwrap().to_string());
+    }
+
+    #[test]
+    fn spot_exact_decimals() {
+        let raw_msg = r#"{"time":1631824310,"channel":"spot.trades","event":"update","result":{"id":1638417042,"create_time":1631824310,"create_time_ms":"1631824310261.896","side":"sell","currency_pair":"SHIB_USDT","amount":"12345678901234.5678","price":"0.00000000012345"}}"#;
+        let trades = &parse_trade(EXCHANGE_NAME, MarketType::Spot, raw_msg).unwrap();
+
+        assert_eq!(trades.len(), 1);
+        assert_eq!("0.00000000012345", trades[0].price_exact.unwrap().to_string());
+        assert_eq!("12345678901234.5678", trades[0].quantity_exact.unwrap().to_string());
+        assert_eq!(round(trades[0].price), 0.0);
+
+        let csv_string = trades[0].to_csv_string();
+        let restored =
+            TradeMsg::from_csv_string(EXCHANGE_NAME, "spot", "trade", "SHIB/USDT", "SHIB_USDT", &csv_string);
+        assert_eq!(trades[0].price_exact, restored.price_exact);
+        assert_eq!(trades[0].quantity_exact, restored.quantity_exact);
+
+        let restored = TradeMsg::from_proto(
+            EXCHANGE_NAME,
+            "spot",
+            "trade",
+            "SHIB/USDT",
+            "SHIB_USDT",
+            &trades[0].to_proto(),
+        );
+        assert_eq!(trades[0].price_exact, restored.price_exact);
+        assert_eq!(trades[0].quantity_exact, restored.quantity_exact);
     }
 
     #[test]
@@ -324,6 +354,8 @@ mod l2_event {
         assert_eq!(orderbook.asks[0].quantity_base, 0.0355);
         assert_eq!(orderbook.asks[0].quantity_quote, 0.0355 * 47813.04);
         assert_eq!(orderbook.asks[0].quantity_contract, None);
+        assert_eq!("47813.04", orderbook.asks[0].price_exact.unwrap().to_string());
+        assert_eq!("0.0355", orderbook.asks[0].quantity_exact.unwrap().to_string());
 
         assert_eq!(orderbook.asks[1].price, 47978.86);
         assert_eq!(orderbook.asks[1].quantity_base, 0.0);
@@ -738,6 +770,10 @@ mod bbo {
         assert_eq!(2.3039, bbo_msg.bid_quantity_base);
         assert_eq!(31738.93 * 2.3039, bbo_msg.bid_quantity_quote);
         assert_eq!(None, bbo_msg.bid_quantity_contract);
+        assert_eq!("31738.93", bbo_msg.bid_price_exact.unwrap().to_string());
+        assert_eq!("2.3039", bbo_msg.bid_quantity_exact.unwrap().to_string());
+        assert_eq!("31738.94", bbo_msg.ask_price_exact.unwrap().to_string());
+        assert_eq!("0.335", bbo_msg.ask_quantity_exact.unwrap().to_string());
     }
 
     #[test]
@@ -769,6 +805,11 @@ mod bbo {
         assert_eq!(19485.0 / 31653.9, bbo_msg.bid_quantity_base);
         assert_eq!(19485.0, bbo_msg.bid_quantity_quote);
         assert_eq!(Some(19485.0), bbo_msg.bid_quantity_contract);
+        // sizes are JSON numbers, only prices are exact
+        assert_eq!("31653.9", bbo_msg.bid_price_exact.unwrap().to_string());
+        assert_eq!(None, bbo_msg.bid_quantity_exact);
+        assert_eq!("31654", bbo_msg.ask_price_exact.unwrap().to_string());
+        assert_eq!(None, bbo_msg.ask_quantity_exact);
     }
 
     #[test]
@@ -819,14 +860,14 @@ mod candlestick {
         );
         assert_eq!("BTC_USDT", extract_symbol(EXCHANGE_NAME, MarketType::Spot, raw_msg).unwrap());
 
-        let arr = parse_candlestick(EXCHANGE_NAME, MarketType::Spot, raw_msg, None).unwrap();
+        let arr = parse_candlestick(EXCHANGE_NAME, MarketType::Spot, raw_msg, None, None).unwrap();
         assert_eq!(1, arr.len());
         let candlestick_msg = &arr[0];
 
         assert_eq!("BTC_USDT", candlestick_msg.symbol);
         assert_eq!(1662803082000, candlestick_msg.timestamp);
-        assert_eq!("1m", candlestick_msg.period);
-        assert_eq!(1662803040, candlestick_msg.begin_time);
+        assert_eq!("1m", candlestick_msg.period.to_string());
+        assert_eq!(1662803040000, candlestick_msg.begin_time);
 
         assert_eq!(21352.26, candlestick_msg.open);
         assert_eq!(21352.26, candlestick_msg.high);
@@ -849,15 +890,15 @@ mod candlestick {
             extract_symbol(EXCHANGE_NAME, MarketType::InverseSwap, raw_msg).unwrap()
         );
 
-        let arr =
-            parse_candlestick(EXCHANGE_NAME, MarketType::InverseFuture, raw_msg, None).unwrap();
+        let arr = parse_candlestick(EXCHANGE_NAME, MarketType::InverseFuture, raw_msg, None, None)
+            .unwrap();
         assert_eq!(2, arr.len());
         let candlestick_msg = &arr[0];
 
         assert_eq!("BTC_USD_20221230", candlestick_msg.symbol);
         assert_eq!(1662690902000, candlestick_msg.timestamp);
-        assert_eq!("5m", candlestick_msg.period);
-        assert_eq!(1662690600, candlestick_msg.begin_time);
+        assert_eq!("5m", candlestick_msg.period.to_string());
+        assert_eq!(1662690600000, candlestick_msg.begin_time);
 
         assert_eq!(19405.1, candlestick_msg.open);
         assert_eq!(19405.1, candlestick_msg.high);
@@ -880,15 +921,15 @@ mod candlestick {
             extract_symbol(EXCHANGE_NAME, MarketType::LinearSwap, raw_msg).unwrap()
         );
 
-        let arr =
-            parse_candlestick(EXCHANGE_NAME, MarketType::LinearFuture, raw_msg, None).unwrap();
+        let arr = parse_candlestick(EXCHANGE_NAME, MarketType::LinearFuture, raw_msg, None, None)
+            .unwrap();
         assert_eq!(1, arr.len());
         let candlestick_msg = &arr[0];
 
         assert_eq!("BTC_USDT_20220930", candlestick_msg.symbol);
         assert_eq!(1662695085000, candlestick_msg.timestamp);
-        assert_eq!("5m", candlestick_msg.period);
-        assert_eq!(1662694800, candlestick_msg.begin_time);
+        assert_eq!("5m", candlestick_msg.period.to_string());
+        assert_eq!(1662694800000, candlestick_msg.begin_time);
 
         assert_eq!(19847.7, candlestick_msg.open);
         assert_eq!(19847.7, candlestick_msg.high);
@@ -912,14 +953,15 @@ mod candlestick {
             extract_symbol(EXCHANGE_NAME, MarketType::InverseSwap, raw_msg).unwrap()
         );
 
-        let arr = parse_candlestick(EXCHANGE_NAME, MarketType::InverseSwap, raw_msg, None).unwrap();
+        let arr =
+            parse_candlestick(EXCHANGE_NAME, MarketType::InverseSwap, raw_msg, None, None).unwrap();
         assert_eq!(1, arr.len());
         let candlestick_msg = &arr[0];
 
         assert_eq!("BTC_USD", candlestick_msg.symbol);
         assert_eq!(1662803074000, candlestick_msg.timestamp);
-        assert_eq!("5m", candlestick_msg.period);
-        assert_eq!(1662802800, candlestick_msg.begin_time);
+        assert_eq!("5m", candlestick_msg.period.to_string());
+        assert_eq!(1662802800000, candlestick_msg.begin_time);
 
         assert_eq!(21323.6, candlestick_msg.open);
         assert_eq!(21357.4, candlestick_msg.high);
@@ -945,14 +987,15 @@ mod candlestick {
             extract_symbol(EXCHANGE_NAME, MarketType::LinearSwap, raw_msg).unwrap()
         );
 
-        let arr = parse_candlestick(EXCHANGE_NAME, MarketType::LinearSwap, raw_msg, None).unwrap();
+        let arr =
+            parse_candlestick(EXCHANGE_NAME, MarketType::LinearSwap, raw_msg, None, None).unwrap();
         assert_eq!(1, arr.len());
         let candlestick_msg = &arr[0];
 
         assert_eq!("BTC_USDT", candlestick_msg.symbol);
         assert_eq!(1662484495000, candlestick_msg.timestamp);
-        assert_eq!("5m", candlestick_msg.period);
-        assert_eq!(1662484200, candlestick_msg.begin_time);
+        assert_eq!("5m", candlestick_msg.period.to_string());
+        assert_eq!(1662484200000, candlestick_msg.begin_time);
 
         assert_eq!(19347.3, candlestick_msg.open);
         assert_eq!(19377.0, candlestick_msg.high);
@@ -967,7 +1010,7 @@ mod candlestick {
 mod ticker {
     use super::EXCHANGE_NAME;
     use crypto_market_type::MarketType;
-    use crypto_msg_parser::{extract_symbol, extract_timestamp};
+    use crypto_msg_parser::{extract_symbol, extract_timestamp, parse_ticker};
 
     #[test]
     fn spot() {
@@ -978,6 +1021,17 @@ mod ticker {
             extract_timestamp(EXCHANGE_NAME, MarketType::Spot, raw_msg).unwrap().unwrap()
         );
         assert_eq!("BTC_USDT", extract_symbol(EXCHANGE_NAME, MarketType::Spot, raw_msg).unwrap());
+
+        let tickers = &parse_ticker(EXCHANGE_NAME, MarketType::Spot, raw_msg, None).unwrap();
+        assert_eq!(1, tickers.len());
+        crate::utils::check_ticker_fields(
+            EXCHANGE_NAME,
+            MarketType::Spot,
+            "BTC/USDT".to_string(),
+            "BTC_USDT".to_string(),
+            &tickers[0],
+            raw_msg,
+        );
     }
 
     #[test]
@@ -1000,6 +1054,18 @@ mod ticker {
             "BTC_USD_20220624",
             extract_symbol(EXCHANGE_NAME, MarketType::InverseSwap, raw_msg).unwrap()
         );
+
+        let tickers =
+            &parse_ticker(EXCHANGE_NAME, MarketType::InverseFuture, raw_msg, None).unwrap();
+        assert_eq!(1, tickers.len());
+        crate::utils::check_ticker_fields(
+            EXCHANGE_NAME,
+            MarketType::InverseFuture,
+            "BTC/USD".to_string(),
+            "BTC_USD_20220624".to_string(),
+            &tickers[0],
+            raw_msg,
+        );
     }
 
     #[test]
@@ -1014,6 +1080,18 @@ mod ticker {
             "BTC_USDT_20220624",
             extract_symbol(EXCHANGE_NAME, MarketType::LinearSwap, raw_msg).unwrap()
         );
+
+        let tickers =
+            &parse_ticker(EXCHANGE_NAME, MarketType::LinearFuture, raw_msg, None).unwrap();
+        assert_eq!(1, tickers.len());
+        crate::utils::check_ticker_fields(
+            EXCHANGE_NAME,
+            MarketType::LinearFuture,
+            "BTC/USDT".to_string(),
+            "BTC_USDT_20220624".to_string(),
+            &tickers[0],
+            raw_msg,
+        );
     }
 
     #[test]
@@ -1028,6 +1106,17 @@ mod ticker {
             "BTC_USD",
             extract_symbol(EXCHANGE_NAME, MarketType::InverseSwap, raw_msg).unwrap()
         );
+
+        let tickers = &parse_ticker(EXCHANGE_NAME, MarketType::InverseSwap, raw_msg, None).unwrap();
+        assert_eq!(1, tickers.len());
+        crate::utils::check_ticker_fields(
+            EXCHANGE_NAME,
+            MarketType::InverseSwap,
+            "BTC/USD".to_string(),
+            "BTC_USD".to_string(),
+            &tickers[0],
+            raw_msg,
+        );
     }
 
     #[test]
@@ -1042,6 +1131,17 @@ mod ticker {
             "BTC_USDT",
             extract_symbol(EXCHANGE_NAME, MarketType::LinearSwap, raw_msg).unwrap()
         );
+
+        let tickers = &parse_ticker(EXCHANGE_NAME, MarketType::LinearSwap, raw_msg, None).unwrap();
+        assert_eq!(1, tickers.len());
+        crate::utils::check_ticker_fields(
+            EXCHANGE_NAME,
+            MarketType::LinearSwap,
+            "BTC/USDT".to_string(),
+            "BTC_USDT".to_string(),
+            &tickers[0],
+            raw_msg,
+        );
     }
 }
 
@@ -1049,12 +1149,15 @@ mod ticker {
 mod l2_snapshot {
     use super::EXCHANGE_NAME;
     use crypto_market_type::MarketType;
-    use crypto_msg_parser::{extract_symbol, extract_timestamp};
+    use crypto_msg_parser::{extract_symbol, extract_timestamp, get_msg_type};
+    use crypto_msg_type::MessageType;
 
     #[test]
     fn spot() {
         let raw_msg = r#"{"current":1654249533113,"update":1654249526591,"asks":[["30168.33","0.1824"],["30177.13","0.18"],["30178.62","0.2495"],["30178.63","1.7315"],["30179.94","0.0158"]],"bids":[["30168.32","0.5748"],["30165.14","0.0158"],["30164.8","0.035"],["30163.13","0.0023"],["30162.67","0.1252"]]}"#;
 
+        assert_eq!(MessageType::L2Snapshot, get_msg_type(EXCHANGE_NAME, raw_msg));
+
         assert_eq!(
             1654249533113,
             extract_timestamp(EXCHANGE_NAME, MarketType::Spot, raw_msg).unwrap().unwrap()
@@ -1066,6 +1169,8 @@ mod l2_snapshot {
     fn inverse_future() {
         let raw_msg = r#"{"current":1654249503.599,"asks":[{"s":564,"p":"30200.9"},{"s":535,"p":"30203.9"},{"s":564,"p":"30210"},{"s":497,"p":"30219"},{"s":487,"p":"30231.1"}],"bids":[{"s":564,"p":"30166.6"},{"s":535,"p":"30163.6"},{"s":513,"p":"30157.6"},{"s":546,"p":"30148.5"},{"s":487,"p":"30136.5"}],"update":1654249503.437}"#;
 
+        assert_eq!(MessageType::L2Snapshot, get_msg_type(EXCHANGE_NAME, raw_msg));
+
         assert_eq!(
             1654249503599,
             extract_timestamp(EXCHANGE_NAME, MarketType::InverseSwap, raw_msg).unwrap().unwrap()
@@ -1080,6 +1185,8 @@ mod l2_snapshot {
     fn linear_future() {
         let raw_msg = r#"{"current":1654251300.95,"asks":[{"s":185,"p":"30199.7"},{"s":176,"p":"30202.7"},{"s":167,"p":"30208.8"},{"s":177,"p":"30217.8"},{"s":158,"p":"30229.9"}],"bids":[{"s":185,"p":"30174.5"},{"s":176,"p":"30171.5"},{"s":167,"p":"30165.4"},{"s":161,"p":"30156.4"},{"s":173,"p":"30144.3"}],"update":1654251300.797}"#;
 
+        assert_eq!(MessageType::L2Snapshot, get_msg_type(EXCHANGE_NAME, raw_msg));
+
         assert_eq!(
             1654251300950,
             extract_timestamp(EXCHANGE_NAME, MarketType::LinearSwap, raw_msg).unwrap().unwrap()
@@ -1091,6 +1198,8 @@ mod l2_snapshot {
     fn inverse_swap() {
         let raw_msg = r#"{"current":1654251302.768,"asks":[{"s":475,"p":"30079.1"},{"s":4000,"p":"30079.2"},{"s":2408,"p":"30079.3"},{"s":10558,"p":"30079.6"},{"s":10,"p":"30090.8"}],"bids":[{"s":2,"p":"30061.6"},{"s":3,"p":"30061.5"},{"s":100,"p":"30056"},{"s":8036,"p":"30050.1"},{"s":500,"p":"30050"}],"update":1654251302.754}"#;
 
+        assert_eq!(MessageType::L2Snapshot, get_msg_type(EXCHANGE_NAME, raw_msg));
+
         assert_eq!(
             1654251302768,
             extract_timestamp(EXCHANGE_NAME, MarketType::InverseSwap, raw_msg).unwrap().unwrap()
@@ -1105,6 +1214,8 @@ mod l2_snapshot {
     fn linear_swap() {
         let raw_msg = r#"{"current":1654251438.92,"asks":[{"s":75703,"p":"30144.6"},{"s":30094,"p":"30144.7"},{"s":1750,"p":"30146.3"},{"s":1991,"p":"30146.4"},{"s":1658,"p":"30146.8"}],"bids":[{"s":324289,"p":"30144.5"},{"s":1369,"p":"30144"},{"s":1399,"p":"30143.9"},{"s":1376,"p":"30143.8"},{"s":1825,"p":"30143.4"}],"update":1654251438.902}"#;
 
+        assert_eq!(MessageType::L2Snapshot, get_msg_type(EXCHANGE_NAME, raw_msg));
+
         assert_eq!(
             1654251438920,
             extract_timestamp(EXCHANGE_NAME, MarketType::LinearSwap, raw_msg).unwrap().unwrap()
@@ -1117,7 +1228,7 @@ mod l2_snapshot {
 mod open_interest {
     use super::EXCHANGE_NAME;
     use crypto_market_type::MarketType;
-    use crypto_msg_parser::{extract_symbol, extract_timestamp};
+    use crypto_msg_parser::{extract_symbol, extract_timestamp, parse_open_interest};
 
     #[test]
     fn inverse_swap() {
@@ -1131,6 +1242,27 @@ mod open_interest {
             "NONE",
             extract_symbol(EXCHANGE_NAME, MarketType::InverseSwap, raw_msg).unwrap()
         );
+
+        let open_interests = &parse_open_interest(
+            EXCHANGE_NAME,
+            MarketType::InverseSwap,
+            raw_msg,
+            Some("BTC_USD"),
+            None,
+        )
+        .unwrap();
+        assert_eq!(open_interests.len(), 2);
+        assert_eq!(open_interests[0].symbol, "BTC_USD");
+        assert_eq!(open_interests[0].timestamp, 1654335000000);
+        assert_eq!(open_interests[0].quantity_contract, 31828902.0);
+        assert_eq!(open_interests[0].quantity_quote, Some(31828902.0));
+        assert_eq!(open_interests[0].quantity_base, Some(31828902.0 / 29710.95));
+
+        // contract stats have no symbol
+        assert!(
+            parse_open_interest(EXCHANGE_NAME, MarketType::InverseSwap, raw_msg, None, None)
+                .is_err()
+        );
     }
 
     #[test]
@@ -1142,5 +1274,97 @@ mod open_interest {
             extract_timestamp(EXCHANGE_NAME, MarketType::LinearSwap, raw_msg).unwrap()
         );
         assert_eq!("NONE", extract_symbol(EXCHANGE_NAME, MarketType::LinearSwap, raw_msg).unwrap());
+
+        let open_interests = &parse_open_interest(
+            EXCHANGE_NAME,
+            MarketType::LinearSwap,
+            raw_msg,
+            Some("BTC_USDT"),
+            None,
+        )
+        .unwrap();
+        assert_eq!(open_interests.len(), 2);
+        assert_eq!(open_interests[0].pair, "BTC/USDT");
+        assert_eq!(open_interests[0].quantity_contract, 749773515.0);
+        assert_eq!(open_interests[0].quantity_base, Some(74977.3515));
+        assert_eq!(open_interests[0].quantity_quote, Some(2226295750.1279));
+    }
+}
+
+#[cfg(test)]
+mod funding_rate {
+    use super::EXCHANGE_NAME;
+    use crypto_market_type::MarketType;
+    use crypto_msg_parser::{get_msg_type, parse_funding_rate};
+    use crypto_msg_type::MessageType;
+
+    #[test]
+    fn inverse_swap() {
+        let raw_msg = r#"{"time":1654162687,"channel":"futures.tickers","event":"update","result":[{"contract":"BTC_USD","last":"29860.8","change_percentage":"-5.6438","total_size":"31659115","volume_24h":"15542254","volume_24h_base":"0","volume_24h_quote":"15542254","volume_24h_settle":"0.0000000000000006","mark_price":"29902.44","funding_rate":"0.0001","funding_rate_indicative":"-0.000292","index_price":"29900.05","quanto_base_rate":"","low_24h":"29259.1","high_24h":"31856.2","volume_24_usd":"15542254","volume_24_btc":"518.8480439694998983"}]}"#;
+        let funding_rates =
+            &parse_funding_rate(EXCHANGE_NAME, MarketType::InverseSwap, raw_msg, None).unwrap();
+
+        assert_eq!(funding_rates.len(), 1);
+        // funding rates are carried by tickers
+        assert_eq!(MessageType::Ticker, get_msg_type(EXCHANGE_NAME, raw_msg));
+
+        assert_eq!(funding_rates[0].symbol, "BTC_USD");
+        assert_eq!(funding_rates[0].pair, "BTC/USD");
+        assert_eq!(funding_rates[0].funding_rate, 0.0001);
+        assert_eq!(funding_rates[0].estimated_rate, Some(-0.000292));
+        assert_eq!(funding_rates[0].funding_time, 1654185600000);
+        assert_eq!(funding_rates[0].timestamp, 1654162687000);
+        assert_eq!(funding_rates[0].json, raw_msg);
+    }
+
+    #[test]
+    fn linear_swap_contract() {
+        let raw_msg = r#"{"name":"BTC_USDT","type":"direct","quanto_multiplier":"0.0001","ref_discount_rate":"0","order_price_deviate":"0.5","maintenance_rate":"0.005","mark_type":"index","last_price":"29885.9","mark_price":"29908","index_price":"29908.95","funding_rate_indicative":"0.000056","mark_price_round":"0.01","funding_offset":0,"in_delisting":false,"risk_limit_base":"1000000","interest_rate":"0.0003","order_price_round":"0.1","order_size_min":1,"ref_rebate_rate":"0.2","funding_interval":28800,"risk_limit_step":"1000000","leverage_min":"1","leverage_max":"100","funding_cap_ratio":"0.75","risk_limit_max":"8000000","maker_fee_rate":"-0.00025","taker_fee_rate":"0.00075","funding_rate":"-0.00004","order_size_max":1000000,"funding_next_apply":1654185600,"short_users":2064,"config_change_time":1654046708,"trade_size":5474891418,"position_size":754413619,"long_users":2806,"funding_impact_value":"60000","orders_limit":50,"trade_id":26855397,"orderbook_id":6286327395}"#;
+        let received_at = 1654162715123;
+        let funding_rates =
+            &parse_funding_rate(EXCHANGE_NAME, MarketType::LinearSwap, raw_msg, Some(received_at))
+                .unwrap();
+
+        assert_eq!(funding_rates.len(), 1);
+        crate::utils::check_funding_rate_fields(
+            EXCHANGE_NAME,
+            MarketType::LinearSwap,
+            &funding_rates[0],
+            raw_msg,
+        );
+        assert_eq!(funding_rates[0].pair, "BTC/USDT");
+        assert_eq!(funding_rates[0].funding_rate, -0.00004);
+        assert_eq!(funding_rates[0].estimated_rate, Some(0.000056));
+        assert_eq!(funding_rates[0].funding_time, 1654185600000);
+        assert_eq!(funding_rates[0].timestamp, received_at);
+        assert_eq!(funding_rates[0].funding_interval, 8 * 3600000);
+        // (1/100 - 0.005) * 0.75
+        assert_eq!(funding_rates[0].funding_rate_cap, Some(0.00375));
+        assert_eq!(funding_rates[0].funding_rate_floor, Some(-0.00375));
+        assert_eq!(funding_rates[0].annualized_rate, -0.0438);
+
+        assert!(parse_funding_rate(EXCHANGE_NAME, MarketType::LinearSwap, raw_msg, None).is_err());
+    }
+
+    #[test]
+    fn linear_swap_contracts() {
+        let raw_msg = r#"[{"name":"BTC_USDT","type":"direct","mark_price":"29908","funding_rate_indicative":"0.000056","funding_interval":28800,"funding_rate":"-0.00004","funding_next_apply":1654185600,"position_size":754413619},{"name":"ETH_USDT","type":"direct","mark_price":"1813.35","funding_rate_indicative":"0.000081","funding_interval":28800,"funding_rate":"0.0001","funding_next_apply":1654185600,"position_size":81204583}]"#;
+        let received_at = 1654162715123;
+        let funding_rates =
+            &parse_funding_rate(EXCHANGE_NAME, MarketType::LinearSwap, raw_msg, Some(received_at))
+                .unwrap();
+
+        assert_eq!(funding_rates.len(), 2);
+        for rate in funding_rates.iter() {
+            crate::utils::check_funding_rate_fields(
+                EXCHANGE_NAME,
+                MarketType::LinearSwap,
+                rate,
+                raw_msg,
+            );
+        }
+        assert_eq!(funding_rates[1].pair, "ETH/USDT");
+        assert_eq!(funding_rates[1].funding_rate, 0.0001);
+        assert_eq!(funding_rates[1].estimated_rate, Some(0.000081));
     }
 }
@@ -1124,14 +1124,14 @@ mod candlestick {
 
         assert_eq!("btcusdt", extract_symbol(EXCHANGE_NAME, MarketType::Spot, raw_msg).unwrap());
 
-        let arr = parse_candlestick(EXCHANGE_NAME, MarketType::Spot, raw_msg, None).unwrap();
+        let arr = parse_candlestick(EXCHANGE_NAME, MarketType::Spot, raw_msg, None, None).unwrap();
         assert_eq!(1, arr.len());
         let candlestick_msg = &arr[0];
 
         assert_eq!("btcusdt", candlestick_msg.symbol);
         assert_eq!(1654081322624, candlestick_msg.timestamp);
-        assert_eq!("1min", candlestick_msg.period);
-        assert_eq!(1654081320, candlestick_msg.begin_time);
+        assert_eq!("1m", candlestick_msg.period.to_string());
+        assert_eq!(1654081320000, candlestick_msg.begin_time);
 
         assert_eq!(31545.71, candlestick_msg.open);
         assert_eq!(31545.72, candlestick_msg.high);
@@ -1154,15 +1154,15 @@ mod candlestick {
             extract_symbol(EXCHANGE_NAME, MarketType::InverseSwap, raw_msg).unwrap()
         );
 
-        let arr =
-            parse_candlestick(EXCHANGE_NAME, MarketType::InverseFuture, raw_msg, None).unwrap();
+        let arr = parse_candlestick(EXCHANGE_NAME, MarketType::InverseFuture, raw_msg, None, None)
+            .unwrap();
         assert_eq!(1, arr.len());
         let candlestick_msg = &arr[0];
 
         assert_eq!("BTC_CQ", candlestick_msg.symbol);
         assert_eq!(1654081396435, candlestick_msg.timestamp);
-        assert_eq!("1min", candlestick_msg.period);
-        assert_eq!(1654081380, candlestick_msg.begin_time);
+        assert_eq!("1m", candlestick_msg.period.to_string());
+        assert_eq!(1654081380000, candlestick_msg.begin_time);
 
         assert_eq!(31565.04, candlestick_msg.open);
         assert_eq!(31565.04, candlestick_msg.high);
@@ -1185,14 +1185,15 @@ mod candlestick {
             extract_symbol(EXCHANGE_NAME, MarketType::InverseSwap, raw_msg).unwrap()
         );
 
-        let arr = parse_candlestick(EXCHANGE_NAME, MarketType::InverseSwap, raw_msg, None).unwrap();
+        let arr =
+            parse_candlestick(EXCHANGE_NAME, MarketType::InverseSwap, raw_msg, None, None).unwrap();
         assert_eq!(1, arr.len());
         let candlestick_msg = &arr[0];
 
         assert_eq!("BTC-USD", candlestick_msg.symbol);
         assert_eq!(1662710342762, candlestick_msg.timestamp);
-        assert_eq!("1min", candlestick_msg.period);
-        assert_eq!(1662710340, candlestick_msg.begin_time);
+        assert_eq!("1m", candlestick_msg.period.to_string());
+        assert_eq!(1662710340000, candlestick_msg.begin_time);
 
         assert_eq!(20635.1, candlestick_msg.open);
         assert_eq!(20635.1, candlestick_msg.high);
@@ -1215,14 +1216,15 @@ mod candlestick {
             extract_symbol(EXCHANGE_NAME, MarketType::LinearSwap, raw_msg).unwrap()
         );
 
-        let arr = parse_candlestick(EXCHANGE_NAME, MarketType::LinearSwap, raw_msg, None).unwrap();
+        let arr =
+            parse_candlestick(EXCHANGE_NAME, MarketType::LinearSwap, raw_msg, None, None).unwrap();
         assert_eq!(1, arr.len());
         let candlestick_msg = &arr[0];
 
         assert_eq!("BTC-USDT", candlestick_msg.symbol);
         assert_eq!(1654081448870, candlestick_msg.timestamp);
-        assert_eq!("1min", candlestick_msg.period);
-        assert_eq!(1654081440, candlestick_msg.begin_time);
+        assert_eq!("1m", candlestick_msg.period.to_string());
+        assert_eq!(1654081440000, candlestick_msg.begin_time);
 
         assert_eq!(31531.9, candlestick_msg.open);
         assert_eq!(31532.0, candlestick_msg.high);
@@ -1237,7 +1239,7 @@ mod candlestick {
 mod ticker {
     use super::EXCHANGE_NAME;
     use crypto_market_type::MarketType;
-    use crypto_msg_parser::{extract_symbol, extract_timestamp};
+    use crypto_msg_parser::{extract_symbol, extract_timestamp, parse_ticker};
 
     #[test]
     fn spot() {
@@ -1248,6 +1250,17 @@ mod ticker {
             extract_timestamp(EXCHANGE_NAME, MarketType::Spot, raw_msg).unwrap().unwrap()
         );
         assert_eq!("btcusdt", extract_symbol(EXCHANGE_NAME, MarketType::Spot, raw_msg).unwrap());
+
+        let tickers = &parse_ticker(EXCHANGE_NAME, MarketType::Spot, raw_msg, None).unwrap();
+        assert_eq!(1, tickers.len());
+        crate::utils::check_ticker_fields(
+            EXCHANGE_NAME,
+            MarketType::Spot,
+            "BTC/USDT".to_string(),
+            "btcusdt".to_string(),
+            &tickers[0],
+            raw_msg,
+        );
     }
 
     #[test]
@@ -1262,6 +1275,18 @@ mod ticker {
             "BTC_CQ",
             extract_symbol(EXCHANGE_NAME, MarketType::InverseSwap, raw_msg).unwrap()
         );
+
+        let tickers =
+            &parse_ticker(EXCHANGE_NAME, MarketType::InverseFuture, raw_msg, None).unwrap();
+        assert_eq!(1, tickers.len());
+        crate::utils::check_ticker_fields(
+            EXCHANGE_NAME,
+            MarketType::InverseFuture,
+            "BTC/USD".to_string(),
+            "BTC_CQ".to_string(),
+            &tickers[0],
+            raw_msg,
+        );
     }
 
     #[test]
@@ -1276,6 +1301,17 @@ mod ticker {
             "BTC-USD",
             extract_symbol(EXCHANGE_NAME, MarketType::InverseSwap, raw_msg).unwrap()
         );
+
+        let tickers = &parse_ticker(EXCHANGE_NAME, MarketType::InverseSwap, raw_msg, None).unwrap();
+        assert_eq!(1, tickers.len());
+        crate::utils::check_ticker_fields(
+            EXCHANGE_NAME,
+            MarketType::InverseSwap,
+            "BTC/USD".to_string(),
+            "BTC-USD".to_string(),
+            &tickers[0],
+            raw_msg,
+        );
     }
 
     #[test]
@@ -1290,6 +1326,17 @@ mod ticker {
             "BTC-USDT",
             extract_symbol(EXCHANGE_NAME, MarketType::LinearSwap, raw_msg).unwrap()
         );
+
+        let tickers = &parse_ticker(EXCHANGE_NAME, MarketType::LinearSwap, raw_msg, None).unwrap();
+        assert_eq!(1, tickers.len());
+        crate::utils::check_ticker_fields(
+            EXCHANGE_NAME,
+            MarketType::LinearSwap,
+            "BTC/USDT".to_string(),
+            "BTC-USDT".to_string(),
+            &tickers[0],
+            raw_msg,
+        );
     }
 }
 
@@ -1357,7 +1404,7 @@ mod l2_snapshot {
 mod open_interest {
     use super::EXCHANGE_NAME;
     use crypto_market_type::MarketType;
-    use crypto_msg_parser::{extract_symbol, extract_timestamp};
+    use crypto_msg_parser::{extract_symbol, extract_timestamp, parse_open_interest};
 
     #[test]
     fn inverse_future() {
@@ -1390,5 +1437,16 @@ mod open_interest {
             extract_timestamp(EXCHANGE_NAME, MarketType::LinearSwap, raw_msg).unwrap().unwrap()
         );
         assert_eq!("ALL", extract_symbol(EXCHANGE_NAME, MarketType::LinearSwap, raw_msg).unwrap());
+
+        let open_interests =
+            &parse_open_interest(EXCHANGE_NAME, MarketType::LinearSwap, raw_msg, None, None)
+                .unwrap();
+        assert!(open_interests.len() > 1);
+        assert_eq!(open_interests[0].symbol, "MANA-USDT");
+        assert_eq!(open_interests[0].pair, "MANA/USDT");
+        assert_eq!(open_interests[0].timestamp, 1654346577824);
+        assert_eq!(open_interests[0].quantity_contract, 288491.0);
+        assert_eq!(open_interests[0].quantity_base, Some(2884910.0));
+        assert_eq!(open_interests[0].quantity_quote, Some(2788265.515));
     }
 }
@@ -446,7 +446,8 @@ mod bbo {
 mod candlestick {
     use super::EXCHANGE_NAME;
     use crypto_market_type::MarketType;
-    use crypto_msg_parser::{extract_symbol, extract_timestamp, parse_candlestick};
+    use crypto_msg_parser::{extract_symbol, extract_timestamp, get_msg_type, parse_candlestick};
+    use crypto_msg_type::MessageType;
 
     #[test]
     fn spot() {
@@ -458,14 +459,14 @@ mod candlestick {
         );
         assert_eq!("XBT/USD", extract_symbol(EXCHANGE_NAME, MarketType::Spot, raw_msg).unwrap());
 
-        let arr = parse_candlestick(EXCHANGE_NAME, MarketType::Spot, raw_msg, None).unwrap();
+        let arr = parse_candlestick(EXCHANGE_NAME, MarketType::Spot, raw_msg, None, None).unwrap();
         assert_eq!(1, arr.len());
         let candlestick_msg = &arr[0];
 
         assert_eq!("XBT/USD", candlestick_msg.symbol);
         assert_eq!(1675209613118, candlestick_msg.timestamp);
-        assert_eq!(1675209600, candlestick_msg.begin_time);
-        assert_eq!("1", candlestick_msg.period);
+        assert_eq!(1675209600000, candlestick_msg.begin_time);
+        assert_eq!("1m", candlestick_msg.period.to_string());
 
         assert_eq!(23135.0, candlestick_msg.open);
         assert_eq!(23135.4, candlestick_msg.high);
@@ -474,13 +475,46 @@ mod candlestick {
         assert_eq!(0.39012812, candlestick_msg.volume);
         assert_eq!(Some(0.39012812 * 23135.13297), candlestick_msg.quote_volume);
     }
+
+    #[test]
+    fn inverse_swap() {
+        let raw_msg = r#"{"feed":"candles_trade_1m","candle":{"time":1654243200000,"open":"29980.5","high":"30008.0","low":"29978.0","close":"30000.0","volume":150000},"product_id":"PI_XBTUSD"}"#;
+
+        assert_eq!(MessageType::Candlestick, get_msg_type(EXCHANGE_NAME, raw_msg));
+        assert_eq!(
+            1654243200000,
+            extract_timestamp(EXCHANGE_NAME, MarketType::InverseSwap, raw_msg).unwrap().unwrap()
+        );
+        assert_eq!(
+            "PI_XBTUSD",
+            extract_symbol(EXCHANGE_NAME, MarketType::InverseSwap, raw_msg).unwrap()
+        );
+
+        let arr =
+            parse_candlestick(EXCHANGE_NAME, MarketType::InverseSwap, raw_msg, None, None).unwrap();
+        assert_eq!(1, arr.len());
+        let candlestick_msg = &arr[0];
+
+        assert_eq!("PI_XBTUSD", candlestick_msg.symbol);
+        assert_eq!("BTC/USD", candlestick_msg.pair);
+        assert_eq!(1654243200000, candlestick_msg.timestamp);
+        assert_eq!(1654243200000, candlestick_msg.begin_time);
+        assert_eq!("1m", candlestick_msg.period.to_string());
+
+        assert_eq!(29980.5, candlestick_msg.open);
+        assert_eq!(30008.0, candlestick_msg.high);
+        assert_eq!(29978.0, candlestick_msg.low);
+        assert_eq!(30000.0, candlestick_msg.close);
+        assert_eq!(5.0, candlestick_msg.volume);
+        assert_eq!(Some(150000.0), candlestick_msg.quote_volume);
+    }
 }
 
 #[cfg(test)]
 mod ticker {
     use super::EXCHANGE_NAME;
     use crypto_market_type::MarketType;
-    use crypto_msg_parser::{extract_symbol, extract_timestamp};
+    use crypto_msg_parser::{extract_symbol, extract_timestamp, parse_ticker};
 
     #[test]
     fn spot() {
@@ -488,6 +522,18 @@ mod ticker {
 
         assert_eq!(None, extract_timestamp(EXCHANGE_NAME, MarketType::Spot, raw_msg).unwrap());
         assert_eq!("XBT/USD", extract_symbol(EXCHANGE_NAME, MarketType::Spot, raw_msg).unwrap());
+
+        let tickers =
+            &parse_ticker(EXCHANGE_NAME, MarketType::Spot, raw_msg, Some(1654164208520)).unwrap();
+        assert_eq!(1, tickers.len());
+        crate::utils::check_ticker_fields(
+            EXCHANGE_NAME,
+            MarketType::Spot,
+            "BTC/USD".to_string(),
+            "XBT/USD".to_string(),
+            &tickers[0],
+            raw_msg,
+        );
     }
 
     #[test]
@@ -561,3 +607,66 @@ mod l2_snapshot {
         );
     }
 }
+
+#[cfg(test)]
+mod checksum {
+    use crypto_market_type::MarketType;
+    use crypto_msg_parser::ChecksumBook;
+
+    #[test]
+    fn spot() {
+        let mut book = ChecksumBook::new("kraken", MarketType::Spot);
+
+        // The book of the checksum example in https://docs.kraken.com/websockets/#book-checksum,
+        // whose concatenated string is
+        // 50055005010500501550050205005025500503050050355005040500504550050505005000500499550049905004985500498050049755004970500496550049605004955500
+        let raw_msg = r#"[0,{"as":[["0.05005","0.00000500","1582905487.684110"],["0.05010","0.00000500","1582905486.187983"],["0.05015","0.00000500","1582905484.480241"],["0.05020","0.00000500","1582905486.645658"],["0.05025","0.00000500","1582905486.859009"],["0.05030","0.00000500","1582905488.601486"],["0.05035","0.00000500","1582905488.357312"],["0.05040","0.00000500","1582905488.785484"],["0.05045","0.00000500","1582905485.302661"],["0.05050","0.00000500","1582905486.157467"]],"bs":[["0.05000","0.00000500","1582905487.439814"],["0.04995","0.00000500","1582905485.119396"],["0.04990","0.00000500","1582905486.432052"],["0.04985","0.00000500","1582905484.749349"],["0.04980","0.00000500","1582905486.251330"],["0.04975","0.00000500","1582905484.899885"],["0.04970","0.00000500","1582905486.362120"],["0.04965","0.00000500","1582905486.109063"],["0.04960","0.00000500","1582905486.385742"],["0.04955","0.00000500","1582905487.193037"]]},"book-10","XBT/USD"]"#;
+        assert_eq!(None, book.update(raw_msg).unwrap());
+        // CRC32 of the concatenated string above
+        assert_eq!(2726735196, book.checksum());
+
+        // republishes a level, the book doesn't change
+        let raw_msg = r#"[0,{"a":[["0.05005","0.00000500","1582905489.000000"]],"c":"2726735196"},"book-10","XBT/USD"]"#;
+        assert_eq!(Some(true), book.update(raw_msg).unwrap());
+
+        let raw_msg = r#"[0,{"b":[["0.05000","0.00000000","1582905490.000000"]],"c":"2726735196"},"book-10","XBT/USD"]"#;
+        assert_eq!(Some(false), book.update(raw_msg).unwrap());
+
+        let orderbook = book.book().unwrap();
+        assert_eq!(orderbook.len(), (10, 9));
+        assert_eq!(orderbook.best_ask().unwrap().price, 0.05005);
+        assert_eq!(orderbook.best_bid().unwrap().price, 0.04995);
+    }
+
+    #[test]
+    fn unsupported() {
+        let mut book = ChecksumBook::new("kraken", MarketType::InverseFuture);
+        assert!(book.update(r#"{"feed":"book","product_id":"PI_XBTUSD"}"#).is_err());
+    }
+}
+
+#[cfg(test)]
+mod funding_rate {
+    use super::EXCHANGE_NAME;
+    use crypto_market_type::MarketType;
+    use crypto_msg_parser::parse_funding_rate;
+
+    #[test]
+    fn inverse_swap() {
+        let raw_msg = r#"{"time":1654164951042,"product_id":"PI_XBTUSD","funding_rate":3.216424e-12,"funding_rate_prediction":-8.63581351e-10,"relative_funding_rate":9.6321875e-8,"relative_funding_rate_prediction":-0.000025847309375,"next_funding_rate_time":1654171200000,"feed":"ticker","bid":29914.5,"ask":29925.0,"bid_size":8400.0,"ask_size":10000.0,"volume":100353210.0,"dtm":0,"leverage":"50x","index":29927.42,"premium":-0.0,"last":29929.0,"change":-5.280481050716035,"suspended":false,"tag":"perpetual","pair":"XBT:USD","openInterest":43967525.0,"markPrice":29919.75,"maturityTime":0,"post_only":false}"#;
+        let funding_rates =
+            &parse_funding_rate(EXCHANGE_NAME, MarketType::InverseSwap, raw_msg, None).unwrap();
+
+        assert_eq!(funding_rates.len(), 1);
+        assert_eq!(funding_rates[0].symbol, "PI_XBTUSD");
+        assert_eq!(funding_rates[0].pair, "BTC/USD");
+        assert_eq!(funding_rates[0].funding_rate, 9.6321875e-8);
+        assert_eq!(funding_rates[0].estimated_rate, Some(-0.000025847309375));
+        assert_eq!(funding_rates[0].funding_time, 1654171200000);
+        assert_eq!(funding_rates[0].timestamp, 1654164951042);
+        assert_eq!(funding_rates[0].funding_interval, 4 * 3600000);
+        assert!((funding_rates[0].annualized_rate - 0.000210945).abs() < 1e-9);
+
+        assert!(parse_funding_rate(EXCHANGE_NAME, MarketType::Spot, raw_msg, None).is_err());
+    }
+}
@@ -136,6 +136,7 @@ mod l2_event {
         assert!(!orderbook.snapshot);
         assert_eq!(orderbook.timestamp, received_at);
         assert_eq!(orderbook.seq_id, Some(1617071937790));
+        assert_eq!(orderbook.prev_seq_id, Some(1617071937789));
 
         crate::utils::check_orderbook_fields(
             EXCHANGE_NAME,
@@ -594,7 +595,8 @@ mod bbo {
 mod l3_event {
     use super::EXCHANGE_NAME;
     use crypto_market_type::MarketType;
-    use crypto_msg_parser::{extract_symbol, extract_timestamp};
+    use crypto_message::{L3Action, TradeSide};
+    use crypto_msg_parser::{extract_symbol, extract_timestamp, parse_l3};
 
     #[test]
     fn spot() {
@@ -605,6 +607,15 @@ mod l3_event {
             extract_timestamp(EXCHANGE_NAME, MarketType::Spot, raw_msg).unwrap().unwrap()
         );
         assert_eq!("BTC-USDT", extract_symbol(EXCHANGE_NAME, MarketType::Spot, raw_msg).unwrap());
+        assert!(parse_l3(EXCHANGE_NAME, MarketType::Spot, raw_msg, None).unwrap().is_empty());
+
+        let raw_msg = r#"{"type":"message","topic":"/spotMarket/level3:BTC-USDT","subject":"update","data":{"symbol":"BTC-USDT","orderId":"629724de1f7e6b00015310cb","sequence":1630234429275,"size":"0.05","ts":1654072542371747612}}"#;
+        let l3_msg = &parse_l3(EXCHANGE_NAME, MarketType::Spot, raw_msg, None).unwrap()[0];
+        assert_eq!(L3Action::Change, l3_msg.action);
+        assert_eq!(None, l3_msg.price);
+        assert_eq!(0.05, l3_msg.quantity_base);
+        assert_eq!(1654072542371, l3_msg.timestamp);
+        assert_eq!(Some(1630234429275), l3_msg.seq_id);
     }
 
     #[test]
@@ -633,6 +644,18 @@ mod l3_event {
             "XBTUSDM",
             extract_symbol(EXCHANGE_NAME, MarketType::InverseSwap, raw_msg).unwrap()
         );
+
+        let l3_msgs = parse_l3(EXCHANGE_NAME, MarketType::InverseSwap, raw_msg, None).unwrap();
+        assert_eq!(1, l3_msgs.len());
+        let l3_msg = &l3_msgs[0];
+        assert_eq!("BTC/USD", l3_msg.pair);
+        assert_eq!("629727c9edde6b0001f422a7", l3_msg.order_id);
+        assert_eq!(L3Action::Open, l3_msg.action);
+        assert_eq!(Some(TradeSide::Buy), l3_msg.side);
+        assert_eq!(Some(31570.0), l3_msg.price);
+        assert_eq!(Some(3671.0), l3_msg.quantity_contract);
+        assert_eq!(3671.0, l3_msg.quantity_quote);
+        assert_eq!(1654073289160, l3_msg.timestamp);
     }
 
     #[test]
@@ -667,18 +690,18 @@ mod candlestick {
         assert_eq!("BTC-USDT", extract_symbol(EXCHANGE_NAME, MarketType::Spot, raw_msg).unwrap());
 
         let candlestick_msg =
-            &parse_candlestick(EXCHANGE_NAME, MarketType::Spot, raw_msg, None).unwrap()[0];
+            &parse_candlestick(EXCHANGE_NAME, MarketType::Spot, raw_msg, None, None).unwrap()[0];
 
         assert_eq!(1654081935182, candlestick_msg.timestamp);
         assert_eq!("BTC-USDT", candlestick_msg.symbol);
-        assert_eq!(1653523200, candlestick_msg.begin_time);
+        assert_eq!(1653523200000, candlestick_msg.begin_time);
         assert_eq!(29543.6, candlestick_msg.open);
         assert_eq!(32406.7, candlestick_msg.high);
         assert_eq!(28014.1, candlestick_msg.low);
         assert_eq!(31613.8, candlestick_msg.close);
         assert_eq!(93044.50911291, candlestick_msg.volume);
         assert_eq!(Some(2_792_095_272.950_902), candlestick_msg.quote_volume);
-        assert_eq!("1week", candlestick_msg.period);
+        assert_eq!("1w", candlestick_msg.period.to_string());
     }
 
     #[test]
@@ -728,7 +751,7 @@ mod candlestick {
 mod ticker {
     use super::EXCHANGE_NAME;
     use crypto_market_type::MarketType;
-    use crypto_msg_parser::{extract_symbol, extract_timestamp};
+    use crypto_msg_parser::{extract_symbol, extract_timestamp, parse_ticker};
 
     #[test]
     fn spot() {
@@ -739,6 +762,40 @@ mod ticker {
             extract_timestamp(EXCHANGE_NAME, MarketType::Spot, raw_msg).unwrap().unwrap()
         );
         assert_eq!("BTC-USDT", extract_symbol(EXCHANGE_NAME, MarketType::Spot, raw_msg).unwrap());
+
+        let tickers = &parse_ticker(EXCHANGE_NAME, MarketType::Spot, raw_msg, None).unwrap();
+        assert_eq!(1, tickers.len());
+        crate::utils::check_ticker_fields(
+            EXCHANGE_NAME,
+            MarketType::Spot,
+            "BTC/USDT".to_string(),
+            "BTC-USDT".to_string(),
+            &tickers[0],
+            raw_msg,
+        );
+    }
+
+    #[test]
+    fn spot_all() {
+        let raw_msg = r#"{"type":"message","topic":"/market/ticker:all","subject":"DOT-USDT","data":{"bestAsk":"10.4686","bestAskSize":"64.9647","bestBid":"10.4647","bestBidSize":"0.1416","price":"10.4686","sequence":"1619386350765","size":"0.0153","time":1653955200018}}"#;
+
+        let tickers = &parse_ticker(EXCHANGE_NAME, MarketType::Spot, raw_msg, None).unwrap();
+        assert_eq!(1, tickers.len());
+        crate::utils::check_ticker_fields(
+            EXCHANGE_NAME,
+            MarketType::Spot,
+            "DOT/USDT".to_string(),
+            "DOT-USDT".to_string(),
+            &tickers[0],
+            raw_msg,
+        );
+        assert_eq!(1653955200018, tickers[0].timestamp);
+        assert_eq!(10.4686, tickers[0].close);
+        assert_eq!(Some(0.0153), tickers[0].last_quantity);
+        assert_eq!(Some(10.4647), tickers[0].best_bid_price);
+        assert_eq!(Some(0.1416), tickers[0].best_bid_quantity);
+        assert_eq!(Some(10.4686), tickers[0].best_ask_price);
+        assert_eq!(Some(64.9647), tickers[0].best_ask_quantity);
     }
 
     #[test]
@@ -753,6 +810,18 @@ mod ticker {
             "XBTMM22",
             extract_symbol(EXCHANGE_NAME, MarketType::InverseSwap, raw_msg).unwrap()
         );
+
+        let tickers =
+            &parse_ticker(EXCHANGE_NAME, MarketType::InverseFuture, raw_msg, None).unwrap();
+        assert_eq!(1, tickers.len());
+        crate::utils::check_ticker_fields(
+            EXCHANGE_NAME,
+            MarketType::InverseFuture,
+            "BTC/USD".to_string(),
+            "XBTMM22".to_string(),
+            &tickers[0],
+            raw_msg,
+        );
     }
 
     #[test]
@@ -767,6 +836,17 @@ mod ticker {
             "XBTUSDM",
             extract_symbol(EXCHANGE_NAME, MarketType::InverseSwap, raw_msg).unwrap()
         );
+
+        let tickers = &parse_ticker(EXCHANGE_NAME, MarketType::InverseSwap, raw_msg, None).unwrap();
+        assert_eq!(1, tickers.len());
+        crate::utils::check_ticker_fields(
+            EXCHANGE_NAME,
+            MarketType::InverseSwap,
+            "BTC/USD".to_string(),
+            "XBTUSDM".to_string(),
+            &tickers[0],
+            raw_msg,
+        );
     }
 
     #[test]
@@ -781,6 +861,17 @@ mod ticker {
             "XBTUSDTM",
             extract_symbol(EXCHANGE_NAME, MarketType::LinearSwap, raw_msg).unwrap()
         );
+
+        let tickers = &parse_ticker(EXCHANGE_NAME, MarketType::LinearSwap, raw_msg, None).unwrap();
+        assert_eq!(1, tickers.len());
+        crate::utils::check_ticker_fields(
+            EXCHANGE_NAME,
+            MarketType::LinearSwap,
+            "BTC/USDT".to_string(),
+            "XBTUSDTM".to_string(),
+            &tickers[0],
+            raw_msg,
+        );
     }
 }
 
@@ -788,12 +879,15 @@ mod ticker {
 mod l2_snapshot {
     use super::EXCHANGE_NAME;
     use crypto_market_type::MarketType;
-    use crypto_msg_parser::{extract_symbol, extract_timestamp};
+    use crypto_msg_parser::{extract_symbol, extract_timestamp, get_msg_type};
+    use crypto_msg_type::MessageType;
 
     #[test]
     fn spot() {
         let raw_msg = r#"{"code":"200000","data":{"time":1654325095225,"sequence":"1630423725254","bids":[["29701.4","1.1244206"],["29701.2","0.00006727"],["29700.1","0.49009689"]],"asks":[["29701.5","0.00023088"],["29701.6","0.48701789"],["29701.7","0.00034976"]]}}"#;
 
+        assert_eq!(MessageType::L2Snapshot, get_msg_type(EXCHANGE_NAME, raw_msg));
+
         assert_eq!(
             1654325095225,
             extract_timestamp(EXCHANGE_NAME, MarketType::Spot, raw_msg).unwrap().unwrap()
@@ -805,6 +899,8 @@ mod l2_snapshot {
     fn inverse_future() {
         let raw_msg = r#"{"code":"200000","data":{"symbol":"XBTMM22","sequence":1647109356374,"asks":[[29680.0,1400],[29688.0,1365],[29695.0,150]],"bids":[[29665.0,5463],[29664.0,1300],[29651.0,1344]],"ts":1654325996914997044}}"#;
 
+        assert_eq!(MessageType::L2Snapshot, get_msg_type(EXCHANGE_NAME, raw_msg));
+
         assert_eq!(
             1654325996914,
             extract_timestamp(EXCHANGE_NAME, MarketType::InverseSwap, raw_msg).unwrap().unwrap()
@@ -819,6 +915,8 @@ mod l2_snapshot {
     fn inverse_swap() {
         let raw_msg = r#"{"code":"200000","data":{"symbol":"XBTUSDM","sequence":1638901902423,"asks":[[29626.0,2521],[29627.0,16476],[29630.0,6266]],"bids":[[29625.0,15226],[29624.0,6845],[29623.0,2521]],"ts":1654326900579981822}}"#;
 
+        assert_eq!(MessageType::L2Snapshot, get_msg_type(EXCHANGE_NAME, raw_msg));
+
         assert_eq!(
             1654326900579,
             extract_timestamp(EXCHANGE_NAME, MarketType::InverseSwap, raw_msg).unwrap().unwrap()
@@ -833,6 +931,8 @@ mod l2_snapshot {
     fn linear_swap() {
         let raw_msg = r#"{"code":"200000","data":{"symbol":"XBTUSDTM","sequence":1645045405918,"asks":[[29641.0,30109],[29642.0,7922],[29643.0,5820]],"bids":[[29640.0,13007],[29639.0,1072],[29638.0,169]],"ts":1654326922830525196}}"#;
 
+        assert_eq!(MessageType::L2Snapshot, get_msg_type(EXCHANGE_NAME, raw_msg));
+
         assert_eq!(
             1654326922830,
             extract_timestamp(EXCHANGE_NAME, MarketType::LinearSwap, raw_msg).unwrap().unwrap()
@@ -859,3 +959,87 @@ mod open_interest {
         assert_eq!(None, extract_timestamp(EXCHANGE_NAME, MarketType::Unknown, raw_msg).unwrap());
     }
 }
+
+#[cfg(test)]
+mod funding_rate {
+    use super::EXCHANGE_NAME;
+    use crypto_market_type::MarketType;
+    use crypto_msg_parser::{extract_symbol, extract_timestamp, parse_funding_rate};
+
+    #[test]
+    fn linear_swap() {
+        let raw_msg = r#"{"topic":"/contract/instrument:XBTUSDTM","subject":"funding.rate","data":{"granularity":60000,"fundingRate":-0.000013,"timestamp":1654161540000},"type":"message"}"#;
+        let funding_rates =
+            &parse_funding_rate(EXCHANGE_NAME, MarketType::LinearSwap, raw_msg, None).unwrap();
+
+        assert_eq!(funding_rates.len(), 1);
+        crate::utils::check_funding_rate_fields(
+            EXCHANGE_NAME,
+            MarketType::LinearSwap,
+            &funding_rates[0],
+            raw_msg,
+        );
+        assert_eq!(
+            "XBTUSDTM",
+            extract_symbol(EXCHANGE_NAME, MarketType::LinearSwap, raw_msg).unwrap()
+        );
+        assert_eq!(
+            1654161540000,
+            extract_timestamp(EXCHANGE_NAME, MarketType::LinearSwap, raw_msg).unwrap().unwrap()
+        );
+
+        assert_eq!(funding_rates[0].pair, "BTC/USDT");
+        assert_eq!(funding_rates[0].funding_rate, -0.000013);
+        assert_eq!(funding_rates[0].estimated_rate, None);
+        assert_eq!(funding_rates[0].funding_time, 1654171200000);
+        assert_eq!(funding_rates[0].timestamp, 1654161540000);
+    }
+
+    #[test]
+    fn contracts() {
+        let raw_msg = r#"{"code":"200000","data":[{"symbol":"XBTUSDTM","rootSymbol":"USDT","type":"FFWCSX","firstOpenDate":1585555200000,"expireDate":null,"settleDate":null,"baseCurrency":"XBT","quoteCurrency":"USDT","settleCurrency":"USDT","maxOrderQty":1000000,"maxPrice":1000000.0000000000,"lotSize":1,"tickSize":1.0,"indexPriceTickSize":0.01,"multiplier":0.001,"initialMargin":0.01,"maintainMargin":0.005,"maxRiskLimit":2000000,"minRiskLimit":2000000,"riskStep":1000000,"makerFeeRate":0.00020,"takerFeeRate":0.00060,"takerFixFee":0.0000000000,"makerFixFee":0.0000000000,"settlementFee":null,"isDeleverage":true,"isQuanto":true,"isInverse":false,"markMethod":"FairPrice","fairMethod":"FundingRate","fundingBaseSymbol":".XBTINT8H","fundingQuoteSymbol":".USDTINT8H","fundingRateSymbol":".XBTUSDTMFPI8H","indexSymbol":".KXBTUSDT","settlementSymbol":"","status":"Open","fundingFeeRate":-0.000013,"predictedFundingFeeRate":0.000048,"openInterest":"9876432","turnoverOf24h":751931474.32877920,"volumeOf24h":25408.11100000,"markPrice":29538.28,"indexPrice":29538.62,"lastTradePrice":29526.0000000000,"nextFundingRateTime":25242841,"maxLeverage":100,"sourceExchanges":["huobi","Okex","Binance","Kucoin","Poloniex","Hitbtc"],"premiumsSymbol1M":".XBTUSDTMPI","premiumsSymbol8H":".XBTUSDTMPI8H","fundingBaseSymbol1M":".XBTINT","fundingQuoteSymbol1M":".USDTINT","lowPrice":29275,"highPrice":29880,"priceChgPct":0.0004,"priceChg":13},{"symbol":"XBTUSDM","rootSymbol":"XBT","type":"FFWCSX","firstOpenDate":1552638575000,"expireDate":null,"settleDate":null,"baseCurrency":"XBT","quoteCurrency":"USD","settleCurrency":"XBT","maxOrderQty":10000000,"maxPrice":1000000.0000000000,"lotSize":1,"tickSize":1.0,"indexPriceTickSize":0.01,"multiplier":-1.0,"initialMargin":0.02,"maintainMargin":0.01,"maxRiskLimit":40,"minRiskLimit":40,"riskStep":20,"makerFeeRate":0.00020,"takerFeeRate":0.00060,"takerFixFee":0.0000000000,"makerFixFee":0.0000000000,"settlementFee":null,"isDeleverage":true,"isQuanto":false,"isInverse":true,"markMethod":"FairPrice","fairMethod":"FundingRate","fundingBaseSymbol":".XBTINT8H","fundingQuoteSymbol":".USDINT8H","fundingRateSymbol":".XBTUSDMFPI8H","indexSymbol":".BXBT","settlementSymbol":null,"status":"Open","fundingFeeRate":0.000100,"predictedFundingFeeRate":0.000086,"openInterest":"36857949","turnoverOf24h":480.86258578,"volumeOf24h":14215097.00000000,"markPrice":29519.05,"indexPrice":29515.53,"lastTradePrice":29486.0000000000,"nextFundingRateTime":25242830,"maxLeverage":50,"sourceExchanges":["Bitstamp","Bittrex","Coinbase","Gemini","Kraken","Liquid"],"premiumsSymbol1M":".XBTUSDMPI","premiumsSymbol8H":".XBTUSDMPI8H","fundingBaseSymbol1M":".XBTINT","fundingQuoteSymbol1M":".USDINT","lowPrice":29274,"highPrice":29864,"priceChgPct":0.0003,"priceChg":10}]}"#;
+        let received_at = 1654145957159;
+        let funding_rates =
+            &parse_funding_rate(EXCHANGE_NAME, MarketType::Unknown, raw_msg, Some(received_at))
+                .unwrap();
+
+        assert_eq!(funding_rates.len(), 2);
+
+        assert_eq!(funding_rates[0].market_type, MarketType::LinearSwap);
+        assert_eq!(funding_rates[0].symbol, "XBTUSDTM");
+        assert_eq!(funding_rates[0].pair, "BTC/USDT");
+        assert_eq!(funding_rates[0].funding_rate, -0.000013);
+        assert_eq!(funding_rates[0].estimated_rate, Some(0.000048));
+        assert_eq!(funding_rates[0].funding_time, 1654171200000);
+        assert_eq!(funding_rates[0].timestamp, received_at);
+
+        assert_eq!(funding_rates[1].market_type, MarketType::InverseSwap);
+        assert_eq!(funding_rates[1].symbol, "XBTUSDM");
+        assert_eq!(funding_rates[1].pair, "BTC/USD");
+        assert_eq!(funding_rates[1].funding_rate, 0.0001);
+        assert_eq!(funding_rates[1].estimated_rate, Some(0.000086));
+        assert_eq!(funding_rates[1].funding_time, 1654171200000);
+
+        assert!(parse_funding_rate(EXCHANGE_NAME, MarketType::Unknown, raw_msg, None).is_err());
+    }
+
+    #[test]
+    fn contract() {
+        let raw_msg = r#"{"code":"200000","data":{"symbol":"ETHUSDTM","rootSymbol":"USDT","type":"FFWCSX","baseCurrency":"ETH","quoteCurrency":"USDT","settleCurrency":"USDT","multiplier":0.01,"isInverse":false,"status":"Open","fundingFeeRate":0.0001,"predictedFundingFeeRate":0.00005,"fundingRateGranularity":14400000,"fundingRateCap":0.003,"fundingRateFloor":-0.003,"openInterest":"5627462","markPrice":1812.35,"indexPrice":1812.41,"nextFundingRateTime":3600000}}"#;
+        let received_at = 1654160400000;
+        let funding_rates =
+            &parse_funding_rate(EXCHANGE_NAME, MarketType::LinearSwap, raw_msg, Some(received_at))
+                .unwrap();
+
+        assert_eq!(funding_rates.len(), 1);
+        assert_eq!(funding_rates[0].pair, "ETH/USDT");
+        assert_eq!(funding_rates[0].funding_rate, 0.0001);
+        assert_eq!(funding_rates[0].estimated_rate, Some(0.00005));
+        assert_eq!(funding_rates[0].funding_time, 1654164000000);
+        assert_eq!(funding_rates[0].funding_interval, 4 * 3600000);
+        assert_eq!(funding_rates[0].funding_rate_cap, Some(0.003));
+        assert_eq!(funding_rates[0].funding_rate_floor, Some(-0.003));
+        assert_eq!(funding_rates[0].annualized_rate, 0.219);
+        assert_eq!(funding_rates[0].json, raw_msg);
+    }
+}
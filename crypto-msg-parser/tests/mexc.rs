@@ -567,15 +567,16 @@ mod candlestick {
         assert_eq!(1654083133266, timestamp.unwrap());
         assert_eq!("BTC_USDT", extract_symbol(EXCHANGE_NAME, MarketType::Spot, raw_msg).unwrap());
 
-        let arr = parse_candlestick(EXCHANGE_NAME, MarketType::Spot, raw_msg, timestamp).unwrap();
+        let arr =
+            parse_candlestick(EXCHANGE_NAME, MarketType::Spot, raw_msg, None, timestamp).unwrap();
         assert_eq!(1, arr.len());
         let candlestick_msg = &arr[0];
 
         assert_eq!("BTC_USDT", candlestick_msg.symbol);
         assert_eq!("BTC/USDT", candlestick_msg.pair);
         assert_eq!(1654083133266, candlestick_msg.timestamp);
-        assert_eq!(1654083120 * 1000 - 60 * 1000, candlestick_msg.begin_time);
-        assert_eq!("Min1", candlestick_msg.period);
+        assert_eq!(1654083120000, candlestick_msg.begin_time);
+        assert_eq!("1m", candlestick_msg.period.to_string());
 
         assert_eq!(31512.39, candlestick_msg.open);
         assert_eq!(31514.41, candlestick_msg.high);
@@ -593,15 +594,16 @@ mod candlestick {
 
         assert_eq!("DXGM_USDT", extract_symbol(EXCHANGE_NAME, MarketType::Spot, raw_msg).unwrap());
 
-        let arr = parse_candlestick(EXCHANGE_NAME, MarketType::Spot, raw_msg, timestamp).unwrap();
+        let arr =
+            parse_candlestick(EXCHANGE_NAME, MarketType::Spot, raw_msg, None, timestamp).unwrap();
         assert_eq!(1, arr.len());
         let candlestick_msg = &arr[0];
 
         assert_eq!("DXGM_USDT", candlestick_msg.symbol);
         assert_eq!("DXGM/USDT", candlestick_msg.pair);
         assert_eq!(1638241800 * 1000, candlestick_msg.timestamp);
-        assert_eq!(1638241800 * 1000 - 5 * 60 * 1000, candlestick_msg.begin_time);
-        assert_eq!("Min5", candlestick_msg.period);
+        assert_eq!(1638241800000, candlestick_msg.begin_time);
+        assert_eq!("5m", candlestick_msg.period.to_string());
 
         assert_eq!(0.0268, candlestick_msg.open);
         assert_eq!(0.0273, candlestick_msg.high);
@@ -623,15 +625,16 @@ mod candlestick {
         );
 
         let arr =
-            parse_candlestick(EXCHANGE_NAME, MarketType::InverseSwap, raw_msg, timestamp).unwrap();
+            parse_candlestick(EXCHANGE_NAME, MarketType::InverseSwap, raw_msg, None, timestamp)
+                .unwrap();
         assert_eq!(1, arr.len());
         let candlestick_msg = &arr[0];
 
         assert_eq!("BTC_USD", candlestick_msg.symbol);
         assert_eq!("BTC/USD", candlestick_msg.pair);
         assert_eq!(1654083720 * 1000, candlestick_msg.timestamp);
-        assert_eq!(1654083720 * 1000 - 60 * 1000, candlestick_msg.begin_time);
-        assert_eq!("Min1", candlestick_msg.period);
+        assert_eq!(1654083720000, candlestick_msg.begin_time);
+        assert_eq!("1m", candlestick_msg.period.to_string());
 
         assert_eq!(31439.5, candlestick_msg.open);
         assert_eq!(31439.5, candlestick_msg.high);
@@ -653,15 +656,16 @@ mod candlestick {
         assert_eq!(1654083512507, timestamp.unwrap());
 
         let arr =
-            parse_candlestick(EXCHANGE_NAME, MarketType::LinearSwap, raw_msg, timestamp).unwrap();
+            parse_candlestick(EXCHANGE_NAME, MarketType::LinearSwap, raw_msg, None, timestamp)
+                .unwrap();
         assert_eq!(1, arr.len());
         let candlestick_msg = &arr[0];
 
         assert_eq!("BTC_USDT", candlestick_msg.symbol);
         assert_eq!("BTC/USDT", candlestick_msg.pair);
         assert_eq!(1654083480 * 1000, candlestick_msg.timestamp);
-        assert_eq!(1654083480 * 1000 - 60 * 1000, candlestick_msg.begin_time);
-        assert_eq!("Min1", candlestick_msg.period);
+        assert_eq!(1654083480000, candlestick_msg.begin_time);
+        assert_eq!("1m", candlestick_msg.period.to_string());
 
         assert_eq!(31576.0, candlestick_msg.open);
         assert_eq!(31585.5, candlestick_msg.high);
@@ -676,7 +680,7 @@ mod candlestick {
 mod ticker {
     use super::EXCHANGE_NAME;
     use crypto_market_type::MarketType;
-    use crypto_msg_parser::{extract_symbol, extract_timestamp};
+    use crypto_msg_parser::{extract_symbol, extract_timestamp, parse_ticker};
 
     #[test]
     fn inverse_swap() {
@@ -690,6 +694,17 @@ mod ticker {
             "BTC_USD",
             extract_symbol(EXCHANGE_NAME, MarketType::InverseSwap, raw_msg).unwrap()
         );
+
+        let tickers = &parse_ticker(EXCHANGE_NAME, MarketType::InverseSwap, raw_msg, None).unwrap();
+        assert_eq!(1, tickers.len());
+        crate::utils::check_ticker_fields(
+            EXCHANGE_NAME,
+            MarketType::InverseSwap,
+            "BTC/USD".to_string(),
+            "BTC_USD".to_string(),
+            &tickers[0],
+            raw_msg,
+        );
     }
 
     #[test]
@@ -704,6 +719,17 @@ mod ticker {
             "BTC_USDT",
             extract_symbol(EXCHANGE_NAME, MarketType::LinearSwap, raw_msg).unwrap()
         );
+
+        let tickers = &parse_ticker(EXCHANGE_NAME, MarketType::LinearSwap, raw_msg, None).unwrap();
+        assert_eq!(1, tickers.len());
+        crate::utils::check_ticker_fields(
+            EXCHANGE_NAME,
+            MarketType::LinearSwap,
+            "BTC/USDT".to_string(),
+            "BTC_USDT".to_string(),
+            &tickers[0],
+            raw_msg,
+        );
     }
 }
 
@@ -711,12 +737,15 @@ mod ticker {
 mod l2_snapshot {
     use super::EXCHANGE_NAME;
     use crypto_market_type::MarketType;
-    use crypto_msg_parser::{extract_symbol, extract_timestamp};
+    use crypto_msg_parser::{extract_symbol, extract_timestamp, get_msg_type};
+    use crypto_msg_type::MessageType;
 
     #[test]
     fn spot() {
         let raw_msg = r#"{"code":200,"data":{"asks":[{"price":"29653.11","quantity":"2.401543"},{"price":"29653.12","quantity":"0.033048"},{"price":"29653.41","quantity":"0.40332"}],"bids":[{"price":"29653.08","quantity":"3.101382"},{"price":"29653.07","quantity":"0.018948"},{"price":"29653.04","quantity":"0.003084"}],"version":"1535017255"}}"#;
 
+        assert_eq!(MessageType::L2Snapshot, get_msg_type(EXCHANGE_NAME, raw_msg));
+
         assert_eq!(
             None,
             extract_timestamp(EXCHANGE_NAME, MarketType::InverseSwap, raw_msg).unwrap()
@@ -731,6 +760,8 @@ mod l2_snapshot {
     fn inverse_swap() {
         let raw_msg = r#"{"success":true,"code":0,"data":{"asks":[[29625,630,1],[29625.5,371,1],[29626,581,1]],"bids":[[29618,357,1],[29617.5,357,1],[29617,581,1]],"version":3094693618,"timestamp":1654326901060}}"#;
 
+        assert_eq!(MessageType::L2Snapshot, get_msg_type(EXCHANGE_NAME, raw_msg));
+
         assert_eq!(
             1654326901060,
             extract_timestamp(EXCHANGE_NAME, MarketType::InverseSwap, raw_msg).unwrap().unwrap()
@@ -745,6 +776,8 @@ mod l2_snapshot {
     fn linear_swap() {
         let raw_msg = r#"{"success":true,"code":0,"data":{"asks":[[29676.5,20696,2],[29677,19821,2],[29677.5,50122,2]],"bids":[[29675.5,59944,2],[29675,29676,2],[29674.5,15455,2]],"version":5216820883,"timestamp":1654328706778}}"#;
 
+        assert_eq!(MessageType::L2Snapshot, get_msg_type(EXCHANGE_NAME, raw_msg));
+
         assert_eq!(
             1654328706778,
             extract_timestamp(EXCHANGE_NAME, MarketType::LinearSwap, raw_msg).unwrap().unwrap()
@@ -752,3 +785,87 @@ mod l2_snapshot {
         assert_eq!("NONE", extract_symbol(EXCHANGE_NAME, MarketType::LinearSwap, raw_msg).unwrap());
     }
 }
+
+#[cfg(test)]
+mod funding_rate {
+    use super::EXCHANGE_NAME;
+    use crypto_market_type::MarketType;
+    use crypto_msg_parser::{extract_symbol, extract_timestamp, parse_funding_rate};
+
+    #[test]
+    fn inverse_swap() {
+        let raw_msg = r#"{"channel":"push.funding.rate","data":{"rate":-0.000012,"symbol":"BTC_USD"},"symbol":"BTC_USD","ts":1654165977028}"#;
+        let funding_rates =
+            &parse_funding_rate(EXCHANGE_NAME, MarketType::InverseSwap, raw_msg, None).unwrap();
+
+        assert_eq!(funding_rates.len(), 1);
+        crate::utils::check_funding_rate_fields(
+            EXCHANGE_NAME,
+            MarketType::InverseSwap,
+            &funding_rates[0],
+            raw_msg,
+        );
+        assert_eq!(
+            "BTC_USD",
+            extract_symbol(EXCHANGE_NAME, MarketType::InverseSwap, raw_msg).unwrap()
+        );
+        assert_eq!(
+            1654165977028,
+            extract_timestamp(EXCHANGE_NAME, MarketType::InverseSwap, raw_msg).unwrap().unwrap()
+        );
+
+        assert_eq!(funding_rates[0].pair, "BTC/USD");
+        assert_eq!(funding_rates[0].funding_rate, -0.000012);
+        assert_eq!(funding_rates[0].estimated_rate, None);
+        assert_eq!(funding_rates[0].funding_time, 1654185600000);
+        assert_eq!(funding_rates[0].timestamp, 1654165977028);
+    }
+
+    #[test]
+    fn linear_swap_rest() {
+        let raw_msg = r#"{"success":true,"code":0,"data":{"symbol":"BTC_USDT","fundingRate":0.000035,"maxFundingRate":0.0018,"minFundingRate":-0.0018,"collectCycle":8,"nextSettleTime":1654185600000,"timestamp":1654166040186}}"#;
+        let funding_rates =
+            &parse_funding_rate(EXCHANGE_NAME, MarketType::LinearSwap, raw_msg, None).unwrap();
+
+        assert_eq!(funding_rates.len(), 1);
+        crate::utils::check_funding_rate_fields(
+            EXCHANGE_NAME,
+            MarketType::LinearSwap,
+            &funding_rates[0],
+            raw_msg,
+        );
+
+        assert_eq!(funding_rates[0].symbol, "BTC_USDT");
+        assert_eq!(funding_rates[0].pair, "BTC/USDT");
+        assert_eq!(funding_rates[0].funding_rate, 0.000035);
+        assert_eq!(funding_rates[0].funding_time, 1654185600000);
+        assert_eq!(funding_rates[0].funding_interval, 8 * 3600000);
+        assert_eq!(funding_rates[0].funding_rate_cap, Some(0.0018));
+        assert_eq!(funding_rates[0].funding_rate_floor, Some(-0.0018));
+        assert_eq!(funding_rates[0].annualized_rate, 0.038325);
+        assert_eq!(funding_rates[0].timestamp, 1654166040186);
+    }
+
+    #[test]
+    fn linear_swap_history() {
+        let raw_msg = r#"{"success":true,"code":0,"data":{"pageSize":2,"totalCount":1180,"totalPage":590,"currentPage":1,"resultList":[{"symbol":"BTC_USDT","fundingRate":0.000066,"settleTime":1654156800000},{"symbol":"BTC_USDT","fundingRate":0.0001,"settleTime":1654128000000}]}}"#;
+        let funding_rates =
+            &parse_funding_rate(EXCHANGE_NAME, MarketType::LinearSwap, raw_msg, None).unwrap();
+
+        assert_eq!(funding_rates.len(), 2);
+        for rate in funding_rates.iter() {
+            crate::utils::check_funding_rate_fields(
+                EXCHANGE_NAME,
+                MarketType::LinearSwap,
+                rate,
+                raw_msg,
+            );
+            assert_eq!(rate.funding_time, rate.timestamp);
+        }
+
+        assert_eq!(funding_rates[0].funding_rate, 0.000066);
+        assert_eq!(funding_rates[0].funding_time, 1654156800000);
+        assert_eq!(funding_rates[1].funding_rate, 0.0001);
+        assert_eq!(funding_rates[1].funding_time, 1654128000000);
+    }
+}
@@ -260,6 +260,124 @@ mod funding_rate {
         assert_eq!(funding_rates[0].funding_time, 1646323200000);
         assert_eq!(funding_rates[0].timestamp, received_at);
     }
+
+    #[test]
+    fn four_hour_interval() {
+        let raw_msg = r#"{"arg":{"channel":"funding-rate","instId":"ORDI-USDT-SWAP"},"data":[{"fundingRate":"0.0005","fundingTime":"1703059200000","impactValue":"","instId":"ORDI-USDT-SWAP","instType":"SWAP","interestRate":"0.0001","maxFundingRate":"0.015","method":"current_period","minFundingRate":"-0.015","nextFundingRate":"","nextFundingTime":"1703073600000","premium":"0.0004","settFundingRate":"0.0004","settState":"settled","ts":"1703058000000"}]}"#;
+        let received_at = 1703058012345;
+        let funding_rates =
+            &parse_funding_rate(EXCHANGE_NAME, MarketType::LinearSwap, raw_msg, Some(received_at))
+                .unwrap();
+
+        assert_eq!(funding_rates.len(), 1);
+        crate::utils::check_funding_rate_fields(
+            EXCHANGE_NAME,
+            MarketType::LinearSwap,
+            &funding_rates[0],
+            raw_msg,
+        );
+
+        assert_eq!(funding_rates[0].pair, "ORDI/USDT".to_string());
+        assert_eq!(funding_rates[0].funding_rate, 0.0005);
+        assert_eq!(funding_rates[0].estimated_rate, None);
+        assert_eq!(funding_rates[0].funding_time, 1703059200000);
+        assert_eq!(funding_rates[0].funding_interval, 4 * 3600000);
+        assert_eq!(funding_rates[0].funding_rate_cap, Some(0.015));
+        assert_eq!(funding_rates[0].funding_rate_floor, Some(-0.015));
+        assert_eq!(funding_rates[0].annualized_rate, 1.095);
+    }
+}
+
+#[cfg(test)]
+mod liquidation {
+    use super::EXCHANGE_NAME;
+    use crypto_market_type::MarketType;
+    use crypto_message::TradeSide;
+    use crypto_msg_parser::{parse_liquidation, round};
+
+    #[test]
+    fn swap() {
+        let raw_msg = r#"{"arg":{"channel":"liquidation-orders","instType":"SWAP"},"data":[{"details":[{"bkLoss":"0","bkPx":"29185.2","ccy":"","posSide":"long","side":"sell","sz":"3","ts":"1689576534012"}],"instFamily":"BTC-USDT","instId":"BTC-USDT-SWAP","instType":"SWAP","uly":"BTC-USDT"},{"details":[{"bkLoss":"0","bkPx":"1912.34","ccy":"","posSide":"short","side":"buy","sz":"10","ts":"1689576534110"}],"instFamily":"ETH-USD","instId":"ETH-USD-SWAP","instType":"SWAP","uly":"ETH-USD"}]}"#;
+        let liquidations =
+            &parse_liquidation(EXCHANGE_NAME, MarketType::LinearSwap, raw_msg, None).unwrap();
+
+        assert_eq!(liquidations.len(), 2);
+        crate::utils::check_liquidation_fields(
+            EXCHANGE_NAME,
+            MarketType::LinearSwap,
+            "BTC/USDT".to_string(),
+            &liquidations[0],
+        );
+        crate::utils::check_liquidation_fields(
+            EXCHANGE_NAME,
+            MarketType::InverseSwap,
+            "ETH/USD".to_string(),
+            &liquidations[1],
+        );
+
+        assert_eq!(liquidations[0].symbol, "BTC-USDT-SWAP");
+        assert_eq!(liquidations[0].timestamp, 1689576534012);
+        assert_eq!(liquidations[0].side, TradeSide::Sell);
+        assert_eq!(liquidations[0].price, 29185.2);
+        assert_eq!(liquidations[0].quantity_contract, Some(3.0));
+        assert_eq!(liquidations[0].quantity_base, round(3.0 * 0.01));
+
+        assert_eq!(liquidations[1].timestamp, 1689576534110);
+        assert_eq!(liquidations[1].side, TradeSide::Buy);
+        assert_eq!(liquidations[1].quantity_contract, Some(10.0));
+        assert_eq!(liquidations[1].quantity_quote, 100.0);
+    }
+}
+
+#[cfg(test)]
+mod mark_price {
+    use super::EXCHANGE_NAME;
+    use crypto_market_type::MarketType;
+    use crypto_msg_parser::{extract_symbol, extract_timestamp, parse_mark_price};
+
+    #[test]
+    fn linear_swap() {
+        let raw_msg = r#"{"arg":{"channel":"mark-price","instId":"BTC-USDT-SWAP"},"data":[{"instType":"SWAP","instId":"BTC-USDT-SWAP","markPx":"29734.6","ts":"1654349110503"}]}"#;
+        let mark_prices =
+            &parse_mark_price(EXCHANGE_NAME, MarketType::LinearSwap, raw_msg, None).unwrap();
+
+        assert_eq!(mark_prices.len(), 1);
+        assert_eq!(mark_prices[0].symbol, "BTC-USDT-SWAP");
+        assert_eq!(mark_prices[0].pair, "BTC/USDT");
+        assert_eq!(mark_prices[0].timestamp, 1654349110503);
+        assert_eq!(mark_prices[0].price, 29734.6);
+        assert_eq!(mark_prices[0].json, raw_msg);
+
+        assert_eq!(
+            "BTC-USDT-SWAP",
+            extract_symbol(EXCHANGE_NAME, MarketType::LinearSwap, raw_msg).unwrap()
+        );
+        assert_eq!(
+            1654349110503,
+            extract_timestamp(EXCHANGE_NAME, MarketType::LinearSwap, raw_msg).unwrap().unwrap()
+        );
+    }
+}
+
+#[cfg(test)]
+mod index_price {
+    use super::EXCHANGE_NAME;
+    use crypto_market_type::MarketType;
+    use crypto_msg_parser::parse_index_price;
+
+    #[test]
+    fn linear_swap() {
+        let raw_msg = r#"{"arg":{"channel":"index-tickers","instId":"BTC-USDT"},"data":[{"instId":"BTC-USDT","idxPx":"29716.1","open24h":"30185.9","high24h":"30299.6","low24h":"29519.3","sodUtc0":"29702.4","sodUtc8":"29898.1","ts":"1654349111400"}]}"#;
+        let index_prices =
+            &parse_index_price(EXCHANGE_NAME, MarketType::LinearSwap, raw_msg, None).unwrap();
+
+        assert_eq!(index_prices.len(), 1);
+        assert_eq!(index_prices[0].market_type, MarketType::LinearSwap);
+        assert_eq!(index_prices[0].symbol, "BTC-USDT");
+        assert_eq!(index_prices[0].pair, "BTC/USDT");
+        assert_eq!(index_prices[0].timestamp, 1654349111400);
+        assert_eq!(index_prices[0].price, 29716.1);
+    }
 }
 
 #[cfg(test)]
@@ -951,14 +1069,15 @@ mod candlestick {
         assert_eq!("BTC-USDT", extract_symbol(EXCHANGE_NAME, MarketType::Spot, raw_msg).unwrap());
 
         let arr =
-            parse_candlestick(EXCHANGE_NAME, MarketType::Spot, raw_msg, Some(received_at)).unwrap();
+            parse_candlestick(EXCHANGE_NAME, MarketType::Spot, raw_msg, None, Some(received_at))
+                .unwrap();
         assert_eq!(1, arr.len());
         let candlestick_msg = &arr[0];
 
         assert_eq!("BTC-USDT", candlestick_msg.symbol);
         assert_eq!(received_at, candlestick_msg.timestamp);
-        assert_eq!("1m", candlestick_msg.period);
-        assert_eq!(1654154580, candlestick_msg.begin_time);
+        assert_eq!("1m", candlestick_msg.period.to_string());
+        assert_eq!(1654154580000, candlestick_msg.begin_time);
 
         assert_eq!(29930.7, candlestick_msg.open);
         assert_eq!(29936.3, candlestick_msg.high);
@@ -966,6 +1085,30 @@ mod candlestick {
         assert_eq!(29936.3, candlestick_msg.close);
         assert_eq!(0.0111536, candlestick_msg.volume);
         assert_eq!(Some(333.86246417), candlestick_msg.quote_volume);
+        assert_eq!(None, candlestick_msg.is_closed);
+    }
+
+    #[test]
+    fn spot_confirmed() {
+        let raw_msg = r#"{"arg":{"channel":"candle1Hutc","instId":"BTC-USDT"},"data":[["1654153200000","29878.1","29950","29868.2","29930.7","84.75406386","2535932.03468561","2535932.03468561","1"]]}"#;
+        let received_at = 1654156800021;
+
+        let arr =
+            parse_candlestick(EXCHANGE_NAME, MarketType::Spot, raw_msg, None, Some(received_at))
+                .unwrap();
+        assert_eq!(1, arr.len());
+        let candlestick_msg = &arr[0];
+
+        assert_eq!("BTC-USDT", candlestick_msg.symbol);
+        assert_eq!("1h", candlestick_msg.period.to_string());
+        assert_eq!(1654153200000, candlestick_msg.begin_time);
+        assert_eq!(1654156800000, candlestick_msg.end_time);
+        assert_eq!(Some(true), candlestick_msg.is_closed);
+
+        assert_eq!(29878.1, candlestick_msg.open);
+        assert_eq!(29930.7, candlestick_msg.close);
+        assert_eq!(84.75406386, candlestick_msg.volume);
+        assert_eq!(Some(2535932.03468561), candlestick_msg.quote_volume);
     }
 
     #[test]
@@ -981,16 +1124,21 @@ mod candlestick {
             extract_symbol(EXCHANGE_NAME, MarketType::InverseSwap, raw_msg).unwrap()
         );
 
-        let arr =
-            parse_candlestick(EXCHANGE_NAME, MarketType::InverseFuture, raw_msg, Some(received_at))
-                .unwrap();
+        let arr = parse_candlestick(
+            EXCHANGE_NAME,
+            MarketType::InverseFuture,
+            raw_msg,
+            None,
+            Some(received_at),
+        )
+        .unwrap();
         assert_eq!(1, arr.len());
         let candlestick_msg = &arr[0];
 
         assert_eq!("BTC-USD-220624", candlestick_msg.symbol);
         assert_eq!(received_at, candlestick_msg.timestamp);
-        assert_eq!("1m", candlestick_msg.period);
-        assert_eq!(1654154580, candlestick_msg.begin_time);
+        assert_eq!("1m", candlestick_msg.period.to_string());
+        assert_eq!(1654154580000, candlestick_msg.begin_time);
 
         assert_eq!(29901.6, candlestick_msg.open);
         assert_eq!(29921.2, candlestick_msg.high);
@@ -1013,16 +1161,21 @@ mod candlestick {
             extract_symbol(EXCHANGE_NAME, MarketType::InverseSwap, raw_msg).unwrap()
         );
 
-        let arr =
-            parse_candlestick(EXCHANGE_NAME, MarketType::LinearFuture, raw_msg, Some(received_at))
-                .unwrap();
+        let arr = parse_candlestick(
+            EXCHANGE_NAME,
+            MarketType::LinearFuture,
+            raw_msg,
+            None,
+            Some(received_at),
+        )
+        .unwrap();
         assert_eq!(1, arr.len());
         let candlestick_msg = &arr[0];
 
         assert_eq!("BTC-USDT-220624", candlestick_msg.symbol);
         assert_eq!(received_at, candlestick_msg.timestamp);
-        assert_eq!("1m", candlestick_msg.period);
-        assert_eq!(1654154520, candlestick_msg.begin_time);
+        assert_eq!("1m", candlestick_msg.period.to_string());
+        assert_eq!(1654154520000, candlestick_msg.begin_time);
 
         assert_eq!(29963.4, candlestick_msg.open);
         assert_eq!(29971.2, candlestick_msg.high);
@@ -1045,16 +1198,21 @@ mod candlestick {
             extract_symbol(EXCHANGE_NAME, MarketType::InverseSwap, raw_msg).unwrap()
         );
 
-        let arr =
-            parse_candlestick(EXCHANGE_NAME, MarketType::InverseSwap, raw_msg, Some(received_at))
-                .unwrap();
+        let arr = parse_candlestick(
+            EXCHANGE_NAME,
+            MarketType::InverseSwap,
+            raw_msg,
+            None,
+            Some(received_at),
+        )
+        .unwrap();
         assert_eq!(1, arr.len());
         let candlestick_msg = &arr[0];
 
         assert_eq!("BTC-USD-SWAP", candlestick_msg.symbol);
         assert_eq!(received_at, candlestick_msg.timestamp);
-        assert_eq!("1m", candlestick_msg.period);
-        assert_eq!(1662716640, candlestick_msg.begin_time);
+        assert_eq!("1m", candlestick_msg.period.to_string());
+        assert_eq!(1662716640000, candlestick_msg.begin_time);
 
         assert_eq!(20713.4, candlestick_msg.open);
         assert_eq!(20721.5, candlestick_msg.high);
@@ -1077,16 +1235,21 @@ mod candlestick {
             extract_symbol(EXCHANGE_NAME, MarketType::LinearSwap, raw_msg).unwrap()
         );
 
-        let arr =
-            parse_candlestick(EXCHANGE_NAME, MarketType::LinearSwap, raw_msg, Some(received_at))
-                .unwrap();
+        let arr = parse_candlestick(
+            EXCHANGE_NAME,
+            MarketType::LinearSwap,
+            raw_msg,
+            None,
+            Some(received_at),
+        )
+        .unwrap();
         assert_eq!(1, arr.len());
         let candlestick_msg = &arr[0];
 
         assert_eq!("BTC-USDT-SWAP", candlestick_msg.symbol);
         assert_eq!(received_at, candlestick_msg.timestamp);
-        assert_eq!("1m", candlestick_msg.period);
-        assert_eq!(1662716640, candlestick_msg.begin_time);
+        assert_eq!("1m", candlestick_msg.period.to_string());
+        assert_eq!(1662716640000, candlestick_msg.begin_time);
 
         assert_eq!(20702.4, candlestick_msg.open);
         assert_eq!(20710.0, candlestick_msg.high);
@@ -1115,7 +1278,7 @@ mod candlestick {
 mod ticker {
     use super::EXCHANGE_NAME;
     use crypto_market_type::MarketType;
-    use crypto_msg_parser::{extract_symbol, extract_timestamp};
+    use crypto_msg_parser::{extract_symbol, extract_timestamp, parse_ticker};
 
     #[test]
     fn spot() {
@@ -1126,6 +1289,17 @@ mod ticker {
             extract_timestamp(EXCHANGE_NAME, MarketType::Spot, raw_msg).unwrap().unwrap()
         );
         assert_eq!("BTC-USDT", extract_symbol(EXCHANGE_NAME, MarketType::Spot, raw_msg).unwrap());
+
+        let tickers = &parse_ticker(EXCHANGE_NAME, MarketType::Spot, raw_msg, None).unwrap();
+        assert_eq!(1, tickers.len());
+        crate::utils::check_ticker_fields(
+            EXCHANGE_NAME,
+            MarketType::Spot,
+            "BTC/USDT".to_string(),
+            "BTC-USDT".to_string(),
+            &tickers[0],
+            raw_msg,
+        );
     }
 
     #[test]
@@ -1140,6 +1314,18 @@ mod ticker {
             "BTC-USD-220624",
             extract_symbol(EXCHANGE_NAME, MarketType::InverseSwap, raw_msg).unwrap()
         );
+
+        let tickers =
+            &parse_ticker(EXCHANGE_NAME, MarketType::InverseFuture, raw_msg, None).unwrap();
+        assert_eq!(1, tickers.len());
+        crate::utils::check_ticker_fields(
+            EXCHANGE_NAME,
+            MarketType::InverseFuture,
+            "BTC/USD".to_string(),
+            "BTC-USD-220624".to_string(),
+            &tickers[0],
+            raw_msg,
+        );
     }
 
     #[test]
@@ -1154,6 +1340,18 @@ mod ticker {
             "BTC-USDT-220624",
             extract_symbol(EXCHANGE_NAME, MarketType::InverseSwap, raw_msg).unwrap()
         );
+
+        let tickers =
+            &parse_ticker(EXCHANGE_NAME, MarketType::LinearFuture, raw_msg, None).unwrap();
+        assert_eq!(1, tickers.len());
+        crate::utils::check_ticker_fields(
+            EXCHANGE_NAME,
+            MarketType::LinearFuture,
+            "BTC/USDT".to_string(),
+            "BTC-USDT-220624".to_string(),
+            &tickers[0],
+            raw_msg,
+        );
     }
 
     #[test]
@@ -1168,6 +1366,17 @@ mod ticker {
             "BTC-USD-SWAP",
             extract_symbol(EXCHANGE_NAME, MarketType::InverseSwap, raw_msg).unwrap()
         );
+
+        let tickers = &parse_ticker(EXCHANGE_NAME, MarketType::InverseSwap, raw_msg, None).unwrap();
+        assert_eq!(1, tickers.len());
+        crate::utils::check_ticker_fields(
+            EXCHANGE_NAME,
+            MarketType::InverseSwap,
+            "BTC/USD".to_string(),
+            "BTC-USD-SWAP".to_string(),
+            &tickers[0],
+            raw_msg,
+        );
     }
 
     #[test]
@@ -1182,6 +1391,17 @@ mod ticker {
             "BTC-USDT-SWAP",
             extract_symbol(EXCHANGE_NAME, MarketType::LinearSwap, raw_msg).unwrap()
         );
+
+        let tickers = &parse_ticker(EXCHANGE_NAME, MarketType::LinearSwap, raw_msg, None).unwrap();
+        assert_eq!(1, tickers.len());
+        crate::utils::check_ticker_fields(
+            EXCHANGE_NAME,
+            MarketType::LinearSwap,
+            "BTC/USDT".to_string(),
+            "BTC-USDT-SWAP".to_string(),
+            &tickers[0],
+            raw_msg,
+        );
     }
 
     #[test]
@@ -1196,6 +1416,50 @@ mod ticker {
             "BTC-USD-220624-50000-C",
             extract_symbol(EXCHANGE_NAME, MarketType::LinearSwap, raw_msg).unwrap()
         );
+
+        let tickers =
+            &parse_ticker(EXCHANGE_NAME, MarketType::EuropeanOption, raw_msg, None).unwrap();
+        assert_eq!(1, tickers.len());
+        crate::utils::check_ticker_fields(
+            EXCHANGE_NAME,
+            MarketType::EuropeanOption,
+            "BTC/USD".to_string(),
+            "BTC-USD-220624-50000-C".to_string(),
+            &tickers[0],
+            raw_msg,
+        );
+    }
+}
+
+#[cfg(test)]
+mod option_ticker {
+    use super::EXCHANGE_NAME;
+    use crypto_market_type::MarketType;
+    use crypto_msg_parser::parse_option_ticker;
+
+    #[test]
+    fn option() {
+        let raw_msg = r#"{"arg":{"channel":"opt-summary","uly":"BTC-USD"},"data":[{"instType":"OPTION","instId":"BTC-USD-220624-30000-C","uly":"BTC-USD","delta":"0.4131256853","gamma":"7.9713420453","vega":"0.0005866543","theta":"-0.0013474421","lever":"13.2351","markVol":"0.6573","bidVol":"0.6436","askVol":"0.6718","realVol":"","deltaBS":"0.4887417622","gammaBS":"0.0001089364","thetaBS":"-39.8541","vegaBS":"21.9183","ts":"1654349111400"},{"instType":"OPTION","instId":"BTC-USD-220624-50000-C","uly":"BTC-USD","delta":"0.0018","gamma":"0.0733","vega":"0.0000125","theta":"-0.0000431","lever":"1979.7","markVol":"1.1342","bidVol":"","askVol":"1.2018","realVol":"","deltaBS":"0.0033","gammaBS":"0.0000021","thetaBS":"-1.2756","vegaBS":"0.3708","ts":"1654349111400"}]}"#;
+        let tickers =
+            &parse_option_ticker(EXCHANGE_NAME, MarketType::EuropeanOption, raw_msg).unwrap();
+
+        assert_eq!(2, tickers.len());
+        let ticker = &tickers[0];
+        assert_eq!(ticker.symbol, "BTC-USD-220624-30000-C");
+        assert_eq!(ticker.pair, "BTC/USD");
+        assert_eq!(ticker.timestamp, 1654349111400);
+        assert_eq!(ticker.mark_iv, 0.6573);
+        assert_eq!(ticker.bid_iv, Some(0.6436));
+        assert_eq!(ticker.ask_iv, Some(0.6718));
+        assert_eq!(ticker.delta, 0.4887417622);
+        assert_eq!(ticker.gamma, 0.0001089364);
+        assert_eq!(ticker.vega, 21.9183);
+        assert_eq!(ticker.theta, -39.8541);
+        assert_eq!(ticker.underlying_price, None);
+        assert_eq!(ticker.open_interest, None);
+
+        assert_eq!(tickers[1].bid_iv, None);
+        assert_eq!(tickers[1].ask_iv, Some(1.2018));
     }
 }
 
@@ -1285,7 +1549,7 @@ mod l2_snapshot {
 mod open_interest {
     use super::EXCHANGE_NAME;
     use crypto_market_type::MarketType;
-    use crypto_msg_parser::{extract_symbol, extract_timestamp};
+    use crypto_msg_parser::{extract_symbol, extract_timestamp, parse_open_interest};
 
     #[test]
     fn inverse_future() {
@@ -1329,6 +1593,19 @@ mod open_interest {
             extract_timestamp(EXCHANGE_NAME, MarketType::InverseSwap, raw_msg).unwrap().unwrap()
         );
         assert_eq!("ALL", extract_symbol(EXCHANGE_NAME, MarketType::InverseSwap, raw_msg).unwrap());
+
+        let open_interests =
+            &parse_open_interest(EXCHANGE_NAME, MarketType::InverseSwap, raw_msg, None, None)
+                .unwrap();
+        assert_eq!(open_interests.len(), 2);
+        assert_eq!(open_interests[0].symbol, "BTC-USD-SWAP");
+        assert_eq!(open_interests[0].pair, "BTC/USD");
+        assert_eq!(open_interests[0].timestamp, 1654348683853);
+        assert_eq!(open_interests[0].quantity_contract, 4092973.0);
+        assert_eq!(open_interests[0].quantity_base, Some(13840.048827662697));
+        assert_eq!(open_interests[0].quantity_quote, Some(4092973.0 * 100.0));
+        assert_eq!(open_interests[1].pair, "ETH/USD");
+        assert_eq!(open_interests[1].quantity_quote, Some(21795246.0 * 10.0));
     }
 
     #[test]
@@ -1351,5 +1628,79 @@ mod open_interest {
             extract_timestamp(EXCHANGE_NAME, MarketType::LinearSwap, raw_msg).unwrap().unwrap()
         );
         assert_eq!("ALL", extract_symbol(EXCHANGE_NAME, MarketType::LinearSwap, raw_msg).unwrap());
+
+        // the market type is derived from instId
+        let open_interests =
+            &parse_open_interest(EXCHANGE_NAME, MarketType::LinearSwap, raw_msg, None, None)
+                .unwrap();
+        assert_eq!(open_interests.len(), 2);
+        assert_eq!(open_interests[0].market_type, MarketType::EuropeanOption);
+        assert_eq!(open_interests[0].quantity_contract, 0.0);
+    }
+}
+
+#[cfg(test)]
+mod checksum {
+    use crypto_market_type::MarketType;
+    use crypto_msg_parser::ChecksumBook;
+
+    #[test]
+    fn option() {
+        let mut book = ChecksumBook::new("okx", MarketType::EuropeanOption);
+
+        let raw_msg = r#"{"arg":{"channel":"books-l2-tbt","instId":"BTC-USD-220304-32000-P"},"action":"snapshot","data":[{"asks":[["0.0005","305","0","1"],["0.001","550","0","2"]],"bids":[],"ts":"1646315100798","checksum":971343753}]}"#;
+        assert_eq!(Some(true), book.update(raw_msg).unwrap());
+
+        let raw_msg = r#"{"arg":{"channel":"books-l2-tbt","instId":"BTC-USD-220304-32000-P"},"action":"update","data":[{"asks":[["0.001","0","0","0"]],"bids":[["0.0003","10","0","1"]],"ts":"1646315100898","checksum":247128701}]}"#;
+        assert_eq!(Some(true), book.update(raw_msg).unwrap());
+
+        // the update is applied twice
+        assert_eq!(Some(true), book.update(raw_msg).unwrap());
+        let raw_msg = r#"{"arg":{"channel":"books-l2-tbt","instId":"BTC-USD-220304-32000-P"},"action":"update","data":[{"asks":[],"bids":[["0.0003","20","0","1"]],"ts":"1646315100998","checksum":247128701}]}"#;
+        assert_eq!(Some(false), book.update(raw_msg).unwrap());
+
+        let orderbook = book.book().unwrap();
+        assert_eq!(orderbook.symbol(), "BTC-USD-220304-32000-P");
+        assert_eq!(orderbook.best_ask().unwrap().price, 0.0005);
+        assert_eq!(orderbook.best_bid().unwrap().price, 0.0003);
+    }
+}
+
+#[cfg(test)]
+mod parse {
+    use super::EXCHANGE_NAME;
+    use crypto_market_type::MarketType;
+    use crypto_message::Message;
+    use crypto_msg_parser::parse;
+    use crypto_msg_type::MessageType;
+
+    #[test]
+    fn okex_alias() {
+        let raw_msg = r#"{"arg":{"channel":"books5","instId":"BTC-USDT"},"data":[{"asks":[["30221.8","0.00439","0","2"]],"bids":[["30221.7","0.30608367","0","6"]],"instId":"BTC-USDT","ts":"1652671418459"}]}"#;
+        let messages = parse("okex", MarketType::Spot, raw_msg, None).unwrap();
+
+        assert_eq!(1, messages.len());
+        assert_eq!(MessageType::L2TopK, messages[0].get_msg_type());
+        if let Message::Level2(orderbook) = &messages[0] {
+            assert_eq!(EXCHANGE_NAME, orderbook.exchange);
+        } else {
+            panic!("expected a level2 message");
+        }
+    }
+
+    #[test]
+    fn mark_price() {
+        let raw_msg = r#"{"arg":{"channel":"mark-price","instId":"BTC-USDT-SWAP"},"data":[{"instType":"SWAP","instId":"BTC-USDT-SWAP","markPx":"29734.6","ts":"1654349110503"}]}"#;
+        let messages = parse(EXCHANGE_NAME, MarketType::LinearSwap, raw_msg, None).unwrap();
+
+        assert_eq!(1, messages.len());
+        assert_eq!(1654349110503, messages[0].get_timestamp());
+        assert!(matches!(messages[0], Message::MarkPrice(_)));
+    }
+
+    #[test]
+    fn subscription_response() {
+        let raw_msg = r#"{"event":"subscribe","arg":{"channel":"trades","instId":"BTC-USDT"}}"#;
+        assert!(parse(EXCHANGE_NAME, MarketType::Spot, raw_msg, None).unwrap().is_empty());
     }
 }
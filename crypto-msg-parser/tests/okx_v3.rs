@@ -235,6 +235,17 @@ mod funding_rate {
         assert_eq!(funding_rates[0].timestamp, received_at);
     }
 
+    #[test]
+    fn settlement_at_funding_time() {
+        let raw_msg = r#"{"table":"swap/funding_rate","data":[{"estimated_rate":"0.00065","funding_rate":"0.00072933","funding_time":"2021-04-02T00:00:00.000Z","instrument_id":"BTC-USD-SWAP","interest_rate":"0","settlement_time":"2021-04-02T00:00:00.000Z"}]}"#;
+        let funding_rates =
+            &parse_funding_rate(EXCHANGE_NAME, MarketType::InverseSwap, raw_msg, Some(0)).unwrap();
+
+        // falls back to 8 hours instead of an infinite annualized rate
+        assert_eq!(funding_rates[0].funding_interval, 8 * 3600000);
+        assert!(funding_rates[0].annualized_rate.is_finite());
+    }
+
     #[test]
     fn linear_swap() {
         let raw_msg = r#"{"table":"swap/funding_rate","data":[{"estimated_rate":"0.00031","funding_rate":"0.00081859","funding_time":"2021-04-02T00:00:00.000Z","instrument_id":"BTC-USDT-SWAP","interest_rate":"0","settlement_time":"2021-04-02T08:00:00.000Z"}]}"#;
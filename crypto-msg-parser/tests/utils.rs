@@ -2,7 +2,7 @@
 use crypto_market_type::MarketType;
 use crypto_msg_type::MessageType;
 
-use crypto_message::{FundingRateMsg, OrderBookMsg, TradeMsg};
+use crypto_message::{FundingRateMsg, LiquidationMsg, OrderBookMsg, TickerMsg, TradeMsg};
 use crypto_msg_parser::{get_msg_type, round};
 
 pub fn check_trade_fields(
@@ -18,11 +18,7 @@ pub fn check_trade_fields(
     assert_eq!(trade.pair, pair);
     assert_eq!(trade.symbol, symbol);
     assert_eq!(trade.msg_type, MessageType::Trade);
-    if ["binance", "bitget", "bitmex", "bybit", "deribit", "ftx", "huobi", "okex"]
-        .contains(&exchange)
-    {
-        assert_eq!(MessageType::Trade, get_msg_type(exchange, raw_msg));
-    }
+    assert_eq!(MessageType::Trade, get_msg_type(exchange, raw_msg));
     assert!(trade.price > 0.0);
     assert!(trade.quantity_base > 0.0);
     assert!(trade.quantity_quote > 0.0);
@@ -47,11 +43,7 @@ pub fn check_orderbook_fields(
     assert_eq!(orderbook.msg_type, msg_type);
     assert_eq!(orderbook.pair, pair);
     assert_eq!(orderbook.symbol, symbol);
-    if ["binance", "bitget", "bitmex", "bybit", "deribit", "ftx", "huobi", "okex"]
-        .contains(&exchange)
-    {
-        assert_eq!(msg_type, get_msg_type(exchange, raw_msg));
-    }
+    assert_eq!(msg_type, get_msg_type(exchange, raw_msg));
     assert_eq!(orderbook.timestamp.to_string().len(), 13);
 
     for order in orderbook.asks.iter() {
@@ -78,11 +70,64 @@ pub fn check_funding_rate_fields(
     assert_eq!(MessageType::FundingRate, get_msg_type(exchange, raw_msg));
     assert!(funding_rate.funding_rate > -1.0);
     assert!(funding_rate.funding_rate < 1.0);
-    if exchange == "bitmex" {
+    assert!(funding_rate.funding_interval >= 3600000);
+    assert_eq!(funding_rate.funding_interval % 3600000, 0);
+    let periods_per_year = (365 * 24 * 3600000 / funding_rate.funding_interval) as f64;
+    assert!(
+        (funding_rate.annualized_rate - funding_rate.funding_rate * periods_per_year).abs() < 1e-8
+    );
+    if let (Some(cap), Some(floor)) =
+        (funding_rate.funding_rate_cap, funding_rate.funding_rate_floor)
+    {
+        assert!(floor < cap);
+    }
+    if exchange == "bitmex" || exchange == "kraken" || exchange == "kucoin" {
         assert_eq!(funding_rate.funding_time % (4 * 3600000), 0);
-    } else if exchange == "bitget" {
+    } else if exchange == "bitget" || exchange == "dydx" {
         assert_eq!(funding_rate.funding_time % 3600000, 0);
+    } else if exchange == "deribit" {
+        // deribit charges funding continuously
+        assert!(funding_rate.funding_time > 0);
     } else {
         assert_eq!(funding_rate.funding_time % (8 * 3600000), 0);
     }
 }
+
+pub fn check_liquidation_fields(
+    exchange: &str,
+    market_type: MarketType,
+    pair: String,
+    liquidation: &LiquidationMsg,
+) {
+    assert_eq!(liquidation.exchange, exchange);
+    assert_eq!(liquidation.market_type, market_type);
+    assert_eq!(liquidation.pair, pair);
+    assert!(liquidation.price > 0.0);
+    assert!(liquidation.quantity_base > 0.0);
+    assert!(liquidation.quantity_quote > 0.0);
+    assert!(liquidation.quantity_contract.unwrap() > 0.0);
+}
+
+pub fn check_ticker_fields(
+    exchange: &str,
+    market_type: MarketType,
+    pair: String,
+    symbol: String,
+    ticker: &TickerMsg,
+    raw_msg: &str,
+) {
+    assert_eq!(ticker.exchange, exchange);
+    assert_eq!(ticker.market_type, market_type);
+    assert_eq!(ticker.pair, pair);
+    assert_eq!(ticker.symbol, symbol);
+    assert_eq!(ticker.msg_type, MessageType::Ticker);
+    assert_eq!(MessageType::Ticker, get_msg_type(exchange, raw_msg));
+    assert!(ticker.open > 0.0);
+    assert!(ticker.high > 0.0);
+    assert!(ticker.low > 0.0);
+    assert!(ticker.close > 0.0);
+    assert!(ticker.low <= ticker.high);
+    assert!(ticker.volume >= 0.0);
+    assert!(ticker.quote_volume >= 0.0);
+    assert_eq!(ticker.timestamp.to_string().len(), 13);
+}
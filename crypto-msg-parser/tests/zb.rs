@@ -319,7 +319,7 @@ mod l2_topk {
 mod ticker {
     use super::EXCHANGE_NAME;
     use crypto_market_type::MarketType;
-    use crypto_msg_parser::{extract_symbol, extract_timestamp};
+    use crypto_msg_parser::{extract_symbol, extract_timestamp, parse_ticker};
 
     #[test]
     fn spot() {
@@ -331,6 +331,17 @@ mod ticker {
             1653781987181,
             extract_timestamp(EXCHANGE_NAME, MarketType::Spot, raw_msg).unwrap().unwrap()
         );
+
+        let tickers = &parse_ticker(EXCHANGE_NAME, MarketType::Spot, raw_msg, None).unwrap();
+        assert_eq!(1, tickers.len());
+        crate::utils::check_ticker_fields(
+            EXCHANGE_NAME,
+            MarketType::Spot,
+            "BTC/USDT".to_string(),
+            "btcusdt".to_string(),
+            &tickers[0],
+            raw_msg,
+        );
     }
 
     #[test]
@@ -346,6 +357,17 @@ mod ticker {
             1653783012000,
             extract_timestamp(EXCHANGE_NAME, MarketType::LinearSwap, raw_msg).unwrap().unwrap()
         );
+
+        let tickers = &parse_ticker(EXCHANGE_NAME, MarketType::LinearSwap, raw_msg, None).unwrap();
+        assert_eq!(1, tickers.len());
+        crate::utils::check_ticker_fields(
+            EXCHANGE_NAME,
+            MarketType::LinearSwap,
+            "BTC/USDT".to_string(),
+            "BTC_USDT".to_string(),
+            &tickers[0],
+            raw_msg,
+        );
     }
 
     #[test]
@@ -358,6 +380,17 @@ mod ticker {
             1653783366000,
             extract_timestamp(EXCHANGE_NAME, MarketType::LinearSwap, raw_msg).unwrap().unwrap()
         );
+
+        let tickers = &parse_ticker(EXCHANGE_NAME, MarketType::LinearSwap, raw_msg, None).unwrap();
+        assert_eq!(2, tickers.len());
+        crate::utils::check_ticker_fields(
+            EXCHANGE_NAME,
+            MarketType::LinearSwap,
+            "BTC/USDT".to_string(),
+            "BTC_USDT".to_string(),
+            &tickers[0],
+            raw_msg,
+        );
     }
 }
 
@@ -377,14 +410,14 @@ mod candlestick {
             1653782160000,
             extract_timestamp(EXCHANGE_NAME, MarketType::Spot, raw_msg).unwrap().unwrap()
         );
-        let arr = parse_candlestick(EXCHANGE_NAME, MarketType::Spot, raw_msg, None).unwrap();
+        let arr = parse_candlestick(EXCHANGE_NAME, MarketType::Spot, raw_msg, None, None).unwrap();
         assert_eq!(2, arr.len());
         let candlestick_msg = &arr[0];
         //[1653782100000,29055.22,29055.22,29030.81,29032.9,19.3130]
         assert_eq!("btcusdt", candlestick_msg.symbol);
         assert_eq!("BTC/USDT", candlestick_msg.pair);
         assert_eq!(1653782100000, candlestick_msg.timestamp);
-        assert_eq!("1min", candlestick_msg.period);
+        assert_eq!("1m", candlestick_msg.period.to_string());
         assert_eq!(1653782040000, candlestick_msg.begin_time);
 
         assert_eq!(29055.22, candlestick_msg.open);
@@ -408,14 +441,15 @@ mod candlestick {
             1653783840000,
             extract_timestamp(EXCHANGE_NAME, MarketType::LinearSwap, raw_msg).unwrap().unwrap()
         );
-        let arr = parse_candlestick(EXCHANGE_NAME, MarketType::LinearSwap, raw_msg, None).unwrap();
+        let arr =
+            parse_candlestick(EXCHANGE_NAME, MarketType::LinearSwap, raw_msg, None, None).unwrap();
         assert_eq!(1, arr.len());
         let candlestick_msg = &arr[0];
         //[28993.54,28996.39,28992.58,28994.78,0.921,1653783840]
         assert_eq!("BTC_USDT", candlestick_msg.symbol);
         assert_eq!("BTC/USDT", candlestick_msg.pair);
         assert_eq!(1653783840000, candlestick_msg.timestamp);
-        assert_eq!("1M", candlestick_msg.period);
+        assert_eq!("1m", candlestick_msg.period.to_string());
         assert_eq!(1653783780000, candlestick_msg.begin_time);
 
         assert_eq!(28993.54, candlestick_msg.open);
@@ -431,12 +465,15 @@ mod candlestick {
 mod l2_snapshot {
     use super::EXCHANGE_NAME;
     use crypto_market_type::MarketType;
-    use crypto_msg_parser::{extract_symbol, extract_timestamp};
+    use crypto_msg_parser::{extract_symbol, extract_timestamp, get_msg_type};
+    use crypto_msg_type::MessageType;
 
     #[test]
     fn spot() {
         let raw_msg = r#"{"asks":[[29704.57,0.0002],[29700.54,0.1500],[29695.72,0.1500]],"bids":[[29680.86,0.1500],[29677.89,0.1500],[29674.97,0.6260]],"timestamp":1654329612}"#;
 
+        assert_eq!(MessageType::L2Snapshot, get_msg_type(EXCHANGE_NAME, raw_msg));
+
         assert_eq!("NONE", extract_symbol(EXCHANGE_NAME, MarketType::Spot, raw_msg).unwrap());
 
         assert_eq!(
@@ -449,6 +486,8 @@ mod l2_snapshot {
     fn linear_swap() {
         let raw_msg = r#"{"code":10000,"desc":"操作成功","data":{"asks":[[29663.89,0.03],[29668.69,0.04],[29676.09,0.04]],"bids":[[29659.12,0.06],[29658.84,0.03],[29652.77,0.04]],"time":1654330502522}}"#;
 
+        assert_eq!(MessageType::L2Snapshot, get_msg_type(EXCHANGE_NAME, raw_msg));
+
         assert_eq!("NONE", extract_symbol(EXCHANGE_NAME, MarketType::LinearSwap, raw_msg).unwrap());
 
         assert_eq!(
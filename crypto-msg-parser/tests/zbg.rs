@@ -320,14 +320,14 @@ mod candlestick {
             1654155660000,
             extract_timestamp(EXCHANGE_NAME, MarketType::Spot, raw_msg).unwrap().unwrap()
         );
-        let arr = parse_candlestick(EXCHANGE_NAME, MarketType::Spot, raw_msg, None).unwrap();
+        let arr = parse_candlestick(EXCHANGE_NAME, MarketType::Spot, raw_msg, None, None).unwrap();
         assert_eq!(2, arr.len());
         let candlestick_msg = &arr[0];
 
         assert_eq!("btc_usdt", candlestick_msg.symbol);
         assert_eq!("BTC/USDT", candlestick_msg.pair);
         assert_eq!(1654155660000, candlestick_msg.timestamp);
-        assert_eq!("1M", candlestick_msg.period);
+        assert_eq!("1m", candlestick_msg.period.to_string());
         assert_eq!(1654155600000, candlestick_msg.begin_time);
 
         assert_eq!(30013.78, candlestick_msg.open);
@@ -348,14 +348,14 @@ mod candlestick {
             1654125240000,
             extract_timestamp(EXCHANGE_NAME, MarketType::Spot, raw_msg).unwrap().unwrap()
         );
-        let arr = parse_candlestick(EXCHANGE_NAME, MarketType::Spot, raw_msg, None).unwrap();
+        let arr = parse_candlestick(EXCHANGE_NAME, MarketType::Spot, raw_msg, None, None).unwrap();
         assert_eq!(1, arr.len());
         let candlestick_msg = &arr[0];
 
         assert_eq!("btc_usdt", candlestick_msg.symbol);
         assert_eq!("BTC/USDT", candlestick_msg.pair);
         assert_eq!(1654125240000, candlestick_msg.timestamp);
-        assert_eq!("1M", candlestick_msg.period);
+        assert_eq!("1m", candlestick_msg.period.to_string());
         assert_eq!(1654125180000, candlestick_msg.begin_time);
 
         assert_eq!(29947.03, candlestick_msg.open);
@@ -380,14 +380,15 @@ mod candlestick {
             extract_timestamp(EXCHANGE_NAME, MarketType::InverseSwap, raw_msg).unwrap().unwrap()
         );
 
-        let arr = parse_candlestick(EXCHANGE_NAME, MarketType::InverseSwap, raw_msg, None).unwrap();
+        let arr =
+            parse_candlestick(EXCHANGE_NAME, MarketType::InverseSwap, raw_msg, None, None).unwrap();
         assert_eq!(2, arr.len());
         let candlestick_msg = &arr[1];
 
         assert_eq!("BTC_USD-R", candlestick_msg.symbol);
         assert_eq!("BTC/USD", candlestick_msg.pair);
         assert_eq!(1652804340000, candlestick_msg.timestamp);
-        assert_eq!("60000", candlestick_msg.period);
+        assert_eq!("1m", candlestick_msg.period.to_string());
         assert_eq!(1652804280000, candlestick_msg.begin_time);
         //[1652804340000,"30005","30005.5","29975.5","29976","6186"]
         assert_eq!(30005.0, candlestick_msg.open);
@@ -411,14 +412,15 @@ mod candlestick {
             1648876680000,
             extract_timestamp(EXCHANGE_NAME, MarketType::LinearSwap, raw_msg).unwrap().unwrap()
         );
-        let arr = parse_candlestick(EXCHANGE_NAME, MarketType::LinearSwap, raw_msg, None).unwrap();
+        let arr =
+            parse_candlestick(EXCHANGE_NAME, MarketType::LinearSwap, raw_msg, None, None).unwrap();
         assert_eq!(2, arr.len());
         let candlestick_msg = &arr[1];
 
         assert_eq!("BTC_USDT", candlestick_msg.symbol);
         assert_eq!("BTC/USDT", candlestick_msg.pair);
         assert_eq!(1648876680000, candlestick_msg.timestamp);
-        assert_eq!("180000", candlestick_msg.period);
+        assert_eq!("3m", candlestick_msg.period.to_string());
         assert_eq!(1648876500000, candlestick_msg.begin_time);
 
         assert_eq!(46550.0, candlestick_msg.open);
@@ -434,7 +436,7 @@ mod candlestick {
 mod ticker {
     use super::EXCHANGE_NAME;
     use crypto_market_type::MarketType;
-    use crypto_msg_parser::{extract_symbol, extract_timestamp};
+    use crypto_msg_parser::{extract_symbol, extract_timestamp, parse_ticker, round};
 
     #[test]
     fn spot() {
@@ -443,6 +445,27 @@ mod ticker {
         assert_eq!("btc_usdt", extract_symbol(EXCHANGE_NAME, MarketType::Spot, raw_msg).unwrap());
 
         assert_eq!(None, extract_timestamp(EXCHANGE_NAME, MarketType::Spot, raw_msg).unwrap());
+
+        assert!(parse_ticker(EXCHANGE_NAME, MarketType::Spot, raw_msg, None).is_err());
+        let tickers =
+            &parse_ticker(EXCHANGE_NAME, MarketType::Spot, raw_msg, Some(1654161207269)).unwrap();
+        assert_eq!(1, tickers.len());
+        crate::utils::check_ticker_fields(
+            EXCHANGE_NAME,
+            MarketType::Spot,
+            "BTC/USDT".to_string(),
+            "btc_usdt".to_string(),
+            &tickers[0],
+            raw_msg,
+        );
+        assert_eq!(1654161207269, tickers[0].timestamp);
+        assert_eq!(round(29980.15 / (1.0 - 0.0496)), round(tickers[0].open));
+        assert_eq!(31890.91, tickers[0].high);
+        assert_eq!(29316.96, tickers[0].low);
+        assert_eq!(29980.15, tickers[0].close);
+        assert_eq!(3104.9576, tickers[0].volume);
+        assert_eq!(Some(29967.06), tickers[0].best_bid_price);
+        assert_eq!(Some(29981.99), tickers[0].best_ask_price);
     }
 
     #[test]
@@ -482,6 +505,24 @@ mod ticker {
             1652804313766,
             extract_timestamp(EXCHANGE_NAME, MarketType::LinearSwap, raw_msg).unwrap().unwrap()
         );
+
+        let tickers = &parse_ticker(EXCHANGE_NAME, MarketType::LinearSwap, raw_msg, None).unwrap();
+        assert_eq!(1, tickers.len());
+        crate::utils::check_ticker_fields(
+            EXCHANGE_NAME,
+            MarketType::LinearSwap,
+            "BTC/USDT".to_string(),
+            "BTC_USDT".to_string(),
+            &tickers[0],
+            raw_msg,
+        );
+        assert_eq!(1652804313766, tickers[0].timestamp);
+        assert_eq!(29436.0, tickers[0].open);
+        assert_eq!(30778.0, tickers[0].high);
+        assert_eq!(29073.5, tickers[0].low);
+        assert_eq!(30043.5, tickers[0].close);
+        assert_eq!(round(18157.0 * 0.01), round(tickers[0].volume));
+        assert_eq!(Some(round(500435.0 * 0.01)), tickers[0].open_interest.map(round));
     }
 }
 
@@ -489,12 +530,15 @@ mod ticker {
 mod l2_snapshot {
     use super::EXCHANGE_NAME;
     use crypto_market_type::MarketType;
-    use crypto_msg_parser::{extract_symbol, extract_timestamp};
+    use crypto_msg_parser::{extract_symbol, extract_timestamp, get_msg_type};
+    use crypto_msg_type::MessageType;
 
     #[test]
     fn spot() {
         let raw_msg = r#"{"datas":{"asks":[[29763.69,"0.6260"],[29725.98,"0.1500"],[29723.01,"0.1500"]],"bids":[[29708.13,"0.1500"],[29705.16,"0.1500"],[29704.59,"0.4000"]],"timestamp":1654331401},"resMsg":{"message":"success !","method":null,"code":"1"}}"#;
 
+        assert_eq!(MessageType::L2Snapshot, get_msg_type(EXCHANGE_NAME, raw_msg));
+
         assert_eq!("NONE", extract_symbol(EXCHANGE_NAME, MarketType::Spot, raw_msg).unwrap());
 
         assert_eq!(
@@ -507,6 +551,8 @@ mod l2_snapshot {
     fn inverse_swap() {
         let raw_msg = r#"{"datas":{"bids":[["29834","121320"],["29833.5","35241"],["29831.5","30812"]],"asks":[["29837","67897"],["29837.5","18902"],["29840.5","26717"]],"timestamp":1654331401775},"resMsg":{"message":"success !","method":null,"code":"1"}}"#;
 
+        assert_eq!(MessageType::L2Snapshot, get_msg_type(EXCHANGE_NAME, raw_msg));
+
         assert_eq!("NONE", extract_symbol(EXCHANGE_NAME, MarketType::LinearSwap, raw_msg).unwrap());
 
         assert_eq!(
@@ -519,6 +565,8 @@ mod l2_snapshot {
     fn linear_swap() {
         let raw_msg = r#"{"datas":{"bids":[["29860","5885"],["29858.5","1214"],["29856.5","1324"]],"asks":[["29861","3415"],["29863","811"],["29866.5","1216"]],"timestamp":1654332304984},"resMsg":{"message":"success !","method":null,"code":"1"}}"#;
 
+        assert_eq!(MessageType::L2Snapshot, get_msg_type(EXCHANGE_NAME, raw_msg));
+
         assert_eq!("NONE", extract_symbol(EXCHANGE_NAME, MarketType::LinearSwap, raw_msg).unwrap());
 
         assert_eq!(
@@ -564,3 +612,84 @@ mod open_interest {
         );
     }
 }
+
+#[cfg(test)]
+mod instrument_registry {
+    use super::EXCHANGE_NAME;
+    use crypto_market_type::MarketType;
+    use crypto_msg_parser::{extract_symbol, parse_trade};
+    use crypto_pair::TableRegistry;
+    use std::sync::Arc;
+
+    #[test]
+    fn spot_symbol_id() {
+        let registry =
+            TableRegistry::from_json(r#"{"symbol_ids":{"zbg":{"spot":{"8888":"abc_usdt"}}}}"#)
+                .unwrap();
+        let _guard = crypto_pair::scoped_instrument_registry(Arc::new(registry));
+
+        let raw_msg = r#"{"trade_statistic":[["8888","0.000045","0","0","0","0","[]","0.000011","0.000057","0"]]}"#;
+        assert_eq!("abc_usdt", extract_symbol(EXCHANGE_NAME, MarketType::Spot, raw_msg).unwrap());
+
+        // unknown IDs still fall back to the embedded table
+        let raw_msg = r#"{"trade_statistic":[["329","29980.15","31890.91","29316.96","3104.9576","-4.96","[]","29967.06","29981.99","0"]]}"#;
+        assert_eq!("btc_usdt", extract_symbol(EXCHANGE_NAME, MarketType::Spot, raw_msg).unwrap());
+    }
+
+    #[test]
+    fn swap_contract_value_history() {
+        let registry = TableRegistry::from_json(
+            r#"{"contract_value_history":{"zbg":{"linear_swap":{"BTC/USDT":[[1616385064675,0.1]]}}}}"#,
+        )
+        .unwrap();
+        let _guard = crypto_pair::scoped_instrument_registry(Arc::new(registry));
+
+        // before the cutoff
+        let raw_msg =
+            r#"["future_tick",{"contractId":1000000,"trades":[1616385064674265,"57326","31",-1]}]"#;
+        let trade = &parse_trade(EXCHANGE_NAME, MarketType::LinearSwap, raw_msg).unwrap()[0];
+        assert_eq!(trade.quantity_base, 0.1 * 31.0);
+
+        // the current contract value applies from the cutoff on
+        let raw_msg =
+            r#"["future_tick",{"contractId":1000000,"trades":[1616385064675265,"57326","31",-1]}]"#;
+        let trade = &parse_trade(EXCHANGE_NAME, MarketType::LinearSwap, raw_msg).unwrap()[0];
+        assert_eq!(trade.quantity_base, 0.01 * 31.0);
+    }
+}
+
+#[cfg(test)]
+mod funding_rate {
+    use super::EXCHANGE_NAME;
+    use crypto_market_type::MarketType;
+    use crypto_msg_parser::parse_funding_rate;
+
+    #[test]
+    fn inverse_swap() {
+        let raw_msg = r#"["future_snapshot_indicator",{"tt":"1335.2544","pp":"30117","lui":1621326625165456,"symbol":"BTC_USD-R","tv":"40286903","c24t":6083.218113,"lp":"29860.5","pv":"12762468","w24pc":"474","orderLimit":"150000","dp":"0","osp":"29860.5","uf":0,"indexPrice":"29883.127199","mq":"2218","mt":4,"ip":"29883.127199","ai":2,"tav":"0","w24pcr":"0.01612985554591394","basis":"0.08%","pcr24":0.0146,"hgp24":30732,"fb":"-0.00059234518798245","pfr":"-0.000059683363091459","pc24":431.0,"volumeUsd24h":"597787","tbv":"0","fr":"-0.000059683363091459","sb":"BTC/USD-R","currencyName":"btc","op24":29429.5,"sl":0,"contractUnit":"1","pcr":"-0.008533244791234332","op":"30117.5","hph":"69159","hpl":"0.5","ci":1000001,"ppi":"-0.000559683363091459","u24t":183544142.552353,"openInterestUSD":"12762468","cp":"29895.8998627","lwp24":29032,"td":20220518,"cs":2,"te":1652804557693495,"pc":"-257","ph":"30154","contractId":"1000001","pi":"-0.001792462854748013","pl":"29855","obp":"29837.5","ts":0,"commodityName":"usd","fundingRate":"-0.0059683363091459%"}]"#;
+        let funding_rates =
+            &parse_funding_rate(EXCHANGE_NAME, MarketType::InverseSwap, raw_msg, None).unwrap();
+
+        assert_eq!(funding_rates.len(), 1);
+        assert_eq!(funding_rates[0].symbol, "BTC_USD-R");
+        assert_eq!(funding_rates[0].pair, "BTC/USD");
+        assert_eq!(funding_rates[0].funding_rate, -0.000059683363091459);
+        assert_eq!(funding_rates[0].estimated_rate, Some(-0.000059683363091459));
+        assert_eq!(funding_rates[0].funding_time, 1652832000000);
+        assert_eq!(funding_rates[0].timestamp, 1652804557693);
+    }
+
+    #[test]
+    fn inverse_swap_rest() {
+        let raw_msg = r#"{"datas":{"mt":4,"ai":2,"ci":1000001,"sb":"BTC_USD-R","td":20220518,"te":1652804560181491,"lp":"29834.5","mq":"3050","op":"30117.5","ph":"30154","pl":"29834.5","hph":"69159","hpl":"0.5","tt":"1335.4253","tv":"40292642","tbv":"0","tav":"0","pp":"30117","cp":"29888.591354933333333334","pv":"12762468","pcr":"-0.009396530256495393","pc":"-283","lui":1621326625165456,"cs":2,"dp":"0","fr":"-0.000063829677908321","pfr":"-0.000063829677908321","pi":"-0.002437963975130133","ppi":"-0.000563829677908321","fb":"-0.000612588122859156","ts":0,"sl":0,"ip":"29906.911974","w24pc":"448","w24pcr":"0.01524509553706634","u24t":0,"c24t":0,"op24":0,"pcr24":0,"pc24":29834.5,"lwp24":0,"hgp24":0,"bids":[["29834","121320"],["29833.5","35241"]],"asks":[["29837","67897"],["29837.5","18902"]],"volumeUsd24h":"601776","currencyName":"btc","commodityName":"usd","contractUnit":"1","orderLimit":"150000","openInterestUSD":"12762468","indexPrice":"29906.911974","basis":"0.24%","fundingRate":"-0.0063829677908321%","symbol":"BTC_USD-R","contractId":"1000001","ask":"29837","bid":"29834","spread":"0.0101%"},"resMsg":{"message":"success !","method":null,"code":"1"}}"#;
+        let funding_rates =
+            &parse_funding_rate(EXCHANGE_NAME, MarketType::InverseSwap, raw_msg, None).unwrap();
+
+        assert_eq!(funding_rates.len(), 1);
+        assert_eq!(funding_rates[0].pair, "BTC/USD");
+        assert_eq!(funding_rates[0].funding_rate, -0.000063829677908321);
+        assert_eq!(funding_rates[0].estimated_rate, Some(-0.000063829677908321));
+        assert_eq!(funding_rates[0].funding_time, 1652832000000);
+        assert_eq!(funding_rates[0].timestamp, 1652804560181);
+    }
+}
@@ -1,6 +1,7 @@
 use std::collections::{BTreeSet, HashSet};
 
 use super::utils::{http_get, normalize_pair_with_quotes};
+use crate::options::{OptionSymbol, parse_option_type, parse_strike, parse_yymmdd};
 
 use crypto_market_type::MarketType;
 use once_cell::sync::Lazy;
@@ -67,10 +68,11 @@ pub(crate) fn normalize_pair(symbol: &str) -> Option<String> {
             let base = remove_date.strip_suffix("USD").unwrap();
             Some(format!("{base}/USD"))
         } else {
-            panic!("Unsupported symbol {symbol}");
+            None
         }
     } else {
-        let quotes = &(*SPOT_QUOTES);
+        let registry = crate::instrument_registry();
+        let quotes = registry.quote_currencies("binance").unwrap_or(&SPOT_QUOTES);
         normalize_pair_with_quotes(symbol, quotes)
     }
 }
@@ -97,6 +99,22 @@ pub(crate) fn get_market_type(symbol: &str, is_spot: Option<bool>) -> MarketType
     }
 }
 
+// BTC-220624-30000-C, margined and settled in USDT
+pub(crate) fn parse_option_symbol(symbol: &str) -> Option<OptionSymbol> {
+    let v: Vec<&str> = symbol.split('-').collect();
+    if v.len() != 4 {
+        return None;
+    }
+    Some(OptionSymbol {
+        underlying: v[0].to_string(),
+        quote: "USDT".to_string(),
+        settle: "USDT".to_string(),
+        expiry: parse_yymmdd(v[1])?,
+        strike: parse_strike(v[2])?,
+        option_type: parse_option_type(v[3])?,
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::fetch_spot_quotes;
@@ -95,6 +95,9 @@ pub(crate) fn normalize_currency(mut currency: &str) -> String {
     if currency.ends_with("F0") {
         currency = &currency[..(currency.len() - 2)]; // Futures only
     }
+    if let Some(currency) = crate::instrument_registry().normalize_currency("bitfinex", currency) {
+        return currency;
+    }
     if BITFINEX_MAPPING.contains_key(currency) {
         currency = BITFINEX_MAPPING[currency].as_str();
     }
@@ -109,12 +112,20 @@ pub(crate) fn normalize_pair(mut symbol: &str) -> Option<String> {
 
     let (base, quote) = if symbol.contains(':') {
         let v: Vec<&str> = symbol.split(':').collect();
-        (v[0].to_string(), v[1].to_string())
+        if v.len() != 2 {
+            return None;
+        }
+        (v[0], v[1])
+    } else if symbol.len() > 3 && symbol.is_char_boundary(symbol.len() - 3) {
+        symbol.split_at(symbol.len() - 3)
     } else {
-        (symbol[..(symbol.len() - 3)].to_string(), symbol[(symbol.len() - 3)..].to_string())
+        return None;
     };
+    if base.trim().is_empty() || quote.trim().is_empty() {
+        return None;
+    }
 
-    Some(format!("{}/{}", normalize_currency(&base), normalize_currency(&quote)))
+    Some(format!("{}/{}", normalize_currency(base), normalize_currency(quote)))
 }
 
 pub(crate) fn get_market_type(symbol: &str) -> MarketType {
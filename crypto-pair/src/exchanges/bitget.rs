@@ -21,14 +21,13 @@ pub(crate) fn normalize_pair(symbol: &str) -> Option<String> {
         } else if pair.ends_with("BTC") {
             Some(format!("{}/BTC", pair.strip_suffix("BTC").unwrap()))
         } else {
-            panic!("Failed to parse {symbol}");
+            None
         }
     } else {
         #[allow(clippy::collapsible_else_if)]
         if symbol.starts_with("cmt_") {
             // linear swap
-            assert!(symbol.ends_with("usdt"));
-            let base = &symbol[4..symbol.len() - 4];
+            let base = symbol.strip_prefix("cmt_")?.strip_suffix("usdt")?;
             Some(format!("{base}/usdt").to_uppercase())
         } else if symbol.contains('_') {
             // spot
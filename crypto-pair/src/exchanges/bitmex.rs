@@ -16,6 +16,9 @@ static FIAT_CURRENCIES: Lazy<HashSet<String>> = Lazy::new(|| {
 
 // See <https://blog.bitmex.com/worlds-first-crypto-margined-perpetual-fx-swap-contracts/>
 pub fn is_fx(symbol: &str) -> bool {
+    if symbol.len() < 6 {
+        return false;
+    }
     let suffix = &symbol[symbol.len() - 3..];
     if FIAT_CURRENCIES.contains(suffix) {
         let base = &symbol[..symbol.len() - 3];
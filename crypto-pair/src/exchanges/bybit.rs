@@ -1,5 +1,7 @@
 use crypto_market_type::MarketType;
 
+use crate::options::{OptionSymbol, parse_ddmmmyy, parse_option_type, parse_strike};
+
 pub(crate) fn normalize_pair(symbol: &str) -> Option<String> {
     let (base, quote) = if symbol.ends_with("USDT") {
         // linear swap
@@ -9,12 +11,12 @@ pub(crate) fn normalize_pair(symbol: &str) -> Option<String> {
         // inverse swap
         let base = symbol.strip_suffix("USD").unwrap();
         (base, "USD")
-    } else if symbol[symbol.len() - 2..].parse::<i64>().is_ok() {
+    } else if symbol.len() > 6 && symbol[symbol.len() - 2..].parse::<i64>().is_ok() {
         // inverse future
         let base = &symbol[..symbol.len() - 6];
         (base, "USD")
     } else {
-        panic!("Unknown symbol {symbol}");
+        return None;
     };
     Some(format!("{base}/{quote}"))
 }
@@ -24,9 +26,27 @@ pub(crate) fn get_market_type(symbol: &str) -> MarketType {
         MarketType::LinearSwap
     } else if symbol.ends_with("USD") {
         MarketType::InverseSwap
-    } else if symbol[symbol.len() - 2..].parse::<i64>().is_ok() {
+    } else if symbol.len() > 6 && symbol[symbol.len() - 2..].parse::<i64>().is_ok() {
         MarketType::InverseFuture
     } else {
         MarketType::Unknown
     }
 }
+
+// BTC-17JUN22-21000-P settles in USDC, BTC-29MAR24-70000-C-USDT in USDT
+pub(crate) fn parse_option_symbol(symbol: &str) -> Option<OptionSymbol> {
+    let v: Vec<&str> = symbol.split('-').collect();
+    let settle = match v.len() {
+        4 => "USDC",
+        5 => v[4],
+        _ => return None,
+    };
+    Some(OptionSymbol {
+        underlying: v[0].to_string(),
+        quote: settle.to_string(),
+        settle: settle.to_string(),
+        expiry: parse_ddmmmyy(v[1])?,
+        strike: parse_strike(v[2])?,
+        option_type: parse_option_type(v[3])?,
+    })
+}
@@ -1,29 +1,35 @@
 use crypto_market_type::MarketType;
 
+use crate::options::{OptionSymbol, parse_ddmmmyy, parse_option_type, parse_strike};
+
 pub(crate) fn normalize_pair(symbol: &str) -> Option<String> {
     if symbol.ends_with("-PERPETUAL") {
         // inverse_swap
         let base = symbol.strip_suffix("-PERPETUAL").unwrap();
         Some(format!("{base}/USD"))
-    } else if symbol.len() > 7 && symbol[(symbol.len() - 2)..].parse::<i64>().is_ok() {
+    } else if is_future(symbol) {
         // inverse_future
-        let pos = symbol.find('-').unwrap();
-        let base = &symbol[..pos];
+        let (base, _) = symbol.split_once('-')?;
         Some(format!("{base}/USD"))
     } else if symbol.ends_with("-P") || symbol.ends_with("-C") {
         // option
-        let pos = symbol.find('-').unwrap();
-        let base = &symbol[..pos];
+        let (base, _) = symbol.split_once('-')?;
         Some(format!("{base}/{base}"))
     } else {
         None
     }
 }
 
+// e.g., BTC-30DEC22, which ends with the year
+fn is_future(symbol: &str) -> bool {
+    symbol.len() > 7
+        && symbol.get(symbol.len() - 2..).is_some_and(|x| x.bytes().all(|b| b.is_ascii_digit()))
+}
+
 pub(crate) fn get_market_type(symbol: &str) -> MarketType {
     if symbol.ends_with("-PERPETUAL") {
         MarketType::InverseSwap
-    } else if symbol.len() > 7 && symbol[(symbol.len() - 2)..].parse::<i64>().is_ok() {
+    } else if is_future(symbol) {
         MarketType::InverseFuture
     } else if symbol.ends_with("-P") || symbol.ends_with("-C") {
         MarketType::EuropeanOption
@@ -32,6 +38,26 @@ pub(crate) fn get_market_type(symbol: &str) -> MarketType {
     }
 }
 
+// BTC-17JUN22-21000-P settles in BTC, XRP_USDC-30JUN23-0d625-C in USDC
+pub(crate) fn parse_option_symbol(symbol: &str) -> Option<OptionSymbol> {
+    let v: Vec<&str> = symbol.split('-').collect();
+    if v.len() != 4 {
+        return None;
+    }
+    let (underlying, quote, settle) = match v[0].split_once('_') {
+        Some((underlying, quote)) => (underlying, quote, quote),
+        None => (v[0], "USD", v[0]),
+    };
+    Some(OptionSymbol {
+        underlying: underlying.to_string(),
+        quote: quote.to_string(),
+        settle: settle.to_string(),
+        expiry: parse_ddmmmyy(v[1])?,
+        strike: parse_strike(v[2])?,
+        option_type: parse_option_type(v[3])?,
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use crypto_market_type::MarketType;
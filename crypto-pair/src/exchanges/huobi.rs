@@ -1,6 +1,7 @@
 use std::collections::{BTreeSet, HashSet};
 
 use super::utils::{http_get, normalize_pair_with_quotes};
+use crate::options::{OptionSymbol, parse_option_type, parse_strike, parse_yymmdd};
 use crypto_market_type::MarketType;
 use once_cell::sync::Lazy;
 
@@ -71,7 +72,8 @@ pub(crate) fn normalize_pair(symbol: &str) -> Option<String> {
         Some(format!("{base}/USD"))
     } else {
         // spot
-        let quotes = &(*SPOT_QUOTES);
+        let registry = crate::instrument_registry();
+        let quotes = registry.quote_currencies("huobi").unwrap_or(&SPOT_QUOTES);
         normalize_pair_with_quotes(symbol, quotes)
     }
 }
@@ -94,6 +96,22 @@ pub(crate) fn get_market_type(symbol: &str) -> MarketType {
     }
 }
 
+// BTC-USDT-210326-C-32000, margined and settled in USDT
+pub(crate) fn parse_option_symbol(symbol: &str) -> Option<OptionSymbol> {
+    let v: Vec<&str> = symbol.split('-').collect();
+    if v.len() != 5 {
+        return None;
+    }
+    Some(OptionSymbol {
+        underlying: v[0].to_uppercase(),
+        quote: v[1].to_uppercase(),
+        settle: v[1].to_uppercase(),
+        expiry: parse_yymmdd(v[2])?,
+        strike: parse_strike(v[4])?,
+        option_type: parse_option_type(v[3])?,
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::fetch_spot_quotes;
@@ -80,6 +80,8 @@ pub(crate) fn normalize_currency(currency: &str) -> String {
 }
 
 pub(crate) fn normalize_pair(symbol: &str) -> Option<String> {
+    let registry = crate::instrument_registry();
+    let quotes = registry.quote_currencies("kraken").unwrap_or(&SPOT_QUOTES);
     if symbol.contains('/') {
         // Spot
         let (base, quote) = {
@@ -93,24 +95,18 @@ pub(crate) fn normalize_pair(symbol: &str) -> Option<String> {
         || symbol.starts_with("PI_")
         || symbol.starts_with("FI_")
     {
-        let pos = if let Some(pos) = symbol.find("usd") {
-            pos
-        } else if let Some(pos) = symbol.find("USD") {
-            pos
-        } else {
-            panic!("Can not find usd or USD in symbol: {symbol}");
-        };
+        let pos = symbol.find("usd").or_else(|| symbol.find("USD"))?;
         let base = symbol[3..pos].to_uppercase();
         Some(format!("{}/USD", normalize_currency(&base),))
-    } else if symbol.len() > 5 && SPOT_QUOTES.contains(&symbol[(symbol.len() - 5)..]) {
+    } else if symbol.len() > 5 && quotes.contains(&symbol[(symbol.len() - 5)..]) {
         let base = &symbol[..(symbol.len() - 5)];
         let quote = &symbol[(symbol.len() - 5)..];
         Some(format!("{}/{}", normalize_currency(base), normalize_currency(quote)))
-    } else if symbol.len() > 4 && SPOT_QUOTES.contains(&symbol[(symbol.len() - 4)..]) {
+    } else if symbol.len() > 4 && quotes.contains(&symbol[(symbol.len() - 4)..]) {
         let base = &symbol[..(symbol.len() - 4)];
         let quote = &symbol[(symbol.len() - 4)..];
         Some(format!("{}/{}", normalize_currency(base), normalize_currency(quote)))
-    } else if symbol.len() > 3 && SPOT_QUOTES.contains(&symbol[(symbol.len() - 3)..]) {
+    } else if symbol.len() > 3 && quotes.contains(&symbol[(symbol.len() - 3)..]) {
         let base = &symbol[..(symbol.len() - 3)];
         let quote = &symbol[(symbol.len() - 3)..];
         Some(format!("{}/{}", normalize_currency(base), normalize_currency(quote)))
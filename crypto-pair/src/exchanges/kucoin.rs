@@ -30,7 +30,7 @@ pub(crate) fn normalize_pair(symbol: &str) -> Option<String> {
         let base = &symbol[0..symbol.len() - 5];
         let quote = &symbol[symbol.len() - 5..symbol.len() - 1];
         (base.to_string(), quote.to_string())
-    } else if symbol[(symbol.len() - 2)..].parse::<i64>().is_ok() {
+    } else if symbol.len() > 4 && symbol[(symbol.len() - 2)..].parse::<i64>().is_ok() {
         // inverse future
         let base = &symbol[..symbol.len() - 4];
         (base.to_string(), "USD".to_string())
@@ -39,7 +39,7 @@ pub(crate) fn normalize_pair(symbol: &str) -> Option<String> {
         let v: Vec<&str> = symbol.split('-').collect();
         (v[0].to_string(), v[1].to_string())
     } else {
-        panic!("Unknown symbol {symbol}");
+        return None;
     };
 
     Some(format!("{}/{}", normalize_currency(&base), normalize_currency(&quote)))
@@ -50,7 +50,7 @@ pub(crate) fn get_market_type(symbol: &str) -> MarketType {
         MarketType::InverseSwap
     } else if symbol.ends_with("USDTM") || symbol.ends_with("USDCM") {
         MarketType::LinearSwap
-    } else if symbol[(symbol.len() - 2)..].parse::<i64>().is_ok() {
+    } else if symbol.len() > 4 && symbol[(symbol.len() - 2)..].parse::<i64>().is_ok() {
         MarketType::InverseFuture
     } else if symbol.contains('-') {
         MarketType::Spot
@@ -1,5 +1,7 @@
 use crypto_market_type::MarketType;
 
+use crate::options::{OptionSymbol, parse_option_type, parse_strike, parse_yymmdd};
+
 pub(crate) fn get_market_type(symbol: &str) -> MarketType {
     if symbol.ends_with("-USD-SWAP") {
         MarketType::InverseSwap
@@ -21,3 +23,19 @@ pub(crate) fn get_market_type(symbol: &str) -> MarketType {
         MarketType::Unknown
     }
 }
+
+// BTC-USD-220624-30000-C, margined and settled in BTC
+pub(crate) fn parse_option_symbol(symbol: &str) -> Option<OptionSymbol> {
+    let v: Vec<&str> = symbol.split('-').collect();
+    if v.len() != 5 {
+        return None;
+    }
+    Some(OptionSymbol {
+        underlying: v[0].to_string(),
+        quote: v[1].to_string(),
+        settle: v[0].to_string(),
+        expiry: parse_yymmdd(v[2])?,
+        strike: parse_strike(v[3])?,
+        option_type: parse_option_type(v[4])?,
+    })
+}
@@ -1,7 +1,9 @@
 use std::collections::HashSet;
 
+#[cfg(all(feature = "online", not(feature = "offline")))]
 use reqwest::{header, Result};
 
+#[cfg(all(feature = "online", not(feature = "offline")))]
 pub(super) fn http_get(url: &str) -> Result<String> {
     let mut headers = header::HeaderMap::new();
     headers.insert(header::CONTENT_TYPE, header::HeaderValue::from_static("application/json"));
@@ -19,6 +21,12 @@ pub(super) fn http_get(url: &str) -> Result<String> {
     }
 }
 
+/// Network access is disabled, callers fall back to the embedded tables.
+#[cfg(not(all(feature = "online", not(feature = "offline"))))]
+pub(super) fn http_get(_url: &str) -> std::result::Result<String, &'static str> {
+    Err("Network access is disabled")
+}
+
 pub(super) fn normalize_pair_with_quotes(symbol: &str, quotes: &HashSet<String>) -> Option<String> {
     for quote in quotes.iter() {
         if symbol.ends_with(quote) {
@@ -2,6 +2,16 @@
 
 use crypto_market_type::MarketType;
 mod exchanges;
+mod options;
+mod registry;
+
+pub use options::{days_from_civil, OptionSymbol, OptionType};
+#[cfg(any(test, feature = "test-support"))]
+pub use registry::{scoped_instrument_registry, RegistryGuard};
+pub use registry::{
+    instrument_registry, set_instrument_registry, EmbeddedRegistry, InstrumentRegistry,
+    TableRegistry,
+};
 
 /// Normalize a trading currency.
 ///
@@ -10,6 +20,11 @@ mod exchanges;
 /// * `currency` - The exchange-specific currency
 /// * `exchange` - The normalized symbol
 pub fn normalize_currency(currency: &str, exchange: &str) -> String {
+    if let Some(currency) =
+        registry::custom_registry().and_then(|x| x.normalize_currency(exchange, currency))
+    {
+        return currency;
+    }
     match exchange {
         "bitfinex" => exchanges::bitfinex::normalize_currency(currency),
         "bitmex" => exchanges::bitmex::normalize_currency(currency),
@@ -38,6 +53,11 @@ pub fn normalize_currency(currency: &str, exchange: &str) -> String {
 /// assert_eq!(Some("BTC/USDT".to_string()), normalize_pair("BTCUST", "bitfinex"));
 /// ```
 pub fn normalize_pair(symbol: &str, exchange: &str) -> Option<String> {
+    if let Some(pair) =
+        registry::custom_registry().and_then(|x| x.normalize_pair(exchange, symbol))
+    {
+        return Some(pair);
+    }
     match exchange {
         "binance" => exchanges::binance::normalize_pair(symbol),
         "bitfinex" => exchanges::bitfinex::normalize_pair(symbol),
@@ -52,10 +72,8 @@ pub fn normalize_pair(symbol: &str, exchange: &str) -> Option<String> {
         "dydx" => exchanges::dydx::normalize_pair(symbol),
         "ftx" => exchanges::ftx::normalize_pair(symbol),
         "gate" => {
-            let (base, quote) = {
-                let v: Vec<&str> = symbol.split('_').collect();
-                (v[0].to_string(), v[1].to_string())
-            };
+            let mut v = symbol.split('_');
+            let (base, quote) = (v.next()?, v.next()?);
 
             Some(format!("{base}/{quote}"))
         }
@@ -64,8 +82,8 @@ pub fn normalize_pair(symbol: &str, exchange: &str) -> Option<String> {
         "kucoin" => exchanges::kucoin::normalize_pair(symbol),
         "mxc" | "mexc" => Some(symbol.replace('_', "/")),
         "okex" | "okx" => {
-            let v: Vec<&str> = symbol.split('-').collect();
-            Some(format!("{}/{}", v[0], v[1]))
+            let mut v = symbol.split('-');
+            Some(format!("{}/{}", v.next()?, v.next()?))
         }
         "Poloniex" => Some(symbol.replace('_', "/")),
         "Upbit" => Some(symbol.replace('-', "/")),
@@ -82,7 +100,7 @@ pub fn normalize_pair(symbol: &str, exchange: &str) -> Option<String> {
 /// contract markets, for example:
 /// * At binance `BTCUSDT` exists in both spot and linear_swap markets
 /// * At gate `BTC_USDT` exists in both spot and linear_swap markets,
-/// `BTC_USD` exists in both spot and inverse_swap markets
+///   `BTC_USD` exists in both spot and inverse_swap markets
 pub fn get_market_type(symbol: &str, exchange: &str, is_spot: Option<bool>) -> MarketType {
     match exchange {
         "binance" => exchanges::binance::get_market_type(symbol, is_spot),
@@ -107,3 +125,33 @@ pub fn get_market_type(symbol: &str, exchange: &str, is_spot: Option<bool>) -> M
         _ => MarketType::Unknown,
     }
 }
+
+/// Decompose an option symbol into underlying, quote and settle currencies,
+/// expiry, strike and option type.
+///
+/// Supported exchanges are deribit, okx, binance, bybit and huobi. Returns
+/// `None` if the symbol is not an option or the exchange is not supported.
+///
+/// # Examples
+///
+/// ```
+/// use crypto_pair::{parse_option_symbol, OptionType};
+///
+/// let option = parse_option_symbol("deribit", "BTC-17JUN22-21000-P").unwrap();
+/// assert_eq!("BTC", option.underlying);
+/// assert_eq!("USD", option.quote);
+/// assert_eq!("BTC", option.settle);
+/// assert_eq!(1655452800000, option.expiry); // 2022-06-17T08:00:00Z
+/// assert_eq!(21000.0, option.strike);
+/// assert_eq!(OptionType::Put, option.option_type);
+/// ```
+pub fn parse_option_symbol(exchange: &str, symbol: &str) -> Option<OptionSymbol> {
+    match exchange {
+        "binance" => exchanges::binance::parse_option_symbol(symbol),
+        "bybit" => exchanges::bybit::parse_option_symbol(symbol),
+        "deribit" => exchanges::deribit::parse_option_symbol(symbol),
+        "huobi" => exchanges::huobi::parse_option_symbol(symbol),
+        "okex" | "okx" => exchanges::okx::parse_option_symbol(symbol),
+        _ => None,
+    }
+}
@@ -0,0 +1,218 @@
+use serde::{Deserialize, Serialize};
+
+/// Call or put.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum OptionType {
+    Call,
+    Put,
+}
+
+/// An option instrument decomposed from its exchange-specific symbol.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct OptionSymbol {
+    /// The underlying currency, e.g., BTC
+    pub underlying: String,
+    /// The currency of the strike price, e.g., USD
+    pub quote: String,
+    /// The currency the option is margined and settled in, e.g., BTC on deribit
+    pub settle: String,
+    /// Expiry time, a UTC Unix timestamp in milliseconds
+    pub expiry: i64,
+    /// Strike price, in the quote currency
+    pub strike: f64,
+    pub option_type: OptionType,
+}
+
+// All supported exchanges settle options at 08:00 UTC
+const EXPIRY_HOUR: i64 = 8;
+
+const MONTHS: [&str; 12] =
+    ["JAN", "FEB", "MAR", "APR", "MAY", "JUN", "JUL", "AUG", "SEP", "OCT", "NOV", "DEC"];
+
+/// Days since 1970-01-01 of a date in the proleptic Gregorian calendar.
+///
+/// The arguments are not validated, e.g., February 30 is March 1 or 2.
+/// See <http://howardhinnant.github.io/date_algorithms.html#days_from_civil>
+///
+/// # Examples
+///
+/// ```
+/// assert_eq!(19160, crypto_pair::days_from_civil(2022, 6, 17));
+/// ```
+pub fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = if year >= 0 { year } else { year - 399 } / 400;
+    let yoe = year - era * 400;
+    let doy = (153 * ((month + 9) % 12) + 2) / 5 + day - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    era * 146097 + doe - 719468
+}
+
+fn to_expiry(year: i64, month: i64, day: i64) -> Option<i64> {
+    if !(1..=12).contains(&month) || day < 1 {
+        return None;
+    }
+    // the last day of a month is the day before the first day of the next month
+    let days_in_month = if month == 12 {
+        31
+    } else {
+        days_from_civil(2000 + year, month + 1, 1) - days_from_civil(2000 + year, month, 1)
+    };
+    if day > days_in_month {
+        return None;
+    }
+    let days = days_from_civil(2000 + year, month, day);
+    Some((days * 24 + EXPIRY_HOUR) * 3600 * 1000)
+}
+
+// Unlike `str::parse()`, rejects signs
+fn parse_digits(s: &str) -> Option<i64> {
+    if s.is_empty() || !s.bytes().all(|x| x.is_ascii_digit()) {
+        return None;
+    }
+    s.parse().ok()
+}
+
+/// Parse expiry dates such as `17JUN22` and `3MAR23`.
+pub(crate) fn parse_ddmmmyy(s: &str) -> Option<i64> {
+    if s.len() < 6 || !s.is_ascii() {
+        return None;
+    }
+    let (day, rest) = s.split_at(s.len() - 5);
+    let (month, year) = rest.split_at(3);
+    let month = MONTHS.iter().position(|x| *x == month)? as i64 + 1;
+    to_expiry(parse_digits(year)?, month, parse_digits(day)?)
+}
+
+/// Parse expiry dates such as `220624`.
+pub(crate) fn parse_yymmdd(s: &str) -> Option<i64> {
+    if s.len() != 6 || !s.is_ascii() {
+        return None;
+    }
+    to_expiry(parse_digits(&s[..2])?, parse_digits(&s[2..4])?, parse_digits(&s[4..])?)
+}
+
+/// Parse strikes such as `21000` and `0.5`, deribit writes `0d625` for `0.625`.
+pub(crate) fn parse_strike(s: &str) -> Option<f64> {
+    s.replace('d', ".").parse::<f64>().ok().filter(|x| x.is_finite() && *x > 0.0)
+}
+
+pub(crate) fn parse_option_type(s: &str) -> Option<OptionType> {
+    match s {
+        "C" => Some(OptionType::Call),
+        "P" => Some(OptionType::Put),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{OptionSymbol, OptionType};
+    use crate::parse_option_symbol;
+
+    fn option(
+        underlying: &str,
+        quote: &str,
+        settle: &str,
+        expiry: i64,
+        strike: f64,
+        option_type: OptionType,
+    ) -> Option<OptionSymbol> {
+        Some(OptionSymbol {
+            underlying: underlying.to_string(),
+            quote: quote.to_string(),
+            settle: settle.to_string(),
+            expiry,
+            strike,
+            option_type,
+        })
+    }
+
+    #[test]
+    fn expiry() {
+        // 2022-06-17T08:00:00Z
+        assert_eq!(Some(1655452800000), super::parse_ddmmmyy("17JUN22"));
+        assert_eq!(Some(1677830400000), super::parse_ddmmmyy("3MAR23"));
+        assert_eq!(Some(1656057600000), super::parse_yymmdd("220624"));
+        // leap day
+        assert_eq!(Some(1709193600000), super::parse_yymmdd("240229"));
+        assert_eq!(None, super::parse_ddmmmyy("17XYZ22"));
+        assert_eq!(None, super::parse_yymmdd("221324"));
+        assert_eq!(None, super::parse_ddmmmyy("30FEB24"));
+        assert_eq!(None, super::parse_ddmmmyy("29FEB23"));
+        assert_eq!(None, super::parse_ddmmmyy("31APR24"));
+        assert_eq!(Some(1709193600000), super::parse_ddmmmyy("29FEB24"));
+        assert_eq!(None, super::parse_yymmdd("000100"));
+        assert_eq!(None, super::parse_ddmmmyy("+3MAR23"));
+        assert_eq!(None, super::parse_ddmmmyy("17JUN+2"));
+        assert_eq!(None, super::parse_yymmdd("22+624"));
+    }
+
+    #[test]
+    fn strike() {
+        assert_eq!(Some(0.625), super::parse_strike("0d625"));
+        assert_eq!(None, super::parse_strike("inf"));
+        assert_eq!(None, super::parse_strike("NaN"));
+        assert_eq!(None, super::parse_strike("-100"));
+    }
+
+    #[test]
+    fn deribit() {
+        assert_eq!(
+            option("BTC", "USD", "BTC", 1655452800000, 21000.0, OptionType::Put),
+            parse_option_symbol("deribit", "BTC-17JUN22-21000-P")
+        );
+        assert_eq!(
+            option("XRP", "USDC", "USDC", 1688112000000, 0.625, OptionType::Call),
+            parse_option_symbol("deribit", "XRP_USDC-30JUN23-0d625-C")
+        );
+        assert_eq!(None, parse_option_symbol("deribit", "BTC-PERPETUAL"));
+        assert_eq!(None, parse_option_symbol("deribit", "BTC-30DEC22"));
+    }
+
+    #[test]
+    fn okx() {
+        assert_eq!(
+            option("BTC", "USD", "BTC", 1656057600000, 30000.0, OptionType::Call),
+            parse_option_symbol("okx", "BTC-USD-220624-30000-C")
+        );
+        assert_eq!(None, parse_option_symbol("okex", "BTC-USD-220624"));
+        assert_eq!(None, parse_option_symbol("okx", "BTC-USDT-SWAP"));
+    }
+
+    #[test]
+    fn binance() {
+        assert_eq!(
+            option("BTC", "USDT", "USDT", 1656057600000, 30000.0, OptionType::Call),
+            parse_option_symbol("binance", "BTC-220624-30000-C")
+        );
+        assert_eq!(None, parse_option_symbol("binance", "BTCUSDT_220624"));
+    }
+
+    #[test]
+    fn bybit() {
+        assert_eq!(
+            option("BTC", "USDC", "USDC", 1655452800000, 21000.0, OptionType::Put),
+            parse_option_symbol("bybit", "BTC-17JUN22-21000-P")
+        );
+        assert_eq!(
+            option("BTC", "USDT", "USDT", 1711699200000, 70000.0, OptionType::Call),
+            parse_option_symbol("bybit", "BTC-29MAR24-70000-C-USDT")
+        );
+        assert_eq!(None, parse_option_symbol("bybit", "BTCUSDM22"));
+    }
+
+    #[test]
+    fn huobi() {
+        assert_eq!(
+            option("BTC", "USDT", "USDT", 1616745600000, 32000.0, OptionType::Call),
+            parse_option_symbol("huobi", "BTC-USDT-210326-C-32000")
+        );
+        assert_eq!(None, parse_option_symbol("huobi", "BTC-USDT"));
+    }
+
+    #[test]
+    fn unsupported_exchange() {
+        assert_eq!(None, parse_option_symbol("bitmex", "XBTUSD"));
+    }
+}
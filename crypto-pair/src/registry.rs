@@ -0,0 +1,306 @@
+#[cfg(any(test, feature = "test-support"))]
+use std::{cell::RefCell, marker::PhantomData};
+use std::{
+    collections::{HashMap, HashSet},
+    sync::{
+        Arc, RwLock,
+        atomic::{AtomicBool, Ordering},
+    },
+};
+
+use crypto_market_type::MarketType;
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+
+/// A source of instrument reference data, e.g., contract values, tick sizes
+/// and symbol mappings.
+///
+/// Every method returns `None` by default, which means the registry knows
+/// nothing about the instrument and the caller falls back to the tables
+/// embedded in these crates.
+pub trait InstrumentRegistry: Send + Sync {
+    /// The normalized pair of an exchange-specific symbol, e.g., `BTC/USDT`.
+    fn normalize_pair(&self, _exchange: &str, _symbol: &str) -> Option<String> {
+        None
+    }
+
+    /// The normalized currency of an exchange-specific currency, e.g., `UST` -> `USDT`.
+    fn normalize_currency(&self, _exchange: &str, _currency: &str) -> Option<String> {
+        None
+    }
+
+    /// Quote currencies used to split spot symbols without a separator, e.g., `BTCUSDT`.
+    fn quote_currencies(&self, _exchange: &str) -> Option<&HashSet<String>> {
+        None
+    }
+
+    /// The contract value of a pair.
+    fn contract_value(
+        &self,
+        _exchange: &str,
+        _market_type: MarketType,
+        _pair: &str,
+    ) -> Option<f64> {
+        None
+    }
+
+    /// The contract value of a pair effective at `timestamp` (in milliseconds),
+    /// `None` if it equals the current one.
+    fn contract_value_at(
+        &self,
+        _exchange: &str,
+        _market_type: MarketType,
+        _pair: &str,
+        _timestamp: i64,
+    ) -> Option<f64> {
+        None
+    }
+
+    /// The tick size of an exchange-specific symbol.
+    fn tick_size(&self, _exchange: &str, _market_type: MarketType, _symbol: &str) -> Option<f64> {
+        None
+    }
+
+    /// The lot size of an exchange-specific symbol, in base coins for spot
+    /// markets and in contracts for derivatives.
+    fn lot_size(&self, _exchange: &str, _market_type: MarketType, _symbol: &str) -> Option<f64> {
+        None
+    }
+
+    /// The minimum order value of an exchange-specific symbol, in quote currency.
+    fn min_notional(
+        &self,
+        _exchange: &str,
+        _market_type: MarketType,
+        _symbol: &str,
+    ) -> Option<f64> {
+        None
+    }
+
+    /// The position of a symbol in the exchange's instrument list, BitMEX encodes
+    /// it into order IDs.
+    fn instrument_index(&self, _exchange: &str, _symbol: &str) -> Option<usize> {
+        None
+    }
+
+    /// The exchange-specific symbol of a numeric instrument ID, e.g., ZBG
+    /// identifies markets by IDs in websocket messages.
+    fn symbol_by_id(&self, _exchange: &str, _market_type: MarketType, _id: i64) -> Option<String> {
+        None
+    }
+}
+
+/// The default registry, it always falls back to the embedded tables.
+#[derive(Copy, Clone, Debug, Default)]
+pub struct EmbeddedRegistry;
+
+impl InstrumentRegistry for EmbeddedRegistry {}
+
+// market type -> pair or symbol -> value
+type MarketTable<T> = HashMap<MarketType, HashMap<String, T>>;
+
+/// A registry backed by in-memory tables, typically loaded from a JSON file.
+///
+/// All tables are keyed by exchange first, instruments missing from them fall
+/// back to the embedded tables.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TableRegistry {
+    /// exchange -> symbol -> pair
+    pub pairs: HashMap<String, HashMap<String, String>>,
+    /// exchange -> currency -> normalized currency
+    pub currencies: HashMap<String, HashMap<String, String>>,
+    /// exchange -> quote currencies
+    pub quote_currencies: HashMap<String, HashSet<String>>,
+    /// exchange -> market type -> pair -> contract value
+    pub contract_values: HashMap<String, MarketTable<f64>>,
+    /// exchange -> market type -> pair -> [(effective until, contract value)]
+    pub contract_value_history: HashMap<String, MarketTable<Vec<(i64, f64)>>>,
+    /// exchange -> market type -> symbol -> tick size
+    pub tick_sizes: HashMap<String, MarketTable<f64>>,
+    /// exchange -> market type -> symbol -> lot size
+    pub lot_sizes: HashMap<String, MarketTable<f64>>,
+    /// exchange -> market type -> symbol -> minimum order value
+    pub min_notionals: HashMap<String, MarketTable<f64>>,
+    /// exchange -> symbol -> index
+    pub instrument_indices: HashMap<String, HashMap<String, usize>>,
+    /// exchange -> market type -> ID -> symbol
+    pub symbol_ids: HashMap<String, HashMap<MarketType, HashMap<i64, String>>>,
+}
+
+impl TableRegistry {
+    /// Load tables from a JSON string.
+    pub fn from_json(json: &str) -> serde_json::Result<Self> {
+        serde_json::from_str(json)
+    }
+}
+
+impl InstrumentRegistry for TableRegistry {
+    fn normalize_pair(&self, exchange: &str, symbol: &str) -> Option<String> {
+        self.pairs.get(exchange)?.get(symbol).cloned()
+    }
+
+    fn normalize_currency(&self, exchange: &str, currency: &str) -> Option<String> {
+        self.currencies.get(exchange)?.get(currency).cloned()
+    }
+
+    fn quote_currencies(&self, exchange: &str) -> Option<&HashSet<String>> {
+        self.quote_currencies.get(exchange)
+    }
+
+    fn contract_value(&self, exchange: &str, market_type: MarketType, pair: &str) -> Option<f64> {
+        self.contract_values.get(exchange)?.get(&market_type)?.get(pair).copied()
+    }
+
+    fn contract_value_at(
+        &self,
+        exchange: &str,
+        market_type: MarketType,
+        pair: &str,
+        timestamp: i64,
+    ) -> Option<f64> {
+        self.contract_value_history
+            .get(exchange)?
+            .get(&market_type)?
+            .get(pair)?
+            .iter()
+            .filter(|(effective_until, _)| timestamp < *effective_until)
+            .min_by_key(|(effective_until, _)| *effective_until)
+            .map(|(_, contract_value)| *contract_value)
+    }
+
+    fn tick_size(&self, exchange: &str, market_type: MarketType, symbol: &str) -> Option<f64> {
+        self.tick_sizes.get(exchange)?.get(&market_type)?.get(symbol).copied()
+    }
+
+    fn lot_size(&self, exchange: &str, market_type: MarketType, symbol: &str) -> Option<f64> {
+        self.lot_sizes.get(exchange)?.get(&market_type)?.get(symbol).copied()
+    }
+
+    fn min_notional(&self, exchange: &str, market_type: MarketType, symbol: &str) -> Option<f64> {
+        self.min_notionals.get(exchange)?.get(&market_type)?.get(symbol).copied()
+    }
+
+    fn instrument_index(&self, exchange: &str, symbol: &str) -> Option<usize> {
+        self.instrument_indices.get(exchange)?.get(symbol).copied()
+    }
+
+    fn symbol_by_id(&self, exchange: &str, market_type: MarketType, id: i64) -> Option<String> {
+        self.symbol_ids.get(exchange)?.get(&market_type)?.get(&id).cloned()
+    }
+}
+
+static EMBEDDED: Lazy<Arc<dyn InstrumentRegistry>> = Lazy::new(|| Arc::new(EmbeddedRegistry));
+
+static REGISTRY: Lazy<RwLock<Arc<dyn InstrumentRegistry>>> =
+    Lazy::new(|| RwLock::new(EMBEDDED.clone()));
+
+// Until a registry is set, lookups return the embedded one without locking
+static CUSTOM: AtomicBool = AtomicBool::new(false);
+
+/// Replace the global instrument registry.
+///
+/// It is consulted by `normalize_pair()`, `normalize_currency()`, contract
+/// values and the exchange parsers before the embedded tables.
+pub fn set_instrument_registry(registry: Arc<dyn InstrumentRegistry>) {
+    *REGISTRY.write().unwrap_or_else(|e| e.into_inner()) = registry;
+    CUSTOM.store(true, Ordering::Release);
+}
+
+/// The global instrument registry.
+pub fn instrument_registry() -> Arc<dyn InstrumentRegistry> {
+    custom_registry().unwrap_or_else(|| EMBEDDED.clone())
+}
+
+// The registry in use, `None` if it is the embedded one.
+pub(crate) fn custom_registry() -> Option<Arc<dyn InstrumentRegistry>> {
+    #[cfg(any(test, feature = "test-support"))]
+    if let Some(registry) = SCOPED.with(|scoped| scoped.borrow().clone()) {
+        return Some(registry);
+    }
+    if CUSTOM.load(Ordering::Acquire) {
+        Some(REGISTRY.read().unwrap_or_else(|e| e.into_inner()).clone())
+    } else {
+        None
+    }
+}
+
+#[cfg(any(test, feature = "test-support"))]
+thread_local! {
+    static SCOPED: RefCell<Option<Arc<dyn InstrumentRegistry>>> = const { RefCell::new(None) };
+}
+
+/// Restores the previous registry of the current thread when dropped.
+#[cfg(any(test, feature = "test-support"))]
+pub struct RegistryGuard {
+    previous: Option<Arc<dyn InstrumentRegistry>>,
+    // the override belongs to the thread that created it
+    _not_send: PhantomData<*const ()>,
+}
+
+#[cfg(any(test, feature = "test-support"))]
+impl Drop for RegistryGuard {
+    fn drop(&mut self) {
+        SCOPED.with(|scoped| *scoped.borrow_mut() = self.previous.take());
+    }
+}
+
+/// Replace the instrument registry of the current thread until the returned
+/// guard is dropped.
+///
+/// Other threads, e.g., tests running in parallel, keep using the global
+/// registry. Requires the `test-support` feature.
+#[cfg(any(test, feature = "test-support"))]
+pub fn scoped_instrument_registry(registry: Arc<dyn InstrumentRegistry>) -> RegistryGuard {
+    let previous = SCOPED.with(|scoped| scoped.borrow_mut().replace(registry));
+    RegistryGuard { previous, _not_send: PhantomData }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{normalize_currency, normalize_pair};
+
+    struct QuoteRegistry {
+        quotes: HashSet<String>,
+    }
+
+    impl InstrumentRegistry for QuoteRegistry {
+        fn quote_currencies(&self, exchange: &str) -> Option<&HashSet<String>> {
+            if exchange == "binance" { Some(&self.quotes) } else { None }
+        }
+    }
+
+    #[test]
+    fn scoped_registry() {
+        let mut table = TableRegistry::default();
+        table.pairs.insert(
+            "bitmex".to_string(),
+            HashMap::from([("FOOUSD".to_string(), "FOO/USD".to_string())]),
+        );
+        table.currencies.insert(
+            "bitfinex".to_string(),
+            HashMap::from([("FOOBAR".to_string(), "FOO".to_string())]),
+        );
+        {
+            let _guard = scoped_instrument_registry(Arc::new(table));
+
+            assert_eq!(Some("FOO/USD".to_string()), normalize_pair("FOOUSD", "bitmex"));
+            assert_eq!("FOO", normalize_currency("FOOBAR", "bitfinex"));
+            assert_eq!(Some("FOO/USDT".to_string()), normalize_pair("tFOOBARUST", "bitfinex"));
+            // Instruments unknown to the registry fall back to the embedded tables
+            assert_eq!(Some("BTC/USD".to_string()), normalize_pair("XBTUSD", "bitmex"));
+            // Other threads don't see the scoped registry
+            std::thread::spawn(|| assert_eq!("FOOBAR", normalize_currency("FOOBAR", "bitfinex")))
+                .join()
+                .unwrap();
+
+            let quotes = QuoteRegistry { quotes: HashSet::from(["ABC".to_string()]) };
+            let _inner = scoped_instrument_registry(Arc::new(quotes));
+            assert_eq!(Some("XYZ/ABC".to_string()), normalize_pair("XYZABC", "binance"));
+        }
+        // The previous registry is restored when the guard is dropped
+        assert_eq!("FOOBAR", normalize_currency("FOOBAR", "bitfinex"));
+        assert_eq!(None, normalize_pair("XYZABC", "binance"));
+    }
+}
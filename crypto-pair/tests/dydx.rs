@@ -34,8 +34,8 @@ fn fetch_markets_raw() -> Vec<PerpetualMarket> {
     let resp = serde_json::from_str::<MarketsResponse>(&txt).unwrap();
     resp.markets
         .values()
-        .cloned()
         .filter(|x| x.status == "ONLINE")
+        .cloned()
         .collect::<Vec<PerpetualMarket>>()
 }
 
@@ -0,0 +1,21 @@
+use crypto_market_type::MarketType;
+use crypto_pair::{InstrumentRegistry, TableRegistry};
+
+#[test]
+fn table_registry() {
+    let registry = TableRegistry::from_json(
+        r#"{
+            "pairs": {"bitmex": {"FOOUSD": "FOO/USD"}},
+            "currencies": {"bitfinex": {"FOOBAR": "FOO"}},
+            "contract_values": {"bitmex": {"inverse_swap": {"FOO/USD": 10.0}}}
+        }"#,
+    )
+    .unwrap();
+
+    assert_eq!(Some("FOO/USD".to_string()), registry.normalize_pair("bitmex", "FOOUSD"));
+    assert_eq!(None, registry.normalize_pair("bitmex", "XBTUSD"));
+    assert_eq!(Some("FOO".to_string()), registry.normalize_currency("bitfinex", "FOOBAR"));
+    assert_eq!(Some(10.0), registry.contract_value("bitmex", MarketType::InverseSwap, "FOO/USD"));
+    assert_eq!(None, registry.contract_value("bitmex", MarketType::LinearSwap, "FOO/USD"));
+    assert_eq!(None, registry.symbol_by_id("zbg", MarketType::Spot, 329));
+}